@@ -1,13 +1,14 @@
 mod registered_modules;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use mimalloc::MiMalloc;
 use modkit::bootstrap::{
     AppConfig, dump_effective_modules_config_json, dump_effective_modules_config_yaml,
     host::init_logging_unified, host::init_panic_tracing, list_module_names, run_migrate,
     run_server,
 };
+use modkit::runtime::{MigrateCommand, MigrateOptions};
 
 use std::path::PathBuf;
 
@@ -60,7 +61,51 @@ enum Commands {
     /// Validate configuration and exit
     Check,
     /// Run database migrations and exit (for cloud deployments)
-    Migrate,
+    Migrate(MigrateArgs),
+}
+
+#[derive(Args)]
+struct MigrateArgs {
+    /// Print the SQL that would run instead of running it
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    action: Option<MigrateAction>,
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Apply pending migrations of all modules (default)
+    Up,
+    /// Show applied and pending migrations of all modules
+    Status,
+    /// Roll back migrations of one module
+    Down {
+        /// Module whose migrations to roll back
+        #[arg(long)]
+        module: String,
+        /// Keep this migration and roll back all later ones (default: roll back only the newest)
+        #[arg(long)]
+        to: Option<String>,
+    },
+}
+
+impl From<&MigrateArgs> for MigrateOptions {
+    fn from(args: &MigrateArgs) -> Self {
+        let command = match &args.action {
+            None | Some(MigrateAction::Up) => MigrateCommand::Up,
+            Some(MigrateAction::Status) => MigrateCommand::Status,
+            Some(MigrateAction::Down { module, to }) => MigrateCommand::Down {
+                module: module.clone(),
+                to: to.clone(),
+            },
+        };
+        MigrateOptions {
+            command,
+            dry_run: args.dry_run,
+        }
+    }
 }
 
 #[tokio::main]
@@ -151,7 +196,7 @@ async fn main() -> Result<()> {
     match cli.command.as_ref().unwrap_or(&Commands::Run) {
         Commands::Run => run_server(config).await,
         Commands::Check => check_config(&config),
-        Commands::Migrate => run_migrate(config).await,
+        Commands::Migrate(args) => run_migrate(config, args.into()).await,
     }
 }
 
@@ -116,11 +116,13 @@ pub(crate) mod resources {
     pub const USER: ResourceType = ResourceType {
         name: "users_info.user",
         supported_properties: &[pep_properties::OWNER_TENANT_ID, pep_properties::RESOURCE_ID],
+        timestamp_properties: &[],
     };
 
     pub const CITY: ResourceType = ResourceType {
         name: "users_info.city",
         supported_properties: &[pep_properties::OWNER_TENANT_ID, pep_properties::RESOURCE_ID],
+        timestamp_properties: &[],
     };
 
     pub const ADDRESS: ResourceType = ResourceType {
@@ -131,6 +133,7 @@ pub(crate) mod resources {
             pep_properties::OWNER_ID,
             properties::CITY_ID,
         ],
+        timestamp_properties: &[],
     };
 }
 
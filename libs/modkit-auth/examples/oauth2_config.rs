@@ -51,6 +51,9 @@ fn config_overview() {
         // Vendor-specific headers (e.g. Azure requires a resource header):
         extra_headers: vec![("x-vendor-id".into(), "acme-corp".into())],
 
+        // Extra token request parameters (e.g. Auth0 requires an audience):
+        extra_params: vec![("audience".into(), "https://api.example.com".into())],
+
         // Refresh policy (defaults shown):
         refresh_offset: std::time::Duration::from_secs(30 * 60),
         jitter_max: std::time::Duration::from_secs(5 * 60),
@@ -38,6 +38,10 @@ pub struct OAuthClientConfig {
     /// Extra headers attached to every token request (vendor quirks).
     pub extra_headers: Vec<(String, String)>,
 
+    /// Extra form parameters attached to every token request
+    /// (e.g. `audience` or `resource`).
+    pub extra_params: Vec<(String, String)>,
+
     // ---- refresh policy -----------------------------------------------------
     /// How far before expiry the token should be refreshed (default: 30 min).
     pub refresh_offset: Duration,
@@ -100,6 +104,7 @@ impl Clone for OAuthClientConfig {
             scopes: self.scopes.clone(),
             auth_method: self.auth_method,
             extra_headers: self.extra_headers.clone(),
+            extra_params: self.extra_params.clone(),
             refresh_offset: self.refresh_offset,
             jitter_max: self.jitter_max,
             min_refresh_period: self.min_refresh_period,
@@ -125,6 +130,7 @@ impl fmt::Debug for OAuthClientConfig {
             .field("scopes", &self.scopes)
             .field("auth_method", &self.auth_method)
             .field("extra_headers", &redacted_headers)
+            .field("extra_params", &self.extra_params)
             .field("refresh_offset", &self.refresh_offset)
             .field("jitter_max", &self.jitter_max)
             .field("min_refresh_period", &self.min_refresh_period)
@@ -144,6 +150,7 @@ impl Default for OAuthClientConfig {
             scopes: Vec::new(),
             auth_method: ClientAuthMethod::default(),
             extra_headers: Vec::new(),
+            extra_params: Vec::new(),
             refresh_offset: Duration::from_secs(30 * 60),
             jitter_max: Duration::from_secs(5 * 60),
             min_refresh_period: Duration::from_secs(10),
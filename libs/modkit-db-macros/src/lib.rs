@@ -37,8 +37,9 @@
 //! - **Resource**: `resource_col = "column_name"` OR `no_resource`
 //! - **Owner**: `owner_col = "column_name"` OR `no_owner`
 //! - **Type**: `type_col = "column_name"` OR `no_type`
-//! - **Unrestricted**: `unrestricted` (forbids all other attributes)
+//! - **Unrestricted**: `unrestricted` (forbids all other attributes except `audit`)
 //! - **Custom PEP property**: `pep_prop(property_name = "column_name")` (repeatable)
+//! - **Audit trail**: `audit` (optional; records every write, see `modkit_db::audit`)
 //!
 //! ## Note on `OData` Macros
 //!
@@ -64,8 +65,9 @@ mod scopable;
 /// - `resource_col = "column_name"` OR `no_resource` - Primary resource ID column
 /// - `owner_col = "column_name"` OR `no_owner` - Owner-based filtering column
 /// - `type_col = "column_name"` OR `no_type` - Type-based filtering column
-/// - `unrestricted` - Mark as global entity (forbids all other attributes)
+/// - `unrestricted` - Mark as global entity (forbids all other attributes except `audit`)
 /// - `pep_prop(property_name = "column_name")` - Custom PEP property mapping (repeatable)
+/// - `audit` - Record inserts, updates and deletes in the audit trail (optional)
 ///
 /// The macro auto-generates `resolve_property()` from dimension columns and `pep_prop` entries:
 /// - `tenant_col` → `"owner_tenant_id"`
@@ -41,6 +41,9 @@ struct SecureConfig {
     // Unrestricted flag
     unrestricted: Option<Span>,
 
+    // Audit trail flag
+    audit: Option<Span>,
+
     // Custom PEP property mappings: (property_name, column_name, span)
     pep_props: Vec<(String, String, Span)>,
 }
@@ -62,12 +65,14 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
     validate_config(&config, &input);
 
     let entity_ident = syn::Ident::new("Entity", input.ident.span());
+    let is_audited = config.audit.is_some();
 
     // If unrestricted, generate simple implementation with all None
     if config.unrestricted.is_some() {
         return quote! {
             impl ::modkit_db::secure::ScopableEntity for #entity_ident {
                 const IS_UNRESTRICTED: bool = true;
+                const IS_AUDITED: bool = #is_audited;
 
                 fn tenant_col() -> ::core::option::Option<Self::Column> {
                     ::core::option::Option::None
@@ -117,6 +122,7 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
     quote! {
         impl ::modkit_db::secure::ScopableEntity for #entity_ident {
             const IS_UNRESTRICTED: bool = false;
+            const IS_AUDITED: bool = #is_audited;
 
             #tenant_col_impl
 
@@ -338,6 +344,14 @@ fn parse_secure_attrs(input: &DeriveInput) -> SecureConfig {
                 return Ok(());
             }
 
+            if meta.path.is_ident("audit") {
+                if config.audit.is_some() {
+                    abort!(span, "duplicate attribute 'audit'");
+                }
+                config.audit = Some(span);
+                return Ok(());
+            }
+
             if meta.path.is_ident("no_tenant") {
                 if config.unrestricted.is_some() {
                     abort!(span, "Cannot use 'no_tenant' with 'unrestricted'");
@@ -526,7 +540,7 @@ fn parse_key_value_attr(config: &mut SecureConfig, meta: syn::meta::ParseNestedM
                 span,
                 "Unknown attribute '{}'. Valid attributes: tenant_col, no_tenant, \
                  resource_col, no_resource, owner_col, no_owner, type_col, no_type, \
-                 unrestricted, pep_prop",
+                 unrestricted, audit, pep_prop",
                 key
             );
         }
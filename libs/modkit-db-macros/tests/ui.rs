@@ -16,6 +16,7 @@ fn ui() {
     t.compile_fail("tests/ui/err_unknown_attr.rs");
     t.compile_fail("tests/ui/err_non_struct.rs");
     t.compile_fail("tests/ui/err_duplicate_tenant_col.rs");
+    t.compile_fail("tests/ui/err_duplicate_audit.rs");
 
     // Error cases: Missing explicit decisions
     t.compile_fail("tests/ui/err_missing_tenant_decision.rs");
@@ -0,0 +1,10 @@
+// Duplicate attribute: audit specified twice should abort.
+
+use modkit_db_macros::Scopable;
+
+#[derive(Scopable)]
+#[secure(audit)]
+#[secure(audit)]
+struct Model;
+
+
@@ -0,0 +1,391 @@
+//! Audit trail for scopable entities.
+//!
+//! Entities opt in with `#[secure(audit)]`. Every insert, update and delete made through
+//! the secure write helpers is then recorded in the `modkit_audit` table, in the same
+//! transaction as the write itself:
+//!
+//! - **who**: the subject id of the [`SecurityContext`] the runner acts for;
+//! - **what**: the entity (table) name, primary key and owning tenant;
+//! - **how**: the operation and a before/after JSON diff — the full row for inserts and
+//!   deletes, only the changed columns for updates.
+//!
+//! Only the single-row helpers know which rows they touch, so audited entities must be
+//! written through [`secure_insert`](crate::secure::secure_insert),
+//! [`secure_update_with_scope`](crate::secure::secure_update_with_scope) and
+//! [`secure_delete_with_scope`](crate::secure::secure_delete_with_scope). The bulk and
+//! upsert builders (`update_many`, `delete_many`, `insert_one`) refuse audited entities
+//! rather than write unrecorded changes.
+//!
+//! # Setup
+//!
+//! ```ignore
+//! #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+//! #[sea_orm(table_name = "users")]
+//! #[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type, audit)]
+//! pub struct Model { /* ... */ }
+//!
+//! fn migrations(&self) -> Vec<Box<dyn MigrationTrait>> {
+//!     vec![Box::new(m001_init::Migration), modkit_db::audit::migration()]
+//! }
+//! ```
+//!
+//! # Recording the subject
+//!
+//! The secure helpers only receive an `AccessScope`, so the acting subject travels with
+//! the runner: take it from a [`Db::with_subject`](crate::Db::with_subject) handle.
+//!
+//! ```ignore
+//! let db = db.with_subject(&ctx);
+//! service.update_user(&db.conn()?, &scope, id, patch).await?;
+//! ```
+//!
+//! Transactions of that handle carry the subject too. Audited writes through a runner
+//! without a subject fail with `ScopeError::Invalid` instead of recording an anonymous
+//! event.
+//!
+//! # Querying
+//!
+//! [`list_events`] pages through the trail with `OData` (`$filter`, `$orderby`, `$count`
+//! and cursors) on `id`, `occurred_at`, `subject_id`, `tenant_id`, `entity`,
+//! `primary_key` and `operation`. Events are scoped by tenant like any other entity;
+//! events of entities without a tenant column are visible to unconstrained scopes only.
+
+use std::future::Future;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use modkit_odata::filter::FieldKind;
+use modkit_odata::{Error as ODataError, ODataQuery, Page, SortDir};
+use modkit_security::{AccessScope, pep_properties};
+use sea_orm::sea_query::{Index, sea_value_to_json_value};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, IdenStatic, Iterable, ModelTrait,
+    PrimaryKeyToColumn, Set,
+};
+use sea_orm_migration::prelude::{
+    Alias, ColumnDef, MigrationName, MigrationTrait, SchemaManager, Table,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::odata::{FieldMap, pager::OPager};
+use crate::secure::{DBRunner, ScopableEntity};
+
+/// Name of the audit table.
+pub const AUDIT_TABLE: &str = "modkit_audit";
+
+mod entity {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "modkit_audit")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub occurred_at: ChronoDateTimeUtc,
+        pub subject_id: Uuid,
+        pub tenant_id: Option<Uuid>,
+        pub entity: String,
+        pub primary_key: String,
+        pub operation: String,
+        pub before: Option<String>,
+        pub after: Option<String>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for entity::Entity {
+    fn tenant_col() -> Option<Self::Column> {
+        Some(entity::Column::TenantId)
+    }
+    fn resource_col() -> Option<Self::Column> {
+        None
+    }
+    fn owner_col() -> Option<Self::Column> {
+        None
+    }
+    fn type_col() -> Option<Self::Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<Self::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            _ => None,
+        }
+    }
+}
+
+// --------------------------- Migration ---------------------------------------
+
+/// Migration creating the audit table.
+///
+/// Idempotent (`IF NOT EXISTS`), so modules sharing a database can all list it. For the
+/// same reason `down` leaves the table in place: other modules may still record into it.
+pub struct AuditMigration;
+
+impl MigrationName for AuditMigration {
+    fn name(&self) -> &'static str {
+        "m0000_modkit_audit"
+    }
+}
+
+#[async_trait]
+impl MigrationTrait for AuditMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(AUDIT_TABLE))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new("occurred_at"))
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Alias::new("subject_id")).uuid().not_null())
+                    .col(ColumnDef::new(Alias::new("tenant_id")).uuid())
+                    .col(ColumnDef::new(Alias::new("entity")).string().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("primary_key"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Alias::new("operation")).string().not_null())
+                    .col(ColumnDef::new(Alias::new("before")).text())
+                    .col(ColumnDef::new(Alias::new("after")).text())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_modkit_audit_entity_key")
+                    .table(Alias::new(AUDIT_TABLE))
+                    .col(Alias::new("entity"))
+                    .col(Alias::new("primary_key"))
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
+
+/// The audit migration, ready to append to a module's migration list.
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(AuditMigration)
+}
+
+// --------------------------- Recording ---------------------------------------
+
+/// Kind of write recorded in the audit trail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AuditOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl AuditOperation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Record a write to `E` by `subject` on `conn`, which must be the transaction of the write.
+///
+/// `before` is the row prior to an update or delete, `after` the row after an insert or
+/// update. The event is built before the returned future is polled, so the models do
+/// not need to outlive this call.
+pub(crate) fn record<'c, E, C>(
+    conn: &'c C,
+    subject: Uuid,
+    operation: AuditOperation,
+    before: Option<&E::Model>,
+    after: Option<&E::Model>,
+) -> impl Future<Output = Result<(), DbErr>> + Send + 'c
+where
+    E: ScopableEntity,
+    C: ConnectionTrait + Sync,
+{
+    let event = after
+        .or(before)
+        .map(|row| event::<E>(subject, operation, row, before, after));
+    async move {
+        if let Some(event) = event {
+            event.insert(conn).await?;
+        }
+        Ok(())
+    }
+}
+
+fn event<E: ScopableEntity>(
+    subject: Uuid,
+    operation: AuditOperation,
+    row: &E::Model,
+    before: Option<&E::Model>,
+    after: Option<&E::Model>,
+) -> entity::ActiveModel {
+    let tenant_id = E::tenant_col().and_then(|col| match row.get(col) {
+        sea_orm::Value::Uuid(Some(id)) => Some(*id),
+        _ => None,
+    });
+    let primary_key = E::PrimaryKey::iter()
+        .map(|pk| key_part(&row.get(pk.into_column())))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut before = before.map(columns::<E>);
+    let mut after = after.map(columns::<E>);
+    if let (Some(old), Some(new)) = (&mut before, &mut after) {
+        let unchanged: Vec<String> = old
+            .iter()
+            .filter(|(name, value)| new.get(*name) == Some(*value))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in unchanged {
+            old.remove(&name);
+            new.remove(&name);
+        }
+    }
+
+    entity::ActiveModel {
+        occurred_at: Set(Utc::now()),
+        subject_id: Set(subject),
+        tenant_id: Set(tenant_id),
+        entity: Set(E::default().table_name().to_owned()),
+        primary_key: Set(primary_key),
+        operation: Set(operation.as_str().to_owned()),
+        before: Set(before.map(|m| Value::Object(m).to_string())),
+        after: Set(after.map(|m| Value::Object(m).to_string())),
+        ..Default::default()
+    }
+}
+
+/// All columns of `model` as a JSON object keyed by column name.
+fn columns<E: EntityTrait>(model: &E::Model) -> Map<String, Value> {
+    E::Column::iter()
+        .map(|col| {
+            (
+                col.as_str().to_owned(),
+                sea_value_to_json_value(&model.get(col)),
+            )
+        })
+        .collect()
+}
+
+/// Primary key column value as stored in `primary_key`: strings unquoted, others as JSON.
+fn key_part(value: &sea_orm::Value) -> String {
+    match sea_value_to_json_value(value) {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+// --------------------------- Querying ----------------------------------------
+
+/// A recorded write.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub occurred_at: DateTime<Utc>,
+    /// Subject that made the write.
+    pub subject_id: Uuid,
+    /// Tenant owning the row; `None` for entities without a tenant column.
+    pub tenant_id: Option<Uuid>,
+    /// Table name of the entity.
+    pub entity: String,
+    /// Primary key of the row; composite keys are comma-separated.
+    pub primary_key: String,
+    /// `"insert"`, `"update"` or `"delete"`.
+    pub operation: String,
+    /// Columns before the write: the full row for deletes, the changed ones for updates.
+    pub before: Option<Value>,
+    /// Columns after the write: the full row for inserts, the changed ones for updates.
+    pub after: Option<Value>,
+}
+
+impl From<entity::Model> for AuditEvent {
+    fn from(m: entity::Model) -> Self {
+        let parse = |json: Option<String>| json.and_then(|s| serde_json::from_str(&s).ok());
+        Self {
+            id: m.id,
+            occurred_at: m.occurred_at,
+            subject_id: m.subject_id,
+            tenant_id: m.tenant_id,
+            entity: m.entity,
+            primary_key: m.primary_key,
+            operation: m.operation,
+            before: parse(m.before),
+            after: parse(m.after),
+        }
+    }
+}
+
+fn field_map() -> FieldMap<entity::Entity> {
+    use entity::{Column, Model};
+
+    FieldMap::new()
+        .insert_with_extractor("id", Column::Id, FieldKind::I64, |m: &Model| {
+            m.id.to_string()
+        })
+        .insert_with_extractor(
+            "occurred_at",
+            Column::OccurredAt,
+            FieldKind::DateTimeUtc,
+            |m: &Model| m.occurred_at.to_rfc3339(),
+        )
+        .insert("subject_id", Column::SubjectId, FieldKind::Uuid)
+        .insert("tenant_id", Column::TenantId, FieldKind::Uuid)
+        .insert_with_extractor("entity", Column::Entity, FieldKind::String, |m: &Model| {
+            m.entity.clone()
+        })
+        .insert_with_extractor(
+            "primary_key",
+            Column::PrimaryKey,
+            FieldKind::String,
+            |m: &Model| m.primary_key.clone(),
+        )
+        .insert_with_extractor(
+            "operation",
+            Column::Operation,
+            FieldKind::String,
+            |m: &Model| m.operation.clone(),
+        )
+}
+
+/// Page through the audit events visible in `scope`, newest first by default.
+///
+/// # Errors
+/// Returns an `OData` error for invalid filters, orderings or cursors, or if the query fails.
+pub async fn list_events(
+    runner: &impl DBRunner,
+    scope: &AccessScope,
+    query: &ODataQuery,
+) -> Result<Page<AuditEvent>, ODataError> {
+    OPager::<entity::Entity, _>::new(scope, runner, &field_map())
+        .tiebreaker("id", SortDir::Desc)
+        .limits(50, 500)
+        .fetch(query, AuditEvent::from)
+        .await
+}
@@ -27,6 +27,8 @@
 //! 3. **Params Merging**: `params` maps are merged, with module params taking precedence
 //! 4. **Pool Configuration**: Module pool config overrides server pool config entirely
 //! 5. **`SQLite` Paths**: `file`/`path` fields are module-only and never inherited from servers
+//! 6. **Replicas**: each entry of `replicas` is merged with its own `server` (if any), then
+//!    inherits `engine`, credentials, `dbname`, `params` and `pool` from the merged primary
 //!
 //! ## Conflict Detection
 //!
@@ -91,6 +93,23 @@ pub struct DbConnConfig {
     // Module-level only: reference to a global server by name.
     // If absent, this module config must be fully self-sufficient (dsn or fields).
     pub server: Option<String>,
+
+    /// Read replicas of this database.
+    ///
+    /// Each replica is a connection config of its own; `engine`, `user`, `password`,
+    /// `dbname`, `params` and `pool` are inherited from the primary when unset. Secure
+    /// SELECTs and `TxConfig::read_only()` transactions are routed to the replicas
+    /// round-robin; everything else goes to the primary.
+    #[serde(default)]
+    pub replicas: Option<Vec<DbConnConfig>>,
+
+    /// Read-your-writes window.
+    ///
+    /// After a successful write through the module's `Db`, reads made by the same tokio
+    /// task (typically the request that wrote) are served by the primary for this long, so
+    /// callers do not observe replica lag on data they just wrote.
+    #[serde(with = "modkit_utils::humantime_serde::option", default)]
+    pub read_your_writes: Option<Duration>,
 }
 
 /// Serializable engine selector for configuration.
@@ -41,6 +41,12 @@ where
         }
     }
 
+    /// Database engine identifier (`"postgres"`, `"mysql"` or `"sqlite"`).
+    #[must_use]
+    pub fn db_engine(&self) -> &'static str {
+        self.db.db_engine()
+    }
+
     /// Create a non-transactional database runner.
     ///
     /// # Errors
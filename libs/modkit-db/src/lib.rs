@@ -74,11 +74,13 @@ pub use sea_orm_migration;
 
 // Core modules
 pub mod advisory_locks;
+pub mod audit;
 pub mod config;
 pub mod manager;
 pub mod migration_runner;
 pub mod odata;
 pub mod options;
+pub mod outbox;
 
 pub mod secure;
 
@@ -86,6 +88,7 @@ mod db_provider;
 
 // Internal modules
 mod pool_opts;
+mod replicas;
 #[cfg(feature = "sqlite")]
 mod sqlite;
 
@@ -114,12 +117,13 @@ pub async fn connect_db(dsn: &str, opts: ConnectOpts) -> Result<Db> {
 
 /// Build a secure `Db` from config (no `DbHandle` exposure).
 ///
+/// If `cfg.replicas` is set, the returned `Db` routes reads to the replicas.
+///
 /// # Errors
 ///
 /// Returns `DbError` if configuration is invalid or connection fails.
 pub async fn build_db(cfg: DbConnConfig, global: Option<&GlobalDatabaseConfig>) -> Result<Db> {
-    let handle = options::build_db_handle(cfg, global).await?;
-    Ok(Db::new(handle))
+    replicas::build_routed_db(cfg, global).await
 }
 
 use std::time::Duration;
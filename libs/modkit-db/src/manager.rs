@@ -6,7 +6,7 @@
 //! - Merging global server configurations with module-specific settings
 
 use crate::config::{DbConnConfig, GlobalDatabaseConfig};
-use crate::options::build_db_handle;
+use crate::replicas::build_routed_db;
 use crate::{Db, DbError, Result};
 use dashmap::DashMap;
 use figment::Figment;
@@ -100,7 +100,36 @@ impl DbManager {
             return Ok(None);
         };
 
-        // If module references a global server, merge configurations
+        let module_home_dir = self.home_dir.join(module);
+        cfg = self.resolve(cfg, &module_home_dir)?;
+
+        // Replicas may reference servers and SQLite files of their own
+        if let Some(replicas) = cfg.replicas.take() {
+            cfg.replicas = Some(
+                replicas
+                    .into_iter()
+                    .map(|replica| self.resolve(replica, &module_home_dir))
+                    .collect::<Result<_>>()?,
+            );
+        }
+
+        // Build the database handle
+        let db = build_routed_db(cfg, self.global.as_ref()).await?;
+
+        tracing::info!(
+            module = %module,
+            engine = ?db.primary().engine(),
+            dsn = %crate::options::redact_credentials_in_dsn(Some(db.primary().dsn())),
+            replicas = db.replica_count(),
+            "Built database handle for module"
+        );
+
+        Ok(Some(db))
+    }
+
+    /// Merge the referenced global server (if any) and finalize `SQLite` paths.
+    fn resolve(&self, mut cfg: DbConnConfig, module_home: &Path) -> Result<DbConnConfig> {
+        // If the config references a global server, merge configurations
         if let Some(server_name) = &cfg.server {
             let server_cfg = self
                 .global
@@ -116,20 +145,7 @@ impl DbManager {
         }
 
         // Finalize SQLite paths if needed
-        let module_home_dir = self.home_dir.join(module);
-        cfg = self.finalize_sqlite_paths(cfg, &module_home_dir)?;
-
-        // Build the database handle
-        let handle = build_db_handle(cfg, self.global.as_ref()).await?;
-
-        tracing::info!(
-            module = %module,
-            engine = ?handle.engine(),
-            dsn = %crate::options::redact_credentials_in_dsn(Some(handle.dsn())),
-            "Built database handle for module"
-        );
-
-        Ok(Some(Db::new(handle)))
+        self.finalize_sqlite_paths(cfg, module_home)
     }
 
     /// Merge global server configuration into module configuration.
@@ -186,6 +202,14 @@ impl DbManager {
             module_cfg.pool = server_cfg.pool;
         }
 
+        // Replicas: a server's replicas are shared by the modules referencing it
+        if module_cfg.replicas.is_none() {
+            module_cfg.replicas = server_cfg.replicas;
+        }
+        if module_cfg.read_your_writes.is_none() {
+            module_cfg.read_your_writes = server_cfg.read_your_writes;
+        }
+
         // Note: file, path, and server fields are module-only and not merged
 
         module_cfg
@@ -18,13 +18,24 @@
 //!
 //! Modules only provide migration definitions via `MigrationTrait`. The runtime executes
 //! them using its privileged connection. Modules never receive raw database access.
+//!
+//! # Status, rollback and dry runs
+//!
+//! Besides applying migrations forward, the runner can report each migration's state
+//! ([`migration_status`]), undo applied migrations with their `down()`
+//! ([`rollback_migrations_for_module`]) and plan either direction without touching the
+//! schema ([`plan_migrations_for_module`], [`plan_rollback_for_module`]). A plan runs
+//! `up()`/`down()` against a recording connection and returns the SQL it issued; reads
+//! made by a migration during planning see no rows.
 
 use sea_orm::{
-    ConnectionTrait, DatabaseBackend, DbErr, ExecResult, FromQueryResult, Statement,
-    TransactionTrait,
+    ConnectionTrait, Database, DatabaseBackend, DbErr, ExecResult, FromQueryResult,
+    ProxyDatabaseTrait, ProxyExecResult, ProxyRow, Statement, TransactionTrait,
 };
-use sea_orm_migration::MigrationTrait;
-use std::collections::HashSet;
+use sea_orm_migration::{MigrationTrait, SchemaManager};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
 use thiserror::Error;
 use tracing::{debug, info};
 use xxhash_rust::xxh3::xxh3_64;
@@ -59,6 +70,32 @@ pub enum MigrationError {
     /// Duplicate migration name found in provided migrations list.
     #[error("duplicate migration name '{name}' for module '{module}'")]
     DuplicateMigrationName { module: String, name: String },
+
+    /// A migration's `down()` failed.
+    #[error("rollback of migration '{migration}' failed for module '{module}': {source}")]
+    RollbackFailed {
+        module: String,
+        migration: String,
+        source: DbErr,
+    },
+
+    /// The rollback target is not one of the module's migrations.
+    #[error("unknown migration '{target}' for module '{module}'")]
+    UnknownMigration { module: String, target: String },
+
+    /// An applied migration is no longer provided by the module, so it cannot be undone.
+    #[error(
+        "migration '{migration}' is applied for module '{module}' but not provided by it; cannot roll back"
+    )]
+    MissingMigration { module: String, migration: String },
+
+    /// Planning a migration against the recording connection failed.
+    #[error("dry run of migration '{migration}' failed for module '{module}': {source}")]
+    DryRunFailed {
+        module: String,
+        migration: String,
+        source: DbErr,
+    },
 }
 
 /// Result of a migration run.
@@ -72,12 +109,43 @@ pub struct MigrationResult {
     pub applied_names: Vec<String>,
 }
 
+/// Result of a rollback.
+#[derive(Debug, Clone)]
+pub struct RollbackResult {
+    /// Names of the migrations that were rolled back, newest first.
+    pub rolled_back: Vec<String>,
+}
+
+/// State of one migration of a module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationStatus {
+    pub name: String,
+    /// When the migration was applied, as stored in the history table; `None` if pending.
+    pub applied_at: Option<String>,
+    /// `false` for a migration recorded in the history but no longer provided by the module.
+    pub known: bool,
+}
+
+/// SQL a migration would run, produced by a dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationPlan {
+    pub name: String,
+    pub statements: Vec<String>,
+}
+
 /// Internal model for querying migration history.
 #[derive(Debug, FromQueryResult)]
 struct MigrationRecord {
     version: String,
 }
 
+/// Internal model for querying migration history with timestamps.
+#[derive(Debug, FromQueryResult)]
+struct MigrationHistoryRow {
+    version: String,
+    applied_at: String,
+}
+
 /// Sanitize a module name into a safe identifier fragment.
 ///
 /// Rules:
@@ -226,6 +294,139 @@ async fn record_migration(
     })
 }
 
+/// Remove a migration from the history after rolling it back.
+async fn delete_migration_record(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+    migration_name: &str,
+) -> Result<ExecResult, MigrationError> {
+    let backend = conn.get_database_backend();
+
+    let sql = match backend {
+        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
+            format!(r#"DELETE FROM "{table_name}" WHERE version = $1"#)
+        }
+        DatabaseBackend::MySql => format!(r"DELETE FROM `{table_name}` WHERE version = ?"),
+    };
+
+    conn.execute(Statement::from_sql_and_values(
+        backend,
+        &sql,
+        [migration_name.into()],
+    ))
+    .await
+    .map_err(|e| MigrationError::RecordFailed {
+        module: module_name.to_owned(),
+        migration: migration_name.to_owned(),
+        source: e,
+    })
+}
+
+/// Query applied migrations with their `applied_at` timestamps rendered as text.
+async fn get_migration_history(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+) -> Result<HashMap<String, String>, MigrationError> {
+    let backend = conn.get_database_backend();
+
+    let sql = match backend {
+        DatabaseBackend::Postgres => {
+            format!(r#"SELECT version, CAST(applied_at AS TEXT) AS applied_at FROM "{table_name}""#)
+        }
+        DatabaseBackend::MySql => {
+            format!(r"SELECT version, CAST(applied_at AS CHAR) AS applied_at FROM `{table_name}`")
+        }
+        DatabaseBackend::Sqlite => format!(r#"SELECT version, applied_at FROM "{table_name}""#),
+    };
+
+    let rows: Vec<MigrationHistoryRow> =
+        MigrationHistoryRow::find_by_statement(Statement::from_string(backend, sql))
+            .all(conn)
+            .await
+            .map_err(|e| MigrationError::QueryHistory {
+                module: module_name.to_owned(),
+                source: e,
+            })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.version, r.applied_at))
+        .collect())
+}
+
+/// Check whether a module's migration history table exists.
+///
+/// Propagates DB errors rather than treating them as "table missing".
+async fn history_table_exists(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+) -> Result<bool, MigrationError> {
+    let backend = conn.get_database_backend();
+    let sql = match backend {
+        DatabaseBackend::Postgres => format!(
+            "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = '{table_name}')"
+        ),
+        DatabaseBackend::MySql => format!(
+            "SELECT COUNT(*) FROM information_schema.tables WHERE table_name = '{table_name}'"
+        ),
+        DatabaseBackend::Sqlite => {
+            format!("SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='{table_name}'")
+        }
+    };
+    let row = conn
+        .query_one(Statement::from_string(backend, sql))
+        .await
+        .map_err(|e| MigrationError::QueryHistory {
+            module: module_name.to_owned(),
+            source: e,
+        })?;
+
+    Ok(match backend {
+        DatabaseBackend::Postgres => row
+            .and_then(|r| r.try_get_by_index::<bool>(0).ok())
+            .unwrap_or(false),
+        DatabaseBackend::MySql => row
+            .and_then(|r| r.try_get_by_index::<i64>(0).ok())
+            .is_some_and(|c| c > 0),
+        DatabaseBackend::Sqlite => row
+            .and_then(|r| r.try_get_by_index::<i32>(0).ok())
+            .is_some_and(|c| c > 0),
+    })
+}
+
+/// Applied migrations of a module; empty if its history table does not exist yet.
+async fn applied_history(
+    conn: &impl ConnectionTrait,
+    module_name: &str,
+) -> Result<HashMap<String, String>, MigrationError> {
+    let table_name = migration_table_name(module_name);
+    if history_table_exists(conn, &table_name, module_name).await? {
+        get_migration_history(conn, &table_name, module_name).await
+    } else {
+        Ok(HashMap::new())
+    }
+}
+
+fn ensure_unique_names(
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<(), MigrationError> {
+    let mut seen = HashSet::new();
+    for m in migrations {
+        let n = m.name().to_owned();
+        if !seen.insert(n.clone()) {
+            return Err(MigrationError::DuplicateMigrationName {
+                module: module_name.to_owned(),
+                name: n,
+            });
+        }
+    }
+    Ok(())
+}
+
 /// Run migrations for a specific module using a `Db`.
 ///
 /// This is the main entry point for the runtime to execute module migrations.
@@ -301,16 +502,7 @@ where
     }
 
     // Reject duplicate migration names early (security/correctness: deterministic + idempotent)
-    let mut seen = HashSet::new();
-    for m in &migrations {
-        let n = m.name().to_owned();
-        if !seen.insert(n.clone()) {
-            return Err(MigrationError::DuplicateMigrationName {
-                module: module_name.to_owned(),
-                name: n,
-            });
-        }
-    }
+    ensure_unique_names(module_name, &migrations)?;
 
     // Get the per-module migration table name
     let table_name = migration_table_name(module_name);
@@ -492,52 +684,7 @@ async fn get_pending_migrations_internal(
     let table_name = migration_table_name(module_name);
 
     // Check if table exists - if not, all migrations are pending.
-    // Propagate DB errors rather than treating them as "table missing".
-    let backend = conn.get_database_backend();
-    let table_exists = match backend {
-        DatabaseBackend::Postgres => {
-            let sql = format!(
-                "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = '{table_name}')"
-            );
-            let row = conn
-                .query_one(Statement::from_string(backend, sql))
-                .await
-                .map_err(|e| MigrationError::QueryHistory {
-                    module: module_name.to_owned(),
-                    source: e,
-                })?;
-            row.and_then(|r| r.try_get_by_index::<bool>(0).ok())
-                .unwrap_or(false)
-        }
-        DatabaseBackend::MySql => {
-            let sql = format!(
-                "SELECT COUNT(*) FROM information_schema.tables WHERE table_name = '{table_name}'"
-            );
-            let row = conn
-                .query_one(Statement::from_string(backend, sql))
-                .await
-                .map_err(|e| MigrationError::QueryHistory {
-                    module: module_name.to_owned(),
-                    source: e,
-                })?;
-            row.and_then(|r| r.try_get_by_index::<i64>(0).ok())
-                .is_some_and(|c| c > 0)
-        }
-        DatabaseBackend::Sqlite => {
-            let sql = format!(
-                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='{table_name}'"
-            );
-            let row = conn
-                .query_one(Statement::from_string(backend, sql))
-                .await
-                .map_err(|e| MigrationError::QueryHistory {
-                    module: module_name.to_owned(),
-                    source: e,
-                })?;
-            row.and_then(|r| r.try_get_by_index::<i32>(0).ok())
-                .is_some_and(|c| c > 0)
-        }
-    };
+    let table_exists = history_table_exists(conn, &table_name, module_name).await?;
 
     if !table_exists {
         return Ok(migrations.iter().map(|m| m.name().to_owned()).collect());
@@ -552,6 +699,288 @@ async fn get_pending_migrations_internal(
         .collect())
 }
 
+/// Report the state of every migration of a module.
+///
+/// Lists the provided migrations in name order, followed by migrations recorded in the
+/// history that the module no longer provides (`known: false`).
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError)` if the migration history cannot be queried.
+pub async fn migration_status(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let conn = db.sea_internal();
+    let mut history = applied_history(&conn, module_name).await?;
+
+    let mut names: Vec<&str> = migrations.iter().map(|m| m.name()).collect();
+    names.sort_unstable();
+    let mut status: Vec<MigrationStatus> = names
+        .into_iter()
+        .map(|name| MigrationStatus {
+            name: name.to_owned(),
+            applied_at: history.remove(name),
+            known: true,
+        })
+        .collect();
+
+    let mut unknown: Vec<MigrationStatus> = history
+        .into_iter()
+        .map(|(name, applied_at)| MigrationStatus {
+            name,
+            applied_at: Some(applied_at),
+            known: false,
+        })
+        .collect();
+    unknown.sort_by(|a, b| a.name.cmp(&b.name));
+    status.extend(unknown);
+    Ok(status)
+}
+
+/// Select the applied migrations to roll back, newest first.
+///
+/// With `target`, every applied migration sorted after it is selected; without, only the
+/// newest applied migration.
+fn rollback_selection<'m>(
+    module_name: &str,
+    migrations: &'m [Box<dyn MigrationTrait>],
+    applied: &HashMap<String, String>,
+    target: Option<&str>,
+) -> Result<Vec<&'m dyn MigrationTrait>, MigrationError> {
+    if let Some(target) = target
+        && !migrations.iter().any(|m| m.name() == target)
+    {
+        return Err(MigrationError::UnknownMigration {
+            module: module_name.to_owned(),
+            target: target.to_owned(),
+        });
+    }
+
+    let mut names: Vec<&String> = applied.keys().collect();
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    let selected: Vec<&String> = match target {
+        Some(target) => names.into_iter().filter(|n| n.as_str() > target).collect(),
+        None => names.into_iter().take(1).collect(),
+    };
+
+    selected
+        .into_iter()
+        .map(|name| {
+            migrations
+                .iter()
+                .find(|m| m.name() == name)
+                .map(AsRef::as_ref)
+                .ok_or_else(|| MigrationError::MissingMigration {
+                    module: module_name.to_owned(),
+                    migration: name.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Roll back applied migrations of a module by running their `down()`.
+///
+/// With `target` (a migration name), every applied migration sorted after it is rolled
+/// back and `target` itself stays applied; without, only the newest applied migration is
+/// rolled back. Each `down()` and its history removal run in one transaction.
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError)` if `target` is unknown, an applied migration to roll back
+/// is not provided, the history cannot be queried or updated, or a `down()` fails.
+pub async fn rollback_migrations_for_module(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: Vec<Box<dyn MigrationTrait>>,
+    target: Option<&str>,
+) -> Result<RollbackResult, MigrationError> {
+    ensure_unique_names(module_name, &migrations)?;
+    let conn = db.sea_internal();
+    let table_name = migration_table_name(module_name);
+    let applied = applied_history(&conn, module_name).await?;
+
+    let mut result = RollbackResult {
+        rolled_back: vec![],
+    };
+    for migration in rollback_selection(module_name, &migrations, &applied, target)? {
+        let name = migration.name().to_owned();
+        info!(module = module_name, migration = %name, "Rolling back migration");
+
+        let rollback_failed = |e| MigrationError::RollbackFailed {
+            module: module_name.to_owned(),
+            migration: name.clone(),
+            source: e,
+        };
+        let txn = conn.begin().await.map_err(rollback_failed)?;
+        let manager = SchemaManager::new(&txn);
+        let res: Result<(), MigrationError> = (async {
+            migration.down(&manager).await.map_err(rollback_failed)?;
+            delete_migration_record(&txn, &table_name, module_name, &name).await?;
+            Ok(())
+        })
+        .await;
+
+        match res {
+            Ok(()) => txn.commit().await.map_err(rollback_failed)?,
+            Err(err) => {
+                _ = txn.rollback().await;
+                return Err(err);
+            }
+        }
+        result.rolled_back.push(name);
+    }
+
+    info!(
+        module = module_name,
+        rolled_back = result.rolled_back.len(),
+        "Migration rollback complete"
+    );
+    Ok(result)
+}
+
+/// Plan the pending migrations of a module without applying them.
+///
+/// Returns the SQL each pending migration's `up()` would run, in application order.
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError)` if the history cannot be queried or a migration fails
+/// while being planned.
+pub async fn plan_migrations_for_module(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<Vec<MigrationPlan>, MigrationError> {
+    ensure_unique_names(module_name, migrations)?;
+    let conn = db.sea_internal();
+    let applied = applied_history(&conn, module_name).await?;
+
+    let mut pending: Vec<&dyn MigrationTrait> = migrations
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|m| !applied.contains_key(m.name()))
+        .collect();
+    pending.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let mut plans = Vec::with_capacity(pending.len());
+    for migration in pending {
+        plans.push(
+            plan(
+                conn.get_database_backend(),
+                module_name,
+                migration,
+                Direction::Up,
+            )
+            .await?,
+        );
+    }
+    Ok(plans)
+}
+
+/// Plan a rollback without running it.
+///
+/// Selects migrations like [`rollback_migrations_for_module`] and returns the SQL each
+/// `down()` would run, newest first.
+///
+/// # Errors
+///
+/// Same as [`rollback_migrations_for_module`], minus execution failures.
+pub async fn plan_rollback_for_module(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+    target: Option<&str>,
+) -> Result<Vec<MigrationPlan>, MigrationError> {
+    ensure_unique_names(module_name, migrations)?;
+    let conn = db.sea_internal();
+    let applied = applied_history(&conn, module_name).await?;
+
+    let mut plans = Vec::new();
+    for migration in rollback_selection(module_name, migrations, &applied, target)? {
+        plans.push(
+            plan(
+                conn.get_database_backend(),
+                module_name,
+                migration,
+                Direction::Down,
+            )
+            .await?,
+        );
+    }
+    Ok(plans)
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+}
+
+/// Run one direction of `migration` against a [`StatementRecorder`] for `backend`.
+async fn plan(
+    backend: DatabaseBackend,
+    module_name: &str,
+    migration: &dyn MigrationTrait,
+    direction: Direction,
+) -> Result<MigrationPlan, MigrationError> {
+    let dry_run_failed = |e| MigrationError::DryRunFailed {
+        module: module_name.to_owned(),
+        migration: migration.name().to_owned(),
+        source: e,
+    };
+    let recorder = StatementRecorder::default();
+    let statements = Arc::clone(&recorder.statements);
+    let conn = Database::connect_proxy(backend, Arc::new(Box::new(recorder)))
+        .await
+        .map_err(dry_run_failed)?;
+
+    let manager = SchemaManager::new(&conn);
+    match direction {
+        Direction::Up => migration.up(&manager).await,
+        Direction::Down => migration.down(&manager).await,
+    }
+    .map_err(dry_run_failed)?;
+
+    let statements =
+        std::mem::take(&mut *statements.lock().unwrap_or_else(PoisonError::into_inner));
+    Ok(MigrationPlan {
+        name: migration.name().to_owned(),
+        statements,
+    })
+}
+
+/// Connection backend that records statements instead of executing them.
+///
+/// Writes report zero affected rows and reads return no rows.
+#[derive(Debug, Default)]
+struct StatementRecorder {
+    statements: Arc<Mutex<Vec<String>>>,
+}
+
+impl StatementRecorder {
+    fn record(&self, statement: &Statement) {
+        self.statements
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(statement.to_string());
+    }
+}
+
+#[async_trait::async_trait]
+impl ProxyDatabaseTrait for StatementRecorder {
+    async fn query(&self, statement: Statement) -> Result<Vec<ProxyRow>, DbErr> {
+        self.record(&statement);
+        Ok(vec![])
+    }
+
+    async fn execute(&self, statement: Statement) -> Result<ProxyExecResult, DbErr> {
+        self.record(&statement);
+        Ok(ProxyExecResult::default())
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -621,11 +1050,32 @@ mod tests {
             Ok(())
         }
 
-        async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+            let backend = manager.get_database_backend();
+            let table_name = format!("test_{}", self.name.replace('-', "_"));
+            let sql = match backend {
+                DatabaseBackend::MySql => format!("DROP TABLE IF EXISTS `{table_name}`"),
+                _ => format!("DROP TABLE IF EXISTS \"{table_name}\""),
+            };
+            manager
+                .get_connection()
+                .execute(Statement::from_string(backend, sql))
+                .await?;
             Ok(())
         }
     }
 
+    fn test_migrations(names: &[&str]) -> Vec<Box<dyn MigrationTrait>> {
+        names
+            .iter()
+            .map(|name| {
+                Box::new(TestMigration {
+                    name: (*name).to_owned(),
+                }) as Box<dyn MigrationTrait>
+            })
+            .collect()
+    }
+
     #[cfg(feature = "sqlite")]
     mod sqlite_tests {
         use super::*;
@@ -875,5 +1325,168 @@ mod tests {
 
             assert_eq!(result.applied, 1);
         }
+
+        async fn table_exists(db: &Db, table: &str) -> bool {
+            let conn = db.sea_internal();
+            history_table_exists(&conn, table, "_").await.unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_migration_status() {
+            let db = setup_test_db().await;
+            let module_name = "test_status";
+
+            run_migrations_for_module(
+                &db,
+                module_name,
+                test_migrations(&["m000_removed", "m001_first"]),
+            )
+            .await
+            .expect("Should succeed");
+
+            let status = migration_status(
+                &db,
+                module_name,
+                &test_migrations(&["m002_second", "m001_first"]),
+            )
+            .await
+            .expect("Should succeed");
+
+            let summary: Vec<_> = status
+                .iter()
+                .map(|s| (s.name.as_str(), s.applied_at.is_some(), s.known))
+                .collect();
+            assert_eq!(
+                summary,
+                vec![
+                    ("m001_first", true, true),
+                    ("m002_second", false, true),
+                    ("m000_removed", true, false),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_rollback_latest_and_to_target() {
+            let db = setup_test_db().await;
+            let module_name = "test_rollback";
+            let names = ["m001_first", "m002_second", "m003_third"];
+
+            run_migrations_for_module(&db, module_name, test_migrations(&names))
+                .await
+                .expect("Should succeed");
+
+            let result =
+                rollback_migrations_for_module(&db, module_name, test_migrations(&names), None)
+                    .await
+                    .expect("Should succeed");
+            assert_eq!(result.rolled_back, vec!["m003_third"]);
+            assert!(!table_exists(&db, "test_m003_third").await);
+            assert!(table_exists(&db, "test_m002_second").await);
+
+            // Nothing left after the target: a no-op.
+            let result = rollback_migrations_for_module(
+                &db,
+                module_name,
+                test_migrations(&names),
+                Some("m002_second"),
+            )
+            .await
+            .expect("Should succeed");
+            assert!(result.rolled_back.is_empty());
+
+            run_migrations_for_module(&db, module_name, test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            let result = rollback_migrations_for_module(
+                &db,
+                module_name,
+                test_migrations(&names),
+                Some("m001_first"),
+            )
+            .await
+            .expect("Should succeed");
+            assert_eq!(result.rolled_back, vec!["m003_third", "m002_second"]);
+            assert!(table_exists(&db, "test_m001_first").await);
+
+            let pending = get_pending_migrations(&db, module_name, &test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert_eq!(pending, vec!["m002_second", "m003_third"]);
+        }
+
+        #[tokio::test]
+        async fn test_rollback_errors() {
+            let db = setup_test_db().await;
+            let module_name = "test_rollback_errors";
+
+            run_migrations_for_module(
+                &db,
+                module_name,
+                test_migrations(&["m001_first", "m002_second"]),
+            )
+            .await
+            .expect("Should succeed");
+
+            let err = rollback_migrations_for_module(
+                &db,
+                module_name,
+                test_migrations(&["m001_first", "m002_second"]),
+                Some("m009_nope"),
+            )
+            .await
+            .unwrap_err();
+            assert!(
+                matches!(err, MigrationError::UnknownMigration { .. }),
+                "{err:?}"
+            );
+
+            // m002 is applied but no longer provided: its `down()` is unavailable.
+            let err = rollback_migrations_for_module(
+                &db,
+                module_name,
+                test_migrations(&["m001_first"]),
+                None,
+            )
+            .await
+            .unwrap_err();
+            assert!(
+                matches!(err, MigrationError::MissingMigration { ref migration, .. } if migration == "m002_second"),
+                "{err:?}"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_plans_record_sql_without_running_it() {
+            let db = setup_test_db().await;
+            let module_name = "test_plan";
+            let names = ["m001_first", "m002_second"];
+
+            run_migrations_for_module(&db, module_name, test_migrations(&names[..1]))
+                .await
+                .expect("Should succeed");
+
+            let plans = plan_migrations_for_module(&db, module_name, &test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert_eq!(plans.len(), 1);
+            assert_eq!(plans[0].name, "m002_second");
+            assert_eq!(
+                plans[0].statements,
+                vec![r#"CREATE TABLE IF NOT EXISTS "test_m002_second" (id INTEGER PRIMARY KEY)"#]
+            );
+            assert!(!table_exists(&db, "test_m002_second").await);
+
+            let plans = plan_rollback_for_module(&db, module_name, &test_migrations(&names), None)
+                .await
+                .expect("Should succeed");
+            assert_eq!(plans.len(), 1);
+            assert_eq!(plans[0].name, "m001_first");
+            assert_eq!(
+                plans[0].statements,
+                vec![r#"DROP TABLE IF EXISTS "test_m001_first""#]
+            );
+            assert!(table_exists(&db, "test_m001_first").await);
+        }
     }
 }
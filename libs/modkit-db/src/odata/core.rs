@@ -8,14 +8,16 @@ use chrono::{NaiveDate, NaiveTime, Utc};
 use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, ODataQuery, SortDir, ast as core};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    ColumnTrait, Condition, DbBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
     sea_query::{Expr, Order},
 };
 use thiserror::Error;
 
-use modkit_odata::filter::FieldKind;
+use modkit_odata::filter::{FieldKind, FilterOp, Quantifier, field_function, lambda_operands};
 
 use crate::odata::LimitCfg;
+use crate::odata::functions::{compare, field_fn_expr, lambda_condition};
+use crate::odata::sea_orm_filter::{backend_of, count_rows, search_condition};
 use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
 
 /// Type alias for cursor extraction function to reduce type complexity
@@ -32,6 +34,7 @@ pub struct Field<E: EntityTrait> {
 #[must_use]
 pub struct FieldMap<E: EntityTrait> {
     map: HashMap<String, Field<E>>,
+    search: Vec<String>,
 }
 
 impl<E: EntityTrait> Default for FieldMap<E> {
@@ -44,6 +47,7 @@ impl<E: EntityTrait> FieldMap<E> {
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            search: Vec::new(),
         }
     }
     pub fn insert(mut self, api_name: impl Into<String>, col: E::Column, kind: FieldKind) -> Self {
@@ -84,6 +88,32 @@ impl<E: EntityTrait> FieldMap<E> {
     pub fn get(&self, name: &str) -> Option<&Field<E>> {
         self.map.get(&name.to_lowercase())
     }
+
+    /// Declare the fields matched by `$search`. Only string fields already
+    /// inserted into the map are accepted; other names are ignored.
+    pub fn with_search_fields(mut self, api_names: &[&str]) -> Self {
+        for name in api_names {
+            let name = name.to_lowercase();
+            if self
+                .map
+                .get(&name)
+                .is_some_and(|f| f.kind == FieldKind::String)
+                && !self.search.contains(&name)
+            {
+                self.search.push(name);
+            }
+        }
+        self
+    }
+
+    /// Columns matched by `$search`, in declaration order.
+    #[must_use]
+    pub fn search_columns(&self) -> Vec<E::Column> {
+        self.search
+            .iter()
+            .filter_map(|name| self.map.get(name).map(|f| f.col))
+            .collect()
+    }
 }
 
 #[derive(Debug, Error, Clone)]
@@ -112,6 +142,9 @@ pub enum ODataBuildError {
     #[error("bare literal not allowed")]
     BareLiteral,
 
+    #[error("invalid function or lambda: {0}")]
+    InvalidFunction(String),
+
     #[error("{0}")]
     Other(&'static str),
 }
@@ -320,6 +353,7 @@ pub fn parse_cursor_value(kind: FieldKind, s: &str) -> ODataBuildResult<sea_orm:
                 .map_err(|_| ODataBuildError::Other("invalid decimal in cursor"))?;
             V::Decimal(Some(Box::new(d)))
         }
+        FieldKind::Json => return Err(ODataBuildError::Other("JSON fields cannot be cursor keys")),
     };
 
     Ok(result)
@@ -456,12 +490,41 @@ pub fn build_cursor_for_model<E: EntityTrait>(
 
 /// Convert an `OData` filter expression AST to a `SeaORM` Condition.
 ///
+/// Expressions whose SQL depends on the database (`indexof`, `year`/`month`/`day`, and
+/// `any`/`all` lambdas) are rejected; use [`expr_to_condition_for`] for those.
+///
 /// # Errors
 /// Returns `ODataBuildError` if the expression contains unknown fields or unsupported operations.
 pub fn expr_to_condition<E: EntityTrait>(
     expr: &core::Expr,
     fmap: &FieldMap<E>,
 ) -> ODataBuildResult<Condition>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    compile_expr::<E>(expr, fmap, None)
+}
+
+/// Like [`expr_to_condition`], generating backend-specific SQL for filter functions and lambdas.
+///
+/// # Errors
+/// Returns `ODataBuildError` if the expression contains unknown fields or unsupported operations.
+pub fn expr_to_condition_for<E: EntityTrait>(
+    expr: &core::Expr,
+    fmap: &FieldMap<E>,
+    backend: DbBackend,
+) -> ODataBuildResult<Condition>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    compile_expr::<E>(expr, fmap, Some(backend))
+}
+
+fn compile_expr<E: EntityTrait>(
+    expr: &core::Expr,
+    fmap: &FieldMap<E>,
+    backend: Option<DbBackend>,
+) -> ODataBuildResult<Condition>
 where
     E::Column: ColumnTrait + Copy,
 {
@@ -470,20 +533,36 @@ where
 
     Ok(match expr {
         X::And(a, b) => {
-            let left = expr_to_condition::<E>(a, fmap)?;
-            let right = expr_to_condition::<E>(b, fmap)?;
+            let left = compile_expr::<E>(a, fmap, backend)?;
+            let right = compile_expr::<E>(b, fmap, backend)?;
             Condition::all().add(left).add(right) // AND
         }
         X::Or(a, b) => {
-            let left = expr_to_condition::<E>(a, fmap)?;
-            let right = expr_to_condition::<E>(b, fmap)?;
+            let left = compile_expr::<E>(a, fmap, backend)?;
+            let right = compile_expr::<E>(b, fmap, backend)?;
             Condition::any().add(left).add(right) // OR
         }
         X::Not(x) => {
-            let inner = expr_to_condition::<E>(x, fmap)?;
+            let inner = compile_expr::<E>(x, fmap, backend)?;
             Condition::all().add(inner).not()
         }
 
+        // func(Identifier) op Value
+        X::Compare(lhs, op, rhs) if matches!(**lhs, X::Function(..)) => {
+            let (X::Function(fname, args), X::Value(val)) = (&**lhs, &**rhs) else {
+                return Err(ODataBuildError::Other("unsupported comparison form"));
+            };
+            let filter_op = match op {
+                Op::Eq => FilterOp::Eq,
+                Op::Ne => FilterOp::Ne,
+                Op::Gt => FilterOp::Gt,
+                Op::Ge => FilterOp::Ge,
+                Op::Lt => FilterOp::Lt,
+                Op::Le => FilterOp::Le,
+            };
+            function_condition(fname, args, filter_op, val, fmap, backend)?
+        }
+
         // Identifier op Value
         X::Compare(lhs, op, rhs) => {
             let (name, rhs_val) = match (&**lhs, &**rhs) {
@@ -539,30 +618,33 @@ where
             }
         }
 
-        // Supported functions: contains/startswith/endswith
+        // Supported functions: contains/startswith/endswith (on a field or a function of it), any/all
         X::Function(fname, args) => {
+            if let Some(quantifier) = Quantifier::from_name(fname) {
+                return lambda_to_condition(quantifier, args, fmap, backend);
+            }
             let n = fname.to_ascii_lowercase();
-            match (n.as_str(), args.as_slice()) {
-                ("contains", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_contains(s)))
-                }
-                ("startswith", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
+            let op = match n.as_str() {
+                "contains" => FilterOp::Contains,
+                "startswith" => FilterOp::StartsWith,
+                "endswith" => FilterOp::EndsWith,
+                _ => return Err(ODataBuildError::UnsupportedFn(fname.clone())),
+            };
+            match args.as_slice() {
+                [X::Identifier(name), X::Value(core::Value::String(s))] => {
                     let f = fmap
                         .get(name)
                         .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
                     ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_starts(s)))
+                    let pattern = match op {
+                        FilterOp::Contains => like_contains(s),
+                        FilterOp::StartsWith => like_starts(s),
+                        _ => like_ends(s),
+                    };
+                    Condition::all().add(Expr::col(f.col).like(pattern))
                 }
-                ("endswith", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_ends(s)))
+                [X::Function(inner, inner_args), X::Value(val)] => {
+                    function_condition(inner, inner_args, op, val, fmap, backend)?
                 }
                 _ => return Err(ODataBuildError::UnsupportedFn(fname.clone())),
             }
@@ -574,6 +656,77 @@ where
     })
 }
 
+/// `func(field) op value`, e.g. `tolower(name) eq 'bob'` or `year(created_at) ge 2024`.
+fn function_condition<E: EntityTrait>(
+    fname: &str,
+    args: &[core::Expr],
+    op: FilterOp,
+    value: &core::Value,
+    fmap: &FieldMap<E>,
+    backend: Option<DbBackend>,
+) -> ODataBuildResult<Condition>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    let (func, name) =
+        field_function(fname, args).map_err(|e| ODataBuildError::InvalidFunction(e.to_string()))?;
+    let f = fmap
+        .get(name)
+        .ok_or_else(|| ODataBuildError::UnknownField(name.to_owned()))?;
+    if !func.accepts(f.kind) {
+        return Err(ODataBuildError::InvalidFunction(format!(
+            "{func}() on {} field '{name}'",
+            f.kind
+        )));
+    }
+    let value_matches = matches!(
+        (func.result_kind(), value),
+        (FieldKind::String, core::Value::String(_)) | (FieldKind::I64, core::Value::Number(_))
+    );
+    if !value_matches {
+        return Err(ODataBuildError::TypeMismatch {
+            expected: func.result_kind(),
+            got: "mismatched literal",
+        });
+    }
+
+    let operand = field_fn_expr(&func, Expr::col(f.col).into(), backend)
+        .map_err(ODataBuildError::InvalidFunction)?;
+    let expr = compare(operand, op, value).map_err(ODataBuildError::InvalidFunction)?;
+    Ok(Condition::all().add(expr))
+}
+
+/// `field/any(x: ...)` / `field/all(x: ...)` over a `FieldKind::Json` column.
+fn lambda_to_condition<E: EntityTrait>(
+    quantifier: Quantifier,
+    args: &[core::Expr],
+    fmap: &FieldMap<E>,
+    backend: Option<DbBackend>,
+) -> ODataBuildResult<Condition>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    let (name, predicate) = lambda_operands(quantifier, args)
+        .map_err(|e| ODataBuildError::InvalidFunction(e.to_string()))?;
+    let f = fmap
+        .get(name)
+        .ok_or_else(|| ODataBuildError::UnknownField(name.to_owned()))?;
+    if f.kind != FieldKind::Json {
+        return Err(ODataBuildError::TypeMismatch {
+            expected: FieldKind::Json,
+            got: "non-json field",
+        });
+    }
+    let expr = lambda_condition(
+        quantifier,
+        Expr::col(f.col).into(),
+        predicate.as_ref(),
+        backend,
+    )
+    .map_err(ODataBuildError::InvalidFunction)?;
+    Ok(Condition::all().add(expr))
+}
+
 /// Apply an optional `OData` filter (via wrapper) to a plain `SeaORM` Select<E>.
 ///
 /// This extension does NOT parse the filter string — it only consumes a parsed AST
@@ -784,7 +937,10 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
     l
 }
 
-/// One-shot pagination combiner that handles filter → cursor predicate → order → overfetch/trim → build cursors.
+/// One-shot pagination combiner that handles filter → search → cursor predicate → order → overfetch/trim → build cursors.
+///
+/// `$search` matches the fields declared with [`FieldMap::with_search_fields`];
+/// `$count=true` fills `page_info.total_count` using a separate `COUNT(*)` query.
 ///
 /// # Errors
 /// Returns `ODataError` if filter application, cursor validation, or database query fails.
@@ -832,11 +988,31 @@ where
     // Apply filter
     if let Some(ast) = q.filter.as_deref() {
         s = s.filter(
-            expr_to_condition::<E>(ast, fmap)
+            expr_to_condition_for::<E>(ast, fmap, backend_of(conn))
                 .map_err(|e| ODataError::InvalidFilter(e.to_string()))?,
         );
     }
 
+    // Apply free-text search
+    if let Some(search) = q.search() {
+        let columns = fmap.search_columns();
+        if columns.is_empty() {
+            return Err(ODataError::InvalidSearch(
+                "$search is not supported for this resource".to_owned(),
+            ));
+        }
+        if let Some(cond) = search_condition(&columns, search) {
+            s = s.filter(cond);
+        }
+    }
+
+    // Total count covers filter + search only (no cursor, order, or limit)
+    let total_count = if q.count {
+        Some(count_rows(s.clone(), conn).await?)
+    } else {
+        None
+    };
+
     // Check if we're paginating backward
     let is_backward = q.cursor.as_ref().is_some_and(|c| c.d == "bwd");
 
@@ -860,7 +1036,7 @@ where
     s = s.limit(fetch);
 
     #[allow(clippy::disallowed_methods)]
-    let mut rows = match DBRunnerInternal::as_seaorm(conn) {
+    let mut rows = match DBRunnerInternal::as_seaorm_read(conn) {
         SeaOrmRunner::Conn(db) => s.all(db).await,
         SeaOrmRunner::Tx(tx) => s.all(tx).await,
     }
@@ -932,6 +1108,7 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count,
         },
     })
 }
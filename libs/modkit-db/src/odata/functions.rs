@@ -0,0 +1,190 @@
+//! SQL for `OData` filter functions and `any`/`all` lambdas over JSON array columns.
+//!
+//! `tolower`, `toupper`, `length` and the string matches are portable. `indexof`, the
+//! date parts and lambdas need the database backend:
+//!
+//! | | `SQLite` | Postgres | `MySQL` |
+//! |---|---|---|---|
+//! | `indexof` | `INSTR(x, s) - 1` | `STRPOS(x, s) - 1` | `INSTR(x, s) - 1` |
+//! | `year`/`month`/`day` | `strftime('%Y', x)` | `EXTRACT(YEAR FROM x)` | `EXTRACT(YEAR FROM x)` |
+//! | lambda elements | `json_each(x)` | `json_array_elements_text(x::json)` | `JSON_TABLE(x, '$[*]' ...)` |
+//!
+//! Lambdas compile to `EXISTS (SELECT 1 FROM <elements> WHERE p)` for `any` and
+//! `NOT EXISTS (SELECT 1 FROM <elements> WHERE NOT p)` for `all`.
+
+use bigdecimal::ToPrimitive;
+use modkit_odata::filter::{ElementPredicate, FieldFn, FilterOp, ODataValue, Quantifier};
+use sea_orm::DbBackend;
+use sea_orm::sea_query::{Alias, Expr, Func, LikeExpr, SimpleExpr};
+
+use crate::odata::sea_orm_filter::escape_like;
+
+/// Alias of the derived table holding the array elements inside a lambda.
+const ELEMENTS: &str = "oj";
+
+fn require_backend(backend: Option<DbBackend>, what: &str) -> Result<DbBackend, String> {
+    backend.ok_or_else(|| format!("{what} requires a database backend"))
+}
+
+/// Apply `func` to `operand`.
+pub fn field_fn_expr(
+    func: &FieldFn,
+    operand: SimpleExpr,
+    backend: Option<DbBackend>,
+) -> Result<SimpleExpr, String> {
+    Ok(match func {
+        FieldFn::ToLower => Func::lower(operand).into(),
+        FieldFn::ToUpper => Func::upper(operand).into(),
+        FieldFn::Length => Func::char_length(operand).into(),
+        FieldFn::IndexOf(needle) => {
+            let template = match require_backend(backend, "indexof()")? {
+                DbBackend::Postgres => "(STRPOS($1, $2) - 1)",
+                DbBackend::MySql | DbBackend::Sqlite => "(INSTR(?, ?) - 1)",
+            };
+            Expr::cust_with_exprs(template, [operand, Expr::val(needle.clone()).into()])
+        }
+        FieldFn::Year | FieldFn::Month | FieldFn::Day => {
+            let (unit, format) = match func {
+                FieldFn::Year => ("YEAR", "%Y"),
+                FieldFn::Month => ("MONTH", "%m"),
+                _ => ("DAY", "%d"),
+            };
+            let template = match require_backend(backend, &format!("{func}()"))? {
+                DbBackend::Sqlite => format!("CAST(strftime('{format}', ?) AS INTEGER)"),
+                DbBackend::Postgres => format!("CAST(EXTRACT({unit} FROM $1) AS INTEGER)"),
+                DbBackend::MySql => format!("EXTRACT({unit} FROM ?)"),
+            };
+            Expr::cust_with_expr(template, operand)
+        }
+    })
+}
+
+/// `operand op value`, where `op` is a comparison or `contains`/`startswith`/`endswith`.
+pub fn compare(
+    operand: SimpleExpr,
+    op: FilterOp,
+    value: &ODataValue,
+) -> Result<SimpleExpr, String> {
+    let operand = Expr::expr(operand);
+    let pattern = |s: &str| match op {
+        FilterOp::Contains => format!("%{}%", escape_like(s)),
+        FilterOp::StartsWith => format!("{}%", escape_like(s)),
+        _ => format!("%{}", escape_like(s)),
+    };
+
+    Ok(match op {
+        FilterOp::Eq => operand.eq(literal(value)?),
+        FilterOp::Ne => operand.ne(literal(value)?),
+        FilterOp::Gt => operand.gt(literal(value)?),
+        FilterOp::Ge => operand.gte(literal(value)?),
+        FilterOp::Lt => operand.lt(literal(value)?),
+        FilterOp::Le => operand.lte(literal(value)?),
+        FilterOp::Contains | FilterOp::StartsWith | FilterOp::EndsWith => {
+            let ODataValue::String(s) = value else {
+                return Err(format!("{op}() expects a string, got {value}"));
+            };
+            operand.like(LikeExpr::new(pattern(s)).escape('\\'))
+        }
+        FilterOp::And | FilterOp::Or => {
+            return Err(format!("Logical operator {op:?} in binary context"));
+        }
+    })
+}
+
+fn literal(value: &ODataValue) -> Result<sea_orm::Value, String> {
+    Ok(match value {
+        ODataValue::String(s) => sea_orm::Value::String(Some(Box::new(s.clone()))),
+        ODataValue::Bool(b) => sea_orm::Value::Bool(Some(*b)),
+        ODataValue::Number(n) => {
+            if let Some(i) = n.to_i64().filter(|_| n.is_integer()) {
+                sea_orm::Value::BigInt(Some(i))
+            } else {
+                sea_orm::Value::Double(Some(n.to_f64().ok_or("Number value out of range")?))
+            }
+        }
+        other => return Err(format!("Unsupported {other} literal")),
+    })
+}
+
+/// `collection/any(x: predicate)` or `collection/all(x: predicate)` over a JSON array.
+pub fn lambda_condition(
+    quantifier: Quantifier,
+    collection: SimpleExpr,
+    predicate: Option<&ElementPredicate>,
+    backend: Option<DbBackend>,
+) -> Result<SimpleExpr, String> {
+    let backend = require_backend(backend, &format!("{quantifier}()"))?;
+    let (elements, placeholder) = match backend {
+        DbBackend::Sqlite => ("json_each(?)", "?"),
+        DbBackend::Postgres => ("json_array_elements_text(CAST($1 AS json))", "$2"),
+        DbBackend::MySql => (
+            "JSON_TABLE(?, '$[*]' COLUMNS (value VARCHAR(4096) PATH '$'))",
+            "?",
+        ),
+    };
+    let from = format!("SELECT 1 FROM {elements} AS {ELEMENTS}");
+
+    Ok(match (quantifier, predicate) {
+        (Quantifier::Any, None) => Expr::cust_with_expr(format!("EXISTS ({from})"), collection),
+        (Quantifier::Any, Some(p)) => Expr::cust_with_exprs(
+            format!("EXISTS ({from} WHERE {placeholder})"),
+            [collection, element_condition(p, backend)?],
+        ),
+        (Quantifier::All, Some(p)) => Expr::cust_with_exprs(
+            format!("NOT EXISTS ({from} WHERE NOT ({placeholder}))"),
+            [collection, element_condition(p, backend)?],
+        ),
+        (Quantifier::All, None) => return Err("all() requires a predicate".to_owned()),
+    })
+}
+
+fn element_condition(pred: &ElementPredicate, backend: DbBackend) -> Result<SimpleExpr, String> {
+    Ok(match pred {
+        ElementPredicate::Compare { op, value } => {
+            compare(element(value, backend), *op, &element_value(value, backend))?
+        }
+        ElementPredicate::In(values) => values
+            .iter()
+            .map(|v| {
+                compare(
+                    element(v, backend),
+                    FilterOp::Eq,
+                    &element_value(v, backend),
+                )
+            })
+            .reduce(|acc, next| Ok(acc?.or(next?)))
+            .unwrap_or_else(|| Ok(Expr::val(1).eq(0)))?,
+        ElementPredicate::And(a, b) => {
+            element_condition(a, backend)?.and(element_condition(b, backend)?)
+        }
+        ElementPredicate::Or(a, b) => {
+            element_condition(a, backend)?.or(element_condition(b, backend)?)
+        }
+        ElementPredicate::Not(inner) => element_condition(inner, backend)?.not(),
+    })
+}
+
+/// The current array element, cast so it compares with `value`.
+///
+/// Postgres and `MySQL` expose elements as text, so numbers are cast to a numeric type;
+/// `SQLite`'s `json_each` keeps the JSON type.
+fn element(value: &ODataValue, backend: DbBackend) -> SimpleExpr {
+    let col = Expr::col((Alias::new(ELEMENTS), Alias::new("value")));
+    match (backend, value) {
+        (DbBackend::Postgres, ODataValue::Number(_)) => col.cast_as(Alias::new("numeric")),
+        (DbBackend::MySql, ODataValue::Number(_)) => {
+            Expr::cust_with_expr("CAST(? AS DECIMAL(65, 10))", col)
+        }
+        _ => col.into(),
+    }
+}
+
+/// Booleans are compared as `'true'`/`'false'` text where elements are text.
+fn element_value(value: &ODataValue, backend: DbBackend) -> ODataValue {
+    match (backend, value) {
+        (DbBackend::Postgres | DbBackend::MySql, ODataValue::Bool(b)) => {
+            ODataValue::String(b.to_string())
+        }
+        _ => value.clone(),
+    }
+}
@@ -17,6 +17,7 @@
 //!
 //! - `core`: Core `OData` to `SeaORM` translation (filters, cursors, ordering) - legacy `FieldMap` based
 //! - `sea_orm_filter`: Type-safe mapping from `FilterNode<F>` to `SeaORM` conditions
+//! - `functions`: Backend-specific SQL for filter functions (`indexof`, `year`, ...) and `any`/`all`
 //! - `pager`: Fluent builder for secure + `OData` pagination
 
 // Core OData functionality (legacy FieldMap-based)
@@ -25,6 +26,9 @@ mod core;
 // SeaORM-specific filter mapping
 pub mod sea_orm_filter;
 
+// Backend-specific SQL for filter functions and lambdas
+mod functions;
+
 // Fluent pagination builder
 pub mod pager;
 
@@ -34,5 +38,5 @@ pub use core::*;
 // Re-export SeaORM filter mapping and pagination
 pub use sea_orm_filter::{
     FieldToColumn, LimitCfg, ODataFieldMapping, encode_cursor_value, filter_node_to_condition,
-    paginate_odata, parse_cursor_value,
+    filter_node_to_condition_for, paginate_odata, parse_cursor_value, search_condition,
 };
@@ -12,10 +12,12 @@ use modkit_odata::filter::{
 };
 use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, Page, PageInfo, SortDir};
 use sea_orm::{
-    Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    sea_query::{Expr, Order},
+    ColumnTrait, Condition, ConnectionTrait, DbBackend, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+    sea_query::{Asterisk, Expr, Func, LikeExpr, Order},
 };
 
+use crate::odata::functions::{compare, field_fn_expr, lambda_condition};
 use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
 
 /// Trait for mapping DTO filter fields to `SeaORM` columns.
@@ -92,6 +94,14 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
         field: F,
     ) -> sea_orm::Value;
 
+    /// Fields matched by `$search`.
+    ///
+    /// Defaults to none, in which case `$search` is rejected with `InvalidSearch`.
+    #[must_use]
+    fn search_fields() -> &'static [F] {
+        &[]
+    }
+
     /// Extract cursor values for all fields in an order.
     ///
     /// This is a convenience method that can be overridden for optimization,
@@ -120,6 +130,9 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
 /// all standard `OData` operations. Concrete modules only need to implement
 /// `FieldToColumn` to map their DTO fields to database columns.
 ///
+/// Nodes whose SQL depends on the database (`indexof`, `year`/`month`/`day`, and
+/// `any`/`all` lambdas) are rejected; use [`filter_node_to_condition_for`] for those.
+///
 /// # Type Parameters
 ///
 /// - `F`: The `FilterField` implementation (generated by `#[derive(ODataFilterable)]`)
@@ -144,6 +157,33 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
 /// # Errors
 /// Returns an error string if the filter contains unsupported operations or invalid values.
 pub fn filter_node_to_condition<F, M>(filter: &FilterNode<F>) -> Result<Condition, String>
+where
+    F: FilterField,
+    M: FieldToColumn<F>,
+{
+    node_to_condition::<F, M>(filter, None)
+}
+
+/// Like [`filter_node_to_condition`], generating backend-specific SQL for filter
+/// functions and lambdas.
+///
+/// # Errors
+/// Returns an error string if the filter contains unsupported operations or invalid values.
+pub fn filter_node_to_condition_for<F, M>(
+    filter: &FilterNode<F>,
+    backend: DbBackend,
+) -> Result<Condition, String>
+where
+    F: FilterField,
+    M: FieldToColumn<F>,
+{
+    node_to_condition::<F, M>(filter, Some(backend))
+}
+
+fn node_to_condition<F, M>(
+    filter: &FilterNode<F>,
+    backend: Option<DbBackend>,
+) -> Result<Condition, String>
 where
     F: FilterField,
     M: FieldToColumn<F>,
@@ -154,6 +194,40 @@ where
             let column = M::map_field(*field);
             build_binary_condition(column, *op, value)
         }
+        FilterNode::Function {
+            func,
+            field,
+            op,
+            value,
+        } => {
+            let operand = field_fn_expr(func, Expr::col(M::map_field(*field)).into(), backend)?;
+            Ok(Condition::all().add(compare(operand, *op, value)?))
+        }
+        FilterNode::In { field, values } => {
+            let column = M::map_field(*field);
+            if values.is_empty() {
+                // IN () → always false
+                return Ok(Condition::all().add(Expr::value(1).eq(0)));
+            }
+            let values = values
+                .iter()
+                .map(odata_value_to_sea_value)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Condition::all().add(Expr::col(column).is_in(values)))
+        }
+        FilterNode::Lambda {
+            quantifier,
+            field,
+            predicate,
+        } => {
+            let collection = Expr::col(M::map_field(*field)).into();
+            Ok(Condition::all().add(lambda_condition(
+                *quantifier,
+                collection,
+                predicate.as_deref(),
+                backend,
+            )?))
+        }
         FilterNode::Composite { op, children } => {
             // Combine child conditions with AND or OR
             let base = match op {
@@ -163,18 +237,26 @@ where
             };
 
             children.iter().try_fold(base, |acc, child| {
-                let child_cond = filter_node_to_condition::<F, M>(child)?;
+                let child_cond = node_to_condition::<F, M>(child, backend)?;
                 Ok(acc.add(child_cond))
             })
         }
         FilterNode::Not(inner) => {
             // FIXED: Call .not() AFTER adding the inner condition
-            let inner_cond = filter_node_to_condition::<F, M>(inner)?;
+            let inner_cond = node_to_condition::<F, M>(inner, backend)?;
             Ok(Condition::all().add(inner_cond).not())
         }
     }
 }
 
+/// Database backend behind `conn`, used to pick backend-specific filter SQL.
+pub(crate) fn backend_of<C: DBRunner>(conn: &C) -> DbBackend {
+    match DBRunnerInternal::as_seaorm_read(conn) {
+        SeaOrmRunner::Conn(db) => db.get_database_backend(),
+        SeaOrmRunner::Tx(tx) => tx.get_database_backend(),
+    }
+}
+
 /// Build a binary condition (field op value) for `SeaORM`.
 ///
 /// This handles all comparison and string function operations.
@@ -278,6 +360,51 @@ pub fn escape_like(s: &str) -> String {
     out
 }
 
+/// Build a case-insensitive `$search` condition over `columns`.
+///
+/// The search text is split on whitespace; every term must match (`LIKE '%term%'`)
+/// at least one of the columns. Returns `None` if the text has no terms.
+pub fn search_condition<C: ColumnTrait>(columns: &[C], search: &str) -> Option<Condition> {
+    let mut all = Condition::all();
+    let mut has_terms = false;
+    for term in search.split_whitespace() {
+        let pattern = format!("%{}%", escape_like(&term.to_lowercase()));
+        let any = columns.iter().fold(Condition::any(), |acc, column| {
+            acc.add(
+                Expr::expr(Func::lower(Expr::col(*column)))
+                    .like(LikeExpr::new(pattern.clone()).escape('\\')),
+            )
+        });
+        all = all.add(any);
+        has_terms = true;
+    }
+    has_terms.then_some(all)
+}
+
+/// Run `SELECT COUNT(*)` over `select`, which must not carry ordering or limits.
+pub(crate) async fn count_rows<E, C>(
+    select: sea_orm::Select<E>,
+    conn: &C,
+) -> Result<u64, ODataError>
+where
+    E: EntityTrait,
+    C: DBRunner,
+{
+    let query = select
+        .select_only()
+        .column_as(Expr::expr(Func::count(Expr::col(Asterisk))), "total_count")
+        .into_tuple::<i64>();
+
+    #[allow(clippy::disallowed_methods)]
+    let total = match DBRunnerInternal::as_seaorm_read(conn) {
+        SeaOrmRunner::Conn(db) => query.one(db).await,
+        SeaOrmRunner::Tx(tx) => query.one(tx).await,
+    }
+    .map_err(|e| ODataError::Db(e.to_string()))?;
+
+    Ok(total.map_or(0, |n| u64::try_from(n).unwrap_or_default()))
+}
+
 /// Encode a `sea_orm::Value` to a string for cursor storage.
 ///
 /// This converts `SeaORM` values to strings that can be embedded in cursors.
@@ -380,6 +507,7 @@ pub fn parse_cursor_value(kind: FieldKind, s: &str) -> Result<sea_orm::Value, St
                 .map_err(|_| "invalid decimal in cursor".to_owned())?;
             V::Decimal(Some(Box::new(d)))
         }
+        FieldKind::Json => return Err("JSON fields cannot be used in cursors".to_owned()),
     };
 
     Ok(result)
@@ -423,14 +551,18 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
 ///
 /// - `select`: Base `SeaORM` select query (already security-scoped if needed)
 /// - `conn`: Database connection
-/// - `query`: `OData` query with filter, order, cursor, and limit
+/// - `query`: `OData` query with filter, search, count, order, cursor, and limit
 /// - `tiebreaker`: Default orderby field and direction for stable pagination
 /// - `limit_cfg`: Default and maximum page sizes
 /// - `model_to_domain`: Function to convert entity models to domain types
 ///
 /// # Returns
 ///
-/// A Page containing the results and pagination metadata (next/prev cursors)
+/// A Page containing the results and pagination metadata (next/prev cursors).
+/// With `$count=true`, `page_info.total_count` holds the number of rows matching the
+/// filter and search, computed by a separate `COUNT(*)` query without cursor or limit.
+///
+/// `$search` matches the columns of `M::search_fields()`; see [`search_condition`].
 ///
 /// # Example
 ///
@@ -491,10 +623,31 @@ where
             .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
 
         s = s.filter(
-            filter_node_to_condition::<F, M>(&filter_node).map_err(ODataError::InvalidFilter)?,
+            filter_node_to_condition_for::<F, M>(&filter_node, backend_of(conn))
+                .map_err(ODataError::InvalidFilter)?,
         );
     }
 
+    // Apply free-text search across the declared search fields
+    if let Some(search) = query.search() {
+        let fields = M::search_fields();
+        if fields.is_empty() {
+            return Err(ODataError::InvalidSearch(
+                "$search is not supported for this resource".to_owned(),
+            ));
+        }
+        let columns: Vec<M::Column> = fields.iter().map(|f| M::map_field(*f)).collect();
+        if let Some(cond) = search_condition(&columns, search) {
+            s = s.filter(cond);
+        }
+    }
+
+    let total_count = if query.count {
+        Some(count_rows(s.clone(), conn).await?)
+    } else {
+        None
+    };
+
     let is_backward = query.cursor.as_ref().is_some_and(|c| c.d == "bwd");
 
     // Apply cursor predicate
@@ -524,7 +677,7 @@ where
     s = s.limit(fetch);
 
     #[allow(clippy::disallowed_methods)]
-    let mut rows = match DBRunnerInternal::as_seaorm(conn) {
+    let mut rows = match DBRunnerInternal::as_seaorm_read(conn) {
         SeaOrmRunner::Conn(db) => s.all(db).await,
         SeaOrmRunner::Tx(tx) => s.all(tx).await,
     }
@@ -587,6 +740,7 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count,
         },
     })
 }
@@ -0,0 +1,556 @@
+//! Transactional outbox.
+//!
+//! Domain events are written to the `modkit_outbox` table inside the same transaction as
+//! the state change that produced them, so a crash after commit can no longer lose an
+//! event. Each event belongs to the module that enqueued it; an [`OutboxRelay`] for that
+//! module later publishes its stored events to an [`OutboxSink`] and marks them published:
+//!
+//! - delivery is **at least once**: a crash between publish and mark re-publishes the event;
+//! - events are published in enqueue order; a failing event blocks the ones after it until
+//!   it succeeds, its `attempts` counter and `last_error` recording each failure. After
+//!   [`OutboxRelayConfig::max_attempts`] failures it is dead-lettered (`dead_lettered_at`
+//!   is set) and the relay moves on; clearing `dead_lettered_at` requeues it. An event
+//!   whose stored payload is not valid JSON is dead-lettered without being published;
+//! - only one relay per module publishes at a time: it holds a lease row in
+//!   `modkit_outbox_lease` that it renews on every poll. If the holder disappears, another
+//!   relay takes over once the lease has been expired for [`OutboxRelayConfig::lease_ttl`].
+//!
+//! # Setup
+//!
+//! Add [`migration()`] to the module's migrations; the tables are then created and
+//! recorded by [`crate::migration_runner`] like any other module migration:
+//!
+//! ```ignore
+//! fn migrations(&self) -> Vec<Box<dyn MigrationTrait>> {
+//!     vec![Box::new(m001_init::Migration), modkit_db::outbox::migration()]
+//! }
+//! ```
+//!
+//! # Enqueue
+//!
+//! ```ignore
+//! let (db, res) = db.transaction_with_config(TxConfig::default(), |tx| {
+//!     Box::pin(async move {
+//!         repo.update_settings(tx, &scope, &settings).await?;
+//!         tx.enqueue("settings", "settings.updated", &SettingsUpdated { user_id }).await?;
+//!         Ok(())
+//!     })
+//! }).await;
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, Index};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    Set, SqlErr,
+};
+use sea_orm_migration::prelude::{
+    Alias, ColumnDef, DbErr, MigrationName, MigrationTrait, SchemaManager, Table,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+use crate::{Db, DbError, DbTx};
+
+/// Name of the outbox table.
+pub const OUTBOX_TABLE: &str = "modkit_outbox";
+
+/// Name of the table holding one relay lease per module.
+pub const OUTBOX_LEASE_TABLE: &str = "modkit_outbox_lease";
+
+mod entity {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "modkit_outbox")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub module: String,
+        pub topic: String,
+        pub payload: String,
+        pub created_at: ChronoDateTimeUtc,
+        pub attempts: i32,
+        pub published_at: Option<ChronoDateTimeUtc>,
+        pub dead_lettered_at: Option<ChronoDateTimeUtc>,
+        pub last_error: Option<String>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod lease {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "modkit_outbox_lease")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub module: String,
+        pub owner: String,
+        pub locked_until: ChronoDateTimeUtc,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+// --------------------------- Migration ---------------------------------------
+
+/// Migration creating the outbox and lease tables.
+///
+/// Idempotent (`IF NOT EXISTS`), so modules sharing a database can all list it. For the
+/// same reason `down` leaves the tables in place: other modules may still use them.
+pub struct OutboxMigration;
+
+impl MigrationName for OutboxMigration {
+    fn name(&self) -> &'static str {
+        "m0000_modkit_outbox"
+    }
+}
+
+#[async_trait]
+impl MigrationTrait for OutboxMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(OUTBOX_TABLE))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("module")).string().not_null())
+                    .col(ColumnDef::new(Alias::new("topic")).string().not_null())
+                    .col(ColumnDef::new(Alias::new("payload")).text().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("created_at"))
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new("attempts"))
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(Alias::new("published_at")).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Alias::new("dead_lettered_at")).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Alias::new("last_error")).text())
+                    .to_owned(),
+            )
+            .await?;
+        // Serves the relay's pending scan: one module, unpublished, in id order.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_modkit_outbox_module_pending")
+                    .table(Alias::new(OUTBOX_TABLE))
+                    .col(Alias::new("module"))
+                    .col(Alias::new("published_at"))
+                    .col(Alias::new("id"))
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(OUTBOX_LEASE_TABLE))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new("module"))
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("owner")).string().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("locked_until"))
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
+
+/// The outbox migration, ready to append to a module's migration list.
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(OutboxMigration)
+}
+
+// --------------------------- Enqueue -----------------------------------------
+
+impl DbTx<'_> {
+    /// Store an event of `module` in the outbox as part of this transaction.
+    ///
+    /// The event becomes visible to the module's relay only if the transaction commits.
+    /// Returns the event id.
+    ///
+    /// # Errors
+    /// Returns an error if `payload` cannot be serialized to JSON or the insert fails.
+    pub async fn enqueue<P: Serialize + Sync>(
+        &self,
+        module: &str,
+        topic: &str,
+        payload: &P,
+    ) -> Result<i64, DbError> {
+        let payload = serde_json::to_string(payload)
+            .map_err(|e| DbError::InvalidParameter(format!("outbox payload for '{topic}': {e}")))?;
+        let row = entity::ActiveModel {
+            module: Set(module.to_owned()),
+            topic: Set(topic.to_owned()),
+            payload: Set(payload),
+            created_at: Set(Utc::now()),
+            attempts: Set(0),
+            published_at: Set(None),
+            dead_lettered_at: Set(None),
+            last_error: Set(None),
+            ..Default::default()
+        }
+        .insert(self.tx)
+        .await?;
+        Ok(row.id)
+    }
+}
+
+// --------------------------- Sinks -------------------------------------------
+
+/// An event read from the outbox, as handed to an [`OutboxSink`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEvent {
+    /// Outbox id; increases in enqueue order and doubles as an idempotency key.
+    pub id: i64,
+    /// Module that enqueued the event.
+    pub module: String,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    /// Failed publish attempts so far.
+    pub attempts: i32,
+}
+
+/// Destination the relay publishes outbox events to.
+///
+/// Because delivery is at least once, consumers should deduplicate on [`OutboxEvent::id`].
+#[async_trait]
+pub trait OutboxSink: Send + Sync {
+    /// Publish one event. An error leaves the event in the outbox for a later retry.
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()>;
+}
+
+/// In-process sink fanning events out over a `tokio::sync::broadcast` channel.
+///
+/// Publishing succeeds even without subscribers; lagging subscribers lose the oldest
+/// events, like any broadcast channel.
+#[derive(Debug, Clone)]
+pub struct BroadcastSink {
+    tx: broadcast::Sender<OutboxEvent>,
+}
+
+impl BroadcastSink {
+    /// Create a sink whose channel buffers up to `capacity` events per subscriber.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Receive the events published from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<OutboxEvent> {
+        self.tx.subscribe()
+    }
+}
+
+#[async_trait]
+impl OutboxSink for BroadcastSink {
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        // No subscribers is not a failure: there is nobody to deliver to.
+        _ = self.tx.send(event.clone());
+        Ok(())
+    }
+}
+
+// --------------------------- Relay -------------------------------------------
+
+/// Relay tuning.
+#[derive(Debug, Clone)]
+pub struct OutboxRelayConfig {
+    /// Delay between polls when the outbox is drained or the lease is held elsewhere.
+    pub poll_interval: Duration,
+    /// Maximum number of events published per poll.
+    pub batch_size: u64,
+    /// How long a relay's lease lasts without renewal; it must exceed the time needed to
+    /// publish one batch. Another relay takes over once it has expired.
+    pub lease_ttl: Duration,
+    /// Failed publishes after which an event is dead-lettered.
+    pub max_attempts: u32,
+}
+
+impl Default for OutboxRelayConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 100,
+            lease_ttl: Duration::from_secs(30),
+            max_attempts: 10,
+        }
+    }
+}
+
+/// Background publisher moving one module's committed outbox events to a sink.
+///
+/// Run it with [`OutboxRelay::run`]; `modkit` also implements `Runnable` for it so it can
+/// be registered as a module's background task.
+pub struct OutboxRelay {
+    db: Db,
+    module: String,
+    /// Lease owner id of this relay instance.
+    owner: String,
+    sink: Arc<dyn OutboxSink>,
+    config: OutboxRelayConfig,
+}
+
+impl std::fmt::Debug for OutboxRelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutboxRelay")
+            .field("db", &self.db)
+            .field("module", &self.module)
+            .field("owner", &self.owner)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OutboxRelay {
+    /// Relay publishing the events enqueued by `module`.
+    #[must_use]
+    pub fn new(db: Db, module: impl Into<String>, sink: Arc<dyn OutboxSink>) -> Self {
+        Self {
+            db,
+            module: module.into(),
+            owner: uuid::Uuid::new_v4().to_string(),
+            sink,
+            config: OutboxRelayConfig::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_config(mut self, config: OutboxRelayConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Publish one batch of the module's pending events.
+    ///
+    /// Takes or renews the module's lease first. Returns the number of events published;
+    /// `0` if the outbox is drained or another relay holds the lease.
+    ///
+    /// # Errors
+    /// Returns an error if the lease or the outbox table cannot be accessed. Sink failures
+    /// are recorded on the event and are not errors.
+    pub async fn relay_once(&self) -> Result<usize, DbError> {
+        if !self.acquire_lease().await? {
+            return Ok(0);
+        }
+        self.publish_pending().await
+    }
+
+    /// Take the module's lease if it is free or expired, or renew it if this relay holds it.
+    async fn acquire_lease(&self) -> Result<bool, DbError> {
+        let conn = self.db.sea_internal();
+        let now = Utc::now();
+        let locked_until = chrono::Duration::from_std(self.config.lease_ttl)
+            .ok()
+            .and_then(|ttl| now.checked_add_signed(ttl))
+            .ok_or_else(|| {
+                DbError::InvalidConfig(format!(
+                    "outbox lease_ttl {:?} is out of range",
+                    self.config.lease_ttl
+                ))
+            })?;
+
+        // A single conditional UPDATE, so two relays cannot both take an expired lease.
+        let taken = lease::Entity::update_many()
+            .col_expr(lease::Column::Owner, Expr::value(self.owner.clone()))
+            .col_expr(lease::Column::LockedUntil, Expr::value(locked_until))
+            .filter(lease::Column::Module.eq(self.module.as_str()))
+            .filter(
+                Condition::any()
+                    .add(lease::Column::Owner.eq(self.owner.as_str()))
+                    .add(lease::Column::LockedUntil.lt(now)),
+            )
+            .exec(&conn)
+            .await?;
+        if taken.rows_affected > 0 {
+            return Ok(true);
+        }
+
+        // No lease row yet: the first relay to insert it wins.
+        let row = lease::ActiveModel {
+            module: Set(self.module.clone()),
+            owner: Set(self.owner.clone()),
+            locked_until: Set(locked_until),
+        };
+        match lease::Entity::insert(row)
+            .exec_without_returning(&conn)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Give up the lease so another relay can take over without waiting for it to expire.
+    async fn release_lease(&self) -> Result<(), DbError> {
+        lease::Entity::update_many()
+            .col_expr(lease::Column::LockedUntil, Expr::value(Utc::now()))
+            .filter(lease::Column::Module.eq(self.module.as_str()))
+            .filter(lease::Column::Owner.eq(self.owner.as_str()))
+            .exec(&self.db.sea_internal())
+            .await?;
+        Ok(())
+    }
+
+    async fn publish_pending(&self) -> Result<usize, DbError> {
+        // Always the primary: a lagging replica would re-publish delivered events.
+        let conn = self.db.sea_internal();
+        let pending = entity::Entity::find()
+            .filter(entity::Column::Module.eq(self.module.as_str()))
+            .filter(entity::Column::PublishedAt.is_null())
+            .filter(entity::Column::DeadLetteredAt.is_null())
+            .order_by_asc(entity::Column::Id)
+            .limit(self.config.batch_size)
+            .all(&conn)
+            .await?;
+
+        let mut published = 0;
+        for row in pending {
+            let payload = match serde_json::from_str(&row.payload) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    // Retrying cannot fix a malformed payload.
+                    let error = anyhow::Error::new(e).context("invalid event payload");
+                    self.record_failure(&row, &error, true).await?;
+                    continue;
+                }
+            };
+            let event = OutboxEvent {
+                id: row.id,
+                module: row.module.clone(),
+                topic: row.topic.clone(),
+                payload,
+                created_at: row.created_at,
+                attempts: row.attempts,
+            };
+            match self.sink.publish(&event).await {
+                Ok(()) => {
+                    entity::Entity::update_many()
+                        .col_expr(entity::Column::PublishedAt, Expr::value(Utc::now()))
+                        .filter(entity::Column::Id.eq(event.id))
+                        .exec(&conn)
+                        .await?;
+                    published += 1;
+                }
+                Err(e) => {
+                    if !self.record_failure(&row, &e, false).await? {
+                        // Keep order: later events wait for this one.
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(published)
+    }
+
+    /// Count a failed publish of `event`; returns `true` if it was dead-lettered.
+    ///
+    /// `permanent` failures are dead-lettered right away.
+    async fn record_failure(
+        &self,
+        event: &entity::Model,
+        error: &anyhow::Error,
+        permanent: bool,
+    ) -> Result<bool, DbError> {
+        let attempts = event.attempts.saturating_add(1);
+        let dead = permanent || u32::try_from(attempts).unwrap_or(0) >= self.config.max_attempts;
+        let mut update = entity::Entity::update_many()
+            .col_expr(entity::Column::Attempts, Expr::value(attempts))
+            .col_expr(entity::Column::LastError, Expr::value(format!("{error:#}")))
+            .filter(entity::Column::Id.eq(event.id));
+        if dead {
+            tracing::error!(
+                id = event.id,
+                module = %event.module,
+                topic = %event.topic,
+                attempts,
+                error = %format!("{error:#}"),
+                "Outbox event cannot be published; dead-lettering it"
+            );
+            update = update.col_expr(entity::Column::DeadLetteredAt, Expr::value(Utc::now()));
+        } else {
+            tracing::warn!(
+                id = event.id,
+                module = %event.module,
+                topic = %event.topic,
+                attempts,
+                error = %error,
+                "Outbox publish failed; will retry"
+            );
+        }
+        update.exec(&self.db.sea_internal()).await?;
+        Ok(dead)
+    }
+
+    /// Relay events until `cancel` fires, then release the lease.
+    ///
+    /// Full batches are followed immediately by the next poll; otherwise the relay
+    /// sleeps for `poll_interval`. Errors are logged and retried on the next poll.
+    pub async fn run(&self, cancel: CancellationToken) {
+        let full_batch = usize::try_from(self.config.batch_size).unwrap_or(usize::MAX);
+        loop {
+            let published = match self.relay_once().await {
+                Ok(n) => n,
+                Err(e) => {
+                    tracing::warn!(module = %self.module, error = %e, "Outbox relay poll failed");
+                    0
+                }
+            };
+            if published >= full_batch && !cancel.is_cancelled() {
+                continue;
+            }
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                () = tokio::time::sleep(self.config.poll_interval) => {}
+            }
+        }
+        if let Err(e) = self.release_lease().await {
+            tracing::warn!(module = %self.module, error = %e, "Failed to release outbox lease");
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Read-replica routing.
+//!
+//! A [`Db`] built from a config with `replicas` keeps one pool per replica next to the
+//! primary pool:
+//!
+//! - secure SELECTs on a [`DbConn`](crate::DbConn) and `TxConfig::read_only()`
+//!   transactions are served by the replicas, round-robin;
+//! - inserts, updates, deletes and read-write transactions go to the primary;
+//! - with a `read_your_writes` window, a successful write pins the reads of the tokio task
+//!   (or, outside a task, the thread) that made it to the primary until the window has
+//!   passed, hiding replica lag from the writer. Other tasks keep reading from the replicas.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+use tokio::task;
+
+use sea_orm::DatabaseConnection;
+
+use crate::config::{DbConnConfig, GlobalDatabaseConfig};
+use crate::options::build_db_handle;
+use crate::{Db, DbError, DbHandle, Result};
+
+/// Whose reads a write pins to the primary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Writer {
+    Task(task::Id),
+    Thread(ThreadId),
+}
+
+impl Writer {
+    fn current() -> Self {
+        task::try_id().map_or_else(|| Self::Thread(thread::current().id()), Self::Task)
+    }
+}
+
+/// Replica pools of a [`Db`] and the read-your-writes bookkeeping.
+#[derive(Debug)]
+pub struct Replicas {
+    handles: Vec<DbHandle>,
+    next: AtomicUsize,
+    read_your_writes: Option<Duration>,
+    /// Writers inside their read-your-writes window, with the time of their last write.
+    writers: Mutex<HashMap<Writer, Instant>>,
+}
+
+impl Replicas {
+    pub fn new(handles: Vec<DbHandle>, read_your_writes: Option<Duration>) -> Self {
+        Self {
+            handles,
+            next: AtomicUsize::new(0),
+            read_your_writes,
+            writers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Connection to serve a read from, or `None` if the read must go to the primary.
+    pub fn pick(&self) -> Option<&DatabaseConnection> {
+        if self.handles.is_empty() || self.within_write_window() {
+            return None;
+        }
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.handles.len();
+        Some(self.handles[i].sea_internal_ref())
+    }
+
+    /// Record a successful write on the primary, opening the read-your-writes window of
+    /// the current task.
+    pub fn note_write(&self) {
+        let Some(window) = self.read_your_writes else {
+            return;
+        };
+        let now = Instant::now();
+        let mut writers = self.writers.lock().unwrap_or_else(PoisonError::into_inner);
+        writers.retain(|_, at| now.duration_since(*at) < window);
+        writers.insert(Writer::current(), now);
+    }
+
+    fn within_write_window(&self) -> bool {
+        let Some(window) = self.read_your_writes else {
+            return false;
+        };
+        self.writers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&Writer::current())
+            .is_some_and(|at| at.elapsed() < window)
+    }
+}
+
+/// Fill the fields a replica inherits from its primary.
+///
+/// The replica's location (`dsn`, `host`, `port`, `file`, `path`) is never inherited.
+fn inherit_from_primary(mut replica: DbConnConfig, primary: &DbConnConfig) -> DbConnConfig {
+    if replica.engine.is_none() {
+        replica.engine = primary.engine;
+    }
+    if replica.user.is_none() {
+        replica.user.clone_from(&primary.user);
+    }
+    if replica.password.is_none() {
+        replica.password.clone_from(&primary.password);
+    }
+    if replica.dbname.is_none() {
+        replica.dbname.clone_from(&primary.dbname);
+    }
+    match (&mut replica.params, &primary.params) {
+        (Some(own), Some(inherited)) => {
+            for (key, value) in inherited {
+                own.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        (None, Some(inherited)) => replica.params = Some(inherited.clone()),
+        _ => {}
+    }
+    if replica.pool.is_none() {
+        replica.pool.clone_from(&primary.pool);
+    }
+    replica
+}
+
+/// Build a [`Db`] for `cfg`, connecting its read replicas if it lists any.
+///
+/// # Errors
+/// Returns an error if any connection fails, a replica nests `replicas` or
+/// `read_your_writes`, or a replica uses a different engine than the primary.
+pub async fn build_routed_db(
+    mut cfg: DbConnConfig,
+    global: Option<&GlobalDatabaseConfig>,
+) -> Result<Db> {
+    let replica_cfgs = cfg.replicas.take().unwrap_or_default();
+    let read_your_writes = cfg.read_your_writes.take();
+    if replica_cfgs.is_empty() {
+        return Ok(Db::new(build_db_handle(cfg, global).await?));
+    }
+
+    let primary_cfg = cfg.clone();
+    let primary = build_db_handle(cfg, global).await?;
+
+    let mut handles = Vec::with_capacity(replica_cfgs.len());
+    for (i, replica) in replica_cfgs.into_iter().enumerate() {
+        if replica.replicas.is_some() || replica.read_your_writes.is_some() {
+            return Err(DbError::InvalidConfig(format!(
+                "replica #{i}: 'replicas' and 'read_your_writes' are only allowed on the primary"
+            )));
+        }
+        let handle = build_db_handle(inherit_from_primary(replica, &primary_cfg), global).await?;
+        if handle.engine() != primary.engine() {
+            return Err(DbError::InvalidConfig(format!(
+                "replica #{i}: engine {:?} does not match primary engine {:?}",
+                handle.engine(),
+                primary.engine()
+            )));
+        }
+        handles.push(handle);
+    }
+
+    Ok(Db::with_replicas(
+        primary,
+        Replicas::new(handles, read_your_writes),
+    ))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn replica_inherits_credentials_but_not_location() {
+        let primary = DbConnConfig {
+            host: Some("primary".to_owned()),
+            user: Some("app".to_owned()),
+            password: Some("secret".to_owned()),
+            dbname: Some("app_db".to_owned()),
+            params: Some(HashMap::from([
+                ("sslmode".to_owned(), "require".to_owned()),
+                ("application_name".to_owned(), "primary".to_owned()),
+            ])),
+            ..Default::default()
+        };
+        let replica = DbConnConfig {
+            host: Some("replica".to_owned()),
+            params: Some(HashMap::from([(
+                "application_name".to_owned(),
+                "replica".to_owned(),
+            )])),
+            ..Default::default()
+        };
+
+        let merged = inherit_from_primary(replica, &primary);
+        assert_eq!(merged.host.as_deref(), Some("replica"));
+        assert_eq!(merged.user.as_deref(), Some("app"));
+        assert_eq!(merged.password.as_deref(), Some("secret"));
+        assert_eq!(merged.dbname.as_deref(), Some("app_db"));
+        let params = merged.params.unwrap();
+        assert_eq!(params["sslmode"], "require");
+        assert_eq!(params["application_name"], "replica");
+    }
+
+    #[tokio::test]
+    async fn write_window_pins_reads_of_the_writing_task() {
+        let replicas =
+            std::sync::Arc::new(Replicas::new(Vec::new(), Some(Duration::from_secs(60))));
+        assert!(!replicas.within_write_window());
+        replicas.note_write();
+        assert!(replicas.within_write_window());
+
+        let other = replicas.clone();
+        let other_task = tokio::spawn(async move { other.within_write_window() });
+        assert!(!other_task.await.unwrap());
+
+        let no_window = Replicas::new(Vec::new(), None);
+        no_window.note_write();
+        assert!(!no_window.within_write_window());
+    }
+}
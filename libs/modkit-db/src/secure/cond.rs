@@ -1,7 +1,14 @@
-use sea_orm::{ColumnTrait, Condition, EntityTrait, sea_query::Expr};
+use sea_orm::sea_query::{Alias, Expr, Query, SelectStatement, SimpleExpr};
+use sea_orm::{ColumnTrait, Condition, EntityTrait};
 
+use crate::secure::projections::{
+    RESOURCE_GROUP_CLOSURE, RESOURCE_GROUP_MEMBERSHIP, TENANT_CLOSURE,
+};
 use crate::secure::{AccessScope, ScopableEntity};
-use modkit_security::access_scope::{ScopeConstraint, ScopeFilter, ScopeValue};
+use modkit_security::access_scope::{
+    CompareOp, GroupScopeFilter, GroupSubtreeScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue,
+    TenantSubtreeScopeFilter,
+};
 
 /// Convert a [`ScopeValue`] to a `sea_query::SimpleExpr` for SQL binding.
 fn scope_value_to_sea_expr(v: &ScopeValue) -> sea_orm::sea_query::SimpleExpr {
@@ -10,6 +17,7 @@ fn scope_value_to_sea_expr(v: &ScopeValue) -> sea_orm::sea_query::SimpleExpr {
         ScopeValue::String(s) => Expr::value(s.clone()),
         ScopeValue::Int(n) => Expr::value(*n),
         ScopeValue::Bool(b) => Expr::value(*b),
+        ScopeValue::Timestamp(t) => Expr::value(*t),
     }
 }
 
@@ -22,6 +30,7 @@ fn scope_values_to_sea_values(values: &[ScopeValue]) -> Vec<sea_orm::Value> {
             ScopeValue::String(s) => sea_orm::Value::from(s.clone()),
             ScopeValue::Int(n) => sea_orm::Value::from(*n),
             ScopeValue::Bool(b) => sea_orm::Value::from(*b),
+            ScopeValue::Timestamp(t) => sea_orm::Value::from(*t),
         })
         .collect()
 }
@@ -92,19 +101,87 @@ where
     }
     let mut and_cond = Condition::all();
     for filter in constraint.filters() {
-        let col = E::resolve_property(filter.property())?;
-        match filter {
-            ScopeFilter::Eq(eq) => {
-                let expr = scope_value_to_sea_expr(eq.value());
-                and_cond = and_cond.add(Expr::col(col).eq(expr));
-            }
-            ScopeFilter::In(inf) => {
-                let sea_values = scope_values_to_sea_values(inf.values());
-                and_cond = and_cond.add(Expr::col(col).is_in(sea_values));
+        and_cond = and_cond.add(build_filter_expr::<E>(filter)?);
+    }
+    Some(and_cond)
+}
+
+/// Build SQL for a single filter.
+///
+/// Returns `None` if the filter references an unknown property (fail-closed).
+fn build_filter_expr<E>(filter: &ScopeFilter) -> Option<SimpleExpr>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    if let ScopeFilter::Not(inner) = filter {
+        return build_filter_expr::<E>(inner).map(SimpleExpr::not);
+    }
+
+    let col = E::resolve_property(filter.property())?;
+    let expr = match filter {
+        ScopeFilter::Eq(eq) => Expr::col(col).eq(scope_value_to_sea_expr(eq.value())),
+        ScopeFilter::In(inf) => Expr::col(col).is_in(scope_values_to_sea_values(inf.values())),
+        ScopeFilter::Compare(cmp) => {
+            let value = scope_value_to_sea_expr(cmp.value());
+            match cmp.op() {
+                CompareOp::Gt => Expr::col(col).gt(value),
+                CompareOp::Gte => Expr::col(col).gte(value),
+                CompareOp::Lt => Expr::col(col).lt(value),
+                CompareOp::Lte => Expr::col(col).lte(value),
             }
         }
+        ScopeFilter::InTenantSubtree(f) => Expr::col(col).in_subquery(tenant_subtree_query(f)),
+        ScopeFilter::InGroup(f) => Expr::col(col).in_subquery(group_members_query(f)),
+        ScopeFilter::InGroupSubtree(f) => {
+            Expr::col(col).in_subquery(group_subtree_members_query(f))
+        }
+        ScopeFilter::Not(_) => unreachable!("handled above"),
+    };
+    Some(expr)
+}
+
+/// `SELECT descendant_id FROM tenant_closure WHERE ancestor_id = ?
+///  [AND barrier = 0] [AND descendant_status IN (...)]`
+fn tenant_subtree_query(f: &TenantSubtreeScopeFilter) -> SelectStatement {
+    let mut query = Query::select();
+    query
+        .column(Alias::new("descendant_id"))
+        .from(Alias::new(TENANT_CLOSURE))
+        .and_where(Expr::col(Alias::new("ancestor_id")).eq(f.root_tenant_id()));
+    if f.respect_barriers() {
+        query.and_where(Expr::col(Alias::new("barrier")).eq(0));
     }
-    Some(and_cond)
+    if !f.tenant_status().is_empty() {
+        query.and_where(
+            Expr::col(Alias::new("descendant_status")).is_in(f.tenant_status().iter().cloned()),
+        );
+    }
+    query
+}
+
+/// `SELECT resource_id FROM resource_group_membership WHERE group_id IN (...)`
+fn group_members_query(f: &GroupScopeFilter) -> SelectStatement {
+    Query::select()
+        .column(Alias::new("resource_id"))
+        .from(Alias::new(RESOURCE_GROUP_MEMBERSHIP))
+        .and_where(Expr::col(Alias::new("group_id")).is_in(f.group_ids().iter().copied()))
+        .to_owned()
+}
+
+/// `SELECT resource_id FROM resource_group_membership WHERE group_id IN
+///  (SELECT descendant_id FROM resource_group_closure WHERE ancestor_id = ?)`
+fn group_subtree_members_query(f: &GroupSubtreeScopeFilter) -> SelectStatement {
+    let groups = Query::select()
+        .column(Alias::new("descendant_id"))
+        .from(Alias::new(RESOURCE_GROUP_CLOSURE))
+        .and_where(Expr::col(Alias::new("ancestor_id")).eq(f.root_group_id()))
+        .to_owned();
+    Query::select()
+        .column(Alias::new("resource_id"))
+        .from(Alias::new(RESOURCE_GROUP_MEMBERSHIP))
+        .and_where(Expr::col(Alias::new("group_id")).in_subquery(groups))
+        .to_owned()
 }
 
 #[cfg(test)]
@@ -266,4 +343,99 @@ mod tests {
             "Expected a real condition, got deny-all: {cond_str}"
         );
     }
+
+    // --- Extended filter SQL ---
+
+    fn where_sql(scope: &AccessScope) -> String {
+        use sea_orm::{DbBackend, QueryFilter, QueryTrait};
+        custom_prop_entity::Entity::find()
+            .filter(build_scope_condition::<custom_prop_entity::Entity>(scope))
+            .build(DbBackend::Postgres)
+            .to_string()
+    }
+
+    #[test]
+    fn test_compare_filters_produce_range_sql() {
+        let scope = AccessScope::single(ScopeConstraint::new(vec![
+            ScopeFilter::compare("department_id", CompareOp::Gte, 10_i64),
+            ScopeFilter::compare("department_id", CompareOp::Lt, 20_i64),
+        ]));
+        let sql = where_sql(&scope);
+        assert!(
+            sql.contains(r#""department_id" >= 10 AND "department_id" < 20"#),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_tenant_subtree_uses_closure_table() {
+        let root = uuid::Uuid::new_v4();
+        let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::InTenantSubtree(
+            TenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, root)
+                .with_tenant_status(vec!["active".to_owned()]),
+        )]));
+        let sql = where_sql(&scope);
+        assert!(
+            sql.contains(&format!(
+                r#""tenant_id" IN (SELECT "descendant_id" FROM "tenant_closure" WHERE "ancestor_id" = '{root}' AND "barrier" = 0 AND "descendant_status" IN ('active'))"#
+            )),
+            "{sql}"
+        );
+
+        let ignore_barriers =
+            AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::InTenantSubtree(
+                TenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, root)
+                    .with_barriers(false),
+            )]));
+        let sql = where_sql(&ignore_barriers);
+        assert!(!sql.contains("barrier"), "{sql}");
+        assert!(!sql.contains("descendant_status"), "{sql}");
+    }
+
+    #[test]
+    fn test_group_filters_use_membership_tables() {
+        let g = uuid::Uuid::new_v4();
+        let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::in_group(
+            pep_properties::RESOURCE_ID,
+            vec![g],
+        )]));
+        let sql = where_sql(&scope);
+        assert!(
+            sql.contains(&format!(
+                r#""id" IN (SELECT "resource_id" FROM "resource_group_membership" WHERE "group_id" IN ('{g}'))"#
+            )),
+            "{sql}"
+        );
+
+        let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::in_group_subtree(
+            pep_properties::RESOURCE_ID,
+            g,
+        )]));
+        let sql = where_sql(&scope);
+        assert!(
+            sql.contains(&format!(
+                r#""group_id" IN (SELECT "descendant_id" FROM "resource_group_closure" WHERE "ancestor_id" = '{g}')"#
+            )),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_not_filter_negates_inner_condition() {
+        let tid = uuid::Uuid::new_v4();
+        let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::negate(
+            ScopeFilter::eq(pep_properties::OWNER_TENANT_ID, tid),
+        )]));
+        let sql = where_sql(&scope);
+        assert!(
+            sql.contains(&format!(r#"NOT "tenant_id" = '{tid}'"#)),
+            "{sql}"
+        );
+
+        // Unknown property inside NOT still fails closed.
+        let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::negate(
+            ScopeFilter::eq("nonexistent", tid),
+        )]));
+        assert!(where_sql(&scope).contains("WHERE FALSE"));
+    }
 }
@@ -43,10 +43,13 @@
 
 use std::{cell::Cell, future::Future, pin::Pin, sync::Arc};
 
+use modkit_security::SecurityContext;
 use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
+use uuid::Uuid;
 
-use super::tx_config::TxConfig;
+use super::tx_config::{TxAccessMode, TxConfig};
 use super::tx_error::TxError;
+use crate::replicas::Replicas;
 use crate::{DbError, DbHandle};
 
 // Task-local guard to detect transaction bypass attempts.
@@ -87,6 +90,13 @@ where
 /// Services and repositories must NOT store this type. They should receive
 /// `&impl DBRunner` as a parameter to all methods that need database access.
 ///
+/// # Read replicas
+///
+/// When built from a config with `replicas`, secure SELECTs on [`DbConn`] and
+/// [`TxConfig::read_only()`] transactions are served by the replicas; writes and
+/// read-write transactions use the primary. See `DbConnConfig::read_your_writes` for
+/// pinning reads to the primary after a write.
+///
 /// # Usage
 ///
 /// ```ignore
@@ -107,12 +117,15 @@ where
 #[derive(Clone)]
 pub struct Db {
     handle: Arc<DbHandle>,
+    replicas: Option<Arc<Replicas>>,
+    subject: Option<Uuid>,
 }
 
 impl std::fmt::Debug for Db {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Db")
             .field("engine", &self.handle.engine())
+            .field("replicas", &self.replica_count())
             .finish_non_exhaustive()
     }
 }
@@ -125,6 +138,48 @@ impl Db {
     pub(crate) fn new(handle: DbHandle) -> Self {
         Self {
             handle: Arc::new(handle),
+            replicas: None,
+            subject: None,
+        }
+    }
+
+    /// **INTERNAL**: Create a `Db` that routes reads to `replicas`.
+    #[must_use]
+    pub(crate) fn with_replicas(handle: DbHandle, replicas: Replicas) -> Self {
+        Self {
+            handle: Arc::new(handle),
+            replicas: Some(Arc::new(replicas)),
+            subject: None,
+        }
+    }
+
+    /// A handle whose runners act on behalf of the subject of `ctx`.
+    ///
+    /// Writes to audited entities record this subject, and fail through runners of a
+    /// handle without one. See [`crate::audit`].
+    #[must_use]
+    pub fn with_subject(&self, ctx: &SecurityContext) -> Self {
+        Self {
+            subject: Some(ctx.subject_id()),
+            ..self.clone()
+        }
+    }
+
+    /// **INTERNAL**: The primary `DbHandle` (engine and redacted DSN for logging).
+    pub(crate) fn primary(&self) -> &DbHandle {
+        &self.handle
+    }
+
+    /// Number of read replicas this handle routes reads to.
+    #[must_use]
+    pub fn replica_count(&self) -> usize {
+        self.replicas.as_ref().map_or(0, |r| r.len())
+    }
+
+    /// Record a committed write for the read-your-writes window.
+    fn note_write(&self) {
+        if let Some(replicas) = &self.replicas {
+            replicas.note_write();
         }
     }
 
@@ -175,6 +230,8 @@ impl Db {
         }
         Ok(DbConn {
             conn: self.handle.sea_internal_ref(),
+            replicas: self.replicas.as_deref(),
+            subject: self.subject,
         })
     }
 
@@ -227,7 +284,10 @@ impl Db {
         T: Send + 'static,
     {
         let txn = self.handle.sea_internal_ref().begin().await?;
-        let tx = DbTx { tx: &txn };
+        let tx = DbTx {
+            tx: &txn,
+            subject: self.subject,
+        };
 
         // Run the closure with the transaction guard set
         let res = with_tx_guard(f(&tx)).await;
@@ -235,6 +295,7 @@ impl Db {
         match res {
             Ok(v) => {
                 txn.commit().await?;
+                self.note_write();
                 Ok(v)
             }
             Err(e) => {
@@ -276,7 +337,10 @@ impl Db {
             .await
             .map_err(DbError::from)
             .map_err(E::from)?;
-        let tx = DbTx { tx: &txn };
+        let tx = DbTx {
+            tx: &txn,
+            subject: self.subject,
+        };
 
         // Run the closure with the transaction guard set
         let res = with_tx_guard(f(&tx)).await;
@@ -284,6 +348,7 @@ impl Db {
         match res {
             Ok(v) => {
                 txn.commit().await.map_err(DbError::from).map_err(E::from)?;
+                self.note_write();
                 Ok(v)
             }
             Err(e) => {
@@ -338,14 +403,20 @@ impl Db {
             Ok(t) => t,
             Err(e) => return (self, Err(e.into())),
         };
-        let tx = DbTx { tx: &txn };
+        let tx = DbTx {
+            tx: &txn,
+            subject: self.subject,
+        };
 
         // Run the closure with the transaction guard set
         let res = with_tx_guard(f(&tx)).await;
 
         match res {
             Ok(v) => match txn.commit().await {
-                Ok(()) => (self, Ok(v)),
+                Ok(()) => {
+                    self.note_write();
+                    (self, Ok(v))
+                }
                 Err(e) => (self, Err(e.into())),
             },
             Err(e) => {
@@ -389,14 +460,20 @@ impl Db {
             Err(e) => return (self, Err(TxError::Infra(InfraError::new(e.to_string())))),
         };
 
-        let tx = DbTx { tx: &txn };
+        let tx = DbTx {
+            tx: &txn,
+            subject: self.subject,
+        };
 
         // Run the closure with the transaction guard set
         let res = with_tx_guard(f(&tx)).await;
 
         match res {
             Ok(v) => match txn.commit().await {
-                Ok(()) => (self, Ok(v)),
+                Ok(()) => {
+                    self.note_write();
+                    (self, Ok(v))
+                }
                 Err(e) => (self, Err(TxError::Infra(InfraError::new(e.to_string())))),
             },
             Err(e) => {
@@ -408,6 +485,9 @@ impl Db {
 
     /// Execute a transaction with custom configuration (isolation level, access mode).
     ///
+    /// Read-only transactions ([`TxConfig::read_only()`]) run on a read replica when the
+    /// `Db` has replicas and no read-your-writes window is open.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -440,26 +520,36 @@ impl Db {
     {
         use sea_orm::{AccessMode, IsolationLevel};
 
+        let read_only = config.access_mode == Some(TxAccessMode::ReadOnly);
         let isolation: Option<IsolationLevel> = config.isolation.map(Into::into);
         let access_mode: Option<AccessMode> = config.access_mode.map(Into::into);
 
-        let txn = match self
-            .handle
-            .sea_internal_ref()
-            .begin_with_config(isolation, access_mode)
-            .await
-        {
+        let target = self
+            .replicas
+            .as_deref()
+            .filter(|_| read_only)
+            .and_then(Replicas::pick)
+            .unwrap_or_else(|| self.handle.sea_internal_ref());
+        let txn = match target.begin_with_config(isolation, access_mode).await {
             Ok(t) => t,
             Err(e) => return (self, Err(e.into())),
         };
-        let tx = DbTx { tx: &txn };
+        let tx = DbTx {
+            tx: &txn,
+            subject: self.subject,
+        };
 
         // Run the closure with the transaction guard set
         let res = with_tx_guard(f(&tx)).await;
 
         match res {
             Ok(v) => match txn.commit().await {
-                Ok(()) => (self, Ok(v)),
+                Ok(()) => {
+                    if !read_only {
+                        self.note_write();
+                    }
+                    (self, Ok(v))
+                }
                 Err(e) => (self, Err(e.into())),
             },
             Err(e) => {
@@ -493,6 +583,9 @@ impl Db {
 /// - Borrows from `Db`: While `DbConn` exists, the `Db` cannot start a transaction
 /// - Cannot be constructed by user code: Only `Db::conn()` creates it
 ///
+/// Secure SELECTs run on a read replica when the parent `Db` has any; writes run on the
+/// primary.
+///
 /// # Example
 ///
 /// ```ignore
@@ -506,6 +599,8 @@ impl Db {
 /// ```
 pub struct DbConn<'a> {
     pub(crate) conn: &'a DatabaseConnection,
+    pub(crate) replicas: Option<&'a Replicas>,
+    pub(crate) subject: Option<Uuid>,
 }
 
 impl std::fmt::Debug for DbConn<'_> {
@@ -541,6 +636,7 @@ impl std::fmt::Debug for DbConn<'_> {
 /// ```
 pub struct DbTx<'a> {
     pub(crate) tx: &'a DatabaseTransaction,
+    pub(crate) subject: Option<Uuid>,
 }
 
 impl std::fmt::Debug for DbTx<'_> {
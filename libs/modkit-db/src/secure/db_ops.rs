@@ -1,10 +1,14 @@
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, InsertResult, IntoActiveModel, ModelTrait,
-    QueryFilter,
-    sea_query::{IntoIden, OnConflict, SimpleExpr},
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, InsertResult, IntoActiveModel,
+    ModelTrait, QueryFilter, TransactionTrait,
+    sea_query::{Expr, IntoIden, OnConflict, SimpleExpr},
 };
+use std::future::Future;
 use std::marker::PhantomData;
+use std::pin::Pin;
 
+use crate::DbTx;
+use crate::audit::{self, AuditOperation};
 use crate::secure::cond::build_scope_condition;
 use crate::secure::error::ScopeError;
 use crate::secure::{
@@ -32,6 +36,7 @@ fn sea_value_to_scope_value(v: &sea_orm::Value) -> Option<modkit_security::Scope
         sea_orm::Value::SmallInt(Some(n)) => Some(ScopeValue::Int(i64::from(*n))),
         sea_orm::Value::TinyInt(Some(n)) => Some(ScopeValue::Int(i64::from(*n))),
         sea_orm::Value::Bool(Some(b)) => Some(ScopeValue::Bool(*b)),
+        sea_orm::Value::TimeDateTimeWithTimeZone(Some(t)) => Some(ScopeValue::Timestamp(**t)),
         _ => None,
     }
 }
@@ -55,6 +60,8 @@ fn sea_value_to_scope_value(v: &sea_orm::Value) -> Option<modkit_security::Scope
 /// - A filter whose property does **not** resolve (unknown property) causes
 ///   that constraint to fail (fail-closed), consistent with the query-path
 ///   behavior in `build_scope_condition`.
+/// - Tenant-subtree and group filters need the closure tables and cannot be
+///   checked in memory; they fail that constraint (fail-closed).
 ///
 /// # Errors
 ///
@@ -96,7 +103,7 @@ where
                         continue 'next_constraint;
                     };
 
-                    if !filter.values().contains(&sv) {
+                    if filter.evaluate(&sv) != Some(true) {
                         continue 'next_constraint;
                     }
                 }
@@ -171,11 +178,17 @@ where
 /// let user = secure_insert::<user::Entity>(am, &ctx, conn).await?;
 /// ```
 ///
+/// # Audit
+///
+/// For entities marked `#[secure(audit)]` the insert is recorded in the audit trail in
+/// the same transaction, with the runner's subject (see [`crate::audit`]).
+///
 /// # Errors
 ///
 /// - Returns `ScopeError::Db` if the database insert fails.
 /// - Returns `ScopeError::Denied` if the `ActiveModel` values do not satisfy any scope constraint.
 /// - Returns `ScopeError::TenantNotInScope` for tenant isolation violations.
+/// - Returns `ScopeError::Invalid` if the entity is audited and the runner has no subject.
 pub async fn secure_insert<E>(
     am: E::ActiveModel,
     scope: &AccessScope,
@@ -196,10 +209,25 @@ where
 
     validate_insert_scope(&am, scope)?;
 
-    match DBRunnerInternal::as_seaorm(runner) {
-        SeaOrmRunner::Conn(db) => Ok(am.insert(db).await?),
-        SeaOrmRunner::Tx(tx) => Ok(am.insert(tx).await?),
+    if E::IS_AUDITED {
+        let subject = runner.audit_subject().ok_or(AUDITED_WITHOUT_SUBJECT)?;
+        return in_write_tx(runner, move |tx| {
+            Box::pin(async move {
+                let model = am.insert(tx).await?;
+                audit::record::<E, _>(tx, subject, AuditOperation::Insert, None, Some(&model))
+                    .await?;
+                Ok(model)
+            })
+        })
+        .await;
     }
+
+    let model = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => am.insert(db).await?,
+        SeaOrmRunner::Tx(tx) => am.insert(tx).await?,
+    };
+    runner.note_write();
+    Ok(model)
 }
 
 /// Secure update helper for updating a single entity by ID inside a scope.
@@ -208,9 +236,14 @@ where
 /// - Verifies the target row exists **within the scope** before updating.
 /// - For tenant-scoped entities, forbids changing `tenant_id` (immutable).
 ///
+/// # Audit
+/// For entities marked `#[secure(audit)]` the changed columns are recorded in the audit
+/// trail in the same transaction (see [`crate::audit`]).
+///
 /// # Errors
 /// - `ScopeError::Denied` if the row is not accessible in the scope.
 /// - `ScopeError::Denied("tenant_id is immutable")` if caller attempts to change `tenant_id`.
+/// - `ScopeError::Invalid` if the entity is audited and the runner has no subject.
 pub async fn secure_update_with_scope<E>(
     am: E::ActiveModel,
     scope: &AccessScope,
@@ -223,19 +256,140 @@ where
     E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
     E::Model: sea_orm::IntoActiveModel<E::ActiveModel> + sea_orm::ModelTrait<Entity = E>,
 {
-    let existing = E::find()
+    if E::IS_AUDITED {
+        let subject = runner.audit_subject().ok_or(AUDITED_WITHOUT_SUBJECT)?;
+        let scope = scope.clone();
+        return in_write_tx(runner, move |tx| {
+            Box::pin(async move {
+                let runner = DbTx {
+                    tx,
+                    subject: Some(subject),
+                };
+                let existing = find_for_update::<E>(&scope, id, &runner).await?;
+                ensure_tenant_unchanged::<E>(&existing, &am)?;
+                let model = am.update(tx).await?;
+                let (before, after) = (Some(&existing), Some(&model));
+                audit::record::<E, _>(tx, subject, AuditOperation::Update, before, after).await?;
+                Ok(model)
+            })
+        })
+        .await;
+    }
+
+    let existing = find_for_update::<E>(scope, id, runner).await?;
+    ensure_tenant_unchanged::<E>(&existing, &am)?;
+
+    let model = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => am.update(db).await?,
+        SeaOrmRunner::Tx(tx) => am.update(tx).await?,
+    };
+    runner.note_write();
+    Ok(model)
+}
+
+/// Secure delete helper for deleting a single entity by ID inside a scope.
+///
+/// # Audit
+/// For entities marked `#[secure(audit)]` the deleted row is recorded in the audit trail
+/// in the same transaction (see [`crate::audit`]).
+///
+/// # Returns
+/// - `Ok(true)` if the entity was deleted
+/// - `Ok(false)` if it does not exist or is not accessible in the scope
+///
+/// # Errors
+/// - `ScopeError::Invalid` if the entity does not have a `resource_col` defined, or is
+///   audited and the runner has no subject.
+/// - `ScopeError::Db` if the database operation fails.
+pub async fn secure_delete_with_scope<E>(
+    scope: &AccessScope,
+    id: uuid::Uuid,
+    runner: &impl DBRunner,
+) -> Result<bool, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    let resource_col = E::resource_col().ok_or(ScopeError::Invalid(
+        "Entity must have a resource_col to use secure_delete_with_scope()",
+    ))?;
+    let by_id = sea_orm::Condition::all().add(Expr::col(resource_col).eq(id));
+
+    if E::IS_AUDITED {
+        let subject = runner.audit_subject().ok_or(AUDITED_WITHOUT_SUBJECT)?;
+        let scope = scope.clone();
+        return in_write_tx(runner, move |tx| {
+            Box::pin(async move {
+                let runner = DbTx {
+                    tx,
+                    subject: Some(subject),
+                };
+                let Some(existing) = E::find()
+                    .secure()
+                    .scope_with(&scope)
+                    .and_id(id)?
+                    .one(&runner)
+                    .await?
+                else {
+                    return Ok(false);
+                };
+                E::delete_many().filter(by_id).exec(tx).await?;
+                audit::record::<E, _>(tx, subject, AuditOperation::Delete, Some(&existing), None)
+                    .await?;
+                Ok(true)
+            })
+        })
+        .await;
+    }
+
+    let result = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => {
+            E::delete_many()
+                .filter(by_id)
+                .filter(build_scope_condition::<E>(scope))
+                .exec(db)
+                .await?
+        }
+        SeaOrmRunner::Tx(tx) => {
+            E::delete_many()
+                .filter(by_id)
+                .filter(build_scope_condition::<E>(scope))
+                .exec(tx)
+                .await?
+        }
+    };
+    runner.note_write();
+    Ok(result.rows_affected > 0)
+}
+
+/// Load the row `id` for an update, denying rows outside `scope`.
+async fn find_for_update<E>(
+    scope: &AccessScope,
+    id: uuid::Uuid,
+    runner: &impl DBRunner,
+) -> Result<E::Model, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    E::find()
         .secure()
         .scope_with(scope)
         .and_id(id)?
         .one(runner)
-        .await?;
-
-    let Some(existing) = existing else {
-        return Err(ScopeError::Denied(
+        .await?
+        .ok_or(ScopeError::Denied(
             "entity not found or not accessible in current security scope",
-        ));
-    };
+        ))
+}
 
+/// Reject updates that would move a row of a tenant-scoped entity to another tenant.
+fn ensure_tenant_unchanged<E>(existing: &E::Model, am: &E::ActiveModel) -> Result<(), ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E>,
+{
     if let Some(tcol) = E::tenant_col() {
         let stored = match existing.get(tcol) {
             sea_orm::Value::Uuid(Some(u)) => *u,
@@ -261,13 +415,42 @@ where
             return Err(ScopeError::Denied("tenant_id is immutable"));
         }
     }
+    Ok(())
+}
 
+/// Run `f` in the runner's transaction, or in a new one committed when `f` succeeds.
+///
+/// Audited writes use this so the row and its audit record are written atomically.
+async fn in_write_tx<T, F>(runner: &impl DBRunner, f: F) -> Result<T, ScopeError>
+where
+    T: Send,
+    F: for<'t> FnOnce(
+            &'t DatabaseTransaction,
+        ) -> Pin<Box<dyn Future<Output = Result<T, ScopeError>> + Send + 't>>
+        + Send,
+{
     match DBRunnerInternal::as_seaorm(runner) {
-        SeaOrmRunner::Conn(db) => Ok(am.update(db).await?),
-        SeaOrmRunner::Tx(tx) => Ok(am.update(tx).await?),
+        SeaOrmRunner::Tx(tx) => f(tx).await,
+        SeaOrmRunner::Conn(db) => {
+            let tx = db.begin().await?;
+            let out = f(&tx).await?;
+            tx.commit().await?;
+            runner.note_write();
+            Ok(out)
+        }
     }
 }
 
+/// Error returned by the bulk and upsert builders for audited entities.
+const AUDITED_BULK_WRITE: ScopeError = ScopeError::Invalid(
+    "audited entities must be written with secure_insert, secure_update_with_scope \
+     or secure_delete_with_scope",
+);
+
+/// Error returned for audited writes through a runner whose `Db` has no subject.
+const AUDITED_WITHOUT_SUBJECT: ScopeError =
+    ScopeError::Invalid("audited writes need the acting subject: use a runner of Db::with_subject");
+
 /// Helper to validate a tenant ID is in the scope.
 ///
 /// Use this when manually setting `tenant_id` in `ActiveModels` to ensure
@@ -459,17 +642,24 @@ where
     /// Execute the insert operation.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Invalid` if the entity is audited (use [`secure_insert`]).
+    /// - Returns `ScopeError::Db` if the database operation fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn exec<C>(self, runner: &C) -> Result<InsertResult<A>, ScopeError>
     where
         C: DBRunner,
         A: Send,
+        A::Entity: ScopableEntity,
     {
-        match DBRunnerInternal::as_seaorm(runner) {
-            SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
-            SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
+        if A::Entity::IS_AUDITED {
+            return Err(AUDITED_BULK_WRITE);
         }
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => self.inner.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => self.inner.exec(tx).await?,
+        };
+        runner.note_write();
+        Ok(result)
     }
 
     /// Execute the insert and return the inserted model.
@@ -478,7 +668,8 @@ where
     /// values (like auto-increment IDs or default values).
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Invalid` if the entity is audited (use [`secure_insert`]).
+    /// - Returns `ScopeError::Db` if the database operation fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn exec_with_returning<C>(
         self,
@@ -487,12 +678,18 @@ where
     where
         C: DBRunner,
         A: Send,
+        A::Entity: ScopableEntity,
         <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
     {
-        match DBRunnerInternal::as_seaorm(runner) {
-            SeaOrmRunner::Conn(db) => Ok(self.inner.exec_with_returning(db).await?),
-            SeaOrmRunner::Tx(tx) => Ok(self.inner.exec_with_returning(tx).await?),
+        if A::Entity::IS_AUDITED {
+            return Err(AUDITED_BULK_WRITE);
         }
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => self.inner.exec_with_returning(db).await?,
+            SeaOrmRunner::Tx(tx) => self.inner.exec_with_returning(tx).await?,
+        };
+        runner.note_write();
+        Ok(result)
     }
 
     /// Unwrap the inner `SeaORM` `Insert` for advanced use cases.
@@ -754,16 +951,69 @@ where
     /// Execute the update operation.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Invalid` if the entity is audited (use [`secure_update_with_scope`]).
+    /// - Returns `ScopeError::Db` if the database operation fails.
     #[allow(clippy::disallowed_methods)]
-    pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::UpdateResult, ScopeError> {
+    pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::UpdateResult, ScopeError>
+    where
+        E: ScopableEntity,
+    {
         if self.tenant_update_attempted {
             return Err(ScopeError::Denied("tenant_id is immutable"));
         }
-        match DBRunnerInternal::as_seaorm(runner) {
-            SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
-            SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
+        if E::IS_AUDITED {
+            return Err(AUDITED_BULK_WRITE);
         }
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => self.inner.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => self.inner.exec(tx).await?,
+        };
+        runner.note_write();
+        Ok(result)
+    }
+
+    /// Execute the update and return the updated models (`UPDATE ... RETURNING`).
+    ///
+    /// # Errors
+    /// - Returns `ScopeError::Invalid` if the entity is audited or the backend is `MySQL`,
+    ///   which has no `RETURNING`.
+    /// - Returns `ScopeError::Db` if the database operation fails.
+    #[allow(clippy::disallowed_methods)]
+    pub async fn exec_with_returning(
+        self,
+        runner: &impl DBRunner,
+    ) -> Result<Vec<E::Model>, ScopeError>
+    where
+        E: ScopableEntity,
+    {
+        use sea_orm::sea_query::Query;
+        use sea_orm::{ConnectionTrait, DbBackend, Iterable, QueryTrait};
+
+        if self.tenant_update_attempted {
+            return Err(ScopeError::Denied("tenant_id is immutable"));
+        }
+        if E::IS_AUDITED {
+            return Err(AUDITED_BULK_WRITE);
+        }
+        let mut query = self.inner.into_query();
+        query.returning(Query::returning().columns(E::Column::iter()));
+        let seaorm = DBRunnerInternal::as_seaorm(runner);
+        let backend = match &seaorm {
+            SeaOrmRunner::Conn(db) => db.get_database_backend(),
+            SeaOrmRunner::Tx(tx) => tx.get_database_backend(),
+        };
+        if backend == DbBackend::MySql {
+            return Err(ScopeError::Invalid(
+                "UPDATE ... RETURNING is not supported by MySQL",
+            ));
+        }
+        let select = E::find().from_raw_sql(backend.build(&query));
+        let models = match seaorm {
+            SeaOrmRunner::Conn(db) => select.all(db).await?,
+            SeaOrmRunner::Tx(tx) => select.all(tx).await?,
+        };
+        runner.note_write();
+        Ok(models)
     }
 
     /// Unwrap the inner `SeaORM` `UpdateMany` for advanced use cases.
@@ -859,13 +1109,22 @@ where
     /// Execute the delete operation.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Invalid` if the entity is audited (use [`secure_delete_with_scope`]).
+    /// - Returns `ScopeError::Db` if the database operation fails.
     #[allow(clippy::disallowed_methods)]
-    pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::DeleteResult, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
-            SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
-            SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
+    pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::DeleteResult, ScopeError>
+    where
+        E: ScopableEntity,
+    {
+        if E::IS_AUDITED {
+            return Err(AUDITED_BULK_WRITE);
         }
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => self.inner.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => self.inner.exec(tx).await?,
+        };
+        runner.note_write();
+        Ok(result)
     }
 
     /// Unwrap the inner `SeaORM` `DeleteMany` for advanced use cases.
@@ -1415,4 +1674,43 @@ mod tests {
             "Unknown property must cause constraint to fail (fail-closed)"
         );
     }
+
+    #[test]
+    fn test_validate_insert_scope_not_and_subtree_filters() {
+        use modkit_security::access_scope::{ScopeConstraint, ScopeFilter};
+        use modkit_security::pep_properties;
+        use owner_entity::ActiveModel;
+        use sea_orm::Set;
+
+        let tenant_id = Uuid::new_v4();
+        let blocked_user = Uuid::new_v4();
+        let am = ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            user_id: Set(Uuid::new_v4()),
+            city_id: Set(Uuid::new_v4()),
+        };
+
+        let not_blocked = AccessScope::single(ScopeConstraint::new(vec![
+            ScopeFilter::eq(pep_properties::OWNER_TENANT_ID, tenant_id),
+            ScopeFilter::negate(ScopeFilter::eq(pep_properties::OWNER_ID, blocked_user)),
+        ]));
+        assert!(validate_insert_scope(&am, &not_blocked).is_ok());
+
+        let blocked = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::negate(
+            ScopeFilter::eq(pep_properties::OWNER_TENANT_ID, tenant_id),
+        )]));
+        assert!(validate_insert_scope(&am, &blocked).is_err());
+
+        // The closure tables are not consulted in memory: fail closed.
+        let subtree =
+            AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::in_tenant_subtree(
+                pep_properties::OWNER_TENANT_ID,
+                tenant_id,
+            )]));
+        assert!(
+            validate_insert_scope(&am, &subtree).is_err(),
+            "Subtree filters cannot be checked on insert and must fail closed"
+        );
+    }
 }
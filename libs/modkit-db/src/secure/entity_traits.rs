@@ -107,6 +107,15 @@ pub trait ScopableEntity: EntityTrait {
     /// Default: `false` (entity participates in scoping logic)
     const IS_UNRESTRICTED: bool = false;
 
+    /// Indicates whether writes to this entity are recorded in the audit trail.
+    ///
+    /// Set via `#[secure(audit)]`. Audited entities can only be written through
+    /// `secure_insert`, `secure_update_with_scope` and `secure_delete_with_scope`;
+    /// see [`crate::audit`].
+    ///
+    /// Default: `false`
+    const IS_AUDITED: bool = false;
+
     /// Returns the column that stores the tenant identifier.
     ///
     /// - Multi-tenant entities: `Some(Column::TenantId)`
@@ -110,6 +110,7 @@ pub mod docs;
 #[allow(clippy::module_inception)]
 mod entity_traits;
 mod error;
+pub mod projections;
 pub mod provider;
 mod runner;
 mod secure_conn;
@@ -157,8 +158,8 @@ pub use select::{
 // Update/Delete/Insert operations
 pub use db_ops::{
     SecureDeleteExt, SecureDeleteMany, SecureInsertExt, SecureInsertOne, SecureOnConflict,
-    SecureUpdateExt, SecureUpdateMany, secure_insert, secure_update_with_scope,
-    validate_tenant_in_scope,
+    SecureUpdateExt, SecureUpdateMany, secure_delete_with_scope, secure_insert,
+    secure_update_with_scope, validate_tenant_in_scope,
 };
 
 // Provider pattern for advanced tenant filtering
@@ -0,0 +1,130 @@
+//! Local projections of the tenant and resource group hierarchies.
+//!
+//! `in_tenant_subtree`, `in_group` and `in_group_subtree` scope filters compile to
+//! subqueries on these tables (schemas in `docs/arch/authorization/DESIGN.md`, section
+//! "Table Schemas (Local Projections)"). A module whose PEP declares the
+//! `tenant_hierarchy`, `group_membership` or `group_hierarchy` capability adds
+//! [`migration()`] to its migrations and keeps the tables in sync with the
+//! Tenant / Resource Group Resolver:
+//!
+//! ```ignore
+//! fn migrations(&self) -> Vec<Box<dyn MigrationTrait>> {
+//!     vec![Box::new(m001_init::Migration), modkit_db::secure::projections::migration()]
+//! }
+//! ```
+
+use async_trait::async_trait;
+use sea_orm::sea_query::Index;
+use sea_orm_migration::prelude::{
+    Alias, ColumnDef, DbErr, MigrationName, MigrationTrait, SchemaManager, Table,
+};
+
+/// Closure table of the tenant hierarchy.
+pub const TENANT_CLOSURE: &str = "tenant_closure";
+/// Closure table of the resource group hierarchy.
+pub const RESOURCE_GROUP_CLOSURE: &str = "resource_group_closure";
+/// Resource-to-group membership.
+pub const RESOURCE_GROUP_MEMBERSHIP: &str = "resource_group_membership";
+
+/// Migration creating the projection tables.
+///
+/// Idempotent (`IF NOT EXISTS`), so modules sharing a database can all list it. `down`
+/// leaves the tables in place, since other modules may still use them.
+pub struct ProjectionsMigration;
+
+impl MigrationName for ProjectionsMigration {
+    fn name(&self) -> &'static str {
+        "m0000_modkit_hierarchy_projections"
+    }
+}
+
+#[async_trait]
+impl MigrationTrait for ProjectionsMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(TENANT_CLOSURE))
+                    .if_not_exists()
+                    .col(ColumnDef::new(Alias::new("ancestor_id")).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("descendant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new("barrier"))
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new("descendant_status"))
+                            .string()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(Alias::new("ancestor_id"))
+                            .col(Alias::new("descendant_id")),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(RESOURCE_GROUP_CLOSURE))
+                    .if_not_exists()
+                    .col(ColumnDef::new(Alias::new("ancestor_id")).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("descendant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(Alias::new("ancestor_id"))
+                            .col(Alias::new("descendant_id")),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(RESOURCE_GROUP_MEMBERSHIP))
+                    .if_not_exists()
+                    .col(ColumnDef::new(Alias::new("resource_id")).uuid().not_null())
+                    .col(ColumnDef::new(Alias::new("group_id")).uuid().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(Alias::new("resource_id"))
+                            .col(Alias::new("group_id")),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Membership subqueries look resources up by group.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_resource_group_membership_group_id")
+                    .table(Alias::new(RESOURCE_GROUP_MEMBERSHIP))
+                    .col(Alias::new("group_id"))
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
+
+/// The projections migration, ready to append to a module's migration list.
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(ProjectionsMigration)
+}
@@ -12,6 +12,8 @@
 
 use super::db::{DbConn, DbTx};
 use super::secure_conn::{SecureConn, SecureTx};
+use crate::replicas::Replicas;
+use uuid::Uuid;
 
 mod sealed {
     pub trait Sealed {}
@@ -28,7 +30,23 @@ pub enum SeaOrmRunner<'a> {
 
 /// Internal-only bridge to `SeaORM`'s executor types.
 pub trait DBRunnerInternal: sealed::Sealed + Send + Sync {
+    /// Executor for statements that may write. Always the primary.
     fn as_seaorm(&self) -> SeaOrmRunner<'_>;
+
+    /// Executor for read-only statements; may be a read replica.
+    fn as_seaorm_read(&self) -> SeaOrmRunner<'_> {
+        self.as_seaorm()
+    }
+
+    /// Record that a write through [`as_seaorm`](Self::as_seaorm) succeeded.
+    ///
+    /// Transactions record their writes on commit, so only `DbConn` does anything here.
+    fn note_write(&self) {}
+
+    /// Subject recorded on audited writes; `None` if the runner's `Db` has none.
+    fn audit_subject(&self) -> Option<Uuid> {
+        None
+    }
 }
 
 /// Hidden capability marker used by repositories and services.
@@ -47,6 +65,20 @@ impl DBRunnerInternal for DbConn<'_> {
     fn as_seaorm(&self) -> SeaOrmRunner<'_> {
         SeaOrmRunner::Conn(self.conn)
     }
+
+    fn as_seaorm_read(&self) -> SeaOrmRunner<'_> {
+        SeaOrmRunner::Conn(self.replicas.and_then(Replicas::pick).unwrap_or(self.conn))
+    }
+
+    fn note_write(&self) {
+        if let Some(replicas) = self.replicas {
+            replicas.note_write();
+        }
+    }
+
+    fn audit_subject(&self) -> Option<Uuid> {
+        self.subject
+    }
 }
 impl DBRunner for DbConn<'_> {}
 
@@ -55,6 +87,10 @@ impl DBRunnerInternal for DbTx<'_> {
     fn as_seaorm(&self) -> SeaOrmRunner<'_> {
         SeaOrmRunner::Tx(self.tx)
     }
+
+    fn audit_subject(&self) -> Option<Uuid> {
+        self.subject
+    }
 }
 impl DBRunner for DbTx<'_> {}
 
@@ -51,7 +51,7 @@ use std::{future::Future, pin::Pin};
 
 use sea_orm::{
     AccessMode, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
-    IsolationLevel, QueryFilter, TransactionTrait, sea_query::Expr,
+    IsolationLevel, TransactionTrait,
 };
 use uuid::Uuid;
 
@@ -405,18 +405,7 @@ impl SecureConn {
         E: ScopableEntity + EntityTrait,
         E::Column: ColumnTrait + Copy,
     {
-        let resource_col = E::resource_col().ok_or_else(|| {
-            ScopeError::Invalid("Entity must have a resource_col to use delete_by_id()")
-        })?;
-
-        let result = E::delete_many()
-            .filter(sea_orm::Condition::all().add(Expr::col(resource_col).eq(id)))
-            .secure()
-            .scope_with(scope)
-            .exec(self)
-            .await?;
-
-        Ok(result.rows_affected > 0)
+        crate::secure::secure_delete_with_scope::<E>(scope, id, self).await
     }
 
     // ========================================================================
@@ -198,7 +198,7 @@ where
     /// Returns `ScopeError::Db` if the database query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn all(self, runner: &impl DBRunner) -> Result<Vec<E::Model>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
@@ -210,7 +210,7 @@ where
     /// Returns `ScopeError::Db` if the database query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn one(self, runner: &impl DBRunner) -> Result<Option<E::Model>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
         }
@@ -225,7 +225,7 @@ where
     where
         E::Model: sea_orm::FromQueryResult + Send + Sync,
     {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.count(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.count(tx).await?),
         }
@@ -573,7 +573,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Option<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
@@ -588,7 +588,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Option<(E::Model, Option<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
         }
@@ -654,7 +654,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Vec<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
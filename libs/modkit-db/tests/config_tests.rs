@@ -30,6 +30,8 @@ fn test_dbconnconfig_serialization() {
             ..Default::default()
         }),
         server: Some("test_server".to_owned()),
+        replicas: None,
+        read_your_writes: None,
     };
 
     // Test serialization to JSON
@@ -70,6 +70,8 @@ async fn test_dbmanager_server_merge() {
                 ..Default::default()
             }),
             server: None,
+            replicas: None,
+            read_your_writes: None,
         },
     );
 
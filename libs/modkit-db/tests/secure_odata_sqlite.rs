@@ -14,6 +14,7 @@ use modkit_db::odata::pager::OPager;
 use modkit_db::secure::{Db, DbConn, ScopableEntity, secure_insert};
 use modkit_db::{ConnectOpts, connect_db};
 use modkit_odata::ODataQuery;
+use modkit_odata::ast::{CompareOperator, Expr, Value};
 use modkit_odata::filter::FieldKind;
 use modkit_security::{AccessScope, pep_properties};
 use sea_orm::Set;
@@ -33,6 +34,8 @@ mod ent {
         pub tenant_id: Uuid,
         pub name: String,
         pub score: i64,
+        pub tags: String,
+        pub created_at: ChronoDateTimeUtc,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -100,6 +103,16 @@ impl mig::MigrationTrait for CreateSecureOdataTest {
                             .big_integer()
                             .not_null(),
                     )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tags"))
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("created_at"))
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
                     .to_owned(),
             )
             .await
@@ -150,13 +163,20 @@ impl TestDb {
 }
 
 async fn seed<R: modkit_db::secure::DBRunner>(runner: &R, tenant_id: Uuid, scope: &AccessScope) {
-    let rows = [("alice", 10), ("bob", 20), ("charlie", 30), ("dave", 40)];
+    let rows = [
+        ("alice", 10, r#"["red","blue"]"#, "2023-05-01T10:00:00Z"),
+        ("bob", 20, r#"["green"]"#, "2024-01-15T10:00:00Z"),
+        ("charlie", 30, "[]", "2024-06-30T10:00:00Z"),
+        ("dave", 40, r#"["red"]"#, "2024-12-01T10:00:00Z"),
+    ];
 
-    for (name, score) in rows {
+    for (name, score, tags, created_at) in rows {
         let am = ent::ActiveModel {
             tenant_id: Set(tenant_id),
             name: Set(name.to_owned()),
             score: Set(score),
+            tags: Set(tags.to_owned()),
+            created_at: Set(created_at.parse().unwrap()),
             ..Default::default()
         };
         secure_insert::<ent::Entity>(am, scope, runner)
@@ -190,3 +210,266 @@ async fn paginate_odata_works_with_secure_conn() {
 
     assert_eq!(page.items.len(), 2, "page size");
 }
+
+fn field_map() -> FieldMap<ent::Entity> {
+    FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("name", ent::Column::Name, FieldKind::String)
+        .insert("score", ent::Column::Score, FieldKind::I64)
+        .insert("tags", ent::Column::Tags, FieldKind::Json)
+        .insert("created_at", ent::Column::CreatedAt, FieldKind::DateTimeUtc)
+        .with_search_fields(&["name", "score"])
+}
+
+#[tokio::test]
+async fn opager_search_and_count() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+    let fmap = field_map();
+
+    // Non-string fields are not searchable; "A" matches alice, charlie, dave case-insensitively.
+    let q = ODataQuery::new()
+        .with_search("A")
+        .with_count(true)
+        .with_limit(2);
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.page_info.total_count, Some(3));
+    assert!(page.page_info.next_cursor.is_some());
+
+    // Every term must match.
+    let q = ODataQuery::new().with_search("a  LI").with_count(true);
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    let mut names = page.items;
+    names.sort();
+    assert_eq!(names, vec!["alice", "charlie"]);
+    assert_eq!(page.page_info.total_count, Some(2));
+
+    // LIKE wildcards in the search text are literal.
+    let q = ODataQuery::new().with_search("%").with_count(true);
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert!(page.items.is_empty());
+    assert_eq!(page.page_info.total_count, Some(0));
+
+    // Without $count no total is reported.
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&ODataQuery::new(), |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, None);
+}
+
+#[tokio::test]
+async fn opager_count_respects_scope() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+    let other = AccessScope::for_tenants(vec![Uuid::new_v4()]);
+    let fmap = field_map();
+
+    let q = ODataQuery::new().with_count(true);
+    let page = OPager::<ent::Entity, _>::new(&other, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, Some(0));
+
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, Some(4));
+}
+
+#[tokio::test]
+async fn opager_search_without_search_fields_is_rejected() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let fmap: FieldMap<ent::Entity> =
+        FieldMap::new().insert("name", ent::Column::Name, FieldKind::String);
+
+    let q = ODataQuery::new().with_search("alice");
+    let err = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, modkit_odata::Error::InvalidSearch(_)));
+}
+
+fn ident(name: &str) -> Expr {
+    Expr::Identifier(name.to_owned())
+}
+
+fn string(s: &str) -> Expr {
+    Expr::Value(Value::String(s.to_owned()))
+}
+
+fn number(n: i64) -> Expr {
+    Expr::Value(Value::Number(n.into()))
+}
+
+fn call(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function(name.to_owned(), args)
+}
+
+fn compare(lhs: Expr, op: CompareOperator, rhs: Expr) -> Expr {
+    Expr::Compare(Box::new(lhs), op, Box::new(rhs))
+}
+
+async fn names_matching(test_db: &TestDb, filter: Expr) -> Vec<String> {
+    let conn = test_db.conn();
+    let fmap = field_map();
+    let q = ODataQuery::new().with_filter(filter);
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    let mut names = page.items;
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn opager_filter_functions() {
+    let test_db = TestDb::new().await;
+    seed(&test_db.conn(), test_db.tenant_id, &test_db.scope).await;
+
+    let filter = compare(
+        call("toupper", vec![ident("name")]),
+        CompareOperator::Eq,
+        string("BOB"),
+    );
+    assert_eq!(names_matching(&test_db, filter).await, vec!["bob"]);
+
+    let filter = compare(
+        call("length", vec![ident("name")]),
+        CompareOperator::Gt,
+        number(4),
+    );
+    assert_eq!(
+        names_matching(&test_db, filter).await,
+        vec!["alice", "charlie"]
+    );
+
+    let filter = compare(
+        call("indexof", vec![ident("name"), string("li")]),
+        CompareOperator::Eq,
+        number(1),
+    );
+    assert_eq!(names_matching(&test_db, filter).await, vec!["alice"]);
+
+    let filter = call(
+        "endswith",
+        vec![call("tolower", vec![ident("name")]), string("e")],
+    );
+    assert_eq!(
+        names_matching(&test_db, filter).await,
+        vec!["alice", "charlie", "dave"]
+    );
+
+    let filter = compare(
+        call("year", vec![ident("created_at")]),
+        CompareOperator::Eq,
+        number(2024),
+    )
+    .and(compare(
+        call("month", vec![ident("created_at")]),
+        CompareOperator::Le,
+        number(6),
+    ));
+    assert_eq!(
+        names_matching(&test_db, filter).await,
+        vec!["bob", "charlie"]
+    );
+
+    let filter = Expr::In(Box::new(ident("score")), vec![number(10), number(30)]);
+    assert_eq!(
+        names_matching(&test_db, filter).await,
+        vec!["alice", "charlie"]
+    );
+}
+
+#[tokio::test]
+async fn opager_filter_lambdas() {
+    let test_db = TestDb::new().await;
+    seed(&test_db.conn(), test_db.tenant_id, &test_db.scope).await;
+    let it = || ident(modkit_odata::ast::LAMBDA_VAR);
+
+    let filter = call(
+        "any",
+        vec![
+            ident("tags"),
+            compare(it(), CompareOperator::Eq, string("red")),
+        ],
+    );
+    assert_eq!(
+        names_matching(&test_db, filter).await,
+        vec!["alice", "dave"]
+    );
+
+    // `all` holds vacuously for empty arrays.
+    let filter = call(
+        "all",
+        vec![
+            ident("tags"),
+            compare(it(), CompareOperator::Ne, string("blue")),
+        ],
+    );
+    assert_eq!(
+        names_matching(&test_db, filter).await,
+        vec!["bob", "charlie", "dave"]
+    );
+
+    let filter = call("any", vec![ident("tags")]);
+    assert_eq!(
+        names_matching(&test_db, filter).await,
+        vec!["alice", "bob", "dave"]
+    );
+
+    let filter = call(
+        "any",
+        vec![
+            ident("tags"),
+            Expr::In(Box::new(it()), vec![string("green"), string("blue")]),
+        ],
+    )
+    .and(compare(ident("score"), CompareOperator::Gt, number(15)));
+    assert_eq!(names_matching(&test_db, filter).await, vec!["bob"]);
+}
+
+#[tokio::test]
+async fn opager_lambda_over_non_json_field_is_rejected() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let fmap = field_map();
+    let filter = call(
+        "any",
+        vec![
+            ident("name"),
+            compare(
+                ident(modkit_odata::ast::LAMBDA_VAR),
+                CompareOperator::Eq,
+                string("x"),
+            ),
+        ],
+    );
+
+    let q = ODataQuery::new().with_filter(filter);
+    let err = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, modkit_odata::Error::InvalidFilter(_)));
+}
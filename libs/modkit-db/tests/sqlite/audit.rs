@@ -0,0 +1,411 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the audit trail of `#[secure(audit)]` entities.
+//!
+//! Security contract:
+//! - No raw SQL in tests.
+//! - Schema is created via `sea-orm-migration` definitions executed by the migration runner.
+
+use modkit_db::audit::{self, AuditEvent};
+use modkit_db::migration_runner::{
+    rollback_migrations_for_module, run_migrations_for_module, run_migrations_for_testing,
+};
+use modkit_db::secure::{
+    Db, DbConn, ScopableEntity, ScopeError, SecureDeleteExt, SecureEntityExt, SecureUpdateExt,
+    secure_delete_with_scope, secure_insert, secure_update_with_scope,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_odata::ODataQuery;
+use modkit_odata::ast::{CompareOperator, Expr, Value};
+use modkit_security::{AccessScope, SecurityContext, pep_properties};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use serde_json::json;
+use uuid::Uuid;
+
+mod note {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "audited_note")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub title: String,
+        pub body: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for note::Entity {
+    const IS_AUDITED: bool = true;
+
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(note::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(note::Column::Id)
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            p if p == pep_properties::RESOURCE_ID => Self::resource_col(),
+            _ => None,
+        }
+    }
+}
+
+struct CreateAuditedNoteTable;
+
+impl mig::MigrationName for CreateAuditedNoteTable {
+    fn name(&self) -> &'static str {
+        "m001_create_audited_note"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateAuditedNoteTable {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("audited_note"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("title"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("body"))
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("audited_note"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+struct TestDb {
+    db: Db,
+    /// Subject of the runners returned by [`TestDb::conn`].
+    subject: Uuid,
+    acting: Db,
+}
+
+impl TestDb {
+    async fn new() -> Self {
+        let test_id = Uuid::new_v4();
+        let dsn = format!("sqlite:file:memdb_audit_{test_id}?mode=memory&cache=shared");
+
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+
+        let db = connect_db(&dsn, opts).await.expect("connect");
+
+        run_migrations_for_testing(
+            &db,
+            vec![audit::migration(), Box::new(CreateAuditedNoteTable)],
+        )
+        .await
+        .expect("migrate");
+
+        let subject = Uuid::new_v4();
+        let acting = db.with_subject(&ctx(subject, Uuid::new_v4()));
+        Self {
+            db,
+            subject,
+            acting,
+        }
+    }
+
+    fn conn(&self) -> DbConn<'_> {
+        self.acting.conn().expect("conn")
+    }
+}
+
+fn ctx(subject_id: Uuid, tenant_id: Uuid) -> SecurityContext {
+    SecurityContext::builder()
+        .subject_id(subject_id)
+        .subject_tenant_id(tenant_id)
+        .build()
+        .expect("security context")
+}
+
+fn new_note(id: Uuid, tenant_id: Uuid, title: &str) -> note::ActiveModel {
+    note::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(tenant_id),
+        title: Set(title.to_owned()),
+        body: Set("body".to_owned()),
+    }
+}
+
+async fn events(conn: &DbConn<'_>, scope: &AccessScope, query: &ODataQuery) -> Vec<AuditEvent> {
+    let mut items = audit::list_events(conn, scope, query)
+        .await
+        .expect("list events")
+        .items;
+    items.sort_by_key(|e| e.id);
+    items
+}
+
+#[tokio::test]
+async fn writes_are_recorded_with_subject_and_diff() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = Uuid::new_v4();
+
+    secure_insert::<note::Entity>(new_note(id, tenant, "draft"), &scope, &conn)
+        .await
+        .expect("insert");
+    secure_update_with_scope::<note::Entity>(
+        note::ActiveModel {
+            id: Set(id),
+            title: Set("final".to_owned()),
+            ..Default::default()
+        },
+        &scope,
+        id,
+        &conn,
+    )
+    .await
+    .expect("update");
+    assert!(
+        secure_delete_with_scope::<note::Entity>(&scope, id, &conn)
+            .await
+            .expect("delete")
+    );
+
+    let events = events(&conn, &scope, &ODataQuery::new()).await;
+    let ops: Vec<&str> = events.iter().map(|e| e.operation.as_str()).collect();
+    assert_eq!(ops, ["insert", "update", "delete"]);
+
+    for event in &events {
+        assert_eq!(event.subject_id, test_db.subject);
+        assert_eq!(event.tenant_id, Some(tenant));
+        assert_eq!(event.entity, "audited_note");
+        assert_eq!(event.primary_key, id.to_string());
+    }
+
+    let inserted = &events[0];
+    assert!(inserted.before.is_none());
+    assert_eq!(inserted.after.as_ref().unwrap()["title"], "draft");
+
+    let updated = &events[1];
+    assert_eq!(updated.before, Some(json!({ "title": "draft" })));
+    assert_eq!(updated.after, Some(json!({ "title": "final" })));
+
+    let deleted = &events[2];
+    assert_eq!(deleted.before.as_ref().unwrap()["title"], "final");
+    assert!(deleted.after.is_none());
+}
+
+#[tokio::test]
+async fn audited_writes_without_subject_are_rejected() {
+    let test_db = TestDb::new().await;
+    let anonymous = test_db.db.conn().expect("conn");
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = Uuid::new_v4();
+
+    let err = secure_insert::<note::Entity>(new_note(id, tenant, "system"), &scope, &anonymous)
+        .await
+        .expect_err("insert without subject must be rejected");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+
+    let (_, result) = test_db
+        .db
+        .clone()
+        .transaction(|tx| {
+            Box::pin(async move {
+                secure_insert::<note::Entity>(new_note(id, tenant, "system"), &scope, tx).await?;
+                Ok(())
+            })
+        })
+        .await;
+    assert!(
+        result.is_err(),
+        "transaction without subject must be rejected"
+    );
+
+    let conn = test_db.conn();
+    let scope = AccessScope::for_tenant(tenant);
+    assert!(events(&conn, &scope, &ODataQuery::new()).await.is_empty());
+    let notes = note::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .expect("notes");
+    assert!(notes.is_empty());
+}
+
+#[tokio::test]
+async fn rolling_back_one_module_keeps_other_modules_events() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+
+    secure_insert::<note::Entity>(new_note(Uuid::new_v4(), tenant, "kept"), &scope, &conn)
+        .await
+        .expect("insert");
+
+    run_migrations_for_module(&test_db.db, "other", vec![audit::migration()])
+        .await
+        .expect("migrate other");
+    let result =
+        rollback_migrations_for_module(&test_db.db, "other", vec![audit::migration()], None)
+            .await
+            .expect("roll back other");
+    assert_eq!(result.rolled_back, ["m0000_modkit_audit"]);
+
+    secure_insert::<note::Entity>(new_note(Uuid::new_v4(), tenant, "after"), &scope, &conn)
+        .await
+        .expect("insert after rollback");
+
+    let events = events(&conn, &scope, &ODataQuery::new()).await;
+    assert_eq!(events.len(), 2);
+}
+
+#[tokio::test]
+async fn denied_write_is_not_recorded() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant_a = Uuid::new_v4();
+    let tenant_b = Uuid::new_v4();
+    let scope_a = AccessScope::for_tenant(tenant_a);
+    let id = Uuid::new_v4();
+
+    secure_insert::<note::Entity>(new_note(id, tenant_a, "mine"), &scope_a, &conn)
+        .await
+        .expect("insert");
+
+    let err = secure_update_with_scope::<note::Entity>(
+        note::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(tenant_b),
+            ..Default::default()
+        },
+        &scope_a,
+        id,
+        &conn,
+    )
+    .await
+    .expect_err("tenant change must be denied");
+    assert!(matches!(err, ScopeError::Denied("tenant_id is immutable")));
+
+    let deleted =
+        secure_delete_with_scope::<note::Entity>(&AccessScope::for_tenant(tenant_b), id, &conn)
+            .await
+            .expect("delete");
+    assert!(!deleted);
+
+    let events = events(&conn, &scope_a, &ODataQuery::new()).await;
+    let ops: Vec<&str> = events.iter().map(|e| e.operation.as_str()).collect();
+    assert_eq!(ops, ["insert"]);
+}
+
+#[tokio::test]
+async fn list_events_is_scoped_and_filterable() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant_a = Uuid::new_v4();
+    let tenant_b = Uuid::new_v4();
+    let scope_a = AccessScope::for_tenant(tenant_a);
+    let scope_b = AccessScope::for_tenant(tenant_b);
+    let id_a = Uuid::new_v4();
+
+    secure_insert::<note::Entity>(new_note(id_a, tenant_a, "a"), &scope_a, &conn)
+        .await
+        .expect("insert a");
+    secure_insert::<note::Entity>(new_note(Uuid::new_v4(), tenant_b, "b"), &scope_b, &conn)
+        .await
+        .expect("insert b");
+    secure_delete_with_scope::<note::Entity>(&scope_a, id_a, &conn)
+        .await
+        .expect("delete a");
+
+    let visible = events(&conn, &scope_a, &ODataQuery::new()).await;
+    assert_eq!(visible.len(), 2);
+    assert!(visible.iter().all(|e| e.tenant_id == Some(tenant_a)));
+
+    let deletes = ODataQuery::new().with_filter(Expr::Compare(
+        Box::new(Expr::Identifier("operation".to_owned())),
+        CompareOperator::Eq,
+        Box::new(Expr::Value(Value::String("delete".to_owned()))),
+    ));
+    let visible = events(&conn, &scope_a, &deletes).await;
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].primary_key, id_a.to_string());
+
+    assert!(events(&conn, &scope_b, &deletes).await.is_empty());
+}
+
+#[tokio::test]
+async fn bulk_writes_to_audited_entities_are_rejected() {
+    use sea_orm::sea_query::Expr as SqlExpr;
+
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+
+    let err = note::Entity::update_many()
+        .secure()
+        .scope_with(&scope)
+        .col_expr(note::Column::Title, SqlExpr::value("bulk"))
+        .exec(&conn)
+        .await
+        .expect_err("bulk update must be rejected");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+
+    let err = note::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .exec(&conn)
+        .await
+        .expect_err("bulk delete must be rejected");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+}
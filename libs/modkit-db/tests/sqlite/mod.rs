@@ -5,10 +5,14 @@
 
 #![cfg(feature = "sqlite")]
 
+mod audit;
 mod concurrency_tests;
 mod manager;
 mod options;
+mod outbox;
 mod pooling_tests;
+mod projections;
+mod replicas;
 mod secure_insert_tenant_validation;
 mod secure_update_tenant_safety;
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -0,0 +1,285 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Transactional outbox tests: enqueue inside transactions, relay to a sink.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use modkit_db::build_db;
+use modkit_db::config::{DbConnConfig, DbEngineCfg};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::outbox::{
+    BroadcastSink, OutboxEvent, OutboxRelay, OutboxRelayConfig, OutboxSink, migration,
+};
+use modkit_db::secure::{Db, TxConfig};
+use serde_json::json;
+use tempfile::TempDir;
+use tokio_util::sync::CancellationToken;
+
+async fn setup() -> (TempDir, Db) {
+    let dir = TempDir::new().unwrap();
+    let cfg = DbConnConfig {
+        engine: Some(DbEngineCfg::Sqlite),
+        path: Some(dir.path().join("outbox.db")),
+        ..Default::default()
+    };
+    let db = build_db(cfg, None).await.expect("connect");
+    run_migrations_for_testing(&db, vec![migration()])
+        .await
+        .expect("migrate");
+    (dir, db)
+}
+
+const MODULE: &str = "billing";
+
+async fn enqueue(db: Db, topic: &'static str, n: i64) -> Db {
+    enqueue_for(db, MODULE, topic, n).await
+}
+
+async fn enqueue_for(db: Db, module: &'static str, topic: &'static str, n: i64) -> Db {
+    let (db, res) = db
+        .transaction_with_config(TxConfig::default(), move |tx| {
+            Box::pin(async move { Ok(tx.enqueue(module, topic, &json!({ "n": n })).await?) })
+        })
+        .await;
+    res.unwrap();
+    db
+}
+
+fn recording_sink(failures: usize) -> Arc<FlakySink> {
+    Arc::new(FlakySink {
+        failures: AtomicUsize::new(failures),
+        seen: std::sync::Mutex::default(),
+    })
+}
+
+fn topics(sink: &FlakySink) -> Vec<String> {
+    sink.seen
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|e| e.topic.clone())
+        .collect()
+}
+
+/// Sink failing its first `failures` publishes, then recording events.
+struct FlakySink {
+    failures: AtomicUsize,
+    seen: std::sync::Mutex<Vec<OutboxEvent>>,
+}
+
+#[async_trait::async_trait]
+impl OutboxSink for FlakySink {
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        if self
+            .failures
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            anyhow::bail!("sink unavailable");
+        }
+        self.seen.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn only_committed_events_are_relayed_in_order() {
+    let (_dir, db) = setup().await;
+    let db = enqueue(db, "first", 1).await;
+
+    let (db, res) = db
+        .transaction_with_config(TxConfig::default(), |tx| {
+            Box::pin(async move {
+                tx.enqueue(MODULE, "rolled_back", &json!({})).await?;
+                Err::<(), _>(anyhow::anyhow!("abort"))
+            })
+        })
+        .await;
+    assert!(res.is_err());
+    let db = enqueue(db, "second", 2).await;
+
+    let sink = BroadcastSink::new(16);
+    let mut rx = sink.subscribe();
+    let relay = OutboxRelay::new(db, MODULE, Arc::new(sink));
+
+    assert_eq!(relay.relay_once().await.unwrap(), 2);
+    let first = rx.recv().await.unwrap();
+    let second = rx.recv().await.unwrap();
+    assert_eq!(
+        (first.topic.as_str(), second.topic.as_str()),
+        ("first", "second")
+    );
+    assert_eq!(first.payload, json!({ "n": 1 }));
+    assert_eq!(first.module, MODULE);
+    assert!(first.id < second.id);
+
+    // Published events are not relayed again.
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn failed_publishes_are_retried_without_reordering() {
+    let (_dir, db) = setup().await;
+    let db = enqueue(db, "a", 1).await;
+    let db = enqueue(db, "b", 2).await;
+
+    let sink = recording_sink(2);
+    let relay = OutboxRelay::new(db, MODULE, sink.clone());
+
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+    assert_eq!(relay.relay_once().await.unwrap(), 2);
+
+    let seen = sink.seen.lock().unwrap();
+    let topics: Vec<_> = seen.iter().map(|e| e.topic.as_str()).collect();
+    assert_eq!(topics, ["a", "b"]);
+    assert_eq!(seen[0].attempts, 2);
+    assert_eq!(seen[1].attempts, 0);
+}
+
+#[tokio::test]
+async fn events_are_dead_lettered_after_max_attempts() {
+    let (_dir, db) = setup().await;
+    let db = enqueue(db, "poison", 1).await;
+    let db = enqueue(db, "next", 2).await;
+
+    let sink = recording_sink(3);
+    let relay = OutboxRelay::new(db, MODULE, sink.clone()).with_config(OutboxRelayConfig {
+        max_attempts: 2,
+        ..OutboxRelayConfig::default()
+    });
+
+    // "poison" fails twice and is dead-lettered; "next" then fails once and is retried.
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+    assert_eq!(relay.relay_once().await.unwrap(), 1);
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+    assert_eq!(topics(&sink), ["next"]);
+}
+
+#[tokio::test]
+async fn malformed_payloads_are_dead_lettered_not_published() {
+    use sea_orm::ConnectionTrait as _;
+
+    let (dir, db) = setup().await;
+    let db = enqueue(db, "corrupt", 1).await;
+    let db = enqueue(db, "next", 2).await;
+
+    let raw = sea_orm::Database::connect(format!(
+        "sqlite://{}",
+        dir.path().join("outbox.db").display()
+    ))
+    .await
+    .unwrap();
+    raw.execute_unprepared("UPDATE modkit_outbox SET payload = '{' WHERE topic = 'corrupt'")
+        .await
+        .unwrap();
+
+    let sink = recording_sink(0);
+    let relay = OutboxRelay::new(db, MODULE, sink.clone());
+    assert_eq!(relay.relay_once().await.unwrap(), 1);
+    assert_eq!(relay.relay_once().await.unwrap(), 0);
+    assert_eq!(topics(&sink), ["next"]);
+
+    let row = raw
+        .query_one(sea_orm::Statement::from_string(
+            raw.get_database_backend(),
+            "SELECT dead_lettered_at IS NOT NULL AS dead, last_error FROM modkit_outbox \
+             WHERE topic = 'corrupt'",
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(row.try_get::<bool>("", "dead").unwrap());
+    let error: String = row.try_get("", "last_error").unwrap();
+    assert!(error.starts_with("invalid event payload"), "{error}");
+}
+
+#[tokio::test]
+async fn relays_only_publish_their_own_module() {
+    let (_dir, db) = setup().await;
+    let db = enqueue_for(db, "billing", "invoice.created", 1).await;
+    let db = enqueue_for(db, "audit", "entry.added", 2).await;
+
+    let billing_sink = recording_sink(0);
+    let audit_sink = recording_sink(0);
+    let billing = OutboxRelay::new(db.clone(), "billing", billing_sink.clone());
+    let audit = OutboxRelay::new(db, "audit", audit_sink.clone());
+
+    // Each module has its own lease, so both relays publish.
+    assert_eq!(billing.relay_once().await.unwrap(), 1);
+    assert_eq!(audit.relay_once().await.unwrap(), 1);
+    assert_eq!(topics(&billing_sink), ["invoice.created"]);
+    assert_eq!(topics(&audit_sink), ["entry.added"]);
+}
+
+#[tokio::test]
+async fn another_relay_takes_over_an_expired_lease() {
+    let (_dir, db) = setup().await;
+    let config = OutboxRelayConfig {
+        lease_ttl: Duration::from_millis(300),
+        ..OutboxRelayConfig::default()
+    };
+    let first_sink = recording_sink(0);
+    let second_sink = recording_sink(0);
+    let first =
+        OutboxRelay::new(db.clone(), MODULE, first_sink.clone()).with_config(config.clone());
+    let second = OutboxRelay::new(db.clone(), MODULE, second_sink.clone()).with_config(config);
+
+    let db = enqueue(db, "a", 1).await;
+    assert_eq!(first.relay_once().await.unwrap(), 1);
+
+    // The first relay now disappears without releasing its lease.
+    let _db = enqueue(db, "b", 2).await;
+    assert_eq!(second.relay_once().await.unwrap(), 0);
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert_eq!(second.relay_once().await.unwrap(), 1);
+    assert_eq!(topics(&first_sink), ["a"]);
+    assert_eq!(topics(&second_sink), ["b"]);
+
+    // The lease now belongs to the second relay.
+    assert_eq!(first.relay_once().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn run_relays_until_cancelled() {
+    let (_dir, db) = setup().await;
+    let db = enqueue(db, "background", 1).await;
+
+    let sink = BroadcastSink::new(4);
+    let mut rx = sink.subscribe();
+    let relay = Arc::new(
+        OutboxRelay::new(db.clone(), MODULE, Arc::new(sink)).with_config(OutboxRelayConfig {
+            poll_interval: Duration::from_millis(20),
+            ..OutboxRelayConfig::default()
+        }),
+    );
+
+    let cancel = CancellationToken::new();
+    let task = tokio::spawn({
+        let (relay, cancel) = (relay.clone(), cancel.clone());
+        async move { relay.run(cancel).await }
+    });
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("relayed in time")
+        .unwrap();
+    assert_eq!(event.topic, "background");
+
+    cancel.cancel();
+    tokio::time::timeout(Duration::from_secs(5), task)
+        .await
+        .expect("stops on cancel")
+        .unwrap();
+
+    // The lease was released on shutdown, so a new relay starts at once.
+    let db = enqueue(db, "after_restart", 2).await;
+    let sink = recording_sink(0);
+    let next = OutboxRelay::new(db, MODULE, sink.clone());
+    assert_eq!(next.relay_once().await.unwrap(), 1);
+    assert_eq!(topics(&sink), ["after_restart"]);
+}
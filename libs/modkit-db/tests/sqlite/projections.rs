@@ -0,0 +1,283 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Hierarchy projection tables: the shipped migration creates the tables that
+//! tenant subtree and group scope filters query.
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{Db, DbConn, ScopableEntity, SecureEntityExt, projections, secure_insert};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::{
+    AccessScope, ScopeConstraint, ScopeFilter, TenantSubtreeScopeFilter, pep_properties,
+};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod doc_ent {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "projection_docs")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for doc_ent::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(doc_ent::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(doc_ent::Column::Id)
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            p if p == pep_properties::RESOURCE_ID => Self::resource_col(),
+            _ => None,
+        }
+    }
+}
+
+mod closure_ent {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "tenant_closure")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub ancestor_id: Uuid,
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub descendant_id: Uuid,
+        pub barrier: i32,
+        pub descendant_status: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod membership_ent {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "resource_group_membership")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub resource_id: Uuid,
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub group_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+macro_rules! unrestricted {
+    ($entity:ty) => {
+        impl ScopableEntity for $entity {
+            const IS_UNRESTRICTED: bool = true;
+
+            fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+            fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+            fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+            fn type_col() -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+            fn resolve_property(_property: &str) -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+        }
+    };
+}
+
+unrestricted!(closure_ent::Entity);
+unrestricted!(membership_ent::Entity);
+
+struct CreateProjectionDocs;
+
+impl mig::MigrationName for CreateProjectionDocs {
+    fn name(&self) -> &'static str {
+        "m001_create_projection_docs"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateProjectionDocs {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("projection_docs"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("projection_docs"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn setup() -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let dsn = format!(
+        "sqlite:file:memdb_projections_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let db = connect_db(&dsn, opts).await.expect("db connect");
+    run_migrations_for_testing(
+        &db,
+        vec![projections::migration(), Box::new(CreateProjectionDocs)],
+    )
+    .await
+    .expect("migrate");
+    db
+}
+
+async fn insert_closure(conn: &DbConn<'_>, ancestor: Uuid, descendant: Uuid, barrier: i32) {
+    let am = closure_ent::ActiveModel {
+        ancestor_id: Set(ancestor),
+        descendant_id: Set(descendant),
+        barrier: Set(barrier),
+        descendant_status: Set("active".to_owned()),
+    };
+    secure_insert::<closure_ent::Entity>(am, &AccessScope::allow_all(), conn)
+        .await
+        .expect("insert closure row");
+}
+
+async fn insert_doc(conn: &DbConn<'_>, tenant_id: Uuid) -> Uuid {
+    let id = Uuid::new_v4();
+    let am = doc_ent::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(tenant_id),
+    };
+    secure_insert::<doc_ent::Entity>(am, &AccessScope::for_tenant(tenant_id), conn)
+        .await
+        .expect("insert doc");
+    id
+}
+
+async fn visible_docs(conn: &DbConn<'_>, filter: ScopeFilter) -> Vec<Uuid> {
+    let scope = AccessScope::from_constraints(vec![ScopeConstraint::new(vec![filter])]);
+    let mut ids: Vec<Uuid> = doc_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(conn)
+        .await
+        .expect("select")
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn tenant_subtree_filter_runs_against_migrated_closure_table() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+
+    // root -> child -> grandchild, where `child` is self-managed (a barrier)
+    let (root, child, grandchild, other) = (
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+    );
+    for (ancestor, descendant, barrier) in [
+        (root, root, 0),
+        (root, child, 0),
+        (root, grandchild, 1),
+        (child, child, 0),
+        (child, grandchild, 0),
+        (grandchild, grandchild, 0),
+        (other, other, 0),
+    ] {
+        insert_closure(&conn, ancestor, descendant, barrier).await;
+    }
+    let root_doc = insert_doc(&conn, root).await;
+    let child_doc = insert_doc(&conn, child).await;
+    let grandchild_doc = insert_doc(&conn, grandchild).await;
+    insert_doc(&conn, other).await;
+
+    let mut expected = vec![root_doc, child_doc];
+    expected.sort();
+    let filter = ScopeFilter::in_tenant_subtree(pep_properties::OWNER_TENANT_ID, root);
+    assert_eq!(visible_docs(&conn, filter).await, expected);
+
+    let filter = ScopeFilter::InTenantSubtree(
+        TenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, root).with_barriers(false),
+    );
+    let mut expected = vec![root_doc, child_doc, grandchild_doc];
+    expected.sort();
+    assert_eq!(visible_docs(&conn, filter).await, expected);
+}
+
+#[tokio::test]
+async fn group_filter_runs_against_migrated_membership_table() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+
+    let tenant = Uuid::new_v4();
+    let grouped = insert_doc(&conn, tenant).await;
+    insert_doc(&conn, tenant).await;
+    let group = Uuid::new_v4();
+    let am = membership_ent::ActiveModel {
+        resource_id: Set(grouped),
+        group_id: Set(group),
+    };
+    secure_insert::<membership_ent::Entity>(am, &AccessScope::allow_all(), &conn)
+        .await
+        .expect("insert membership");
+
+    let filter = ScopeFilter::in_group(pep_properties::RESOURCE_ID, vec![group]);
+    assert_eq!(visible_docs(&conn, filter).await, vec![grouped]);
+}
@@ -0,0 +1,302 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Read-replica routing tests.
+//!
+//! Two `SQLite` files stand in for the primary and its replica. They are seeded with
+//! different rows, so every read reveals which database served it.
+
+use std::path::Path;
+use std::time::Duration;
+
+use figment::{Figment, providers::Serialized};
+use modkit_db::build_db;
+use modkit_db::config::{DbConnConfig, DbEngineCfg};
+use modkit_db::manager::DbManager;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{DBRunner, Db, ScopableEntity, SecureEntityExt, TxConfig, secure_insert};
+use modkit_security::{AccessScope, pep_properties};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod ent {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "replica_test")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub origin: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for ent::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(ent::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            _ => None,
+        }
+    }
+}
+
+struct CreateReplicaTest;
+
+impl mig::MigrationName for CreateReplicaTest {
+    fn name(&self) -> &'static str {
+        "m001_create_replica_test"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateReplicaTest {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("replica_test"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("origin"))
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("replica_test"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn insert(runner: &impl DBRunner, scope: &AccessScope, tenant_id: Uuid, origin: &str) {
+    let am = ent::ActiveModel {
+        tenant_id: Set(tenant_id),
+        origin: Set(origin.to_owned()),
+        ..Default::default()
+    };
+    secure_insert::<ent::Entity>(am, scope, runner)
+        .await
+        .expect("insert");
+}
+
+/// Create `file` under `dir`, migrate it and insert one row tagged `origin`.
+async fn seed(dir: &Path, file: &str, scope: &AccessScope, tenant_id: Uuid, origin: &str) {
+    let cfg = DbConnConfig {
+        engine: Some(DbEngineCfg::Sqlite),
+        path: Some(dir.join(file)),
+        ..Default::default()
+    };
+    let db = build_db(cfg, None).await.expect("connect");
+    run_migrations_for_testing(&db, vec![Box::new(CreateReplicaTest)])
+        .await
+        .expect("migrate");
+    insert(&db.conn().unwrap(), scope, tenant_id, origin).await;
+}
+
+async fn origins(runner: &impl DBRunner, scope: &AccessScope) -> Vec<String> {
+    let mut rows: Vec<String> = ent::Entity::find()
+        .secure()
+        .scope_with(scope)
+        .all(runner)
+        .await
+        .expect("select")
+        .into_iter()
+        .map(|m| m.origin)
+        .collect();
+    rows.sort();
+    rows
+}
+
+struct Setup {
+    _home: TempDir,
+    db: Db,
+    tenant_id: Uuid,
+    scope: AccessScope,
+}
+
+async fn setup(read_your_writes: Option<&str>) -> Setup {
+    let home = TempDir::new().unwrap();
+    let module_dir = home.path().join("replicated");
+    std::fs::create_dir_all(&module_dir).unwrap();
+
+    let tenant_id = Uuid::new_v4();
+    let scope = AccessScope::for_tenants(vec![tenant_id]);
+    seed(&module_dir, "primary.db", &scope, tenant_id, "primary").await;
+    seed(&module_dir, "replica.db", &scope, tenant_id, "replica").await;
+
+    let mut database = serde_json::json!({
+        "engine": "sqlite",
+        "file": "primary.db",
+        "replicas": [{ "file": "replica.db" }]
+    });
+    if let Some(window) = read_your_writes {
+        database["read_your_writes"] = window.into();
+    }
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": { "replicated": { "database": database } }
+    })));
+    let manager = DbManager::from_figment(figment, home.path().to_path_buf()).unwrap();
+    let db = manager
+        .get("replicated")
+        .await
+        .unwrap()
+        .expect("db configured");
+
+    Setup {
+        _home: home,
+        db,
+        tenant_id,
+        scope,
+    }
+}
+
+#[tokio::test]
+async fn reads_go_to_replica_and_writes_to_primary() {
+    let s = setup(None).await;
+    assert_eq!(s.db.replica_count(), 1);
+
+    let conn = s.db.conn().unwrap();
+    assert_eq!(origins(&conn, &s.scope).await, vec!["replica"]);
+
+    // The write lands on the primary; without a read-your-writes window the replica
+    // keeps serving reads.
+    insert(&conn, &s.scope, s.tenant_id, "written").await;
+    assert_eq!(origins(&conn, &s.scope).await, vec!["replica"]);
+
+    let scope = s.scope.clone();
+    let (db, primary) =
+        s.db.transaction(|tx| Box::pin(async move { Ok(origins(tx, &scope).await) }))
+            .await;
+    assert_eq!(primary.unwrap(), vec!["primary", "written"]);
+
+    let scope = s.scope.clone();
+    let (_db, replica) = db
+        .transaction_with_config(TxConfig::read_only(), |tx| {
+            Box::pin(async move { Ok(origins(tx, &scope).await) })
+        })
+        .await;
+    assert_eq!(replica.unwrap(), vec!["replica"]);
+}
+
+#[tokio::test]
+async fn read_your_writes_window_pins_reads_to_primary() {
+    let s = setup(Some("1s")).await;
+    let conn = s.db.conn().unwrap();
+    assert_eq!(origins(&conn, &s.scope).await, vec!["replica"]);
+
+    insert(&conn, &s.scope, s.tenant_id, "written").await;
+    assert_eq!(origins(&conn, &s.scope).await, vec!["primary", "written"]);
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(origins(&conn, &s.scope).await, vec!["replica"]);
+
+    // Committed read-write transactions open the window too.
+    let (scope, tenant_id) = (s.scope.clone(), s.tenant_id);
+    let (db, res) =
+        s.db.transaction(|tx| {
+            Box::pin(async move {
+                insert(tx, &scope, tenant_id, "in_tx").await;
+                Ok(())
+            })
+        })
+        .await;
+    res.unwrap();
+    let conn = db.conn().unwrap();
+    assert_eq!(
+        origins(&conn, &s.scope).await,
+        vec!["in_tx", "primary", "written"]
+    );
+}
+
+#[tokio::test]
+async fn read_your_writes_window_is_scoped_to_the_writer() {
+    let s = setup(Some("60s")).await;
+
+    // A write that fails opens no window.
+    let (db, scope) = (s.db.clone(), s.scope.clone());
+    let tenant_id = s.tenant_id;
+    tokio::spawn(async move {
+        let conn = db.conn().unwrap();
+        let duplicate = ent::ActiveModel {
+            id: Set(1),
+            tenant_id: Set(tenant_id),
+            origin: Set("duplicate".to_owned()),
+        };
+        assert!(
+            secure_insert::<ent::Entity>(duplicate, &scope, &conn)
+                .await
+                .is_err()
+        );
+        assert_eq!(origins(&conn, &scope).await, vec!["replica"]);
+    })
+    .await
+    .unwrap();
+
+    // Only the task that wrote reads from the primary.
+    let conn = s.db.conn().unwrap();
+    insert(&conn, &s.scope, s.tenant_id, "written").await;
+    assert_eq!(origins(&conn, &s.scope).await, vec!["primary", "written"]);
+
+    let (db, scope) = (s.db.clone(), s.scope.clone());
+    let other = tokio::spawn(async move { origins(&db.conn().unwrap(), &scope).await });
+    assert_eq!(other.await.unwrap(), vec!["replica"]);
+}
+
+#[tokio::test]
+async fn nested_replicas_are_rejected() {
+    let home = TempDir::new().unwrap();
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": { "replicated": { "database": {
+            "engine": "sqlite",
+            "file": "primary.db",
+            "replicas": [{ "file": "replica.db", "replicas": [{ "file": "nested.db" }] }]
+        } } }
+    })));
+    let manager = DbManager::from_figment(figment, home.path().to_path_buf()).unwrap();
+    let err = manager.get("replicated").await.unwrap_err();
+    assert!(
+        err.to_string().contains("only allowed on the primary"),
+        "{err}"
+    );
+}
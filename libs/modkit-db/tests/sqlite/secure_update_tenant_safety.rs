@@ -291,3 +291,39 @@ async fn update_many_rejects_setting_tenant_id() {
 
     assert!(matches!(err, ScopeError::Denied("tenant_id is immutable")));
 }
+
+#[tokio::test]
+async fn update_many_with_returning_stays_in_scope() {
+    use sea_orm::sea_query::Expr;
+
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant_a = Uuid::new_v4();
+    let tenant_b = Uuid::new_v4();
+
+    for tenant in [tenant_a, tenant_b] {
+        secure_insert::<tenant_ent::Entity>(
+            tenant_ent::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant),
+                name: Set("before".to_owned()),
+            },
+            &AccessScope::for_tenant(tenant),
+            &conn,
+        )
+        .await
+        .expect("insert");
+    }
+
+    let updated = tenant_ent::Entity::update_many()
+        .secure()
+        .scope_with(&AccessScope::for_tenant(tenant_a))
+        .col_expr(tenant_ent::Column::Name, Expr::value("after"))
+        .exec_with_returning(&conn)
+        .await
+        .expect("update");
+
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].tenant_id, tenant_a);
+    assert_eq!(updated[0].name, "after");
+}
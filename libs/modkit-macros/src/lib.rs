@@ -33,6 +33,7 @@ enum Capability {
     System,
     GrpcHub,
     Grpc,
+    Reconfigurable,
 }
 
 impl Capability {
@@ -44,6 +45,7 @@ impl Capability {
         "system",
         "grpc_hub",
         "grpc",
+        "reconfigurable",
     ];
 
     fn suggest_similar(input: &str) -> Vec<&'static str> {
@@ -71,11 +73,12 @@ impl Capability {
             "system" => Ok(Capability::System),
             "grpc_hub" => Ok(Capability::GrpcHub),
             "grpc" => Ok(Capability::Grpc),
+            "reconfigurable" => Ok(Capability::Reconfigurable),
             other => {
                 let suggestions = Self::suggest_similar(other);
                 let error_msg = if suggestions.is_empty() {
                     format!(
-                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc"
+                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc, reconfigurable"
                     )
                 } else {
                     format!(
@@ -98,11 +101,12 @@ impl Capability {
             "system" => Ok(Capability::System),
             "grpc_hub" => Ok(Capability::GrpcHub),
             "grpc" => Ok(Capability::Grpc),
+            "reconfigurable" => Ok(Capability::Reconfigurable),
             other => {
                 let suggestions = Self::suggest_similar(other);
                 let error_msg = if suggestions.is_empty() {
                     format!(
-                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc"
+                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc, reconfigurable"
                     )
                 } else {
                     format!(
@@ -590,6 +594,15 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                     {}
                 };
             },
+            Capability::Reconfigurable => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_ReconfigurableCapability_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::ReconfigurableCapability,
+                    {}
+                };
+            },
         };
         cap_asserts.push(q);
     }
@@ -739,6 +752,10 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                 b.register_grpc_service_with_meta(#name_lit,
                     module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::GrpcServiceCapability>);
             },
+            Capability::Reconfigurable => quote! {
+                b.register_reconfigurable_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::ReconfigurableCapability>);
+            },
         }
     });
 
@@ -17,6 +17,8 @@ pub enum FieldKind {
     Date,
     Time,
     Decimal,
+    /// JSON array of scalars; filterable only with the `any`/`all` lambda operators.
+    Json,
 }
 
 impl fmt::Display for FieldKind {
@@ -31,6 +33,7 @@ impl fmt::Display for FieldKind {
             FieldKind::Date => write!(f, "Date"),
             FieldKind::Time => write!(f, "Time"),
             FieldKind::Decimal => write!(f, "Decimal"),
+            FieldKind::Json => write!(f, "Json"),
         }
     }
 }
@@ -83,6 +86,112 @@ impl fmt::Display for FilterOp {
     }
 }
 
+/// Scalar function applied to a field before it is compared, e.g. `tolower(name) eq 'bob'`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldFn {
+    ToLower,
+    ToUpper,
+    Length,
+    /// `indexof(field, 'needle')`: zero-based position of `needle`, or `-1` if absent.
+    IndexOf(String),
+    Year,
+    Month,
+    Day,
+}
+
+impl FieldFn {
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldFn::ToLower => "tolower",
+            FieldFn::ToUpper => "toupper",
+            FieldFn::Length => "length",
+            FieldFn::IndexOf(_) => "indexof",
+            FieldFn::Year => "year",
+            FieldFn::Month => "month",
+            FieldFn::Day => "day",
+        }
+    }
+
+    /// Kind of the value the function returns.
+    #[must_use]
+    pub fn result_kind(&self) -> FieldKind {
+        match self {
+            FieldFn::ToLower | FieldFn::ToUpper => FieldKind::String,
+            FieldFn::Length
+            | FieldFn::IndexOf(_)
+            | FieldFn::Year
+            | FieldFn::Month
+            | FieldFn::Day => FieldKind::I64,
+        }
+    }
+
+    /// Whether the function can be applied to a field of `kind`.
+    #[must_use]
+    pub fn accepts(&self, kind: FieldKind) -> bool {
+        match self {
+            FieldFn::ToLower | FieldFn::ToUpper | FieldFn::Length | FieldFn::IndexOf(_) => {
+                kind == FieldKind::String
+            }
+            FieldFn::Year | FieldFn::Month | FieldFn::Day => {
+                matches!(kind, FieldKind::DateTimeUtc | FieldKind::Date)
+            }
+        }
+    }
+}
+
+impl fmt::Display for FieldFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Lambda operator over a collection field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantifier {
+    /// `field/any(x: ...)`: at least one element matches (or, without a predicate, the collection is non-empty).
+    Any,
+    /// `field/all(x: ...)`: every element matches; true for an empty collection.
+    All,
+}
+
+impl Quantifier {
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("any") {
+            Some(Quantifier::Any)
+        } else if name.eq_ignore_ascii_case("all") {
+            Some(Quantifier::All)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Quantifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quantifier::Any => write!(f, "any"),
+            Quantifier::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Predicate over the elements of a collection inside `any`/`all`.
+#[derive(Debug, Clone)]
+pub enum ElementPredicate {
+    /// `x op value`; `op` is a comparison or `contains`/`startswith`/`endswith`.
+    Compare {
+        op: FilterOp,
+        value: ODataValue,
+    },
+    /// `x in (v1, v2, ...)`
+    In(Vec<ODataValue>),
+    And(Box<ElementPredicate>, Box<ElementPredicate>),
+    Or(Box<ElementPredicate>, Box<ElementPredicate>),
+    Not(Box<ElementPredicate>),
+}
+
 #[derive(Debug, Clone)]
 pub enum FilterNode<F: FilterField> {
     Binary {
@@ -90,6 +199,24 @@ pub enum FilterNode<F: FilterField> {
         op: FilterOp,
         value: ODataValue,
     },
+    /// `func(field) op value`, e.g. `year(created_at) ge 2024` or `contains(tolower(name), 'bob')`.
+    Function {
+        func: FieldFn,
+        field: F,
+        op: FilterOp,
+        value: ODataValue,
+    },
+    /// `field in (v1, v2, ...)`
+    In {
+        field: F,
+        values: Vec<ODataValue>,
+    },
+    /// `field/any(x: ...)` or `field/all(x: ...)` over a [`FieldKind::Json`] array.
+    Lambda {
+        quantifier: Quantifier,
+        field: F,
+        predicate: Option<Box<ElementPredicate>>,
+    },
     Composite {
         op: FilterOp,
         children: Vec<FilterNode<F>>,
@@ -166,6 +293,7 @@ pub fn parse_odata_filter<F: FilterField>(raw: &str) -> FilterResult<FilterNode<
     {
         use odata_params::filters::parse_str;
 
+        let raw = crate::lambda::rewrite_lambdas(raw).map_err(FilterError::InvalidExpression)?;
         let ast = parse_str(raw).map_err(|e| FilterError::InvalidExpression(format!("{e:?}")))?;
         let ast: odata_ast::Expr = ast.into();
         convert_expr_to_filter_node::<F>(&ast)
@@ -210,6 +338,9 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
         E::Compare(left, op, right) => {
             let (field_name, value) = match (&**left, &**right) {
                 (E::Identifier(name), E::Value(val)) => (name.as_str(), val.clone()),
+                (E::Function(name, args), E::Value(val)) => {
+                    return convert_function_compare::<F>(name, args, compare_op(*op), val);
+                }
                 (E::Identifier(_), E::Identifier(_)) => {
                     return Err(FilterError::FieldToFieldComparison);
                 }
@@ -225,76 +356,38 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
 
             validate_value_type(field, &value)?;
 
-            let filter_op = match op {
-                odata_ast::CompareOperator::Eq => FilterOp::Eq,
-                odata_ast::CompareOperator::Ne => FilterOp::Ne,
-                odata_ast::CompareOperator::Gt => FilterOp::Gt,
-                odata_ast::CompareOperator::Ge => FilterOp::Ge,
-                odata_ast::CompareOperator::Lt => FilterOp::Lt,
-                odata_ast::CompareOperator::Le => FilterOp::Le,
-            };
-
-            Ok(FilterNode::binary(field, filter_op, value))
+            Ok(FilterNode::binary(field, compare_op(*op), value))
         }
 
         E::Function(func_name, args) => {
-            let name_lower = func_name.to_ascii_lowercase();
-            match (name_lower.as_str(), args.as_slice()) {
-                (
-                    "contains",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
-                    let field = F::from_name(field_name)
-                        .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
-
-                    if field.kind() != FieldKind::String {
-                        return Err(FilterError::TypeMismatch {
-                            field: field_name.clone(),
-                            expected: FieldKind::String,
-                            got: "non-string".to_owned(),
-                        });
-                    }
-
-                    Ok(FilterNode::binary(
-                        field,
-                        FilterOp::Contains,
-                        odata_ast::Value::String(s.clone()),
-                    ))
+            if let Some(quantifier) = Quantifier::from_name(func_name) {
+                let (field_name, predicate) = lambda_operands(quantifier, args)?;
+                let field = F::from_name(field_name)
+                    .ok_or_else(|| FilterError::UnknownField(field_name.to_owned()))?;
+                if field.kind() != FieldKind::Json {
+                    return Err(FilterError::TypeMismatch {
+                        field: field_name.to_owned(),
+                        expected: FieldKind::Json,
+                        got: field.kind().to_string(),
+                    });
                 }
-                (
-                    "startswith",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
-                    let field = F::from_name(field_name)
-                        .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
-
-                    if field.kind() != FieldKind::String {
-                        return Err(FilterError::TypeMismatch {
-                            field: field_name.clone(),
-                            expected: FieldKind::String,
-                            got: "non-string".to_owned(),
-                        });
-                    }
+                return Ok(FilterNode::Lambda {
+                    quantifier,
+                    field,
+                    predicate: predicate.map(Box::new),
+                });
+            }
 
-                    Ok(FilterNode::binary(
-                        field,
-                        FilterOp::StartsWith,
-                        odata_ast::Value::String(s.clone()),
-                    ))
-                }
-                (
-                    "endswith",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
+            let Some(op) = string_match_op(func_name) else {
+                return Err(FilterError::UnsupportedOperation(format!(
+                    "Function '{func_name}'"
+                )));
+            };
+            match args.as_slice() {
+                [
+                    E::Identifier(field_name),
+                    E::Value(odata_ast::Value::String(s)),
+                ] => {
                     let field = F::from_name(field_name)
                         .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
 
@@ -308,25 +401,252 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
 
                     Ok(FilterNode::binary(
                         field,
-                        FilterOp::EndsWith,
+                        op,
                         odata_ast::Value::String(s.clone()),
                     ))
                 }
+                [E::Function(inner, inner_args), E::Value(value)] => {
+                    convert_function_compare::<F>(inner, inner_args, op, value)
+                }
                 _ => Err(FilterError::UnsupportedOperation(format!(
                     "Function '{func_name}'"
                 ))),
             }
         }
 
-        E::In(_left, _list) => Err(FilterError::UnsupportedOperation(
-            "IN operator not yet supported in typed filters".to_owned(),
-        )),
+        E::In(left, list) => {
+            let E::Identifier(field_name) = &**left else {
+                return Err(FilterError::InvalidExpression(
+                    "Left side of IN must be a field".to_owned(),
+                ));
+            };
+            let field = F::from_name(field_name)
+                .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
+            let values = list
+                .iter()
+                .map(|item| match item {
+                    E::Value(v) => validate_value_type(field, v).map(|()| v.clone()),
+                    _ => Err(FilterError::InvalidExpression(
+                        "IN list supports only literals".to_owned(),
+                    )),
+                })
+                .collect::<FilterResult<Vec<_>>>()?;
+            Ok(FilterNode::In { field, values })
+        }
 
         E::Identifier(name) => Err(FilterError::BareIdentifier(name.clone())),
         E::Value(_) => Err(FilterError::BareLiteral),
     }
 }
 
+fn compare_op(op: odata_ast::CompareOperator) -> FilterOp {
+    match op {
+        odata_ast::CompareOperator::Eq => FilterOp::Eq,
+        odata_ast::CompareOperator::Ne => FilterOp::Ne,
+        odata_ast::CompareOperator::Gt => FilterOp::Gt,
+        odata_ast::CompareOperator::Ge => FilterOp::Ge,
+        odata_ast::CompareOperator::Lt => FilterOp::Lt,
+        odata_ast::CompareOperator::Le => FilterOp::Le,
+    }
+}
+
+/// Map `contains`/`startswith`/`endswith` to their `FilterOp`.
+fn string_match_op(name: &str) -> Option<FilterOp> {
+    match name.to_ascii_lowercase().as_str() {
+        "contains" => Some(FilterOp::Contains),
+        "startswith" => Some(FilterOp::StartsWith),
+        "endswith" => Some(FilterOp::EndsWith),
+        _ => None,
+    }
+}
+
+/// Split a function operand such as `tolower(name)` or `indexof(name, 'x')` into the
+/// function and the name of the field it is applied to.
+///
+/// # Errors
+///
+/// Returns `FilterError::UnsupportedOperation` for unknown functions or argument shapes.
+pub fn field_function<'a>(
+    name: &str,
+    args: &'a [odata_ast::Expr],
+) -> FilterResult<(FieldFn, &'a str)> {
+    use odata_ast::Expr as E;
+
+    let func = match name.to_ascii_lowercase().as_str() {
+        "tolower" => FieldFn::ToLower,
+        "toupper" => FieldFn::ToUpper,
+        "length" => FieldFn::Length,
+        "year" => FieldFn::Year,
+        "month" => FieldFn::Month,
+        "day" => FieldFn::Day,
+        "indexof" => match args {
+            [
+                E::Identifier(field),
+                E::Value(odata_ast::Value::String(needle)),
+            ] => {
+                return Ok((FieldFn::IndexOf(needle.clone()), field.as_str()));
+            }
+            _ => {
+                return Err(FilterError::UnsupportedOperation(
+                    "indexof() expects a field and a string".to_owned(),
+                ));
+            }
+        },
+        _ => {
+            return Err(FilterError::UnsupportedOperation(format!(
+                "Function '{name}'"
+            )));
+        }
+    };
+
+    match args {
+        [E::Identifier(field)] => Ok((func, field.as_str())),
+        _ => Err(FilterError::UnsupportedOperation(format!(
+            "{func}() expects a single field argument"
+        ))),
+    }
+}
+
+/// `func(field) op value`, with `op` a comparison or a string match.
+fn convert_function_compare<F: FilterField>(
+    name: &str,
+    args: &[odata_ast::Expr],
+    op: FilterOp,
+    value: &odata_ast::Value,
+) -> FilterResult<FilterNode<F>> {
+    let (func, field_name) = field_function(name, args)?;
+    let field =
+        F::from_name(field_name).ok_or_else(|| FilterError::UnknownField(field_name.to_owned()))?;
+
+    if !func.accepts(field.kind()) {
+        return Err(FilterError::UnsupportedOperation(format!(
+            "{func}() on {} field '{field_name}'",
+            field.kind()
+        )));
+    }
+
+    let expected = func.result_kind();
+    let matches = matches!(
+        (expected, value),
+        (FieldKind::String, odata_ast::Value::String(_))
+            | (FieldKind::I64, odata_ast::Value::Number(_))
+    );
+    if !matches {
+        return Err(FilterError::TypeMismatch {
+            field: format!("{func}({field_name})"),
+            expected,
+            got: value.to_string(),
+        });
+    }
+
+    Ok(FilterNode::Function {
+        func,
+        field,
+        op,
+        value: value.clone(),
+    })
+}
+
+/// Split the arguments of an `any`/`all` call (as produced by the lambda rewrite) into
+/// the collection field name and the optional element predicate.
+///
+/// # Errors
+///
+/// Returns `FilterError::InvalidExpression` if the arguments are malformed or `all` has
+/// no predicate, and any error from [`convert_element_predicate`].
+pub fn lambda_operands(
+    quantifier: Quantifier,
+    args: &[odata_ast::Expr],
+) -> FilterResult<(&str, Option<ElementPredicate>)> {
+    match (quantifier, args) {
+        (Quantifier::Any, [odata_ast::Expr::Identifier(field)]) => Ok((field.as_str(), None)),
+        (_, [odata_ast::Expr::Identifier(field), body]) => {
+            Ok((field.as_str(), Some(convert_element_predicate(body)?)))
+        }
+        (Quantifier::All, [odata_ast::Expr::Identifier(_)]) => Err(FilterError::InvalidExpression(
+            "all() requires a predicate".to_owned(),
+        )),
+        _ => Err(FilterError::InvalidExpression(format!(
+            "{quantifier}() must be applied to a collection field"
+        ))),
+    }
+}
+
+/// Convert the body of an `any`/`all` lambda into an `ElementPredicate`.
+///
+/// The body may only reference the range variable ([`odata_ast::LAMBDA_VAR`]) and compare
+/// it against string, number or boolean literals.
+///
+/// # Errors
+///
+/// Returns `FilterError` for references to other fields, nested lambdas, or unsupported forms.
+pub fn convert_element_predicate(expr: &odata_ast::Expr) -> FilterResult<ElementPredicate> {
+    use odata_ast::Expr as E;
+
+    let is_element = |e: &E| matches!(e, E::Identifier(name) if name == odata_ast::LAMBDA_VAR);
+
+    match expr {
+        E::And(a, b) => Ok(ElementPredicate::And(
+            Box::new(convert_element_predicate(a)?),
+            Box::new(convert_element_predicate(b)?),
+        )),
+        E::Or(a, b) => Ok(ElementPredicate::Or(
+            Box::new(convert_element_predicate(a)?),
+            Box::new(convert_element_predicate(b)?),
+        )),
+        E::Not(inner) => Ok(ElementPredicate::Not(Box::new(convert_element_predicate(
+            inner,
+        )?))),
+        E::Compare(left, op, right) if is_element(left) => match &**right {
+            E::Value(value) => Ok(ElementPredicate::Compare {
+                op: compare_op(*op),
+                value: element_literal(value)?,
+            }),
+            _ => Err(FilterError::InvalidExpression(
+                "Lambda comparisons must be against a literal".to_owned(),
+            )),
+        },
+        E::Function(name, args) => match (string_match_op(name), args.as_slice()) {
+            (Some(op), [element, E::Value(odata_ast::Value::String(s))]) if is_element(element) => {
+                Ok(ElementPredicate::Compare {
+                    op,
+                    value: odata_ast::Value::String(s.clone()),
+                })
+            }
+            _ if Quantifier::from_name(name).is_some() => Err(FilterError::UnsupportedOperation(
+                "Nested lambda operators".to_owned(),
+            )),
+            _ => Err(FilterError::UnsupportedOperation(format!(
+                "Function '{name}' in lambda predicate"
+            ))),
+        },
+        E::In(left, list) if is_element(left) => list
+            .iter()
+            .map(|item| match item {
+                E::Value(value) => element_literal(value),
+                _ => Err(FilterError::InvalidExpression(
+                    "IN list supports only literals".to_owned(),
+                )),
+            })
+            .collect::<FilterResult<Vec<_>>>()
+            .map(ElementPredicate::In),
+        _ => Err(FilterError::InvalidExpression(
+            "Lambda predicates may only compare the range variable with literals".to_owned(),
+        )),
+    }
+}
+
+fn element_literal(value: &odata_ast::Value) -> FilterResult<odata_ast::Value> {
+    match value {
+        odata_ast::Value::String(_) | odata_ast::Value::Number(_) | odata_ast::Value::Bool(_) => {
+            Ok(value.clone())
+        }
+        other => Err(FilterError::InvalidExpression(format!(
+            "Unsupported {other} literal in lambda predicate"
+        ))),
+    }
+}
+
 fn validate_value_type<F: FilterField>(field: F, value: &odata_ast::Value) -> FilterResult<()> {
     use odata_ast::Value as V;
 
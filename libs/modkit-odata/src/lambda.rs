@@ -0,0 +1,210 @@
+//! Pre-parse rewrite of `OData` lambda operators.
+//!
+//! The underlying `$filter` grammar has no path segments, so `tags/any(t: t eq 'x')`
+//! cannot be parsed directly. Before parsing, lambdas are rewritten into plain function
+//! calls with the range variable replaced by a reserved identifier:
+//!
+//! - `tags/any(t: t eq 'x')` → `any(tags, __it eq 'x')`
+//! - `tags/all(t: t gt 1)` → `all(tags, __it gt 1)`
+//! - `tags/any()` → `any(tags)`
+//!
+//! The conversion into [`crate::ast::Expr`] then maps `__it` to [`crate::ast::LAMBDA_VAR`].
+
+use std::borrow::Cow;
+
+/// Identifier substituted for the lambda range variable before parsing.
+pub const LAMBDA_PLACEHOLDER: &str = "__it";
+
+/// An open lambda: its range variable and the paren depth of its opening `(`.
+struct Scope {
+    var: String,
+    depth: usize,
+}
+
+/// Rewrite `field/any(v: ...)` and `field/all(v: ...)` into function-call form.
+///
+/// Returns the input unchanged if it contains no `/`.
+///
+/// # Errors
+/// Returns a message if a lambda is malformed (missing `(`, range variable, or `:`).
+pub fn rewrite_lambdas(raw: &str) -> Result<Cow<'_, str>, String> {
+    if !raw.contains('/') {
+        return Ok(Cow::Borrowed(raw));
+    }
+
+    let chars: Vec<char> = raw.chars().collect();
+    let mut out = String::with_capacity(raw.len() + 8);
+    let mut scopes: Vec<Scope> = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' => {
+                // Copy the string literal verbatim, honouring backslash escapes.
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        out.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if chars[i - 1] == '\'' {
+                        break;
+                    }
+                }
+            }
+            '(' => {
+                depth += 1;
+                out.push(c);
+                i += 1;
+            }
+            ')' => {
+                if scopes.last().is_some_and(|s| s.depth == depth) {
+                    scopes.pop();
+                }
+                depth = depth.saturating_sub(1);
+                out.push(c);
+                i += 1;
+            }
+            c if is_ident_start(c) && (i == 0 || !is_ident_char(chars[i - 1])) => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+
+                if let Some((quantifier, next)) = lambda_operator(&chars, i) {
+                    let (var, next) = lambda_variable(&chars, next)
+                        .ok_or_else(|| format!("malformed lambda after '{ident}/{quantifier}'"))?;
+                    out.push_str(quantifier);
+                    out.push('(');
+                    out.push_str(&ident);
+                    depth += 1;
+                    if let Some(var) = var {
+                        out.push_str(", ");
+                        scopes.push(Scope { var, depth });
+                    }
+                    i = next;
+                } else if scopes.iter().any(|s| s.var == ident) {
+                    out.push_str(LAMBDA_PLACEHOLDER);
+                } else {
+                    out.push_str(&ident);
+                }
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Ok(Cow::Owned(out))
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn skip_ws(chars: &[char], mut i: usize) -> usize {
+    while i < chars.len() && chars[i].is_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// Match `/any(` or `/all(` at `i`; returns the operator and the index after `(`.
+fn lambda_operator(chars: &[char], i: usize) -> Option<(&'static str, usize)> {
+    if chars.get(i) != Some(&'/') {
+        return None;
+    }
+    let word: String = chars.get(i + 1..i + 4)?.iter().collect();
+    let quantifier = if word.eq_ignore_ascii_case("any") {
+        "any"
+    } else if word.eq_ignore_ascii_case("all") {
+        "all"
+    } else {
+        return None;
+    };
+    if chars.get(i + 4).copied().is_some_and(is_ident_char) {
+        return None;
+    }
+    let open = skip_ws(chars, i + 4);
+    (chars.get(open) == Some(&'(')).then_some((quantifier, open + 1))
+}
+
+/// Parse `v:` (or an empty `)`) after the lambda's `(`.
+///
+/// Returns the range variable (`None` for `any()`) and the index to continue from;
+/// for `any()` the closing `)` is left in place.
+fn lambda_variable(chars: &[char], i: usize) -> Option<(Option<String>, usize)> {
+    let i = skip_ws(chars, i);
+    if chars.get(i) == Some(&')') {
+        return Some((None, i));
+    }
+    if !chars.get(i).copied().is_some_and(is_ident_start) {
+        return None;
+    }
+    let mut end = i;
+    while end < chars.len() && is_ident_char(chars[end]) {
+        end += 1;
+    }
+    let colon = skip_ws(chars, end);
+    (chars.get(colon) == Some(&':')).then(|| {
+        (
+            Some(chars[i..end].iter().collect()),
+            skip_ws(chars, colon + 1),
+        )
+    })
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_plain_filters_untouched() {
+        let raw = "name eq 'a/b' and age gt 3";
+        assert_eq!(rewrite_lambdas(raw).unwrap(), raw);
+        assert!(matches!(
+            rewrite_lambdas("name eq 'x'").unwrap(),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn rewrites_any_and_all() {
+        assert_eq!(
+            rewrite_lambdas("tags/any(t: t eq 'x' or t eq 'y') and id eq 1").unwrap(),
+            "any(tags, __it eq 'x' or __it eq 'y') and id eq 1"
+        );
+        assert_eq!(
+            rewrite_lambdas("scores/ALL(s:s gt 10)").unwrap(),
+            "all(scores, __it gt 10)"
+        );
+        assert_eq!(rewrite_lambdas("tags/any()").unwrap(), "any(tags)");
+    }
+
+    #[test]
+    fn only_renames_the_range_variable_inside_the_lambda() {
+        assert_eq!(
+            rewrite_lambdas("tags/any(t: contains(t, 't')) and t eq 'tags/any'").unwrap(),
+            "any(tags, contains(__it, 't')) and t eq 'tags/any'"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lambdas() {
+        assert!(rewrite_lambdas("tags/any(t eq 'x')").is_err());
+        assert!(rewrite_lambdas("tags/any(: t eq 'x')").is_err());
+    }
+}
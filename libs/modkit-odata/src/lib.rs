@@ -2,6 +2,8 @@
 pub mod builder;
 pub mod errors;
 pub mod filter;
+#[cfg(feature = "with-odata-params")]
+mod lambda;
 pub mod limits;
 pub mod page;
 pub mod pagination;
@@ -19,6 +21,12 @@ pub mod ast {
     use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
     use uuid::Uuid;
 
+    /// Identifier of the range variable inside `any`/`all` lambda predicates.
+    ///
+    /// `tags/any(t: t eq 'x')` parses into
+    /// `Function("any", [Identifier("tags"), Compare(Identifier(LAMBDA_VAR), Eq, 'x')])`.
+    pub const LAMBDA_VAR: &str = "$it";
+
     #[derive(Clone, Debug)]
     pub enum Expr {
         And(Box<Expr>, Box<Expr>),
@@ -318,6 +326,13 @@ pub enum Error {
     #[error("invalid cursor: invalid sort direction")]
     CursorInvalidDirection,
 
+    // $search / $expand errors
+    #[error("invalid $search: {0}")]
+    InvalidSearch(String),
+
+    #[error("unsupported $expand relation: {0}")]
+    InvalidExpand(String),
+
     // Database and low-level errors
     #[error("database error: {0}")]
     Db(String),
@@ -466,6 +481,12 @@ pub struct ODataQuery {
     pub cursor: Option<CursorV1>,
     pub filter_hash: Option<String>,
     pub select: Option<Vec<String>>,
+    /// `$count=true`: include the total number of matching items in `PageInfo`.
+    pub count: bool,
+    /// `$search`: free-text search across the fields the endpoint declares searchable.
+    pub search: Option<String>,
+    /// `$expand`: related entities to inline in each item.
+    pub expand: Option<Vec<String>>,
 }
 
 impl ODataQuery {
@@ -503,6 +524,21 @@ impl ODataQuery {
         self
     }
 
+    pub fn with_count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn with_search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    pub fn with_expand(mut self, relations: Vec<String>) -> Self {
+        self.expand = Some(relations);
+        self
+    }
+
     /// Get filter as AST
     #[must_use]
     pub fn filter(&self) -> Option<&ast::Expr> {
@@ -532,6 +568,38 @@ impl ODataQuery {
     pub fn selected_fields(&self) -> Option<&[String]> {
         self.select.as_deref()
     }
+
+    /// Get the `$search` text, if any
+    #[must_use]
+    pub fn search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
+
+    /// Get expanded relations
+    #[must_use]
+    pub fn expanded(&self) -> Option<&[String]> {
+        self.expand.as_deref()
+    }
+
+    /// Check if `relation` was requested via `$expand` (case-insensitive)
+    #[must_use]
+    pub fn expands(&self, relation: &str) -> bool {
+        self.expanded()
+            .is_some_and(|rels| rels.iter().any(|r| r.eq_ignore_ascii_case(relation)))
+    }
+
+    /// Ensure every `$expand` relation is one of `allowed` (case-insensitive).
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidExpand` naming the first relation that is not allowed.
+    pub fn ensure_expand_allowed(&self, allowed: &[&str]) -> Result<(), Error> {
+        for rel in self.expanded().unwrap_or_default() {
+            if !allowed.iter().any(|a| a.eq_ignore_ascii_case(rel)) {
+                return Err(Error::InvalidExpand(rel.clone()));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl From<Option<ast::Expr>> for ODataQuery {
@@ -547,7 +615,8 @@ mod tests;
 
 #[cfg(feature = "with-odata-params")]
 mod convert_odata_params {
-    use super::ast::{CompareOperator, Expr, Value};
+    use super::ast::{CompareOperator, Expr, LAMBDA_VAR, Value};
+    use super::lambda::LAMBDA_PLACEHOLDER;
     use odata_params::filters as od;
 
     impl From<od::CompareOperator> for CompareOperator {
@@ -596,6 +665,7 @@ mod convert_odata_params {
                     list.into_iter().map(Into::into).collect(),
                 ),
                 Function(n, args) => Expr::Function(n, args.into_iter().map(Into::into).collect()),
+                Identifier(s) if s == LAMBDA_PLACEHOLDER => Expr::Identifier(LAMBDA_VAR.to_owned()),
                 Identifier(s) => Expr::Identifier(s),
                 Value(v) => Expr::Value(v.into()),
             }
@@ -662,6 +732,7 @@ pub fn parse_filter_string(raw: &str) -> Result<ParsedFilter, Error> {
         }
     }
 
+    let raw = lambda::rewrite_lambdas(raw).map_err(Error::InvalidFilter)?;
     let ast_src = od::parse_str(raw).map_err(|e| Error::InvalidFilter(format!("{e:?}")))?;
 
     let node_count = count_ast_nodes(&ast_src);
@@ -6,6 +6,9 @@ pub struct PageInfo {
     pub next_cursor: Option<String>,
     pub prev_cursor: Option<String>,
     pub limit: u64,
+    /// Total number of items matching the query, present when `$count=true` was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
 }
 
 #[cfg_attr(feature = "with-utoipa", derive(utoipa::ToSchema))]
@@ -31,6 +34,7 @@ impl<T> Page<T> {
                 next_cursor: None,
                 prev_cursor: None,
                 limit,
+                total_count: None,
             },
         }
     }
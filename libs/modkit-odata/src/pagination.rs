@@ -90,6 +90,24 @@ pub fn short_filter_hash(expr: Option<&ast::Expr>) -> Option<String> {
     })
 }
 
+/// Like [`short_filter_hash`], but also covers the `$search` text so cursors
+/// cannot be replayed against a different result set.
+/// Without a search this is identical to [`short_filter_hash`].
+#[must_use]
+pub fn short_query_hash(expr: Option<&ast::Expr>, search: Option<&str>) -> Option<String> {
+    let Some(search) = search else {
+        return short_filter_hash(expr);
+    };
+    let mut normalized = expr.map(normalize_filter_for_hash).unwrap_or_default();
+    normalized.push_str("|SEARCH(");
+    normalized.push_str(search);
+    normalized.push(')');
+    let mut hasher = Sha256::new();
+    hasher.update(normalized.as_bytes());
+    let bytes = hasher.finalize();
+    Some(hex::encode(&bytes[..8]))
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -137,4 +155,21 @@ mod tests {
     fn test_short_filter_hash_none() {
         assert_eq!(short_filter_hash(None), None);
     }
+
+    #[test]
+    fn test_short_query_hash_covers_search() {
+        let expr = Expr::Identifier("active".to_owned());
+
+        assert_eq!(
+            short_query_hash(Some(&expr), None),
+            short_filter_hash(Some(&expr))
+        );
+        assert_eq!(short_query_hash(None, None), None);
+
+        let with_search = short_query_hash(Some(&expr), Some("alice"));
+        assert!(with_search.is_some());
+        assert_ne!(with_search, short_filter_hash(Some(&expr)));
+        assert_ne!(with_search, short_query_hash(Some(&expr), Some("bob")));
+        assert!(short_query_hash(None, Some("alice")).is_some());
+    }
 }
@@ -13,8 +13,8 @@ impl From<Error> for Problem {
         use Error::{
             CursorInvalidBase64, CursorInvalidDirection, CursorInvalidFields, CursorInvalidJson,
             CursorInvalidKeys, CursorInvalidVersion, Db, FilterMismatch, InvalidCursor,
-            InvalidFilter, InvalidLimit, InvalidOrderByField, OrderMismatch, OrderWithCursor,
-            ParsingUnavailable,
+            InvalidExpand, InvalidFilter, InvalidLimit, InvalidOrderByField, InvalidSearch,
+            OrderMismatch, OrderWithCursor, ParsingUnavailable,
         };
 
         match err {
@@ -26,6 +26,13 @@ impl From<Error> for Problem {
             InvalidOrderByField(field) => ErrorCode::odata_errors_invalid_orderby_v1()
                 .as_problem(format!("Unsupported $orderby field: {field}")),
 
+            // $search / $expand errors → 422
+            InvalidSearch(msg) => ErrorCode::odata_errors_invalid_search_v1()
+                .as_problem(format!("Invalid $search: {msg}")),
+
+            InvalidExpand(rel) => ErrorCode::odata_errors_invalid_expand_v1()
+                .as_problem(format!("Unsupported $expand relation: {rel}")),
+
             // All cursor-related errors → 422
             InvalidCursor
             | CursorInvalidBase64
@@ -109,4 +116,19 @@ mod tests {
         assert!(problem.code.contains("odata"));
         assert!(problem.code.contains("invalid_cursor"));
     }
+
+    #[test]
+    fn test_search_and_expand_errors_convert_to_problem() {
+        use http::StatusCode;
+
+        let problem: Problem = Error::InvalidSearch("not supported".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.title, "Invalid Search");
+        assert!(problem.code.contains("invalid_search"));
+
+        let problem: Problem = Error::InvalidExpand("owner".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.title, "Invalid Expand");
+        assert!(problem.detail.contains("owner"));
+    }
 }
@@ -382,4 +382,25 @@ mod tests {
             "unsupported $orderby field: unknown_field"
         );
     }
+
+    #[test]
+    fn test_odata_query_count_search_expand() {
+        let query = ODataQuery::new()
+            .with_count(true)
+            .with_search("alice")
+            .with_expand(vec!["owner".to_owned(), "Tags".to_owned()]);
+
+        assert!(query.count);
+        assert_eq!(query.search(), Some("alice"));
+        assert!(query.expands("OWNER"));
+        assert!(query.expands("tags"));
+        assert!(!query.expands("members"));
+
+        assert!(query.ensure_expand_allowed(&["owner", "tags"]).is_ok());
+        assert!(matches!(
+            query.ensure_expand_allowed(&["owner"]),
+            Err(Error::InvalidExpand(rel)) if rel == "Tags"
+        ));
+        assert!(ODataQuery::new().ensure_expand_allowed(&[]).is_ok());
+    }
 }
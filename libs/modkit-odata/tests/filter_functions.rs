@@ -0,0 +1,163 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[cfg(feature = "with-odata-params")]
+mod tests {
+    use modkit_odata::filter::{
+        ElementPredicate, FieldFn, FieldKind, FilterError, FilterField, FilterNode, FilterOp,
+        ODataValue, Quantifier, parse_odata_filter,
+    };
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    enum Field {
+        Name,
+        CreatedAt,
+        Score,
+        Tags,
+    }
+
+    impl FilterField for Field {
+        const FIELDS: &'static [Self] = &[Field::Name, Field::CreatedAt, Field::Score, Field::Tags];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Field::Name => "name",
+                Field::CreatedAt => "created_at",
+                Field::Score => "score",
+                Field::Tags => "tags",
+            }
+        }
+
+        fn kind(&self) -> FieldKind {
+            match self {
+                Field::Name => FieldKind::String,
+                Field::CreatedAt => FieldKind::DateTimeUtc,
+                Field::Score => FieldKind::I64,
+                Field::Tags => FieldKind::Json,
+            }
+        }
+    }
+
+    fn parse(raw: &str) -> FilterNode<Field> {
+        parse_odata_filter::<Field>(raw).unwrap()
+    }
+
+    #[test]
+    fn string_and_date_functions() {
+        let node = parse("tolower(name) eq 'bob'");
+        assert!(matches!(
+            node,
+            FilterNode::Function { func: FieldFn::ToLower, field: Field::Name, op: FilterOp::Eq, value: ODataValue::String(ref s) } if s == "bob"
+        ));
+
+        let node = parse("endswith(toupper(name), 'OB')");
+        assert!(matches!(
+            node,
+            FilterNode::Function {
+                func: FieldFn::ToUpper,
+                op: FilterOp::EndsWith,
+                ..
+            }
+        ));
+
+        let node = parse("indexof(name, 'o') ge 1");
+        assert!(matches!(
+            node,
+            FilterNode::Function { func: FieldFn::IndexOf(ref needle), op: FilterOp::Ge, .. } if needle == "o"
+        ));
+
+        let node = parse("year(created_at) eq 2024 and month(created_at) le 6");
+        let FilterNode::Composite { children, .. } = node else {
+            panic!("expected composite");
+        };
+        assert!(matches!(
+            children[0],
+            FilterNode::Function {
+                func: FieldFn::Year,
+                field: Field::CreatedAt,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn functions_are_checked_against_field_kinds() {
+        assert!(matches!(
+            parse_odata_filter::<Field>("year(name) eq 2024"),
+            Err(FilterError::UnsupportedOperation(_))
+        ));
+        assert!(matches!(
+            parse_odata_filter::<Field>("length(name) eq 'x'"),
+            Err(FilterError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            parse_odata_filter::<Field>("tolower(score) eq 'x'"),
+            Err(FilterError::UnsupportedOperation(_))
+        ));
+        assert!(matches!(
+            parse_odata_filter::<Field>("trim(name) eq 'x'"),
+            Err(FilterError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[test]
+    fn in_operator() {
+        let node = parse("score in (1, 2, 3)");
+        assert!(
+            matches!(node, FilterNode::In { field: Field::Score, ref values } if values.len() == 3)
+        );
+
+        assert!(matches!(
+            parse_odata_filter::<Field>("score in ('a')"),
+            Err(FilterError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn lambda_operators() {
+        let node = parse("tags/any(t: t eq 'red' or startswith(t, 'bl'))");
+        let FilterNode::Lambda {
+            quantifier: Quantifier::Any,
+            field: Field::Tags,
+            predicate: Some(predicate),
+        } = node
+        else {
+            panic!("expected any lambda");
+        };
+        assert!(matches!(*predicate, ElementPredicate::Or(..)));
+
+        let node = parse("tags/all(x: x in ('a', 'b'))");
+        assert!(matches!(
+            node,
+            FilterNode::Lambda {
+                quantifier: Quantifier::All,
+                predicate: Some(_),
+                ..
+            }
+        ));
+
+        let node = parse("tags/any()");
+        assert!(matches!(
+            node,
+            FilterNode::Lambda {
+                quantifier: Quantifier::Any,
+                predicate: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn lambda_errors() {
+        // Only JSON collections have elements
+        assert!(matches!(
+            parse_odata_filter::<Field>("name/any(t: t eq 'x')"),
+            Err(FilterError::TypeMismatch { .. })
+        ));
+        // The predicate may not reference other fields
+        assert!(parse_odata_filter::<Field>("tags/any(t: name eq 'x')").is_err());
+        // `all` needs a predicate
+        assert!(parse_odata_filter::<Field>("tags/all()").is_err());
+        // Nested lambdas are not supported
+        assert!(parse_odata_filter::<Field>("tags/any(t: tags/any(u: u eq 'x'))").is_err());
+    }
+}
@@ -1,4 +1,7 @@
+use std::cmp::Ordering;
 use std::fmt;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
 use uuid::Uuid;
 
 /// A scalar value for scope filtering.
@@ -16,6 +19,8 @@ pub enum ScopeValue {
     Int(i64),
     /// Boolean value.
     Bool(bool),
+    /// Timestamp value (creation/modification times, expiry, etc.)
+    Timestamp(OffsetDateTime),
 }
 
 impl ScopeValue {
@@ -28,7 +33,21 @@ impl ScopeValue {
         match self {
             Self::Uuid(u) => Some(*u),
             Self::String(s) => Uuid::parse_str(s).ok(),
-            Self::Int(_) | Self::Bool(_) => None,
+            Self::Int(_) | Self::Bool(_) | Self::Timestamp(_) => None,
+        }
+    }
+
+    /// Order this value against another of the same kind.
+    ///
+    /// Integers, strings and timestamps are ordered; other kinds, and values
+    /// of different kinds, are not comparable.
+    #[must_use]
+    pub fn compare_to(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => Some(a.cmp(b)),
+            (Self::String(a), Self::String(b)) => Some(a.cmp(b)),
+            (Self::Timestamp(a), Self::Timestamp(b)) => Some(a.cmp(b)),
+            _ => None,
         }
     }
 }
@@ -40,6 +59,10 @@ impl fmt::Display for ScopeValue {
             Self::String(s) => write!(f, "{s}"),
             Self::Int(n) => write!(f, "{n}"),
             Self::Bool(b) => write!(f, "{b}"),
+            Self::Timestamp(t) => match t.format(&Rfc3339) {
+                Ok(s) => write!(f, "{s}"),
+                Err(_) => write!(f, "{t}"),
+            },
         }
     }
 }
@@ -86,6 +109,13 @@ impl From<bool> for ScopeValue {
     }
 }
 
+impl From<OffsetDateTime> for ScopeValue {
+    #[inline]
+    fn from(t: OffsetDateTime) -> Self {
+        Self::Timestamp(t)
+    }
+}
+
 /// Well-known authorization property names.
 ///
 /// These constants are shared between the PEP compiler and the ORM condition
@@ -110,18 +140,56 @@ pub mod pep_properties {
 /// Variants mirror the predicate types from the PDP response:
 /// - [`ScopeFilter::Eq`] — equality (`property = value`)
 /// - [`ScopeFilter::In`] — set membership (`property IN (values)`)
+/// - [`ScopeFilter::Compare`] — range (`property > value`, `<=`, ...)
+/// - [`ScopeFilter::InTenantSubtree`] — tenant hierarchy via `tenant_closure`
+/// - [`ScopeFilter::InGroup`] / [`ScopeFilter::InGroupSubtree`] — resource
+///   group membership via `resource_group_membership` / `resource_group_closure`
+/// - [`ScopeFilter::Not`] — negation of another filter
 ///
-/// ## Future extensions
-///
-/// Additional filter types (`in_tenant_subtree`, `in_group`,
-/// `in_group_subtree`) are planned. See the authorization design document
-/// (`docs/arch/authorization/DESIGN.md`) for the full predicate taxonomy.
+/// See the authorization design document (`docs/arch/authorization/DESIGN.md`)
+/// for the predicate taxonomy and the closure table schemas.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ScopeFilter {
     /// Equality: `property = value`.
     Eq(EqScopeFilter),
     /// Set membership: `property IN (values)`.
     In(InScopeFilter),
+    /// Comparison: `property <op> value`.
+    Compare(CompareScopeFilter),
+    /// Tenant subtree: `property IN (descendants of root_tenant_id)`.
+    InTenantSubtree(TenantSubtreeScopeFilter),
+    /// Group membership: `property IN (members of group_ids)`.
+    InGroup(GroupScopeFilter),
+    /// Group subtree: `property IN (members of any group under root_group_id)`.
+    InGroupSubtree(GroupSubtreeScopeFilter),
+    /// Negation: the wrapped filter must not match.
+    Not(Box<ScopeFilter>),
+}
+
+/// Comparison operator of a [`CompareScopeFilter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompareOp {
+    /// `property > value`
+    Gt,
+    /// `property >= value`
+    Gte,
+    /// `property < value`
+    Lt,
+    /// `property <= value`
+    Lte,
+}
+
+impl CompareOp {
+    /// Whether `property.cmp(value)` satisfies this operator.
+    #[must_use]
+    pub fn accepts(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Gte => ordering != Ordering::Less,
+            Self::Lt => ordering == Ordering::Less,
+            Self::Lte => ordering != Ordering::Greater,
+        }
+    }
 }
 
 /// Equality scope filter: `property = value`.
@@ -142,6 +210,52 @@ pub struct InScopeFilter {
     values: Vec<ScopeValue>,
 }
 
+/// Comparison scope filter: `property <op> value`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CompareScopeFilter {
+    /// Authorization property name.
+    property: String,
+    /// The comparison operator.
+    op: CompareOp,
+    /// The value to compare against.
+    value: ScopeValue,
+}
+
+/// Tenant subtree scope filter: the property holds a tenant that descends
+/// from (or is) `root_tenant_id` in the `tenant_closure` table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TenantSubtreeScopeFilter {
+    /// Authorization property name (typically `pep_properties::OWNER_TENANT_ID`).
+    property: String,
+    /// Root of the tenant subtree.
+    root_tenant_id: Uuid,
+    /// Stop at self-managed tenants (closure rows with a barrier on the path).
+    respect_barriers: bool,
+    /// Only descendants in one of these statuses; empty means any status.
+    tenant_status: Vec<String>,
+}
+
+/// Group membership scope filter: the property holds a resource that is a
+/// member of one of `group_ids` in the `resource_group_membership` table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GroupScopeFilter {
+    /// Authorization property name (typically `pep_properties::RESOURCE_ID`).
+    property: String,
+    /// The groups whose members match.
+    group_ids: Vec<Uuid>,
+}
+
+/// Group subtree scope filter: the property holds a resource that is a
+/// member of `root_group_id` or any of its descendants in the
+/// `resource_group_closure` table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GroupSubtreeScopeFilter {
+    /// Authorization property name (typically `pep_properties::RESOURCE_ID`).
+    property: String,
+    /// Root of the group subtree.
+    root_group_id: Uuid,
+}
+
 impl EqScopeFilter {
     /// Create an equality scope filter.
     #[must_use]
@@ -204,6 +318,145 @@ impl InScopeFilter {
     }
 }
 
+impl CompareScopeFilter {
+    /// Create a comparison scope filter.
+    #[must_use]
+    pub fn new(property: impl Into<String>, op: CompareOp, value: impl Into<ScopeValue>) -> Self {
+        Self {
+            property: property.into(),
+            op,
+            value: value.into(),
+        }
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// The comparison operator.
+    #[inline]
+    #[must_use]
+    pub fn op(&self) -> CompareOp {
+        self.op
+    }
+
+    /// The value compared against.
+    #[inline]
+    #[must_use]
+    pub fn value(&self) -> &ScopeValue {
+        &self.value
+    }
+}
+
+impl TenantSubtreeScopeFilter {
+    /// Create a tenant subtree filter that respects barriers and accepts
+    /// tenants in any status.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_tenant_id,
+            respect_barriers: true,
+            tenant_status: Vec::new(),
+        }
+    }
+
+    /// Set whether traversal stops at barriers (self-managed tenants).
+    #[must_use]
+    pub fn with_barriers(mut self, respect: bool) -> Self {
+        self.respect_barriers = respect;
+        self
+    }
+
+    /// Only match descendants in one of the given statuses.
+    #[must_use]
+    pub fn with_tenant_status(mut self, statuses: Vec<String>) -> Self {
+        self.tenant_status = statuses;
+        self
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Root of the tenant subtree.
+    #[inline]
+    #[must_use]
+    pub fn root_tenant_id(&self) -> Uuid {
+        self.root_tenant_id
+    }
+
+    /// Whether traversal stops at barriers.
+    #[inline]
+    #[must_use]
+    pub fn respect_barriers(&self) -> bool {
+        self.respect_barriers
+    }
+
+    /// Accepted descendant statuses; empty means any status.
+    #[inline]
+    #[must_use]
+    pub fn tenant_status(&self) -> &[String] {
+        &self.tenant_status
+    }
+}
+
+impl GroupScopeFilter {
+    /// Create a group membership filter.
+    #[must_use]
+    pub fn new(property: impl Into<String>, group_ids: Vec<Uuid>) -> Self {
+        Self {
+            property: property.into(),
+            group_ids,
+        }
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// The groups whose members match.
+    #[inline]
+    #[must_use]
+    pub fn group_ids(&self) -> &[Uuid] {
+        &self.group_ids
+    }
+}
+
+impl GroupSubtreeScopeFilter {
+    /// Create a group subtree filter.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_group_id,
+        }
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Root of the group subtree.
+    #[inline]
+    #[must_use]
+    pub fn root_group_id(&self) -> Uuid {
+        self.root_group_id
+    }
+}
+
 impl ScopeFilter {
     /// Create an equality filter (`property = value`).
     #[must_use]
@@ -226,23 +479,87 @@ impl ScopeFilter {
         ))
     }
 
+    /// Create a comparison filter (`property <op> value`).
+    #[must_use]
+    pub fn compare(
+        property: impl Into<String>,
+        op: CompareOp,
+        value: impl Into<ScopeValue>,
+    ) -> Self {
+        Self::Compare(CompareScopeFilter::new(property, op, value))
+    }
+
+    /// Create a tenant subtree filter that respects barriers.
+    #[must_use]
+    pub fn in_tenant_subtree(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self::InTenantSubtree(TenantSubtreeScopeFilter::new(property, root_tenant_id))
+    }
+
+    /// Create a group membership filter.
+    #[must_use]
+    pub fn in_group(property: impl Into<String>, group_ids: Vec<Uuid>) -> Self {
+        Self::InGroup(GroupScopeFilter::new(property, group_ids))
+    }
+
+    /// Create a group subtree filter.
+    #[must_use]
+    pub fn in_group_subtree(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self::InGroupSubtree(GroupSubtreeScopeFilter::new(property, root_group_id))
+    }
+
+    /// Negate a filter.
+    #[must_use]
+    pub fn negate(filter: ScopeFilter) -> Self {
+        Self::Not(Box::new(filter))
+    }
+
     /// The authorization property name.
+    ///
+    /// For `Not`, the property of the negated filter.
     #[must_use]
     pub fn property(&self) -> &str {
         match self {
             Self::Eq(f) => f.property(),
             Self::In(f) => f.property(),
+            Self::Compare(f) => f.property(),
+            Self::InTenantSubtree(f) => f.property(),
+            Self::InGroup(f) => f.property(),
+            Self::InGroupSubtree(f) => f.property(),
+            Self::Not(f) => f.property(),
         }
     }
 
     /// Collect all values as a slice-like view for iteration.
     ///
     /// For `Eq`, returns a single-element slice; for `In`, returns the values slice.
+    /// Other filters do not enumerate the property values they accept and
+    /// return an empty view.
     #[must_use]
     pub fn values(&self) -> ScopeFilterValues<'_> {
         match self {
             Self::Eq(f) => ScopeFilterValues::Single(&f.value),
             Self::In(f) => ScopeFilterValues::Multiple(&f.values),
+            Self::Compare(_)
+            | Self::InTenantSubtree(_)
+            | Self::InGroup(_)
+            | Self::InGroupSubtree(_)
+            | Self::Not(_) => ScopeFilterValues::Multiple(&[]),
+        }
+    }
+
+    /// Evaluate the filter against a property value in memory.
+    ///
+    /// Returns `None` when the outcome cannot be decided from the value alone:
+    /// hierarchy and group filters need the closure tables, and comparisons
+    /// need values of the same orderable kind.
+    #[must_use]
+    pub fn evaluate(&self, value: &ScopeValue) -> Option<bool> {
+        match self {
+            Self::Eq(f) => Some(f.value() == value),
+            Self::In(f) => Some(f.values().contains(value)),
+            Self::Compare(f) => value.compare_to(f.value()).map(|o| f.op().accepts(o)),
+            Self::InTenantSubtree(_) | Self::InGroup(_) | Self::InGroupSubtree(_) => None,
+            Self::Not(f) => f.evaluate(value).map(|matched| !matched),
         }
     }
 
@@ -591,6 +908,45 @@ mod tests {
         assert_eq!(values, &[uid(T1), uid(T2)]);
     }
 
+    #[test]
+    fn non_literal_filters_expose_no_values() {
+        let scope = AccessScope::single(ScopeConstraint::new(vec![
+            ScopeFilter::in_tenant_subtree(pep_properties::OWNER_TENANT_ID, uid(T1)),
+            ScopeFilter::negate(ScopeFilter::eq(pep_properties::OWNER_TENANT_ID, uid(T2))),
+        ]));
+        assert!(scope.has_property(pep_properties::OWNER_TENANT_ID));
+        assert!(
+            scope
+                .all_uuid_values_for(pep_properties::OWNER_TENANT_ID)
+                .is_empty()
+        );
+        assert!(!scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T2)));
+    }
+
+    #[test]
+    fn evaluate_compare_and_not() {
+        let cutoff = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let later = ScopeValue::Timestamp(cutoff + time::Duration::hours(1));
+        let gt = ScopeFilter::compare("created_at", CompareOp::Gt, cutoff);
+        assert_eq!(gt.evaluate(&later), Some(true));
+        assert_eq!(gt.evaluate(&ScopeValue::Timestamp(cutoff)), Some(false));
+        assert_eq!(gt.evaluate(&ScopeValue::Int(1)), None);
+
+        let lte = ScopeFilter::compare("priority", CompareOp::Lte, 5_i64);
+        assert_eq!(lte.evaluate(&ScopeValue::Int(5)), Some(true));
+        assert_eq!(
+            ScopeFilter::negate(lte).evaluate(&ScopeValue::Int(5)),
+            Some(false)
+        );
+
+        let subtree = ScopeFilter::in_tenant_subtree(pep_properties::OWNER_TENANT_ID, uid(T1));
+        assert_eq!(subtree.evaluate(&ScopeValue::Uuid(uid(T1))), None);
+        assert_eq!(
+            ScopeFilter::negate(subtree).property(),
+            pep_properties::OWNER_TENANT_ID
+        );
+    }
+
     #[test]
     fn contains_value_works_with_eq() {
         let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::eq(
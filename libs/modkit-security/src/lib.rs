@@ -6,8 +6,9 @@ pub mod context;
 pub mod prelude;
 
 pub use access_scope::{
-    AccessScope, EqScopeFilter, InScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue,
-    pep_properties,
+    AccessScope, CompareOp, CompareScopeFilter, EqScopeFilter, GroupScopeFilter,
+    GroupSubtreeScopeFilter, InScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue,
+    TenantSubtreeScopeFilter, pep_properties,
 };
 pub use context::{SecurityContext, SecurityContextBuildError};
 
@@ -45,6 +45,18 @@ impl fmt::Display for SecretString {
     }
 }
 
+/// Deserializes from a plain string, so config structs can hold secrets
+/// (e.g. values resolved from `${secret:...}` references) without exposing them in `Debug`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SecretString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -93,4 +105,12 @@ mod tests {
         s.zeroize();
         assert!(s.0.is_empty(), "buffer should be empty after zeroize");
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializes_from_string() {
+        let s: SecretString = serde_json::from_str("\"hunter2\"").unwrap();
+        assert_eq!(s.expose(), "hunter2");
+        assert_eq!(format!("{s:?}"), "[REDACTED]");
+    }
 }
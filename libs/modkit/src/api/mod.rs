@@ -23,8 +23,8 @@ pub use error_layer::{
 };
 pub use openapi_registry::{OpenApiInfo, OpenApiRegistry, OpenApiRegistryImpl, ensure_schema};
 pub use operation_builder::{
-    Missing, OperationBuilder, OperationSpec, ParamLocation, ParamSpec, Present, RateLimitSpec,
-    ResponseSpec, state,
+    DeprecationSpec, Missing, OperationBuilder, OperationSpec, ParamLocation, ParamSpec, Present,
+    RateLimitSpec, ResponseSpec, state,
 };
 pub use problem::{
     APPLICATION_PROBLEM_JSON, Problem, ValidationError, bad_request, conflict, internal_error,
@@ -2,6 +2,7 @@ use axum::extract::{FromRequestParts, Query};
 use axum::http::request::Parts;
 use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, OrderKey, SortDir};
 use serde::Deserialize;
+use std::sync::Arc;
 
 // Re-export types from modkit-odata for convenience and better DX
 pub use modkit_odata::ODataQuery;
@@ -11,6 +12,13 @@ pub use modkit_odata::ODataQuery;
 pub mod error;
 pub use error::odata_error_to_problem;
 
+/// Relations an operation accepts in `$expand`.
+///
+/// Attached to the route as a request extension by `OperationBuilder::with_odata_expand`;
+/// `$expand` on a route without it is rejected.
+#[derive(Debug, Clone, Default)]
+pub struct ODataExpandRelations(pub Arc<[String]>);
+
 #[derive(Deserialize, Default)]
 pub struct ODataParams {
     #[serde(rename = "$filter")]
@@ -19,6 +27,12 @@ pub struct ODataParams {
     pub orderby: Option<String>,
     #[serde(rename = "$select")]
     pub select: Option<String>,
+    #[serde(rename = "$count")]
+    pub count: Option<String>,
+    #[serde(rename = "$search")]
+    pub search: Option<String>,
+    #[serde(rename = "$expand")]
+    pub expand: Option<String>,
     pub limit: Option<u64>,
     pub cursor: Option<String>,
 }
@@ -29,6 +43,9 @@ pub const MAX_ORDERBY_LEN: usize = 1024;
 pub const MAX_ORDER_FIELDS: usize = 10;
 pub const MAX_SELECT_LEN: usize = 2048;
 pub const MAX_SELECT_FIELDS: usize = 100;
+pub const MAX_SEARCH_LEN: usize = 256;
+pub const MAX_EXPAND_LEN: usize = 1024;
+pub const MAX_EXPAND_FIELDS: usize = 16;
 
 /// Parse $select string into a list of field names.
 /// Format: "field1, field2, field3, ..."
@@ -76,6 +93,91 @@ pub fn parse_select(raw: &str) -> Result<Vec<String>, crate::api::problem::Probl
     Ok(fields)
 }
 
+/// Parse $count value. Accepts `true` or `false` (case-insensitive).
+///
+/// # Errors
+/// Returns a `Problem` if the value is not a boolean.
+#[allow(clippy::result_large_err)]
+pub fn parse_count(raw: &str) -> Result<bool, crate::api::problem::Problem> {
+    let raw = raw.trim();
+    if raw.eq_ignore_ascii_case("true") {
+        Ok(true)
+    } else if raw.eq_ignore_ascii_case("false") {
+        Ok(false)
+    } else {
+        Err(crate::api::bad_request("$count must be true or false"))
+    }
+}
+
+/// Parse $search string into normalized search text.
+/// Whitespace is trimmed and collapsed; an empty search is rejected.
+///
+/// # Errors
+/// Returns a `Problem` if the search string is empty or too long.
+#[allow(clippy::result_large_err)]
+pub fn parse_search(raw: &str) -> Result<String, crate::api::problem::Problem> {
+    if raw.len() > MAX_SEARCH_LEN {
+        return Err(crate::api::bad_request("$search too long"));
+    }
+
+    let search = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if search.is_empty() {
+        return Err(crate::api::bad_request("$search cannot be empty"));
+    }
+
+    Ok(search)
+}
+
+/// Parse $expand string into a list of relation names.
+/// Format: "relation1, relation2, ..."
+/// Nested options such as `relation($select=...)` are not supported.
+///
+/// # Errors
+/// Returns a `Problem` if the expand string is invalid.
+#[allow(clippy::result_large_err)]
+pub fn parse_expand(raw: &str) -> Result<Vec<String>, crate::api::problem::Problem> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(crate::api::bad_request("$expand cannot be empty"));
+    }
+
+    if raw.len() > MAX_EXPAND_LEN {
+        return Err(crate::api::bad_request("$expand too long"));
+    }
+
+    let mut relations: Vec<String> = Vec::new();
+    for rel in raw.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+        if !rel
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '/')
+        {
+            return Err(crate::api::bad_request(format!(
+                "invalid relation in $expand: {rel}"
+            )));
+        }
+        if relations.iter().any(|r| r.eq_ignore_ascii_case(rel)) {
+            return Err(crate::api::bad_request(format!(
+                "duplicate relation in $expand: {rel}"
+            )));
+        }
+        relations.push(rel.to_owned());
+    }
+
+    if relations.is_empty() {
+        return Err(crate::api::bad_request(
+            "$expand must contain at least one relation",
+        ));
+    }
+
+    if relations.len() > MAX_EXPAND_FIELDS {
+        return Err(crate::api::bad_request(
+            "$expand contains too many relations",
+        ));
+    }
+
+    Ok(relations)
+}
+
 /// Parse $orderby string into `ODataOrderBy`.
 /// Format: "field1 [asc|desc], field2 [asc|desc], ..."
 /// Default direction is asc if not specified.
@@ -135,7 +237,7 @@ pub fn parse_orderby(raw: &str) -> Result<ODataOrderBy, modkit_odata::Error> {
 }
 
 /// Extract and validate full `OData` query from request parts.
-/// - Parses $filter, $orderby, limit, cursor
+/// - Parses $filter, $orderby, $select, $count, $search, $expand, limit, cursor
 /// - Enforces budgets and validates formats
 /// - Returns unified `ODataQuery`
 ///
@@ -182,19 +284,21 @@ where
                 return Err(crate::api::bad_request("Filter too complex"));
             }
 
-            // Generate filter hash for cursor consistency (use non-consuming accessor)
-            let filter_hash = modkit_odata::pagination::short_filter_hash(Some(parsed.as_expr()));
-
             // Extract expression for query
-            let core_expr = parsed.into_expr();
-
-            query = query.with_filter(core_expr);
-            if let Some(hash) = filter_hash {
-                query = query.with_filter_hash(hash);
-            }
+            query = query.with_filter(parsed.into_expr());
         }
     }
 
+    // Parse search
+    if let Some(raw_search) = params.search.as_ref() {
+        query = query.with_search(parse_search(raw_search)?);
+    }
+
+    // Generate filter hash for cursor consistency; it also covers $search
+    if let Some(hash) = modkit_odata::pagination::short_query_hash(query.filter(), query.search()) {
+        query = query.with_filter_hash(hash);
+    }
+
     // Check for cursor+orderby conflict before parsing either
     if params.cursor.is_some() && params.orderby.is_some() {
         return Err(crate::api::odata::odata_error_to_problem(
@@ -237,13 +341,31 @@ where
         query = query.with_select(fields);
     }
 
+    // Parse count
+    if let Some(raw_count) = params.count.as_ref() {
+        query = query.with_count(parse_count(raw_count)?);
+    }
+
+    // Parse expand and check it against the relations the route declares
+    if let Some(raw_expand) = params.expand.as_ref() {
+        query = query.with_expand(parse_expand(raw_expand)?);
+        let allowed: Vec<&str> = parts
+            .extensions
+            .get::<ODataExpandRelations>()
+            .map(|r| r.0.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        query
+            .ensure_expand_allowed(&allowed)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, parts.uri.path(), None))?;
+    }
+
     Ok(query)
 }
 
 use std::ops::Deref;
 
 /// Simple Axum extractor for full `OData` query parameters.
-/// Parses $filter, $orderby, limit, and cursor parameters.
+/// Parses $filter, $orderby, $select, $count, $search, $expand, limit, and cursor parameters.
 /// Usage in handlers:
 ///   async fn `list_users(OData(query)`: `OData`, /* ... */) { /* use `query` */ }
 #[derive(Debug, Clone)]
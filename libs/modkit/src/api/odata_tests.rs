@@ -260,4 +260,80 @@ mod tests {
         let query_back: ODataQuery = odata.into();
         assert!(query_back.has_filter());
     }
+
+    #[test]
+    fn test_parse_count() {
+        assert!(parse_count("true").unwrap());
+        assert!(parse_count(" TRUE ").unwrap());
+        assert!(!parse_count("false").unwrap());
+        assert_eq!(
+            parse_count("yes").unwrap_err().status,
+            http::StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn test_parse_search() {
+        assert_eq!(parse_search("  alice   smith ").unwrap(), "alice smith");
+        assert!(parse_search("   ").is_err());
+        assert!(parse_search(&"a".repeat(MAX_SEARCH_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_parse_expand() {
+        assert_eq!(
+            parse_expand(" owner , tags ").unwrap(),
+            vec!["owner".to_owned(), "tags".to_owned()]
+        );
+        assert!(parse_expand("").is_err());
+        assert!(parse_expand(" , ").is_err());
+        assert!(parse_expand("owner,Owner").is_err());
+        assert!(parse_expand("owner($select=name)").is_err());
+        let many = (0..=MAX_EXPAND_FIELDS)
+            .map(|i| format!("r{i}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(parse_expand(&many).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_count_search_expand() {
+        let uri = "/?%24count=true&%24search=alice%20smith&%24expand=owner%2Ctags&%24filter=age%20gt%2018";
+
+        let request = Request::builder().uri(uri).body(()).unwrap();
+
+        let (mut parts, _body) = request.into_parts();
+        parts.extensions.insert(ODataExpandRelations(
+            vec!["owner".to_owned(), "tags".to_owned()].into(),
+        ));
+
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+
+        assert!(query.count);
+        assert_eq!(query.search(), Some("alice smith"));
+        assert_eq!(
+            query.expanded(),
+            Some(&["owner".to_owned(), "tags".to_owned()][..])
+        );
+        assert_eq!(
+            query.filter_hash,
+            modkit_odata::pagination::short_query_hash(query.filter(), Some("alice smith"))
+        );
+        assert_ne!(
+            query.filter_hash,
+            modkit_odata::pagination::short_filter_hash(query.filter())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_invalid_count() {
+        let uri = "/?%24count=maybe";
+
+        let request = Request::builder().uri(uri).body(()).unwrap();
+
+        let (mut parts, _body) = request.into_parts();
+
+        let result = extract_odata_query(&mut parts, &()).await;
+        assert_eq!(result.unwrap_err().status, http::StatusCode::BAD_REQUEST);
+    }
 }
@@ -6,10 +6,10 @@
 use anyhow::Result;
 use arc_swap::ArcSwap;
 use dashmap::DashMap;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use utoipa::openapi::{
-    OpenApi, OpenApiBuilder, Ref, RefOr, Required,
+    Deprecated, OpenApi, OpenApiBuilder, Ref, RefOr, Required,
     content::ContentBuilder,
     info::InfoBuilder,
     path::{
@@ -105,16 +105,51 @@ impl OpenApiRegistryImpl {
     /// # Errors
     /// Returns an error if the `OpenAPI` specification cannot be built.
     pub fn build_openapi(&self, info: &OpenApiInfo) -> Result<OpenApi> {
-        use http::Method;
-
         // Log operation count for visibility
         let op_count = self.operation_specs.len();
         tracing::info!("Building OpenAPI: found {op_count} registered operations");
 
+        Ok(self.build_document(info, |_| true))
+    }
+
+    /// Build the `OpenAPI` document of a single API version.
+    ///
+    /// Only operations whose [`effective_version`](operation_builder::OperationSpec::effective_version)
+    /// is `version` are included; schema components are shared by all versions.
+    ///
+    /// # Errors
+    /// Returns an error if the `OpenAPI` specification cannot be built.
+    pub fn build_openapi_for_version(&self, info: &OpenApiInfo, version: &str) -> Result<OpenApi> {
+        Ok(self.build_document(info, |spec| spec.effective_version() == Some(version)))
+    }
+
+    /// API versions of the registered operations, sorted.
+    #[must_use]
+    pub fn api_versions(&self) -> Vec<String> {
+        let versions: BTreeSet<String> = self
+            .operation_specs
+            .iter()
+            .filter_map(|e| e.value().effective_version().map(str::to_owned))
+            .collect();
+        versions.into_iter().collect()
+    }
+
+    fn build_document(
+        &self,
+        info: &OpenApiInfo,
+        include: impl Fn(&operation_builder::OperationSpec) -> bool,
+    ) -> OpenApi {
+        use http::Method;
+
         // 1) Paths
         let mut paths = PathsBuilder::new();
 
-        for spec in self.operation_specs.iter().map(|e| e.value().clone()) {
+        for spec in self
+            .operation_specs
+            .iter()
+            .filter(|e| include(e.value()))
+            .map(|e| e.value().clone())
+        {
             let mut op = UOperationBuilder::new()
                 .operation_id(spec.operation_id.clone().or(Some(spec.handler_id.clone())))
                 .summary(spec.summary.clone())
@@ -137,16 +172,19 @@ impl OpenApiRegistryImpl {
                 );
             }
 
-            // Pagination
-            if let Some(pagination) = spec.vendor_extensions.x_odata_filter.as_ref()
-                && let Ok(value) = serde_json::to_value(pagination)
+            // OData vendor extensions (x-odata-filter, x-odata-orderby, x-odata-search, x-odata-expand)
+            if let Ok(serde_json::Value::Object(odata)) =
+                serde_json::to_value(&spec.vendor_extensions)
             {
-                ext.insert("x-odata-filter".to_owned(), value);
+                ext.extend(odata);
             }
-            if let Some(pagination) = spec.vendor_extensions.x_odata_orderby.as_ref()
-                && let Ok(value) = serde_json::to_value(pagination)
-            {
-                ext.insert("x-odata-orderby".to_owned(), value);
+
+            // Deprecation lifecycle
+            if let Some(deprecation) = spec.deprecation.as_ref() {
+                op = op.deprecated(Some(Deprecated::True));
+                if let Some(sunset) = deprecation.sunset {
+                    ext.insert("x-sunset".to_owned(), serde_json::json!(sunset.to_string()));
+                }
             }
 
             if !ext.is_empty() {
@@ -337,13 +375,11 @@ impl OpenApiRegistryImpl {
             .description(info.description.clone())
             .build();
 
-        let openapi = OpenApiBuilder::new()
+        OpenApiBuilder::new()
             .info(openapi_info)
             .paths(paths.build())
             .components(Some(components.build()))
-            .build();
-
-        Ok(openapi)
+            .build()
     }
 }
 
@@ -401,7 +437,7 @@ impl OpenApiRegistry for OpenApiRegistryImpl {
 mod tests {
     use super::*;
     use crate::api::operation_builder::{
-        OperationSpec, ParamLocation, ParamSpec, ResponseSpec, VendorExtensions,
+        DeprecationSpec, OperationSpec, ParamLocation, ParamSpec, ResponseSpec, VendorExtensions,
     };
     use http::Method;
 
@@ -437,6 +473,10 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            disable_compression: false,
+            idempotency_key: false,
+            version: None,
+            deprecation: None,
         };
 
         registry.register_operation(&spec);
@@ -500,6 +540,10 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            disable_compression: false,
+            idempotency_key: false,
+            version: None,
+            deprecation: None,
         };
 
         registry.register_operation(&spec);
@@ -560,6 +604,10 @@ mod tests {
             allowed_request_content_types: Some(vec!["application/octet-stream"]),
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            disable_compression: false,
+            idempotency_key: false,
+            version: None,
+            deprecation: None,
         };
 
         registry.register_operation(&spec);
@@ -639,9 +687,19 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            disable_compression: false,
+            idempotency_key: false,
+            version: None,
+            deprecation: None,
         };
         spec.vendor_extensions.x_odata_filter = Some(filter);
         spec.vendor_extensions.x_odata_orderby = Some(order_by);
+        spec.vendor_extensions.x_odata_search = Some(operation_builder::ODataPagination {
+            allowed_fields: vec!["name".to_owned()],
+        });
+        spec.vendor_extensions.x_odata_expand = Some(operation_builder::ODataPagination {
+            allowed_fields: vec!["owner".to_owned()],
+        });
 
         registry.register_operation(&spec);
         let info = OpenApiInfo::default();
@@ -666,5 +724,84 @@ mod tests {
         let allowed_order = order_ext.get("allowedFields").unwrap().as_array().unwrap();
         assert!(allowed_order.iter().any(|v| v.as_str() == Some("name asc")));
         assert!(allowed_order.iter().any(|v| v.as_str() == Some("age desc")));
+
+        assert_eq!(
+            op["x-odata-search"]["allowedFields"],
+            serde_json::json!(["name"])
+        );
+        assert_eq!(
+            op["x-odata-expand"]["allowedFields"],
+            serde_json::json!(["owner"])
+        );
+    }
+
+    fn versioned_spec(path: &str, version: Option<&str>) -> OperationSpec {
+        OperationSpec {
+            method: Method::GET,
+            path: path.to_owned(),
+            operation_id: None,
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![],
+            request_body: None,
+            responses: vec![ResponseSpec {
+                status: 200,
+                content_type: "application/json",
+                description: "OK".to_owned(),
+                schema_name: None,
+            }],
+            handler_id: format!("get{}", path.replace('/', "_")),
+            authenticated: false,
+            is_public: true,
+            rate_limit: None,
+            allowed_request_content_types: None,
+            vendor_extensions: VendorExtensions::default(),
+            license_requirement: None,
+            disable_compression: false,
+            idempotency_key: false,
+            version: version.map(str::to_owned),
+            deprecation: None,
+        }
+    }
+
+    #[test]
+    fn test_build_openapi_per_version() {
+        let registry = OpenApiRegistryImpl::new();
+        let mut old = versioned_spec("/items/v1/list", None);
+        old.deprecation = Some(DeprecationSpec {
+            deprecated_since: chrono::NaiveDate::from_ymd_opt(2026, 1, 1),
+            sunset: chrono::NaiveDate::from_ymd_opt(2026, 7, 1),
+            link: None,
+        });
+        registry.register_operation(&old);
+        registry.register_operation(&versioned_spec("/items/v2/list", None));
+        registry.register_operation(&versioned_spec("/items/search", Some("v2")));
+        registry.register_operation(&versioned_spec("/health/status", None));
+
+        assert_eq!(registry.api_versions(), vec!["v1", "v2"]);
+
+        let info = OpenApiInfo::default();
+        let v1 =
+            serde_json::to_value(registry.build_openapi_for_version(&info, "v1").unwrap()).unwrap();
+        let v1_paths = v1["paths"].as_object().unwrap();
+        assert_eq!(v1_paths.len(), 1);
+        let op = &v1_paths["/items/v1/list"]["get"];
+        assert_eq!(op["deprecated"], true);
+        assert_eq!(op["x-sunset"], "2026-07-01");
+
+        let v2 =
+            serde_json::to_value(registry.build_openapi_for_version(&info, "v2").unwrap()).unwrap();
+        let mut v2_paths: Vec<_> = v2["paths"].as_object().unwrap().keys().cloned().collect();
+        v2_paths.sort();
+        assert_eq!(v2_paths, vec!["/items/search", "/items/v2/list"]);
+        assert!(
+            v2["paths"]["/items/v2/list"]["get"]
+                .get("deprecated")
+                .is_none()
+        );
+
+        let all = serde_json::to_value(registry.build_openapi(&info).unwrap()).unwrap();
+        assert_eq!(all["paths"].as_object().unwrap().len(), 4);
     }
 }
@@ -13,6 +13,7 @@
 
 use crate::api::{api_dto, problem};
 use axum::{Router, handler::Handler, routing::MethodRouter};
+use chrono::NaiveDate;
 use http::Method;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -193,6 +194,7 @@ pub struct LicenseReqSpec {
 
 /// Simplified operation specification for the type-safe builder
 #[derive(Clone, Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct OperationSpec {
     pub method: Method,
     pub path: String,
@@ -221,6 +223,54 @@ pub struct OperationSpec {
     /// `OpenAPI` vendor extensions (x-*)
     pub vendor_extensions: VendorExtensions,
     pub license_requirement: Option<LicenseReqSpec>,
+    /// Never compress responses of this operation (e.g. streaming endpoints)
+    pub disable_compression: bool,
+    /// Honour the `Idempotency-Key` request header: the gateway stores the first response
+    /// per key and replays it for retries
+    pub idempotency_key: bool,
+    /// API version the operation belongs to (e.g. `v2`); when unset, the version is taken
+    /// from a `/vN/` path segment (see [`OperationSpec::effective_version`])
+    pub version: Option<String>,
+    /// Deprecation schedule; `None` while the operation is current
+    pub deprecation: Option<DeprecationSpec>,
+}
+
+impl OperationSpec {
+    /// API version of the operation: the declared one, or else the first path segment
+    /// of the form `vN` (`/file-parser/v1/info` belongs to `v1`).
+    #[must_use]
+    pub fn effective_version(&self) -> Option<&str> {
+        self.version.as_deref().or_else(|| {
+            self.path.split('/').find(|segment| {
+                segment
+                    .strip_prefix('v')
+                    .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+            })
+        })
+    }
+
+    /// Add `param`, replacing a parameter with the same name and location.
+    fn replace_param(&mut self, param: ParamSpec) {
+        match self
+            .params
+            .iter_mut()
+            .find(|p| p.name == param.name && p.location == param.location)
+        {
+            Some(existing) => *existing = param,
+            None => self.params.push(param),
+        }
+    }
+}
+
+/// Deprecation and sunset dates of an operation (UTC days)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeprecationSpec {
+    /// Day the operation was (or will be) deprecated
+    pub deprecated_since: Option<NaiveDate>,
+    /// Day from which the operation is no longer served
+    pub sunset: Option<NaiveDate>,
+    /// Migration guide or successor documentation
+    pub link: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -229,6 +279,10 @@ pub struct VendorExtensions {
     pub x_odata_filter: Option<ODataPagination<BTreeMap<String, Vec<String>>>>,
     #[serde(rename = "x-odata-orderby", skip_serializing_if = "Option::is_none")]
     pub x_odata_orderby: Option<ODataPagination<Vec<String>>>,
+    #[serde(rename = "x-odata-search", skip_serializing_if = "Option::is_none")]
+    pub x_odata_search: Option<ODataPagination<Vec<String>>>,
+    #[serde(rename = "x-odata-expand", skip_serializing_if = "Option::is_none")]
+    pub x_odata_expand: Option<ODataPagination<Vec<String>>>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -272,6 +326,22 @@ pub trait OperationBuilderODataExt<S, H, R> {
     fn with_odata_orderby<T>(self) -> Self
     where
         T: modkit_odata::filter::FilterField;
+
+    /// Adds optional `$count` query parameter to `OpenAPI`.
+    #[must_use]
+    fn with_odata_count(self) -> Self;
+
+    /// Adds optional `$search` query parameter to `OpenAPI`, matching the given fields.
+    #[must_use]
+    fn with_odata_search<T>(self, fields: &[T]) -> Self
+    where
+        T: modkit_odata::filter::FilterField;
+
+    /// Adds optional `$expand` query parameter to `OpenAPI`, allowing the given relations.
+    ///
+    /// The `OData` extractor rejects any other relation with `InvalidExpand`.
+    #[must_use]
+    fn with_odata_expand(self, relations: &[&str]) -> Self;
 }
 
 impl<S, H, R, A, L> OperationBuilderODataExt<S, H, R> for OperationBuilder<H, R, S, A, L>
@@ -299,17 +369,29 @@ where
             let kind = field.kind();
 
             let ops: Vec<String> = match kind {
-                FieldKind::String => vec!["eq", "ne", "contains", "startswith", "endswith", "in"],
+                FieldKind::String => vec![
+                    "eq",
+                    "ne",
+                    "contains",
+                    "startswith",
+                    "endswith",
+                    "in",
+                    "tolower",
+                    "toupper",
+                    "length",
+                    "indexof",
+                ],
                 FieldKind::Uuid => vec!["eq", "ne", "in"],
                 FieldKind::Bool => vec!["eq", "ne"],
-                FieldKind::I64
-                | FieldKind::F64
-                | FieldKind::Decimal
-                | FieldKind::DateTimeUtc
-                | FieldKind::Date
-                | FieldKind::Time => {
+                FieldKind::I64 | FieldKind::F64 | FieldKind::Decimal | FieldKind::Time => {
                     vec!["eq", "ne", "gt", "ge", "lt", "le", "in"]
                 }
+                FieldKind::DateTimeUtc | FieldKind::Date => {
+                    vec![
+                        "eq", "ne", "gt", "ge", "lt", "le", "in", "year", "month", "day",
+                    ]
+                }
+                FieldKind::Json => vec!["any", "all"],
             }
             .into_iter()
             .map(String::from)
@@ -376,6 +458,79 @@ where
         self.spec.vendor_extensions.x_odata_orderby = Some(order_by);
         self
     }
+
+    fn with_odata_count(mut self) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "$count".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(
+                "OData v4 count: when true, the response includes the total number of matching items"
+                    .to_owned(),
+            ),
+            param_type: "boolean".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_search<T>(mut self, fields: &[T]) -> Self
+    where
+        T: modkit_odata::filter::FilterField,
+    {
+        let mut search = self
+            .spec
+            .vendor_extensions
+            .x_odata_search
+            .take()
+            .unwrap_or_default();
+        for field in fields {
+            let name = field.name().to_owned();
+            if !search.allowed_fields.contains(&name) {
+                search.allowed_fields.push(name);
+            }
+        }
+        let description = format!(
+            "OData v4 free-text search across: {}",
+            search.allowed_fields.join(", ")
+        );
+        self.spec.replace_param(ParamSpec {
+            name: "$search".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(description),
+            param_type: "string".to_owned(),
+        });
+        self.spec.vendor_extensions.x_odata_search = Some(search);
+        self
+    }
+
+    fn with_odata_expand(mut self, relations: &[&str]) -> Self {
+        let mut expand = self
+            .spec
+            .vendor_extensions
+            .x_odata_expand
+            .take()
+            .unwrap_or_default();
+        for rel in relations {
+            let rel = (*rel).to_owned();
+            if !expand.allowed_fields.contains(&rel) {
+                expand.allowed_fields.push(rel);
+            }
+        }
+        let description = format!(
+            "OData v4 expand: comma-separated relations to inline ({})",
+            expand.allowed_fields.join(", ")
+        );
+        self.spec.replace_param(ParamSpec {
+            name: "$expand".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(description),
+            param_type: "string".to_owned(),
+        });
+        self.spec.vendor_extensions.x_odata_expand = Some(expand);
+        self
+    }
 }
 
 // Re-export from openapi_registry for backward compatibility
@@ -437,6 +592,10 @@ impl<S> OperationBuilder<Missing, Missing, S, AuthNotSet> {
                 allowed_request_content_types: None,
                 vendor_extensions: VendorExtensions::default(),
                 license_requirement: None,
+                disable_compression: false,
+                idempotency_key: false,
+                version: None,
+                deprecation: None,
             },
             method_router: (), // no router in Missing state
             _has_handler: PhantomData,
@@ -509,6 +668,76 @@ where
         self
     }
 
+    /// Opt this operation out of gateway response compression.
+    ///
+    /// Use for endpoints that stream (e.g. SSE) or return already compressed content.
+    pub fn disable_compression(mut self) -> Self {
+        self.spec.disable_compression = true;
+        self
+    }
+
+    /// Make this operation honour the `Idempotency-Key` request header.
+    ///
+    /// Retries carrying the same key (and the same body) get the stored first response
+    /// instead of executing the operation again. Intended for unsafe methods such as POST.
+    pub fn idempotent(mut self) -> Self {
+        self.spec.idempotency_key = true;
+        self.spec.params.push(ParamSpec {
+            name: "Idempotency-Key".to_owned(),
+            location: ParamLocation::Header,
+            required: false,
+            description: Some(
+                "Unique key of this request; retries with the same key are not executed twice"
+                    .to_owned(),
+            ),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
+
+    /// Assign this operation to an API version (e.g. `"v2"`).
+    ///
+    /// Without it the version is inferred from a `/vN/` path segment. Each version gets its
+    /// own `OpenAPI` document.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.spec.version = Some(version.into());
+        self
+    }
+
+    /// Mark this operation deprecated as of `date`.
+    ///
+    /// The gateway announces it with a `Deprecation` response header and the `OpenAPI`
+    /// document flags the operation as deprecated.
+    pub fn deprecated_since(mut self, date: NaiveDate) -> Self {
+        self.spec
+            .deprecation
+            .get_or_insert_with(DeprecationSpec::default)
+            .deprecated_since = Some(date);
+        self
+    }
+
+    /// Stop serving this operation from `date` (00:00 UTC) on.
+    ///
+    /// Until then responses carry a `Sunset` header; afterwards the gateway answers
+    /// `410 Gone` without calling the handler.
+    pub fn sunset(mut self, date: NaiveDate) -> Self {
+        self.spec
+            .deprecation
+            .get_or_insert_with(DeprecationSpec::default)
+            .sunset = Some(date);
+        self
+    }
+
+    /// Link clients of this deprecated operation to its migration guide or successor,
+    /// sent as `Link: <url>; rel="deprecation"`.
+    pub fn deprecation_link(mut self, url: impl Into<String>) -> Self {
+        self.spec
+            .deprecation
+            .get_or_insert_with(DeprecationSpec::default)
+            .link = Some(url.into());
+        self
+    }
+
     /// Set the operation summary
     pub fn summary(mut self, text: impl Into<String>) -> Self {
         self.spec.summary = Some(text.into());
@@ -1549,8 +1778,17 @@ where
         // into an OpenAPI Operation + RequestBody + Responses with component refs).
         openapi.register_operation(&self.spec);
 
+        // Routes declaring `$expand` relations expose them to the `OData` extractor.
+        let method_router =
+            match &self.spec.vendor_extensions.x_odata_expand {
+                Some(expand) => self.method_router.layer(axum::Extension(
+                    crate::api::odata::ODataExpandRelations(expand.allowed_fields.clone().into()),
+                )),
+                None => self.method_router,
+            };
+
         // In Present state the method_router is guaranteed to be a real MethodRouter<S>.
-        router.route(&self.spec.path, self.method_router)
+        router.route(&self.spec.path, method_router)
     }
 }
 
@@ -1990,6 +2228,69 @@ mod tests {
         assert_eq!(builder.spec.responses.len(), 2);
     }
 
+    #[test]
+    fn disable_compression_sets_flag() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/events");
+        assert!(!builder.spec.disable_compression);
+
+        let builder = builder
+            .disable_compression()
+            .public()
+            .handler(test_handler)
+            .json_response(http::StatusCode::OK, "Success");
+        assert!(builder.spec.disable_compression);
+    }
+
+    #[test]
+    fn idempotent_sets_flag_and_documents_header() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::post("/tests/v1/items")
+            .idempotent()
+            .public()
+            .handler(test_handler)
+            .json_response(http::StatusCode::CREATED, "Created");
+
+        assert!(builder.spec.idempotency_key);
+        let param = builder.spec.params.first().unwrap();
+        assert_eq!(param.name, "Idempotency-Key");
+        assert_eq!(param.location, ParamLocation::Header);
+        assert!(!param.required);
+    }
+
+    #[test]
+    fn deprecation_schedule_is_recorded() {
+        let since = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let sunset = NaiveDate::from_ymd_opt(2026, 7, 1).unwrap();
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/items")
+            .deprecated_since(since)
+            .sunset(sunset)
+            .deprecation_link("https://example.com/migrate")
+            .public()
+            .handler(test_handler)
+            .json_response(http::StatusCode::OK, "Success");
+
+        assert_eq!(
+            builder.spec.deprecation,
+            Some(DeprecationSpec {
+                deprecated_since: Some(since),
+                sunset: Some(sunset),
+                link: Some("https://example.com/migrate".to_owned()),
+            })
+        );
+    }
+
+    #[test]
+    fn version_is_declared_or_inferred_from_path() {
+        let inferred = OperationBuilder::<Missing, Missing, ()>::get("/file-parser/v1/info");
+        assert_eq!(inferred.spec.effective_version(), Some("v1"));
+
+        let declared =
+            OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/items").version("v2");
+        assert_eq!(declared.spec.effective_version(), Some("v2"));
+
+        let unversioned = OperationBuilder::<Missing, Missing, ()>::get("/health/vault");
+        assert_eq!(unversioned.spec.effective_version(), None);
+    }
+
     #[test]
     fn multipart_file_request() {
         let builder = OperationBuilder::<Missing, Missing, ()>::post("/tests/v1/upload")
@@ -2135,4 +2436,104 @@ mod tests {
             );
         }
     }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    enum SearchField {
+        Name,
+        Email,
+    }
+
+    impl modkit_odata::filter::FilterField for SearchField {
+        const FIELDS: &'static [Self] = &[Self::Name, Self::Email];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Self::Name => "name",
+                Self::Email => "email",
+            }
+        }
+
+        fn kind(&self) -> modkit_odata::filter::FieldKind {
+            modkit_odata::filter::FieldKind::String
+        }
+    }
+
+    #[test]
+    fn odata_search_and_expand_merge_into_one_param() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/items")
+            .with_odata_search(&[SearchField::Name])
+            .with_odata_search(&[SearchField::Email, SearchField::Name])
+            .with_odata_expand(&["owner"])
+            .with_odata_expand(&["tags"]);
+
+        for name in ["$search", "$expand"] {
+            let count = builder
+                .spec
+                .params
+                .iter()
+                .filter(|p| p.name == name)
+                .count();
+            assert_eq!(count, 1, "{name} documented once");
+        }
+        let search = builder.spec.params.iter().find(|p| p.name == "$search");
+        assert!(
+            search
+                .unwrap()
+                .description
+                .as_deref()
+                .unwrap()
+                .ends_with("name, email")
+        );
+        assert_eq!(
+            builder
+                .spec
+                .vendor_extensions
+                .x_odata_expand
+                .unwrap()
+                .allowed_fields,
+            vec!["owner", "tags"]
+        );
+    }
+
+    #[tokio::test]
+    async fn odata_expand_is_limited_to_declared_relations() {
+        use tower::ServiceExt as _;
+
+        async fn list(
+            crate::api::odata::OData(query): crate::api::odata::OData,
+        ) -> Json<serde_json::Value> {
+            Json(serde_json::json!({ "expand": query.expanded() }))
+        }
+
+        let registry = MockRegistry::new();
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/items")
+            .public()
+            .with_odata_expand(&["owner"])
+            .handler(list)
+            .json_response(http::StatusCode::OK, "Items")
+            .register(Router::new(), &registry)
+            .route("/tests/v1/plain", axum::routing::get(list));
+
+        let status = |uri: &'static str| {
+            let router = router.clone();
+            async move {
+                let request = http::Request::get(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap();
+                router.oneshot(request).await.unwrap().status()
+            }
+        };
+        assert_eq!(
+            status("/tests/v1/items?$expand=Owner").await,
+            http::StatusCode::OK
+        );
+        assert_eq!(
+            status("/tests/v1/items?$expand=owner,tags").await,
+            http::StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            status("/tests/v1/plain?$expand=owner").await,
+            http::StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
 }
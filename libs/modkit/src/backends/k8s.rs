@@ -0,0 +1,770 @@
+//! Kubernetes backend implementation
+//!
+//! Runs each out-of-process module as a Kubernetes `Deployment`, managed
+//! through the Kubernetes REST API.
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use http::StatusCode;
+use modkit_http::{
+    HttpClient, HttpClientBuilder, HttpClientConfig, TlsRootConfig, TransportSecurity,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::{BackendKind, InstanceHandle, ModuleRuntimeBackend, OopModuleConfig};
+use crate::runtime::MODKIT_DIRECTORY_ENDPOINT_ENV;
+
+/// Service account token mounted into every pod
+const IN_CLUSTER_TOKEN_FILE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+
+/// Timeout for a single Kubernetes API request
+const API_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Label carrying the module name
+const MODULE_LABEL: &str = "app.kubernetes.io/name";
+
+/// Label marking Deployments created by the host
+const MANAGED_BY_LABEL: &str = "app.kubernetes.io/managed-by";
+
+/// Label carrying the host-side instance id of a Deployment
+const INSTANCE_LABEL: &str = "modkit.cyberfabric.io/instance-id";
+
+/// Label carrying the id of the backend (host) that created a Deployment
+const OWNER_LABEL: &str = "modkit.cyberfabric.io/owner-id";
+
+/// Hex digits of the instance id appended to Deployment names
+const NAME_SUFFIX_LEN: usize = 10;
+
+/// Deployment settings of an out-of-process module.
+///
+/// ```yaml
+/// runtime:
+///   type: oop
+///   backend: k8s
+///   k8s:
+///     image: registry.example.com/calculator:1.2.0
+///     namespace: cyberfabric
+///     replicas: 2
+///     directory_endpoint: http://cyberfabric-host.cyberfabric:50051
+/// ```
+///
+/// The API server and token default to the in-cluster service account
+/// (`KUBERNETES_SERVICE_HOST`/`KUBERNETES_SERVICE_PORT` and the mounted
+/// token). TLS is verified against the OS trust store; in-cluster, point
+/// `SSL_CERT_FILE` at the service account `ca.crt`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct K8sDeploymentConfig {
+    /// Container image of the module.
+    pub image: String,
+    /// Namespace to create the Deployment in.
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+    /// Number of pod replicas.
+    #[serde(default = "default_replicas")]
+    pub replicas: u32,
+    /// Container image pull policy (`Always`, `IfNotPresent`, `Never`).
+    #[serde(default)]
+    pub image_pull_policy: Option<String>,
+    /// Extra labels for the Deployment and its pods.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Directory endpoint reachable from the pods. Overrides the host's
+    /// local gRPC hub address, which pods usually cannot reach.
+    #[serde(default)]
+    pub directory_endpoint: Option<String>,
+    /// Kubernetes API server URL (default: in-cluster service address).
+    #[serde(default)]
+    pub api_server: Option<String>,
+    /// Bearer token file (default: in-cluster service account token, if present).
+    #[serde(default)]
+    pub token_file: Option<PathBuf>,
+}
+
+fn default_namespace() -> String {
+    "default".to_owned()
+}
+
+fn default_replicas() -> u32 {
+    1
+}
+
+impl K8sDeploymentConfig {
+    /// Config for `image` with default namespace and a single replica.
+    #[must_use]
+    pub fn new(image: impl Into<String>) -> Self {
+        Self {
+            image: image.into(),
+            namespace: default_namespace(),
+            replicas: default_replicas(),
+            image_pull_policy: None,
+            labels: BTreeMap::new(),
+            directory_endpoint: None,
+            api_server: None,
+            token_file: None,
+        }
+    }
+}
+
+/// Base name for a module's Deployments: DNS-1123 label derived from the module name.
+fn module_name(module: &str) -> String {
+    let name: String = module
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let name = format!("cf-{}", name.trim_matches('-'));
+    name.chars()
+        .take(63 - NAME_SUFFIX_LEN - 1)
+        .collect::<String>()
+        .trim_end_matches('-')
+        .to_owned()
+}
+
+/// Deployment name of one instance: the module name plus the random tail of its id, so
+/// hosts running the same module never share a Deployment.
+fn deployment_name(module: &str, instance_id: Uuid) -> String {
+    let id = instance_id.simple().to_string();
+    format!(
+        "{}-{}",
+        module_name(module),
+        &id[id.len() - NAME_SUFFIX_LEN..]
+    )
+}
+
+/// Minimal client for the `apps/v1` Deployments API
+struct DeploymentsApi {
+    http: HttpClient,
+    base: String,
+    token_file: Option<PathBuf>,
+}
+
+impl DeploymentsApi {
+    fn new(cfg: &K8sDeploymentConfig) -> Result<Self> {
+        let server = if let Some(server) = &cfg.api_server {
+            server.trim_end_matches('/').to_owned()
+        } else {
+            in_cluster_server()?
+        };
+
+        let token_file = match &cfg.token_file {
+            Some(path) => Some(path.clone()),
+            None => Some(PathBuf::from(IN_CLUSTER_TOKEN_FILE)).filter(|p| p.exists()),
+        };
+
+        let insecure = server.starts_with("http://");
+        let http = HttpClientBuilder::with_config(HttpClientConfig {
+            transport: if insecure {
+                TransportSecurity::AllowInsecureHttp
+            } else {
+                TransportSecurity::TlsOnly
+            },
+            tls_roots: if insecure {
+                TlsRootConfig::WebPki
+            } else {
+                TlsRootConfig::Native
+            },
+            ..HttpClientConfig::default()
+        })
+        .timeout(API_REQUEST_TIMEOUT)
+        .build()
+        .context("failed to build Kubernetes API client")?;
+
+        Ok(Self {
+            http,
+            base: format!(
+                "{server}/apis/apps/v1/namespaces/{}/deployments",
+                cfg.namespace
+            ),
+            token_file,
+        })
+    }
+
+    fn auth_header(&self) -> Result<Option<String>> {
+        let Some(path) = &self.token_file else {
+            return Ok(None);
+        };
+        // Re-read on every request: projected service account tokens rotate.
+        let token = read_token(path)?;
+        Ok(Some(format!("Bearer {token}")))
+    }
+
+    fn with_auth(&self, req: modkit_http::RequestBuilder) -> Result<modkit_http::RequestBuilder> {
+        Ok(match self.auth_header()? {
+            Some(value) => req.header("authorization", &value),
+            None => req,
+        })
+    }
+
+    /// Create the Deployment and return its `metadata.uid`.
+    ///
+    /// An existing Deployment with the same name belongs to someone else and is an error.
+    async fn create(&self, deployment: &Value) -> Result<String> {
+        let resp = self
+            .with_auth(self.http.post(&self.base))?
+            .json(deployment)?
+            .send()
+            .await?;
+        let created: Value = check_status(resp, "create deployment")
+            .await?
+            .map_or(Value::Null, |body| {
+                serde_json::from_str(&body).unwrap_or(Value::Null)
+            });
+        created
+            .pointer("/metadata/uid")
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .context("Kubernetes API returned a deployment without metadata.uid")
+    }
+
+    /// Delete the Deployment if it is still the one with `uid`; a missing or
+    /// replaced Deployment is not an error.
+    async fn delete(&self, name: &str, uid: &str) -> Result<()> {
+        let url = format!("{}/{name}", self.base);
+        let options = json!({
+            "kind": "DeleteOptions",
+            "apiVersion": "v1",
+            "propagationPolicy": "Background",
+            "preconditions": { "uid": uid },
+        });
+        let resp = self
+            .with_auth(self.http.delete(&url))?
+            .json(&options)?
+            .send()
+            .await?;
+        match resp.status() {
+            StatusCode::NOT_FOUND => Ok(()),
+            StatusCode::CONFLICT => {
+                tracing::debug!(deployment = %name, "Deployment was replaced by someone else, leaving it");
+                Ok(())
+            }
+            _ => check_status(resp, "delete deployment").await.map(drop),
+        }
+    }
+}
+
+/// API server address of the cluster the host runs in.
+fn in_cluster_server() -> Result<String> {
+    let host = std::env::var("KUBERNETES_SERVICE_HOST")
+        .context("k8s.api_server is not set and KUBERNETES_SERVICE_HOST is not available")?;
+    let port = std::env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_owned());
+    if host.contains(':') {
+        Ok(format!("https://[{host}]:{port}"))
+    } else {
+        Ok(format!("https://{host}:{port}"))
+    }
+}
+
+fn read_token(path: &Path) -> Result<String> {
+    let token = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read Kubernetes token from {}", path.display()))?;
+    Ok(token.trim().to_owned())
+}
+
+/// Body of a successful response (`None` if unreadable), or the API error.
+async fn check_status(resp: modkit_http::HttpResponse, action: &str) -> Result<Option<String>> {
+    let status = resp.status();
+    let body = resp
+        .bytes()
+        .await
+        .map(|b| String::from_utf8_lossy(&b).into_owned());
+    if status.is_success() {
+        return Ok(body.ok());
+    }
+    let body = body.unwrap_or_default();
+    let message = serde_json::from_str::<Value>(&body)
+        .ok()
+        .and_then(|v| v.get("message").and_then(Value::as_str).map(str::to_owned))
+        .unwrap_or(body);
+    bail!("Kubernetes API failed to {action}: {status}: {message}")
+}
+
+/// Build the `apps/v1` Deployment manifest for a module.
+fn deployment_manifest(
+    owner: Uuid,
+    instance_id: Uuid,
+    cfg: &OopModuleConfig,
+    k8s: &K8sDeploymentConfig,
+) -> Value {
+    let module = module_name(&cfg.name);
+    let mut labels = k8s.labels.clone();
+    labels.insert(MODULE_LABEL.to_owned(), module.clone());
+    labels.insert(MANAGED_BY_LABEL.to_owned(), "modkit".to_owned());
+    labels.insert(INSTANCE_LABEL.to_owned(), instance_id.to_string());
+    labels.insert(OWNER_LABEL.to_owned(), owner.to_string());
+
+    let mut env: BTreeMap<&str, &str> = cfg
+        .env
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    if let Some(endpoint) = &k8s.directory_endpoint {
+        env.insert(MODKIT_DIRECTORY_ENDPOINT_ENV, endpoint);
+    }
+    let env: Vec<Value> = env
+        .into_iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect();
+
+    let mut container = json!({
+        "name": module,
+        "image": k8s.image,
+        "args": cfg.args,
+        "env": env,
+    });
+    if let Some(policy) = &k8s.image_pull_policy {
+        container["imagePullPolicy"] = json!(policy);
+    }
+    if let Some(dir) = &cfg.working_directory {
+        container["workingDir"] = json!(dir);
+    }
+
+    json!({
+        "apiVersion": "apps/v1",
+        "kind": "Deployment",
+        "metadata": {
+            "name": deployment_name(&cfg.name, instance_id),
+            "namespace": k8s.namespace,
+            "labels": labels,
+        },
+        "spec": {
+            "replicas": k8s.replicas,
+            "selector": {
+                "matchLabels": {
+                    MODULE_LABEL: module,
+                    MANAGED_BY_LABEL: "modkit",
+                    INSTANCE_LABEL: instance_id.to_string(),
+                },
+            },
+            "template": {
+                "metadata": { "labels": labels },
+                "spec": { "containers": [container] },
+            },
+        },
+    })
+}
+
+/// A Deployment created by the backend
+struct K8sInstance {
+    handle: InstanceHandle,
+    name: String,
+    uid: String,
+    api: Arc<DeploymentsApi>,
+}
+
+impl K8sInstance {
+    async fn delete(&self) -> Result<()> {
+        self.api.delete(&self.name, &self.uid).await
+    }
+}
+
+/// Backend that runs `OoP` modules as Kubernetes Deployments.
+///
+/// Each spawned module becomes its own Deployment named `cf-<module>-<id>`,
+/// labelled with the backend's owner id; pods register themselves with the
+/// directory like any other `OoP` module. Restarts are left to Kubernetes, so
+/// the module's restart policy is not applied here.
+///
+/// Several hosts may run the same module: each backend only ever deletes the
+/// Deployments it created (matched by uid), when an instance is stopped or the
+/// cancellation token is triggered.
+pub struct K8sBackend {
+    owner: Uuid,
+    instances: Arc<RwLock<HashMap<Uuid, K8sInstance>>>,
+}
+
+impl K8sBackend {
+    /// Create a new `K8sBackend` with the given cancellation token.
+    ///
+    /// When the token is cancelled, all Deployments created by the backend are deleted.
+    #[must_use]
+    pub fn new(cancel: CancellationToken) -> Self {
+        let backend = Self {
+            owner: Uuid::new_v4(),
+            instances: Arc::new(RwLock::new(HashMap::new())),
+        };
+
+        let instances = Arc::clone(&backend.instances);
+        tokio::spawn(async move {
+            cancel.cancelled().await;
+            let all: Vec<K8sInstance> = instances.write().drain().map(|(_, i)| i).collect();
+            if all.is_empty() {
+                return;
+            }
+            tracing::info!(
+                count = all.len(),
+                "K8sBackend: deleting OoP module deployments"
+            );
+            for inst in all {
+                if let Err(e) = inst.delete().await {
+                    tracing::warn!(deployment = %inst.name, error = %e, "Failed to delete deployment");
+                }
+            }
+        });
+
+        backend
+    }
+}
+
+#[async_trait]
+impl ModuleRuntimeBackend for K8sBackend {
+    async fn spawn_instance(&self, cfg: &OopModuleConfig) -> Result<InstanceHandle> {
+        if cfg.backend != BackendKind::K8s {
+            bail!(
+                "K8sBackend can only spawn K8s instances, got {:?}",
+                cfg.backend
+            );
+        }
+        let k8s = cfg
+            .k8s
+            .as_ref()
+            .context("k8s deployment config must be set for K8s backend")?;
+
+        let api = Arc::new(DeploymentsApi::new(k8s)?);
+        let instance_id = Uuid::now_v7();
+        let name = deployment_name(&cfg.name, instance_id);
+
+        let uid = api
+            .create(&deployment_manifest(self.owner, instance_id, cfg, k8s))
+            .await?;
+
+        tracing::info!(
+            module = %cfg.name,
+            instance_id = %instance_id,
+            deployment = %name,
+            namespace = %k8s.namespace,
+            replicas = k8s.replicas,
+            "Applied Kubernetes deployment for OoP module"
+        );
+
+        let handle = InstanceHandle {
+            module: cfg.name.clone(),
+            instance_id,
+            backend: BackendKind::K8s,
+            pid: None,
+            created_at: Instant::now(),
+        };
+        self.instances.write().insert(
+            instance_id,
+            K8sInstance {
+                handle: handle.clone(),
+                name,
+                uid,
+                api,
+            },
+        );
+
+        Ok(handle)
+    }
+
+    async fn stop_instance(&self, handle: &InstanceHandle) -> Result<()> {
+        let inst = self.instances.write().remove(&handle.instance_id);
+        if let Some(inst) = inst {
+            inst.delete().await?;
+        } else {
+            tracing::debug!(
+                module = %handle.module,
+                instance_id = %handle.instance_id,
+                "stop_instance called for unknown instance, ignoring"
+            );
+        }
+        Ok(())
+    }
+
+    async fn list_instances(&self, module: &str) -> Result<Vec<InstanceHandle>> {
+        let instances = self.instances.read();
+        Ok(instances
+            .values()
+            .filter(|inst| inst.handle.module == module)
+            .map(|inst| inst.handle.clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use std::io::Write;
+
+    const DEPLOYMENTS: &str = "/apis/apps/v1/namespaces/modules/deployments";
+
+    fn module_config(server: &MockServer, token_file: Option<PathBuf>) -> OopModuleConfig {
+        let mut k8s = K8sDeploymentConfig::new("registry.local/calc:1.0");
+        k8s.namespace = "modules".to_owned();
+        k8s.replicas = 2;
+        k8s.api_server = Some(server.base_url());
+        k8s.token_file = token_file;
+        k8s.directory_endpoint = Some("http://host.modules:50051".to_owned());
+
+        let mut cfg = OopModuleConfig::new("calc_module", BackendKind::K8s);
+        cfg.args = vec!["--config".to_owned(), "/etc/calc.yaml".to_owned()];
+        cfg.env.insert(
+            MODKIT_DIRECTORY_ENDPOINT_ENV.to_owned(),
+            "http://127.0.0.1:50051".to_owned(),
+        );
+        cfg.k8s = Some(k8s);
+        cfg
+    }
+
+    const CALC_DEPLOYMENT: &str =
+        "^/apis/apps/v1/namespaces/modules/deployments/cf-calc-module-[0-9a-f]{10}$";
+
+    fn created(uid: &str) -> serde_json::Value {
+        serde_json::json!({"metadata": {"uid": uid}})
+    }
+
+    #[test]
+    fn test_deployment_name_is_dns_label() {
+        assert_eq!(module_name("calc_module"), "cf-calc-module");
+        assert_eq!(module_name("_Calc.V2_"), "cf-calc-v2");
+
+        let id = Uuid::now_v7();
+        let name = deployment_name("calc_module", id);
+        assert!(name.starts_with("cf-calc-module-"), "{name}");
+        assert!(
+            id.simple()
+                .to_string()
+                .ends_with(&name["cf-calc-module-".len()..])
+        );
+        assert!(deployment_name(&"x".repeat(100), id).len() <= 63);
+        assert_ne!(name, deployment_name("calc_module", Uuid::now_v7()));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_creates_deployment() {
+        let server = MockServer::start_async().await;
+        let mut token = tempfile::NamedTempFile::new().unwrap();
+        writeln!(token, "secret-token").unwrap();
+
+        let create = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path(DEPLOYMENTS)
+                    .header("authorization", "Bearer secret-token")
+                    .json_body_includes(
+                        r#"{
+                            "kind": "Deployment",
+                            "metadata": {
+                                "namespace": "modules",
+                                "labels": {"app.kubernetes.io/name": "cf-calc-module"}
+                            },
+                            "spec": {
+                                "replicas": 2,
+                                "template": {"spec": {"containers": [{
+                                    "name": "cf-calc-module",
+                                    "image": "registry.local/calc:1.0",
+                                    "args": ["--config", "/etc/calc.yaml"],
+                                    "env": [{"name": "MODKIT_DIRECTORY_ENDPOINT", "value": "http://host.modules:50051"}]
+                                }]}}
+                            }
+                        }"#,
+                    );
+                then.status(201).json_body(created("uid-1"));
+            })
+            .await;
+
+        let backend = K8sBackend::new(CancellationToken::new());
+        let handle = backend
+            .spawn_instance(&module_config(&server, Some(token.path().to_path_buf())))
+            .await
+            .unwrap();
+
+        create.assert_async().await;
+        assert_eq!(handle.backend, BackendKind::K8s);
+        assert_eq!(handle.pid, None);
+        assert_eq!(
+            backend.list_instances("calc_module").await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawn_never_replaces_existing_deployment() {
+        let server = MockServer::start_async().await;
+        let create = server
+            .mock_async(|when, then| {
+                when.method(POST).path(DEPLOYMENTS);
+                then.status(409)
+                    .json_body(serde_json::json!({"message": "already exists"}));
+            })
+            .await;
+        let replace = server
+            .mock_async(|when, then| {
+                when.method(PUT);
+                then.status(200).json_body(serde_json::json!({}));
+            })
+            .await;
+
+        let backend = K8sBackend::new(CancellationToken::new());
+        let err = backend
+            .spawn_instance(&module_config(&server, None))
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("already exists"), "{err}");
+        create.assert_async().await;
+        replace.assert_calls_async(0).await;
+    }
+
+    #[tokio::test]
+    async fn test_spawn_surfaces_api_errors() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path(DEPLOYMENTS);
+                then.status(403)
+                    .json_body(serde_json::json!({"message": "deployments is forbidden"}));
+            })
+            .await;
+
+        let backend = K8sBackend::new(CancellationToken::new());
+        let err = backend
+            .spawn_instance(&module_config(&server, None))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("deployments is forbidden"),
+            "{err}"
+        );
+        assert!(
+            backend
+                .list_instances("calc_module")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stop_and_cancel_delete_deployments() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path(DEPLOYMENTS);
+                then.status(201).json_body(created("uid-1"));
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(DELETE)
+                    .path_matches(CALC_DEPLOYMENT)
+                    .json_body_includes(
+                        r#"{"propagationPolicy": "Background", "preconditions": {"uid": "uid-1"}}"#,
+                    );
+                then.status(200).json_body(serde_json::json!({}));
+            })
+            .await;
+
+        let cancel = CancellationToken::new();
+        let backend = K8sBackend::new(cancel.clone());
+
+        let handle = backend
+            .spawn_instance(&module_config(&server, None))
+            .await
+            .unwrap();
+        backend.stop_instance(&handle).await.unwrap();
+        delete.assert_calls_async(1).await;
+        assert!(
+            backend
+                .list_instances("calc_module")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        backend
+            .spawn_instance(&module_config(&server, None))
+            .await
+            .unwrap();
+        cancel.cancel();
+        for _ in 0..200 {
+            if delete.calls_async().await == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        delete.assert_calls_async(2).await;
+    }
+
+    #[tokio::test]
+    async fn test_hosts_sharing_a_module_delete_only_their_deployments() {
+        let server = MockServer::start_async().await;
+        let (first, second) = (
+            K8sBackend::new(CancellationToken::new()),
+            K8sBackend::new(CancellationToken::new()),
+        );
+        for (backend, uid) in [(&first, "uid-first"), (&second, "uid-second")] {
+            let owner = backend.owner.to_string();
+            server
+                .mock_async(move |when, then| {
+                    when.method(POST).path(DEPLOYMENTS).json_body_includes(
+                        serde_json::json!({"metadata": {"labels": {OWNER_LABEL: owner}}})
+                            .to_string(),
+                    );
+                    then.status(201).json_body(created(uid));
+                })
+                .await;
+        }
+        let delete_first = server
+            .mock_async(|when, then| {
+                when.method(DELETE)
+                    .path_matches(CALC_DEPLOYMENT)
+                    .json_body_includes(r#"{"preconditions": {"uid": "uid-first"}}"#);
+                then.status(200).json_body(serde_json::json!({}));
+            })
+            .await;
+        let delete_second = server
+            .mock_async(|when, then| {
+                when.method(DELETE)
+                    .json_body_includes(r#"{"preconditions": {"uid": "uid-second"}}"#);
+                then.status(200).json_body(serde_json::json!({}));
+            })
+            .await;
+
+        let handle = first
+            .spawn_instance(&module_config(&server, None))
+            .await
+            .unwrap();
+        second
+            .spawn_instance(&module_config(&server, None))
+            .await
+            .unwrap();
+        let names: Vec<String> = [&first, &second]
+            .iter()
+            .flat_map(|b| {
+                b.instances
+                    .read()
+                    .values()
+                    .map(|i| i.name.clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_ne!(names[0], names[1]);
+
+        first.stop_instance(&handle).await.unwrap();
+        delete_first.assert_calls_async(1).await;
+        delete_second.assert_calls_async(0).await;
+    }
+
+    #[tokio::test]
+    async fn test_spawn_requires_k8s_config() {
+        let backend = K8sBackend::new(CancellationToken::new());
+        let cfg = OopModuleConfig::new("calc", BackendKind::K8s);
+        assert!(backend.spawn_instance(&cfg).await.is_err());
+    }
+}
@@ -4,17 +4,20 @@ use anyhow::{Context, Result, bail};
 use async_trait::async_trait;
 use parking_lot::RwLock;
 use std::collections::HashMap;
-use std::path::Path;
-use std::process::Stdio;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::process::{Child, Command};
+use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use super::log_forwarder::{StreamKind, spawn_stream_forwarder};
+use super::restart::{RestartDecision, RestartTracker};
 use super::{BackendKind, InstanceHandle, ModuleRuntimeBackend, OopModuleConfig};
+use crate::runtime::{MODKIT_INSTANCE_ID_ENV, ModuleManager};
 
 /// Grace period before force-killing processes on shutdown
 const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
@@ -145,9 +148,18 @@ async fn wait_forwarder(handle: Option<JoinHandle<()>>) {
     }
 }
 
-/// Internal representation of a local process instance
-struct LocalInstance {
-    handle: InstanceHandle,
+/// Everything needed to (re)start the process of an instance
+#[derive(Clone)]
+struct ProcessSpec {
+    module: String,
+    binary: PathBuf,
+    args: Vec<String>,
+    env: HashMap<String, String>,
+    working_directory: Option<String>,
+}
+
+/// A started process together with its log forwarders
+struct RunningProcess {
     child: Child,
     /// Task handle for stdout log forwarder
     stdout_forwarder: Option<JoinHandle<()>>,
@@ -155,11 +167,233 @@ struct LocalInstance {
     stderr_forwarder: Option<JoinHandle<()>>,
 }
 
+impl RunningProcess {
+    /// Wait for the log forwarders to flush the remaining output.
+    async fn drain(self) {
+        wait_forwarder(self.stdout_forwarder).await;
+        wait_forwarder(self.stderr_forwarder).await;
+    }
+}
+
+/// Start the process for `instance_id` with stdout/stderr forwarded to tracing.
+///
+/// The instance ID is passed to the process so that it registers in the
+/// directory under the same ID across restarts.
+fn start_process(
+    spec: &ProcessSpec,
+    instance_id: Uuid,
+    cancel: &CancellationToken,
+) -> Result<RunningProcess> {
+    // Build command
+    let mut cmd = Command::new(&spec.binary);
+    cmd.args(&spec.args);
+    cmd.envs(&spec.env);
+    cmd.env(MODKIT_INSTANCE_ID_ENV, instance_id.to_string());
+
+    // Pipe stdout/stderr for log forwarding
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    // Set working directory if specified
+    if let Some(ref working_dir) = spec.working_directory {
+        let path = Path::new(working_dir);
+        if path.exists() && path.is_dir() {
+            cmd.current_dir(path);
+        } else {
+            tracing::warn!(
+                module = %spec.module,
+                working_dir = %working_dir,
+                "Working directory does not exist or is not a directory, using current dir"
+            );
+        }
+    }
+
+    // Spawn the process
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("failed to spawn process: {}", spec.binary.display()))?;
+
+    // Spawn log forwarder tasks for stdout/stderr with cancellation support
+    let stdout_forwarder = child.stdout.take().map(|stdout| {
+        spawn_stream_forwarder(
+            stdout,
+            spec.module.clone(),
+            instance_id,
+            cancel.clone(),
+            StreamKind::Stdout,
+        )
+    });
+    let stderr_forwarder = child.stderr.take().map(|stderr| {
+        spawn_stream_forwarder(
+            stderr,
+            spec.module.clone(),
+            instance_id,
+            cancel.clone(),
+            StreamKind::Stderr,
+        )
+    });
+
+    Ok(RunningProcess {
+        child,
+        stdout_forwarder,
+        stderr_forwarder,
+    })
+}
+
+/// Internal representation of a local process instance
+struct LocalInstance {
+    handle: InstanceHandle,
+    /// Asks the supervisor to stop the process within the given grace period
+    stop: oneshot::Sender<Duration>,
+    /// Task that owns the process and applies the restart policy
+    supervisor: JoinHandle<()>,
+}
+
+impl LocalInstance {
+    /// Stop the process and wait for the supervisor to finish.
+    async fn stop(self, grace: Duration) {
+        // The supervisor may already be gone if the process exited for good.
+        _ = self.stop.send(grace);
+        _ = self.supervisor.await;
+    }
+}
+
 /// Map key type for instances - uses Uuid directly
 type InstanceMap = HashMap<Uuid, LocalInstance>;
 
+/// Watches one instance's process and restarts it according to its policy.
+///
+/// Restart events are reported to the `ModuleManager` (if any) so that they
+/// show up in the instance state of the module directory.
+struct Supervisor {
+    spec: ProcessSpec,
+    handle: InstanceHandle,
+    tracker: RestartTracker,
+    instances: Arc<RwLock<InstanceMap>>,
+    module_manager: Option<Arc<ModuleManager>>,
+    cancel: CancellationToken,
+}
+
+impl Supervisor {
+    async fn run(mut self, mut process: RunningProcess, mut stop: oneshot::Receiver<Duration>) {
+        loop {
+            let status = tokio::select! {
+                status = process.child.wait() => status.ok(),
+                grace = &mut stop => {
+                    let grace = grace.unwrap_or(INSTANCE_STOP_GRACE_PERIOD);
+                    stop_child_with_grace(&mut process.child, &self.handle, grace, "stop").await;
+                    process.drain().await;
+                    return;
+                }
+            };
+            process.drain().await;
+
+            match self.restart_after(status, &mut stop).await {
+                Some(next) => process = next,
+                None => return,
+            }
+        }
+    }
+
+    /// Apply the restart policy to an exit. Returns the restarted process, or
+    /// `None` once supervision of this instance is over.
+    async fn restart_after(
+        &mut self,
+        mut status: Option<ExitStatus>,
+        stop: &mut oneshot::Receiver<Duration>,
+    ) -> Option<RunningProcess> {
+        let module = self.handle.module.clone();
+        let instance_id = self.handle.instance_id;
+        loop {
+            match self.tracker.on_exit(status, Instant::now()) {
+                RestartDecision::Stop => {
+                    tracing::info!(
+                        module = %module,
+                        instance_id = %instance_id,
+                        status = ?status,
+                        "OoP module process exited"
+                    );
+                    self.release();
+                    return None;
+                }
+                RestartDecision::GiveUp => {
+                    tracing::error!(
+                        module = %module,
+                        instance_id = %instance_id,
+                        status = ?status,
+                        "OoP module is crash-looping, giving up on restarts"
+                    );
+                    if let Some(mm) = &self.module_manager {
+                        mm.mark_failed(&module, instance_id);
+                    }
+                    self.release();
+                    return None;
+                }
+                RestartDecision::Restart { delay, attempt } => {
+                    tracing::warn!(
+                        module = %module,
+                        instance_id = %instance_id,
+                        status = ?status,
+                        attempt,
+                        delay_ms = delay.as_millis(),
+                        "OoP module process exited, restarting"
+                    );
+                    if let Some(mm) = &self.module_manager {
+                        mm.record_restart(&module, instance_id);
+                    }
+
+                    tokio::select! {
+                        () = tokio::time::sleep(delay) => {}
+                        _ = &mut *stop => {
+                            // Stopped during backoff: no process will come back.
+                            if let Some(mm) = &self.module_manager {
+                                mm.deregister(&module, instance_id);
+                            }
+                            return None;
+                        }
+                    }
+
+                    match start_process(&self.spec, instance_id, &self.cancel) {
+                        Ok(process) => {
+                            let pid = process.child.id();
+                            if let Some(inst) = self.instances.write().get_mut(&instance_id) {
+                                inst.handle.pid = pid;
+                            }
+                            tracing::info!(
+                                module = %module,
+                                instance_id = %instance_id,
+                                pid = ?pid,
+                                attempt,
+                                "Restarted OoP module"
+                            );
+                            return Some(process);
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                module = %module,
+                                instance_id = %instance_id,
+                                error = %e,
+                                "Failed to restart OoP module"
+                            );
+                            status = None;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Forget the instance once its process is gone for good.
+    fn release(&self) {
+        self.instances.write().remove(&self.handle.instance_id);
+    }
+}
+
 /// Backend that spawns modules as local child processes and manages their lifecycle.
 ///
+/// Each instance is watched by a supervisor task that restarts the process
+/// according to the instance's [`RestartPolicy`](super::RestartPolicy).
+///
 /// When the cancellation token is triggered, the backend will:
 /// 1. Send termination signal to all processes (SIGTERM on Unix, `TerminateProcess` on Windows)
 /// 2. Wait up to 5 seconds for graceful shutdown
@@ -193,7 +427,7 @@ impl LocalProcessBackend {
 
     /// Gracefully stop all tracked instances with timeout.
     async fn shutdown_all_instances(instances: Arc<RwLock<InstanceMap>>) {
-        let mut all_instances: Vec<LocalInstance> = {
+        let all_instances: Vec<LocalInstance> = {
             let mut guard = instances.write();
             guard.drain().map(|(_, inst)| inst).collect()
         };
@@ -204,21 +438,9 @@ impl LocalProcessBackend {
 
         tracing::info!(count = all_instances.len(), "Stopping OoP module processes");
 
-        // Stop all processes with grace period
-        for inst in &mut all_instances {
-            stop_child_with_grace(
-                &mut inst.child,
-                &inst.handle,
-                SHUTDOWN_GRACE_PERIOD,
-                "shutdown",
-            )
-            .await;
-        }
-
-        // Wait for forwarders to drain
+        // Stop all processes with grace period; supervisors drain their forwarders
         for inst in all_instances {
-            wait_forwarder(inst.stdout_forwarder).await;
-            wait_forwarder(inst.stderr_forwarder).await;
+            inst.stop(SHUTDOWN_GRACE_PERIOD).await;
         }
 
         tracing::info!("All OoP module processes stopped");
@@ -245,63 +467,23 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
         // Generate unique instance ID using UUID v7
         let instance_id = Uuid::now_v7();
 
-        // Build command
-        let mut cmd = Command::new(binary);
-        cmd.args(&cfg.args);
-        cmd.envs(&cfg.env);
-
-        // Pipe stdout/stderr for log forwarding
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-
-        // Set working directory if specified
-        if let Some(ref working_dir) = cfg.working_directory {
-            let path = Path::new(working_dir);
-            if path.exists() && path.is_dir() {
-                cmd.current_dir(path);
-            } else {
-                tracing::warn!(
-                    module = %cfg.name,
-                    working_dir = %working_dir,
-                    "Working directory does not exist or is not a directory, using current dir"
-                );
-            }
-        }
-
-        // Spawn the process
-        let mut child = cmd
-            .spawn()
-            .with_context(|| format!("failed to spawn process: {}", binary.display()))?;
+        let spec = ProcessSpec {
+            module: cfg.name.clone(),
+            binary: binary.clone(),
+            args: cfg.args.clone(),
+            env: cfg.env.clone(),
+            working_directory: cfg.working_directory.clone(),
+        };
+        let process = start_process(&spec, instance_id, &self.cancel)?;
 
         // Get PID
-        let pid = child.id();
-
-        // Spawn log forwarder tasks for stdout/stderr with cancellation support
-        let module_name = cfg.name.clone();
-        let cancel = self.cancel.clone();
-        let stdout_forwarder = child.stdout.take().map(|stdout| {
-            spawn_stream_forwarder(
-                stdout,
-                module_name.clone(),
-                instance_id,
-                cancel.clone(),
-                StreamKind::Stdout,
-            )
-        });
-        let stderr_forwarder = child.stderr.take().map(|stderr| {
-            spawn_stream_forwarder(
-                stderr,
-                module_name.clone(),
-                instance_id,
-                cancel.clone(),
-                StreamKind::Stderr,
-            )
-        });
+        let pid = process.child.id();
 
         tracing::info!(
             module = %cfg.name,
             instance_id = %instance_id,
             pid = ?pid,
+            restart = ?cfg.restart.policy,
             "Spawned OoP module with log forwarding"
         );
 
@@ -314,16 +496,28 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
             created_at: std::time::Instant::now(),
         };
 
-        // Store in instances map
+        let supervisor = Supervisor {
+            spec,
+            handle: handle.clone(),
+            tracker: RestartTracker::new(cfg.restart.clone()),
+            instances: Arc::clone(&self.instances),
+            module_manager: cfg.module_manager.clone(),
+            cancel: self.cancel.clone(),
+        };
+        let (stop, stop_rx) = oneshot::channel();
+
+        // Store in instances map. The lock is held while the supervisor is
+        // spawned so that an immediate exit cannot release the instance before
+        // it is inserted.
         {
             let mut instances = self.instances.write();
+            let supervisor = tokio::spawn(supervisor.run(process, stop_rx));
             instances.insert(
                 instance_id,
                 LocalInstance {
                     handle: handle.clone(),
-                    child,
-                    stdout_forwarder,
-                    stderr_forwarder,
+                    stop,
+                    supervisor,
                 },
             );
         }
@@ -337,17 +531,8 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
             instances.remove(&handle.instance_id)
         };
 
-        if let Some(mut local) = local {
-            stop_child_with_grace(
-                &mut local.child,
-                &local.handle,
-                INSTANCE_STOP_GRACE_PERIOD,
-                "stop_instance",
-            )
-            .await;
-
-            // we do not await forwarders here, they'll stop on their own via CancellationToken and pipe close;
-            // shutdown_all_instances handles draining for global shutdown
+        if let Some(local) = local {
+            local.stop(INSTANCE_STOP_GRACE_PERIOD).await;
         } else {
             tracing::debug!(
                 module = %handle.module,
@@ -538,6 +723,98 @@ mod tests {
         assert_eq!(instances.len(), 0);
     }
 
+    #[cfg(unix)]
+    fn failing_module(name: &str, restart: super::super::RestartPolicy) -> OopModuleConfig {
+        let mut cfg = OopModuleConfig::new(name, BackendKind::LocalProcess);
+        cfg.binary = Some(PathBuf::from("/bin/sh"));
+        cfg.args = vec!["-c".to_owned(), "exit 3".to_owned()];
+        cfg.restart = restart;
+        cfg
+    }
+
+    #[cfg(unix)]
+    async fn wait_until(mut cond: impl FnMut() -> bool) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !cond() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("condition not reached in time");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exited_instance_without_restart_is_released() {
+        let backend = test_backend();
+        let cfg = failing_module("oneshot", super::super::RestartPolicy::default());
+
+        backend.spawn_instance(&cfg).await.expect("should spawn");
+        wait_until(|| backend.instances.read().is_empty()).await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_crash_loop_restarts_then_gives_up() {
+        use super::super::{RestartMode, RestartPolicy};
+        use crate::runtime::InstanceState;
+
+        let backend = test_backend();
+        let manager = Arc::new(ModuleManager::new());
+        let mut cfg = failing_module(
+            "crashy",
+            RestartPolicy {
+                policy: RestartMode::OnFailure,
+                initial_backoff: Duration::from_millis(10),
+                max_backoff: Duration::from_millis(20),
+                max_restarts: 2,
+                window: Duration::from_secs(60),
+            },
+        );
+        cfg.module_manager = Some(Arc::clone(&manager));
+
+        let handle = backend.spawn_instance(&cfg).await.expect("should spawn");
+        wait_until(|| {
+            manager
+                .instances_of("crashy")
+                .first()
+                .is_some_and(|i| i.state() == InstanceState::Failed)
+        })
+        .await;
+
+        let instance = &manager.instances_of("crashy")[0];
+        assert_eq!(instance.instance_id, handle.instance_id);
+        assert_eq!(instance.restart_count(), 2);
+        wait_until(|| backend.instances.read().is_empty()).await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stop_during_backoff_ends_supervision() {
+        use super::super::{RestartMode, RestartPolicy};
+
+        let backend = test_backend();
+        let manager = Arc::new(ModuleManager::new());
+        let mut cfg = failing_module(
+            "backoff",
+            RestartPolicy {
+                policy: RestartMode::Always,
+                initial_backoff: Duration::from_secs(60),
+                ..RestartPolicy::default()
+            },
+        );
+        cfg.module_manager = Some(Arc::clone(&manager));
+
+        let handle = backend.spawn_instance(&cfg).await.expect("should spawn");
+        wait_until(|| manager.instances_of("backoff").len() == 1).await;
+
+        tokio::time::timeout(Duration::from_secs(1), backend.stop_instance(&handle))
+            .await
+            .expect("stop should not wait for the backoff")
+            .expect("should stop");
+        assert!(manager.instances_of("backoff").is_empty());
+    }
+
     mod send_terminate_signal_tests {
         #[cfg(unix)]
         use {super::send_terminate_signal, std::time::Duration};
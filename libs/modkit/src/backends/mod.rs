@@ -2,16 +2,23 @@
 //!
 //! This module provides traits and types for spawning and managing `OoP` module instances.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 use uuid::Uuid;
 
+use crate::runtime::ModuleManager;
+
 /// The kind of backend used to spawn and manage module instances
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BackendKind {
+    #[default]
+    #[serde(alias = "local")]
     LocalProcess,
     K8s,
     Static,
@@ -19,6 +26,7 @@ pub enum BackendKind {
 }
 
 /// Configuration for an out-of-process module
+#[derive(Clone)]
 pub struct OopModuleConfig {
     pub name: String,
     pub binary: Option<PathBuf>,
@@ -27,6 +35,14 @@ pub struct OopModuleConfig {
     pub working_directory: Option<String>,
     pub backend: BackendKind,
     pub version: Option<String>,
+    /// What to do when the process exits on its own.
+    pub restart: RestartPolicy,
+    /// Directory to report restart events to, if any.
+    pub module_manager: Option<Arc<ModuleManager>>,
+    /// Pre-existing endpoints (`Static` backend).
+    pub static_endpoints: Option<StaticEndpointsConfig>,
+    /// Deployment settings (`K8s` backend).
+    pub k8s: Option<K8sDeploymentConfig>,
 }
 
 impl OopModuleConfig {
@@ -39,6 +55,10 @@ impl OopModuleConfig {
             working_directory: None,
             backend,
             version: None,
+            restart: RestartPolicy::default(),
+            module_manager: None,
+            static_endpoints: None,
+            k8s: None,
         }
     }
 }
@@ -76,10 +96,53 @@ pub trait ModuleRuntimeBackend: Send + Sync {
 /// Configuration passed to `OopBackend::spawn`
 pub struct OopSpawnConfig {
     pub module_name: String,
-    pub binary: PathBuf,
+    pub backend: BackendKind,
+    pub binary: Option<PathBuf>,
     pub args: Vec<String>,
     pub env: HashMap<String, String>,
     pub working_directory: Option<String>,
+    pub restart: RestartPolicy,
+    /// Directory to report restart events to, if any.
+    pub module_manager: Option<Arc<ModuleManager>>,
+    /// Pre-existing endpoints (`Static` backend).
+    pub static_endpoints: Option<StaticEndpointsConfig>,
+    /// Deployment settings (`K8s` backend).
+    pub k8s: Option<K8sDeploymentConfig>,
+}
+
+impl OopSpawnConfig {
+    /// Expand into per-instance module configs.
+    ///
+    /// Produces one config per configured static endpoint for the `Static`
+    /// backend, and a single config otherwise.
+    #[must_use]
+    pub fn into_module_configs(self) -> Vec<OopModuleConfig> {
+        let mut base = OopModuleConfig::new(self.module_name, self.backend);
+        base.binary = self.binary;
+        base.args = self.args;
+        base.env = self.env;
+        base.working_directory = self.working_directory;
+        base.restart = self.restart;
+        base.module_manager = self.module_manager;
+        base.k8s = self.k8s;
+
+        let Some(static_cfg) = self.static_endpoints else {
+            return vec![base];
+        };
+        static_cfg
+            .instances
+            .iter()
+            .map(|instance| {
+                let mut cfg = base.clone();
+                cfg.static_endpoints = Some(StaticEndpointsConfig {
+                    instances: vec![instance.clone()],
+                    health_check_interval: static_cfg.health_check_interval,
+                    health_check_timeout: static_cfg.health_check_timeout,
+                });
+                cfg
+            })
+            .collect()
+    }
 }
 
 /// A type-erased backend for spawning `OoP` modules.
@@ -94,10 +157,16 @@ pub trait OopBackend: Send + Sync {
     async fn shutdown_all(&self);
 }
 
+pub mod k8s;
 pub mod local;
 pub mod log_forwarder;
+pub mod restart;
+pub mod static_endpoints;
 
+pub use k8s::{K8sBackend, K8sDeploymentConfig};
 pub use local::LocalProcessBackend;
+pub use restart::{RestartMode, RestartPolicy};
+pub use static_endpoints::{StaticBackend, StaticEndpointsConfig, StaticInstanceConfig};
 
 /// Adapter that implements `OopBackend` trait for `LocalProcessBackend`.
 ///
@@ -105,13 +174,9 @@ pub use local::LocalProcessBackend;
 #[async_trait]
 impl OopBackend for LocalProcessBackend {
     async fn spawn(&self, config: OopSpawnConfig) -> Result<()> {
-        let mut oop_config = OopModuleConfig::new(&config.module_name, BackendKind::LocalProcess);
-        oop_config.binary = Some(config.binary);
-        oop_config.args = config.args;
-        oop_config.env = config.env;
-        oop_config.working_directory = config.working_directory;
-
-        self.spawn_instance(&oop_config).await?;
+        for oop_config in config.into_module_configs() {
+            self.spawn_instance(&oop_config).await?;
+        }
         Ok(())
     }
 
@@ -122,6 +187,48 @@ impl OopBackend for LocalProcessBackend {
     }
 }
 
+/// `OoP` backend that dispatches each module to the backend of its configured kind.
+#[derive(Default)]
+pub struct BackendRouter {
+    backends: HashMap<BackendKind, Arc<dyn ModuleRuntimeBackend>>,
+}
+
+impl BackendRouter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the backend that spawns modules of `kind`.
+    #[must_use]
+    pub fn with_backend(
+        mut self,
+        kind: BackendKind,
+        backend: Arc<dyn ModuleRuntimeBackend>,
+    ) -> Self {
+        self.backends.insert(kind, backend);
+        self
+    }
+}
+
+#[async_trait]
+impl OopBackend for BackendRouter {
+    async fn spawn(&self, config: OopSpawnConfig) -> Result<()> {
+        let backend = self
+            .backends
+            .get(&config.backend)
+            .with_context(|| format!("no OoP backend registered for {:?}", config.backend))?;
+        for oop_config in config.into_module_configs() {
+            backend.spawn_instance(&oop_config).await?;
+        }
+        Ok(())
+    }
+
+    async fn shutdown_all(&self) {
+        // Each backend stops its instances when its cancellation token is triggered.
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -0,0 +1,246 @@
+//! Restart policy for supervised out-of-process module instances
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+/// When a module process that exited on its own is started again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartMode {
+    /// Never restart; the instance is gone once the process exits.
+    #[default]
+    Never,
+    /// Restart only when the process exits with a failure status or is killed by a signal.
+    OnFailure,
+    /// Restart whenever the process exits, including clean exits.
+    Always,
+}
+
+/// Per-module restart policy with exponential backoff and a crash-loop limit.
+///
+/// ```yaml
+/// runtime:
+///   type: oop
+///   restart:
+///     policy: on-failure
+///     initial_backoff: 1s
+///     max_backoff: 30s
+///     max_restarts: 5
+///     window: 60s
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RestartPolicy {
+    /// Which exits trigger a restart.
+    pub policy: RestartMode,
+    /// Delay before the first restart within the window; doubles on each further restart.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub initial_backoff: Duration,
+    /// Upper bound for the restart delay.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_backoff: Duration,
+    /// Maximum number of restarts within `window` before the module is
+    /// considered crash-looping and given up on.
+    pub max_restarts: u32,
+    /// Sliding window for the crash-loop limit.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            policy: RestartMode::Never,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: 5,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Whether a process that exited with `status` should be restarted,
+    /// ignoring the crash-loop limit.
+    #[must_use]
+    pub fn wants_restart(&self, status: Option<ExitStatus>) -> bool {
+        match self.policy {
+            RestartMode::Never => false,
+            RestartMode::OnFailure => !status.is_some_and(|s| s.success()),
+            RestartMode::Always => true,
+        }
+    }
+}
+
+/// What the supervisor should do after a process exit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RestartDecision {
+    /// Restart after the given delay; carries the total restart count.
+    Restart { delay: Duration, attempt: u32 },
+    /// The policy does not restart this exit.
+    Stop,
+    /// The crash-loop limit was reached.
+    GiveUp,
+}
+
+/// Tracks restarts of one instance against its [`RestartPolicy`].
+#[derive(Debug)]
+pub(crate) struct RestartTracker {
+    policy: RestartPolicy,
+    recent: VecDeque<Instant>,
+    total: u32,
+}
+
+impl RestartTracker {
+    pub(crate) fn new(policy: RestartPolicy) -> Self {
+        Self {
+            policy,
+            recent: VecDeque::new(),
+            total: 0,
+        }
+    }
+
+    /// Decide what to do about an exit observed at `now`.
+    ///
+    /// `status` is `None` when the process could not be started or waited on,
+    /// which counts as a failure.
+    pub(crate) fn on_exit(&mut self, status: Option<ExitStatus>, now: Instant) -> RestartDecision {
+        if !self.policy.wants_restart(status) {
+            return RestartDecision::Stop;
+        }
+
+        while self
+            .recent
+            .front()
+            .is_some_and(|t| now.saturating_duration_since(*t) > self.policy.window)
+        {
+            self.recent.pop_front();
+        }
+        if self.recent.len() >= self.policy.max_restarts as usize {
+            return RestartDecision::GiveUp;
+        }
+
+        let exponent = u32::try_from(self.recent.len()).unwrap_or(u32::MAX).min(31);
+        let delay = self
+            .policy
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.policy.max_backoff);
+
+        self.recent.push_back(now);
+        self.total += 1;
+        RestartDecision::Restart {
+            delay,
+            attempt: self.total,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn exit(code: i32) -> ExitStatus {
+        use std::os::unix::process::ExitStatusExt;
+        ExitStatus::from_raw(code << 8)
+    }
+
+    #[cfg(windows)]
+    fn exit(code: i32) -> ExitStatus {
+        use std::os::windows::process::ExitStatusExt;
+        ExitStatus::from_raw(code.cast_unsigned())
+    }
+
+    fn policy(mode: RestartMode) -> RestartPolicy {
+        RestartPolicy {
+            policy: mode,
+            ..RestartPolicy::default()
+        }
+    }
+
+    #[test]
+    fn mode_decides_which_exits_restart() {
+        assert!(!policy(RestartMode::Never).wants_restart(Some(exit(1))));
+
+        let on_failure = policy(RestartMode::OnFailure);
+        assert!(on_failure.wants_restart(Some(exit(1))));
+        assert!(on_failure.wants_restart(None));
+        assert!(!on_failure.wants_restart(Some(exit(0))));
+
+        assert!(policy(RestartMode::Always).wants_restart(Some(exit(0))));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let mut tracker = RestartTracker::new(RestartPolicy {
+            policy: RestartMode::Always,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            max_restarts: 10,
+            window: Duration::from_secs(60),
+        });
+        let now = Instant::now();
+        let delays: Vec<_> = (0..4)
+            .map(|_| match tracker.on_exit(Some(exit(1)), now) {
+                RestartDecision::Restart { delay, .. } => delay.as_secs(),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn crash_loop_limit_is_per_window() {
+        let mut tracker = RestartTracker::new(RestartPolicy {
+            policy: RestartMode::OnFailure,
+            max_restarts: 2,
+            window: Duration::from_secs(10),
+            ..RestartPolicy::default()
+        });
+        let start = Instant::now();
+        assert!(matches!(
+            tracker.on_exit(Some(exit(1)), start),
+            RestartDecision::Restart { attempt: 1, .. }
+        ));
+        assert!(matches!(
+            tracker.on_exit(Some(exit(1)), start + Duration::from_secs(1)),
+            RestartDecision::Restart { attempt: 2, .. }
+        ));
+        assert_eq!(
+            tracker.on_exit(Some(exit(1)), start + Duration::from_secs(2)),
+            RestartDecision::GiveUp
+        );
+
+        // Once the earlier restarts leave the window, the backoff starts over.
+        assert_eq!(
+            tracker.on_exit(Some(exit(1)), start + Duration::from_secs(30)),
+            RestartDecision::Restart {
+                delay: Duration::from_secs(1),
+                attempt: 3
+            }
+        );
+        assert_eq!(
+            tracker.on_exit(Some(exit(0)), start + Duration::from_secs(31)),
+            RestartDecision::Stop
+        );
+    }
+
+    #[test]
+    fn policy_deserializes_from_config() {
+        let policy: RestartPolicy = serde_json::from_value(serde_json::json!({
+            "policy": "on-failure",
+            "initial_backoff": "500ms",
+            "max_restarts": 3,
+        }))
+        .unwrap();
+        assert_eq!(policy.policy, RestartMode::OnFailure);
+        assert_eq!(policy.initial_backoff, Duration::from_millis(500));
+        assert_eq!(policy.max_backoff, Duration::from_secs(30));
+        assert_eq!(policy.max_restarts, 3);
+        assert_eq!(RestartPolicy::default().policy, RestartMode::Never);
+    }
+}
@@ -0,0 +1,456 @@
+//! Static endpoint backend implementation
+//!
+//! Registers module instances that are deployed and run outside of the host
+//! (e.g., separately managed pods or VMs) and keeps their directory entries in
+//! sync with a periodic TCP health check.
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::{BackendKind, InstanceHandle, ModuleRuntimeBackend, OopModuleConfig};
+use crate::directory::{
+    DirectoryClient, LocalDirectoryClient, RegisterInstanceInfo, ServiceEndpoint,
+};
+
+/// Static endpoints of an out-of-process module.
+///
+/// ```yaml
+/// runtime:
+///   type: oop
+///   backend: static
+///   static:
+///     health_check_interval: 5s
+///     instances:
+///       - endpoint: http://calculator-0.calculator:50051
+///         services: [calculator.v1.CalculatorService]
+///         version: 1.2.0
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StaticEndpointsConfig {
+    /// Pre-existing instances of the module.
+    pub instances: Vec<StaticInstanceConfig>,
+    /// How often each endpoint is probed.
+    #[serde(
+        default = "default_health_check_interval",
+        with = "modkit_utils::humantime_serde"
+    )]
+    pub health_check_interval: Duration,
+    /// Connect timeout of a single probe.
+    #[serde(
+        default = "default_health_check_timeout",
+        with = "modkit_utils::humantime_serde"
+    )]
+    pub health_check_timeout: Duration,
+}
+
+/// One pre-existing instance of a module.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StaticInstanceConfig {
+    /// gRPC endpoint URI (`http://host:port` or `https://host:port`).
+    pub endpoint: String,
+    /// Fully qualified gRPC service names served at `endpoint`.
+    pub services: Vec<String>,
+    /// Module version reported to the directory.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Directory instance id; generated when omitted. Set the same value as
+    /// `MODKIT_INSTANCE_ID` on the remote process if it also registers itself.
+    #[serde(default)]
+    pub instance_id: Option<Uuid>,
+}
+
+fn default_health_check_interval() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn default_health_check_timeout() -> Duration {
+    Duration::from_secs(2)
+}
+
+impl StaticEndpointsConfig {
+    /// Single-instance config with default health check settings.
+    #[must_use]
+    pub fn single(instance: StaticInstanceConfig) -> Self {
+        Self {
+            instances: vec![instance],
+            health_check_interval: default_health_check_interval(),
+            health_check_timeout: default_health_check_timeout(),
+        }
+    }
+}
+
+/// A registered static instance with its health check task
+struct StaticInstance {
+    handle: InstanceHandle,
+    cancel: CancellationToken,
+}
+
+/// Backend for module instances that already run somewhere else.
+///
+/// `spawn_instance` registers the first entry of `cfg.static_endpoints` with
+/// the directory and starts a health check task for it: every
+/// `health_check_interval` the endpoint is probed with a TCP connect. While the
+/// probe succeeds the backend sends directory heartbeats on the instance's
+/// behalf; when it fails the instance is deregistered so it is no longer
+/// resolved, and it is registered again once the endpoint is reachable.
+///
+/// When the cancellation token is triggered, all health checks stop and the
+/// instances are deregistered.
+pub struct StaticBackend {
+    instances: Arc<RwLock<HashMap<Uuid, StaticInstance>>>,
+    cancel: CancellationToken,
+}
+
+impl StaticBackend {
+    /// Create a new `StaticBackend` with the given cancellation token.
+    #[must_use]
+    pub fn new(cancel: CancellationToken) -> Self {
+        Self {
+            instances: Arc::new(RwLock::new(HashMap::new())),
+            cancel,
+        }
+    }
+}
+
+/// Extract `host:port` from an endpoint URI for probing.
+fn probe_address(endpoint: &str) -> Result<String> {
+    let uri: http::Uri = endpoint
+        .parse()
+        .with_context(|| format!("invalid static endpoint '{endpoint}'"))?;
+    let host = uri
+        .host()
+        .with_context(|| format!("static endpoint '{endpoint}' has no host"))?;
+    let port = match (uri.port_u16(), uri.scheme_str()) {
+        (Some(port), _) => port,
+        (None, Some("https")) => 443,
+        (None, Some("http")) => 80,
+        _ => bail!("static endpoint '{endpoint}' must be an http(s) URI"),
+    };
+    Ok(format!("{host}:{port}"))
+}
+
+/// Periodically probes one static instance and mirrors the result in the directory.
+struct HealthCheck {
+    directory: Arc<dyn DirectoryClient>,
+    info: RegisterInstanceInfo,
+    address: String,
+    interval: Duration,
+    timeout: Duration,
+}
+
+impl HealthCheck {
+    async fn probe(&self) -> bool {
+        matches!(
+            tokio::time::timeout(self.timeout, TcpStream::connect(&self.address)).await,
+            Ok(Ok(_))
+        )
+    }
+
+    /// Probe once and update the directory; returns whether the instance is registered.
+    async fn check(&self, registered: bool) -> bool {
+        let module = &self.info.module;
+        let instance_id = &self.info.instance_id;
+
+        if !self.probe().await {
+            if registered {
+                tracing::warn!(
+                    module = %module,
+                    instance_id = %instance_id,
+                    address = %self.address,
+                    "Static instance health check failed, removing from directory"
+                );
+                self.deregister().await;
+            }
+            return false;
+        }
+
+        if !registered {
+            if let Err(e) = self.directory.register_instance(self.info.clone()).await {
+                tracing::warn!(module = %module, instance_id = %instance_id, error = %e, "Failed to re-register static instance");
+                return false;
+            }
+            tracing::info!(module = %module, instance_id = %instance_id, "Static instance is reachable again");
+        }
+        if let Err(e) = self.directory.send_heartbeat(module, instance_id).await {
+            tracing::warn!(module = %module, instance_id = %instance_id, error = %e, "Failed to send heartbeat for static instance");
+        }
+        true
+    }
+
+    async fn deregister(&self) {
+        let (module, instance_id) = (&self.info.module, &self.info.instance_id);
+        if let Err(e) = self
+            .directory
+            .deregister_instance(module, instance_id)
+            .await
+        {
+            tracing::warn!(module = %module, instance_id = %instance_id, error = %e, "Failed to deregister static instance");
+        }
+    }
+
+    async fn run(self, cancel: CancellationToken) {
+        let mut registered = true;
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            registered = self.check(registered).await;
+        }
+
+        if registered {
+            self.deregister().await;
+        }
+    }
+}
+
+#[async_trait]
+impl ModuleRuntimeBackend for StaticBackend {
+    async fn spawn_instance(&self, cfg: &OopModuleConfig) -> Result<InstanceHandle> {
+        if cfg.backend != BackendKind::Static {
+            bail!(
+                "StaticBackend can only spawn Static instances, got {:?}",
+                cfg.backend
+            );
+        }
+
+        let static_cfg = cfg
+            .static_endpoints
+            .as_ref()
+            .context("static endpoints must be set for Static backend")?;
+        let instance = static_cfg
+            .instances
+            .first()
+            .context("static endpoints must list at least one instance")?;
+        let module_manager = cfg
+            .module_manager
+            .as_ref()
+            .context("Static backend requires a module manager to register instances")?;
+
+        let address = probe_address(&instance.endpoint)?;
+        let instance_id = instance.instance_id.unwrap_or_else(Uuid::now_v7);
+        let info = RegisterInstanceInfo {
+            module: cfg.name.clone(),
+            instance_id: instance_id.to_string(),
+            grpc_services: instance
+                .services
+                .iter()
+                .map(|svc| (svc.clone(), ServiceEndpoint::new(instance.endpoint.clone())))
+                .collect(),
+            version: instance.version.clone().or_else(|| cfg.version.clone()),
+        };
+
+        let directory: Arc<dyn DirectoryClient> =
+            Arc::new(LocalDirectoryClient::new(Arc::clone(module_manager)));
+        directory.register_instance(info.clone()).await?;
+
+        tracing::info!(
+            module = %cfg.name,
+            instance_id = %instance_id,
+            endpoint = %instance.endpoint,
+            "Registered static OoP module instance"
+        );
+
+        let handle = InstanceHandle {
+            module: cfg.name.clone(),
+            instance_id,
+            backend: BackendKind::Static,
+            pid: None,
+            created_at: Instant::now(),
+        };
+
+        let cancel = self.cancel.child_token();
+        let check = HealthCheck {
+            directory,
+            info,
+            address,
+            interval: static_cfg.health_check_interval,
+            timeout: static_cfg.health_check_timeout,
+        };
+        tokio::spawn(check.run(cancel.clone()));
+
+        self.instances.write().insert(
+            instance_id,
+            StaticInstance {
+                handle: handle.clone(),
+                cancel,
+            },
+        );
+
+        Ok(handle)
+    }
+
+    async fn stop_instance(&self, handle: &InstanceHandle) -> Result<()> {
+        if let Some(inst) = self.instances.write().remove(&handle.instance_id) {
+            inst.cancel.cancel();
+        } else {
+            tracing::debug!(
+                module = %handle.module,
+                instance_id = %handle.instance_id,
+                "stop_instance called for unknown instance, ignoring"
+            );
+        }
+        Ok(())
+    }
+
+    async fn list_instances(&self, module: &str) -> Result<Vec<InstanceHandle>> {
+        let instances = self.instances.read();
+        Ok(instances
+            .values()
+            .filter(|inst| inst.handle.module == module)
+            .map(|inst| inst.handle.clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::runtime::{InstanceState, ModuleManager};
+    use tokio::net::TcpListener;
+
+    fn static_config(endpoint: String, instance_id: Uuid) -> StaticEndpointsConfig {
+        StaticEndpointsConfig {
+            instances: vec![StaticInstanceConfig {
+                endpoint,
+                services: vec!["calc.v1.Calculator".to_owned()],
+                version: Some("1.0.0".to_owned()),
+                instance_id: Some(instance_id),
+            }],
+            health_check_interval: Duration::from_millis(20),
+            health_check_timeout: Duration::from_millis(200),
+        }
+    }
+
+    fn module_config(
+        static_cfg: StaticEndpointsConfig,
+        mgr: &Arc<ModuleManager>,
+    ) -> OopModuleConfig {
+        let mut cfg = OopModuleConfig::new("calc", BackendKind::Static);
+        cfg.static_endpoints = Some(static_cfg);
+        cfg.module_manager = Some(Arc::clone(mgr));
+        cfg
+    }
+
+    async fn wait_until(mut cond: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if cond() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("condition not reached in time");
+    }
+
+    fn state_of(mgr: &ModuleManager, id: Uuid) -> Option<InstanceState> {
+        mgr.instances_of("calc")
+            .iter()
+            .find(|i| i.instance_id == id)
+            .map(|i| i.state())
+    }
+
+    #[test]
+    fn test_probe_address_defaults_port_from_scheme() {
+        assert_eq!(
+            probe_address("http://10.0.0.1:50051").unwrap(),
+            "10.0.0.1:50051"
+        );
+        assert_eq!(probe_address("https://calc.svc").unwrap(), "calc.svc:443");
+        assert!(probe_address("uds:///tmp/calc.sock").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_requires_static_config_and_manager() {
+        let backend = StaticBackend::new(CancellationToken::new());
+        let mgr = Arc::new(ModuleManager::new());
+
+        let cfg = OopModuleConfig::new("calc", BackendKind::Static);
+        assert!(backend.spawn_instance(&cfg).await.is_err());
+
+        let mut cfg = module_config(
+            static_config("http://127.0.0.1:1".to_owned(), Uuid::new_v4()),
+            &mgr,
+        );
+        cfg.module_manager = None;
+        assert!(backend.spawn_instance(&cfg).await.is_err());
+
+        let mut cfg = module_config(
+            static_config("http://127.0.0.1:1".to_owned(), Uuid::new_v4()),
+            &mgr,
+        );
+        cfg.backend = BackendKind::LocalProcess;
+        assert!(backend.spawn_instance(&cfg).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_follows_endpoint_reachability() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let id = Uuid::new_v4();
+        let mgr = Arc::new(ModuleManager::new());
+        let backend = StaticBackend::new(CancellationToken::new());
+
+        let handle = backend
+            .spawn_instance(&module_config(
+                static_config(format!("http://{addr}"), id),
+                &mgr,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(handle.instance_id, id);
+        assert_eq!(handle.backend, BackendKind::Static);
+
+        // A successful probe sends a heartbeat, which makes the instance routable
+        wait_until(|| state_of(&mgr, id) == Some(InstanceState::Healthy)).await;
+        assert!(mgr.pick_service_round_robin("calc.v1.Calculator").is_some());
+
+        // Endpoint goes away: instance is removed from the directory
+        drop(listener);
+        wait_until(|| state_of(&mgr, id).is_none()).await;
+
+        // Endpoint comes back: instance is registered again
+        let _listener = TcpListener::bind(addr).await.unwrap();
+        wait_until(|| state_of(&mgr, id) == Some(InstanceState::Healthy)).await;
+
+        backend.stop_instance(&handle).await.unwrap();
+        wait_until(|| state_of(&mgr, id).is_none()).await;
+        assert!(backend.list_instances("calc").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_deregisters_instances() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let id = Uuid::new_v4();
+        let mgr = Arc::new(ModuleManager::new());
+        let cancel = CancellationToken::new();
+        let backend = StaticBackend::new(cancel.clone());
+
+        backend
+            .spawn_instance(&module_config(
+                static_config(format!("http://{addr}"), id),
+                &mgr,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(backend.list_instances("calc").await.unwrap().len(), 1);
+        wait_until(|| state_of(&mgr, id) == Some(InstanceState::Healthy)).await;
+
+        cancel.cancel();
+        wait_until(|| state_of(&mgr, id).is_none()).await;
+    }
+}
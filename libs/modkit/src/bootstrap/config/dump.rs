@@ -37,7 +37,8 @@ pub fn list_module_names(app: &AppConfig) -> Vec<String> {
 ///
 /// For each module, it includes:
 /// - `runtime`: Module runtime type (local/oop) if configured
-/// - `config`: Module-specific configuration section (as-is from config file)
+/// - `config`: Module-specific configuration section (as-is from config file, secret
+///   references left unresolved)
 /// - `database`: Final resolved database configuration with redacted DSN (if applicable)
 ///
 /// This is a read-only inspection operation that does not create any directories
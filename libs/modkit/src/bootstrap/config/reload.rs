@@ -0,0 +1,245 @@
+//! Config file watching for hot reload of module configuration.
+//!
+//! The watcher polls the main config file and the YAML files in `modules_dir`.
+//! When any of them changes it re-runs [`AppConfig::load_layered_with`] and publishes the
+//! result; the runtime then delivers changed module sections to reconfigurable modules.
+//! A config that fails to load is logged and skipped, so the previous one stays current.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+use super::AppConfig;
+use crate::ConfigProvider;
+
+/// Modification state of every watched file, used to detect changes between polls.
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+/// Start watching the files `config` was loaded from.
+///
+/// Returns `None` if `config_reload.enabled` is false or the config was not loaded
+/// from a file. The watcher stops when `cancel` fires.
+#[must_use]
+pub fn watch_config(
+    config: &AppConfig,
+    cancel: CancellationToken,
+) -> Option<watch::Receiver<Arc<dyn ConfigProvider>>> {
+    if !config.config_reload.enabled {
+        return None;
+    }
+    let Some(path) = config.config_path.clone() else {
+        tracing::warn!("config_reload is enabled but no config file was loaded; reload disabled");
+        return None;
+    };
+
+    let initial: Arc<dyn ConfigProvider> = Arc::new(config.clone());
+    let (tx, rx) = watch::channel(initial);
+    let interval = config.config_reload.poll_interval;
+    let mut modules_dir = config.modules_dir.clone();
+    let secrets = config.secrets.clone();
+    let mut last = fingerprint(&path, modules_dir.as_deref());
+
+    tokio::spawn(async move {
+        tracing::info!(path = %path.display(), ?interval, "Watching config for changes");
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => return,
+                () = tokio::time::sleep(interval) => {}
+            }
+
+            let current = fingerprint(&path, modules_dir.as_deref());
+            if current == last {
+                continue;
+            }
+            last = current;
+
+            match AppConfig::load_layered_with(&path, &secrets) {
+                Ok(next) => {
+                    tracing::info!(path = %path.display(), "Config changed; reloading");
+                    if next.modules_dir != modules_dir {
+                        modules_dir.clone_from(&next.modules_dir);
+                        last = fingerprint(&path, modules_dir.as_deref());
+                    }
+                    if tx.send(Arc::new(next)).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %format!("{e:#}"),
+                        "Failed to reload config; keeping previous config"
+                    );
+                }
+            }
+        }
+    });
+
+    Some(rx)
+}
+
+fn fingerprint(config_path: &Path, modules_dir: Option<&str>) -> Fingerprint {
+    let mut files = vec![config_path.to_path_buf()];
+    if let Some(entries) = modules_dir.and_then(|dir| std::fs::read_dir(dir).ok()) {
+        files.extend(
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| {
+                    p.extension().and_then(|s| s.to_str()).is_some_and(|ext| {
+                        ext.eq_ignore_ascii_case("yml") || ext.eq_ignore_ascii_case("yaml")
+                    })
+                }),
+        );
+        files[1..].sort();
+    }
+
+    files
+        .into_iter()
+        .map(|p| {
+            let meta = std::fs::metadata(&p).ok();
+            let modified = meta.as_ref().and_then(|m| m.modified().ok());
+            let len = meta.map_or(0, |m| m.len());
+            (p, modified, len)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn write_config(path: &Path, home: &Path, limit: u32) {
+        let yaml = format!(
+            "server:\n  home_dir: {}\nconfig_reload:\n  enabled: true\n  poll_interval: 10ms\nmodules:\n  limiter:\n    config:\n      limit: {limit}\n",
+            home.display()
+        );
+        std::fs::write(path, yaml).unwrap();
+    }
+
+    fn limit(provider: &dyn ConfigProvider) -> Option<u64> {
+        provider.get_module_config("limiter")?["config"]["limit"].as_u64()
+    }
+
+    async fn next_update(
+        rx: &mut watch::Receiver<Arc<dyn ConfigProvider>>,
+    ) -> Arc<dyn ConfigProvider> {
+        tokio::time::timeout(Duration::from_secs(5), rx.changed())
+            .await
+            .expect("timed out waiting for config update")
+            .unwrap();
+        rx.borrow_and_update().clone()
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let config = AppConfig::default();
+        assert!(!config.config_reload.enabled);
+        assert_eq!(config.config_reload.poll_interval, Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn publishes_changed_config_and_skips_invalid() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.yaml");
+        write_config(&path, tmp.path(), 1);
+
+        let config = AppConfig::load_layered(&path).unwrap();
+        assert_eq!(config.config_path.as_deref(), Some(path.as_path()));
+        let cancel = CancellationToken::new();
+        let mut rx = watch_config(&config, cancel.clone()).expect("reload enabled");
+        assert_eq!(limit(rx.borrow().as_ref()), Some(1));
+
+        write_config(&path, tmp.path(), 250);
+        assert_eq!(limit(next_update(&mut rx).await.as_ref()), Some(250));
+
+        // A broken file is not published; the next valid one is.
+        std::fs::write(&path, "modules: [unclosed").unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!rx.has_changed().unwrap());
+
+        write_config(&path, tmp.path(), 3);
+        assert_eq!(limit(next_update(&mut rx).await.as_ref()), Some(3));
+
+        cancel.cancel();
+    }
+
+    #[tokio::test]
+    async fn watches_modules_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let modules_dir = tmp.path().join("modules");
+        std::fs::create_dir(&modules_dir).unwrap();
+        let path = tmp.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            format!(
+                "server:\n  home_dir: {}\nmodules_dir: {}\nconfig_reload:\n  enabled: true\n  poll_interval: 10ms\n",
+                tmp.path().display(),
+                modules_dir.display()
+            ),
+        )
+        .unwrap();
+        std::fs::write(modules_dir.join("limiter.yaml"), "config:\n  limit: 1\n").unwrap();
+
+        let config = AppConfig::load_layered(&path).unwrap();
+        let cancel = CancellationToken::new();
+        let mut rx = watch_config(&config, cancel.clone()).unwrap();
+
+        std::fs::write(modules_dir.join("limiter.yaml"), "config:\n  limit: 42\n").unwrap();
+        assert_eq!(limit(next_update(&mut rx).await.as_ref()), Some(42));
+
+        cancel.cancel();
+    }
+
+    #[tokio::test]
+    async fn reload_uses_the_loading_secret_resolver() {
+        struct Vault(std::sync::atomic::AtomicU32);
+        impl crate::bootstrap::config::SecretProvider for Vault {
+            fn get(&self, path: &str) -> anyhow::Result<modkit_utils::SecretString> {
+                let n = self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(modkit_utils::SecretString::new(format!("{path}-{n}")))
+            }
+        }
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.yaml");
+        let write = |limit: u32| {
+            let yaml = format!(
+                "server:\n  home_dir: {}\nconfig_reload:\n  enabled: true\n  poll_interval: 10ms\nmodules:\n  limiter:\n    config:\n      limit: {limit}\n      token: \"${{secret:vault:limiter}}\"\n",
+                tmp.path().display()
+            );
+            std::fs::write(&path, yaml).unwrap();
+        };
+        let token = |provider: &dyn ConfigProvider| {
+            provider.resolved_module_config("limiter").unwrap()["config"]["token"]
+                .as_str()
+                .map(str::to_owned)
+        };
+        write(1);
+
+        let secrets = super::super::SecretResolver::default()
+            .with_provider("vault", Arc::new(Vault(0.into())));
+        let config = AppConfig::load_layered_with(&path, &secrets).unwrap();
+        let cancel = CancellationToken::new();
+        let mut rx = watch_config(&config, cancel.clone()).unwrap();
+        assert_eq!(token(rx.borrow().as_ref()).as_deref(), Some("limiter-0"));
+
+        write(2);
+        let next = next_update(&mut rx).await;
+        assert_eq!(limit(next.as_ref()), Some(2));
+        assert_eq!(token(next.as_ref()).as_deref(), Some("limiter-1"));
+
+        cancel.cancel();
+    }
+
+    #[test]
+    fn not_started_without_config_file() {
+        let mut config = AppConfig::default();
+        config.config_reload.enabled = true;
+        assert!(watch_config(&config, CancellationToken::new()).is_none());
+    }
+}
@@ -21,8 +21,8 @@ pub mod oop;
 
 // Re-export commonly used config types at crate root for convenience
 pub use config::{
-    AppConfig, CliArgs, ConsoleFormat, LoggingConfig, MODKIT_MODULE_CONFIG_ENV, ModuleConfig,
-    ModuleRuntime, RenderedModuleConfig, RuntimeKind, Section, ServerConfig,
+    AppConfig, CliArgs, ConfigReloadConfig, ConsoleFormat, LoggingConfig, MODKIT_MODULE_CONFIG_ENV,
+    ModuleConfig, ModuleRuntime, RenderedModuleConfig, RuntimeKind, Section, ServerConfig,
     dump_effective_modules_config_json, dump_effective_modules_config_yaml, list_module_names,
     render_effective_modules_config,
 };
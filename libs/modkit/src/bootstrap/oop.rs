@@ -58,8 +58,8 @@ use super::config::{
 };
 use crate::bootstrap::host::{init_logging_unified, init_panic_tracing};
 use crate::runtime::{
-    ClientRegistration, DbOptions, MODKIT_DIRECTORY_ENDPOINT_ENV, RunOptions, ShutdownOptions, run,
-    shutdown,
+    ClientRegistration, DbOptions, MODKIT_DIRECTORY_ENDPOINT_ENV, MODKIT_INSTANCE_ID_ENV,
+    RunOptions, ShutdownOptions, run, shutdown,
 };
 use cf_system_sdks::directory::{DirectoryClient, DirectoryGrpcClient};
 
@@ -69,7 +69,8 @@ pub struct OopRunOptions {
     /// Logical module name (e.g., "`file-parser`")
     pub module_name: String,
 
-    /// Instance ID (defaults to a random UUID if None)
+    /// Instance ID (defaults to `MODKIT_INSTANCE_ID` when spawned by the
+    /// master host, otherwise a random UUID if None)
     pub instance_id: Option<Uuid>,
 
     /// Directory service gRPC endpoint (e.g., "<http://127.0.0.1:50051>")
@@ -158,6 +159,10 @@ fn build_oop_config_and_db(
             }
             // If local has "config", it already overrides - no action needed
         }
+        let secrets = config.secrets.clone();
+        config
+            .resolve_secrets(&secrets)
+            .context("Failed to resolve secret references")?;
 
         debug!(
             module = %module_name,
@@ -403,8 +408,16 @@ fn merge_json_objects(target: &mut serde_json::Value, source: &serde_json::Value
     )
 )]
 pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
-    // Generate instance ID if not provided
-    let instance_id = opts.instance_id.unwrap_or_else(Uuid::new_v4);
+    // Use the instance ID assigned by the master host (stable across restarts),
+    // or generate one if not provided
+    let instance_id = opts
+        .instance_id
+        .or_else(|| {
+            std::env::var(MODKIT_INSTANCE_ID_ENV)
+                .ok()
+                .and_then(|v| Uuid::parse_str(&v).ok())
+        })
+        .unwrap_or_else(Uuid::new_v4);
 
     // Create root cancellation token for the entire process.
     // This token drives shutdown for the module runtime and all background tasks.
@@ -575,6 +588,7 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
         )],
         instance_id,
         oop: None, // OoP modules don't spawn other OoP modules
+        config_updates: None,
     };
 
     let result = run(run_options).await;
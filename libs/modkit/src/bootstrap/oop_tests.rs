@@ -7,8 +7,9 @@
 
 use super::*;
 use crate::bootstrap::config::{
-    AppConfig, ConsoleFormat, GlobalDatabaseConfig, LoggingConfig, RenderedDbConfig,
-    RenderedModuleConfig, Section, SectionFile, ServerConfig, default_logging_config,
+    AppConfig, ConfigReloadConfig, ConsoleFormat, GlobalDatabaseConfig, LoggingConfig,
+    RenderedDbConfig, RenderedModuleConfig, ResolvedSecrets, SecretResolver, Section, SectionFile,
+    ServerConfig, default_logging_config,
 };
 use modkit_db::{DbConnConfig, PoolCfg};
 use std::collections::HashMap;
@@ -26,6 +27,10 @@ fn minimal_app_config() -> AppConfig {
         tracing: None,
         modules_dir: None,
         modules: HashMap::new(),
+        config_reload: ConfigReloadConfig::default(),
+        config_path: None,
+        secrets: SecretResolver::default(),
+        resolved_secrets: ResolvedSecrets::default(),
     }
 }
 
@@ -352,6 +357,8 @@ mod database_merge {
                     max_lifetime: None,
                     test_before_acquire: None,
                 }),
+                replicas: None,
+                read_your_writes: None,
             },
         );
         GlobalDatabaseConfig {
@@ -374,6 +381,8 @@ mod database_merge {
             path: None,
             params: None,
             pool: None,
+            replicas: None,
+            read_your_writes: None,
         }
     }
 
@@ -553,6 +562,8 @@ mod database_merge {
                 path: None,
                 params: None,
                 pool: None,
+                replicas: None,
+                read_your_writes: None,
             },
         );
         local_config.database = Some(GlobalDatabaseConfig {
@@ -1,9 +1,12 @@
-use super::config::{get_module_runtime_config, render_module_config_for_oop};
+use super::config::{get_module_runtime_config, render_module_config_for_oop, watch_config};
 use super::host::normalize_path;
 use super::{AppConfig, RuntimeKind};
-use crate::backends::LocalProcessBackend;
+use crate::backends::{
+    BackendKind, BackendRouter, K8sBackend, LocalProcessBackend, OopBackend, StaticBackend,
+};
 use crate::runtime::{
-    DbOptions, OopModuleSpawnConfig, OopSpawnOptions, RunOptions, ShutdownOptions, run, shutdown,
+    DbOptions, MigrateCommand, MigrateOptions, OopModuleSpawnConfig, OopSpawnOptions, RunOptions,
+    ShutdownOptions, run, shutdown,
 };
 use figment::Figment;
 use figment::providers::Serialized;
@@ -61,12 +64,24 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
     // Build config provider and resolve database options
     let db_options = resolve_db_options(&config)?;
 
-    // Create OoP backend with cancellation token - it will auto-shutdown all processes on cancel
-    let oop_backend = LocalProcessBackend::new(cancel.clone());
+    // Create OoP backends with cancellation token - they stop all instances on cancel
+    let oop_backend = BackendRouter::new()
+        .with_backend(
+            BackendKind::LocalProcess,
+            Arc::new(LocalProcessBackend::new(cancel.clone())),
+        )
+        .with_backend(
+            BackendKind::Static,
+            Arc::new(StaticBackend::new(cancel.clone())),
+        )
+        .with_backend(BackendKind::K8s, Arc::new(K8sBackend::new(cancel.clone())));
 
     // Build OoP spawn configuration
     let oop_options = build_oop_spawn_options(&config, oop_backend)?;
 
+    // Watch config files for module config changes (if config_reload is enabled)
+    let config_updates = watch_config(&config, cancel.clone());
+
     // Run the ModKit runtime with the root cancellation token.
     // Shutdown is driven by the signal handler spawned above, not by ShutdownOptions::Signals.
     // OoP modules are spawned after the start phase (once grpc-hub has bound its port).
@@ -77,6 +92,7 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
         clients: vec![],
         instance_id,
         oop: oop_options,
+        config_updates,
     };
 
     let result = run(run_options).await;
@@ -88,17 +104,17 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
     result
 }
 
-/// Run database migrations and exit.
+/// Run a database migration command and exit.
 ///
 /// This mode is designed for cloud deployment workflows where database
 /// migrations need to run as a separate step before starting the application.
 ///
-/// Phases executed:
-/// - Pre-init (wire runtime internals)
-/// - DB migration (run all pending migrations)
+/// - [`MigrateCommand::Up`] runs the pre-init and DB migration phases, applying all
+///   pending migrations;
+/// - [`MigrateCommand::Status`] prints applied and pending migrations per module;
+/// - [`MigrateCommand::Down`] rolls back migrations of one module via their `down()`.
 ///
-/// The process exits after migrations complete. Any errors are reported
-/// and propagated as non-zero exit codes.
+/// With `dry_run`, `Up` and `Down` print the SQL they would run and change nothing.
 ///
 /// # Errors
 ///
@@ -106,10 +122,11 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
 /// - No database configuration is found
 /// - Module discovery fails
 /// - Pre-init phase fails
-/// - Migration phase fails
+/// - A migration, rollback or status query fails
+/// - `Down` names a module without a database
 #[allow(unknown_lints, de1301_no_print_macros)]
-pub async fn run_migrate(config: AppConfig) -> anyhow::Result<()> {
-    tracing::info!("Starting migration mode...");
+pub async fn run_migrate(config: AppConfig, options: MigrateOptions) -> anyhow::Result<()> {
+    tracing::info!(command = ?options.command, dry_run = options.dry_run, "Starting migration mode...");
 
     // Generate process-level instance ID for this migration run
     let instance_id = uuid::Uuid::new_v4();
@@ -147,8 +164,11 @@ pub async fn run_migrate(config: AppConfig) -> anyhow::Result<()> {
         None, // No OoP spawning during migration
     );
 
-    // Run only the migration phases (pre-init + DB migration)
-    let result = host.run_migration_phases().await;
+    let result = match (&options.command, options.dry_run) {
+        // Run only the migration phases (pre-init + DB migration)
+        (MigrateCommand::Up, false) => host.run_migration_phases().await,
+        (command, dry_run) => run_migrate_command(&host, command, dry_run).await,
+    };
 
     // Graceful shutdown - flush any remaining traces
     #[cfg(feature = "otel")]
@@ -156,11 +176,91 @@ pub async fn run_migrate(config: AppConfig) -> anyhow::Result<()> {
 
     result?;
 
-    tracing::info!("All migrations completed successfully");
-    println!("[OK] Database migrations completed successfully");
+    if options.command == MigrateCommand::Up && !options.dry_run {
+        tracing::info!("All migrations completed successfully");
+        println!("[OK] Database migrations completed successfully");
+    }
+    Ok(())
+}
+
+/// Run a `migrate` command other than a plain `Up`, printing its outcome.
+#[allow(unknown_lints, de1301_no_print_macros)]
+async fn run_migrate_command(
+    host: &crate::runtime::HostRuntime,
+    command: &MigrateCommand,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    use modkit_db::migration_runner as runner;
+
+    let mut targets = host.migration_targets().await?;
+    match command {
+        MigrateCommand::Status => {
+            for t in &targets {
+                println!("{}:", t.module);
+                for m in runner::migration_status(&t.db, t.module, &t.migrations).await? {
+                    let state = match (&m.applied_at, m.known) {
+                        (Some(at), true) => format!("applied {at}"),
+                        (Some(at), false) => format!("applied {at}, not provided by module"),
+                        (None, _) => "pending".to_owned(),
+                    };
+                    println!("  [{state}] {}", m.name);
+                }
+            }
+        }
+        MigrateCommand::Up => {
+            for t in &targets {
+                let plans =
+                    runner::plan_migrations_for_module(&t.db, t.module, &t.migrations).await?;
+                print_plans(t.module, "up", &plans);
+            }
+        }
+        MigrateCommand::Down { module, to } => {
+            let index = targets
+                .iter()
+                .position(|t| t.module == module.as_str())
+                .ok_or_else(|| anyhow::anyhow!("module '{module}' has no database to migrate"))?;
+            let t = targets.swap_remove(index);
+            if dry_run {
+                let plans =
+                    runner::plan_rollback_for_module(&t.db, t.module, &t.migrations, to.as_deref())
+                        .await?;
+                print_plans(t.module, "down", &plans);
+            } else {
+                let result = runner::rollback_migrations_for_module(
+                    &t.db,
+                    t.module,
+                    t.migrations,
+                    to.as_deref(),
+                )
+                .await?;
+                if result.rolled_back.is_empty() {
+                    println!("[OK] {module}: nothing to roll back");
+                } else {
+                    println!(
+                        "[OK] {module}: rolled back {}",
+                        result.rolled_back.join(", ")
+                    );
+                }
+            }
+        }
+    }
     Ok(())
 }
 
+#[allow(unknown_lints, de1301_no_print_macros)]
+fn print_plans(
+    module: &str,
+    direction: &str,
+    plans: &[modkit_db::migration_runner::MigrationPlan],
+) {
+    for plan in plans {
+        println!("-- {module}: {} ({direction})", plan.name);
+        for statement in &plan.statements {
+            println!("{statement};");
+        }
+    }
+}
+
 fn resolve_db_options(config: &AppConfig) -> anyhow::Result<DbOptions> {
     if config.database.is_none() {
         tracing::warn!("No global database section found; running without databases");
@@ -182,7 +282,7 @@ fn resolve_db_options(config: &AppConfig) -> anyhow::Result<DbOptions> {
 /// The actual spawning happens in the `HostRuntime` after the start phase.
 fn build_oop_spawn_options(
     config: &AppConfig,
-    backend: LocalProcessBackend,
+    backend: impl OopBackend + 'static,
 ) -> anyhow::Result<Option<OopSpawnOptions>> {
     let home_dir = PathBuf::from(&config.server.home_dir);
     let mut modules = Vec::new();
@@ -218,13 +318,27 @@ fn try_build_oop_module_config(
         return Ok(None);
     }
 
-    let exec_cfg = runtime_cfg.execution.as_ref().ok_or_else(|| {
-        anyhow::anyhow!("module '{module_name}' is type=oop but execution config is missing")
-    })?;
+    match runtime_cfg.backend {
+        BackendKind::LocalProcess if runtime_cfg.execution.is_none() => {
+            anyhow::bail!("module '{module_name}' is type=oop but execution config is missing")
+        }
+        BackendKind::Static if runtime_cfg.static_endpoints.is_none() => {
+            anyhow::bail!(
+                "module '{module_name}' uses the static backend but `static` config is missing"
+            )
+        }
+        BackendKind::K8s if runtime_cfg.k8s.is_none() => {
+            anyhow::bail!("module '{module_name}' uses the k8s backend but `k8s` config is missing")
+        }
+        _ => {}
+    }
 
-    let binary = normalize_path(&exec_cfg.executable_path)?;
-    let spawn_args = exec_cfg.args.clone();
-    let env = exec_cfg.environment.clone();
+    let exec_cfg = runtime_cfg.execution.clone().unwrap_or_default();
+    let binary = if exec_cfg.executable_path.is_empty() {
+        None
+    } else {
+        Some(normalize_path(&exec_cfg.executable_path)?)
+    };
 
     // Render the complete module config (with resolved DB)
     let rendered_config = render_module_config_for_oop(config, module_name, home_dir)?;
@@ -238,10 +352,14 @@ fn try_build_oop_module_config(
 
     Ok(Some(OopModuleSpawnConfig {
         module_name: module_name.to_owned(),
+        backend: runtime_cfg.backend,
         binary,
-        args: spawn_args,
-        env,
-        working_directory: exec_cfg.working_directory.clone(),
+        args: exec_cfg.args,
+        env: exec_cfg.environment,
+        working_directory: exec_cfg.working_directory,
         rendered_config_json: rendered_json,
+        restart: runtime_cfg.restart,
+        static_endpoints: runtime_cfg.static_endpoints,
+        k8s: runtime_cfg.k8s,
     }))
 }
@@ -10,6 +10,8 @@
 //!    - Used by `module_config_required`
 //!    - Returns errors when configuration is missing or invalid
 
+use std::borrow::Cow;
+
 use serde::de::DeserializeOwned;
 
 /// Configuration error for typed config operations
@@ -33,6 +35,14 @@ pub enum ConfigError {
 pub trait ConfigProvider: Send + Sync {
     /// Returns raw JSON section for the module, if any.
     fn get_module_config(&self, module_name: &str) -> Option<&serde_json::Value>;
+
+    /// Returns the module's section with secret references substituted, for deserializing
+    /// into the module's config type (declare secret fields as `modkit_utils::SecretString`).
+    ///
+    /// Drop the returned value once deserialized; by default it is the raw section.
+    fn resolved_module_config(&self, module_name: &str) -> Option<Cow<'_, serde_json::Value>> {
+        self.get_module_config(module_name).map(Cow::Borrowed)
+    }
 }
 
 /// Lenient configuration loader that falls back to defaults.
@@ -52,7 +62,7 @@ pub fn module_config_or_default<T: DeserializeOwned + Default>(
     module_name: &str,
 ) -> Result<T, ConfigError> {
     // If module not found, use defaults
-    let Some(module_raw) = provider.get_module_config(module_name) else {
+    let Some(module_raw) = provider.resolved_module_config(module_name) else {
         return Ok(T::default());
     };
 
@@ -92,12 +102,11 @@ pub fn module_config_required<T: DeserializeOwned>(
     provider: &dyn ConfigProvider,
     module_name: &str,
 ) -> Result<T, ConfigError> {
-    let module_raw =
-        provider
-            .get_module_config(module_name)
-            .ok_or_else(|| ConfigError::ModuleNotFound {
-                module: module_name.to_owned(),
-            })?;
+    let module_raw = provider
+        .resolved_module_config(module_name)
+        .ok_or_else(|| ConfigError::ModuleNotFound {
+            module: module_name.to_owned(),
+        })?;
 
     // Extract config section from: modules.<name> = { database: ..., config: ... }
     let obj = module_raw
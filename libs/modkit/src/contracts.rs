@@ -98,6 +98,28 @@ pub trait RunnableCapability: Send + Sync {
     async fn stop(&self, cancel: CancellationToken) -> anyhow::Result<()>;
 }
 
+/// Reconfigurable capability: accepts a changed `config` section at runtime.
+///
+/// When the host reloads its configuration, modules with this capability receive
+/// their new `modules.<name>.config` section instead of requiring a restart.
+/// `ModuleCtx::config()` keeps returning the startup snapshot, so a module that
+/// opts in must keep its live settings in its own state.
+#[async_trait]
+pub trait ReconfigurableCapability: Send + Sync {
+    /// Check a candidate `config` section without applying it.
+    ///
+    /// # Errors
+    /// Returns an error if the section is invalid; the previous config stays in effect.
+    fn validate_config(&self, config: &serde_json::Value) -> anyhow::Result<()>;
+
+    /// Apply a `config` section that passed [`validate_config`](Self::validate_config).
+    ///
+    /// # Errors
+    /// Returns an error if the section could not be applied; the host keeps
+    /// treating the previous config as current.
+    async fn apply_config(&self, config: &serde_json::Value) -> anyhow::Result<()>;
+}
+
 /// Represents a gRPC service registration callback used by the gRPC hub.
 ///
 /// Each module that exposes gRPC services provides one or more of these.
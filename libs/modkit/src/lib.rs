@@ -118,6 +118,8 @@ pub mod telemetry;
 
 pub mod backends;
 pub mod lifecycle;
+#[cfg(feature = "db")]
+pub mod outbox;
 pub mod plugins;
 pub mod runtime;
 
@@ -146,8 +148,9 @@ pub mod gts;
 pub use modkit_sdk::{Secured, WithSecurityContext};
 
 pub use backends::{
-    BackendKind, InstanceHandle, LocalProcessBackend, ModuleRuntimeBackend, OopBackend,
-    OopModuleConfig, OopSpawnConfig,
+    BackendKind, BackendRouter, InstanceHandle, K8sBackend, K8sDeploymentConfig,
+    LocalProcessBackend, ModuleRuntimeBackend, OopBackend, OopModuleConfig, OopSpawnConfig,
+    RestartMode, RestartPolicy, StaticBackend, StaticEndpointsConfig, StaticInstanceConfig,
 };
 pub use lifecycle::{Lifecycle, Runnable, Status, StopReason, WithLifecycle};
 pub use plugins::GtsPluginSelector;
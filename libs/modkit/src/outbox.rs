@@ -0,0 +1,109 @@
+//! Outbox relay integration.
+//!
+//! Re-exports the transactional outbox from `modkit-db`, runs [`OutboxRelay`] as a
+//! [`Runnable`] background task and adds sinks built on `modkit` types:
+//!
+//! - [`BroadcastSink`]: in-process fan-out;
+//! - [`SseBroadcaster<OutboxEvent>`]: pushes events to SSE subscribers;
+//! - [`WebhookSink`]: POSTs each event as JSON to a URL.
+//!
+//! ```ignore
+//! let relay = OutboxRelay::new(db, "settings", Arc::new(WebhookSink::new(client, url)));
+//! let task = WithLifecycle::new(relay);
+//! ```
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use modkit_http::HttpClient;
+use tokio_util::sync::CancellationToken;
+
+pub use modkit_db::outbox::{
+    BroadcastSink, OUTBOX_LEASE_TABLE, OUTBOX_TABLE, OutboxEvent, OutboxMigration, OutboxRelay,
+    OutboxRelayConfig, OutboxSink, migration,
+};
+
+use crate::SseBroadcaster;
+use crate::lifecycle::Runnable;
+
+#[async_trait]
+impl Runnable for OutboxRelay {
+    async fn run(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        OutboxRelay::run(&self, cancel).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxSink for SseBroadcaster<OutboxEvent> {
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        self.send(event.clone());
+        Ok(())
+    }
+}
+
+/// Sink sending each event as a JSON `POST` to a fixed URL.
+///
+/// Any non-2xx response is a failed publish and is retried by the relay. The event id is
+/// also sent as `Idempotency-Key` so receivers can drop redeliveries.
+pub struct WebhookSink {
+    client: HttpClient,
+    url: String,
+}
+
+impl WebhookSink {
+    #[must_use]
+    pub fn new(client: HttpClient, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for WebhookSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookSink")
+            .field("url", &self.url)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl OutboxSink for WebhookSink {
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        self.client
+            .post(&self.url)
+            .header("idempotency-key", &event.id.to_string())
+            .json(event)?
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn sse_sink_forwards_events_to_subscribers() {
+        let sse = SseBroadcaster::<OutboxEvent>::new(4);
+        let mut stream = Box::pin(sse.subscribe_stream());
+        let event: OutboxEvent = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "module": "settings",
+            "topic": "settings.updated",
+            "payload": { "theme": "dark" },
+            "created_at": "2024-01-01T00:00:00Z",
+            "attempts": 0
+        }))
+        .unwrap();
+
+        sse.publish(&event).await.unwrap();
+        assert_eq!(stream.next().await, Some(event));
+    }
+}
@@ -22,6 +22,7 @@ pub enum Capability {
     RestApi(Arc<dyn contracts::RestApiCapability>),
     ApiGateway(Arc<dyn contracts::ApiGatewayCapability>),
     Runnable(Arc<dyn contracts::RunnableCapability>),
+    Reconfigurable(Arc<dyn contracts::ReconfigurableCapability>),
     System(Arc<dyn contracts::SystemCapability>),
     GrpcHub(Arc<dyn contracts::GrpcHubCapability>),
     GrpcService(Arc<dyn contracts::GrpcServiceCapability>),
@@ -35,6 +36,9 @@ impl std::fmt::Debug for Capability {
             Capability::RestApi(_) => write!(f, "RestApi(<impl RestApiCapability>)"),
             Capability::ApiGateway(_) => write!(f, "ApiGateway(<impl ApiGatewayCapability>)"),
             Capability::Runnable(_) => write!(f, "Runnable(<impl RunnableCapability>)"),
+            Capability::Reconfigurable(_) => {
+                write!(f, "Reconfigurable(<impl ReconfigurableCapability>)")
+            }
             Capability::System(_) => write!(f, "System(<impl SystemCapability>)"),
             Capability::GrpcHub(_) => write!(f, "GrpcHub(<impl GrpcHubCapability>)"),
             Capability::GrpcService(_) => write!(f, "GrpcService(<impl GrpcServiceCapability>)"),
@@ -98,6 +102,18 @@ impl CapTag for RunnableCap {
     }
 }
 
+/// Tag for querying `ReconfigurableCapability`.
+pub struct ReconfigurableCap;
+impl CapTag for ReconfigurableCap {
+    type Out = dyn contracts::ReconfigurableCapability;
+    fn try_get(cap: &Capability) -> Option<&Arc<Self::Out>> {
+        match cap {
+            Capability::Reconfigurable(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
 /// Tag for querying `SystemCapability`.
 pub struct SystemCap;
 impl CapTag for SystemCap {
@@ -183,6 +199,7 @@ impl CapabilitySet {
                 Capability::RestApi(_) => "rest",
                 Capability::ApiGateway(_) => "rest_host",
                 Capability::Runnable(_) => "stateful",
+                Capability::Reconfigurable(_) => "reconfigurable",
                 Capability::System(_) => "system",
                 Capability::GrpcHub(_) => "grpc_hub",
                 Capability::GrpcService(_) => "grpc",
@@ -246,6 +263,7 @@ impl std::fmt::Debug for ModuleEntry {
             .field("is_rest_host", &self.caps.has::<ApiGatewayCap>())
             .field("has_db", &self.caps.has_db())
             .field("has_stateful", &self.caps.has::<RunnableCap>())
+            .field("is_reconfigurable", &self.caps.has::<ReconfigurableCap>())
             .field("is_system", &self.caps.has::<SystemCap>())
             .field("is_grpc_hub", &self.caps.has::<GrpcHubCap>())
             .field("has_grpc_service", &self.caps.has::<GrpcServiceCap>())
@@ -411,6 +429,17 @@ impl RegistryBuilder {
             .push(Capability::Runnable(m));
     }
 
+    pub fn register_reconfigurable_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::ReconfigurableCapability>,
+    ) {
+        self.capabilities
+            .entry(name)
+            .or_default()
+            .push(Capability::Reconfigurable(m));
+    }
+
     pub fn register_system_with_meta(
         &mut self,
         name: &'static str,
@@ -11,12 +11,14 @@
 //! - REST wiring (modules with REST capability; requires a single REST host)
 //! - gRPC registration (modules with gRPC capability; requires a single gRPC hub)
 //! - start/stop (stateful modules)
+//! - config reload (reconfigurable modules; between start and stop)
 //! - `OoP` spawn / wait / stop (host-only orchestration)
 
 use axum::Router;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -28,7 +30,10 @@ use crate::registry::{
     ApiGatewayCap, GrpcHubCap, ModuleEntry, ModuleRegistry, RegistryError, RestApiCap, RunnableCap,
     SystemCap,
 };
-use crate::runtime::{GrpcInstallerStore, ModuleManager, OopSpawnOptions, SystemContext};
+use crate::runtime::reload::ConfigReloader;
+use crate::runtime::{
+    GrpcInstallerStore, MigrationHistory, ModuleManager, OopSpawnOptions, SystemContext,
+};
 
 #[cfg(feature = "db")]
 use crate::registry::DatabaseCap;
@@ -58,6 +63,10 @@ pub const MODKIT_DIRECTORY_ENDPOINT_ENV: &str = "MODKIT_DIRECTORY_ENDPOINT";
 /// Environment variable name for passing rendered module config to `OoP` modules.
 pub const MODKIT_MODULE_CONFIG_ENV: &str = "MODKIT_MODULE_CONFIG";
 
+/// Environment variable name for passing the instance ID to `OoP` modules,
+/// so that a restarted process keeps its directory identity.
+pub const MODKIT_INSTANCE_ID_ENV: &str = "MODKIT_INSTANCE_ID";
+
 /// `HostRuntime` owns the lifecycle orchestration for `ModKit`.
 ///
 /// It encapsulates all runtime state and drives modules through the full lifecycle (see module docs).
@@ -74,6 +83,12 @@ pub struct HostRuntime {
     db_options: DbOptions,
     /// `OoP` module spawn configuration and backend
     oop_options: Option<OopSpawnOptions>,
+    /// Config the modules were initialized with; baseline for reloads
+    modules_cfg: Arc<dyn ConfigProvider>,
+    /// Reloaded configs to deliver to reconfigurable modules
+    config_updates: Option<watch::Receiver<Arc<dyn ConfigProvider>>>,
+    /// Migration history recorded by the DB phase, shared with system modules
+    migration_history: Arc<MigrationHistory>,
 }
 
 impl HostRuntime {
@@ -102,7 +117,7 @@ impl HostRuntime {
 
         let ctx_builder = ModuleContextBuilder::new(
             instance_id,
-            modules_cfg,
+            modules_cfg.clone(),
             client_hub.clone(),
             cancel.clone(),
             db_manager,
@@ -118,9 +133,22 @@ impl HostRuntime {
             cancel,
             db_options,
             oop_options,
+            modules_cfg,
+            config_updates: None,
+            migration_history: Arc::default(),
         }
     }
 
+    /// Deliver configs published on `updates` to reconfigurable modules while running.
+    #[must_use]
+    pub fn with_config_updates(
+        mut self,
+        updates: watch::Receiver<Arc<dyn ConfigProvider>>,
+    ) -> Self {
+        self.config_updates = Some(updates);
+        self
+    }
+
     /// `PRE_INIT` phase: wire runtime internals into system modules.
     ///
     /// This phase runs before init and only for modules with the "system" capability.
@@ -134,7 +162,8 @@ impl HostRuntime {
             self.instance_id,
             Arc::clone(&self.module_manager),
             Arc::clone(&self.grpc_installers),
-        );
+        )
+        .with_migration_history(Arc::clone(&self.migration_history));
 
         for entry in self.registry.modules() {
             // Check for cancellation before processing each module
@@ -247,6 +276,58 @@ impl HostRuntime {
         Ok(())
     }
 
+    /// Snapshot a module's migration history for system modules.
+    ///
+    /// Best-effort: a failure only leaves the module without a recorded history.
+    #[cfg(feature = "db")]
+    async fn record_migration_history(
+        &self,
+        module_name: &'static str,
+        db: &modkit_db::Db,
+        db_module: &dyn crate::contracts::DatabaseCapability,
+    ) {
+        let migrations = db_module.migrations();
+        match modkit_db::migration_runner::migration_status(db, module_name, &migrations).await {
+            Ok(status) => self
+                .migration_history
+                .record(module_name, status.into_iter().map(Into::into).collect()),
+            Err(e) => tracing::warn!(
+                module = module_name,
+                error = %e,
+                "Failed to read migration history"
+            ),
+        }
+    }
+
+    /// Databases and migrations of all modules with DB capability, system modules first.
+    ///
+    /// Used by `migrate` commands that inspect or roll back migrations instead of
+    /// running the DB phase. Modules without a configured database are skipped.
+    ///
+    /// # Errors
+    /// Returns `RegistryError` if a module context or database cannot be resolved.
+    #[cfg(feature = "db")]
+    pub async fn migration_targets(
+        &self,
+    ) -> Result<Vec<crate::runtime::MigrationTarget>, RegistryError> {
+        let mut targets = Vec::new();
+        for entry in self.registry.modules_by_system_priority() {
+            let ctx = self.module_context(entry.name).await?;
+            let db_module = entry.caps.query::<DatabaseCap>();
+            if let Some((db, dbm)) = self
+                .db_migration_target(entry.name, &ctx, db_module)
+                .await?
+            {
+                targets.push(crate::runtime::MigrationTarget {
+                    module: entry.name,
+                    db,
+                    migrations: dbm.migrations(),
+                });
+            }
+        }
+        Ok(targets)
+    }
+
     /// DB MIGRATION phase: run migrations for all modules with DB capability.
     ///
     /// Runs before init, with system modules processed first.
@@ -274,7 +355,9 @@ impl HostRuntime {
                 .await?
             {
                 Some((db, dbm)) => {
-                    Self::migrate_module(entry.name, &db, dbm).await?;
+                    Self::migrate_module(entry.name, &db, Arc::clone(&dbm)).await?;
+                    self.record_migration_history(entry.name, &db, dbm.as_ref())
+                        .await;
                 }
                 None if db_module.is_some() => {
                     tracing::debug!(
@@ -330,7 +413,8 @@ impl HostRuntime {
             self.instance_id,
             Arc::clone(&self.module_manager),
             Arc::clone(&self.grpc_installers),
-        );
+        )
+        .with_migration_history(Arc::clone(&self.migration_history));
 
         for entry in self.registry.modules_by_system_priority() {
             if let Some(sys_mod) = entry.caps.query::<SystemCap>() {
@@ -570,6 +654,39 @@ impl HostRuntime {
         Ok(())
     }
 
+    /// RUNNING phase: wait for cancellation, applying config reloads in the meantime.
+    ///
+    /// Without a config update channel this simply waits for cancellation. If the
+    /// publisher goes away, reloads stop but the runtime keeps running.
+    async fn run_until_cancelled(&mut self) {
+        let Some(mut updates) = self.config_updates.take() else {
+            self.cancel.cancelled().await;
+            return;
+        };
+
+        let mut reloader = ConfigReloader::new(&self.registry, self.modules_cfg.as_ref());
+        loop {
+            tokio::select! {
+                () = self.cancel.cancelled() => return,
+                changed = updates.changed() => {
+                    if changed.is_err() {
+                        tracing::debug!("Config update channel closed; reloads disabled");
+                        self.cancel.cancelled().await;
+                        return;
+                    }
+                    let next = updates.borrow_and_update().clone();
+                    let summary = reloader.apply(&self.registry, next.as_ref()).await;
+                    tracing::info!(
+                        applied = ?summary.applied,
+                        rejected = ?summary.rejected,
+                        restart_required = ?summary.restart_required,
+                        "Config reload processed"
+                    );
+                }
+            }
+        }
+    }
+
     /// `OoP` SPAWN phase: spawn out-of-process modules after start phase.
     ///
     /// This phase runs after `grpc-hub` is already listening, so we can pass
@@ -602,10 +719,15 @@ impl HostRuntime {
 
             let spawn_config = OopSpawnConfig {
                 module_name: module_cfg.module_name.clone(),
+                backend: module_cfg.backend,
                 binary: module_cfg.binary.clone(),
                 args,
                 env,
                 working_directory: module_cfg.working_directory.clone(),
+                restart: module_cfg.restart.clone(),
+                module_manager: Some(Arc::clone(&self.module_manager)),
+                static_endpoints: module_cfg.static_endpoints.clone(),
+                k8s: module_cfg.k8s.clone(),
             };
 
             oop_opts
@@ -712,9 +834,9 @@ impl HostRuntime {
     /// 6. gRPC (modules with gRPC capability)
     /// 7. Start (runnable modules)
     /// 8. `OoP` spawn (out-of-process modules)
-    /// 9. Wait for cancellation
+    /// 9. Wait for cancellation (applying config reloads, if enabled)
     /// 10. Stop (runnable modules in reverse order)
-    async fn run_phases_internal(self, mode: RunMode) -> anyhow::Result<()> {
+    async fn run_phases_internal(mut self, mode: RunMode) -> anyhow::Result<()> {
         // Log execution mode
         match mode {
             RunMode::Full => {
@@ -762,8 +884,8 @@ impl HostRuntime {
         // 8. OoP spawn phase (after grpc_hub is running)
         self.run_oop_spawn_phase().await?;
 
-        // 9. Wait for cancellation
-        self.cancel.cancelled().await;
+        // 9. Wait for cancellation, delivering config reloads meanwhile
+        self.run_until_cancelled().await;
 
         // 10. Stop phase
         self.run_stop_phase().await?;
@@ -0,0 +1,79 @@
+//! Migration commands and the per-module migration history snapshot.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+
+/// What the `migrate` command does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// Apply pending migrations of all modules.
+    #[default]
+    Up,
+    /// Report applied and pending migrations of all modules.
+    Status,
+    /// Roll back migrations of one module.
+    ///
+    /// With `to`, every migration after `to` is rolled back; without, only the newest.
+    Down { module: String, to: Option<String> },
+}
+
+/// Options of the `migrate` command.
+#[derive(Debug, Clone, Default)]
+pub struct MigrateOptions {
+    pub command: MigrateCommand,
+    /// Print the SQL `Up`/`Down` would run instead of running it.
+    pub dry_run: bool,
+}
+
+/// State of one migration of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationInfo {
+    pub name: String,
+    /// When the migration was applied; `None` if it is pending.
+    pub applied_at: Option<String>,
+    /// `false` for an applied migration the module no longer provides.
+    pub known: bool,
+}
+
+/// Migration history of every module with a database, captured by the runtime right after
+/// its DB phase.
+///
+/// System modules receive it through [`SystemContext`](crate::runtime::SystemContext) to
+/// expose it read-only; they never get database access for it.
+#[derive(Debug, Default)]
+pub struct MigrationHistory {
+    modules: RwLock<HashMap<String, Vec<MigrationInfo>>>,
+}
+
+impl MigrationHistory {
+    /// History of `module`, or `None` if it has no database or was not migrated.
+    #[must_use]
+    pub fn get(&self, module: &str) -> Option<Vec<MigrationInfo>> {
+        self.modules.read().get(module).cloned()
+    }
+
+    /// Replace the recorded history of `module`.
+    pub fn record(&self, module: &str, migrations: Vec<MigrationInfo>) {
+        self.modules.write().insert(module.to_owned(), migrations);
+    }
+}
+
+#[cfg(feature = "db")]
+impl From<modkit_db::migration_runner::MigrationStatus> for MigrationInfo {
+    fn from(status: modkit_db::migration_runner::MigrationStatus) -> Self {
+        Self {
+            name: status.name,
+            applied_at: status.applied_at,
+            known: status.known,
+        }
+    }
+}
+
+/// A module's database together with the migrations it provides.
+#[cfg(feature = "db")]
+pub struct MigrationTarget {
+    pub module: &'static str,
+    pub db: modkit_db::Db,
+    pub migrations: Vec<Box<dyn sea_orm_migration::MigrationTrait>>,
+}
@@ -1,6 +1,8 @@
 mod grpc_installers;
 mod host_runtime;
+mod migrations;
 mod module_manager;
+mod reload;
 mod runner;
 mod system_context;
 
@@ -12,8 +14,12 @@ mod tests;
 
 pub use grpc_installers::{GrpcInstallerData, GrpcInstallerStore, ModuleInstallers};
 pub use host_runtime::{
-    DbOptions, HostRuntime, MODKIT_DIRECTORY_ENDPOINT_ENV, MODKIT_MODULE_CONFIG_ENV,
+    DbOptions, HostRuntime, MODKIT_DIRECTORY_ENDPOINT_ENV, MODKIT_INSTANCE_ID_ENV,
+    MODKIT_MODULE_CONFIG_ENV,
 };
+#[cfg(feature = "db")]
+pub use migrations::MigrationTarget;
+pub use migrations::{MigrateCommand, MigrateOptions, MigrationHistory, MigrationInfo};
 pub use module_manager::{Endpoint, InstanceState, ModuleInstance, ModuleManager};
 pub use runner::{
     ClientRegistration, OopModuleSpawnConfig, OopSpawnOptions, RunOptions, ShutdownOptions, run,
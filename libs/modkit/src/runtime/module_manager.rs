@@ -75,6 +75,10 @@ pub enum InstanceState {
     Healthy,
     Quarantined,
     Draining,
+    /// The process exited and the supervisor is about to start it again
+    Restarting,
+    /// The supervisor gave up restarting the process (crash loop)
+    Failed,
 }
 
 /// Runtime state of an instance (guarded by `RwLock` for safe mutation)
@@ -82,6 +86,8 @@ pub enum InstanceState {
 pub struct InstanceRuntimeState {
     pub last_heartbeat: Instant,
     pub state: InstanceState,
+    /// Number of times the supervisor restarted this instance's process
+    pub restart_count: u32,
 }
 
 /// Represents a single instance of a module
@@ -120,6 +126,7 @@ impl ModuleInstance {
             inner: Arc::new(parking_lot::RwLock::new(InstanceRuntimeState {
                 last_heartbeat: Instant::now(),
                 state: InstanceState::Registered,
+                restart_count: 0,
             })),
         }
     }
@@ -150,6 +157,12 @@ impl ModuleInstance {
     pub fn last_heartbeat(&self) -> Instant {
         self.inner.read().last_heartbeat
     }
+
+    /// Number of times the supervisor restarted this instance's process
+    #[must_use]
+    pub fn restart_count(&self) -> u32 {
+        self.inner.read().restart_count
+    }
 }
 
 /// Central registry that tracks all running module instances in the system.
@@ -200,6 +213,8 @@ impl ModuleManager {
             .iter()
             .position(|i| i.instance_id == instance.instance_id)
         {
+            // A restarted process re-registers under the same id; keep its history.
+            instance.inner.write().restart_count = vec[pos].restart_count();
             vec[pos] = instance;
         } else {
             vec.push(instance);
@@ -248,6 +263,36 @@ impl ModuleManager {
         }
     }
 
+    /// Record that the supervisor is restarting an instance's process.
+    ///
+    /// Registers a placeholder if the process exited before registering itself,
+    /// so restarts stay visible in the directory.
+    pub fn record_restart(&self, module: &str, instance_id: Uuid) {
+        let inst = self.get_or_insert(module, instance_id);
+        let mut state = inst.inner.write();
+        state.state = InstanceState::Restarting;
+        state.restart_count += 1;
+        state.last_heartbeat = Instant::now();
+    }
+
+    /// Mark an instance as failed (the supervisor gave up restarting it)
+    pub fn mark_failed(&self, module: &str, instance_id: Uuid) {
+        let inst = self.get_or_insert(module, instance_id);
+        let mut state = inst.inner.write();
+        state.state = InstanceState::Failed;
+        state.last_heartbeat = Instant::now();
+    }
+
+    fn get_or_insert(&self, module: &str, instance_id: Uuid) -> Arc<ModuleInstance> {
+        let mut vec = self.inner.entry(module.to_owned()).or_default();
+        if let Some(inst) = vec.iter().find(|i| i.instance_id == instance_id) {
+            return Arc::clone(inst);
+        }
+        let inst = Arc::new(ModuleInstance::new(module, instance_id));
+        vec.push(Arc::clone(&inst));
+        inst
+    }
+
     /// Remove an instance from the directory
     pub fn deregister(&self, module: &str, instance_id: Uuid) {
         let mut remove_module = false;
@@ -287,7 +332,7 @@ impl ModuleManager {
 
     /// Quarantine or evict stale instances based on heartbeat policy
     pub fn evict_stale(&self, now: Instant) {
-        use InstanceState::{Draining, Quarantined};
+        use InstanceState::{Draining, Failed, Quarantined, Restarting};
         let mut empty_modules = Vec::new();
 
         for mut entry in self.inner.iter_mut() {
@@ -297,15 +342,19 @@ impl ModuleManager {
                 let state = inst.inner.read();
                 let age = now.saturating_duration_since(state.last_heartbeat);
 
-                // Quarantine instances that have exceeded TTL
-                if age >= self.hb_ttl && !matches!(state.state, Quarantined | Draining) {
+                // Quarantine instances that have exceeded TTL. Restarting
+                // instances are silent during backoff; the supervisor resolves them.
+                if age >= self.hb_ttl
+                    && !matches!(state.state, Quarantined | Draining | Restarting | Failed)
+                {
                     drop(state); // Release read lock before write
                     inst.inner.write().state = Quarantined;
                     return true; // Keep quarantined instances for now
                 }
 
-                // Evict quarantined instances that exceed grace period
-                if state.state == Quarantined && age >= self.hb_ttl + self.hb_grace {
+                // Evict quarantined and failed instances that exceed grace period
+                if matches!(state.state, Quarantined | Failed) && age >= self.hb_ttl + self.hb_grace
+                {
                     return false; // Remove from directory
                 }
 
@@ -731,4 +780,43 @@ mod tests {
         // Endpoints should differ
         assert_ne!(ep1, ep2);
     }
+
+    #[test]
+    fn test_restart_events_survive_re_registration() {
+        let dir = ModuleManager::new()
+            .with_heartbeat_policy(Duration::from_millis(1), Duration::from_millis(1));
+        let instance_id = Uuid::new_v4();
+
+        // Process crashed before it ever registered: a placeholder appears.
+        dir.record_restart("oop_module", instance_id);
+        let instances = dir.instances_of("oop_module");
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].state(), InstanceState::Restarting);
+        assert_eq!(instances[0].restart_count(), 1);
+
+        // Restarting instances are not quarantined while silent.
+        sleep(Duration::from_millis(5));
+        dir.evict_stale(Instant::now());
+        assert_eq!(
+            dir.instances_of("oop_module")[0].state(),
+            InstanceState::Restarting
+        );
+
+        // The restarted process registers again under the same id.
+        dir.register_instance(Arc::new(ModuleInstance::new("oop_module", instance_id)));
+        let instances = dir.instances_of("oop_module");
+        assert_eq!(instances[0].state(), InstanceState::Registered);
+        assert_eq!(instances[0].restart_count(), 1);
+
+        dir.record_restart("oop_module", instance_id);
+        dir.mark_failed("oop_module", instance_id);
+        let instances = dir.instances_of("oop_module");
+        assert_eq!(instances[0].state(), InstanceState::Failed);
+        assert_eq!(instances[0].restart_count(), 2);
+
+        // Failed instances are evicted after the grace period.
+        sleep(Duration::from_millis(5));
+        dir.evict_stale(Instant::now());
+        assert!(dir.instances_of("oop_module").is_empty());
+    }
 }
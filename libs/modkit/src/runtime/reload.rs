@@ -0,0 +1,271 @@
+//! Runtime config reload - delivers changed module `config` sections to reconfigurable modules.
+//!
+//! The host keeps a digest of the last `config` section each module accepted (sections may
+//! hold resolved secrets, so they are not retained). When a new
+//! `ConfigProvider` arrives, every module whose section changed is handled as follows:
+//! - reconfigurable modules get `validate_config` and then `apply_config`; on any error the
+//!   previous section stays current, so the next reload diffs against it again;
+//! - other modules are reported as requiring a restart and keep their startup config.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use serde_json::Value;
+
+use crate::config::ConfigProvider;
+use crate::registry::{ModuleRegistry, ReconfigurableCap};
+
+/// Outcome of a single config reload.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReloadSummary {
+    /// Modules that validated and applied their new section.
+    pub applied: Vec<&'static str>,
+    /// Modules that rejected their new section (validation or apply failed).
+    pub rejected: Vec<&'static str>,
+    /// Modules whose section changed but which are not reconfigurable.
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ReloadSummary {
+    /// Returns `true` if no module section changed.
+    #[cfg(test)]
+    fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.rejected.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+/// Tracks a digest of the `config` section currently in effect for each module.
+pub struct ConfigReloader {
+    current: HashMap<&'static str, u64>,
+}
+
+impl ConfigReloader {
+    /// Snapshot the sections the modules were initialized with.
+    pub fn new(registry: &ModuleRegistry, initial: &dyn ConfigProvider) -> Self {
+        let current = registry
+            .modules()
+            .iter()
+            .map(|e| (e.name, digest(&config_section(initial, e.name))))
+            .collect();
+        Self { current }
+    }
+
+    /// Diff `next` against the sections in effect and deliver changes.
+    pub async fn apply(
+        &mut self,
+        registry: &ModuleRegistry,
+        next: &dyn ConfigProvider,
+    ) -> ReloadSummary {
+        let mut summary = ReloadSummary::default();
+
+        for entry in registry.modules() {
+            let candidate = config_section(next, entry.name);
+            let candidate_digest = digest(&candidate);
+            if self.current.get(entry.name) == Some(&candidate_digest) {
+                continue;
+            }
+
+            let Some(module) = entry.caps.query::<ReconfigurableCap>() else {
+                tracing::warn!(
+                    module = entry.name,
+                    "Config changed for a module that is not reconfigurable; restart required"
+                );
+                summary.restart_required.push(entry.name);
+                continue;
+            };
+
+            if let Err(e) = module.validate_config(&candidate) {
+                tracing::warn!(
+                    module = entry.name,
+                    error = %e,
+                    "Config reload rejected; keeping previous config"
+                );
+                summary.rejected.push(entry.name);
+                continue;
+            }
+
+            if let Err(e) = module.apply_config(&candidate).await {
+                tracing::error!(
+                    module = entry.name,
+                    error = %e,
+                    "Failed to apply reloaded config; keeping previous config"
+                );
+                summary.rejected.push(entry.name);
+                continue;
+            }
+
+            tracing::info!(module = entry.name, "Applied reloaded config");
+            self.current.insert(entry.name, candidate_digest);
+            summary.applied.push(entry.name);
+        }
+
+        summary
+    }
+}
+
+/// Extract `modules.<name>.config` with secrets resolved, treating a missing section as `null`.
+fn config_section(provider: &dyn ConfigProvider, module_name: &str) -> Value {
+    provider
+        .resolved_module_config(module_name)
+        .and_then(|m| m.get("config").cloned())
+        .unwrap_or(Value::Null)
+}
+
+fn digest(section: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    section.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::context::ModuleCtx;
+    use crate::contracts::{Module, ReconfigurableCapability};
+    use crate::registry::RegistryBuilder;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    struct MapProvider(HashMap<String, Value>);
+
+    impl MapProvider {
+        fn new(entries: &[(&str, Value)]) -> Self {
+            Self(
+                entries
+                    .iter()
+                    .map(|(k, v)| ((*k).to_owned(), json!({ "config": v })))
+                    .collect(),
+            )
+        }
+    }
+
+    impl ConfigProvider for MapProvider {
+        fn get_module_config(&self, module_name: &str) -> Option<&Value> {
+            self.0.get(module_name)
+        }
+    }
+
+    /// Accepts any config whose `limit` is a positive number.
+    #[derive(Default)]
+    struct Limiter {
+        limit: Mutex<u64>,
+        fail_apply: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl Module for Limiter {
+        async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ReconfigurableCapability for Limiter {
+        fn validate_config(&self, config: &Value) -> anyhow::Result<()> {
+            match config["limit"].as_u64() {
+                Some(n) if n > 0 => Ok(()),
+                _ => anyhow::bail!("limit must be a positive integer"),
+            }
+        }
+
+        async fn apply_config(&self, config: &Value) -> anyhow::Result<()> {
+            if self.fail_apply {
+                anyhow::bail!("apply failed");
+            }
+            *self.limit.lock().unwrap() = config["limit"].as_u64().unwrap_or_default();
+            Ok(())
+        }
+    }
+
+    struct Plain;
+
+    #[async_trait::async_trait]
+    impl Module for Plain {
+        async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn registry(limiter: &Arc<Limiter>) -> ModuleRegistry {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("limiter", &[], limiter.clone() as Arc<dyn Module>);
+        b.register_core_with_meta("plain", &[], Arc::new(Plain) as Arc<dyn Module>);
+        b.register_reconfigurable_with_meta(
+            "limiter",
+            limiter.clone() as Arc<dyn ReconfigurableCapability>,
+        );
+        b.build_topo_sorted().unwrap()
+    }
+
+    #[tokio::test]
+    async fn applies_changed_section_and_skips_unchanged() {
+        let limiter = Arc::new(Limiter::default());
+        let registry = registry(&limiter);
+        let initial = MapProvider::new(&[("limiter", json!({"limit": 1})), ("plain", json!({}))]);
+        let mut reloader = ConfigReloader::new(&registry, &initial);
+
+        let unchanged = reloader.apply(&registry, &initial).await;
+        assert!(unchanged.is_empty());
+
+        let next = MapProvider::new(&[("limiter", json!({"limit": 5})), ("plain", json!({}))]);
+        let summary = reloader.apply(&registry, &next).await;
+        assert_eq!(summary.applied, vec!["limiter"]);
+        assert!(summary.rejected.is_empty());
+        assert!(summary.restart_required.is_empty());
+        assert_eq!(*limiter.limit.lock().unwrap(), 5);
+
+        // Same provider again is a no-op.
+        assert!(reloader.apply(&registry, &next).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_section_keeps_previous_config() {
+        let limiter = Arc::new(Limiter::default());
+        let registry = registry(&limiter);
+        let initial = MapProvider::new(&[("limiter", json!({"limit": 1}))]);
+        let mut reloader = ConfigReloader::new(&registry, &initial);
+
+        let invalid = MapProvider::new(&[("limiter", json!({"limit": 0}))]);
+        let summary = reloader.apply(&registry, &invalid).await;
+        assert_eq!(summary.rejected, vec!["limiter"]);
+        assert_eq!(*limiter.limit.lock().unwrap(), 0, "apply must not run");
+
+        // The rejected section is not recorded; reverting to the original is a no-op.
+        assert!(reloader.apply(&registry, &initial).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_apply_is_retried_on_next_reload() {
+        let limiter = Arc::new(Limiter {
+            fail_apply: true,
+            ..Limiter::default()
+        });
+        let registry = registry(&limiter);
+        let initial = MapProvider::new(&[("limiter", json!({"limit": 1}))]);
+        let mut reloader = ConfigReloader::new(&registry, &initial);
+
+        let next = MapProvider::new(&[("limiter", json!({"limit": 2}))]);
+        assert_eq!(
+            reloader.apply(&registry, &next).await.rejected,
+            vec!["limiter"]
+        );
+        assert_eq!(
+            reloader.apply(&registry, &next).await.rejected,
+            vec!["limiter"]
+        );
+    }
+
+    #[tokio::test]
+    async fn non_reconfigurable_change_requires_restart() {
+        let limiter = Arc::new(Limiter::default());
+        let registry = registry(&limiter);
+        let initial = MapProvider::new(&[("plain", json!({"level": "info"}))]);
+        let mut reloader = ConfigReloader::new(&registry, &initial);
+
+        let next = MapProvider::new(&[("plain", json!({"level": "debug"}))]);
+        let summary = reloader.apply(&registry, &next).await;
+        assert_eq!(summary.restart_required, vec!["plain"]);
+        assert!(summary.applied.is_empty());
+    }
+}
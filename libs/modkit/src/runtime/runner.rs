@@ -14,7 +14,9 @@
 //! - `OoP` modules are spawned after the start phase so that `grpc-hub` is already running
 //!   and the real directory endpoint is known.
 
-use crate::backends::OopBackend;
+use crate::backends::{
+    BackendKind, K8sDeploymentConfig, OopBackend, RestartPolicy, StaticEndpointsConfig,
+};
 use crate::client_hub::ClientHub;
 use crate::config::ConfigProvider;
 use crate::registry::ModuleRegistry;
@@ -23,6 +25,7 @@ use crate::runtime::{DbOptions, HostRuntime};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::{future::Future, pin::Pin, sync::Arc};
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -75,8 +78,10 @@ pub enum ShutdownOptions {
 pub struct OopModuleSpawnConfig {
     /// Module name (e.g., "calculator")
     pub module_name: String,
-    /// Path to the executable
-    pub binary: PathBuf,
+    /// Backend that runs the module
+    pub backend: BackendKind,
+    /// Path to the executable (`LocalProcess` backend)
+    pub binary: Option<PathBuf>,
     /// Command-line arguments (user controls --config via execution.args in master config)
     pub args: Vec<String>,
     /// Environment variables to set
@@ -85,6 +90,12 @@ pub struct OopModuleSpawnConfig {
     pub working_directory: Option<String>,
     /// Rendered module config JSON (for `MODKIT_MODULE_CONFIG` env var)
     pub rendered_config_json: String,
+    /// Restart policy applied by the backend when the process exits
+    pub restart: RestartPolicy,
+    /// Pre-existing endpoints (`Static` backend)
+    pub static_endpoints: Option<StaticEndpointsConfig>,
+    /// Deployment settings (`K8s` backend)
+    pub k8s: Option<K8sDeploymentConfig>,
 }
 
 /// Options for spawning `OoP` modules.
@@ -119,6 +130,11 @@ pub struct RunOptions {
     /// These modules are spawned after the start phase, once `grpc-hub` is running
     /// and the real directory endpoint is known.
     pub oop: Option<OopSpawnOptions>,
+    /// Reloaded module configs published while the runtime is running.
+    ///
+    /// Each new value is diffed against the config in effect; changed sections are
+    /// validated and applied by modules with the `reconfigurable` capability.
+    pub config_updates: Option<watch::Receiver<Arc<dyn ConfigProvider>>>,
 }
 
 /// Full cycle is orchestrated by `HostRuntime` (see `runtime/host_runtime.rs` docs).
@@ -181,7 +197,7 @@ pub async fn run(opts: RunOptions) -> anyhow::Result<()> {
     }
 
     // 5. Instantiate HostRuntime
-    let host = HostRuntime::new(
+    let mut host = HostRuntime::new(
         registry,
         opts.modules_cfg.clone(),
         opts.db,
@@ -190,6 +206,9 @@ pub async fn run(opts: RunOptions) -> anyhow::Result<()> {
         opts.instance_id,
         opts.oop,
     );
+    if let Some(updates) = opts.config_updates {
+        host = host.with_config_updates(updates);
+    }
 
     // 6. Run full lifecycle
     host.run_module_phases().await
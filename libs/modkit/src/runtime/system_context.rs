@@ -3,7 +3,7 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::runtime::{GrpcInstallerStore, ModuleManager};
+use crate::runtime::{GrpcInstallerStore, MigrationHistory, ModuleManager};
 
 /// System-level context provided to system modules during the wiring phase.
 ///
@@ -20,6 +20,9 @@ pub struct SystemContext {
 
     /// gRPC service installer store
     pub grpc_installers: Arc<GrpcInstallerStore>,
+
+    /// Per-module migration history, filled by the runtime after the DB phase
+    pub migration_history: Arc<MigrationHistory>,
 }
 
 impl SystemContext {
@@ -33,9 +36,17 @@ impl SystemContext {
             instance_id,
             module_manager,
             grpc_installers,
+            migration_history: Arc::default(),
         }
     }
 
+    /// Share the runtime's migration history with system modules.
+    #[must_use]
+    pub fn with_migration_history(mut self, migration_history: Arc<MigrationHistory>) -> Self {
+        self.migration_history = migration_history;
+        self
+    }
+
     /// Returns the process-level instance ID.
     ///
     /// This is a unique identifier for this process instance, shared by all modules
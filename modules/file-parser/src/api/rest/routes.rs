@@ -75,6 +75,7 @@ pub fn register_routes(
         .operation_id("file_parser.upload")
         .summary("Upload and parse a file")
         .tag("File Parser")
+        .idempotent()
         .authenticated()
         .require_license_features::<License>([])
         .query_param_typed(
@@ -22,6 +22,7 @@ pub(crate) type DbProvider = DBProvider<modkit_db::DbError>;
 pub(crate) const SETTINGS_RESOURCE: ResourceType = ResourceType {
     name: "simple_user_settings.settings",
     supported_properties: &[pep_properties::OWNER_TENANT_ID, pep_properties::RESOURCE_ID],
+    timestamp_properties: &[],
 };
 
 pub(crate) mod actions {
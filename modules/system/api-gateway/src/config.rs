@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
 
 fn default_require_auth_by_default() -> bool {
     true
@@ -41,6 +44,26 @@ pub struct ApiGatewayConfig {
     /// If true, routes without explicit security requirement still require authentication (AuthN-only).
     #[serde(default = "default_require_auth_by_default")]
     pub require_auth_by_default: bool,
+
+    /// How rate-limit buckets are keyed and where their state is kept
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+
+    /// Negotiated response compression
+    #[serde(default)]
+    pub compression: CompressionConfig,
+
+    /// Automatic weak `ETag`s and `If-None-Match` handling for GET operations
+    #[serde(default)]
+    pub etag: EtagConfig,
+
+    /// Stored responses for operations that honour `Idempotency-Key`
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
+
+    /// Serve HTTPS instead of plain HTTP on `bind_addr`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -79,6 +102,234 @@ impl Default for RateLimitDefaults {
     }
 }
 
+/// Rate-limit bucket keys, overrides and shared state.
+///
+/// Every route has its own buckets. By default a route has a single bucket shared by
+/// all callers; `key` splits it further by attributes of the request.
+///
+/// Buckets keyed by `tenant` or `subject` are checked after authentication. Before it, each
+/// route then has a coarse bucket per peer IP holding `pre_auth_multiplier` times the most
+/// generous caller quota, so floods are rejected without calling the `AuthN` resolver while
+/// callers behind one address (NAT, proxies) do not limit each other.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct RateLimitConfig {
+    /// Request attributes that get a separate bucket per route, e.g. `["tenant"]`
+    pub key: Vec<RateLimitKeyPart>,
+    /// Limits that replace the route limits for the given tenants; requires `tenant` in `key`
+    pub tenant_overrides: HashMap<Uuid, RateLimitOverride>,
+    /// Size of the pre-auth per-IP bucket relative to the largest caller quota
+    pub pre_auth_multiplier: u32,
+    /// Where bucket state is kept
+    pub backend: RateLimitBackend,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            key: Vec::new(),
+            tenant_overrides: HashMap::new(),
+            pre_auth_multiplier: 10,
+            backend: RateLimitBackend::default(),
+        }
+    }
+}
+
+/// Request attribute used in rate-limit bucket keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKeyPart {
+    /// Tenant of the caller's `SecurityContext`
+    Tenant,
+    /// Subject of the caller's `SecurityContext`
+    Subject,
+    /// Peer IP address of the connection
+    Ip,
+}
+
+/// Per-tenant replacement for a route's rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitOverride {
+    pub rps: u32,
+    pub burst: u32,
+}
+
+/// Storage for rate-limit bucket state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitBackend {
+    /// Process-local state; each replica enforces the limits on its own.
+    #[default]
+    Memory,
+    /// State shared by all replicas through the module's database.
+    ///
+    /// Requires a `database:` section for the module on `PostgreSQL` or `SQLite`.
+    Database,
+}
+
+/// Response compression negotiated via `Accept-Encoding`.
+///
+/// Operations registered with `disable_compression()` and `text/event-stream`, gRPC and
+/// image responses are never compressed.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct CompressionConfig {
+    pub enabled: bool,
+    /// Responses smaller than this are sent uncompressed
+    pub min_size_bytes: u16,
+    /// Encodings offered to clients
+    pub algorithms: Vec<CompressionAlgorithm>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_size_bytes: 1024,
+            algorithms: vec![
+                CompressionAlgorithm::Zstd,
+                CompressionAlgorithm::Br,
+                CompressionAlgorithm::Gzip,
+            ],
+        }
+    }
+}
+
+/// Content coding used for response compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Br,
+    Zstd,
+}
+
+/// Weak `ETag`s computed from the body of successful GET responses.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct EtagConfig {
+    pub enabled: bool,
+    /// Larger bodies (and bodies of unknown length, e.g. streams) get no `ETag`
+    pub max_body_bytes: usize,
+}
+
+impl Default for EtagConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_body_bytes: 4 * 1024 * 1024,
+        }
+    }
+}
+
+/// Responses stored for operations registered with `idempotent()`.
+///
+/// A response is kept per (tenant, subject, `Idempotency-Key`) and replayed for retries
+/// with the same request body.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct IdempotencyConfig {
+    /// How long a stored response is replayed
+    pub ttl_secs: u64,
+    /// How long a key stays locked by a request that has not completed (e.g. a crashed replica)
+    pub lock_ttl_secs: u64,
+    /// Larger responses (and responses of unknown length) are not stored
+    pub max_response_bytes: usize,
+    /// Where stored responses are kept
+    pub backend: IdempotencyBackend,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 24 * 60 * 60,
+            lock_ttl_secs: 60,
+            max_response_bytes: 1024 * 1024,
+            backend: IdempotencyBackend::default(),
+        }
+    }
+}
+
+/// Storage for idempotency records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdempotencyBackend {
+    /// Process-local records; retries must reach the same replica.
+    #[default]
+    Memory,
+    /// Records shared by all replicas through the module's database.
+    ///
+    /// Requires a `database:` section for the module on `PostgreSQL` or `SQLite`.
+    Database,
+}
+
+/// TLS termination with rustls.
+///
+/// Certificate and key files are PEM. They are polled for changes and the new
+/// certificates are used for subsequent connections, so rotated files need no restart.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    /// Certificate chain served when no SNI certificate matches
+    pub cert_path: PathBuf,
+    /// Private key of `cert_path`
+    pub key_path: PathBuf,
+    /// Additional certificates selected by the server name the client sends (SNI)
+    #[serde(default)]
+    pub sni: Vec<SniCertConfig>,
+    /// How often certificate files are checked for changes; 0 disables reloading
+    #[serde(default = "default_tls_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+    /// Verify client certificates (mTLS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_auth: Option<ClientAuthConfig>,
+}
+
+fn default_tls_reload_interval_secs() -> u64 {
+    30
+}
+
+/// Certificate served for the given server names.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SniCertConfig {
+    /// Server names, matched case-insensitively against the client's SNI
+    pub server_names: Vec<String>,
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+/// Client certificate verification.
+///
+/// A verified certificate whose subject is listed in `subjects` authenticates the request
+/// when it carries no bearer token.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientAuthConfig {
+    /// PEM bundle of CAs that client certificates must chain to
+    pub ca_path: PathBuf,
+    /// Reject handshakes without a client certificate; otherwise the certificate is optional
+    #[serde(default)]
+    pub required: bool,
+    /// Identities by certificate subject in RFC 4514 form, e.g. `CN=billing,O=Example`
+    #[serde(default)]
+    pub subjects: HashMap<String, ClientCertIdentity>,
+}
+
+/// Identity given to requests authenticated by a client certificate.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientCertIdentity {
+    pub subject_id: Uuid,
+    pub tenant_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_type: Option<String>,
+    /// Token scopes granted to the identity; empty means unrestricted
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields, default)]
 pub struct CorsConfig {
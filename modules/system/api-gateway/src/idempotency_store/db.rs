@@ -0,0 +1,383 @@
+//! Database-backed idempotency records shared by all gateway replicas.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use modkit_db::secure::{
+    AccessScope, DbConn, ScopeError, SecureDeleteExt, SecureEntityExt, SecureInsertExt,
+    SecureUpdateExt,
+};
+use modkit_db::{DBProvider, DbError};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{Condition, DbErr, EntityTrait, QueryFilter, Set};
+use sea_orm_migration::prelude::{
+    Alias, ColumnDef, MigrationName, MigrationTrait, SchemaManager, Table,
+};
+
+use super::{IdempotencyBegin, IdempotencyStore, StoredResponse, expiry};
+use crate::rate_limit_store::now_nanos;
+
+const TABLE: &str = "api_gateway_idempotency";
+
+/// Attempts at locking a contended key before the request is treated as in progress.
+const MAX_ATTEMPTS: usize = 8;
+
+/// Minimum time between sweeps of expired records.
+const PRUNE_INTERVAL_NANOS: i64 = 60 * 1_000_000_000;
+
+mod entity {
+    use modkit_db_macros::Scopable;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "api_gateway_idempotency")]
+    #[secure(unrestricted)]
+    pub struct Model {
+        /// Tenant, subject and `Idempotency-Key` of the request.
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub key: String,
+        pub fingerprint: String,
+        /// Response status; `None` while the request is in progress.
+        pub status: Option<i32>,
+        /// Response headers as a JSON array of `[name, value]` pairs.
+        pub headers: Option<String>,
+        pub body: Option<Vec<u8>>,
+        /// Nanoseconds since the Unix epoch.
+        pub expires_at: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use entity::{ActiveModel, Column, Entity, Model};
+
+/// Creates the `api_gateway_idempotency` table used by [`DbIdempotencyStore`].
+pub struct IdempotencyMigration;
+
+impl MigrationName for IdempotencyMigration {
+    fn name(&self) -> &'static str {
+        "m002_api_gateway_idempotency"
+    }
+}
+
+#[async_trait]
+impl MigrationTrait for IdempotencyMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(TABLE))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new("key"))
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new("fingerprint"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Alias::new("status")).integer().null())
+                    .col(ColumnDef::new(Alias::new("headers")).text().null())
+                    .col(ColumnDef::new(Alias::new("body")).binary().null())
+                    .col(
+                        ColumnDef::new(Alias::new("expires_at"))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new(TABLE))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Idempotency records kept in the module's database, so retries may reach any replica.
+pub struct DbIdempotencyStore {
+    db: DBProvider<DbError>,
+    last_prune: AtomicI64,
+}
+
+impl DbIdempotencyStore {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self {
+            db,
+            last_prune: AtomicI64::new(0),
+        }
+    }
+
+    /// Insert a locked record; `false` if another request created it first.
+    async fn insert(
+        conn: &DbConn<'_>,
+        key: &str,
+        fingerprint: &str,
+        expires_at: i64,
+    ) -> Result<bool, ScopeError> {
+        let am = ActiveModel {
+            key: Set(key.to_owned()),
+            fingerprint: Set(fingerprint.to_owned()),
+            status: Set(None),
+            headers: Set(None),
+            body: Set(None),
+            expires_at: Set(expires_at),
+        };
+        let inserted = Entity::insert(am)
+            .secure()
+            .scope_unchecked(&AccessScope::allow_all())?
+            .on_conflict_raw(OnConflict::column(Column::Key).do_nothing().to_owned())
+            .exec(conn)
+            .await;
+        match inserted {
+            Ok(_) => Ok(true),
+            Err(ScopeError::Db(DbErr::RecordNotInserted)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Lock an expired record for a new request; `false` if it changed meanwhile.
+    async fn take_over(
+        conn: &DbConn<'_>,
+        expired: &Model,
+        fingerprint: &str,
+        expires_at: i64,
+    ) -> Result<bool, ScopeError> {
+        let result = Entity::update_many()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .col_expr(Column::Fingerprint, Expr::value(fingerprint))
+            .col_expr(Column::Status, Expr::value(Option::<i32>::None))
+            .col_expr(Column::Headers, Expr::value(Option::<String>::None))
+            .col_expr(Column::Body, Expr::value(Option::<Vec<u8>>::None))
+            .col_expr(Column::ExpiresAt, Expr::value(expires_at))
+            .filter(
+                Condition::all()
+                    .add(Expr::col(Column::Key).eq(expired.key.as_str()))
+                    .add(Expr::col(Column::ExpiresAt).eq(expired.expires_at)),
+            )
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+
+    /// Condition matching the in-progress record of `key` locked for `fingerprint`.
+    fn locked_by(key: &str, fingerprint: &str) -> Condition {
+        Condition::all()
+            .add(Expr::col(Column::Key).eq(key))
+            .add(Expr::col(Column::Fingerprint).eq(fingerprint))
+            .add(Expr::col(Column::Status).is_null())
+    }
+
+    /// Drop expired records, at most once per [`PRUNE_INTERVAL_NANOS`].
+    async fn prune(&self, conn: &DbConn<'_>, now: i64) -> Result<(), ScopeError> {
+        let last = self.last_prune.load(Ordering::Relaxed);
+        if now - last < PRUNE_INTERVAL_NANOS
+            || self
+                .last_prune
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            return Ok(());
+        }
+        Entity::delete_many()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Condition::all().add(Expr::col(Column::ExpiresAt).lt(now)))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    fn stored_response(model: Model, status: i32) -> anyhow::Result<StoredResponse> {
+        let headers = match model.headers {
+            Some(json) => serde_json::from_str(&json).context("Invalid stored headers")?,
+            None => Vec::new(),
+        };
+        Ok(StoredResponse {
+            status: u16::try_from(status).context("Invalid stored status")?,
+            headers,
+            body: model.body.unwrap_or_default(),
+        })
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for DbIdempotencyStore {
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lock_ttl: Duration,
+    ) -> anyhow::Result<IdempotencyBegin> {
+        let conn = self.db.conn()?;
+
+        for _ in 0..MAX_ATTEMPTS {
+            let now = now_nanos();
+            let current = Entity::find()
+                .filter(Condition::all().add(Expr::col(Column::Key).eq(key)))
+                .secure()
+                .scope_with(&AccessScope::allow_all())
+                .one(&conn)
+                .await?;
+
+            let acquired = match current {
+                Some(record) if record.expires_at > now => {
+                    return if record.fingerprint != fingerprint {
+                        Ok(IdempotencyBegin::Mismatch)
+                    } else if let Some(status) = record.status {
+                        Self::stored_response(record, status).map(IdempotencyBegin::Replay)
+                    } else {
+                        Ok(IdempotencyBegin::InProgress)
+                    };
+                }
+                Some(expired) => {
+                    Self::take_over(&conn, &expired, fingerprint, expiry(now, lock_ttl)).await?
+                }
+                None => {
+                    self.prune(&conn, now).await?;
+                    Self::insert(&conn, key, fingerprint, expiry(now, lock_ttl)).await?
+                }
+            };
+            if acquired {
+                return Ok(IdempotencyBegin::Acquired);
+            }
+        }
+
+        Ok(IdempotencyBegin::InProgress)
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+        ttl: Duration,
+    ) -> anyhow::Result<()> {
+        let conn = self.db.conn()?;
+        let headers = serde_json::to_string(&response.headers)?;
+        Entity::update_many()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .col_expr(Column::Status, Expr::value(i32::from(response.status)))
+            .col_expr(Column::Headers, Expr::value(headers))
+            .col_expr(Column::Body, Expr::value(response.body))
+            .col_expr(Column::ExpiresAt, Expr::value(expiry(now_nanos(), ttl)))
+            .filter(Self::locked_by(key, fingerprint))
+            .exec(&conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn release(&self, key: &str, fingerprint: &str) -> anyhow::Result<()> {
+        let conn = self.db.conn()?;
+        Entity::delete_many()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Self::locked_by(key, fingerprint))
+            .exec(&conn)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+
+    const LOCK: Duration = Duration::from_secs(60);
+    const TTL: Duration = Duration::from_secs(3600);
+
+    async fn store() -> DbIdempotencyStore {
+        let dsn = format!(
+            "sqlite:file:memdb_idempotency_{}?mode=memory&cache=shared",
+            uuid::Uuid::new_v4()
+        );
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+        let db = connect_db(&dsn, opts).await.expect("connect");
+        run_migrations_for_testing(&db, vec![Box::new(IdempotencyMigration)])
+            .await
+            .expect("migrate");
+        DbIdempotencyStore::new(DBProvider::new(db))
+    }
+
+    fn response() -> StoredResponse {
+        StoredResponse {
+            status: 201,
+            headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+            body: b"{\"id\":1}".to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn locks_completes_and_replays() {
+        let store = store().await;
+
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::Acquired
+        );
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::InProgress
+        );
+        assert_eq!(
+            store.begin("k", "other", LOCK).await.unwrap(),
+            IdempotencyBegin::Mismatch
+        );
+
+        store.complete("k", "f", response(), TTL).await.unwrap();
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::Replay(response())
+        );
+    }
+
+    #[tokio::test]
+    async fn released_and_expired_keys_are_free_again() {
+        let store = store().await;
+
+        store.begin("k", "f", LOCK).await.unwrap();
+        store.release("k", "f").await.unwrap();
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::Acquired
+        );
+
+        store.begin("expired", "f", Duration::ZERO).await.unwrap();
+        assert_eq!(
+            store.begin("expired", "other", LOCK).await.unwrap(),
+            IdempotencyBegin::Acquired
+        );
+
+        // A second replica sharing the database sees the lock
+        let second = DbIdempotencyStore::new(store.db.clone());
+        assert_eq!(
+            second.begin("expired", "other", LOCK).await.unwrap(),
+            IdempotencyBegin::InProgress
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Records of requests made with an `Idempotency-Key`.
+//!
+//! A record is created (locked) when the first request with a key starts, and holds that
+//! request's fingerprint. Once the request completes, the record keeps its response for
+//! replay until it expires. Records of requests that never complete expire after the
+//! lock TTL, so a crashed replica does not block a key forever.
+
+mod db;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::rate_limit_store::now_nanos;
+
+pub use db::{DbIdempotencyStore, IdempotencyMigration};
+
+/// Response kept for replay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Outcome of [`IdempotencyStore::begin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyBegin {
+    /// The key is new (or expired) and now locked by the caller, who must
+    /// [`complete`](IdempotencyStore::complete) or [`release`](IdempotencyStore::release) it.
+    Acquired,
+    /// Another request with the key is still being processed.
+    InProgress,
+    /// The key was used for a request with a different fingerprint.
+    Mismatch,
+    /// The key's request completed with this response.
+    Replay(StoredResponse),
+}
+
+/// Backend holding idempotency records.
+///
+/// Implementations must make [`begin`](Self::begin) atomic per key, so that concurrent
+/// requests on any replica sharing the store cannot both acquire it.
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Look up `key` and lock it for `lock_ttl` if it is free.
+    ///
+    /// # Errors
+    /// Returns an error if the record cannot be read or written.
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lock_ttl: Duration,
+    ) -> anyhow::Result<IdempotencyBegin>;
+
+    /// Store the response of the request that acquired `key`, to be replayed for `ttl`.
+    ///
+    /// # Errors
+    /// Returns an error if the record cannot be written.
+    async fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+        ttl: Duration,
+    ) -> anyhow::Result<()>;
+
+    /// Unlock `key` without storing a response, so that a retry executes again.
+    ///
+    /// # Errors
+    /// Returns an error if the record cannot be removed.
+    async fn release(&self, key: &str, fingerprint: &str) -> anyhow::Result<()>;
+}
+
+/// `now + ttl` in nanoseconds since the Unix epoch.
+fn expiry(now: i64, ttl: Duration) -> i64 {
+    now.saturating_add(i64::try_from(ttl.as_nanos()).unwrap_or(i64::MAX))
+}
+
+/// Number of records above which expired ones are dropped before adding another.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Record {
+    fingerprint: String,
+    response: Option<StoredResponse>,
+    expires_at: i64,
+}
+
+/// Process-local records; each replica only knows the keys it has seen.
+#[derive(Default)]
+pub struct InMemoryIdempotencyStore {
+    records: DashMap<String, Record>,
+}
+
+impl InMemoryIdempotencyStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lock_ttl: Duration,
+    ) -> anyhow::Result<IdempotencyBegin> {
+        let now = now_nanos();
+        if self.records.len() >= PRUNE_THRESHOLD {
+            self.records.retain(|_, r| r.expires_at > now);
+        }
+
+        let locked = Record {
+            fingerprint: fingerprint.to_owned(),
+            response: None,
+            expires_at: expiry(now, lock_ttl),
+        };
+        match self.records.entry(key.to_owned()) {
+            Entry::Occupied(mut entry) => {
+                let record = entry.get();
+                if record.expires_at <= now {
+                    entry.insert(locked);
+                    Ok(IdempotencyBegin::Acquired)
+                } else if record.fingerprint != fingerprint {
+                    Ok(IdempotencyBegin::Mismatch)
+                } else if let Some(response) = &record.response {
+                    Ok(IdempotencyBegin::Replay(response.clone()))
+                } else {
+                    Ok(IdempotencyBegin::InProgress)
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(locked);
+                Ok(IdempotencyBegin::Acquired)
+            }
+        }
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+        ttl: Duration,
+    ) -> anyhow::Result<()> {
+        if let Some(mut record) = self.records.get_mut(key)
+            && record.fingerprint == fingerprint
+            && record.response.is_none()
+        {
+            record.response = Some(response);
+            record.expires_at = expiry(now_nanos(), ttl);
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str, fingerprint: &str) -> anyhow::Result<()> {
+        self.records.remove_if(key, |_, r| {
+            r.fingerprint == fingerprint && r.response.is_none()
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    const LOCK: Duration = Duration::from_secs(60);
+    const TTL: Duration = Duration::from_secs(3600);
+
+    fn response() -> StoredResponse {
+        StoredResponse {
+            status: 201,
+            headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+            body: b"{\"id\":1}".to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn in_memory_store_locks_then_replays() {
+        let store = InMemoryIdempotencyStore::new();
+
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::Acquired
+        );
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::InProgress
+        );
+        assert_eq!(
+            store.begin("k", "other", LOCK).await.unwrap(),
+            IdempotencyBegin::Mismatch
+        );
+
+        store.complete("k", "f", response(), TTL).await.unwrap();
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::Replay(response())
+        );
+        assert_eq!(
+            store.begin("k", "other", LOCK).await.unwrap(),
+            IdempotencyBegin::Mismatch
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_store_release_and_expiry_free_the_key() {
+        let store = InMemoryIdempotencyStore::new();
+
+        store.begin("k", "f", LOCK).await.unwrap();
+        store.release("k", "f").await.unwrap();
+        assert_eq!(
+            store.begin("k", "f", LOCK).await.unwrap(),
+            IdempotencyBegin::Acquired
+        );
+
+        store.begin("expired", "f", Duration::ZERO).await.unwrap();
+        assert_eq!(
+            store.begin("expired", "other", LOCK).await.unwrap(),
+            IdempotencyBegin::Acquired
+        );
+    }
+}
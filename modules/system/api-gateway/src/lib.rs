@@ -13,9 +13,17 @@ mod assets;
 mod config;
 mod cors;
 pub mod error;
+pub mod idempotency_store;
 pub mod middleware;
+pub mod rate_limit_store;
 mod router_cache;
+pub mod tls;
 mod web;
 
 // === RE-EXPORTS ===
-pub use config::{ApiGatewayConfig, CorsConfig};
+pub use config::{
+    ApiGatewayConfig, ClientAuthConfig, ClientCertIdentity, CompressionAlgorithm,
+    CompressionConfig, CorsConfig, EtagConfig, IdempotencyBackend, IdempotencyConfig,
+    RateLimitBackend, RateLimitConfig, RateLimitKeyPart, RateLimitOverride, SniCertConfig,
+    TlsConfig,
+};
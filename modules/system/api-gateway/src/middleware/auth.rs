@@ -139,6 +139,51 @@ impl GatewayRoutePolicy {
 pub struct AuthState {
     pub authn_client: Arc<dyn AuthNResolverClient>,
     pub route_policy: GatewayRoutePolicy,
+    /// Security contexts of mTLS client certificates, by certificate subject
+    pub client_cert_contexts: Arc<HashMap<String, SecurityContext>>,
+}
+
+/// Build the security contexts of the client certificate subjects configured for mTLS.
+///
+/// # Errors
+///
+/// Returns an error if a configured identity cannot be turned into a `SecurityContext`.
+pub fn client_cert_contexts(
+    cfg: &crate::config::ApiGatewayConfig,
+) -> Result<HashMap<String, SecurityContext>, anyhow::Error> {
+    let Some(client_auth) = cfg.tls.as_ref().and_then(|tls| tls.client_auth.as_ref()) else {
+        return Ok(HashMap::new());
+    };
+    client_auth
+        .subjects
+        .iter()
+        .map(|(subject, identity)| {
+            let mut builder = SecurityContext::builder()
+                .subject_id(identity.subject_id)
+                .subject_tenant_id(identity.tenant_id)
+                .token_scopes(identity.scopes.clone());
+            if let Some(subject_type) = &identity.subject_type {
+                builder = builder.subject_type(subject_type);
+            }
+            Ok((subject.clone(), builder.build()?))
+        })
+        .collect()
+}
+
+/// Security context of the request's verified client certificate, if its subject is mapped.
+fn client_cert_context<'a>(
+    state: &'a AuthState,
+    req: &axum::extract::Request,
+) -> Option<&'a SecurityContext> {
+    let cert = req.extensions().get::<crate::tls::ClientCertificate>()?;
+    let ctx = state.client_cert_contexts.get(cert.subject());
+    if ctx.is_none() {
+        tracing::debug!(
+            subject = cert.subject(),
+            "Client certificate subject is not mapped"
+        );
+    }
+    ctx
 }
 
 /// Helper to build `GatewayRoutePolicy` from operation requirements.
@@ -193,7 +238,8 @@ pub fn build_route_policy(
 /// 1. Skips CORS preflight requests
 /// 2. Resolves the route's auth requirement via `GatewayRoutePolicy`
 /// 3. For public routes: inserts anonymous `SecurityContext`
-/// 4. For required routes: extracts bearer token, calls `AuthN` Resolver, inserts `SecurityContext`
+/// 4. For required routes: extracts bearer token, calls `AuthN` Resolver, inserts `SecurityContext`;
+///    without a bearer token, a mapped mTLS client certificate provides the `SecurityContext`
 pub async fn authn_middleware(
     axum::extract::State(state): axum::extract::State<AuthState>,
     mut req: axum::extract::Request,
@@ -213,6 +259,11 @@ pub async fn authn_middleware(
         }
         AuthRequirement::Required => {
             let Some(token) = extract_bearer_token(req.headers()) else {
+                if let Some(ctx) = client_cert_context(&state, &req) {
+                    let ctx = ctx.clone();
+                    req.extensions_mut().insert(ctx);
+                    return next.run(req).await;
+                }
                 return Problem::new(
                     axum::http::StatusCode::UNAUTHORIZED,
                     "Unauthorized",
@@ -0,0 +1,78 @@
+//! Negotiated response compression with per-operation opt-out
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use http::{Extensions, HeaderMap, Method, StatusCode, Version};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tower_http::compression::CompressionLayer;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+
+use modkit::api::OperationSpec;
+
+use crate::config::{CompressionAlgorithm, CompressionConfig};
+
+/// Operations registered with `disable_compression()`, by (method, path)
+pub type CompressionOptOutSet = Arc<HashSet<(Method, String)>>;
+
+/// Response extension that keeps the compression layer from encoding a response.
+#[derive(Clone, Copy, Debug)]
+struct CompressionDisabled;
+
+/// Build the set of operations that opted out of compression
+#[must_use]
+pub fn build_compression_opt_out_set(specs: &[OperationSpec]) -> CompressionOptOutSet {
+    Arc::new(
+        specs
+            .iter()
+            .filter(|spec| spec.disable_compression)
+            .map(|spec| (spec.method.clone(), spec.path.clone()))
+            .collect(),
+    )
+}
+
+/// Build the compression layer for `cfg`.
+///
+/// On top of the size threshold, responses of opted-out operations and the content types
+/// skipped by tower-http's default predicate (SSE, gRPC, images) stay uncompressed.
+#[must_use]
+pub fn build_compression_layer(
+    cfg: &CompressionConfig,
+) -> CompressionLayer<impl Predicate + use<>> {
+    let predicate = SizeAbove::new(cfg.min_size_bytes)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE)
+        .and(
+            |_: StatusCode, _: Version, _: &HeaderMap, extensions: &Extensions| {
+                extensions.get::<CompressionDisabled>().is_none()
+            },
+        );
+    let enabled = |algorithm| cfg.algorithms.contains(&algorithm);
+
+    CompressionLayer::new()
+        .gzip(enabled(CompressionAlgorithm::Gzip))
+        .br(enabled(CompressionAlgorithm::Br))
+        .zstd(enabled(CompressionAlgorithm::Zstd))
+        .no_deflate()
+        .compress_when(predicate)
+}
+
+/// Mark responses of opted-out operations; must run inside the compression layer.
+pub async fn compression_opt_out_middleware(
+    opt_out: CompressionOptOutSet,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+
+    let mut res = next.run(req).await;
+    if opt_out.contains(&(method, path)) {
+        res.extensions_mut().insert(CompressionDisabled);
+    }
+    res
+}
@@ -0,0 +1,162 @@
+//! `Deprecation`/`Sunset`/`Link` headers for deprecated operations and `410 Gone` once
+//! their sunset date has passed
+use axum::extract::Request;
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use modkit::api::{DeprecationSpec, OperationSpec, Problem};
+
+/// Response header announcing deprecation (RFC 9745)
+pub const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+
+/// Response header announcing the sunset date (RFC 8594)
+pub const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Start (00:00 UTC) of `date`
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Lifecycle headers of one operation, rendered once at startup
+struct Lifecycle {
+    sunset: Option<NaiveDate>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl Lifecycle {
+    fn new(path: &str, spec: &DeprecationSpec) -> Self {
+        let mut headers = Vec::new();
+        if let Some(since) = spec.deprecated_since {
+            // Structured-field date: `@` followed by Unix seconds
+            let value = format!("@{}", start_of_day(since).timestamp());
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.push((DEPRECATION, value));
+            }
+        }
+        if let Some(sunset) = spec.sunset {
+            let value = start_of_day(sunset)
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string();
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.push((SUNSET, value));
+            }
+        }
+        if let Some(link) = &spec.link {
+            if let Ok(value) = HeaderValue::from_str(&format!("<{link}>; rel=\"deprecation\"")) {
+                headers.push((header::LINK, value));
+            } else {
+                tracing::warn!(path, link, "Ignoring invalid deprecation link");
+            }
+        }
+        Self {
+            sunset: spec.sunset,
+            headers,
+        }
+    }
+
+    fn is_sunset(&self, now: DateTime<Utc>) -> bool {
+        self.sunset.is_some_and(|date| now >= start_of_day(date))
+    }
+}
+
+/// Deprecated operations by (method, path)
+#[derive(Clone)]
+pub struct DeprecationMap {
+    routes: Arc<HashMap<(Method, String), Lifecycle>>,
+}
+
+impl DeprecationMap {
+    /// Collect the operations of `specs` that declare a deprecation schedule
+    #[must_use]
+    pub fn from_specs(specs: &[OperationSpec]) -> Self {
+        Self {
+            routes: Arc::new(
+                specs
+                    .iter()
+                    .filter_map(|spec| {
+                        let lifecycle = Lifecycle::new(&spec.path, spec.deprecation.as_ref()?);
+                        Some(((spec.method.clone(), spec.path.clone()), lifecycle))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Whether any operation is deprecated, i.e. whether the middleware is needed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+/// Announce the deprecation of deprecated operations on every response, and answer
+/// `410 Gone` (without calling the handler) from the sunset date on.
+pub async fn deprecation_middleware(map: DeprecationMap, req: Request, next: Next) -> Response {
+    let path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+    let Some(lifecycle) = map.routes.get(&(req.method().clone(), path)) else {
+        return next.run(req).await;
+    };
+
+    let mut res = if lifecycle.is_sunset(Utc::now()) {
+        let date = lifecycle.sunset.map(|d| d.to_string()).unwrap_or_default();
+        Problem::new(
+            StatusCode::GONE,
+            "Gone",
+            format!("This operation was retired on {date}"),
+        )
+        .into_response()
+    } else {
+        next.run(req).await
+    };
+    for (name, value) in &lifecycle.headers {
+        res.headers_mut().append(name.clone(), value.clone());
+    }
+    res
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn header<'a>(lifecycle: &'a Lifecycle, name: &HeaderName) -> Option<&'a str> {
+        lifecycle
+            .headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.to_str().ok())
+    }
+
+    #[test]
+    fn lifecycle_headers_use_rfc_formats() {
+        let lifecycle = Lifecycle::new(
+            "/tests/v1/items",
+            &DeprecationSpec {
+                deprecated_since: NaiveDate::from_ymd_opt(2026, 1, 1),
+                sunset: NaiveDate::from_ymd_opt(2026, 7, 1),
+                link: Some("https://example.com/migrate".to_owned()),
+            },
+        );
+
+        assert_eq!(header(&lifecycle, &DEPRECATION), Some("@1767225600"));
+        assert_eq!(
+            header(&lifecycle, &SUNSET),
+            Some("Wed, 01 Jul 2026 00:00:00 GMT")
+        );
+        assert_eq!(
+            header(&lifecycle, &header::LINK),
+            Some("<https://example.com/migrate>; rel=\"deprecation\"")
+        );
+
+        let sunset = start_of_day(NaiveDate::from_ymd_opt(2026, 7, 1).unwrap());
+        assert!(!lifecycle.is_sunset(sunset - chrono::Duration::seconds(1)));
+        assert!(lifecycle.is_sunset(sunset));
+    }
+}
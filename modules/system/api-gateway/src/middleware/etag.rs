@@ -0,0 +1,144 @@
+//! Automatic weak `ETag`s and `If-None-Match` handling for GET operations
+use axum::body::{Body, HttpBody};
+use axum::extract::Request;
+use axum::http::header::InvalidHeaderValue;
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use modkit::api::OperationSpec;
+
+use crate::config::EtagConfig;
+
+/// GET operations that get `ETag`s, by path, plus the body size limit
+#[derive(Clone)]
+pub struct EtagRoutes {
+    paths: Arc<HashSet<String>>,
+    max_body_bytes: u64,
+}
+
+impl EtagRoutes {
+    /// Collect the GET operations from `specs`
+    #[must_use]
+    pub fn from_specs(specs: &[OperationSpec], cfg: &EtagConfig) -> Self {
+        Self {
+            paths: Arc::new(
+                specs
+                    .iter()
+                    .filter(|spec| spec.method == Method::GET)
+                    .map(|spec| spec.path.clone())
+                    .collect(),
+            ),
+            max_body_bytes: u64::try_from(cfg.max_body_bytes).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Weak entity tag of a response body.
+///
+/// Weak because compression upstream may change the bytes on the wire while the
+/// representation stays the same.
+fn weak_etag(body: &[u8]) -> Result<HeaderValue, InvalidHeaderValue> {
+    let hash = xxhash_rust::xxh3::xxh3_64(body);
+    HeaderValue::from_str(&format!("W/\"{hash:016x}\""))
+}
+
+/// Weak comparison of `etag` against an `If-None-Match` header value (RFC 9110 §13.1.2).
+fn if_none_match_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(candidates) = if_none_match.to_str() else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_owned();
+    let etag = etag.to_str().map(opaque).unwrap_or_default();
+    candidates
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// Tag successful GET responses of known operations and answer matching conditional
+/// requests with `304 Not Modified`.
+///
+/// Only buffers bodies of known length up to the configured limit, so streams pass
+/// through untouched; responses that already carry an `ETag` are left as they are.
+pub async fn etag_middleware(routes: EtagRoutes, req: Request, next: Next) -> Response {
+    if req.method() != Method::GET {
+        return next.run(req).await;
+    }
+    let path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+    if !routes.paths.contains(&path) {
+        return next.run(req).await;
+    }
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+
+    let res = next.run(req).await;
+    if res.status() != StatusCode::OK || res.headers().contains_key(header::ETAG) {
+        return res;
+    }
+    match res.body().size_hint().exact() {
+        Some(len) if len <= routes.max_body_bytes => {}
+        _ => return res,
+    }
+
+    let (mut parts, body) = res.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = %e, path = %path, "Failed to buffer response body for ETag");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Ok(etag) = weak_etag(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    if if_none_match.is_some_and(|v| if_none_match_matches(&v, &etag)) {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        parts.headers.remove(header::CONTENT_TYPE);
+        parts.headers.insert(header::ETAG, etag);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    parts.headers.insert(header::ETAG, etag);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let etag = weak_etag(b"{\"items\":[]}").unwrap();
+        let tag = etag.to_str().unwrap().to_owned();
+        let strong = tag.trim_start_matches("W/").to_owned();
+
+        for header in [
+            tag.clone(),
+            strong,
+            "*".to_owned(),
+            format!("W/\"0000000000000000\", {tag}"),
+        ] {
+            assert!(
+                if_none_match_matches(&HeaderValue::from_str(&header).unwrap(), &etag),
+                "{header} should match {tag}"
+            );
+        }
+        assert!(!if_none_match_matches(
+            &HeaderValue::from_static("W/\"0000000000000000\""),
+            &etag
+        ));
+    }
+
+    #[test]
+    fn etag_depends_on_body() {
+        assert_eq!(weak_etag(b"a").unwrap(), weak_etag(b"a").unwrap());
+        assert_ne!(weak_etag(b"a").unwrap(), weak_etag(b"b").unwrap());
+    }
+}
@@ -0,0 +1,253 @@
+//! `Idempotency-Key` handling for operations registered with `idempotent()`
+use axum::body::{Body, HttpBody};
+use axum::extract::Request;
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode, header::HeaderMap};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use xxhash_rust::xxh3::Xxh3;
+
+use modkit::api::{OperationSpec, Problem};
+use modkit_security::SecurityContext;
+
+use crate::config::IdempotencyConfig;
+use crate::idempotency_store::{IdempotencyBegin, IdempotencyStore, StoredResponse};
+
+/// Request header carrying the client's key
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Response header set on replayed responses
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Longest accepted `Idempotency-Key`
+const MAX_KEY_LEN: usize = 255;
+
+/// Idempotent operations by (method, path), plus the store and its settings
+#[derive(Clone)]
+pub struct IdempotencyMap {
+    routes: Arc<HashSet<(Method, String)>>,
+    store: Arc<dyn IdempotencyStore>,
+    ttl: Duration,
+    lock_ttl: Duration,
+    max_response_bytes: u64,
+}
+
+impl IdempotencyMap {
+    /// Collect the operations registered with `idempotent()` from `specs`
+    #[must_use]
+    pub fn from_specs(
+        specs: &[OperationSpec],
+        cfg: &IdempotencyConfig,
+        store: Arc<dyn IdempotencyStore>,
+    ) -> Self {
+        Self {
+            routes: Arc::new(
+                specs
+                    .iter()
+                    .filter(|spec| spec.idempotency_key)
+                    .map(|spec| (spec.method.clone(), spec.path.clone()))
+                    .collect(),
+            ),
+            store,
+            ttl: Duration::from_secs(cfg.ttl_secs),
+            lock_ttl: Duration::from_secs(cfg.lock_ttl_secs),
+            max_response_bytes: u64::try_from(cfg.max_response_bytes).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Hash of everything that must be identical for a retry to be replayed.
+fn fingerprint(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Xxh3::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    format!("{:032x}", hasher.digest128())
+}
+
+/// Headers kept with a stored response; the request id belongs to each retry.
+fn stored_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    let request_id = crate::middleware::request_id::header();
+    headers
+        .iter()
+        .filter(|(name, _)| **name != request_id)
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+        })
+        .collect()
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut res = Response::new(Body::from(stored.body));
+    *res.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = res.headers_mut();
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    res
+}
+
+fn problem(status: StatusCode, title: &str, detail: &str) -> Response {
+    Problem::new(status, title, detail).into_response()
+}
+
+/// Caller-scoped key of `req`, `None` if the request is not subject to deduplication.
+fn scoped_key(map: &IdempotencyMap, req: &Request) -> Option<Result<String, Box<Response>>> {
+    let path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+    if !map.routes.contains(&(req.method().clone(), path)) {
+        return None;
+    }
+    let client_key = req.headers().get(IDEMPOTENCY_KEY)?;
+    let client_key = match client_key.to_str() {
+        Ok(k) if !k.is_empty() && k.len() <= MAX_KEY_LEN => k,
+        _ => {
+            return Some(Err(Box::new(problem(
+                StatusCode::BAD_REQUEST,
+                "Invalid Idempotency-Key",
+                &format!("Idempotency-Key must be 1 to {MAX_KEY_LEN} visible ASCII characters"),
+            ))));
+        }
+    };
+    Some(Ok(match req.extensions().get::<SecurityContext>() {
+        Some(ctx) => format!(
+            "{}:{}:{client_key}",
+            ctx.subject_tenant_id(),
+            ctx.subject_id()
+        ),
+        None => format!("-:-:{client_key}"),
+    }))
+}
+
+/// Buffer the request body and compute the request's fingerprint.
+async fn fingerprint_request(req: Request) -> Result<(Request, String), Box<Response>> {
+    let (parts, body) = req.into_parts();
+    let body = axum::body::to_bytes(body, usize::MAX).await.map_err(|e| {
+        Box::new(problem(
+            StatusCode::BAD_REQUEST,
+            "Bad Request",
+            &format!("Failed to read request body: {e}"),
+        ))
+    })?;
+    let fingerprint = fingerprint(&parts.method, parts.uri.path(), &body);
+    Ok((Request::from_parts(parts, Body::from(body)), fingerprint))
+}
+
+/// Response for a request whose key was not acquired.
+fn not_acquired(outcome: IdempotencyBegin) -> Response {
+    match outcome {
+        IdempotencyBegin::Replay(stored) => replay(stored),
+        IdempotencyBegin::Mismatch => problem(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Idempotency-Key Reused",
+            "This Idempotency-Key was already used for a different request",
+        ),
+        IdempotencyBegin::InProgress | IdempotencyBegin::Acquired => problem(
+            StatusCode::CONFLICT,
+            "Request In Progress",
+            "A request with this Idempotency-Key is still being processed",
+        ),
+    }
+}
+
+async fn release(map: &IdempotencyMap, key: &str, fingerprint: &str) {
+    if let Err(e) = map.store.release(key, fingerprint).await {
+        tracing::warn!(error = %e, "Failed to release Idempotency-Key");
+    }
+}
+
+async fn complete(map: &IdempotencyMap, key: &str, fingerprint: &str, stored: StoredResponse) {
+    if let Err(e) = map.store.complete(key, fingerprint, stored, map.ttl).await {
+        tracing::warn!(error = %e, "Failed to store response for Idempotency-Key");
+    }
+}
+
+/// Store `res` for the acquired key, or release the key if `res` must not be replayed.
+async fn finish(map: &IdempotencyMap, key: &str, fingerprint: &str, res: Response) -> Response {
+    let storable = !res.status().is_server_error()
+        && res
+            .body()
+            .size_hint()
+            .exact()
+            .is_some_and(|len| len <= map.max_response_bytes);
+    if !storable {
+        release(map, key, fingerprint).await;
+        return res;
+    }
+
+    let (parts, body) = res.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to buffer response body for Idempotency-Key");
+            release(map, key, fingerprint).await;
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: stored_headers(&parts.headers),
+        body: body.to_vec(),
+    };
+    complete(map, key, fingerprint, stored).await;
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Run `req` at most once per (tenant, subject, `Idempotency-Key`).
+///
+/// Requests without the header pass through. The first request with a key is executed
+/// and its response stored; retries with the same key and body get that response back
+/// with `Idempotent-Replayed: true`. A retry while the first request is still running
+/// gets 409, and reusing the key for a different request gets 422. Server errors and
+/// responses too large to store are not kept, so such requests can be retried.
+pub async fn idempotency_middleware(map: IdempotencyMap, req: Request, next: Next) -> Response {
+    let key = match scoped_key(&map, &req) {
+        None => return next.run(req).await,
+        Some(Err(res)) => return *res,
+        Some(Ok(key)) => key,
+    };
+    let (req, fingerprint) = match fingerprint_request(req).await {
+        Ok(v) => v,
+        Err(res) => return *res,
+    };
+
+    match map.store.begin(&key, &fingerprint, map.lock_ttl).await {
+        Ok(IdempotencyBegin::Acquired) => {}
+        Ok(outcome) => return not_acquired(outcome),
+        Err(e) => {
+            // Fail open: an unavailable store must not take the operation down.
+            tracing::warn!(error = %e, "Idempotency store failed; request not deduplicated");
+            return next.run(req).await;
+        }
+    }
+
+    let res = next.run(req).await;
+    finish(&map, &key, &fingerprint, res).await
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_covers_route_and_body() {
+        let base = fingerprint(&Method::POST, "/v1/items", b"{}");
+        assert_eq!(base, fingerprint(&Method::POST, "/v1/items", b"{}"));
+        assert_ne!(base, fingerprint(&Method::POST, "/v1/items", b"{\"a\":1}"));
+        assert_ne!(base, fingerprint(&Method::POST, "/v1/other", b"{}"));
+        assert_ne!(base, fingerprint(&Method::PUT, "/v1/items", b"{}"));
+    }
+}
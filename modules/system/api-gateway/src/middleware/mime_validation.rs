@@ -159,6 +159,10 @@ mod tests {
             authenticated: false,
             is_public: false,
             license_requirement: None,
+            disable_compression: false,
+            idempotency_key: false,
+            version: None,
+            deprecation: None,
             rate_limit: None,
             allowed_request_content_types: Some(vec!["multipart/form-data", "application/pdf"]),
             vendor_extensions: VendorExtensions::default(),
@@ -1,4 +1,8 @@
 pub mod auth;
+pub mod compression;
+pub mod deprecation;
+pub mod etag;
+pub mod idempotency;
 pub mod license_validation;
 pub mod mime_validation;
 pub mod rate_limit;
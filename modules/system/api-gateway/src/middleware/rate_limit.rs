@@ -1,46 +1,74 @@
-use crate::config::ApiGatewayConfig;
-use anyhow::{Context, Result, anyhow};
+use crate::config::{ApiGatewayConfig, RateLimitKeyPart};
+use crate::rate_limit_store::{
+    InMemoryRateLimitStore, RateLimitDecision, RateLimitQuota, RateLimitStore,
+};
+use anyhow::{Context, Result, anyhow, ensure};
+use axum::extract::ConnectInfo;
 use axum::http::{HeaderValue, Method, StatusCode, header};
 use axum::{
     extract::Request,
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use governor::clock::Clock;
-use governor::middleware::StateInformationMiddleware;
-use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use modkit_security::SecurityContext;
 use std::collections::HashMap;
-use std::num::NonZeroU32;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
+use uuid::Uuid;
 
 type RateLimitKey = (Method, String);
-type BucketMap = Arc<HashMap<RateLimitKey, Arc<BucketMapEntry>>>;
+type LimitMap = Arc<HashMap<RateLimitKey, Arc<Limit>>>;
 type InflightMap = Arc<HashMap<RateLimitKey, Arc<Semaphore>>>;
 
-#[derive(Default, Clone)]
+/// Per-route limits, applied in two stages around auth.
+///
+/// The pre-auth stage rejects floods before the `AuthN` resolver is called. When
+/// `rate_limit.key` has no `tenant` or `subject`, it enforces the configured buckets on its
+/// own. Otherwise it keeps a coarse bucket per route and peer IP (see
+/// [`RateLimitConfig`](crate::config::RateLimitConfig)) and the tenant/subject buckets are
+/// applied after auth.
+#[derive(Clone)]
 pub struct RateLimiterMap {
-    buckets: BucketMap,
+    limits: LimitMap,
+    pre_auth_limits: LimitMap,
     inflight: InflightMap,
+    /// Key of the post-auth buckets; empty when the key has no tenant or subject.
+    key_parts: Arc<[RateLimitKeyPart]>,
+    pre_auth_key_parts: Arc<[RateLimitKeyPart]>,
+    tenant_overrides: Arc<HashMap<Uuid, Arc<Limit>>>,
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl Default for RateLimiterMap {
+    fn default() -> Self {
+        Self {
+            limits: LimitMap::default(),
+            pre_auth_limits: LimitMap::default(),
+            inflight: InflightMap::default(),
+            key_parts: Arc::new([]),
+            pre_auth_key_parts: Arc::new([]),
+            tenant_overrides: Arc::default(),
+            store: Arc::new(InMemoryRateLimitStore::new()),
+        }
+    }
 }
 
-struct BucketMapEntry {
-    bucket: DefaultDirectRateLimiter<StateInformationMiddleware>,
+struct Limit {
+    quota: RateLimitQuota,
     policy: HeaderValue,
     burst: HeaderValue,
 }
 
-impl BucketMapEntry {
+impl Limit {
     pub fn new(rps: u32, burst: u32) -> Result<Self> {
-        let bucket = RateLimiter::direct(
-            Quota::per_second(NonZeroU32::new(rps).with_context(|| anyhow!("rps is zero"))?)
-                .allow_burst(NonZeroU32::new(burst).with_context(|| anyhow!("burst is zero"))?),
-        )
-        .with_middleware::<StateInformationMiddleware>();
+        ensure!(rps > 0, "rps is zero");
+        ensure!(burst > 0, "burst is zero");
         let policy = HeaderValue::from_str(&format!("\"burst\";q={burst};w={rps}"))
             .context("Failed to create rate limit policy")?;
         Ok(Self {
-            bucket,
+            quota: RateLimitQuota { rps, burst },
             policy,
             burst: burst.into(),
         })
@@ -49,14 +77,39 @@ impl BucketMapEntry {
 
 impl RateLimiterMap {
     /// # Errors
-    /// Returns an error if any rate limit spec is 0.
+    /// Returns an error if any rate limit spec, tenant override or the pre-auth multiplier
+    /// is 0, or if tenant overrides are configured without `tenant` in the bucket key.
     pub fn from_specs(
         specs: &Vec<modkit::api::OperationSpec>,
         cfg: &ApiGatewayConfig,
+        store: Arc<dyn RateLimitStore>,
     ) -> Result<Self> {
-        let mut buckets = HashMap::new();
+        let rl = &cfg.rate_limit;
+        ensure!(
+            rl.tenant_overrides.is_empty() || rl.key.contains(&RateLimitKeyPart::Tenant),
+            "rate_limit.tenant_overrides requires `tenant` in rate_limit.key"
+        );
+        ensure!(
+            rl.pre_auth_multiplier > 0,
+            "rate_limit.pre_auth_multiplier is zero"
+        );
+        let tenant_overrides: HashMap<Uuid, Arc<Limit>> = rl
+            .tenant_overrides
+            .iter()
+            .map(|(tenant, o)| {
+                Limit::new(o.rps, o.burst)
+                    .with_context(|| anyhow!("RateLimit override for tenant {tenant} invalid"))
+                    .map(|limit| (*tenant, Arc::new(limit)))
+            })
+            .collect::<Result<_>>()?;
+        let keyed_by_caller = rl
+            .key
+            .iter()
+            .any(|part| matches!(part, RateLimitKeyPart::Tenant | RateLimitKeyPart::Subject));
+
+        let mut limits = HashMap::new();
+        let mut pre_auth_limits = HashMap::new();
         let mut inflight = HashMap::new();
-        // TODO: Add support for per-route rate limiting
         for spec in specs {
             let (rps, burst, max_in_flight) = spec.rate_limit.as_ref().map_or(
                 (
@@ -67,53 +120,151 @@ impl RateLimiterMap {
                 |r| (r.rps, r.burst, r.in_flight),
             );
             let key = (spec.method.clone(), spec.path.clone());
-            buckets.insert(
-                key.clone(),
-                Arc::new(
-                    BucketMapEntry::new(rps, burst)
-                        .with_context(|| anyhow!("RateLimit spec invalid {spec:?} invalid"))?,
-                ),
+            let limit = Arc::new(
+                Limit::new(rps, burst)
+                    .with_context(|| anyhow!("RateLimit spec invalid {spec:?} invalid"))?,
             );
+            let pre_auth_limit = if keyed_by_caller {
+                let (rps, burst) = tenant_overrides
+                    .values()
+                    .fold((rps, burst), |(rps, burst), o| {
+                        (rps.max(o.quota.rps), burst.max(o.quota.burst))
+                    });
+                let factor = rl.pre_auth_multiplier;
+                Arc::new(Limit::new(
+                    rps.saturating_mul(factor),
+                    burst.saturating_mul(factor),
+                )?)
+            } else {
+                limit.clone()
+            };
+            limits.insert(key.clone(), limit);
+            pre_auth_limits.insert(key.clone(), pre_auth_limit);
             inflight.insert(key, Arc::new(Semaphore::new(max_in_flight as usize)));
         }
+
+        let (key_parts, pre_auth_key_parts) = if keyed_by_caller {
+            (
+                rl.key.iter().copied().collect(),
+                Arc::from([RateLimitKeyPart::Ip]),
+            )
+        } else {
+            (Arc::from([]), rl.key.iter().copied().collect())
+        };
         Ok(Self {
-            buckets: Arc::new(buckets),
+            limits: Arc::new(limits),
+            pre_auth_limits: Arc::new(pre_auth_limits),
             inflight: Arc::new(inflight),
+            key_parts,
+            pre_auth_key_parts,
+            tenant_overrides: Arc::new(tenant_overrides),
+            store,
         })
     }
+
+    /// Bucket key of a request: the route plus the given request attributes.
+    ///
+    /// Attributes that are unavailable (no `SecurityContext`, no peer address) map to
+    /// `-`, so such requests share one bucket per route.
+    fn bucket_key(
+        (method, path): &RateLimitKey,
+        parts: &[RateLimitKeyPart],
+        ctx: Option<&SecurityContext>,
+        req: &Request,
+    ) -> String {
+        let mut key = format!("{method} {path}");
+        for part in parts {
+            let value = match part {
+                RateLimitKeyPart::Tenant => ctx.map(|c| c.subject_tenant_id().to_string()),
+                RateLimitKeyPart::Subject => ctx.map(|c| c.subject_id().to_string()),
+                RateLimitKeyPart::Ip => req
+                    .extensions()
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip().to_string()),
+            };
+            let name = match part {
+                RateLimitKeyPart::Tenant => "tenant",
+                RateLimitKeyPart::Subject => "subject",
+                RateLimitKeyPart::Ip => "ip",
+            };
+            _ = write!(key, " {name}={}", value.as_deref().unwrap_or("-"));
+        }
+        key
+    }
+
+    /// Take one request from `bucket`; returns the response to send if it is limited.
+    ///
+    /// Rate-limit headers are added to the request for the handler to see.
+    async fn acquire(&self, bucket: &str, limit: &Limit, req: &mut Request) -> Option<Response> {
+        let headers = req.headers_mut();
+        headers.insert("RateLimit-Policy", limit.policy.clone());
+        match self.store.acquire(bucket, limit.quota).await {
+            Ok(RateLimitDecision::Allowed { remaining }) => {
+                headers.insert("RateLimit-Limit", limit.burst.clone());
+                headers.insert("RateLimit-Limit-Remaining", remaining.into());
+                headers.insert("X-RateLimit-Limit", limit.burst.clone());
+                headers.insert("X-RateLimit-Remaining", remaining.into());
+                None
+            }
+            Ok(RateLimitDecision::Limited { retry_after }) => {
+                let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                Some(
+                    (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [(header::RETRY_AFTER, HeaderValue::from(secs))],
+                    )
+                        .into_response(),
+                )
+            }
+            Err(e) => {
+                // Fail open: an unavailable store must not take the whole API down.
+                tracing::warn!(error = %e, bucket = %bucket, "Rate-limit store failed; request not limited");
+                None
+            }
+        }
+    }
 }
 
-// TODO: Use tower-governor instead of own implementation (upd: https://github.com/benwis/tower-governor/issues/59 )
-pub async fn rate_limit_middleware(map: RateLimiterMap, mut req: Request, next: Next) -> Response {
-    let method = req.method().clone();
+fn route_key(req: &Request) -> RateLimitKey {
     // Use MatchedPath extension (set by Axum router) for accurate route matching
     let path = req
         .extensions()
         .get::<axum::extract::MatchedPath>()
         .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
-    let key = (method, path);
+    (req.method().clone(), path)
+}
 
-    if let Some(bucker_map_entry) = map.buckets.get(&key) {
-        let headers = req.headers_mut();
-        headers.insert("RateLimit-Policy", bucker_map_entry.policy.clone());
-        match bucker_map_entry.bucket.check() {
-            Ok(state) => {
-                headers.insert("RateLimit-Limit", bucker_map_entry.burst.clone());
-                headers.insert(
-                    "RateLimit-Limit-Remaining",
-                    state.remaining_burst_capacity().into(),
-                );
-                headers.insert("X-RateLimit-Limit", bucker_map_entry.burst.clone());
-                headers.insert(
-                    "X-RateLimit-Remaining",
-                    state.remaining_burst_capacity().into(),
-                );
-            }
-            Err(not_until) => {
-                let wait = not_until.wait_time_from(bucker_map_entry.bucket.clock().now());
-                headers.insert(header::RETRY_AFTER, wait.as_secs().into());
-                return StatusCode::TOO_MANY_REQUESTS.into_response();
-            }
+// Runs outside auth, so unauthenticated floods are rejected before the AuthN resolver.
+pub async fn pre_auth_rate_limit_middleware(
+    map: RateLimiterMap,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let key = route_key(&req);
+    if let Some(limit) = map.pre_auth_limits.get(&key) {
+        let bucket = RateLimiterMap::bucket_key(&key, &map.pre_auth_key_parts, None, &req);
+        if let Some(limited) = map.acquire(&bucket, limit, &mut req).await {
+            return limited;
+        }
+    }
+    next.run(req).await
+}
+
+// Runs inside auth so that bucket keys and overrides can use the `SecurityContext`.
+pub async fn rate_limit_middleware(map: RateLimiterMap, mut req: Request, next: Next) -> Response {
+    let key = route_key(&req);
+
+    if !map.key_parts.is_empty()
+        && let Some(route_limit) = map.limits.get(&key)
+    {
+        let ctx = req.extensions().get::<SecurityContext>();
+        let limit = ctx
+            .and_then(|c| map.tenant_overrides.get(&c.subject_tenant_id()))
+            .unwrap_or(route_limit)
+            .clone();
+        let bucket = RateLimiterMap::bucket_key(&key, &map.key_parts, ctx, &req);
+        if let Some(limited) = map.acquire(&bucket, &limit, &mut req).await {
+            return limited;
         }
     }
 
@@ -131,3 +282,182 @@ pub async fn rate_limit_middleware(map: RateLimiterMap, mut req: Request, next:
 
     next.run(req).await
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::config::RateLimitOverride;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::middleware::from_fn;
+    use axum::routing::get;
+    use modkit::api::OperationSpec;
+    use modkit::api::operation_builder::{RateLimitSpec, VendorExtensions};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    fn map(cfg: &ApiGatewayConfig) -> Result<RateLimiterMap> {
+        RateLimiterMap::from_specs(&Vec::new(), cfg, Arc::new(InMemoryRateLimitStore::new()))
+    }
+
+    fn spec(rps: u32, burst: u32) -> OperationSpec {
+        OperationSpec {
+            method: Method::GET,
+            path: "/v1/items".to_owned(),
+            operation_id: None,
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![],
+            request_body: None,
+            responses: vec![],
+            handler_id: "test".to_owned(),
+            authenticated: false,
+            is_public: false,
+            license_requirement: None,
+            disable_compression: false,
+            idempotency_key: false,
+            version: None,
+            deprecation: None,
+            rate_limit: Some(RateLimitSpec {
+                rps,
+                burst,
+                in_flight: 64,
+            }),
+            allowed_request_content_types: None,
+            vendor_extensions: VendorExtensions::default(),
+        }
+    }
+
+    fn ctx(tenant: Uuid, subject: Uuid) -> SecurityContext {
+        SecurityContext::builder()
+            .subject_id(subject)
+            .subject_tenant_id(tenant)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn bucket_key_includes_configured_parts() {
+        let mut cfg = ApiGatewayConfig::default();
+        cfg.rate_limit.key = vec![
+            RateLimitKeyPart::Tenant,
+            RateLimitKeyPart::Subject,
+            RateLimitKeyPart::Ip,
+        ];
+        let map = map(&cfg).unwrap();
+        let (tenant, subject) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut req = Request::new(Body::empty());
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([203, 0, 113, 7], 4000))));
+        let route = (Method::GET, "/v1/items".to_owned());
+
+        assert_eq!(
+            RateLimiterMap::bucket_key(&route, &map.key_parts, Some(&ctx(tenant, subject)), &req),
+            format!("GET /v1/items tenant={tenant} subject={subject} ip=203.0.113.7")
+        );
+        assert_eq!(
+            RateLimiterMap::bucket_key(&route, &map.key_parts, None, &Request::new(Body::empty())),
+            "GET /v1/items tenant=- subject=- ip=-"
+        );
+        assert_eq!(
+            RateLimiterMap::bucket_key(&route, &map.pre_auth_key_parts, None, &req),
+            "GET /v1/items ip=203.0.113.7"
+        );
+    }
+
+    #[test]
+    fn tenant_overrides_require_tenant_key() {
+        let mut cfg = ApiGatewayConfig::default();
+        cfg.rate_limit
+            .tenant_overrides
+            .insert(Uuid::new_v4(), RateLimitOverride { rps: 10, burst: 10 });
+        assert!(map(&cfg).is_err());
+
+        cfg.rate_limit.key = vec![RateLimitKeyPart::Tenant];
+        assert!(map(&cfg).is_ok());
+    }
+
+    #[test]
+    fn caller_keys_split_limits_around_auth() {
+        let store: Arc<dyn RateLimitStore> = Arc::new(InMemoryRateLimitStore::new());
+        let route = (Method::GET, "/v1/items".to_owned());
+        let mut cfg = ApiGatewayConfig::default();
+
+        cfg.rate_limit.key = vec![RateLimitKeyPart::Ip];
+        let map = RateLimiterMap::from_specs(&vec![spec(1, 2)], &cfg, store.clone()).unwrap();
+        assert!(map.key_parts.is_empty());
+        assert_eq!(&*map.pre_auth_key_parts, &[RateLimitKeyPart::Ip]);
+        assert_eq!(
+            map.pre_auth_limits[&route].quota,
+            RateLimitQuota { rps: 1, burst: 2 }
+        );
+
+        cfg.rate_limit.key = vec![RateLimitKeyPart::Tenant];
+        cfg.rate_limit
+            .tenant_overrides
+            .insert(Uuid::new_v4(), RateLimitOverride { rps: 5, burst: 1 });
+        let map = RateLimiterMap::from_specs(&vec![spec(1, 2)], &cfg, store).unwrap();
+        assert_eq!(&*map.key_parts, &[RateLimitKeyPart::Tenant]);
+        assert_eq!(&*map.pre_auth_key_parts, &[RateLimitKeyPart::Ip]);
+        assert_eq!(
+            map.pre_auth_limits[&route].quota,
+            RateLimitQuota { rps: 50, burst: 20 }
+        );
+    }
+
+    #[tokio::test]
+    async fn coarse_limit_rejects_before_auth() {
+        let mut cfg = ApiGatewayConfig::default();
+        cfg.rate_limit.key = vec![RateLimitKeyPart::Tenant];
+        cfg.rate_limit.pre_auth_multiplier = 3;
+        let map = RateLimiterMap::from_specs(
+            &vec![spec(1, 1)],
+            &cfg,
+            Arc::new(InMemoryRateLimitStore::new()),
+        )
+        .unwrap();
+
+        let auth_calls = Arc::new(AtomicUsize::new(0));
+        let calls = auth_calls.clone();
+        let (inner, outer) = (map.clone(), map);
+        let router = Router::new()
+            .route("/v1/items", get(|| async { "ok" }))
+            .layer(from_fn(move |req, next| {
+                rate_limit_middleware(inner.clone(), req, next)
+            }))
+            .layer(from_fn(move |mut req: Request, next: Next| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let tenant = req.headers()["x-tenant"].to_str().unwrap().parse().unwrap();
+                req.extensions_mut().insert(ctx(tenant, Uuid::new_v4()));
+                next.run(req)
+            }))
+            .layer(from_fn(move |req, next| {
+                pre_auth_rate_limit_middleware(outer.clone(), req, next)
+            }));
+        let call = |tenant: Uuid| {
+            let router = router.clone();
+            async move {
+                let req = Request::builder()
+                    .uri("/v1/items")
+                    .header("x-tenant", tenant.to_string())
+                    .body(Body::empty())
+                    .unwrap();
+                router.oneshot(req).await.unwrap().status()
+            }
+        };
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // Each tenant has its own bucket after auth...
+        assert_eq!(call(a).await, StatusCode::OK);
+        assert_eq!(call(a).await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(call(b).await, StatusCode::OK);
+        assert_eq!(auth_calls.load(Ordering::SeqCst), 3);
+
+        // ...while the shared address is cut off before auth runs.
+        assert_eq!(call(b).await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(auth_calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -27,8 +27,14 @@ use tracing::debug;
 
 use authn_resolver_sdk::AuthNResolverClient;
 
-use crate::config::ApiGatewayConfig;
+use crate::config::{ApiGatewayConfig, IdempotencyBackend, RateLimitBackend};
+use crate::idempotency_store::{
+    DbIdempotencyStore, IdempotencyMigration, IdempotencyStore, InMemoryIdempotencyStore,
+};
 use crate::middleware::auth;
+use crate::rate_limit_store::{
+    DbRateLimitStore, InMemoryRateLimitStore, RateLimitMigration, RateLimitStore,
+};
 use modkit_security::SecurityContext;
 use modkit_security::constants::{DEFAULT_SUBJECT_ID, DEFAULT_TENANT_ID};
 
@@ -40,7 +46,7 @@ use crate::web;
 /// typed operation specs to emit a single `OpenAPI` document.
 #[modkit::module(
 	name = "api-gateway",
-	capabilities = [rest_host, rest, stateful],
+	capabilities = [rest_host, rest, stateful, db],
     deps = ["grpc-hub", "authn-resolver"],
 	lifecycle(entry = "serve", stop_timeout = "30s", await_ready)
 )]
@@ -55,6 +61,10 @@ pub struct ApiGateway {
     pub(crate) final_router: Mutex<Option<axum::Router>>,
     // AuthN Resolver client (resolved during init, None when auth_disabled)
     pub(crate) authn_client: Mutex<Option<Arc<dyn AuthNResolverClient>>>,
+    // Rate-limit bucket state (in-memory unless the database backend is configured)
+    pub(crate) rate_limit_store: Mutex<Arc<dyn RateLimitStore>>,
+    // Idempotency records (in-memory unless the database backend is configured)
+    pub(crate) idempotency_store: Mutex<Arc<dyn IdempotencyStore>>,
 
     // Duplicate detection (per (method, path) and per handler id)
     pub(crate) registered_routes: DashMap<(Method, String), ()>,
@@ -70,6 +80,8 @@ impl Default for ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            rate_limit_store: Mutex::new(Arc::new(InMemoryRateLimitStore::new())),
+            idempotency_store: Mutex::new(Arc::new(InMemoryIdempotencyStore::new())),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -87,6 +99,8 @@ impl ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            rate_limit_store: Mutex::new(Arc::new(InMemoryRateLimitStore::new())),
+            idempotency_store: Mutex::new(Arc::new(InMemoryIdempotencyStore::new())),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -127,6 +141,9 @@ impl ApiGateway {
         public_routes.insert((Method::GET, "/healthz".to_owned()));
         public_routes.insert((Method::GET, "/docs".to_owned()));
         public_routes.insert((Method::GET, "/openapi.json".to_owned()));
+        for version in self.openapi_registry.api_versions() {
+            public_routes.insert((Method::GET, format!("/openapi/{version}.json")));
+        }
 
         for spec in &self.openapi_registry.operation_specs {
             let spec = spec.value();
@@ -158,7 +175,7 @@ impl ApiGateway {
         Ok(route_policy)
     }
 
-    /// Apply all middleware layers to a router (request ID, tracing, timeout, body limit, CORS, rate limiting, error mapping, auth)
+    /// Apply all middleware layers to a router (request ID, tracing, timeout, body limit, CORS, error mapping, auth, rate limiting)
     pub(crate) fn apply_middleware_stack(
         &self,
         mut router: Router,
@@ -172,14 +189,16 @@ impl ApiGateway {
         //
         // Desired request execution order (outermost -> innermost):
         // SetRequestId -> PropagateRequestId -> Trace -> push_req_id_to_extensions
-        // -> Timeout -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth -> Router
+        // -> Timeout -> BodyLimit -> CORS -> Compression -> MIME validation -> ErrorMapping -> Auth
+        // -> RateLimit -> License validation -> Idempotency -> ETag -> Deprecation -> Router
         //
         // Therefore we must add layers in the reverse order (innermost -> outermost) below.
         // Due future refactoring, this order must be maintained.
 
         let config = self.get_cached_config();
 
-        // Collect specs once; used by MIME validation + rate limiting maps.
+        // Collect specs once; used by MIME validation, rate limiting, license, compression,
+        // idempotency, ETag and deprecation maps.
         let specs: Vec<_> = self
             .openapi_registry
             .operation_specs
@@ -187,7 +206,43 @@ impl ApiGateway {
             .map(|e| e.value().clone())
             .collect();
 
-        // 11) License validation
+        // 15) Deprecation headers / 410 for sunset operations (innermost: only routed, admitted
+        // requests of deprecated operations are affected)
+        let deprecation_map = middleware::deprecation::DeprecationMap::from_specs(&specs);
+        if !deprecation_map.is_empty() {
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let map = deprecation_map.clone();
+                    middleware::deprecation::deprecation_middleware(map, req, next)
+                },
+            ));
+        }
+
+        // 14) ETag / conditional GET (inner to compression, so it hashes the handler's body)
+        if config.etag.enabled {
+            let etag_routes = middleware::etag::EtagRoutes::from_specs(&specs, &config.etag);
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let map = etag_routes.clone();
+                    middleware::etag::etag_middleware(map, req, next)
+                },
+            ));
+        }
+
+        // 13) Idempotency-Key (inner to auth and limits: keyed by caller, retries still count)
+        let idempotency_map = middleware::idempotency::IdempotencyMap::from_specs(
+            &specs,
+            &config.idempotency,
+            self.idempotency_store.lock().clone(),
+        );
+        router = router.layer(from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let map = idempotency_map.clone();
+                middleware::idempotency::idempotency_middleware(map, req, next)
+            },
+        ));
+
+        // 12) License validation
         let license_map = middleware::license_validation::LicenseRequirementMap::from_specs(&specs);
         router = router.layer(from_fn(
             move |req: axum::extract::Request, next: axum::middleware::Next| {
@@ -196,6 +251,21 @@ impl ApiGateway {
             },
         ));
 
+        // 11) Tenant/subject rate-limit buckets & in-flight limits (inner to auth so buckets
+        // can be keyed by the caller's tenant or subject)
+        let rate_map = middleware::rate_limit::RateLimiterMap::from_specs(
+            &specs,
+            &config,
+            self.rate_limit_store.lock().clone(),
+        )?;
+        let pre_auth_rate_map = rate_map.clone();
+        router = router.layer(from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let map = rate_map.clone();
+                middleware::rate_limit::rate_limit_middleware(map, req, next)
+            },
+        ));
+
         // 10) Auth
         if config.auth_disabled {
             // Build security contexts for compatibility during migration
@@ -222,6 +292,7 @@ impl ApiGateway {
             let auth_state = auth::AuthState {
                 authn_client: client,
                 route_policy,
+                client_cert_contexts: Arc::new(auth::client_cert_contexts(&config)?),
             };
             router = router.layer(from_fn_with_state(auth_state, auth::authn_middleware));
         } else {
@@ -231,19 +302,18 @@ impl ApiGateway {
             ));
         }
 
-        // 9) Error mapping (outer to auth so it can translate auth/handler errors)
-        router = router.layer(from_fn(modkit::api::error_layer::error_mapping_middleware));
-
-        // 8) Per-route rate limiting & in-flight limits
-        let rate_map = middleware::rate_limit::RateLimiterMap::from_specs(&specs, &config)?;
+        // 9b) Route/IP rate limiting (outer to auth so floods never reach the AuthN resolver)
         router = router.layer(from_fn(
             move |req: axum::extract::Request, next: axum::middleware::Next| {
-                let map = rate_map.clone();
-                middleware::rate_limit::rate_limit_middleware(map, req, next)
+                let map = pre_auth_rate_map.clone();
+                middleware::rate_limit::pre_auth_rate_limit_middleware(map, req, next)
             },
         ));
 
-        // 7) MIME type validation
+        // 9) Error mapping (outer to auth so it can translate auth/handler errors)
+        router = router.layer(from_fn(modkit::api::error_layer::error_mapping_middleware));
+
+        // 8) MIME type validation
         let mime_map = middleware::mime_validation::build_mime_validation_map(&specs);
         router = router.layer(from_fn(
             move |req: axum::extract::Request, next: axum::middleware::Next| {
@@ -252,6 +322,20 @@ impl ApiGateway {
             },
         ));
 
+        // 7) Compression (the opt-out marker must be inner to the compression layer)
+        if config.compression.enabled {
+            let opt_out = middleware::compression::build_compression_opt_out_set(&specs);
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let opt_out = opt_out.clone();
+                    middleware::compression::compression_opt_out_middleware(opt_out, req, next)
+                },
+            ));
+            router = router.layer(middleware::compression::build_compression_layer(
+                &config.compression,
+            ));
+        }
+
         // 6) CORS (must be outer to auth/limits so OPTIONS preflight short-circuits)
         if config.cors_enabled {
             router = router.layer(crate::cors::build_cors_layer(&config));
@@ -374,13 +458,50 @@ impl ApiGateway {
     /// # Errors
     /// Returns an error if `OpenAPI` specification building fails.
     pub fn build_openapi(&self) -> Result<utoipa::openapi::OpenApi> {
+        self.openapi_registry.build_openapi(&self.openapi_info())
+    }
+
+    /// Build the `OpenAPI` document of a single API version (e.g. `v1`)
+    ///
+    /// # Errors
+    /// Returns an error if the `OpenAPI` document cannot be built.
+    pub fn build_openapi_for_version(&self, version: &str) -> Result<utoipa::openapi::OpenApi> {
+        self.openapi_registry
+            .build_openapi_for_version(&self.openapi_info(), version)
+    }
+
+    fn openapi_info(&self) -> modkit::api::OpenApiInfo {
         let config = self.get_cached_config();
-        let info = modkit::api::OpenApiInfo {
-            title: config.openapi.title.clone(),
-            version: config.openapi.version.clone(),
+        modkit::api::OpenApiInfo {
+            title: config.openapi.title,
+            version: config.openapi.version,
             description: config.openapi.description,
-        };
-        self.openapi_registry.build_openapi(&info)
+        }
+    }
+
+    /// Handler serving a prebuilt `OpenAPI` document as JSON
+    fn openapi_handler(
+        doc: Arc<utoipa::openapi::OpenApi>,
+    ) -> impl Fn() -> std::future::Ready<axum::response::Response> + Clone + Send + Sync + 'static
+    {
+        use axum::{http::header, response::IntoResponse};
+        move || {
+            let res = match serde_json::to_string_pretty(doc.as_ref()) {
+                Ok(json_string) => (
+                    [
+                        (header::CONTENT_TYPE, "application/json"),
+                        (header::CACHE_CONTROL, "no-store"),
+                    ],
+                    json_string,
+                )
+                    .into_response(),
+                Err(e) => {
+                    tracing::error!("Failed to serialize OpenAPI doc: {}", e);
+                    http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+            };
+            std::future::ready(res)
+        }
     }
 
     /// Parse bind address from configuration string.
@@ -415,12 +536,24 @@ impl ApiGateway {
         let cfg = self.get_cached_config();
         let addr = Self::parse_bind_address(&cfg.bind_addr)?;
         let router = self.get_or_build_router()?;
+        // Load certificates before binding, so that bad TLS files fail startup
+        let tls = cfg
+            .tls
+            .as_ref()
+            .map(crate::tls::TlsServerConfig::load)
+            .transpose()?;
 
         // Bind the socket, only now consider the service "ready"
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        tracing::info!("HTTP server bound on {}", addr);
         ready.notify(); // Starting -> Running
 
+        if let Some(tls) = tls {
+            tracing::info!("HTTPS server bound on {}", addr);
+            crate::tls::serve(listener, router, Arc::new(tls), cancel).await;
+            return Ok(());
+        }
+        tracing::info!("HTTP server bound on {}", addr);
+
         // Graceful shutdown on cancel
         let shutdown = {
             let cancel = cancel.clone();
@@ -430,10 +563,15 @@ impl ApiGateway {
             }
         };
 
-        axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown)
-            .await
-            .map_err(|e| anyhow::anyhow!(e))
+        // Connect info exposes the peer address to handlers (e.g. IP-scoped
+        // rate limits in the outbound gateway).
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))
     }
 
     /// Check if `handler_id` is already registered (returns true if duplicate)
@@ -493,32 +631,18 @@ impl ApiGateway {
         let openapi_doc = Arc::new(self.build_openapi()?);
 
         router = router
-            .route(
-                "/openapi.json",
-                get({
-                    use axum::{http::header, response::IntoResponse};
-                    let doc = openapi_doc;
-                    move || async move {
-                        let json_string = match serde_json::to_string_pretty(doc.as_ref()) {
-                            Ok(json) => json,
-                            Err(e) => {
-                                tracing::error!("Failed to serialize OpenAPI doc: {}", e);
-                                return (http::StatusCode::INTERNAL_SERVER_ERROR).into_response();
-                            }
-                        };
-                        (
-                            [
-                                (header::CONTENT_TYPE, "application/json"),
-                                (header::CACHE_CONTROL, "no-store"),
-                            ],
-                            json_string,
-                        )
-                            .into_response()
-                    }
-                }),
-            )
+            .route("/openapi.json", get(Self::openapi_handler(openapi_doc)))
             .route("/docs", get(web::serve_docs));
 
+        // One document per API version, e.g. /openapi/v1.json
+        for version in self.openapi_registry.api_versions() {
+            let doc = Arc::new(self.build_openapi_for_version(&version)?);
+            router = router.route(
+                &format!("/openapi/{version}.json"),
+                get(Self::openapi_handler(doc)),
+            );
+        }
+
         #[cfg(feature = "embed_elements")]
         {
             router = router.route(
@@ -531,6 +655,12 @@ impl ApiGateway {
     }
 }
 
+impl modkit::contracts::DatabaseCapability for ApiGateway {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        vec![Box::new(RateLimitMigration), Box::new(IdempotencyMigration)]
+    }
+}
+
 // Manual implementation of Module trait with config loading
 #[async_trait]
 impl modkit::Module for ApiGateway {
@@ -544,6 +674,22 @@ impl modkit::Module for ApiGateway {
             self.config.load()
         );
 
+        if cfg.rate_limit.backend == RateLimitBackend::Database {
+            let db = ctx.db_required()?;
+            if db.db_engine() == "mysql" {
+                return Err(anyhow::anyhow!(
+                    "`rate_limit.backend: database` needs PostgreSQL or SQLite; \
+                     MySQL has no `UPDATE ... RETURNING`, use the `memory` backend"
+                ));
+            }
+            *self.rate_limit_store.lock() = Arc::new(DbRateLimitStore::new(db));
+            tracing::info!("Rate-limit state is shared through the module database");
+        }
+        if cfg.idempotency.backend == IdempotencyBackend::Database {
+            *self.idempotency_store.lock() = Arc::new(DbIdempotencyStore::new(ctx.db_required()?));
+            tracing::info!("Idempotency records are shared through the module database");
+        }
+
         if cfg.auth_disabled {
             tracing::info!(
                 tenant_id = %DEFAULT_TENANT_ID,
@@ -0,0 +1,308 @@
+//! Database-backed bucket state shared by all gateway replicas.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use modkit_db::secure::{
+    AccessScope, DbConn, ScopeError, SecureDeleteExt, SecureEntityExt, SecureInsertExt,
+    SecureUpdateExt,
+};
+use modkit_db::{DBProvider, DbError};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{Condition, DbErr, EntityTrait, QueryFilter, Set};
+use sea_orm_migration::prelude::{
+    Alias, ColumnDef, MigrationName, MigrationTrait, SchemaManager, Table,
+};
+
+use super::{RateLimitDecision, RateLimitQuota, RateLimitStore, now_nanos};
+
+const TABLE: &str = "api_gateway_rate_limits";
+
+/// Minimum time between sweeps of expired buckets.
+const PRUNE_INTERVAL_NANOS: i64 = 60 * 1_000_000_000;
+
+mod entity {
+    use modkit_db_macros::Scopable;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "api_gateway_rate_limits")]
+    #[secure(unrestricted)]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub bucket: String,
+        /// Theoretical arrival time of the next request, in nanoseconds since the Unix epoch.
+        pub tat: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use entity::{ActiveModel, Column, Entity};
+
+/// Creates the `api_gateway_rate_limits` table used by [`DbRateLimitStore`].
+pub struct RateLimitMigration;
+
+impl MigrationName for RateLimitMigration {
+    fn name(&self) -> &'static str {
+        "m001_api_gateway_rate_limits"
+    }
+}
+
+#[async_trait]
+impl MigrationTrait for RateLimitMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(TABLE))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new("bucket"))
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("tat")).big_integer().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new(TABLE))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Bucket state kept in the module's database, so a limit holds across replicas.
+///
+/// Each bucket is one row whose TAT advances in a single conditional `UPDATE ... RETURNING`,
+/// so concurrent requests serialize on the row instead of retrying. `RETURNING` needs
+/// `PostgreSQL` or `SQLite`, so the module refuses this store on `MySQL` at init. Replica
+/// clocks are expected to be synchronized; skew shifts refills by the same amount.
+pub struct DbRateLimitStore {
+    db: DBProvider<DbError>,
+    last_prune: AtomicI64,
+}
+
+impl DbRateLimitStore {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self {
+            db,
+            last_prune: AtomicI64::new(0),
+        }
+    }
+
+    /// Insert a new bucket; `false` if another request created it first.
+    async fn insert(conn: &DbConn<'_>, key: &str, tat: i64) -> Result<bool, ScopeError> {
+        let am = ActiveModel {
+            bucket: Set(key.to_owned()),
+            tat: Set(tat),
+        };
+        let inserted = Entity::insert(am)
+            .secure()
+            .scope_unchecked(&AccessScope::allow_all())?
+            .on_conflict_raw(OnConflict::column(Column::Bucket).do_nothing().to_owned())
+            .exec(conn)
+            .await;
+        match inserted {
+            Ok(_) => Ok(true),
+            Err(ScopeError::Db(DbErr::RecordNotInserted)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Take one request from an existing bucket that has room; returns its new TAT.
+    ///
+    /// `None` if the bucket is full or does not exist.
+    async fn take(
+        conn: &DbConn<'_>,
+        key: &str,
+        quota: RateLimitQuota,
+        now: i64,
+    ) -> Result<Option<i64>, ScopeError> {
+        let interval = quota.interval_nanos();
+        // GCRA admits while `max(tat, now) + interval - now` stays within the burst.
+        let latest = now.saturating_add(quota.capacity_nanos() - interval);
+        let next = Expr::case(
+            Expr::col(Column::Tat).gt(now),
+            Expr::col(Column::Tat).add(interval),
+        )
+        .finally(Expr::value(now.saturating_add(interval)));
+        let updated = Entity::update_many()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .col_expr(Column::Tat, next.into())
+            .filter(
+                Condition::all()
+                    .add(Expr::col(Column::Bucket).eq(key))
+                    .add(Expr::col(Column::Tat).lte(latest)),
+            )
+            .exec_with_returning(conn)
+            .await?;
+        Ok(updated.first().map(|m| m.tat))
+    }
+
+    /// Drop buckets whose TAT has passed, at most once per [`PRUNE_INTERVAL_NANOS`].
+    ///
+    /// Such buckets are full again, so removing them does not change any decision.
+    async fn prune(&self, conn: &DbConn<'_>, now: i64) -> Result<(), ScopeError> {
+        let last = self.last_prune.load(Ordering::Relaxed);
+        if now - last < PRUNE_INTERVAL_NANOS
+            || self
+                .last_prune
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            return Ok(());
+        }
+        Entity::delete_many()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Condition::all().add(Expr::col(Column::Tat).lt(now)))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for DbRateLimitStore {
+    async fn acquire(&self, key: &str, quota: RateLimitQuota) -> anyhow::Result<RateLimitDecision> {
+        let conn = self.db.conn()?;
+        let now = now_nanos();
+        if let Some(tat) = Self::take(&conn, key, quota, now).await? {
+            return Ok(RateLimitDecision::Allowed {
+                remaining: quota.remaining(tat, now),
+            });
+        }
+
+        // Full or missing; the stored TAT tells which, and how long a full bucket needs.
+        let current = Entity::find()
+            .filter(Condition::all().add(Expr::col(Column::Bucket).eq(key)))
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .one(&conn)
+            .await?
+            .map(|m| m.tat);
+        match (current, quota.admit(current, now)) {
+            (_, Err(retry_after)) => return Ok(RateLimitDecision::Limited { retry_after }),
+            (None, Ok((next, remaining))) => {
+                self.prune(&conn, now).await?;
+                if Self::insert(&conn, key, next).await? {
+                    return Ok(RateLimitDecision::Allowed { remaining });
+                }
+            }
+            // Refilled or created by another request since the update; retry it below.
+            (Some(_), Ok(_)) => {}
+        }
+
+        // The bucket exists now, so this update alone decides.
+        Ok(match Self::take(&conn, key, quota, now).await? {
+            Some(tat) => RateLimitDecision::Allowed {
+                remaining: quota.remaining(tat, now),
+            },
+            None => RateLimitDecision::Limited {
+                retry_after: Duration::from_nanos(
+                    u64::try_from(quota.interval_nanos()).unwrap_or(0),
+                ),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+
+    async fn store() -> DbRateLimitStore {
+        let dsn = format!(
+            "sqlite:file:memdb_rate_limits_{}?mode=memory&cache=shared",
+            uuid::Uuid::new_v4()
+        );
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+        let db = connect_db(&dsn, opts).await.expect("connect");
+        run_migrations_for_testing(&db, vec![Box::new(RateLimitMigration)])
+            .await
+            .expect("migrate");
+        DbRateLimitStore::new(DBProvider::new(db))
+    }
+
+    #[tokio::test]
+    async fn enforces_burst_per_bucket() {
+        let store = store().await;
+        let quota = RateLimitQuota { rps: 1, burst: 2 };
+
+        assert_eq!(
+            store.acquire("a", quota).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 1 }
+        );
+        assert_eq!(
+            store.acquire("a", quota).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 0 }
+        );
+        assert!(matches!(
+            store.acquire("a", quota).await.unwrap(),
+            RateLimitDecision::Limited { .. }
+        ));
+        assert_eq!(
+            store.acquire("b", quota).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 1 }
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_take_exactly_the_burst() {
+        let store = std::sync::Arc::new(store().await);
+        let quota = RateLimitQuota { rps: 1, burst: 16 };
+
+        let tasks: Vec<_> = (0..32)
+            .map(|_| {
+                let store = store.clone();
+                tokio::spawn(async move { store.acquire("hot", quota).await.unwrap() })
+            })
+            .collect();
+        let mut allowed = 0;
+        for task in tasks {
+            if matches!(task.await.unwrap(), RateLimitDecision::Allowed { .. }) {
+                allowed += 1;
+            }
+        }
+        assert_eq!(allowed, 16);
+    }
+
+    #[tokio::test]
+    async fn stores_sharing_a_database_share_buckets() {
+        let first = store().await;
+        let second = DbRateLimitStore::new(first.db.clone());
+        let quota = RateLimitQuota { rps: 1, burst: 1 };
+
+        assert!(matches!(
+            first.acquire("shared", quota).await.unwrap(),
+            RateLimitDecision::Allowed { .. }
+        ));
+        assert!(matches!(
+            second.acquire("shared", quota).await.unwrap(),
+            RateLimitDecision::Limited { .. }
+        ));
+    }
+}
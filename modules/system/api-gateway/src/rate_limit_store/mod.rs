@@ -0,0 +1,201 @@
+//! Shared state for rate-limit buckets.
+//!
+//! Buckets follow the generic cell rate algorithm (GCRA): the only state per bucket is
+//! the theoretical arrival time (TAT) of the next request, which makes it cheap to keep
+//! in a shared store. A bucket with `rps` and `burst` admits `burst` requests at once and
+//! then one request every `1/rps` seconds, like a token bucket.
+
+mod db;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub use db::{DbRateLimitStore, RateLimitMigration};
+
+/// Steady-state rate and burst capacity of a bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    pub rps: u32,
+    pub burst: u32,
+}
+
+/// Outcome of [`RateLimitStore::acquire`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    /// The request may proceed; `remaining` more requests fit in the burst right now.
+    Allowed { remaining: u32 },
+    /// The bucket is empty; a request may succeed after `retry_after`.
+    Limited { retry_after: Duration },
+}
+
+/// Backend holding the TAT of every bucket.
+///
+/// Implementations must apply [`RateLimitQuota::admit`] atomically per key, so that
+/// concurrent requests on any replica sharing the store cannot both take the last slot.
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Take one request from the bucket `key`.
+    ///
+    /// # Errors
+    /// Returns an error if the bucket state cannot be read or written.
+    async fn acquire(&self, key: &str, quota: RateLimitQuota) -> anyhow::Result<RateLimitDecision>;
+}
+
+impl RateLimitQuota {
+    const fn interval_nanos(self) -> i64 {
+        // rps is validated to be non-zero when the limiter map is built.
+        1_000_000_000_i64.div_euclid(self.rps as i64)
+    }
+
+    /// One GCRA step at `now` for a bucket whose TAT is `tat` (`None` for a new bucket).
+    ///
+    /// Returns the new TAT and the remaining burst, or how long to wait before retrying.
+    /// Times are nanoseconds since the Unix epoch.
+    ///
+    /// # Errors
+    /// Returns the time until the bucket can admit a request if it is empty.
+    pub fn admit(self, tat: Option<i64>, now: i64) -> Result<(i64, u32), Duration> {
+        let next = tat
+            .map_or(now, |tat| tat.max(now))
+            .saturating_add(self.interval_nanos());
+        let used = next - now;
+        let capacity = self.capacity_nanos();
+        if used > capacity {
+            return Err(Duration::from_nanos(
+                u64::try_from(used - capacity).unwrap_or(0),
+            ));
+        }
+        Ok((next, self.remaining(next, now)))
+    }
+
+    /// Burst capacity expressed as time: how far a TAT may run ahead of now.
+    fn capacity_nanos(self) -> i64 {
+        self.interval_nanos().saturating_mul(i64::from(self.burst))
+    }
+
+    /// Requests that still fit in the burst at `now` for a bucket whose TAT is `tat`.
+    fn remaining(self, tat: i64, now: i64) -> u32 {
+        let free = self.capacity_nanos() - (tat - now).max(0);
+        u32::try_from(free.max(0).div_euclid(self.interval_nanos())).unwrap_or(u32::MAX)
+    }
+}
+
+impl RateLimitDecision {
+    fn from_admit(result: Result<(i64, u32), Duration>) -> Self {
+        match result {
+            Ok((_, remaining)) => Self::Allowed { remaining },
+            Err(retry_after) => Self::Limited { retry_after },
+        }
+    }
+}
+
+/// Current wall-clock time in nanoseconds since the Unix epoch.
+///
+/// Wall-clock time (rather than a monotonic clock) keeps TATs comparable across
+/// replicas sharing a store; their clocks are expected to be synchronized.
+pub(crate) fn now_nanos() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_nanos()).unwrap_or(i64::MAX))
+}
+
+/// Number of buckets above which expired ones are dropped before adding another.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Process-local bucket state; each replica enforces the limits on its own.
+#[derive(Default)]
+pub struct InMemoryRateLimitStore {
+    buckets: DashMap<String, i64>,
+}
+
+impl InMemoryRateLimitStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn acquire(&self, key: &str, quota: RateLimitQuota) -> anyhow::Result<RateLimitDecision> {
+        let now = now_nanos();
+        if let Some(mut tat) = self.buckets.get_mut(key) {
+            let result = quota.admit(Some(*tat), now);
+            if let Ok((next, _)) = result {
+                *tat = next;
+            }
+            return Ok(RateLimitDecision::from_admit(result));
+        }
+
+        if self.buckets.len() >= PRUNE_THRESHOLD {
+            // A bucket whose TAT has passed is full again, same as a missing one.
+            self.buckets.retain(|_, tat| *tat > now);
+        }
+        let mut tat = self.buckets.entry(key.to_owned()).or_insert(now);
+        let result = quota.admit(Some(*tat), now);
+        if let Ok((next, _)) = result {
+            *tat = next;
+        }
+        Ok(RateLimitDecision::from_admit(result))
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    #[test]
+    fn admits_burst_then_one_per_interval() {
+        let quota = RateLimitQuota { rps: 2, burst: 3 };
+        let now = 100 * SECOND;
+
+        let mut tat = None;
+        for expected_remaining in [2, 1, 0] {
+            let (next, remaining) = quota.admit(tat, now).expect("within burst");
+            assert_eq!(remaining, expected_remaining);
+            tat = Some(next);
+        }
+
+        let wait = quota.admit(tat, now).expect_err("burst exhausted");
+        assert_eq!(wait, Duration::from_millis(500));
+
+        let (_, remaining) = quota
+            .admit(tat, now + 500_000_000)
+            .expect("one interval later");
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn idle_bucket_refills_to_burst() {
+        let quota = RateLimitQuota { rps: 1, burst: 2 };
+        let now = 100 * SECOND;
+        let (tat, _) = quota.admit(None, now).unwrap();
+        let (tat, _) = quota.admit(Some(tat), now).unwrap();
+
+        let (_, remaining) = quota.admit(Some(tat), now + 10 * SECOND).unwrap();
+        assert_eq!(remaining, 1);
+    }
+
+    #[tokio::test]
+    async fn in_memory_store_keeps_buckets_apart() {
+        let store = InMemoryRateLimitStore::new();
+        let quota = RateLimitQuota { rps: 1, burst: 1 };
+
+        assert!(matches!(
+            store.acquire("a", quota).await.unwrap(),
+            RateLimitDecision::Allowed { remaining: 0 }
+        ));
+        assert!(matches!(
+            store.acquire("a", quota).await.unwrap(),
+            RateLimitDecision::Limited { .. }
+        ));
+        assert!(matches!(
+            store.acquire("b", quota).await.unwrap(),
+            RateLimitDecision::Allowed { .. }
+        ));
+    }
+}
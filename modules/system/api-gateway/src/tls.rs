@@ -0,0 +1,353 @@
+//! TLS termination for the gateway's listener.
+//!
+//! Certificates are chosen per connection by the server name the client sends (SNI), and the
+//! certificate files are polled so that rotated certificates are picked up by new connections
+//! without a restart. With `client_auth` configured, client certificates are verified against
+//! the configured CAs and the verified subject is exposed to the auth middleware as a
+//! [`ClientCertificate`] request extension.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use arc_swap::ArcSwap;
+use axum::Router;
+use axum::extract::ConnectInfo;
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::server::graceful::{GracefulShutdown, Watcher};
+use hyper_util::service::TowerToHyperService;
+use parking_lot::Mutex;
+use rustls::crypto::CryptoProvider;
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use rustls::{RootCertStore, ServerConfig, ServerConnection};
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+use x509_cert::Certificate;
+use x509_cert::der::Decode;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::config::{ClientAuthConfig, TlsConfig};
+
+/// Connections that do not complete the handshake in time are dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Verified client certificate of the connection a request arrived on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificate {
+    subject: String,
+}
+
+impl ClientCertificate {
+    /// Subject of the certificate in RFC 4514 form, e.g. `CN=billing,O=Example`
+    #[must_use]
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Certificate presented by the peer of `conn`, if any.
+    ///
+    /// rustls only completes the handshake after the certificate has been verified.
+    fn from_connection(conn: &ServerConnection) -> Option<Self> {
+        let end_entity = conn.peer_certificates()?.first()?;
+        match Certificate::from_der(end_entity) {
+            Ok(cert) => Some(Self {
+                subject: cert.tbs_certificate.subject.to_string(),
+            }),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to parse verified client certificate");
+                None
+            }
+        }
+    }
+}
+
+/// Certificate for the client's server name, falling back to the default one.
+#[derive(Debug)]
+struct SniResolver {
+    default: Arc<CertifiedKey>,
+    by_name: HashMap<String, Arc<CertifiedKey>>,
+}
+
+impl ResolvesServerCert for SniResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let key = client_hello
+            .server_name()
+            .and_then(|name| self.by_name.get(&name.to_ascii_lowercase()))
+            .unwrap_or(&self.default);
+        Some(Arc::clone(key))
+    }
+}
+
+/// Installed default provider, or aws-lc-rs without installing it globally.
+fn crypto_provider() -> Arc<CryptoProvider> {
+    CryptoProvider::get_default()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(rustls::crypto::aws_lc_rs::default_provider()))
+}
+
+fn load_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+        .with_context(|| format!("failed to read certificates from {}", path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificates found in {}", path.display());
+    }
+    Ok(certs)
+}
+
+fn load_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+    provider: &CryptoProvider,
+) -> anyhow::Result<Arc<CertifiedKey>> {
+    let certs = load_certs(cert_path)?;
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("failed to read private key from {}", key_path.display()))?;
+    let certified = CertifiedKey::from_der(certs, key, provider).with_context(|| {
+        format!(
+            "private key {} does not match certificate {}",
+            key_path.display(),
+            cert_path.display()
+        )
+    })?;
+    Ok(Arc::new(certified))
+}
+
+fn client_verifier(
+    cfg: &ClientAuthConfig,
+    provider: Arc<CryptoProvider>,
+) -> anyhow::Result<Arc<dyn rustls::server::danger::ClientCertVerifier>> {
+    let mut roots = RootCertStore::empty();
+    let (_, ignored) = roots.add_parsable_certificates(load_certs(&cfg.ca_path)?);
+    if ignored > 0 {
+        tracing::warn!(
+            ignored,
+            path = %cfg.ca_path.display(),
+            "Some client CA certificates could not be parsed"
+        );
+    }
+    let builder = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+    let builder = if cfg.required {
+        builder
+    } else {
+        builder.allow_unauthenticated()
+    };
+    builder
+        .build()
+        .context("failed to build client certificate verifier")
+}
+
+fn build_server_config(cfg: &TlsConfig) -> anyhow::Result<ServerConfig> {
+    let provider = crypto_provider();
+
+    let default = load_certified_key(&cfg.cert_path, &cfg.key_path, &provider)?;
+    let mut by_name = HashMap::new();
+    for sni in &cfg.sni {
+        let key = load_certified_key(&sni.cert_path, &sni.key_path, &provider)?;
+        for name in &sni.server_names {
+            by_name.insert(name.to_ascii_lowercase(), Arc::clone(&key));
+        }
+    }
+
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .context("failed to set TLS protocol versions")?;
+    let builder = match &cfg.client_auth {
+        Some(client_auth) => {
+            builder.with_client_cert_verifier(client_verifier(client_auth, provider)?)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let mut config = builder.with_cert_resolver(Arc::new(SniResolver { default, by_name }));
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// Hash of the contents of every file `cfg` refers to.
+fn files_fingerprint(cfg: &TlsConfig) -> std::io::Result<u64> {
+    let mut hasher = Xxh3::new();
+    let sni_paths = cfg
+        .sni
+        .iter()
+        .flat_map(|sni| [&sni.cert_path, &sni.key_path]);
+    let ca_path = cfg.client_auth.as_ref().map(|c| &c.ca_path);
+    for path in [&cfg.cert_path, &cfg.key_path]
+        .into_iter()
+        .chain(sni_paths)
+        .chain(ca_path)
+    {
+        hasher.update(&std::fs::read(path)?);
+    }
+    Ok(hasher.digest())
+}
+
+/// Current rustls configuration of the listener, rebuilt when its files change.
+pub struct TlsServerConfig {
+    cfg: TlsConfig,
+    current: ArcSwap<ServerConfig>,
+    fingerprint: Mutex<u64>,
+}
+
+impl TlsServerConfig {
+    /// Load the certificates, keys and client CAs referenced by `cfg`.
+    ///
+    /// # Errors
+    /// Returns an error if a file cannot be read or parsed, or a key does not match its certificate.
+    pub fn load(cfg: &TlsConfig) -> anyhow::Result<Self> {
+        let fingerprint = files_fingerprint(cfg).context("failed to read TLS files")?;
+        Ok(Self {
+            cfg: cfg.clone(),
+            current: ArcSwap::from_pointee(build_server_config(cfg)?),
+            fingerprint: Mutex::new(fingerprint),
+        })
+    }
+
+    /// Acceptor for a new connection, using the latest loaded configuration.
+    #[must_use]
+    pub fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.current.load_full())
+    }
+
+    /// Rebuild the configuration if any of its files changed; returns whether it was replaced.
+    ///
+    /// On error the previous configuration stays in use, so a half-written rotation does not
+    /// take the listener down; the next check picks up the completed files.
+    ///
+    /// # Errors
+    /// Returns an error if the changed files cannot be read or parsed.
+    pub fn reload_if_changed(&self) -> anyhow::Result<bool> {
+        let fingerprint = files_fingerprint(&self.cfg).context("failed to read TLS files")?;
+        if fingerprint == *self.fingerprint.lock() {
+            return Ok(false);
+        }
+        self.current
+            .store(Arc::new(build_server_config(&self.cfg)?));
+        *self.fingerprint.lock() = fingerprint;
+        Ok(true)
+    }
+
+    /// Poll the files every `reload_interval_secs` until `cancel` fires.
+    ///
+    /// Cognitive complexity is inflated by tracing macro expansion.
+    #[allow(clippy::cognitive_complexity)]
+    async fn watch(self: Arc<Self>, cancel: CancellationToken) {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.cfg.reload_interval_secs));
+        interval.tick().await;
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => return,
+                _ = interval.tick() => {}
+            }
+            match self.reload_if_changed() {
+                Ok(true) => tracing::info!("Reloaded TLS certificates"),
+                Ok(false) => {}
+                Err(e) => tracing::warn!(error = %e, "Failed to reload TLS certificates"),
+            }
+        }
+    }
+}
+
+/// Serve `router` over TLS on `listener` until `cancel` fires, then drain open connections.
+///
+/// Requests carry the peer address as `ConnectInfo<SocketAddr>`, like the plain HTTP server,
+/// and the verified client certificate (if any) as [`ClientCertificate`]. Accept, handshake
+/// and connection errors are logged; they affect only the connection concerned.
+pub async fn serve(
+    listener: TcpListener,
+    router: Router,
+    tls: Arc<TlsServerConfig>,
+    cancel: CancellationToken,
+) {
+    if tls.cfg.reload_interval_secs > 0 {
+        tokio::spawn(Arc::clone(&tls).watch(cancel.clone()));
+    }
+
+    let graceful = GracefulShutdown::new();
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => break,
+            accepted = accept(&listener) => if let Some((stream, peer)) = accepted {
+                tokio::spawn(serve_connection(
+                    tls.acceptor(),
+                    stream,
+                    peer,
+                    router.clone(),
+                    graceful.watcher(),
+                ));
+            },
+        }
+    }
+
+    tracing::info!("HTTPS server shutting down gracefully (cancellation)");
+    graceful.shutdown().await;
+}
+
+async fn accept(listener: &TcpListener) -> Option<(TcpStream, SocketAddr)> {
+    match listener.accept().await {
+        Ok(accepted) => Some(accepted),
+        Err(e) => {
+            // e.g. out of file descriptors; back off instead of spinning
+            tracing::warn!(error = %e, "Failed to accept connection");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            None
+        }
+    }
+}
+
+async fn handshake(
+    acceptor: TlsAcceptor,
+    stream: TcpStream,
+    peer: SocketAddr,
+) -> Option<TlsStream<TcpStream>> {
+    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+        Ok(Ok(stream)) => Some(stream),
+        Ok(Err(e)) => {
+            tracing::debug!(error = %e, %peer, "TLS handshake failed");
+            None
+        }
+        Err(_) => {
+            tracing::debug!(%peer, "TLS handshake timed out");
+            None
+        }
+    }
+}
+
+async fn serve_connection(
+    acceptor: TlsAcceptor,
+    stream: TcpStream,
+    peer: SocketAddr,
+    router: Router,
+    watcher: Watcher,
+) {
+    let Some(stream) = handshake(acceptor, stream, peer).await else {
+        return;
+    };
+
+    let client_cert = ClientCertificate::from_connection(stream.get_ref().1);
+    let service = router.map_request(move |mut req: axum::http::Request<Incoming>| {
+        req.extensions_mut().insert(ConnectInfo(peer));
+        if let Some(cert) = &client_cert {
+            req.extensions_mut().insert(cert.clone());
+        }
+        req
+    });
+
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let conn = builder
+        .serve_connection_with_upgrades(TokioIo::new(stream), TowerToHyperService::new(service));
+    if let Err(e) = watcher.watch(conn.into_owned()).await {
+        tracing::debug!(error = %e, %peer, "HTTPS connection closed with error");
+    }
+}
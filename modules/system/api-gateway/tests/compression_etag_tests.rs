@@ -0,0 +1,231 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for response compression and `ETag` / conditional GET handling
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    extract::Json,
+    http::{Request, StatusCode, header},
+    routing::get,
+};
+use modkit::{
+    Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api-gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+fn create_test_module_ctx(config: &serde_json::Value) -> ModuleCtx {
+    ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider {
+            config: serde_json::json!({ "config": config }),
+        }),
+        Arc::new(modkit::ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    )
+}
+
+pub struct ItemsModule;
+
+#[async_trait]
+impl Module for ItemsModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for ItemsModule {
+    fn register_rest(
+        &self,
+        _ctx: &modkit::ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<axum::Router> {
+        let router = OperationBuilder::get("/tests/v1/items")
+            .operation_id("test:items")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(items_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/raw-items")
+            .operation_id("test:raw_items")
+            .disable_compression()
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(items_handler))
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn items_handler() -> Json<Vec<String>> {
+    Json((0..500).map(|i| format!("item-{i}")).collect())
+}
+
+async fn build_router(config: &serde_json::Value) -> Router {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx(config);
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = ItemsModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router")
+}
+
+fn default_config() -> serde_json::Value {
+    serde_json::json!({
+        "bind_addr": "127.0.0.1:0",
+        "auth_disabled": true
+    })
+}
+
+fn get_request(uri: &str, headers: &[(header::HeaderName, &str)]) -> Request<Body> {
+    let mut builder = Request::builder().uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(name, *value);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_large_response_is_compressed_when_accepted() {
+    let router = build_router(&default_config()).await;
+
+    for encoding in ["gzip", "br", "zstd"] {
+        let res = router
+            .clone()
+            .oneshot(get_request(
+                "/tests/v1/items",
+                &[(header::ACCEPT_ENCODING, encoding)],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(header::CONTENT_ENCODING).unwrap(),
+            encoding
+        );
+    }
+
+    let res = router
+        .oneshot(get_request("/tests/v1/items", &[]))
+        .await
+        .unwrap();
+    assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn test_opted_out_route_is_not_compressed() {
+    let router = build_router(&default_config()).await;
+
+    let res = router
+        .oneshot(get_request(
+            "/tests/v1/raw-items",
+            &[(header::ACCEPT_ENCODING, "gzip")],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn test_compression_can_be_disabled() {
+    let mut config = default_config();
+    config["compression"] = serde_json::json!({ "enabled": false });
+    let router = build_router(&config).await;
+
+    let res = router
+        .oneshot(get_request(
+            "/tests/v1/items",
+            &[(header::ACCEPT_ENCODING, "gzip")],
+        ))
+        .await
+        .unwrap();
+    assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn test_matching_if_none_match_returns_not_modified() {
+    let router = build_router(&default_config()).await;
+
+    let res = router
+        .clone()
+        .oneshot(get_request(
+            "/tests/v1/items",
+            &[(header::ACCEPT_ENCODING, "gzip")],
+        ))
+        .await
+        .unwrap();
+    let etag = res
+        .headers()
+        .get(header::ETAG)
+        .expect("GET response should carry an ETag")
+        .to_str()
+        .unwrap()
+        .to_owned();
+    assert!(etag.starts_with("W/\""), "ETag should be weak: {etag}");
+
+    // Same representation without compression shares the weak ETag
+    let res = router
+        .clone()
+        .oneshot(get_request("/tests/v1/items", &[]))
+        .await
+        .unwrap();
+    assert_eq!(res.headers().get(header::ETAG).unwrap(), etag.as_str());
+
+    let res = router
+        .clone()
+        .oneshot(get_request(
+            "/tests/v1/items",
+            &[
+                (header::IF_NONE_MATCH, etag.as_str()),
+                (header::ACCEPT_ENCODING, "gzip"),
+            ],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(res.headers().get(header::ETAG).unwrap(), etag.as_str());
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty());
+
+    let res = router
+        .oneshot(get_request(
+            "/tests/v1/items",
+            &[(header::IF_NONE_MATCH, "W/\"stale\"")],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
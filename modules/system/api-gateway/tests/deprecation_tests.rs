@@ -0,0 +1,205 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for API versions and the deprecation lifecycle of operations
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::NaiveDate;
+use modkit::{
+    Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api-gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+fn create_test_module_ctx() -> ModuleCtx {
+    let config = serde_json::json!({
+        "config": {
+            "bind_addr": "127.0.0.1:0",
+            "enable_docs": true,
+            "auth_disabled": true
+        }
+    });
+    ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        Arc::new(modkit::ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    )
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Test module with current, deprecated and retired operations in two versions
+pub struct VersionedModule;
+
+#[async_trait]
+impl Module for VersionedModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for VersionedModule {
+    fn register_rest(
+        &self,
+        _ctx: &modkit::ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<axum::Router> {
+        let router = OperationBuilder::get("/tests/v1/items")
+            .operation_id("test:list_items_v1")
+            .deprecated_since(date(2020, 1, 1))
+            .sunset(date(2999, 1, 1))
+            .deprecation_link("https://example.com/docs/items-v2")
+            .public()
+            .text_response(http::StatusCode::OK, "Items", "text/plain")
+            .handler(|| async { "v1 items" })
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/legacy")
+            .operation_id("test:legacy_v1")
+            .deprecated_since(date(2020, 1, 1))
+            .sunset(date(2021, 1, 1))
+            .public()
+            .text_response(http::StatusCode::OK, "Legacy", "text/plain")
+            .handler(|| async { "legacy" })
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v2/items")
+            .operation_id("test:list_items_v2")
+            .public()
+            .text_response(http::StatusCode::OK, "Items", "text/plain")
+            .handler(|| async { "v2 items" })
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/search")
+            .operation_id("test:search_v2")
+            .version("v2")
+            .public()
+            .text_response(http::StatusCode::OK, "Search", "text/plain")
+            .handler(|| async { "search" })
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn build_router() -> Router {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = VersionedModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router")
+}
+
+async fn get(router: &Router, uri: &str) -> axum::response::Response {
+    router
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_json(res: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_deprecated_operation_announces_lifecycle() {
+    let router = build_router().await;
+
+    let res = get(&router, "/tests/v1/items").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get("deprecation").unwrap(), "@1577836800");
+    assert_eq!(
+        res.headers().get("sunset").unwrap(),
+        "Tue, 01 Jan 2999 00:00:00 GMT"
+    );
+    assert_eq!(
+        res.headers().get("link").unwrap(),
+        "<https://example.com/docs/items-v2>; rel=\"deprecation\""
+    );
+
+    let res = get(&router, "/tests/v2/items").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get("deprecation").is_none());
+    assert!(res.headers().get("sunset").is_none());
+}
+
+#[tokio::test]
+async fn test_sunset_operation_is_gone() {
+    let router = build_router().await;
+
+    let res = get(&router, "/tests/v1/legacy").await;
+    assert_eq!(res.status(), StatusCode::GONE);
+    assert_eq!(
+        res.headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+    assert!(res.headers().get("sunset").is_some());
+    let problem = body_json(res).await;
+    assert_eq!(problem["status"], 410);
+    assert_eq!(
+        problem["detail"],
+        "This operation was retired on 2021-01-01"
+    );
+}
+
+#[tokio::test]
+async fn test_openapi_document_per_version() {
+    let router = build_router().await;
+
+    let res = get(&router, "/openapi/v1.json").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let v1 = body_json(res).await;
+    let mut v1_paths: Vec<_> = v1["paths"].as_object().unwrap().keys().cloned().collect();
+    v1_paths.sort();
+    assert_eq!(v1_paths, vec!["/tests/v1/items", "/tests/v1/legacy"]);
+    assert_eq!(v1["paths"]["/tests/v1/items"]["get"]["deprecated"], true);
+
+    let v2 = body_json(get(&router, "/openapi/v2.json").await).await;
+    let mut v2_paths: Vec<_> = v2["paths"].as_object().unwrap().keys().cloned().collect();
+    v2_paths.sort();
+    assert_eq!(v2_paths, vec!["/tests/search", "/tests/v2/items"]);
+
+    let res = get(&router, "/openapi/v3.json").await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let all = body_json(get(&router, "/openapi.json").await).await;
+    assert_eq!(all["paths"].as_object().unwrap().len(), 4);
+}
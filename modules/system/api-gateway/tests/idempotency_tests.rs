@@ -0,0 +1,217 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for `Idempotency-Key` handling on idempotent operations
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    extract::{Json, State},
+    http::{Request, StatusCode},
+    routing::post,
+};
+use modkit::{
+    Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api-gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+fn create_test_module_ctx() -> ModuleCtx {
+    let config = serde_json::json!({
+        "config": {
+            "bind_addr": "127.0.0.1:0",
+            "auth_disabled": true
+        }
+    });
+    ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        Arc::new(modkit::ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    )
+}
+
+/// Test module counting how often its handlers actually run
+#[derive(Clone, Default)]
+pub struct CreateModule {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Module for CreateModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for CreateModule {
+    fn register_rest(
+        &self,
+        _ctx: &modkit::ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<axum::Router> {
+        let router = OperationBuilder::post("/tests/v1/items")
+            .operation_id("test:create_item")
+            .idempotent()
+            .public()
+            .json_response(http::StatusCode::CREATED, "Created")
+            .handler(post(create_handler).with_state(self.calls.clone()))
+            .register(router, openapi);
+
+        let router = OperationBuilder::post("/tests/v1/plain-items")
+            .operation_id("test:create_plain_item")
+            .public()
+            .json_response(http::StatusCode::CREATED, "Created")
+            .handler(post(create_handler).with_state(self.calls.clone()))
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn create_handler(
+    State(calls): State<Arc<AtomicUsize>>,
+    Json(body): Json<serde_json::Value>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+    (
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "id": n, "name": body["name"] })),
+    )
+}
+
+async fn build_router(module: &CreateModule) -> Router {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = module
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router")
+}
+
+fn create_request(uri: &str, key: Option<&str>, name: &str) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(key) = key {
+        builder = builder.header("idempotency-key", key);
+    }
+    builder
+        .body(Body::from(serde_json::json!({ "name": name }).to_string()))
+        .unwrap()
+}
+
+async fn body_json(res: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_retry_with_same_key_is_replayed() {
+    let module = CreateModule::default();
+    let router = build_router(&module).await;
+
+    let first = router
+        .clone()
+        .oneshot(create_request("/tests/v1/items", Some("key-1"), "a"))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::CREATED);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    let first = body_json(first).await;
+
+    let retry = router
+        .oneshot(create_request("/tests/v1/items", Some("key-1"), "a"))
+        .await
+        .unwrap();
+    assert_eq!(retry.status(), StatusCode::CREATED);
+    assert_eq!(retry.headers().get("idempotent-replayed").unwrap(), "true");
+    assert_eq!(
+        retry.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(body_json(retry).await, first);
+
+    assert_eq!(module.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_key_reused_for_different_body_is_rejected() {
+    let module = CreateModule::default();
+    let router = build_router(&module).await;
+
+    let first = router
+        .clone()
+        .oneshot(create_request("/tests/v1/items", Some("key-1"), "a"))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::CREATED);
+
+    let reused = router
+        .oneshot(create_request("/tests/v1/items", Some("key-1"), "b"))
+        .await
+        .unwrap();
+    assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(module.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_requests_without_key_or_opt_in_are_not_deduplicated() {
+    let module = CreateModule::default();
+    let router = build_router(&module).await;
+
+    for request in [
+        create_request("/tests/v1/items", None, "a"),
+        create_request("/tests/v1/items", None, "a"),
+        create_request("/tests/v1/plain-items", Some("key-1"), "a"),
+        create_request("/tests/v1/plain-items", Some("key-1"), "a"),
+    ] {
+        let res = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+    assert_eq!(module.calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn test_invalid_key_is_rejected() {
+    let module = CreateModule::default();
+    let router = build_router(&module).await;
+
+    let long_key = "k".repeat(256);
+    let res = router
+        .oneshot(create_request("/tests/v1/items", Some(&long_key), "a"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(module.calls.load(Ordering::SeqCst), 0);
+}
@@ -4,7 +4,8 @@
 //!
 //! The intended order is documented in `modules/api_gateway/src/lib.rs`:
 //! set request id -> propagate request id -> trace -> push request id to extensions
-//! -> timeout -> body limit -> CORS -> MIME validation -> rate limit -> error mapping -> auth -> router
+//! -> timeout -> body limit -> CORS -> compression -> MIME validation -> error mapping
+//! -> route/IP rate limit -> auth -> tenant/subject rate limit -> license -> idempotency -> `ETag` -> deprecation -> router
 //!
 use anyhow::Result;
 use api_gateway::middleware::request_id::XRequestId;
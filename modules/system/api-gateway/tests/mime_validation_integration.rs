@@ -52,6 +52,10 @@ async fn test_middleware_allows_configured_content_type() {
         authenticated: false,
         is_public: true,
         license_requirement: None,
+        disable_compression: false,
+        idempotency_key: false,
+        version: None,
+        deprecation: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
         vendor_extensions: VendorExtensions::default(),
@@ -97,6 +101,10 @@ async fn test_middleware_strips_content_type_parameters() {
         authenticated: false,
         is_public: true,
         license_requirement: None,
+        disable_compression: false,
+        idempotency_key: false,
+        version: None,
+        deprecation: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
         vendor_extensions: VendorExtensions::default(),
@@ -142,6 +150,10 @@ async fn test_middleware_rejects_disallowed_content_type() {
         authenticated: false,
         is_public: true,
         license_requirement: None,
+        disable_compression: false,
+        idempotency_key: false,
+        version: None,
+        deprecation: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
         vendor_extensions: VendorExtensions::default(),
@@ -193,6 +205,10 @@ async fn test_middleware_rejects_missing_content_type() {
         authenticated: false,
         is_public: true,
         license_requirement: None,
+        disable_compression: false,
+        idempotency_key: false,
+        version: None,
+        deprecation: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["multipart/form-data"]),
         vendor_extensions: VendorExtensions::default(),
@@ -268,6 +284,10 @@ async fn test_middleware_allows_multiple_content_types() {
         authenticated: false,
         is_public: true,
         license_requirement: None,
+        disable_compression: false,
+        idempotency_key: false,
+        version: None,
+        deprecation: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec![
             "application/json",
@@ -4,7 +4,13 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
-use axum::{Router, extract::Json, routing::get};
+use axum::{
+    Router,
+    body::Body,
+    extract::Json,
+    http::{Request, StatusCode, header},
+    routing::get,
+};
 use modkit::{
     Module, ModuleCtx, RestApiCapability,
     api::OperationBuilder,
@@ -14,6 +20,7 @@ use modkit::{
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::time::{Duration, sleep};
+use tower::ServiceExt;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -249,3 +256,44 @@ async fn test_rate_limit_metadata_stored() {
     let test_op = json.pointer("/paths/~1tests~1v1~1test/get");
     assert!(test_op.is_some(), "Test endpoint should be in OpenAPI");
 }
+
+#[tokio::test]
+async fn test_limited_request_gets_retry_after() {
+    let config = serde_json::json!({
+        "bind_addr": "127.0.0.1:0",
+        "cors_enabled": false,
+        "auth_disabled": true,
+        "rate_limit": {
+            "key": ["tenant", "subject"]
+        }
+    });
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx_with_config(&config);
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = RateLimitedModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    let router = api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router");
+
+    let request = || {
+        Request::builder()
+            .uri("/tests/v1/limited")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let first = router.clone().oneshot(request()).await.unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+
+    let second = router.oneshot(request()).await.unwrap();
+    assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        second.headers().get(header::RETRY_AFTER).unwrap(),
+        "1",
+        "Retry-After should be rounded up to whole seconds"
+    );
+}
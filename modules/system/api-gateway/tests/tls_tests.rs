@@ -0,0 +1,438 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for TLS termination: SNI certificates, certificate reload and mTLS
+//! client authentication. Certificates are generated per test with a throwaway CA.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{Extension, Router, body::Body, http::StatusCode};
+use hyper_util::rt::TokioIo;
+use modkit::{
+    ClientHub, Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use modkit_security::SecurityContext;
+use rcgen::{
+    BasicConstraints, CertificateParams, CertifiedIssuer, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair,
+};
+use rustls::RootCertStore;
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use serde_json::json;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio_rustls::TlsConnector;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use api_gateway::TlsConfig;
+use api_gateway::tls::TlsServerConfig;
+
+const VALID_TOKEN: &str = "valid-token";
+
+/// Throwaway CA issuing server and client certificates into a temp directory
+struct Pki {
+    dir: TempDir,
+    ca: CertifiedIssuer<'static, KeyPair>,
+}
+
+impl Pki {
+    fn new() -> Self {
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Test CA");
+        let ca = CertifiedIssuer::self_signed(params, KeyPair::generate().unwrap()).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ca.pem"), ca.pem()).unwrap();
+        Self { dir, ca }
+    }
+
+    fn ca_path(&self) -> PathBuf {
+        self.dir.path().join("ca.pem")
+    }
+
+    /// Issue a certificate and write it to `<file>.pem` / `<file>.key`.
+    fn issue(
+        &self,
+        file: &str,
+        common_name: &str,
+        server_names: &[&str],
+        usage: ExtendedKeyUsagePurpose,
+    ) -> (PathBuf, PathBuf) {
+        let names: Vec<String> = server_names.iter().map(|n| (*n).to_owned()).collect();
+        let mut params = CertificateParams::new(names).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, common_name);
+        params.extended_key_usages = vec![usage];
+        let key = KeyPair::generate().unwrap();
+        let cert = params.signed_by(&key, &self.ca).unwrap();
+
+        let cert_path = self.dir.path().join(format!("{file}.pem"));
+        let key_path = self.dir.path().join(format!("{file}.key"));
+        std::fs::write(&cert_path, cert.pem()).unwrap();
+        std::fs::write(&key_path, key.serialize_pem()).unwrap();
+        (cert_path, key_path)
+    }
+
+    fn server_cert(&self, file: &str, server_names: &[&str]) -> (PathBuf, PathBuf) {
+        self.issue(
+            file,
+            server_names[0],
+            server_names,
+            ExtendedKeyUsagePurpose::ServerAuth,
+        )
+    }
+
+    fn client_cert(&self, file: &str, common_name: &str) -> (PathBuf, PathBuf) {
+        self.issue(file, common_name, &[], ExtendedKeyUsagePurpose::ClientAuth)
+    }
+}
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api-gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+/// `AuthN` Resolver accepting only `VALID_TOKEN`
+struct TokenAuthN;
+
+#[async_trait]
+impl AuthNResolverClient for TokenAuthN {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        if bearer_token != VALID_TOKEN {
+            return Err(AuthNResolverError::Unauthorized("invalid token".to_owned()));
+        }
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(Uuid::nil())
+                .subject_tenant_id(Uuid::nil())
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+/// Test module exposing the caller's subject id
+pub struct WhoAmIModule;
+
+#[async_trait]
+impl Module for WhoAmIModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for WhoAmIModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let router = OperationBuilder::get("/tests/v1/whoami")
+            .operation_id("test:whoami")
+            .authenticated()
+            .no_license_required()
+            .text_response(http::StatusCode::OK, "Subject id", "text/plain")
+            .handler(whoami)
+            .register(router, openapi);
+        Ok(router)
+    }
+}
+
+async fn whoami(Extension(ctx): Extension<SecurityContext>) -> String {
+    ctx.subject_id().to_string()
+}
+
+/// A running HTTPS gateway
+struct Gateway {
+    addr: SocketAddr,
+    tls: Arc<TlsServerConfig>,
+    cancel: CancellationToken,
+}
+
+impl Drop for Gateway {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+async fn start_gateway(tls: serde_json::Value) -> Gateway {
+    let config = json!({
+        "config": {
+            "bind_addr": "127.0.0.1:0",
+            "auth_disabled": false,
+            "tls": tls,
+        }
+    });
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn AuthNResolverClient>(Arc::new(TokenAuthN));
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = WhoAmIModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    let router = api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router");
+
+    let tls_config: TlsConfig = serde_json::from_value(tls).unwrap();
+    let tls = Arc::new(TlsServerConfig::load(&tls_config).expect("Failed to load TLS files"));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let cancel = CancellationToken::new();
+    tokio::spawn(api_gateway::tls::serve(
+        listener,
+        router,
+        Arc::clone(&tls),
+        cancel.clone(),
+    ));
+    Gateway { addr, tls, cancel }
+}
+
+/// Outcome of one HTTPS request
+struct Reply {
+    status: StatusCode,
+    body: String,
+    server_cert: CertificateDer<'static>,
+}
+
+/// `GET /tests/v1/whoami` over a new TLS connection.
+async fn whoami_request(
+    gateway: &Gateway,
+    pki: &Pki,
+    server_name: &str,
+    client_cert: Option<&(PathBuf, PathBuf)>,
+    bearer: Option<&str>,
+) -> Result<Reply> {
+    let mut roots = RootCertStore::empty();
+    roots.add(CertificateDer::from_pem_file(pki.ca_path())?)?;
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(roots);
+    let config = match client_cert {
+        Some((cert, key)) => builder.with_client_auth_cert(
+            vec![CertificateDer::from_pem_file(cert)?],
+            PrivateKeyDer::from_pem_file(key)?,
+        )?,
+        None => builder.with_no_client_auth(),
+    };
+
+    let tcp = tokio::net::TcpStream::connect(gateway.addr).await?;
+    let stream = TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from(server_name.to_owned())?, tcp)
+        .await?;
+    let server_cert = stream.get_ref().1.peer_certificates().unwrap()[0].clone();
+
+    let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+    tokio::spawn(conn);
+    let mut request =
+        http::Request::get("/tests/v1/whoami").header(http::header::HOST, server_name);
+    if let Some(token) = bearer {
+        request = request.header(http::header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let response = sender.send_request(request.body(Body::empty())?).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(Body::new(response.into_body()), usize::MAX).await?;
+    Ok(Reply {
+        status,
+        body: String::from_utf8(body.to_vec())?,
+        server_cert,
+    })
+}
+
+fn read_cert(path: &PathBuf) -> CertificateDer<'static> {
+    CertificateDer::from_pem_file(path).unwrap()
+}
+
+#[tokio::test]
+async fn test_certificate_is_selected_by_sni() {
+    let pki = Pki::new();
+    let (cert, key) = pki.server_cert("default", &["localhost"]);
+    let (api_cert, api_key) = pki.server_cert("api", &["api.example.test"]);
+    let gateway = start_gateway(json!({
+        "cert_path": cert,
+        "key_path": key,
+        "sni": [{
+            "server_names": ["API.example.test"],
+            "cert_path": api_cert,
+            "key_path": api_key,
+        }],
+    }))
+    .await;
+
+    let reply = whoami_request(&gateway, &pki, "localhost", None, Some(VALID_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(reply.status, StatusCode::OK);
+    assert_eq!(reply.server_cert, read_cert(&cert));
+
+    let reply = whoami_request(&gateway, &pki, "api.example.test", None, Some(VALID_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(reply.status, StatusCode::OK);
+    assert_eq!(reply.server_cert, read_cert(&api_cert));
+}
+
+#[tokio::test]
+async fn test_rotated_certificate_is_served_after_reload() {
+    let pki = Pki::new();
+    let (cert, key) = pki.server_cert("server", &["localhost"]);
+    let gateway = start_gateway(json!({
+        "cert_path": cert,
+        "key_path": key,
+        "reload_interval_secs": 0,
+    }))
+    .await;
+
+    let before = whoami_request(&gateway, &pki, "localhost", None, Some(VALID_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(before.server_cert, read_cert(&cert));
+    assert!(!gateway.tls.reload_if_changed().unwrap());
+
+    // Rotate: a new certificate and key at the same paths
+    pki.server_cert("server", &["localhost"]);
+    assert!(gateway.tls.reload_if_changed().unwrap());
+
+    let after = whoami_request(&gateway, &pki, "localhost", None, Some(VALID_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(after.status, StatusCode::OK);
+    assert_eq!(after.server_cert, read_cert(&cert));
+    assert_ne!(after.server_cert, before.server_cert);
+}
+
+#[tokio::test]
+async fn test_client_certificate_authenticates_mapped_subject() {
+    let pki = Pki::new();
+    let (cert, key) = pki.server_cert("server", &["localhost"]);
+    let billing = pki.client_cert("billing", "billing-service");
+    let unknown = pki.client_cert("unknown", "unknown-service");
+    let subject_id = Uuid::new_v4();
+    let gateway = start_gateway(json!({
+        "cert_path": cert,
+        "key_path": key,
+        "client_auth": {
+            "ca_path": pki.ca_path(),
+            "subjects": {
+                "CN=billing-service": {
+                    "subject_id": subject_id,
+                    "tenant_id": Uuid::new_v4(),
+                    "subject_type": "service",
+                },
+            },
+        },
+    }))
+    .await;
+
+    let reply = whoami_request(&gateway, &pki, "localhost", Some(&billing), None)
+        .await
+        .unwrap();
+    assert_eq!(reply.status, StatusCode::OK);
+    assert_eq!(reply.body, subject_id.to_string());
+
+    // A bearer token takes precedence over the certificate
+    let reply = whoami_request(
+        &gateway,
+        &pki,
+        "localhost",
+        Some(&billing),
+        Some(VALID_TOKEN),
+    )
+    .await
+    .unwrap();
+    assert_eq!(reply.status, StatusCode::OK);
+    assert_eq!(reply.body, Uuid::nil().to_string());
+
+    let reply = whoami_request(&gateway, &pki, "localhost", Some(&unknown), None)
+        .await
+        .unwrap();
+    assert_eq!(reply.status, StatusCode::UNAUTHORIZED);
+
+    // Client certificates are optional unless `required` is set
+    let reply = whoami_request(&gateway, &pki, "localhost", None, None)
+        .await
+        .unwrap();
+    assert_eq!(reply.status, StatusCode::UNAUTHORIZED);
+    let reply = whoami_request(&gateway, &pki, "localhost", None, Some(VALID_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(reply.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_required_client_certificate_is_enforced() {
+    let pki = Pki::new();
+    let (cert, key) = pki.server_cert("server", &["localhost"]);
+    let client = pki.client_cert("client", "billing-service");
+    let gateway = start_gateway(json!({
+        "cert_path": cert,
+        "key_path": key,
+        "client_auth": { "ca_path": pki.ca_path(), "required": true },
+    }))
+    .await;
+
+    assert!(
+        whoami_request(&gateway, &pki, "localhost", None, Some(VALID_TOKEN))
+            .await
+            .is_err()
+    );
+
+    // A certificate from another CA is rejected as well
+    let other_pki = Pki::new();
+    let foreign = other_pki.client_cert("client", "billing-service");
+    assert!(
+        whoami_request(
+            &gateway,
+            &pki,
+            "localhost",
+            Some(&foreign),
+            Some(VALID_TOKEN)
+        )
+        .await
+        .is_err()
+    );
+
+    let reply = whoami_request(
+        &gateway,
+        &pki,
+        "localhost",
+        Some(&client),
+        Some(VALID_TOKEN),
+    )
+    .await
+    .unwrap();
+    assert_eq!(reply.status, StatusCode::OK);
+}
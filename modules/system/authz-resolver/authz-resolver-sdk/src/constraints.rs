@@ -5,17 +5,23 @@
 //!
 //! ## Supported predicates
 //!
-//! Only `Eq` and `In` predicates are supported in the first iteration.
+//! - `eq`, `in` — literal matches
+//! - `gt`, `gte`, `lt`, `lte` — range comparisons (numbers, strings,
+//!   RFC 3339 timestamps)
+//! - `in_tenant_subtree` — tenant hierarchy (requires
+//!   `Capability::TenantHierarchy`)
+//! - `in_group`, `in_group_subtree` — resource group membership (require
+//!   `Capability::GroupMembership` / `Capability::GroupHierarchy`)
+//! - `not` — negation of another predicate
 //!
-//! ## Future extensions
-//!
-//! Additional predicate types (`in_tenant_subtree`, `in_group`,
-//! `in_group_subtree`) are planned. See the authorization design document
-//! (`docs/arch/authorization/DESIGN.md`) for the full predicate taxonomy.
+//! See the authorization design document (`docs/arch/authorization/DESIGN.md`)
+//! for the full predicate taxonomy.
 
+use crate::models::BarrierMode;
 use crate::pep::IntoPropertyValue;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use uuid::Uuid;
 
 /// A constraint on a specific resource property.
 ///
@@ -36,6 +42,32 @@ pub enum Predicate {
     Eq(EqPredicate),
     /// Set membership: `resource_property IN (values)`
     In(InPredicate),
+    /// Greater than: `resource_property > value`
+    Gt(ComparisonPredicate),
+    /// Greater than or equal: `resource_property >= value`
+    Gte(ComparisonPredicate),
+    /// Less than: `resource_property < value`
+    Lt(ComparisonPredicate),
+    /// Less than or equal: `resource_property <= value`
+    Lte(ComparisonPredicate),
+    /// Tenant hierarchy: `resource_property` is `root_tenant_id` or one of its descendants
+    InTenantSubtree(InTenantSubtreePredicate),
+    /// Group membership: `resource_property` is a member of one of `group_ids`
+    InGroup(InGroupPredicate),
+    /// Group hierarchy: `resource_property` is a member of `root_group_id` or one of its descendants
+    InGroupSubtree(InGroupSubtreePredicate),
+    /// Negation: the inner predicate must not hold
+    Not(NotPredicate),
+}
+
+impl Predicate {
+    /// Negate this predicate.
+    #[must_use]
+    pub fn negate(self) -> Self {
+        Self::Not(NotPredicate {
+            predicate: Box::new(self),
+        })
+    }
 }
 
 /// Equality predicate: `property = value`.
@@ -84,6 +116,101 @@ impl InPredicate {
     }
 }
 
+/// Comparison predicate used by `gt`, `gte`, `lt` and `lte`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonPredicate {
+    /// Resource property name (e.g., `created_at`).
+    pub property: String,
+    /// The bound to compare against (number, string or RFC 3339 timestamp).
+    pub value: Value,
+}
+
+impl ComparisonPredicate {
+    /// Create a comparison predicate with any convertible value.
+    #[must_use]
+    pub fn new(property: impl Into<String>, value: impl IntoPropertyValue) -> Self {
+        Self {
+            property: property.into(),
+            value: value.into_filter_value(),
+        }
+    }
+}
+
+/// Tenant subtree predicate: `property IN (descendants of root_tenant_id)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InTenantSubtreePredicate {
+    /// Resource property name (typically `pep_properties::OWNER_TENANT_ID`).
+    pub property: String,
+    /// Root of the subtree; the root itself is included.
+    pub root_tenant_id: Uuid,
+    /// Whether traversal stops at self-managed tenants.
+    #[serde(default)]
+    pub barrier_mode: BarrierMode,
+    /// Only descendants in one of these statuses; absent means any status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_status: Option<Vec<String>>,
+}
+
+impl InTenantSubtreePredicate {
+    /// Create a tenant subtree predicate that respects barriers.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_tenant_id,
+            barrier_mode: BarrierMode::default(),
+            tenant_status: None,
+        }
+    }
+}
+
+/// Group membership predicate: `property IN (members of group_ids)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InGroupPredicate {
+    /// Resource property name (typically `pep_properties::RESOURCE_ID`).
+    pub property: String,
+    /// The groups whose members match.
+    pub group_ids: Vec<Uuid>,
+}
+
+impl InGroupPredicate {
+    /// Create a group membership predicate.
+    #[must_use]
+    pub fn new(property: impl Into<String>, group_ids: impl IntoIterator<Item = Uuid>) -> Self {
+        Self {
+            property: property.into(),
+            group_ids: group_ids.into_iter().collect(),
+        }
+    }
+}
+
+/// Group subtree predicate: `property IN (members of root_group_id and its descendants)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InGroupSubtreePredicate {
+    /// Resource property name (typically `pep_properties::RESOURCE_ID`).
+    pub property: String,
+    /// Root of the group subtree; the root itself is included.
+    pub root_group_id: Uuid,
+}
+
+impl InGroupSubtreePredicate {
+    /// Create a group subtree predicate.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_group_id,
+        }
+    }
+}
+
+/// Negation predicate: `NOT (predicate)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotPredicate {
+    /// The negated predicate.
+    pub predicate: Box<Predicate>,
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -132,4 +259,50 @@ mod tests {
         let json_str = serde_json::to_string(&in_pred).unwrap();
         assert!(json_str.contains(r#""op":"in""#));
     }
+
+    #[test]
+    fn extended_predicates_serialization() {
+        let root = Uuid::parse_str("11111111-1111-1111-1111-111111111111").unwrap();
+
+        let gt = Predicate::Gt(ComparisonPredicate::new(
+            "created_at",
+            "2024-01-01T00:00:00Z",
+        ));
+        assert_eq!(
+            serde_json::to_value(&gt).unwrap(),
+            json!({"op": "gt", "property": "created_at", "value": "2024-01-01T00:00:00Z"})
+        );
+
+        let subtree = Predicate::InTenantSubtree(InTenantSubtreePredicate::new(
+            pep_properties::OWNER_TENANT_ID,
+            root,
+        ));
+        assert_eq!(
+            serde_json::to_value(&subtree).unwrap(),
+            json!({
+                "op": "in_tenant_subtree",
+                "property": pep_properties::OWNER_TENANT_ID,
+                "root_tenant_id": root,
+                "barrier_mode": "respect",
+            })
+        );
+
+        let not =
+            Predicate::InGroup(InGroupPredicate::new(pep_properties::RESOURCE_ID, [root])).negate();
+        let value = serde_json::to_value(&not).unwrap();
+        assert_eq!(value["op"], "not");
+        assert_eq!(value["predicate"]["op"], "in_group");
+
+        let parsed: Predicate = serde_json::from_value(json!({
+            "op": "in_tenant_subtree",
+            "property": pep_properties::OWNER_TENANT_ID,
+            "root_tenant_id": root,
+        }))
+        .unwrap();
+        let Predicate::InTenantSubtree(p) = parsed else {
+            panic!("expected in_tenant_subtree");
+        };
+        assert_eq!(p.barrier_mode, BarrierMode::Respect);
+        assert!(p.tenant_status.is_none());
+    }
 }
@@ -21,6 +21,7 @@
 //! const USER: ResourceType = ResourceType {
 //!     name: "gts.x.core.users.user.v1~",
 //!     supported_properties: &["owner_tenant_id", "id"],
+//!     timestamp_properties: &[],
 //! };
 //!
 //! // Get the client from ClientHub
@@ -52,7 +53,10 @@ pub mod plugin_api;
 
 // Re-export main types at crate root
 pub use api::AuthZResolverClient;
-pub use constraints::{Constraint, EqPredicate, InPredicate, Predicate};
+pub use constraints::{
+    ComparisonPredicate, Constraint, EqPredicate, InGroupPredicate, InGroupSubtreePredicate,
+    InPredicate, InTenantSubtreePredicate, NotPredicate, Predicate,
+};
 pub use error::AuthZResolverError;
 pub use gts::AuthZResolverPluginSpecV1;
 pub use models::{
@@ -11,17 +11,23 @@
 //! | true              | empty       | `ConstraintsRequiredButAbsent` |
 //! | true              | present     | Compile constraints → `AccessScope` |
 //!
-//! Unknown/unsupported properties fail that constraint (fail-closed).
+//! Unknown/unsupported properties fail that constraint (fail-closed), and so
+//! do hierarchy/group predicates the PEP did not declare a [`Capability`] for.
 //!
 //! When `require_constraints=false`, empty constraints are treated as
 //! `allow_all()` (legitimate PDP "yes, no row-level filtering"). When
 //! `require_constraints=true`, empty constraints are an error (fail-closed).
 //! If the PDP returns constraints regardless of the flag, they are compiled.
 
-use modkit_security::{AccessScope, ScopeConstraint, ScopeFilter, ScopeValue};
+use modkit_security::{
+    AccessScope, CompareOp, GroupScopeFilter, GroupSubtreeScopeFilter, ScopeConstraint,
+    ScopeFilter, ScopeValue, TenantSubtreeScopeFilter,
+};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
 
-use crate::constraints::{Constraint, Predicate};
-use crate::models::EvaluationResponse;
+use crate::constraints::{ComparisonPredicate, Constraint, Predicate};
+use crate::models::{BarrierMode, Capability, EvaluationResponse};
 
 /// Error during constraint compilation.
 #[derive(Debug, thiserror::Error)]
@@ -52,7 +58,13 @@ pub enum ConstraintCompileError {
 ///
 /// The compiler is property-agnostic: it validates predicates against the
 /// provided `supported_properties` list and converts them structurally.
-/// Unknown properties fail that constraint (fail-closed).
+/// Values of `timestamp_properties` must be RFC 3339 strings and compile to
+/// `ScopeValue::Timestamp`; strings of other properties are never reinterpreted
+/// as timestamps.
+/// Unknown properties fail that constraint (fail-closed), as do
+/// `in_tenant_subtree` / `in_group` / `in_group_subtree` predicates without
+/// the matching entry in `capabilities` (`GroupHierarchy` implies
+/// `GroupMembership`).
 /// If ALL constraints fail compilation, returns `AllConstraintsFailed`.
 ///
 /// # Errors
@@ -63,6 +75,8 @@ pub fn compile_to_access_scope(
     response: &EvaluationResponse,
     require_constraints: bool,
     supported_properties: &[&str],
+    timestamp_properties: &[&str],
+    capabilities: &[Capability],
 ) -> Result<AccessScope, ConstraintCompileError> {
     // Step 1: Handle empty constraints based on require_constraints flag.
     if response.context.constraints.is_empty() {
@@ -77,7 +91,11 @@ pub fn compile_to_access_scope(
     let mut fail_reasons: Vec<String> = Vec::new();
 
     for constraint in &response.context.constraints {
-        match compile_constraint(constraint, supported_properties) {
+        let properties = Properties {
+            supported: supported_properties,
+            timestamps: timestamp_properties,
+        };
+        match compile_constraint(constraint, &properties, capabilities) {
             Ok(sc) => constraints.push(sc),
             Err(reason) => {
                 tracing::warn!(
@@ -104,55 +122,156 @@ pub fn compile_to_access_scope(
     Ok(AccessScope::from_constraints(constraints))
 }
 
+/// Property declarations of the resource type being compiled for.
+struct Properties<'a> {
+    supported: &'a [&'a str],
+    timestamps: &'a [&'a str],
+}
+
+impl Properties<'_> {
+    /// Convert a predicate value of `property`.
+    fn value(&self, property: &str, v: &serde_json::Value) -> Result<ScopeValue, String> {
+        if self.timestamps.contains(&property) {
+            json_to_timestamp(property, v)
+        } else {
+            json_to_scope_value(v)
+        }
+    }
+}
+
 /// Compile a single PDP constraint into a `ScopeConstraint`.
 ///
-/// Each predicate becomes a `ScopeFilter`. If any predicate's property
-/// is not in `supported_properties`, the entire constraint fails (fail-closed).
+/// Each predicate becomes a `ScopeFilter`. If any predicate cannot be
+/// compiled, the entire constraint fails (fail-closed).
 fn compile_constraint(
     constraint: &Constraint,
-    supported_properties: &[&str],
+    properties: &Properties<'_>,
+    capabilities: &[Capability],
 ) -> Result<ScopeConstraint, String> {
-    let mut filters = Vec::new();
+    let filters = constraint
+        .predicates
+        .iter()
+        .map(|p| compile_predicate(p, properties, capabilities))
+        .collect::<Result<_, _>>()?;
 
-    for predicate in &constraint.predicates {
-        let (property, filter) = match predicate {
-            Predicate::Eq(eq) => {
-                let value = json_to_scope_value(&eq.value)?;
-                (eq.property.as_str(), ScopeFilter::eq(&eq.property, value))
-            }
-            Predicate::In(p) => {
-                let values: Vec<ScopeValue> = p
-                    .values
-                    .iter()
-                    .map(json_to_scope_value)
-                    .collect::<Result<_, _>>()?;
-                (p.property.as_str(), ScopeFilter::r#in(&p.property, values))
-            }
-        };
+    Ok(ScopeConstraint::new(filters))
+}
 
-        if !supported_properties.contains(&property) {
-            return Err(format!("unsupported property: {property}"));
+/// Compile a single predicate into a `ScopeFilter`.
+fn compile_predicate(
+    predicate: &Predicate,
+    properties: &Properties<'_>,
+    capabilities: &[Capability],
+) -> Result<ScopeFilter, String> {
+    let (property, filter) = match predicate {
+        Predicate::Eq(eq) => {
+            let value = properties.value(&eq.property, &eq.value)?;
+            (eq.property.as_str(), ScopeFilter::eq(&eq.property, value))
+        }
+        Predicate::In(p) => {
+            let values: Vec<ScopeValue> = p
+                .values
+                .iter()
+                .map(|v| properties.value(&p.property, v))
+                .collect::<Result<_, _>>()?;
+            (p.property.as_str(), ScopeFilter::r#in(&p.property, values))
+        }
+        Predicate::Gt(p) => (
+            p.property.as_str(),
+            compile_comparison(p, CompareOp::Gt, properties)?,
+        ),
+        Predicate::Gte(p) => (
+            p.property.as_str(),
+            compile_comparison(p, CompareOp::Gte, properties)?,
+        ),
+        Predicate::Lt(p) => (
+            p.property.as_str(),
+            compile_comparison(p, CompareOp::Lt, properties)?,
+        ),
+        Predicate::Lte(p) => (
+            p.property.as_str(),
+            compile_comparison(p, CompareOp::Lte, properties)?,
+        ),
+        Predicate::InTenantSubtree(p) => {
+            require_capability(
+                capabilities,
+                &Capability::TenantHierarchy,
+                "in_tenant_subtree",
+            )?;
+            let filter = TenantSubtreeScopeFilter::new(&p.property, p.root_tenant_id)
+                .with_barriers(p.barrier_mode == BarrierMode::Respect)
+                .with_tenant_status(p.tenant_status.clone().unwrap_or_default());
+            (p.property.as_str(), ScopeFilter::InTenantSubtree(filter))
+        }
+        Predicate::InGroup(p) => {
+            if !capabilities.contains(&Capability::GroupHierarchy) {
+                require_capability(capabilities, &Capability::GroupMembership, "in_group")?;
+            }
+            let filter = GroupScopeFilter::new(&p.property, p.group_ids.clone());
+            (p.property.as_str(), ScopeFilter::InGroup(filter))
+        }
+        Predicate::InGroupSubtree(p) => {
+            require_capability(
+                capabilities,
+                &Capability::GroupHierarchy,
+                "in_group_subtree",
+            )?;
+            let filter = GroupSubtreeScopeFilter::new(&p.property, p.root_group_id);
+            (p.property.as_str(), ScopeFilter::InGroupSubtree(filter))
         }
+        Predicate::Not(p) => {
+            let inner = compile_predicate(&p.predicate, properties, capabilities)?;
+            return Ok(ScopeFilter::negate(inner));
+        }
+    };
 
-        filters.push(filter);
+    if !properties.supported.contains(&property) {
+        return Err(format!("unsupported property: {property}"));
     }
 
-    Ok(ScopeConstraint::new(filters))
+    Ok(filter)
+}
+
+/// Compile a range predicate. Only orderable values (integers, strings and
+/// timestamps) can be compared.
+fn compile_comparison(
+    p: &ComparisonPredicate,
+    op: CompareOp,
+    properties: &Properties<'_>,
+) -> Result<ScopeFilter, String> {
+    match properties.value(&p.property, &p.value)? {
+        value @ (ScopeValue::Int(_) | ScopeValue::String(_) | ScopeValue::Timestamp(_)) => {
+            Ok(ScopeFilter::compare(&p.property, op, value))
+        }
+        other => Err(format!(
+            "value {other} of property {} is not orderable",
+            p.property
+        )),
+    }
+}
+
+fn require_capability(
+    capabilities: &[Capability],
+    capability: &Capability,
+    op: &str,
+) -> Result<(), String> {
+    if capabilities.contains(capability) {
+        Ok(())
+    } else {
+        Err(format!(
+            "predicate {op} requires the {capability:?} capability"
+        ))
+    }
 }
 
 /// Convert a `serde_json::Value` to a `ScopeValue`.
 ///
-/// UUID strings are detected and stored as `ScopeValue::Uuid`;
-/// other strings become `ScopeValue::String`.
+/// UUID strings are detected and stored as `ScopeValue::Uuid`; other strings
+/// become `ScopeValue::String`.
 fn json_to_scope_value(v: &serde_json::Value) -> Result<ScopeValue, String> {
     match v {
-        serde_json::Value::String(s) => {
-            if let Ok(uuid) = uuid::Uuid::parse_str(s) {
-                Ok(ScopeValue::Uuid(uuid))
-            } else {
-                Ok(ScopeValue::String(s.clone()))
-            }
-        }
+        serde_json::Value::String(s) => Ok(uuid::Uuid::parse_str(s)
+            .map_or_else(|_| ScopeValue::String(s.clone()), ScopeValue::Uuid)),
         serde_json::Value::Number(n) => n.as_i64().map(ScopeValue::Int).ok_or_else(|| {
             format!("only integer JSON numbers are supported for scope filters, got: {n}")
         }),
@@ -163,11 +282,22 @@ fn json_to_scope_value(v: &serde_json::Value) -> Result<ScopeValue, String> {
     }
 }
 
+/// Convert the value of a timestamp-typed property, which must be an RFC 3339 string.
+fn json_to_timestamp(property: &str, v: &serde_json::Value) -> Result<ScopeValue, String> {
+    v.as_str()
+        .and_then(|s| OffsetDateTime::parse(s, &Rfc3339).ok())
+        .map(ScopeValue::Timestamp)
+        .ok_or_else(|| format!("value {v} of timestamp property {property} is not RFC 3339"))
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
-    use crate::constraints::{EqPredicate, InPredicate};
+    use crate::constraints::{
+        ComparisonPredicate, EqPredicate, InGroupPredicate, InGroupSubtreePredicate, InPredicate,
+        InTenantSubtreePredicate,
+    };
     use crate::models::EvaluationResponseContext;
     use modkit_security::pep_properties;
     use serde_json::json;
@@ -197,7 +327,7 @@ mod tests {
             context: EvaluationResponseContext::default(),
         };
 
-        let scope = compile_to_access_scope(&response, false, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, false, DEFAULT_PROPS, &[], &[]).unwrap();
         assert!(scope.is_unconstrained());
     }
 
@@ -216,7 +346,7 @@ mod tests {
             },
         };
 
-        let scope = compile_to_access_scope(&response, false, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, false, DEFAULT_PROPS, &[], &[]).unwrap();
         assert!(!scope.is_unconstrained());
         assert_eq!(
             scope.all_uuid_values_for(pep_properties::OWNER_TENANT_ID),
@@ -231,7 +361,7 @@ mod tests {
             context: EvaluationResponseContext::default(),
         };
 
-        let result = compile_to_access_scope(&response, true, DEFAULT_PROPS);
+        let result = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]);
         assert!(matches!(
             result,
             Err(ConstraintCompileError::ConstraintsRequiredButAbsent)
@@ -255,7 +385,7 @@ mod tests {
             },
         };
 
-        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]).unwrap();
         assert_eq!(
             scope.all_uuid_values_for(pep_properties::OWNER_TENANT_ID),
             &[uuid(T1)]
@@ -286,7 +416,7 @@ mod tests {
             },
         };
 
-        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]).unwrap();
         assert_eq!(
             scope.all_uuid_values_for(pep_properties::OWNER_TENANT_ID),
             &[uuid(T1), uuid(T2)]
@@ -308,7 +438,7 @@ mod tests {
             },
         };
 
-        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]).unwrap();
         assert!(
             scope
                 .all_uuid_values_for(pep_properties::OWNER_TENANT_ID)
@@ -347,7 +477,7 @@ mod tests {
             },
         };
 
-        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]).unwrap();
         // Each constraint is a separate ScopeConstraint (ORed)
         assert_eq!(scope.constraints().len(), 2);
         // Both tenants accessible
@@ -370,7 +500,7 @@ mod tests {
             },
         };
 
-        let result = compile_to_access_scope(&response, true, DEFAULT_PROPS);
+        let result = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]);
         assert!(matches!(
             result,
             Err(ConstraintCompileError::AllConstraintsFailed { .. })
@@ -403,7 +533,7 @@ mod tests {
         };
 
         // Should succeed — the second constraint compiled
-        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]).unwrap();
         assert_eq!(
             scope.all_uuid_values_for(pep_properties::OWNER_TENANT_ID),
             &[uuid(T2)]
@@ -431,7 +561,7 @@ mod tests {
             },
         };
 
-        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]).unwrap();
         // Single constraint with both properties (AND)
         assert_eq!(scope.constraints().len(), 1);
         assert_eq!(
@@ -474,7 +604,7 @@ mod tests {
             },
         };
 
-        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+        let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]).unwrap();
         assert_eq!(scope.constraints().len(), 2);
         // First constraint has 2 filters (AND), second has 1 filter
         assert_eq!(scope.constraints()[0].filters().len(), 2);
@@ -499,7 +629,177 @@ mod tests {
             },
         };
 
-        let result = compile_to_access_scope(&response, true, limited_props);
+        let result = compile_to_access_scope(&response, true, limited_props, &[], &[]);
+        assert!(matches!(
+            result,
+            Err(ConstraintCompileError::AllConstraintsFailed { .. })
+        ));
+    }
+
+    // === Extended Predicates ===
+
+    fn single_constraint(predicates: Vec<Predicate>) -> EvaluationResponse {
+        EvaluationResponse {
+            decision: true,
+            context: EvaluationResponseContext {
+                constraints: vec![Constraint { predicates }],
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn range_predicates_compile_to_compare_filters() {
+        let props: &[&str] = &["created_at", "priority"];
+        let response = single_constraint(vec![
+            Predicate::Gte(ComparisonPredicate::new(
+                "created_at",
+                "2024-01-01T00:00:00Z",
+            )),
+            Predicate::Lt(ComparisonPredicate::new("priority", 10_i64)),
+        ]);
+
+        let scope = compile_to_access_scope(&response, true, props, &["created_at"], &[]).unwrap();
+        let filters = scope.constraints()[0].filters();
+        let ScopeFilter::Compare(created) = &filters[0] else {
+            panic!("expected compare filter");
+        };
+        assert_eq!(created.op(), CompareOp::Gte);
+        assert_eq!(
+            created.value(),
+            &ScopeValue::Timestamp(
+                OffsetDateTime::parse("2024-01-01T00:00:00Z", &Rfc3339).unwrap()
+            )
+        );
+        assert_eq!(
+            filters[1],
+            ScopeFilter::compare("priority", CompareOp::Lt, 10_i64)
+        );
+    }
+
+    #[test]
+    fn only_timestamp_properties_parse_timestamps() {
+        let props: &[&str] = &["created_at", "label"];
+        let response = single_constraint(vec![Predicate::Eq(EqPredicate::new(
+            "label",
+            "2024-01-01T00:00:00Z",
+        ))]);
+        let scope = compile_to_access_scope(&response, true, props, &["created_at"], &[]).unwrap();
+        assert_eq!(
+            scope.constraints()[0].filters()[0],
+            ScopeFilter::eq("label", "2024-01-01T00:00:00Z")
+        );
+
+        let response = single_constraint(vec![Predicate::Gte(ComparisonPredicate::new(
+            "created_at",
+            "yesterday",
+        ))]);
+        let result = compile_to_access_scope(&response, true, props, &["created_at"], &[]);
+        assert!(matches!(
+            result,
+            Err(ConstraintCompileError::AllConstraintsFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn range_predicate_on_unorderable_value_fails() {
+        let response = single_constraint(vec![Predicate::Gt(ComparisonPredicate::new(
+            pep_properties::RESOURCE_ID,
+            uuid(R1),
+        ))]);
+
+        let result = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]);
+        assert!(matches!(
+            result,
+            Err(ConstraintCompileError::AllConstraintsFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn hierarchy_predicates_require_capabilities() {
+        let response = single_constraint(vec![
+            Predicate::InTenantSubtree(InTenantSubtreePredicate::new(
+                pep_properties::OWNER_TENANT_ID,
+                uuid(T1),
+            )),
+            Predicate::InGroup(InGroupPredicate::new(
+                pep_properties::RESOURCE_ID,
+                [uuid(T2)],
+            )),
+        ]);
+
+        for caps in [
+            vec![],
+            vec![Capability::TenantHierarchy],
+            vec![Capability::GroupMembership],
+        ] {
+            let result = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &caps);
+            assert!(
+                matches!(
+                    result,
+                    Err(ConstraintCompileError::AllConstraintsFailed { .. })
+                ),
+                "{caps:?}"
+            );
+        }
+
+        // GroupHierarchy implies GroupMembership.
+        let scope = compile_to_access_scope(
+            &response,
+            true,
+            DEFAULT_PROPS,
+            &[],
+            &[Capability::TenantHierarchy, Capability::GroupHierarchy],
+        )
+        .unwrap();
+        let filters = scope.constraints()[0].filters();
+        let ScopeFilter::InTenantSubtree(subtree) = &filters[0] else {
+            panic!("expected tenant subtree filter");
+        };
+        assert_eq!(subtree.root_tenant_id(), uuid(T1));
+        assert!(subtree.respect_barriers());
+        assert_eq!(
+            filters[1],
+            ScopeFilter::in_group(pep_properties::RESOURCE_ID, vec![uuid(T2)])
+        );
+    }
+
+    #[test]
+    fn group_subtree_and_not_compile() {
+        let response = single_constraint(vec![
+            Predicate::InGroupSubtree(InGroupSubtreePredicate::new(
+                pep_properties::RESOURCE_ID,
+                uuid(T1),
+            )),
+            Predicate::Eq(EqPredicate::new(pep_properties::OWNER_TENANT_ID, uuid(T2))).negate(),
+        ]);
+
+        let scope = compile_to_access_scope(
+            &response,
+            true,
+            DEFAULT_PROPS,
+            &[],
+            &[Capability::GroupHierarchy],
+        )
+        .unwrap();
+        let filters = scope.constraints()[0].filters();
+        assert_eq!(
+            filters[0],
+            ScopeFilter::in_group_subtree(pep_properties::RESOURCE_ID, uuid(T1))
+        );
+        assert_eq!(
+            filters[1],
+            ScopeFilter::negate(ScopeFilter::eq(pep_properties::OWNER_TENANT_ID, uuid(T2)))
+        );
+    }
+
+    #[test]
+    fn not_with_unsupported_property_fails() {
+        let response = single_constraint(vec![
+            Predicate::Eq(EqPredicate::new("secret_flag", true)).negate(),
+        ]);
+
+        let result = compile_to_access_scope(&response, true, DEFAULT_PROPS, &[], &[]);
         assert!(matches!(
             result,
             Err(ConstraintCompileError::AllConstraintsFailed { .. })
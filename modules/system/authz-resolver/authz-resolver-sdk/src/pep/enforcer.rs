@@ -167,6 +167,9 @@ pub struct ResourceType {
     pub name: &'static str,
     /// Properties the PEP can compile from PDP constraints.
     pub supported_properties: &'static [&'static str],
+    /// Supported properties holding timestamps; their values must be RFC 3339
+    /// strings and compile to `ScopeValue::Timestamp`.
+    pub timestamp_properties: &'static [&'static str],
 }
 
 /// Policy Enforcement Point.
@@ -185,6 +188,7 @@ pub struct ResourceType {
 /// const USER: ResourceType = ResourceType {
 ///     name: "gts.x.core.users.user.v1~",
 ///     supported_properties: &[pep_properties::OWNER_TENANT_ID, pep_properties::RESOURCE_ID],
+///     timestamp_properties: &[],
 /// };
 ///
 /// let enforcer = PolicyEnforcer::new(authz.clone());
@@ -355,6 +359,8 @@ impl PolicyEnforcer {
             &response,
             require,
             resource.supported_properties,
+            resource.timestamp_properties,
+            &self.capabilities,
         )?)
     }
 }
@@ -480,6 +486,7 @@ mod tests {
     const TEST_RESOURCE: ResourceType = ResourceType {
         name: "gts.x.core.users.user.v1~",
         supported_properties: &[pep_properties::OWNER_TENANT_ID, pep_properties::RESOURCE_ID],
+        timestamp_properties: &[],
     };
 
     fn enforcer(mock: impl AuthZResolverClient + 'static) -> PolicyEnforcer {
@@ -852,6 +859,7 @@ mod tests {
         const USERS_RESOURCE: ResourceType = ResourceType {
             name: "gts.x.core.users.user.v1~",
             supported_properties: &[pep_properties::OWNER_TENANT_ID],
+            timestamp_properties: &[],
         };
 
         let context_tenant_id = Uuid::parse_str("11111111-1111-1111-1111-111111111111").unwrap();
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use modkit::runtime::InstanceState;
+use modkit::runtime::{InstanceState, MigrationInfo};
 
 use crate::domain::model::{DeploymentMode, InstanceInfo, ModuleInfo};
 
@@ -44,12 +44,28 @@ pub struct ModuleInstanceDto {
     /// Module version (if reported during registration)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
-    /// Current instance state (e.g., "registered", "healthy", "quarantined")
+    /// Current instance state (e.g., "registered", "healthy", "restarting", "failed")
     pub state: String,
+    /// Number of times the process of this instance was restarted
+    pub restart_count: u32,
     /// gRPC services provided by this instance (service name -> endpoint URI)
     pub grpc_services: HashMap<String, String>,
 }
 
+/// Response DTO for one database migration of a module
+#[modkit_macros::api_dto(response)]
+pub struct MigrationDto {
+    /// Migration name
+    pub name: String,
+    /// Whether the migration has been applied
+    pub applied: bool,
+    /// When the migration was applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_at: Option<String>,
+    /// Whether the module still provides this migration
+    pub provided: bool,
+}
+
 /// Response DTO for a plugin (reserved for follow-up implementation)
 #[modkit_macros::api_dto(response)]
 pub struct PluginDto {
@@ -98,9 +114,23 @@ impl From<&InstanceInfo> for ModuleInstanceDto {
                 InstanceState::Healthy => "healthy",
                 InstanceState::Quarantined => "quarantined",
                 InstanceState::Draining => "draining",
+                InstanceState::Restarting => "restarting",
+                InstanceState::Failed => "failed",
             }
             .to_owned(),
+            restart_count: instance.restart_count,
             grpc_services: instance.grpc_services.clone(),
         }
     }
 }
+
+impl From<&MigrationInfo> for MigrationDto {
+    fn from(migration: &MigrationInfo) -> Self {
+        Self {
+            name: migration.name.clone(),
+            applied: migration.applied_at.is_some(),
+            applied_at: migration.applied_at.clone(),
+            provided: migration.known,
+        }
+    }
+}
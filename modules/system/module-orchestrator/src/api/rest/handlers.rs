@@ -1,8 +1,9 @@
 use axum::Extension;
+use axum::extract::Path;
 use modkit::api::prelude::*;
 use std::sync::Arc;
 
-use super::dto::ModuleDto;
+use super::dto::{MigrationDto, ModuleDto};
 use crate::domain::service::ModulesService;
 
 /// List all registered modules with their capabilities, instances, and deployment mode.
@@ -16,3 +17,22 @@ pub async fn list_modules(
     let modules: Vec<ModuleDto> = svc.list_modules().iter().map(ModuleDto::from).collect();
     Ok(Json(modules))
 }
+
+/// List the database migrations of a module, applied and pending, oldest first.
+///
+/// # Errors
+///
+/// Returns `404 Not Found` if the module is unknown.
+pub async fn list_module_migrations(
+    Extension(svc): Extension<Arc<ModulesService>>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<Vec<MigrationDto>>> {
+    let migrations = svc.module_migrations(&name).ok_or_else(|| {
+        Problem::new(
+            StatusCode::NOT_FOUND,
+            "Module not found",
+            format!("Module '{name}' is not registered"),
+        )
+    })?;
+    Ok(Json(migrations.iter().map(MigrationDto::from).collect()))
+}
@@ -3,7 +3,7 @@ use axum::{Extension, Router};
 use modkit::api::{OpenApiRegistry, OperationBuilder};
 use std::sync::Arc;
 
-use super::dto::ModuleDto;
+use super::dto::{MigrationDto, ModuleDto};
 use super::handlers;
 use crate::domain::service::ModulesService;
 
@@ -34,6 +34,28 @@ pub fn register_routes(
         .standard_errors(openapi)
         .register(router, openapi);
 
+    // GET /module-orchestrator/v1/modules/{name}/migrations - Migration history of a module
+    router = OperationBuilder::get("/module-orchestrator/v1/modules/{name}/migrations")
+        .operation_id("module_orchestrator.list_module_migrations")
+        .summary("List database migrations of a module")
+        .description(
+            "Returns the applied and pending database migrations of a module as of the last \
+         migration run. Modules without a database have no migrations.",
+        )
+        .tag("module-orchestrator")
+        .authenticated()
+        .no_license_required()
+        .path_param("name", "Module name")
+        .handler(handlers::list_module_migrations)
+        .json_response_with_schema::<Vec<MigrationDto>>(
+            openapi,
+            http::StatusCode::OK,
+            "Migrations of the module",
+        )
+        .problem_response(openapi, http::StatusCode::NOT_FOUND, "Module not found")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
     router = router.layer(Extension(service));
 
     router
@@ -30,5 +30,6 @@ pub struct InstanceInfo {
     pub instance_id: Uuid,
     pub version: Option<String>,
     pub state: InstanceState,
+    pub restart_count: u32,
     pub grpc_services: HashMap<String, String>,
 }
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use modkit::registry::ModuleRegistry;
-use modkit::runtime::ModuleManager;
+use modkit::runtime::{MigrationHistory, MigrationInfo, ModuleManager};
 use modkit_macros::domain_model;
 
 use super::model::{DeploymentMode, InstanceInfo, ModuleInfo};
@@ -22,6 +22,8 @@ pub struct ModulesService {
     compiled: Vec<CompiledModule>,
     /// Runtime module manager for live instance queries.
     module_manager: Arc<ModuleManager>,
+    /// Migration history recorded by the runtime (absent when not wired).
+    migration_history: Option<Arc<MigrationHistory>>,
 }
 
 impl ModulesService {
@@ -49,9 +51,36 @@ impl ModulesService {
         Self {
             compiled,
             module_manager,
+            migration_history: None,
         }
     }
 
+    /// Serve migration history from the runtime's snapshot.
+    #[must_use]
+    pub fn with_migration_history(mut self, history: Arc<MigrationHistory>) -> Self {
+        self.migration_history = Some(history);
+        self
+    }
+
+    /// Migration history of a module, oldest first.
+    ///
+    /// Returns `None` if the module is unknown, and an empty list for modules without a
+    /// database.
+    #[must_use]
+    pub fn module_migrations(&self, name: &str) -> Option<Vec<MigrationInfo>> {
+        let known = self.compiled.iter().any(|cm| cm.name == name)
+            || !self.module_manager.instances_of(name).is_empty();
+        if !known {
+            return None;
+        }
+        Some(
+            self.migration_history
+                .as_ref()
+                .and_then(|history| history.get(name))
+                .unwrap_or_default(),
+        )
+    }
+
     /// List all registered modules, merging compile-time catalog data with runtime instances.
     #[must_use]
     pub fn list_modules(&self) -> Vec<ModuleInfo> {
@@ -113,6 +142,7 @@ impl ModulesService {
                     instance_id: inst.instance_id,
                     version: inst.version.clone(),
                     state: inst.state(),
+                    restart_count: inst.restart_count(),
                     grpc_services,
                 }
             })
@@ -273,4 +303,28 @@ mod tests {
         assert_eq!(modules[0].name, "alpha");
         assert_eq!(modules[1].name, "zebra");
     }
+
+    #[test]
+    fn module_migrations_distinguish_unknown_modules_from_modules_without_db() {
+        let registry = build_registry(&[
+            ("settings", &[], false, false),
+            ("gateway", &[], true, false),
+        ]);
+        let history = Arc::new(MigrationHistory::default());
+        history.record(
+            "settings",
+            vec![MigrationInfo {
+                name: "m001_initial".to_owned(),
+                applied_at: None,
+                known: true,
+            }],
+        );
+
+        let svc = ModulesService::new(&registry, Arc::new(ModuleManager::new()))
+            .with_migration_history(history);
+
+        assert_eq!(svc.module_migrations("settings").unwrap().len(), 1);
+        assert_eq!(svc.module_migrations("gateway"), Some(vec![]));
+        assert_eq!(svc.module_migrations("missing"), None);
+    }
 }
@@ -13,7 +13,7 @@ use modkit::contracts::{
 };
 use modkit::directory::LocalDirectoryClient;
 use modkit::registry::ModuleRegistry;
-use modkit::runtime::ModuleManager;
+use modkit::runtime::{MigrationHistory, ModuleManager};
 
 use cf_system_sdks::directory::DIRECTORY_SERVICE_NAME;
 
@@ -40,6 +40,7 @@ pub struct ModuleOrchestrator {
     config: RwLock<ModuleOrchestratorConfig>,
     directory_api: OnceLock<Arc<dyn DirectoryClient>>,
     module_manager: OnceLock<Arc<ModuleManager>>,
+    migration_history: OnceLock<Arc<MigrationHistory>>,
     modules_service: OnceLock<Arc<ModulesService>>,
 }
 
@@ -49,6 +50,7 @@ impl Default for ModuleOrchestrator {
             config: RwLock::new(ModuleOrchestratorConfig),
             directory_api: OnceLock::new(),
             module_manager: OnceLock::new(),
+            migration_history: OnceLock::new(),
             modules_service: OnceLock::new(),
         }
     }
@@ -60,6 +62,11 @@ impl SystemCapability for ModuleOrchestrator {
         self.module_manager
             .set(Arc::clone(&sys.module_manager))
             .map_err(|_| anyhow::anyhow!("ModuleManager already set (pre_init called twice?)"))?;
+        self.migration_history
+            .set(Arc::clone(&sys.migration_history))
+            .map_err(|_| {
+                anyhow::anyhow!("MigrationHistory already set (pre_init called twice?)")
+            })?;
         Ok(())
     }
 }
@@ -91,7 +98,11 @@ impl modkit::Module for ModuleOrchestrator {
         // Build compiled-module catalog from inventory and create the ModulesService
         let registry = ModuleRegistry::discover_and_build()
             .map_err(|e| anyhow::anyhow!("Failed to build module registry: {e}"))?;
-        let modules_service = Arc::new(ModulesService::new(&registry, manager));
+        let mut modules_service = ModulesService::new(&registry, manager);
+        if let Some(history) = self.migration_history.get() {
+            modules_service = modules_service.with_migration_history(Arc::clone(history));
+        }
+        let modules_service = Arc::new(modules_service);
         self.modules_service
             .set(modules_service)
             .map_err(|_| anyhow::anyhow!("ModulesService already set (init called twice?)"))?;
@@ -0,0 +1,113 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! End-to-end tests for the `GET /module-orchestrator/v1/modules/{name}/migrations` REST endpoint.
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use modkit::registry::RegistryBuilder;
+use modkit::runtime::{MigrationHistory, MigrationInfo, ModuleManager};
+use module_orchestrator::api::rest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use module_orchestrator::domain::service::ModulesService;
+
+#[derive(Default)]
+struct DummyCore;
+#[async_trait::async_trait]
+impl modkit::Module for DummyCore {
+    async fn init(&self, _ctx: &modkit::context::ModuleCtx) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+fn build_router(modules: &[&'static str], history: Arc<MigrationHistory>) -> Router {
+    let mut b = RegistryBuilder::default();
+    for &name in modules {
+        b.register_core_with_meta(name, &[], Arc::new(DummyCore));
+    }
+    let registry = b.build_topo_sorted().unwrap();
+
+    let svc = Arc::new(
+        ModulesService::new(&registry, Arc::new(ModuleManager::new()))
+            .with_migration_history(history),
+    );
+    let openapi = api_gateway::ApiGateway::default();
+    rest::routes::register_routes(Router::new(), &openapi, svc)
+}
+
+async fn get_migrations(router: Router, module: &str) -> (StatusCode, serde_json::Value) {
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!(
+                    "/module-orchestrator/v1/modules/{module}/migrations"
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    (status, json)
+}
+
+#[tokio::test]
+async fn returns_recorded_history() {
+    let history = Arc::new(MigrationHistory::default());
+    history.record(
+        "settings",
+        vec![
+            MigrationInfo {
+                name: "m001_initial".to_owned(),
+                applied_at: Some("2024-01-01 00:00:00".to_owned()),
+                known: true,
+            },
+            MigrationInfo {
+                name: "m002_add_theme".to_owned(),
+                applied_at: None,
+                known: true,
+            },
+        ],
+    );
+    let router = build_router(&["settings"], history);
+
+    let (status, json) = get_migrations(router, "settings").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let migrations = json.as_array().unwrap();
+    assert_eq!(migrations.len(), 2);
+    assert_eq!(migrations[0]["name"], "m001_initial");
+    assert_eq!(migrations[0]["applied"], true);
+    assert_eq!(migrations[0]["applied_at"], "2024-01-01 00:00:00");
+    assert_eq!(migrations[0]["provided"], true);
+    assert_eq!(migrations[1]["applied"], false);
+    assert!(migrations[1].get("applied_at").is_none());
+}
+
+#[tokio::test]
+async fn module_without_database_has_no_migrations() {
+    let router = build_router(&["api_gateway"], Arc::default());
+
+    let (status, json) = get_migrations(router, "api_gateway").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(json.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn unknown_module_returns_404() {
+    let router = build_router(&["api_gateway"], Arc::default());
+
+    let (status, json) = get_migrations(router, "missing").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(json["status"], 404);
+}
@@ -1,3 +1,6 @@
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
 use modkit_security::SecurityContext;
 use uuid::Uuid;
 
@@ -32,6 +35,42 @@ impl ErrorSource {
     }
 }
 
+/// Address of the client that sent a proxied request.
+///
+/// Set by the transport as a request extension
+/// (`req.extensions_mut().insert(ClientIp(addr))`); used for IP-scoped rate
+/// limits. Requests without it share a single IP bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Trailers sent by the upstream after a streamed response body, e.g. gRPC
+/// `grpc-status` and `grpc-message`.
+///
+/// Available on gRPC proxy responses via
+/// `resp.extensions().get::<ResponseTrailers>()`. Empty until the response
+/// body has been read to the end.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseTrailers(Arc<Mutex<Option<http::HeaderMap>>>);
+
+impl ResponseTrailers {
+    /// Record the trailers. Called by the gateway when the body ends.
+    pub fn set(&self, trailers: http::HeaderMap) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(trailers);
+    }
+
+    /// The trailers, once the body has ended with them.
+    #[must_use]
+    pub fn get(&self) -> Option<http::HeaderMap> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Take the trailers, leaving none behind.
+    #[must_use]
+    pub fn take(&self) -> Option<http::HeaderMap> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Service trait
 // ---------------------------------------------------------------------------
@@ -132,13 +171,27 @@ pub trait ServiceGatewayClientV1: Send + Sync {
     ///
     /// # Protocol mapping
     ///
-    /// All three protocols map to `Request<Body> → Response<Body>`:
+    /// All protocols map to `Request<Body> → Response<Body>`:
     ///
     /// | Protocol  | Request Body          | Response Body          |
     /// |-----------|-----------------------|------------------------|
     /// | HTTP      | `Body::Bytes`/`Empty` | `Body::Bytes`          |
     /// | SSE       | `Body::Bytes`/`Empty` | `Body::Stream`         |
     /// | WebSocket | `Body::Stream`        | `Body::Stream`         |
+    /// | gRPC      | `Body::Bytes`/`Stream`| `Body::Stream`         |
+    ///
+    /// A WebSocket request is a `GET` with `Upgrade: websocket`. On success
+    /// the response is `101 Switching Protocols`; each request body chunk is
+    /// sent upstream as one message and each upstream message arrives as one
+    /// response body chunk. Chunks start with the message opcode (see
+    /// [`WebSocketMessage::to_body_chunk`](crate::ws::WebSocketMessage::to_body_chunk)).
+    /// The upstream connection closes when the request body ends.
+    ///
+    /// A gRPC request carries `content-type: application/grpc`. A streamed
+    /// body is forwarded as it is produced, so client-streaming and
+    /// bidirectional calls work. Its trailers
+    /// are available via `resp.extensions().get::<ResponseTrailers>()` once
+    /// the response body has been consumed.
     async fn proxy_request(
         &self,
         ctx: SecurityContext,
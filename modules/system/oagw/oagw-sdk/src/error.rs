@@ -16,6 +16,10 @@ pub enum ServiceGatewayError {
     #[error("{detail}")]
     AuthenticationFailed { detail: String, instance: String },
 
+    /// Request rejected by a guard plugin (e.g. CORS origin not allowed).
+    #[error("{detail}")]
+    GuardRejected { detail: String, instance: String },
+
     #[error("{entity} not found")]
     NotFound { entity: String, instance: String },
 
@@ -32,6 +36,20 @@ pub enum ServiceGatewayError {
         retry_after_secs: Option<u64>,
     },
 
+    #[error("{detail}")]
+    QueueTimeout {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
     #[error("{detail}")]
     SecretNotFound { detail: String, instance: String },
 
@@ -8,9 +8,12 @@ pub mod ws;
 pub mod models;
 
 pub use models::{
-    AuthConfig, BurstConfig, CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
-    CreateUpstreamRequestBuilder, Endpoint, GrpcMatch, HeadersConfig, HttpMatch, HttpMethod,
-    ListQuery, MatchRules, PassthroughMode, PathSuffixMode, PluginsConfig, RateLimitAlgorithm,
+    AuthConfig, BurstConfig, CacheConfig, CircuitBreakerConfig, CircuitBreakerScope,
+    CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
+    CreateUpstreamRequestBuilder, DegradeConfig, Endpoint, FailureConditions, FallbackResponse,
+    GrpcMatch, HeadersConfig, HealthCheckConfig, HttpMatch, HttpMethod, ListQuery,
+    LoadBalancingConfig, LoadBalancingStrategy, MatchRules, OutlierDetectionConfig,
+    PassthroughMode, PathSuffixMode, PluginsConfig, QueueConfig, RateLimitAlgorithm,
     RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules, ResponseHeaderRules,
     Route, Scheme, Server, SharingMode, SustainedRate, UpdateRouteRequest,
     UpdateRouteRequestBuilder, UpdateUpstreamRequest, UpdateUpstreamRequestBuilder, Upstream,
@@ -5,6 +5,7 @@
 //! serialization concerns belong to the REST layer.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use uuid::Uuid;
 
@@ -42,6 +43,8 @@ pub struct Endpoint {
     pub scheme: Scheme,
     pub host: String,
     pub port: u16,
+    /// Relative share of traffic under [`LoadBalancingStrategy::Weighted`]. Defaults to 1.
+    pub weight: u32,
 }
 
 impl Endpoint {
@@ -67,6 +70,90 @@ pub struct Server {
     pub endpoints: Vec<Endpoint>,
 }
 
+// ---------------------------------------------------------------------------
+// LoadBalancingConfig
+// ---------------------------------------------------------------------------
+
+/// How requests are distributed across the endpoints of a multi-endpoint upstream.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LoadBalancingConfig {
+    pub strategy: LoadBalancingStrategy,
+    /// Active HTTP probing. When absent, only passive outlier detection applies.
+    pub health_check: Option<HealthCheckConfig>,
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    /// Pick the endpoint with the fewest requests currently in flight.
+    LeastInFlight,
+    /// Smooth weighted round-robin using [`Endpoint::weight`].
+    Weighted,
+}
+
+/// Active HTTP health probe settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    /// Path probed with `GET` on every endpoint (must start with `/`).
+    pub path: String,
+    pub interval_secs: u32,
+    pub timeout_secs: u32,
+    /// Consecutive successful probes before an unhealthy endpoint is restored.
+    pub healthy_threshold: u32,
+    /// Consecutive failed probes before an endpoint is marked unhealthy.
+    pub unhealthy_threshold: u32,
+}
+
+/// Passive outlier ejection based on proxied request outcomes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlierDetectionConfig {
+    /// Consecutive failures (connect errors, timeouts, 5xx) that trigger ejection.
+    pub consecutive_failures: u32,
+    /// Base ejection time; multiplied by the number of times the endpoint was ejected.
+    pub base_ejection_secs: u32,
+    /// Upper bound on the share of the pool that may be ejected at once (0-100).
+    pub max_ejection_percent: u32,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Closed/open/half-open circuit breaker guarding an upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    /// Consecutive failures that open a closed circuit.
+    pub failure_threshold: u32,
+    /// Consecutive half-open successes that close the circuit again.
+    pub success_threshold: u32,
+    /// How long an open circuit rejects requests before admitting probes.
+    pub timeout_secs: u32,
+    /// Concurrent requests admitted while half-open.
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+/// Which upstream outcomes count as circuit breaker failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    /// One circuit for the whole upstream.
+    #[default]
+    Global,
+    /// One circuit per endpoint; open endpoints are skipped by load balancing.
+    PerEndpoint,
+}
+
 // ---------------------------------------------------------------------------
 // AuthConfig
 // ---------------------------------------------------------------------------
@@ -138,6 +225,12 @@ pub struct RateLimitConfig {
     pub scope: RateLimitScope,
     pub strategy: RateLimitStrategy,
     pub cost: u32,
+    /// Wait queue used when `strategy` is [`RateLimitStrategy::Queue`].
+    pub queue: Option<QueueConfig>,
+    /// Fallback used when `strategy` is [`RateLimitStrategy::Degrade`].
+    pub degrade: Option<DegradeConfig>,
+    /// Emit `X-RateLimit-*` headers on successful responses.
+    pub response_headers: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -189,6 +282,50 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+/// Bounded wait queue for requests that exceed the rate limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// Maximum number of requests waiting per limiter key.
+    pub max_depth: u32,
+    /// Maximum time a request may wait for capacity.
+    pub timeout: Duration,
+}
+
+/// Degraded-mode behaviour for requests that exceed the rate limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DegradeConfig {
+    pub fallback_response: FallbackResponse,
+}
+
+/// Response served by the gateway instead of calling the upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: String,
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+/// Opt-in HTTP response caching for a route.
+///
+/// Only `GET` responses are cached. Freshness follows the upstream
+/// `Cache-Control`/`Expires` headers; `default_ttl_secs` applies when the
+/// upstream sends none, and every lifetime is capped at `max_ttl_secs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// Freshness lifetime for responses without explicit caching headers
+    /// (0 = store only when the upstream allows it).
+    pub default_ttl_secs: u32,
+    /// Upper bound on any freshness lifetime.
+    pub max_ttl_secs: u32,
+    /// Responses with larger bodies are proxied but never stored.
+    pub max_entry_bytes: u64,
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -199,6 +336,9 @@ pub struct PluginsConfig {
     pub sharing: SharingMode,
     /// Plugin references: GTS identifiers (builtin) or UUIDs (custom).
     pub items: Vec<String>,
+    /// Per-plugin configuration keyed by plugin reference (flat key-value
+    /// pairs; schema varies by plugin).
+    pub config: HashMap<String, HashMap<String, String>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -262,6 +402,7 @@ pub struct Route {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -281,6 +422,8 @@ pub struct Upstream {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
 }
 
@@ -317,6 +460,8 @@ pub struct CreateUpstreamRequest {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -332,6 +477,8 @@ impl CreateUpstreamRequest {
             headers: None,
             plugins: None,
             rate_limit: None,
+            load_balancing: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -358,6 +505,12 @@ impl CreateUpstreamRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn load_balancing(&self) -> Option<&LoadBalancingConfig> {
+        self.load_balancing.as_ref()
+    }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -374,6 +527,8 @@ pub struct CreateUpstreamRequestBuilder {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -399,6 +554,14 @@ impl CreateUpstreamRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn load_balancing(mut self, load_balancing: LoadBalancingConfig) -> Self {
+        self.load_balancing = Some(load_balancing);
+        self
+    }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -416,6 +579,8 @@ impl CreateUpstreamRequestBuilder {
             headers: self.headers,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            load_balancing: self.load_balancing,
+            circuit_breaker: self.circuit_breaker,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -433,6 +598,8 @@ pub struct UpdateUpstreamRequest {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
 }
@@ -464,6 +631,12 @@ impl UpdateUpstreamRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn load_balancing(&self) -> Option<&LoadBalancingConfig> {
+        self.load_balancing.as_ref()
+    }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
     pub fn tags(&self) -> Option<&[String]> {
         self.tags.as_deref()
     }
@@ -481,6 +654,8 @@ pub struct UpdateUpstreamRequestBuilder {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
 }
@@ -514,6 +689,14 @@ impl UpdateUpstreamRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn load_balancing(mut self, load_balancing: LoadBalancingConfig) -> Self {
+        self.load_balancing = Some(load_balancing);
+        self
+    }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
         self
@@ -531,6 +714,8 @@ impl UpdateUpstreamRequestBuilder {
             headers: self.headers,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            load_balancing: self.load_balancing,
+            circuit_breaker: self.circuit_breaker,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -548,6 +733,7 @@ pub struct CreateRouteRequest {
     match_rules: MatchRules,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -561,6 +747,7 @@ impl CreateRouteRequest {
             match_rules,
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -579,6 +766,9 @@ impl CreateRouteRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn cache(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -595,6 +785,7 @@ pub struct CreateRouteRequestBuilder {
     match_rules: MatchRules,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -609,6 +800,10 @@ impl CreateRouteRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -627,6 +822,7 @@ impl CreateRouteRequestBuilder {
             match_rules: self.match_rules,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            cache: self.cache,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -641,6 +837,7 @@ pub struct UpdateRouteRequest {
     match_rules: Option<MatchRules>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
@@ -661,6 +858,9 @@ impl UpdateRouteRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn cache(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref()
+    }
     pub fn tags(&self) -> Option<&[String]> {
         self.tags.as_deref()
     }
@@ -677,6 +877,7 @@ pub struct UpdateRouteRequestBuilder {
     match_rules: Option<MatchRules>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
@@ -695,6 +896,10 @@ impl UpdateRouteRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
         self
@@ -712,6 +917,7 @@ impl UpdateRouteRequestBuilder {
             match_rules: self.match_rules,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            cache: self.cache,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -733,6 +939,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "api.openai.com");
     }
@@ -743,6 +950,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "example.com".into(),
             port: 80,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "example.com");
     }
@@ -753,6 +961,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 8443,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "api.openai.com:8443");
     }
@@ -768,6 +977,7 @@ mod tests {
             scheme: Scheme::Wss,
             host: "stream.example.com".into(),
             port: 9090,
+            weight: 1,
         };
         let ep2 = ep.clone();
         assert_eq!(ep, ep2);
@@ -790,6 +1000,7 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
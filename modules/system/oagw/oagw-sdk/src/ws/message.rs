@@ -2,6 +2,7 @@
 
 use std::pin::Pin;
 
+use bytes::{BufMut, Bytes, BytesMut};
 use futures_core::Stream;
 use futures_util::sink::Sink;
 
@@ -22,6 +23,51 @@ pub enum WebSocketMessage {
     Close(Option<WebSocketCloseFrame>),
 }
 
+/// Opcode byte of a text message chunk (RFC 6455 section 5.2).
+pub const TEXT_OPCODE: u8 = 0x1;
+
+/// Opcode byte of a binary message chunk (RFC 6455 section 5.2).
+pub const BINARY_OPCODE: u8 = 0x2;
+
+impl WebSocketMessage {
+    /// Encode a data message as a proxy body chunk: its opcode byte
+    /// ([`TEXT_OPCODE`] or [`BINARY_OPCODE`]) followed by the payload.
+    ///
+    /// Returns `None` for control frames, which never travel as chunks.
+    #[must_use]
+    pub fn to_body_chunk(&self) -> Option<Bytes> {
+        let (opcode, payload) = match self {
+            Self::Text(text) => (TEXT_OPCODE, text.as_bytes()),
+            Self::Binary(data) => (BINARY_OPCODE, data.as_slice()),
+            Self::Ping(_) | Self::Pong(_) | Self::Close(_) => return None,
+        };
+        let mut chunk = BytesMut::with_capacity(payload.len() + 1);
+        chunk.put_u8(opcode);
+        chunk.put_slice(payload);
+        Some(chunk.freeze())
+    }
+
+    /// Decode a proxy body chunk produced by [`Self::to_body_chunk`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StreamingError::WebSocketBridge`] for a chunk without a known
+    /// opcode, or a text chunk that is not valid UTF-8.
+    pub fn from_body_chunk(chunk: &[u8]) -> Result<Self, StreamingError> {
+        match chunk.split_first() {
+            Some((&TEXT_OPCODE, payload)) => String::from_utf8(payload.to_vec())
+                .map(Self::Text)
+                .map_err(|_| StreamingError::WebSocketBridge {
+                    detail: "text message is not valid UTF-8".into(),
+                }),
+            Some((&BINARY_OPCODE, payload)) => Ok(Self::Binary(payload.to_vec())),
+            _ => Err(StreamingError::WebSocketBridge {
+                detail: "message chunk does not start with a text or binary opcode".into(),
+            }),
+        }
+    }
+}
+
 /// WebSocket close frame with status code and reason.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WebSocketCloseFrame {
@@ -37,3 +83,25 @@ pub type WebSocketSink = Pin<Box<dyn Sink<WebSocketMessage, Error = StreamingErr
 /// A stream for receiving WebSocket messages.
 pub type WebSocketReceiver =
     Pin<Box<dyn Stream<Item = Result<WebSocketMessage, StreamingError>> + Send>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_chunks_carry_the_opcode() {
+        let text = WebSocketMessage::Text("hi".into());
+        let chunk = text.to_body_chunk().unwrap();
+        assert_eq!(&chunk[..], &[TEXT_OPCODE, b'h', b'i']);
+        assert_eq!(WebSocketMessage::from_body_chunk(&chunk).unwrap(), text);
+
+        // Valid UTF-8 sent as binary stays binary.
+        let binary = WebSocketMessage::Binary(b"hi".to_vec());
+        let chunk = binary.to_body_chunk().unwrap();
+        assert_eq!(WebSocketMessage::from_body_chunk(&chunk).unwrap(), binary);
+
+        assert!(WebSocketMessage::Ping(vec![]).to_body_chunk().is_none());
+        assert!(WebSocketMessage::from_body_chunk(&[]).is_err());
+        assert!(WebSocketMessage::from_body_chunk(&[TEXT_OPCODE, 0xff]).is_err());
+    }
+}
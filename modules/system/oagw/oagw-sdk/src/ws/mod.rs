@@ -3,5 +3,8 @@ pub mod axum_adapter;
 mod message;
 mod stream;
 
-pub use message::{WebSocketCloseFrame, WebSocketMessage, WebSocketReceiver, WebSocketSink};
+pub use message::{
+    BINARY_OPCODE, TEXT_OPCODE, WebSocketCloseFrame, WebSocketMessage, WebSocketReceiver,
+    WebSocketSink,
+};
 pub use stream::{FromWebSocketMessage, WebSocketSender, WebSocketStream, WebSocketStreamReceiver};
@@ -4,7 +4,6 @@ use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use bytes::Bytes;
 use futures_core::Stream;
 use futures_util::{SinkExt, StreamExt};
 
@@ -200,10 +199,11 @@ impl<T: FromWebSocketMessage> WebSocketSender<T> {
 }
 
 impl WebSocketSender {
-    /// Forward a [`BodyStream`] as WebSocket text messages.
+    /// Forward a [`BodyStream`] of message chunks as WebSocket messages.
     ///
-    /// Each `Bytes` chunk from the stream is sent as a `Text` message.
-    /// Completes when the stream ends or an error occurs.
+    /// Each chunk is decoded with [`WebSocketMessage::from_body_chunk`], so
+    /// text and binary messages keep their type. Completes when the stream
+    /// ends or an error occurs.
     pub async fn forward_body_stream(
         &mut self,
         mut stream: BodyStream,
@@ -211,10 +211,7 @@ impl WebSocketSender {
         while let Some(chunk) = stream.next().await {
             match chunk {
                 Ok(bytes) => {
-                    let msg = match String::from_utf8(bytes.to_vec()) {
-                        Ok(text) => WebSocketMessage::Text(text),
-                        Err(e) => WebSocketMessage::Binary(e.into_bytes()),
-                    };
+                    let msg = WebSocketMessage::from_body_chunk(&bytes)?;
                     self.sink.send(msg).await?;
                 }
                 Err(e) => return Err(StreamingError::Stream(e)),
@@ -251,22 +248,21 @@ impl<T: FromWebSocketMessage> WebSocketStreamReceiver<T> {
 impl WebSocketStreamReceiver {
     /// Convert this receiver into a [`BodyStream`] for use as a proxy request body.
     ///
-    /// Text and Binary messages become `Bytes` chunks. Control frames (Ping, Pong)
-    /// are filtered. The stream terminates on Close or end-of-stream.
+    /// Text and Binary messages become chunks encoded with
+    /// [`WebSocketMessage::to_body_chunk`]. Control frames (Ping, Pong) are
+    /// filtered. The stream terminates on Close or end-of-stream.
     pub fn into_body_stream(self) -> BodyStream {
         Box::pin(futures_util::stream::unfold(
             self.receiver,
             |mut rx| async {
                 loop {
                     match rx.next().await? {
-                        Ok(WebSocketMessage::Text(text)) => {
-                            return Some((Ok(Bytes::from(text)), rx));
-                        }
-                        Ok(WebSocketMessage::Binary(data)) => {
-                            return Some((Ok(Bytes::from(data)), rx));
-                        }
                         Ok(WebSocketMessage::Close(_)) => return None,
-                        Ok(_) => continue,
+                        Ok(msg) => {
+                            if let Some(chunk) = msg.to_body_chunk() {
+                                return Some((Ok(chunk), rx));
+                            }
+                        }
                         Err(e) => {
                             return Some((Err(Box::new(e) as BoxError), rx));
                         }
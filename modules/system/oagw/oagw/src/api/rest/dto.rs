@@ -4,6 +4,7 @@
 // to/from internal domain types via `From` impls for the service layer boundary.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -45,17 +46,167 @@ pub struct Endpoint {
     pub host: String,
     #[serde(default = "default_port")]
     pub port: u16,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
 }
 
 fn default_port() -> u16 {
     443
 }
 
+fn default_weight() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Server {
     pub endpoints: Vec<Endpoint>,
 }
 
+// ---------------------------------------------------------------------------
+// LoadBalancingConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+pub struct LoadBalancingConfig {
+    #[serde(default)]
+    pub strategy: LoadBalancingStrategy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    LeastInFlight,
+    Weighted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct HealthCheckConfig {
+    pub path: String,
+    #[serde(default = "default_health_interval_secs")]
+    pub interval_secs: u32,
+    #[serde(default = "default_health_timeout_secs")]
+    pub timeout_secs: u32,
+    #[serde(default = "default_healthy_threshold")]
+    pub healthy_threshold: u32,
+    #[serde(default = "default_unhealthy_threshold")]
+    pub unhealthy_threshold: u32,
+}
+
+fn default_health_interval_secs() -> u32 {
+    10
+}
+
+fn default_health_timeout_secs() -> u32 {
+    2
+}
+
+fn default_healthy_threshold() -> u32 {
+    2
+}
+
+fn default_unhealthy_threshold() -> u32 {
+    3
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OutlierDetectionConfig {
+    #[serde(default = "default_consecutive_failures")]
+    pub consecutive_failures: u32,
+    #[serde(default = "default_base_ejection_secs")]
+    pub base_ejection_secs: u32,
+    #[serde(default = "default_max_ejection_percent")]
+    pub max_ejection_percent: u32,
+}
+
+fn default_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_base_ejection_secs() -> u32 {
+    30
+}
+
+fn default_max_ejection_percent() -> u32 {
+    50
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CircuitBreakerConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default = "default_success_threshold")]
+    pub success_threshold: u32,
+    #[serde(default = "default_open_timeout_secs")]
+    pub timeout_secs: u32,
+    #[serde(default = "default_half_open_max_requests")]
+    pub half_open_max_requests: u32,
+    #[serde(default)]
+    pub failure_conditions: FailureConditions,
+    #[serde(default)]
+    pub scope: CircuitBreakerScope,
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_success_threshold() -> u32 {
+    3
+}
+
+fn default_open_timeout_secs() -> u32 {
+    30
+}
+
+fn default_half_open_max_requests() -> u32 {
+    3
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FailureConditions {
+    #[serde(default = "default_failure_status_codes")]
+    pub status_codes: Vec<u16>,
+    #[serde(default = "default_true")]
+    pub timeout: bool,
+    #[serde(default = "default_true")]
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: default_failure_status_codes(),
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+fn default_failure_status_codes() -> Vec<u16> {
+    vec![500, 502, 503, 504]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerScope {
+    #[default]
+    Global,
+    PerEndpoint,
+}
+
 // ---------------------------------------------------------------------------
 // AuthConfig
 // ---------------------------------------------------------------------------
@@ -134,6 +285,12 @@ pub struct RateLimitConfig {
     pub strategy: RateLimitStrategy,
     #[serde(default = "default_cost")]
     pub cost: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<QueueConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degrade: Option<DegradeConfig>,
+    #[serde(default = "default_true")]
+    pub response_headers: bool,
 }
 
 fn default_cost() -> u32 {
@@ -190,6 +347,74 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QueueConfig {
+    #[serde(default = "default_queue_max_depth")]
+    pub max_depth: u32,
+    /// Maximum wait, as a humantime duration (e.g. `"2s"`).
+    #[serde(
+        default = "default_queue_timeout",
+        with = "modkit_utils::humantime_serde"
+    )]
+    #[schema(value_type = String, example = "2s")]
+    pub timeout: Duration,
+}
+
+fn default_queue_max_depth() -> u32 {
+    100
+}
+
+fn default_queue_timeout() -> Duration {
+    Duration::from_secs(5)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DegradeConfig {
+    pub fallback_response: FallbackResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FallbackResponse {
+    #[serde(default = "default_fallback_status")]
+    pub status: u16,
+    #[serde(default = "default_fallback_content_type")]
+    pub content_type: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+fn default_fallback_status() -> u16 {
+    503
+}
+
+fn default_fallback_content_type() -> String {
+    "application/json".to_string()
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CacheConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub default_ttl_secs: u32,
+    #[serde(default = "default_cache_max_ttl_secs")]
+    pub max_ttl_secs: u32,
+    #[serde(default = "default_cache_max_entry_bytes")]
+    pub max_entry_bytes: u64,
+}
+
+fn default_cache_max_ttl_secs() -> u32 {
+    3600
+}
+
+fn default_cache_max_entry_bytes() -> u64 {
+    1024 * 1024
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -200,6 +425,9 @@ pub struct PluginsConfig {
     pub sharing: SharingMode,
     #[serde(default)]
     pub items: Vec<String>,
+    /// Per-plugin configuration keyed by plugin reference.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub config: HashMap<String, HashMap<String, String>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -267,6 +495,10 @@ pub struct CreateUpstreamRequest {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_balancing: Option<LoadBalancingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default = "default_true")]
@@ -290,6 +522,10 @@ pub struct UpdateUpstreamRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_balancing: Option<LoadBalancingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
@@ -308,6 +544,8 @@ pub struct CreateRouteRequest {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default)]
@@ -325,6 +563,8 @@ pub struct UpdateRouteRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub priority: Option<i32>,
@@ -352,10 +592,57 @@ pub struct UpstreamResponse {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_balancing: Option<LoadBalancingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EndpointStatusResponse {
+    pub host: String,
+    pub port: u16,
+    pub healthy: bool,
+    pub ejected: bool,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ejection_remaining_secs: u64,
+    pub in_flight: u64,
+    pub consecutive_failures: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerStatusResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpstreamStatusResponse {
+    pub id: String,
+    pub endpoints: Vec<EndpointStatusResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerStatusResponse>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    HalfOpen,
+    Open,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CircuitBreakerStatusResponse {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub retry_after_secs: u64,
+    pub failures_total: u64,
+    pub rejected_requests_total: u64,
+    pub state_changes_total: u64,
+    pub half_open_successes_total: u64,
+    pub half_open_failures_total: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct RouteResponse {
     pub id: String,
@@ -367,6 +654,8 @@ pub struct RouteResponse {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
     pub priority: i32,
@@ -405,6 +694,7 @@ impl From<Endpoint> for domain::Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -417,6 +707,81 @@ impl From<Server> for domain::Server {
     }
 }
 
+impl From<LoadBalancingStrategy> for domain::LoadBalancingStrategy {
+    fn from(v: LoadBalancingStrategy) -> Self {
+        match v {
+            LoadBalancingStrategy::RoundRobin => Self::RoundRobin,
+            LoadBalancingStrategy::LeastInFlight => Self::LeastInFlight,
+            LoadBalancingStrategy::Weighted => Self::Weighted,
+        }
+    }
+}
+
+impl From<HealthCheckConfig> for domain::HealthCheckConfig {
+    fn from(v: HealthCheckConfig) -> Self {
+        Self {
+            path: v.path,
+            interval_secs: v.interval_secs,
+            timeout_secs: v.timeout_secs,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+        }
+    }
+}
+
+impl From<OutlierDetectionConfig> for domain::OutlierDetectionConfig {
+    fn from(v: OutlierDetectionConfig) -> Self {
+        Self {
+            consecutive_failures: v.consecutive_failures,
+            base_ejection_secs: v.base_ejection_secs,
+            max_ejection_percent: v.max_ejection_percent,
+        }
+    }
+}
+
+impl From<LoadBalancingConfig> for domain::LoadBalancingConfig {
+    fn from(v: LoadBalancingConfig) -> Self {
+        Self {
+            strategy: v.strategy.into(),
+            health_check: v.health_check.map(Into::into),
+            outlier_detection: v.outlier_detection.map(Into::into),
+        }
+    }
+}
+
+impl From<FailureConditions> for domain::FailureConditions {
+    fn from(v: FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<CircuitBreakerScope> for domain::CircuitBreakerScope {
+    fn from(v: CircuitBreakerScope) -> Self {
+        match v {
+            CircuitBreakerScope::Global => Self::Global,
+            CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            success_threshold: v.success_threshold,
+            timeout_secs: v.timeout_secs,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
 impl From<AuthConfig> for domain::AuthConfig {
     fn from(v: AuthConfig) -> Self {
         Self {
@@ -537,6 +902,41 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
             scope: v.scope.into(),
             strategy: v.strategy.into(),
             cost: v.cost,
+            queue: v.queue.map(Into::into),
+            degrade: v.degrade.map(Into::into),
+            response_headers: v.response_headers,
+        }
+    }
+}
+
+impl From<QueueConfig> for domain::QueueConfig {
+    fn from(v: QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            timeout: v.timeout,
+        }
+    }
+}
+
+impl From<DegradeConfig> for domain::DegradeConfig {
+    fn from(v: DegradeConfig) -> Self {
+        Self {
+            fallback_response: domain::FallbackResponse {
+                status: v.fallback_response.status,
+                content_type: v.fallback_response.content_type,
+                body: v.fallback_response.body,
+            },
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_ttl_secs: v.max_ttl_secs,
+            max_entry_bytes: v.max_entry_bytes,
         }
     }
 }
@@ -546,6 +946,7 @@ impl From<PluginsConfig> for domain::PluginsConfig {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -632,6 +1033,7 @@ impl From<domain::Endpoint> for Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -644,6 +1046,121 @@ impl From<domain::Server> for Server {
     }
 }
 
+impl From<domain::LoadBalancingStrategy> for LoadBalancingStrategy {
+    fn from(v: domain::LoadBalancingStrategy) -> Self {
+        match v {
+            domain::LoadBalancingStrategy::RoundRobin => Self::RoundRobin,
+            domain::LoadBalancingStrategy::LeastInFlight => Self::LeastInFlight,
+            domain::LoadBalancingStrategy::Weighted => Self::Weighted,
+        }
+    }
+}
+
+impl From<domain::HealthCheckConfig> for HealthCheckConfig {
+    fn from(v: domain::HealthCheckConfig) -> Self {
+        Self {
+            path: v.path,
+            interval_secs: v.interval_secs,
+            timeout_secs: v.timeout_secs,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+        }
+    }
+}
+
+impl From<domain::OutlierDetectionConfig> for OutlierDetectionConfig {
+    fn from(v: domain::OutlierDetectionConfig) -> Self {
+        Self {
+            consecutive_failures: v.consecutive_failures,
+            base_ejection_secs: v.base_ejection_secs,
+            max_ejection_percent: v.max_ejection_percent,
+        }
+    }
+}
+
+impl From<domain::LoadBalancingConfig> for LoadBalancingConfig {
+    fn from(v: domain::LoadBalancingConfig) -> Self {
+        Self {
+            strategy: v.strategy.into(),
+            health_check: v.health_check.map(Into::into),
+            outlier_detection: v.outlier_detection.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::FailureConditions> for FailureConditions {
+    fn from(v: domain::FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerScope> for CircuitBreakerScope {
+    fn from(v: domain::CircuitBreakerScope) -> Self {
+        match v {
+            domain::CircuitBreakerScope::Global => Self::Global,
+            domain::CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: domain::CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            success_threshold: v.success_threshold,
+            timeout_secs: v.timeout_secs,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<domain::CircuitState> for CircuitState {
+    fn from(v: domain::CircuitState) -> Self {
+        match v {
+            domain::CircuitState::Closed => Self::Closed,
+            domain::CircuitState::HalfOpen => Self::HalfOpen,
+            domain::CircuitState::Open => Self::Open,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerStatus> for CircuitBreakerStatusResponse {
+    fn from(v: domain::CircuitBreakerStatus) -> Self {
+        Self {
+            state: v.state.into(),
+            consecutive_failures: v.consecutive_failures,
+            retry_after_secs: v.retry_after_secs,
+            failures_total: v.failures_total,
+            rejected_requests_total: v.rejected_requests_total,
+            state_changes_total: v.state_changes_total,
+            half_open_successes_total: v.half_open_successes_total,
+            half_open_failures_total: v.half_open_failures_total,
+        }
+    }
+}
+
+impl From<domain::EndpointStatus> for EndpointStatusResponse {
+    fn from(v: domain::EndpointStatus) -> Self {
+        Self {
+            host: v.host,
+            port: v.port,
+            healthy: v.healthy,
+            ejected: v.ejected,
+            ejection_remaining_secs: v.ejection_remaining_secs,
+            in_flight: v.in_flight,
+            consecutive_failures: v.consecutive_failures,
+            circuit_breaker: v.circuit_breaker.map(Into::into),
+        }
+    }
+}
+
 impl From<domain::AuthConfig> for AuthConfig {
     fn from(v: domain::AuthConfig) -> Self {
         Self {
@@ -764,6 +1281,41 @@ impl From<domain::RateLimitConfig> for RateLimitConfig {
             scope: v.scope.into(),
             strategy: v.strategy.into(),
             cost: v.cost,
+            queue: v.queue.map(Into::into),
+            degrade: v.degrade.map(Into::into),
+            response_headers: v.response_headers,
+        }
+    }
+}
+
+impl From<domain::QueueConfig> for QueueConfig {
+    fn from(v: domain::QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            timeout: v.timeout,
+        }
+    }
+}
+
+impl From<domain::DegradeConfig> for DegradeConfig {
+    fn from(v: domain::DegradeConfig) -> Self {
+        Self {
+            fallback_response: FallbackResponse {
+                status: v.fallback_response.status,
+                content_type: v.fallback_response.content_type,
+                body: v.fallback_response.body,
+            },
+        }
+    }
+}
+
+impl From<domain::CacheConfig> for CacheConfig {
+    fn from(v: domain::CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_ttl_secs: v.max_ttl_secs,
+            max_entry_bytes: v.max_entry_bytes,
         }
     }
 }
@@ -773,6 +1325,7 @@ impl From<domain::PluginsConfig> for PluginsConfig {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -841,6 +1394,8 @@ impl From<CreateUpstreamRequest> for domain::CreateUpstreamRequest {
             headers: r.headers.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            load_balancing: r.load_balancing.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -857,6 +1412,8 @@ impl From<UpdateUpstreamRequest> for domain::UpdateUpstreamRequest {
             headers: r.headers.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            load_balancing: r.load_balancing.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -870,6 +1427,7 @@ impl From<CreateRouteRequest> for domain::CreateRouteRequest {
             match_rules: r.match_rules.into(),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            cache: r.cache.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -883,6 +1441,7 @@ impl From<UpdateRouteRequest> for domain::UpdateRouteRequest {
             match_rules: r.match_rules.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            cache: r.cache.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -901,6 +1460,7 @@ impl modkit::api::api_dto::RequestApiDto for UpdateRouteRequest {}
 
 impl modkit::api::api_dto::ResponseApiDto for UpstreamResponse {}
 impl modkit::api::api_dto::ResponseApiDto for RouteResponse {}
+impl modkit::api::api_dto::ResponseApiDto for UpstreamStatusResponse {}
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -909,3 +1469,7 @@ impl modkit::api::api_dto::ResponseApiDto for RouteResponse {}
 fn default_true() -> bool {
     true
 }
+
+fn is_zero(v: &u64) -> bool {
+    *v == 0
+}
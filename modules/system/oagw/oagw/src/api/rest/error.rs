@@ -17,12 +17,16 @@ pub(crate) const ERR_INVALID_TARGET_HOST: &str =
 pub(crate) const ERR_UNKNOWN_TARGET_HOST: &str =
     "gts.x.core.errors.err.v1~x.oagw.routing.unknown_target_host.v1";
 pub(crate) const ERR_AUTH_FAILED: &str = "gts.x.core.errors.err.v1~x.oagw.auth.failed.v1";
+pub(crate) const ERR_GUARD_REJECTED: &str = "gts.x.core.errors.err.v1~x.oagw.guard.rejected.v1";
 pub(crate) const ERR_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.resource.not_found.v1";
 pub(crate) const ERR_ROUTE_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.route.not_found.v1";
 pub(crate) const ERR_PAYLOAD_TOO_LARGE: &str =
     "gts.x.core.errors.err.v1~x.oagw.payload.too_large.v1";
 pub(crate) const ERR_RATE_LIMIT_EXCEEDED: &str =
     "gts.x.core.errors.err.v1~x.oagw.rate_limit.exceeded.v1";
+pub(crate) const ERR_QUEUE_TIMEOUT: &str = "gts.x.core.errors.err.v1~x.oagw.queue.timeout.v1";
+pub(crate) const ERR_CIRCUIT_BREAKER_OPEN: &str =
+    "gts.x.core.errors.err.v1~x.oagw.circuit_breaker.open.v1";
 pub(crate) const ERR_SECRET_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.secret.not_found.v1";
 pub(crate) const ERR_DOWNSTREAM: &str = "gts.x.core.errors.err.v1~x.oagw.downstream.error.v1";
 pub(crate) const ERR_PROTOCOL: &str = "gts.x.core.errors.err.v1~x.oagw.protocol.error.v1";
@@ -43,12 +47,15 @@ fn gts_type(err: &DomainError) -> &str {
         DomainError::InvalidTargetHost { .. } => ERR_INVALID_TARGET_HOST,
         DomainError::UnknownTargetHost { .. } => ERR_UNKNOWN_TARGET_HOST,
         DomainError::AuthenticationFailed { .. } => ERR_AUTH_FAILED,
+        DomainError::GuardRejected { .. } => ERR_GUARD_REJECTED,
         DomainError::NotFound {
             entity: "route", ..
         } => ERR_ROUTE_NOT_FOUND,
         DomainError::NotFound { .. } => ERR_NOT_FOUND,
         DomainError::PayloadTooLarge { .. } => ERR_PAYLOAD_TOO_LARGE,
         DomainError::RateLimitExceeded { .. } => ERR_RATE_LIMIT_EXCEEDED,
+        DomainError::QueueTimeout { .. } => ERR_QUEUE_TIMEOUT,
+        DomainError::CircuitBreakerOpen { .. } => ERR_CIRCUIT_BREAKER_OPEN,
         DomainError::SecretNotFound { .. } => ERR_SECRET_NOT_FOUND,
         DomainError::DownstreamError { .. } | DomainError::Internal { .. } => ERR_DOWNSTREAM,
         DomainError::ProtocolError { .. } => ERR_PROTOCOL,
@@ -66,6 +73,7 @@ fn http_status_code(err: &DomainError) -> StatusCode {
         | DomainError::UnknownTargetHost { .. } => StatusCode::BAD_REQUEST,
         DomainError::Conflict { .. } => StatusCode::CONFLICT,
         DomainError::AuthenticationFailed { .. } => StatusCode::UNAUTHORIZED,
+        DomainError::GuardRejected { .. } => StatusCode::FORBIDDEN,
         DomainError::NotFound { .. } => StatusCode::NOT_FOUND,
         DomainError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
         DomainError::RateLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
@@ -75,7 +83,9 @@ fn http_status_code(err: &DomainError) -> StatusCode {
         DomainError::DownstreamError { .. } | DomainError::ProtocolError { .. } => {
             StatusCode::BAD_GATEWAY
         }
-        DomainError::UpstreamDisabled { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        DomainError::UpstreamDisabled { .. }
+        | DomainError::QueueTimeout { .. }
+        | DomainError::CircuitBreakerOpen { .. } => StatusCode::SERVICE_UNAVAILABLE,
         DomainError::ConnectionTimeout { .. } | DomainError::RequestTimeout { .. } => {
             StatusCode::GATEWAY_TIMEOUT
         }
@@ -90,9 +100,12 @@ fn error_title(err: &DomainError) -> &str {
         DomainError::InvalidTargetHost { .. } => "Invalid Target Host",
         DomainError::UnknownTargetHost { .. } => "Unknown Target Host",
         DomainError::AuthenticationFailed { .. } => "Authentication Failed",
+        DomainError::GuardRejected { .. } => "Request Rejected",
         DomainError::NotFound { .. } => "Not Found",
         DomainError::PayloadTooLarge { .. } => "Payload Too Large",
         DomainError::RateLimitExceeded { .. } => "Rate Limit Exceeded",
+        DomainError::QueueTimeout { .. } => "Queue Timeout",
+        DomainError::CircuitBreakerOpen { .. } => "Circuit Breaker Open",
         DomainError::SecretNotFound { .. } => "Secret Not Found",
         DomainError::DownstreamError { .. } | DomainError::Internal { .. } => "Downstream Error",
         DomainError::ProtocolError { .. } => "Protocol Error",
@@ -109,8 +122,11 @@ fn error_instance(err: &DomainError) -> &str {
         | DomainError::InvalidTargetHost { instance, .. }
         | DomainError::UnknownTargetHost { instance, .. }
         | DomainError::AuthenticationFailed { instance, .. }
+        | DomainError::GuardRejected { instance, .. }
         | DomainError::PayloadTooLarge { instance, .. }
         | DomainError::RateLimitExceeded { instance, .. }
+        | DomainError::QueueTimeout { instance, .. }
+        | DomainError::CircuitBreakerOpen { instance, .. }
         | DomainError::SecretNotFound { instance, .. }
         | DomainError::DownstreamError { instance, .. }
         | DomainError::ProtocolError { instance, .. }
@@ -155,16 +171,68 @@ pub(crate) fn domain_error_to_problem(err: DomainError, instance: &str) -> Probl
     p
 }
 
-/// Convert a `DomainError` into an axum `Response` with the
-/// `x-oagw-error-source: gateway` header. Used by the proxy handler.
-pub fn error_response(err: DomainError) -> Response {
-    let retry_after = match &err {
+fn retry_after_secs(err: &DomainError) -> Option<u64> {
+    match err {
         DomainError::RateLimitExceeded {
             retry_after_secs: Some(secs),
             ..
         } => Some(*secs),
+        DomainError::QueueTimeout {
+            retry_after_secs, ..
+        }
+        | DomainError::CircuitBreakerOpen {
+            retry_after_secs, ..
+        } => Some(*retry_after_secs),
         _ => None,
-    };
+    }
+}
+
+/// gRPC status code for a gateway error, following the HTTP mapping of
+/// the gRPC status code specification.
+fn grpc_status_code(err: &DomainError) -> u16 {
+    match err {
+        DomainError::Validation { .. }
+        | DomainError::MissingTargetHost { .. }
+        | DomainError::InvalidTargetHost { .. }
+        | DomainError::UnknownTargetHost { .. } => 3, // INVALID_ARGUMENT
+        DomainError::RequestTimeout { .. } => 4, // DEADLINE_EXCEEDED
+        DomainError::NotFound {
+            entity: "route", ..
+        } => 12, // UNIMPLEMENTED
+        DomainError::NotFound { .. } => 5,       // NOT_FOUND
+        DomainError::Conflict { .. } => 6,       // ALREADY_EXISTS
+        DomainError::GuardRejected { .. } => 7,  // PERMISSION_DENIED
+        DomainError::PayloadTooLarge { .. } | DomainError::RateLimitExceeded { .. } => 8, // RESOURCE_EXHAUSTED
+        DomainError::SecretNotFound { .. } | DomainError::Internal { .. } => 13, // INTERNAL
+        DomainError::QueueTimeout { .. }
+        | DomainError::CircuitBreakerOpen { .. }
+        | DomainError::UpstreamDisabled { .. }
+        | DomainError::DownstreamError { .. }
+        | DomainError::ProtocolError { .. }
+        | DomainError::ConnectionTimeout { .. } => 14, // UNAVAILABLE
+        DomainError::AuthenticationFailed { .. } => 16,                          // UNAUTHENTICATED
+    }
+}
+
+/// Percent-encode a `grpc-message` value: everything outside printable
+/// ASCII, and `%` itself, is escaped.
+fn encode_grpc_message(message: &str) -> String {
+    let mut encoded = String::with_capacity(message.len());
+    for b in message.bytes() {
+        if (0x20..=0x7e).contains(&b) && b != b'%' {
+            encoded.push(char::from(b));
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Convert a `DomainError` into an axum `Response` with the
+/// `x-oagw-error-source: gateway` header. Used by the proxy handler.
+pub fn error_response(err: DomainError) -> Response {
+    let retry_after = retry_after_secs(&err);
+    let circuit_open = matches!(err, DomainError::CircuitBreakerOpen { .. });
 
     let problem: Problem = err.into();
     let mut response = problem.into_response();
@@ -180,6 +248,41 @@ pub fn error_response(err: DomainError) -> Response {
         response.headers_mut().insert("retry-after", v);
     }
 
+    if circuit_open {
+        response
+            .headers_mut()
+            .insert("x-circuit-state", HeaderValue::from_static("OPEN"));
+    }
+
+    response
+}
+
+/// Convert a `DomainError` into a trailers-only gRPC response: HTTP `200`
+/// with `grpc-status` and `grpc-message` in the headers. gRPC clients ignore
+/// problem bodies, so gateway errors on gRPC routes are reported this way.
+pub(crate) fn grpc_error_response(err: DomainError) -> Response {
+    let retry_after = retry_after_secs(&err);
+    let code = grpc_status_code(&err);
+    let message = encode_grpc_message(&err.to_string());
+
+    let mut response = StatusCode::OK.into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/grpc"),
+    );
+    headers.insert("grpc-status", HeaderValue::from(code));
+    if let Ok(v) = HeaderValue::from_str(&message) {
+        headers.insert("grpc-message", v);
+    }
+    headers.insert(
+        "x-oagw-error-source",
+        HeaderValue::from_static(ErrorSource::Gateway.as_str()),
+    );
+    if let Some(secs) = retry_after {
+        headers.insert("retry-after", HeaderValue::from(secs));
+    }
+
     response
 }
 
@@ -224,6 +327,39 @@ mod tests {
         assert_eq!(p.type_url, ERR_RATE_LIMIT_EXCEEDED);
     }
 
+    #[test]
+    fn circuit_breaker_open_sets_retry_after_and_state_headers() {
+        let err = DomainError::CircuitBreakerOpen {
+            detail: "circuit breaker is open for upstream 'api.openai.com'".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/chat/completions".into(),
+            retry_after_secs: 15,
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "15");
+        assert_eq!(resp.headers().get("x-circuit-state").unwrap(), "OPEN");
+        assert_eq!(
+            resp.headers().get("x-oagw-error-source").unwrap(),
+            "gateway"
+        );
+    }
+
+    #[test]
+    fn queue_timeout_produces_503_with_retry_after() {
+        let err = DomainError::QueueTimeout {
+            detail: "request queued for 2s, no rate limit capacity available".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/chat/completions".into(),
+            retry_after_secs: 1,
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "1");
+        assert_eq!(
+            resp.headers().get("x-oagw-error-source").unwrap(),
+            "gateway"
+        );
+    }
+
     #[test]
     fn not_found_produces_404() {
         let err = DomainError::NotFound {
@@ -259,6 +395,10 @@ mod tests {
                 detail: "test".into(),
                 instance: "/test".into(),
             },
+            DomainError::GuardRejected {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
             DomainError::NotFound {
                 entity: "route",
                 id: uuid::Uuid::nil(),
@@ -272,6 +412,16 @@ mod tests {
                 instance: "/test".into(),
                 retry_after_secs: None,
             },
+            DomainError::QueueTimeout {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: 1,
+            },
+            DomainError::CircuitBreakerOpen {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: 5,
+            },
             DomainError::SecretNotFound {
                 detail: "test".into(),
                 instance: "/test".into(),
@@ -343,4 +493,31 @@ mod tests {
             "gateway"
         );
     }
+
+    #[test]
+    fn grpc_error_response_is_trailers_only() {
+        let resp = grpc_error_response(DomainError::RateLimitExceeded {
+            detail: "quota of 10% used up".into(),
+            instance: "/oagw/v1/proxy/svc/pkg.Svc/Call".into(),
+            retry_after_secs: Some(3),
+        });
+        assert_eq!(resp.status(), StatusCode::OK);
+        let headers = resp.headers();
+        assert_eq!(headers["content-type"], "application/grpc");
+        assert_eq!(headers["grpc-status"], "8");
+        assert!(
+            headers["grpc-message"]
+                .to_str()
+                .unwrap()
+                .contains("quota of 10%25 used up")
+        );
+        assert_eq!(headers["retry-after"], "3");
+        assert_eq!(headers["x-oagw-error-source"], "gateway");
+
+        let resp = grpc_error_response(DomainError::NotFound {
+            entity: "route",
+            id: uuid::Uuid::nil(),
+        });
+        assert_eq!(resp.headers()["grpc-status"], "12");
+    }
 }
@@ -1,18 +1,35 @@
 use crate::domain::error::DomainError;
-use crate::infra::proxy::headers;
+use crate::infra::proxy::{grpc, headers, websocket};
 use axum::body::Body;
-use axum::extract::{Extension, Request};
+use std::net::SocketAddr;
+
+use axum::extract::ws::WebSocketUpgrade;
+use axum::extract::{ConnectInfo, Extension, FromRequestParts, Request};
 use axum::response::Response;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use http::StatusCode;
+use http::request::Parts;
+use http_body::Frame;
+use http_body_util::StreamBody;
 use modkit_security::SecurityContext;
-use oagw_sdk::api::ErrorSource;
+use oagw_sdk::api::{ClientIp, ErrorSource, ResponseTrailers};
+use oagw_sdk::body::{BodyStream, BoxError};
+use oagw_sdk::{WebSocketMessage, WebSocketStream};
 
-use crate::api::rest::error::error_response;
+use crate::api::rest::error::{error_response, grpc_error_response};
 use crate::module::AppState;
 
+/// Client messages buffered between the downstream socket and the upstream
+/// bridge of a WebSocket session.
+const WEBSOCKET_BUFFER: usize = 16;
+
 /// Proxy handler for `/oagw/v1/proxy/{alias}/{path:.*}`.
 ///
 /// Parses the alias and path suffix from the URL, validates the request,
 /// builds an `http::Request<oagw_sdk::Body>`, and delegates to the Data Plane service.
+/// WebSocket upgrades are bridged once the upstream has switched protocols;
+/// gateway errors on gRPC calls are reported as gRPC statuses.
 pub async fn proxy_handler(
     Extension(state): Extension<AppState>,
     Extension(ctx): Extension<SecurityContext>,
@@ -22,12 +39,12 @@ pub async fn proxy_handler(
     let (mut parts, body) = req.into_parts();
 
     // Parse alias from the URI to validate it's present.
-    let path = parts.uri.path();
+    let path = parts.uri.path().to_string();
     let prefix = "/oagw/v1/proxy/";
     let remaining = path.strip_prefix(prefix).ok_or_else(|| {
         error_response(DomainError::Validation {
             detail: "invalid proxy path".into(),
-            instance: path.to_string(),
+            instance: path.clone(),
         })
     })?;
 
@@ -36,44 +53,40 @@ pub async fn proxy_handler(
     if alias_end == 0 {
         return Err(error_response(DomainError::Validation {
             detail: "missing alias in proxy path".into(),
-            instance: path.to_string(),
+            instance: path.clone(),
         }));
     }
 
+    let to_error = if grpc::is_grpc_request(&parts.headers) {
+        grpc_error_response
+    } else {
+        error_response
+    };
+
     // Validate Content-Length if present.
     if let Some(cl) = parts.headers.get(http::header::CONTENT_LENGTH) {
         let cl_str = cl.to_str().map_err(|_| {
-            error_response(DomainError::Validation {
+            to_error(DomainError::Validation {
                 detail: "invalid Content-Length header".into(),
-                instance: path.to_string(),
+                instance: path.clone(),
             })
         })?;
         let cl_val: usize = cl_str.parse().map_err(|_| {
-            error_response(DomainError::Validation {
+            to_error(DomainError::Validation {
                 detail: format!("Content-Length is not a valid integer: '{cl_str}'"),
-                instance: path.to_string(),
+                instance: path.clone(),
             })
         })?;
         if cl_val > max_body_size {
-            return Err(error_response(DomainError::PayloadTooLarge {
+            return Err(to_error(DomainError::PayloadTooLarge {
                 detail: format!(
                     "request body of {cl_val} bytes exceeds maximum of {max_body_size} bytes"
                 ),
-                instance: path.to_string(),
+                instance: path.clone(),
             }));
         }
     }
 
-    // Read body bytes (limited to max_body_size).
-    let body_bytes = axum::body::to_bytes(body, max_body_size)
-        .await
-        .map_err(|_| {
-            error_response(DomainError::PayloadTooLarge {
-                detail: format!("request body exceeds maximum of {max_body_size} bytes"),
-                instance: path.to_string(),
-            })
-        })?;
-
     // Strip the proxy prefix from the URI so the DP receives /{alias}/{path}?query.
     let new_uri_str = if let Some(query) = parts.uri.query() {
         format!("/{remaining}?{query}")
@@ -81,31 +94,153 @@ pub async fn proxy_handler(
         format!("/{remaining}")
     };
     parts.uri = new_uri_str.parse().map_err(|_| {
-        error_response(DomainError::Validation {
+        to_error(DomainError::Validation {
             detail: "failed to parse proxy URI".into(),
-            instance: path.to_string(),
+            instance: path.clone(),
         })
     })?;
 
+    // Forward the peer address for IP-scoped rate limits. Only present when
+    // the server was started with connect info.
+    if let Some(ConnectInfo(addr)) = parts.extensions.get::<ConnectInfo<SocketAddr>>() {
+        let ip = ClientIp(addr.ip());
+        parts.extensions.insert(ip);
+    }
+
+    if websocket::is_upgrade_request(&parts.method, &parts.headers) {
+        return proxy_websocket(state, ctx, parts, path).await;
+    }
+
+    // gRPC bodies are streamed so client-streaming and bidirectional calls
+    // reach the upstream as the client sends them. Message sizes are left
+    // to the upstream's gRPC limits.
+    if grpc::is_grpc_request(&parts.headers) {
+        let stream: BodyStream = Box::pin(
+            body.into_data_stream()
+                .map(|chunk| chunk.map_err(|e| Box::new(e) as BoxError)),
+        );
+        let proxy_req = http::Request::from_parts(parts, oagw_sdk::Body::Stream(stream));
+        let proxy_resp = state
+            .dp
+            .proxy_request(ctx, proxy_req)
+            .await
+            .map_err(to_error)?;
+        return Ok(into_response(proxy_resp));
+    }
+
+    // Read body bytes (limited to max_body_size).
+    let body_bytes = axum::body::to_bytes(body, max_body_size)
+        .await
+        .map_err(|_| {
+            to_error(DomainError::PayloadTooLarge {
+                detail: format!("request body exceeds maximum of {max_body_size} bytes"),
+                instance: path.clone(),
+            })
+        })?;
+
     // Build http::Request<Body> for the DP service.
     let sdk_body = oagw_sdk::Body::from(body_bytes);
     let proxy_req = http::Request::from_parts(parts, sdk_body);
 
     // Execute proxy pipeline.
+    let proxy_resp = state
+        .dp
+        .proxy_request(ctx, proxy_req)
+        .await
+        .map_err(to_error)?;
+
+    Ok(into_response(proxy_resp))
+}
+
+/// Proxy a WebSocket upgrade. The DP performs the upstream handshake with the
+/// client messages as a request body stream; once it answers `101`, the
+/// downstream connection is upgraded and both directions are pumped until
+/// either side closes. Any other DP answer is returned as is.
+async fn proxy_websocket(
+    state: AppState,
+    ctx: SecurityContext,
+    mut parts: Parts,
+    path: String,
+) -> Result<Response, Response> {
+    let ws = WebSocketUpgrade::from_request_parts(&mut parts, &())
+        .await
+        .map_err(|e| {
+            error_response(DomainError::Validation {
+                detail: format!("invalid WebSocket upgrade request: {}", e.body_text()),
+                instance: path.clone(),
+            })
+        })?;
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, BoxError>>(WEBSOCKET_BUFFER);
+    let client_messages: BodyStream =
+        Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|msg| (msg, rx))
+        }));
+    let proxy_req = http::Request::from_parts(parts, oagw_sdk::Body::Stream(client_messages));
+
     let proxy_resp = state
         .dp
         .proxy_request(ctx, proxy_req)
         .await
         .map_err(error_response)?;
+    if proxy_resp.status() != StatusCode::SWITCHING_PROTOCOLS {
+        return Ok(into_response(proxy_resp));
+    }
 
-    // Convert http::Response<oagw_sdk::Body> to axum Response.
-    let (resp_parts, sdk_body) = proxy_resp.into_parts();
+    let (resp_parts, upstream) = proxy_resp.into_parts();
+    let error_source = error_source(&resp_parts.extensions);
+    let mut resp_headers = resp_parts.headers;
+    let protocol = resp_headers
+        .remove(http::header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|v| v.to_str().ok().map(|p| p.trim().to_owned()));
+    headers::sanitize_response_headers(&mut resp_headers);
+
+    let ws = match protocol {
+        Some(protocol) => ws.protocols([protocol]),
+        None => ws,
+    };
+    let mut response = ws.on_upgrade(move |socket| async move {
+        let (mut sender, receiver) = WebSocketStream::from(socket).split();
+        let inbound = async move {
+            let mut messages = receiver.into_body_stream();
+            while let Some(msg) = messages.next().await {
+                if tx.send(msg).await.is_err() {
+                    break;
+                }
+            }
+            // Dropping the sender ends the client stream, which closes
+            // the upstream connection.
+        };
+        let outbound = async move {
+            let _ = sender.forward_body_stream(upstream.into_stream()).await;
+            let _ = sender.send(&WebSocketMessage::Close(None)).await;
+        };
+        tokio::join!(inbound, outbound);
+    });
 
-    let error_source = resp_parts
-        .extensions
+    let headers = response.headers_mut();
+    for (name, value) in &resp_headers {
+        headers.append(name, value.clone());
+    }
+    headers.insert(
+        "x-oagw-error-source",
+        http::HeaderValue::from_static(error_source.as_str()),
+    );
+    Ok(response)
+}
+
+fn error_source(extensions: &http::Extensions) -> ErrorSource {
+    extensions
         .get::<ErrorSource>()
         .copied()
-        .unwrap_or(ErrorSource::Upstream);
+        .unwrap_or(ErrorSource::Upstream)
+}
+
+/// Convert http::Response<oagw_sdk::Body> to axum Response.
+fn into_response(proxy_resp: http::Response<oagw_sdk::Body>) -> Response {
+    let (resp_parts, sdk_body) = proxy_resp.into_parts();
+
+    let error_source = error_source(&resp_parts.extensions);
 
     // Sanitize upstream response headers: strip hop-by-hop and x-oagw-*.
     let mut resp_headers = resp_parts.headers;
@@ -122,10 +257,19 @@ pub async fn proxy_handler(
     // Add error source header.
     builder = builder.header("x-oagw-error-source", error_source.as_str());
 
-    // Stream the response body.
-    let body = Body::from_stream(sdk_body.into_stream());
+    // Stream the response body, ending with the upstream trailers for gRPC.
+    let body = match resp_parts.extensions.get::<ResponseTrailers>().cloned() {
+        Some(trailers) => {
+            let data = sdk_body.into_stream().map(|chunk| chunk.map(Frame::data));
+            let end = futures_util::stream::once(async move {
+                Ok(Frame::trailers(trailers.take().unwrap_or_default()))
+            });
+            Body::new(StreamBody::new(data.chain(end)))
+        }
+        None => Body::from_stream(sdk_body.into_stream()),
+    };
 
-    builder.body(body).map_err(|e| {
+    builder.body(body).unwrap_or_else(|e| {
         error_response(DomainError::DownstreamError {
             detail: format!("failed to build response: {e}"),
             instance: String::new(),
@@ -5,7 +5,9 @@ use http::StatusCode;
 use modkit::api::problem::Problem;
 use modkit_security::SecurityContext;
 
-use crate::api::rest::dto::{CreateUpstreamRequest, UpdateUpstreamRequest, UpstreamResponse};
+use crate::api::rest::dto::{
+    CreateUpstreamRequest, UpdateUpstreamRequest, UpstreamResponse, UpstreamStatusResponse,
+};
 use crate::api::rest::error::domain_error_to_problem;
 use crate::api::rest::extractors::{PaginationQuery, parse_gts_id};
 use crate::domain::gts_helpers as gts;
@@ -24,6 +26,8 @@ fn to_response(u: Upstream) -> UpstreamResponse {
         headers: u.headers.map(Into::into),
         plugins: u.plugins.map(Into::into),
         rate_limit: u.rate_limit.map(Into::into),
+        load_balancing: u.load_balancing.map(Into::into),
+        circuit_breaker: u.circuit_breaker.map(Into::into),
         tags: u.tags,
     }
 }
@@ -56,6 +60,26 @@ pub async fn get_upstream(
     Ok(Json(to_response(upstream)))
 }
 
+pub async fn get_upstream_status(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/upstreams/{id}/status");
+    let uuid = parse_gts_id(&id, &instance)?;
+    let upstream = state
+        .cp
+        .get_upstream(&ctx, uuid)
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    let status = state.dp.upstream_status(&upstream);
+    Ok(Json(UpstreamStatusResponse {
+        id: gts::format_upstream_gts(status.upstream_id),
+        endpoints: status.endpoints.into_iter().map(Into::into).collect(),
+        circuit_breaker: status.circuit_breaker.map(Into::into),
+    }))
+}
+
 pub async fn list_upstreams(
     Extension(state): Extension<AppState>,
     Extension(ctx): Extension<SecurityContext>,
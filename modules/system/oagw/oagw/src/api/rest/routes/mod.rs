@@ -49,6 +49,10 @@ pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Ro
                 .patch(upstream_h::update_upstream)
                 .delete(upstream_h::delete_upstream),
         )
+        .route(
+            "/oagw/v1/upstreams/{id}/status",
+            get(upstream_h::get_upstream_status),
+        )
         // Route CRUD
         .route("/oagw/v1/routes", post(route_h::create_route))
         .route(
@@ -13,6 +13,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .summary("Create route")
         .description("Create a new route mapping for an upstream service")
         .tag("routes")
+        .idempotent()
         .authenticated()
         .require_license_features::<License>([])
         .json_request::<dto::CreateRouteRequest>(openapi, "Route configuration")
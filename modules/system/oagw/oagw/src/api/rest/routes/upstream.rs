@@ -13,6 +13,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .summary("Create upstream")
         .description("Create a new upstream service configuration")
         .tag("upstreams")
+        .idempotent()
         .authenticated()
         .require_license_features::<License>([])
         .json_request::<dto::CreateUpstreamRequest>(openapi, "Upstream configuration")
@@ -67,6 +68,26 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .standard_errors(openapi)
         .register(router, openapi);
 
+    // GET /oagw/v1/upstreams/{id}/status — Endpoint health
+    router = OperationBuilder::get("/oagw/v1/upstreams/{id}/status")
+        .operation_id("oagw.get_upstream_status")
+        .summary("Get upstream endpoint status")
+        .description(
+            "Report per-endpoint health, passive ejection state, and in-flight load for an upstream",
+        )
+        .tag("upstreams")
+        .path_param("id", "Upstream GTS identifier")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::upstream::get_upstream_status)
+        .json_response_with_schema::<dto::UpstreamStatusResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Upstream endpoint status",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
     // PATCH /oagw/v1/upstreams/{id} — Update upstream
     router = OperationBuilder::patch("/oagw/v1/upstreams/{id}")
         .operation_id("oagw.update_upstream")
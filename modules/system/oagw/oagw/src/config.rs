@@ -11,11 +11,52 @@ pub struct OagwConfig {
     pub proxy_timeout_secs: u64,
     #[serde(default = "default_max_body_size_bytes")]
     pub max_body_size_bytes: usize,
-    /// Optional credentials to pre-load into the in-memory credential resolver.
+    /// Optional credentials to pre-load into the credential resolver.
     /// Keys are secret references (e.g., `cred://openai-key`), values are secrets.
-    /// Intended for development and testing only.
+    /// Values may be `${secret:...}` references, resolved when the config is loaded.
+    /// Credentials are kept in memory only and are never written to the database.
     #[serde(default)]
     pub credentials: HashMap<String, String>,
+    /// Where upstreams and routes are stored.
+    ///
+    /// `database` requires a `database:` section for the module; the
+    /// SQL engine (SQLite or Postgres) is selected by that section.
+    #[serde(default)]
+    pub storage: StorageBackend,
+    /// Limits of the in-memory cache used by routes with `cache` enabled.
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+}
+
+/// Size limits of the node-local proxy response cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseCacheConfig {
+    #[serde(default = "default_response_cache_max_entries")]
+    pub max_entries: usize,
+    /// Upper bound on the combined size of cached bodies and headers.
+    #[serde(default = "default_response_cache_max_bytes")]
+    pub max_bytes: usize,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: default_response_cache_max_entries(),
+            max_bytes: default_response_cache_max_bytes(),
+        }
+    }
+}
+
+/// Persistence backend for upstream and route definitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// Process-local maps; everything is lost on restart.
+    #[default]
+    Memory,
+    /// SeaORM tables managed by the module's migrations.
+    Database,
 }
 
 impl Default for OagwConfig {
@@ -24,6 +65,8 @@ impl Default for OagwConfig {
             proxy_timeout_secs: default_proxy_timeout_secs(),
             max_body_size_bytes: default_max_body_size_bytes(),
             credentials: HashMap::new(),
+            storage: StorageBackend::default(),
+            response_cache: ResponseCacheConfig::default(),
         }
     }
 }
@@ -36,6 +79,14 @@ fn default_max_body_size_bytes() -> usize {
     10 * 1024 * 1024 // 10 MB
 }
 
+fn default_response_cache_max_entries() -> usize {
+    1000
+}
+
+fn default_response_cache_max_bytes() -> usize {
+    64 * 1024 * 1024 // 64 MB
+}
+
 /// Read-only runtime configuration exposed to handlers via `AppState`.
 ///
 /// Derived from [`OagwConfig`] at init time, excluding sensitive fields
@@ -58,6 +109,8 @@ impl fmt::Debug for OagwConfig {
         f.debug_struct("OagwConfig")
             .field("proxy_timeout_secs", &self.proxy_timeout_secs)
             .field("max_body_size_bytes", &self.max_body_size_bytes)
+            .field("storage", &self.storage)
+            .field("response_cache", &self.response_cache)
             .field(
                 "credentials",
                 &self
@@ -89,4 +142,25 @@ mod tests {
         assert!(debug_output.contains("cred://openai-key"));
         assert!(debug_output.contains("[REDACTED]"));
     }
+
+    #[test]
+    fn storage_defaults_to_memory() {
+        let config: OagwConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.storage, StorageBackend::Memory);
+
+        let config: OagwConfig = serde_json::from_str(r#"{"storage":"database"}"#).unwrap();
+        assert_eq!(config.storage, StorageBackend::Database);
+    }
+
+    #[test]
+    fn response_cache_limits_default_and_override() {
+        let config: OagwConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.response_cache.max_entries, 1000);
+        assert_eq!(config.response_cache.max_bytes, 64 * 1024 * 1024);
+
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"response_cache":{"max_entries":10}}"#).unwrap();
+        assert_eq!(config.response_cache.max_entries, 10);
+        assert_eq!(config.response_cache.max_bytes, 64 * 1024 * 1024);
+    }
 }
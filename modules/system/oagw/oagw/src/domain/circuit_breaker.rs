@@ -0,0 +1,401 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    CircuitBreakerConfig, CircuitBreakerStatus, CircuitState, FailureConditions,
+};
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+
+/// Closed/open/half-open circuit breakers keyed by upstream or endpoint.
+///
+/// State is local to this node; see `docs/adr-circuit-breaker.md` for the
+/// distributed design this will eventually move to.
+#[domain_model]
+pub struct CircuitBreaker {
+    circuits: DashMap<String, Arc<Mutex<Circuit>>>,
+}
+
+/// Result of an upstream call, as seen by the breaker.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallOutcome {
+    Status(u16),
+    Timeout,
+    ConnectionError,
+}
+
+impl CallOutcome {
+    fn is_failure(self, conditions: &FailureConditions) -> bool {
+        match self {
+            Self::Status(code) => conditions.status_codes.contains(&code),
+            Self::Timeout => conditions.timeout,
+            Self::ConnectionError => conditions.connection_error,
+        }
+    }
+}
+
+#[domain_model]
+struct Circuit {
+    config: CircuitBreakerConfig,
+    state: CircuitState,
+    consecutive_failures: u32,
+    half_open_successes: u32,
+    half_open_in_flight: u32,
+    opened_at: Option<Instant>,
+    stats: CircuitBreakerStatus,
+}
+
+impl Circuit {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            half_open_successes: 0,
+            half_open_in_flight: 0,
+            opened_at: None,
+            stats: CircuitBreakerStatus::default(),
+        }
+    }
+
+    fn open_timeout(&self) -> Duration {
+        Duration::from_secs(u64::from(self.config.timeout_secs))
+    }
+
+    fn retry_after_secs(&self, now: Instant) -> u64 {
+        match (self.state, self.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => {
+                let remaining = self
+                    .open_timeout()
+                    .saturating_sub(now.saturating_duration_since(opened_at));
+                remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+            }
+            _ => 0,
+        }
+    }
+
+    fn transition(&mut self, key: &str, to: CircuitState, now: Instant) {
+        let from = self.state;
+        self.state = to;
+        self.stats.state_changes_total += 1;
+        match to {
+            CircuitState::Open => self.opened_at = Some(now),
+            CircuitState::HalfOpen => {
+                self.half_open_successes = 0;
+                self.half_open_in_flight = 0;
+            }
+            CircuitState::Closed => {
+                self.opened_at = None;
+                self.consecutive_failures = 0;
+            }
+        }
+        tracing::info!(
+            metric = "oagw_circuit_breaker_state_changes_total",
+            circuit = key,
+            from_state = state_str(from),
+            to_state = state_str(to),
+            "Circuit breaker state changed"
+        );
+    }
+
+    fn status(&self, now: Instant) -> CircuitBreakerStatus {
+        CircuitBreakerStatus {
+            state: self.state,
+            consecutive_failures: self.consecutive_failures,
+            retry_after_secs: self.retry_after_secs(now),
+            ..self.stats.clone()
+        }
+    }
+}
+
+fn state_str(state: CircuitState) -> &'static str {
+    match state {
+        CircuitState::Closed => "CLOSED",
+        CircuitState::HalfOpen => "HALF_OPEN",
+        CircuitState::Open => "OPEN",
+    }
+}
+
+fn lock(circuit: &Mutex<Circuit>) -> MutexGuard<'_, Circuit> {
+    circuit.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Admission to call the upstream. Report the outcome with
+/// [`CircuitPermit::record`]; dropping an unrecorded permit frees its
+/// half-open slot without affecting the circuit.
+#[domain_model]
+pub struct CircuitPermit {
+    key: String,
+    circuit: Arc<Mutex<Circuit>>,
+    half_open: bool,
+    recorded: bool,
+}
+
+impl CircuitPermit {
+    pub fn record(&mut self, outcome: CallOutcome) {
+        if std::mem::replace(&mut self.recorded, true) {
+            return;
+        }
+        let now = Instant::now();
+        let mut c = lock(&self.circuit);
+        if self.half_open {
+            c.half_open_in_flight = c.half_open_in_flight.saturating_sub(1);
+        }
+        let failed = outcome.is_failure(&c.config.failure_conditions);
+        if failed {
+            c.stats.failures_total += 1;
+        }
+
+        match c.state {
+            CircuitState::Closed if failed => {
+                c.consecutive_failures += 1;
+                if c.consecutive_failures >= c.config.failure_threshold {
+                    c.transition(&self.key, CircuitState::Open, now);
+                }
+            }
+            CircuitState::Closed => c.consecutive_failures = 0,
+            // Requests admitted before the circuit opened don't decide recovery.
+            CircuitState::HalfOpen if !self.half_open => {}
+            CircuitState::HalfOpen if failed => {
+                c.stats.half_open_failures_total += 1;
+                c.transition(&self.key, CircuitState::Open, now);
+            }
+            CircuitState::HalfOpen => {
+                c.stats.half_open_successes_total += 1;
+                c.half_open_successes += 1;
+                if c.half_open_successes >= c.config.success_threshold {
+                    c.transition(&self.key, CircuitState::Closed, now);
+                }
+            }
+            CircuitState::Open => {}
+        }
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if !self.recorded && self.half_open {
+            let mut c = lock(&self.circuit);
+            c.half_open_in_flight = c.half_open_in_flight.saturating_sub(1);
+        }
+    }
+}
+
+impl CircuitBreaker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            circuits: DashMap::new(),
+        }
+    }
+
+    /// Get the circuit for `key`, resetting it if `config` changed.
+    fn circuit(&self, key: &str, config: &CircuitBreakerConfig) -> Arc<Mutex<Circuit>> {
+        let mut entry = self
+            .circuits
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(Circuit::new(config.clone()))));
+        if lock(&entry).config != *config {
+            *entry = Arc::new(Mutex::new(Circuit::new(config.clone())));
+        }
+        entry.clone()
+    }
+
+    /// Ask the circuit for `key` whether a request may proceed.
+    ///
+    /// # Errors
+    /// Returns `DomainError::CircuitBreakerOpen` while the circuit is open, or
+    /// while half-open with every probe slot taken.
+    pub fn try_acquire(
+        &self,
+        key: &str,
+        config: &CircuitBreakerConfig,
+        instance_uri: &str,
+    ) -> Result<CircuitPermit, DomainError> {
+        let circuit = self.circuit(key, config);
+        let now = Instant::now();
+        let half_open = {
+            let mut c = lock(&circuit);
+            if c.state == CircuitState::Open
+                && c.opened_at
+                    .is_some_and(|at| now.saturating_duration_since(at) >= c.open_timeout())
+            {
+                c.transition(key, CircuitState::HalfOpen, now);
+            }
+            match c.state {
+                CircuitState::Closed => false,
+                CircuitState::HalfOpen
+                    if c.half_open_in_flight < c.config.half_open_max_requests =>
+                {
+                    c.half_open_in_flight += 1;
+                    true
+                }
+                state => {
+                    c.stats.rejected_requests_total += 1;
+                    tracing::debug!(
+                        metric = "oagw_circuit_breaker_rejected_requests_total",
+                        circuit = key,
+                        state = state_str(state),
+                        "Circuit breaker rejected request"
+                    );
+                    return Err(DomainError::CircuitBreakerOpen {
+                        detail: format!("circuit breaker is open for {key}"),
+                        instance: instance_uri.to_string(),
+                        retry_after_secs: c.retry_after_secs(now).max(1),
+                    });
+                }
+            }
+        };
+        Ok(CircuitPermit {
+            key: key.to_string(),
+            circuit,
+            half_open,
+            recorded: false,
+        })
+    }
+
+    /// Current state and counters for `key`, if the circuit has seen traffic.
+    #[must_use]
+    pub fn status(&self, key: &str) -> Option<CircuitBreakerStatus> {
+        self.circuits
+            .get(key)
+            .map(|c| lock(&c).status(Instant::now()))
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::CircuitBreakerScope;
+
+    use super::*;
+
+    fn make_config(failure_threshold: u32, success_threshold: u32) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            enabled: true,
+            failure_threshold,
+            success_threshold,
+            timeout_secs: 30,
+            half_open_max_requests: 1,
+            failure_conditions: FailureConditions {
+                status_codes: vec![500, 502, 503, 504],
+                timeout: true,
+                connection_error: true,
+            },
+            scope: CircuitBreakerScope::Global,
+        }
+    }
+
+    fn call(cb: &CircuitBreaker, config: &CircuitBreakerConfig, outcome: CallOutcome) {
+        let mut permit = cb.try_acquire("test", config, "/test").unwrap();
+        permit.record(outcome);
+    }
+
+    /// Pretend the open timeout has already elapsed.
+    fn expire_open_timeout(cb: &CircuitBreaker) {
+        let circuit = cb.circuits.get("test").unwrap();
+        let mut c = lock(&circuit);
+        c.opened_at = c.opened_at.map(|at| at - c.open_timeout());
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let cb = CircuitBreaker::new();
+        let config = make_config(3, 1);
+        for _ in 0..3 {
+            call(&cb, &config, CallOutcome::Status(503));
+        }
+        match cb.try_acquire("test", &config, "/test") {
+            Err(DomainError::CircuitBreakerOpen {
+                retry_after_secs, ..
+            }) => assert_eq!(retry_after_secs, 30),
+            _ => panic!("expected CircuitBreakerOpen"),
+        }
+        let status = cb.status("test").unwrap();
+        assert_eq!(status.state, CircuitState::Open);
+        assert_eq!(status.failures_total, 3);
+        assert_eq!(status.rejected_requests_total, 1);
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let cb = CircuitBreaker::new();
+        let config = make_config(2, 1);
+        call(&cb, &config, CallOutcome::Timeout);
+        call(&cb, &config, CallOutcome::Status(200));
+        call(&cb, &config, CallOutcome::ConnectionError);
+        assert_eq!(cb.status("test").unwrap().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn ignores_outcomes_outside_failure_conditions() {
+        let cb = CircuitBreaker::new();
+        let mut config = make_config(1, 1);
+        config.failure_conditions.timeout = false;
+        call(&cb, &config, CallOutcome::Status(404));
+        call(&cb, &config, CallOutcome::Timeout);
+        assert_eq!(cb.status("test").unwrap().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_closes_after_successes() {
+        let cb = CircuitBreaker::new();
+        let config = make_config(1, 2);
+        call(&cb, &config, CallOutcome::Status(500));
+        expire_open_timeout(&cb);
+
+        call(&cb, &config, CallOutcome::Status(200));
+        assert_eq!(cb.status("test").unwrap().state, CircuitState::HalfOpen);
+        call(&cb, &config, CallOutcome::Status(200));
+
+        let status = cb.status("test").unwrap();
+        assert_eq!(status.state, CircuitState::Closed);
+        assert_eq!(status.half_open_successes_total, 2);
+        assert_eq!(status.state_changes_total, 3);
+    }
+
+    #[test]
+    fn half_open_failure_reopens() {
+        let cb = CircuitBreaker::new();
+        let config = make_config(1, 1);
+        call(&cb, &config, CallOutcome::Status(500));
+        expire_open_timeout(&cb);
+
+        call(&cb, &config, CallOutcome::ConnectionError);
+        let status = cb.status("test").unwrap();
+        assert_eq!(status.state, CircuitState::Open);
+        assert_eq!(status.half_open_failures_total, 1);
+    }
+
+    #[test]
+    fn half_open_limits_concurrent_probes() {
+        let cb = CircuitBreaker::new();
+        let config = make_config(1, 1);
+        call(&cb, &config, CallOutcome::Status(500));
+        expire_open_timeout(&cb);
+
+        let permit = cb.try_acquire("test", &config, "/test").unwrap();
+        assert!(cb.try_acquire("test", &config, "/test").is_err());
+        // An abandoned probe frees its slot.
+        drop(permit);
+        assert!(cb.try_acquire("test", &config, "/test").is_ok());
+    }
+
+    #[test]
+    fn config_change_resets_circuit() {
+        let cb = CircuitBreaker::new();
+        let config = make_config(1, 1);
+        call(&cb, &config, CallOutcome::Status(500));
+        assert!(cb.try_acquire("test", &config, "/test").is_err());
+
+        let relaxed = make_config(5, 1);
+        assert!(cb.try_acquire("test", &relaxed, "/test").is_ok());
+    }
+}
@@ -34,6 +34,9 @@ pub enum DomainError {
     #[error("{detail}")]
     AuthenticationFailed { detail: String, instance: String },
 
+    #[error("{detail}")]
+    GuardRejected { detail: String, instance: String },
+
     #[error("{detail}")]
     PayloadTooLarge { detail: String, instance: String },
 
@@ -44,6 +47,20 @@ pub enum DomainError {
         retry_after_secs: Option<u64>,
     },
 
+    #[error("{detail}")]
+    QueueTimeout {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
     #[error("{detail}")]
     SecretNotFound { detail: String, instance: String },
 
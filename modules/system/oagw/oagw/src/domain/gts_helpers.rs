@@ -28,6 +28,7 @@ pub const OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID: &str =
     "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.oauth2_client_cred.v1";
 pub const OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID: &str =
     "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.oauth2_client_cred_basic.v1";
+pub const HMAC_AUTH_PLUGIN_ID: &str = "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.hmac.v1";
 
 // -- Builtin guard plugin instances --
 pub const TIMEOUT_GUARD_PLUGIN_ID: &str = "gts.x.core.oagw.guard_plugin.v1~x.core.oagw.timeout.v1";
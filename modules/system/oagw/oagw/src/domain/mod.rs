@@ -1,3 +1,4 @@
+pub(crate) mod circuit_breaker;
 pub(crate) mod credential;
 pub(crate) mod error;
 pub(crate) mod gts_helpers;
@@ -5,9 +6,12 @@ pub(crate) mod model;
 pub(crate) mod plugin;
 pub(crate) mod rate_limit;
 pub(crate) mod repo;
+pub(crate) mod response_cache;
 pub(crate) mod services;
+pub(crate) mod tenant_hierarchy;
 pub(crate) mod type_catalog;
 pub(crate) mod type_provisioning;
+pub(crate) mod upstream_observer;
 
 #[cfg(any(test, feature = "test-utils"))]
 pub(crate) mod test_support;
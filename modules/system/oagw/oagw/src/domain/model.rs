@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use modkit_macros::domain_model;
 use uuid::Uuid;
@@ -37,6 +38,7 @@ pub struct Endpoint {
     pub scheme: Scheme,
     pub host: String,
     pub port: u16,
+    pub weight: u32,
 }
 
 impl Endpoint {
@@ -56,6 +58,77 @@ pub struct Server {
     pub endpoints: Vec<Endpoint>,
 }
 
+// ---------------------------------------------------------------------------
+// LoadBalancingConfig
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LoadBalancingConfig {
+    pub strategy: LoadBalancingStrategy,
+    pub health_check: Option<HealthCheckConfig>,
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    LeastInFlight,
+    Weighted,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    pub path: String,
+    pub interval_secs: u32,
+    pub timeout_secs: u32,
+    pub healthy_threshold: u32,
+    pub unhealthy_threshold: u32,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlierDetectionConfig {
+    pub consecutive_failures: u32,
+    pub base_ejection_secs: u32,
+    pub max_ejection_percent: u32,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    pub failure_threshold: u32,
+    pub success_threshold: u32,
+    pub timeout_secs: u32,
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    #[default]
+    Global,
+    PerEndpoint,
+}
+
 // ---------------------------------------------------------------------------
 // AuthConfig
 // ---------------------------------------------------------------------------
@@ -120,6 +193,12 @@ pub struct RateLimitConfig {
     pub scope: RateLimitScope,
     pub strategy: RateLimitStrategy,
     pub cost: u32,
+    /// Wait queue used by [`RateLimitStrategy::Queue`].
+    pub queue: Option<QueueConfig>,
+    /// Fallback used by [`RateLimitStrategy::Degrade`].
+    pub degrade: Option<DegradeConfig>,
+    /// Emit `X-RateLimit-*` headers on successful responses.
+    pub response_headers: bool,
 }
 
 #[domain_model]
@@ -173,6 +252,45 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+/// Bounded wait queue for requests that exceed the rate limit.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// Maximum number of requests waiting per limiter key.
+    pub max_depth: u32,
+    /// Maximum time a request may wait for capacity.
+    pub timeout: Duration,
+}
+
+/// Degraded-mode behaviour for requests that exceed the rate limit.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DegradeConfig {
+    pub fallback_response: FallbackResponse,
+}
+
+/// Response served by the gateway instead of calling the upstream.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: String,
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub default_ttl_secs: u32,
+    pub max_ttl_secs: u32,
+    pub max_entry_bytes: u64,
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -182,6 +300,8 @@ pub enum RateLimitStrategy {
 pub struct PluginsConfig {
     pub sharing: SharingMode,
     pub items: Vec<String>,
+    /// Per-plugin configuration keyed by plugin reference.
+    pub config: HashMap<String, HashMap<String, String>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -222,6 +342,14 @@ pub struct GrpcMatch {
     pub method: String,
 }
 
+impl GrpcMatch {
+    /// HTTP/2 request path of the matched call: `/{service}/{method}`.
+    #[must_use]
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.service, self.method)
+    }
+}
+
 #[domain_model]
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchRules {
@@ -242,6 +370,7 @@ pub struct Route {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -260,9 +389,62 @@ pub struct Upstream {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
 }
 
+// ---------------------------------------------------------------------------
+// Endpoint health (runtime, not persisted)
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    pub host: String,
+    pub port: u16,
+    pub healthy: bool,
+    pub ejected: bool,
+    pub ejection_remaining_secs: u64,
+    pub in_flight: u64,
+    pub consecutive_failures: u32,
+    /// Present when the upstream's circuit breaker uses per-endpoint scope.
+    pub circuit_breaker: Option<CircuitBreakerStatus>,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamStatus {
+    pub upstream_id: Uuid,
+    pub endpoints: Vec<EndpointStatus>,
+    /// Present when the upstream's circuit breaker uses global scope.
+    pub circuit_breaker: Option<CircuitBreakerStatus>,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitState {
+    #[default]
+    Closed,
+    HalfOpen,
+    Open,
+}
+
+/// Circuit state plus lifetime counters for one breaker.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// Seconds until an open circuit admits half-open probes; zero otherwise.
+    pub retry_after_secs: u64,
+    pub failures_total: u64,
+    pub rejected_requests_total: u64,
+    pub state_changes_total: u64,
+    pub half_open_successes_total: u64,
+    pub half_open_failures_total: u64,
+}
+
 // ---------------------------------------------------------------------------
 // Pagination
 // ---------------------------------------------------------------------------
@@ -294,6 +476,8 @@ pub struct CreateUpstreamRequest {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
     pub enabled: bool,
 }
@@ -308,6 +492,8 @@ pub struct UpdateUpstreamRequest {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Option<Vec<String>>,
     pub enabled: Option<bool>,
 }
@@ -319,6 +505,7 @@ pub struct CreateRouteRequest {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -330,6 +517,7 @@ pub struct UpdateRouteRequest {
     pub match_rules: Option<MatchRules>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Option<Vec<String>>,
     pub priority: Option<i32>,
     pub enabled: Option<bool>,
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
+use bytes::Bytes;
 use modkit_macros::domain_model;
 
+use crate::domain::model::{PluginsConfig, SharingMode};
+
 // ---------------------------------------------------------------------------
 // Plugin errors
 // ---------------------------------------------------------------------------
@@ -12,10 +15,8 @@ pub enum PluginError {
     #[error("secret not found: {0}")]
     SecretNotFound(String),
     #[error("authentication failed: {0}")]
-    #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
     AuthFailed(String),
     #[error("request rejected: {0}")]
-    #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
     Rejected(String),
     #[error("plugin error: {0}")]
     Internal(String),
@@ -29,9 +30,158 @@ pub enum PluginError {
 pub struct AuthContext {
     pub headers: HashMap<String, String>,
     pub config: HashMap<String, String>,
+    /// Outbound HTTP method.
+    pub method: String,
+    /// Outbound path and query string, as sent to the upstream.
+    pub path: String,
+    /// Outbound request body. Empty for WebSocket and gRPC requests, whose
+    /// bodies are streamed to the upstream.
+    pub body: Bytes,
 }
 
 #[async_trait::async_trait]
 pub trait AuthPlugin: Send + Sync {
     async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError>;
+
+    /// Called when the upstream rejected a request authenticated by this
+    /// plugin with `401`. Plugins caching credentials should drop them so the
+    /// next request fetches fresh ones. The rejected request is not retried.
+    async fn on_unauthorized(&self, _config: &HashMap<String, String>) {}
+}
+
+// ---------------------------------------------------------------------------
+// Guard plugin
+// ---------------------------------------------------------------------------
+
+/// Inbound request as seen by guard plugins.
+#[domain_model]
+pub struct GuardContext {
+    /// Inbound HTTP method.
+    pub method: String,
+    /// Inbound request headers with lowercase names.
+    pub headers: HashMap<String, String>,
+    pub config: HashMap<String, String>,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardDecision {
+    /// Let the request through; the headers are added to the response
+    /// returned to the client.
+    Allow {
+        response_headers: Vec<(String, String)>,
+    },
+    /// Answer locally without contacting the upstream.
+    Respond {
+        status: u16,
+        headers: Vec<(String, String)>,
+    },
+}
+
+#[async_trait::async_trait]
+pub trait GuardPlugin: Send + Sync {
+    /// Inspect the inbound request. Returns `PluginError::Rejected` to refuse it.
+    async fn guard_request(&self, ctx: &GuardContext) -> Result<GuardDecision, PluginError>;
+}
+
+// ---------------------------------------------------------------------------
+// Plugin chain
+// ---------------------------------------------------------------------------
+
+/// A plugin attached to a request, with its configuration.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginBinding {
+    /// GTS identifier (builtin) or UUID (custom).
+    pub plugin_ref: String,
+    pub config: HashMap<String, String>,
+    /// Bound by a level with `sharing: enforce`; later levels cannot change
+    /// its configuration.
+    pub enforced: bool,
+}
+
+/// Append one configuration level to the chain built from the levels above it.
+///
+/// New plugins are appended in `items` order. A plugin already in the chain
+/// keeps its position; its configuration is replaced by this level's unless
+/// it was enforced.
+#[must_use]
+pub fn merge_plugins(
+    mut chain: Vec<PluginBinding>,
+    level: Option<&PluginsConfig>,
+) -> Vec<PluginBinding> {
+    let Some(level) = level else {
+        return chain;
+    };
+    let enforced = level.sharing == SharingMode::Enforce;
+    for plugin_ref in &level.items {
+        let config = level.config.get(plugin_ref).cloned().unwrap_or_default();
+        match chain.iter_mut().find(|b| &b.plugin_ref == plugin_ref) {
+            Some(existing) if existing.enforced => {}
+            Some(existing) => {
+                existing.config = config;
+                existing.enforced = enforced;
+            }
+            None => chain.push(PluginBinding {
+                plugin_ref: plugin_ref.clone(),
+                config,
+                enforced,
+            }),
+        }
+    }
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(sharing: SharingMode, items: &[(&str, &str)]) -> PluginsConfig {
+        PluginsConfig {
+            sharing,
+            items: items.iter().map(|(id, _)| (*id).to_string()).collect(),
+            config: items
+                .iter()
+                .map(|(id, origin)| {
+                    (
+                        (*id).to_string(),
+                        HashMap::from([("allowed_origins".to_string(), (*origin).to_string())]),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn origins(chain: &[PluginBinding]) -> Vec<(&str, &str)> {
+        chain
+            .iter()
+            .map(|b| (b.plugin_ref.as_str(), b.config["allowed_origins"].as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn later_levels_append_and_override() {
+        let upstream = level(SharingMode::Inherit, &[("cors", "https://a"), ("x", "-")]);
+        let route = level(SharingMode::Private, &[("cors", "https://b"), ("y", "-")]);
+
+        let chain = merge_plugins(Vec::new(), Some(&upstream));
+        let chain = merge_plugins(chain, None);
+        let chain = merge_plugins(chain, Some(&route));
+
+        assert_eq!(
+            origins(&chain),
+            vec![("cors", "https://b"), ("x", "-"), ("y", "-")]
+        );
+    }
+
+    #[test]
+    fn enforced_config_cannot_be_overridden() {
+        let upstream = level(SharingMode::Enforce, &[("cors", "https://a")]);
+        let route = level(SharingMode::Private, &[("cors", "https://evil")]);
+
+        let chain = merge_plugins(merge_plugins(Vec::new(), Some(&upstream)), Some(&route));
+
+        assert_eq!(origins(&chain), vec![("cors", "https://a")]);
+        assert!(chain[0].enforced);
+    }
 }
@@ -1,14 +1,97 @@
 use std::collections::HashSet;
-use std::time::Instant;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::domain::error::DomainError;
-use crate::domain::model::{RateLimitConfig, Window};
+use crate::domain::model::{
+    BurstConfig, FallbackResponse, QueueConfig, RateLimitAlgorithm, RateLimitConfig,
+    RateLimitScope, RateLimitStrategy, SustainedRate, Window,
+};
 use dashmap::DashMap;
 use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
 
+/// Wait reported when a limit can never be satisfied (zero rate or cost above capacity).
+const UNSATISFIABLE_WAIT: Duration = Duration::from_secs(60);
+
+/// Lower bound on a queued request's sleep between capacity checks.
+const MIN_QUEUE_POLL: Duration = Duration::from_millis(1);
+
+/// Per-key rate limiters plus the wait queues used by `RateLimitStrategy::Queue`.
+///
+/// State is local to this node.
 #[domain_model]
 pub struct RateLimiter {
-    buckets: DashMap<String, TokenBucket>,
+    limiters: DashMap<String, Limiter>,
+    queue_depths: DashMap<String, Arc<AtomicU32>>,
+}
+
+/// Outcome of [`RateLimiter::acquire`] for requests that may proceed.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Admission {
+    /// Capacity was available, possibly after waiting in the queue.
+    Admitted(RateLimitQuota),
+    /// The limit was exceeded and the `degrade` strategy applies: serve this
+    /// response instead of calling the upstream.
+    Degraded(FallbackResponse),
+}
+
+/// Limiter state right after a request was admitted, reported to clients
+/// through `X-RateLimit-*` headers.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    /// Requests allowed per window (or burst capacity for token buckets).
+    pub limit: u32,
+    /// Requests that can still be made right now.
+    pub remaining: u32,
+    /// Time until the limiter is back to full capacity.
+    pub reset_after: Duration,
+}
+
+#[domain_model]
+enum Limiter {
+    TokenBucket(TokenBucket),
+    SlidingWindow(SlidingWindow),
+}
+
+impl Limiter {
+    fn new(config: &RateLimitConfig, now: Instant) -> Self {
+        match config.algorithm {
+            RateLimitAlgorithm::TokenBucket => Self::TokenBucket(TokenBucket::new(config, now)),
+            RateLimitAlgorithm::SlidingWindow => {
+                Self::SlidingWindow(SlidingWindow::new(config, now))
+            }
+        }
+    }
+
+    /// Whether this limiter was built from an equivalent configuration.
+    fn matches(&self, config: &RateLimitConfig) -> bool {
+        match self {
+            Self::TokenBucket(b) => {
+                config.algorithm == RateLimitAlgorithm::TokenBucket
+                    && b.capacity == TokenBucket::capacity_for(config)
+                    && b.refill_rate == TokenBucket::refill_rate_for(config)
+            }
+            Self::SlidingWindow(w) => {
+                config.algorithm == RateLimitAlgorithm::SlidingWindow
+                    && w.limit == f64::from(config.sustained.rate)
+                    && w.window == window_duration(config.sustained.window)
+            }
+        }
+    }
+
+    /// Consume `cost` or return how long until it could be consumed.
+    fn try_acquire(&mut self, cost: f64, now: Instant) -> Result<RateLimitQuota, Duration> {
+        match self {
+            Self::TokenBucket(b) => b.try_acquire(cost, now),
+            Self::SlidingWindow(w) => w.try_acquire(cost, now),
+        }
+    }
 }
 
 #[domain_model]
@@ -20,56 +103,161 @@ struct TokenBucket {
 }
 
 impl TokenBucket {
-    fn new(config: &RateLimitConfig) -> Self {
-        let capacity = config
-            .burst
-            .as_ref()
-            .map_or(config.sustained.rate as f64, |b| b.capacity as f64);
-        let window_secs = window_to_secs(&config.sustained.window);
-        let refill_rate = config.sustained.rate as f64 / window_secs;
+    fn new(config: &RateLimitConfig, now: Instant) -> Self {
+        let capacity = Self::capacity_for(config);
         Self {
             capacity,
             tokens: capacity,
-            refill_rate,
-            last_refill: Instant::now(),
+            refill_rate: Self::refill_rate_for(config),
+            last_refill: now,
         }
     }
 
-    fn refill(&mut self) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+    fn capacity_for(config: &RateLimitConfig) -> f64 {
+        config
+            .burst
+            .as_ref()
+            .map_or(config.sustained.rate as f64, |b| b.capacity as f64)
+    }
+
+    fn refill_rate_for(config: &RateLimitConfig) -> f64 {
+        config.sustained.rate as f64 / window_duration(config.sustained.window).as_secs_f64()
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
         self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity);
         self.last_refill = now;
     }
 
-    fn try_consume(&mut self, cost: f64) -> bool {
-        self.refill();
+    fn try_acquire(&mut self, cost: f64, now: Instant) -> Result<RateLimitQuota, Duration> {
+        self.refill(now);
         if self.tokens >= cost {
             self.tokens -= cost;
-            true
-        } else {
-            false
+            return Ok(self.quota());
+        }
+        if self.refill_rate <= 0.0 || cost > self.capacity {
+            return Err(UNSATISFIABLE_WAIT);
+        }
+        Err(Duration::from_secs_f64(
+            (cost - self.tokens) / self.refill_rate,
+        ))
+    }
+
+    fn quota(&self) -> RateLimitQuota {
+        let missing = self.capacity - self.tokens;
+        RateLimitQuota {
+            limit: self.capacity as u32,
+            remaining: self.tokens.floor() as u32,
+            reset_after: if missing > 0.0 && self.refill_rate > 0.0 {
+                Duration::from_secs_f64(missing / self.refill_rate)
+            } else {
+                Duration::ZERO
+            },
+        }
+    }
+}
+
+/// Sliding-window counter: the previous fixed window's count is weighted by
+/// how much of it still overlaps the sliding window ending now.
+#[domain_model]
+struct SlidingWindow {
+    limit: f64,
+    window: Duration,
+    origin: Instant,
+    current_index: u64,
+    current: f64,
+    previous: f64,
+}
+
+impl SlidingWindow {
+    fn new(config: &RateLimitConfig, now: Instant) -> Self {
+        Self {
+            limit: f64::from(config.sustained.rate),
+            window: window_duration(config.sustained.window),
+            origin: now,
+            current_index: 0,
+            current: 0.0,
+            previous: 0.0,
+        }
+    }
+
+    /// Roll the fixed windows forward and return the elapsed fraction of the current one.
+    fn advance(&mut self, now: Instant) -> f64 {
+        let position =
+            now.saturating_duration_since(self.origin).as_secs_f64() / self.window.as_secs_f64();
+        let index = position.floor() as u64;
+        if index == self.current_index + 1 {
+            self.previous = self.current;
+            self.current = 0.0;
+        } else if index > self.current_index {
+            self.previous = 0.0;
+            self.current = 0.0;
         }
+        self.current_index = index;
+        position.fract()
     }
 
-    fn retry_after_secs(&self, cost: f64) -> u64 {
-        if self.refill_rate <= 0.0 {
-            return 60;
+    fn try_acquire(&mut self, cost: f64, now: Instant) -> Result<RateLimitQuota, Duration> {
+        let elapsed = self.advance(now);
+        let used = self.previous * (1.0 - elapsed) + self.current;
+        if used + cost <= self.limit {
+            self.current += cost;
+            return Ok(self.quota(used + cost, elapsed));
         }
-        let needed = cost - self.tokens;
-        if needed <= 0.0 {
-            return 0;
+        if cost > self.limit {
+            return Err(UNSATISFIABLE_WAIT);
+        }
+        // Fraction of a window (measured from the current window start) at
+        // which the weighted count drops far enough to admit `cost`.
+        let target = if self.current + cost <= self.limit {
+            1.0 - (self.limit - cost - self.current) / self.previous
+        } else {
+            // Nothing frees up until the current window becomes the previous one.
+            2.0 - (self.limit - cost) / self.current
+        };
+        Err(self.window.mul_f64((target - elapsed).max(0.0)))
+    }
+
+    fn quota(&self, used: f64, elapsed: f64) -> RateLimitQuota {
+        // Counts in the current window stop weighing once it has fully slid
+        // out, i.e. at the end of the next window.
+        let clear_at = if self.current > 0.0 {
+            2.0
+        } else if self.previous > 0.0 {
+            1.0
+        } else {
+            elapsed
+        };
+        RateLimitQuota {
+            limit: self.limit as u32,
+            remaining: (self.limit - used).max(0.0).floor() as u32,
+            reset_after: self.window.mul_f64(clear_at - elapsed),
         }
-        (needed / self.refill_rate).ceil() as u64
     }
 }
 
-fn window_to_secs(window: &Window) -> f64 {
+fn window_duration(window: Window) -> Duration {
     match window {
-        Window::Second => 1.0,
-        Window::Minute => 60.0,
-        Window::Hour => 3600.0,
-        Window::Day => 86400.0,
+        Window::Second => Duration::from_secs(1),
+        Window::Minute => Duration::from_secs(60),
+        Window::Hour => Duration::from_secs(3600),
+        Window::Day => Duration::from_secs(86400),
+    }
+}
+
+fn retry_after_secs(wait: Duration) -> u64 {
+    wait.as_secs_f64().ceil() as u64
+}
+
+/// Releases a queue slot when the waiting request finishes or is dropped.
+struct QueueSlot(Arc<AtomicU32>);
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
     }
 }
 
@@ -77,7 +265,8 @@ impl RateLimiter {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            buckets: DashMap::new(),
+            limiters: DashMap::new(),
+            queue_depths: DashMap::new(),
         }
     }
 
@@ -87,10 +276,24 @@ impl RateLimiter {
     // so that stale rate-limit buckets are purged when entities are removed.
     #[allow(dead_code)]
     pub fn purge_keys(&self, active_keys: &HashSet<String>) {
-        self.buckets.retain(|k, _| active_keys.contains(k));
+        self.limiters.retain(|k, _| active_keys.contains(k));
+        self.queue_depths
+            .retain(|k, d| active_keys.contains(k) || d.load(Ordering::Acquire) > 0);
     }
 
-    /// Try to consume tokens for the given key.
+    fn check(&self, key: &str, config: &RateLimitConfig) -> Result<RateLimitQuota, Duration> {
+        let now = Instant::now();
+        let mut limiter = self
+            .limiters
+            .entry(key.to_string())
+            .or_insert_with(|| Limiter::new(config, now));
+        if !limiter.matches(config) {
+            *limiter = Limiter::new(config, now);
+        }
+        limiter.try_acquire(config.cost as f64, now)
+    }
+
+    /// Try to consume capacity for the given key without waiting.
     ///
     /// # Errors
     /// Returns `DomainError::RateLimitExceeded` with Retry-After seconds when exhausted.
@@ -99,31 +302,217 @@ impl RateLimiter {
         key: &str,
         config: &RateLimitConfig,
         instance_uri: &str,
-    ) -> Result<(), DomainError> {
-        let cost = config.cost as f64;
-        let mut bucket = self
-            .buckets
-            .entry(key.to_string())
-            .or_insert_with(|| TokenBucket::new(config));
-
-        if bucket.try_consume(cost) {
-            Ok(())
-        } else {
-            let retry_after = bucket.retry_after_secs(cost);
-            Err(DomainError::RateLimitExceeded {
+    ) -> Result<RateLimitQuota, DomainError> {
+        self.check(key, config)
+            .map_err(|wait| DomainError::RateLimitExceeded {
                 detail: format!("rate limit exceeded for key: {key}"),
                 instance: instance_uri.to_string(),
-                retry_after_secs: Some(retry_after),
+                retry_after_secs: Some(retry_after_secs(wait)),
             })
+    }
+
+    /// Consume capacity for the given key, applying the configured strategy
+    /// when the limit is exceeded.
+    ///
+    /// - `reject` fails immediately.
+    /// - `queue` waits for capacity up to `queue.timeout`, with at most
+    ///   `queue.max_depth` requests waiting per key.
+    /// - `degrade` returns [`Admission::Degraded`] with the configured
+    ///   fallback response, or rejects when no fallback is configured.
+    ///
+    /// # Errors
+    /// Returns `DomainError::RateLimitExceeded` when rejected (including a
+    /// full queue) and `DomainError::QueueTimeout` when a queued request
+    /// does not get capacity in time.
+    pub async fn acquire(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+    ) -> Result<Admission, DomainError> {
+        let err = match self.try_consume(key, config, instance_uri) {
+            Ok(quota) => return Ok(Admission::Admitted(quota)),
+            Err(err) => err,
+        };
+        match (config.strategy, &config.queue, &config.degrade) {
+            (RateLimitStrategy::Queue, Some(queue), _) => self
+                .wait_in_queue(key, config, queue, instance_uri)
+                .await
+                .map(Admission::Admitted),
+            (RateLimitStrategy::Degrade, _, Some(degrade)) => {
+                tracing::debug!(
+                    metric = "oagw_backpressure_total",
+                    key,
+                    strategy = "degrade",
+                    reason = "rate_limit",
+                    "Serving degraded fallback response"
+                );
+                Ok(Admission::Degraded(degrade.fallback_response.clone()))
+            }
+            _ => Err(err),
+        }
+    }
+
+    async fn wait_in_queue(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        queue: &QueueConfig,
+        instance_uri: &str,
+    ) -> Result<RateLimitQuota, DomainError> {
+        let depth = self
+            .queue_depths
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        if depth.fetch_add(1, Ordering::AcqRel) >= queue.max_depth {
+            depth.fetch_sub(1, Ordering::AcqRel);
+            tracing::debug!(
+                metric = "oagw_backpressure_total",
+                key,
+                strategy = "queue",
+                reason = "queue_full",
+                "Rate limit queue full"
+            );
+            return Err(DomainError::RateLimitExceeded {
+                detail: format!(
+                    "rate limit exceeded for key: {key}; wait queue is full ({} requests)",
+                    queue.max_depth
+                ),
+                instance: instance_uri.to_string(),
+                retry_after_secs: Some(retry_after_secs(queue.timeout).max(1)),
+            });
+        }
+        let _slot = QueueSlot(depth);
+
+        let started = Instant::now();
+        let deadline = started + queue.timeout;
+        loop {
+            let wait = match self.check(key, config) {
+                Ok(quota) => return Ok(quota),
+                Err(wait) => wait,
+            };
+            let now = Instant::now();
+            if now >= deadline {
+                tracing::debug!(
+                    metric = "oagw_backpressure_total",
+                    key,
+                    strategy = "queue",
+                    reason = "queue_timeout",
+                    "Rate limit queue wait timed out"
+                );
+                return Err(DomainError::QueueTimeout {
+                    detail: format!(
+                        "request queued for {:.1}s, no rate limit capacity available for key: {key}",
+                        now.duration_since(started).as_secs_f64()
+                    ),
+                    instance: instance_uri.to_string(),
+                    retry_after_secs: retry_after_secs(wait).max(1),
+                });
+            }
+            tokio::time::sleep(wait.max(MIN_QUEUE_POLL).min(deadline - now)).await;
+        }
+    }
+}
+
+/// Limits that apply to one proxied request, as resolved by the Control Plane.
+#[domain_model]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EffectiveRateLimits {
+    /// The upstream's limit (narrowed by what ancestors share), counted in
+    /// an aggregate bucket across all of the upstream's routes.
+    pub upstream: Option<RateLimitConfig>,
+    /// The route's limit narrowed by the upstream limit; `None` when the
+    /// route sets no limit of its own.
+    pub route: Option<RateLimitConfig>,
+}
+
+/// Bucket key for a request, partitioned by the limit's scope.
+///
+/// Keys are always prefixed with the upstream id so that limiters of
+/// different upstreams never share state. `client_ip` is `None` when the
+/// transport did not report a peer address; such requests share one bucket.
+#[must_use]
+pub fn bucket_key(
+    config: &RateLimitConfig,
+    ctx: &SecurityContext,
+    upstream_id: Uuid,
+    route_id: Uuid,
+    client_ip: Option<IpAddr>,
+) -> String {
+    match config.scope {
+        RateLimitScope::Global => format!("upstream:{upstream_id}:global"),
+        RateLimitScope::Tenant => {
+            format!("upstream:{upstream_id}:tenant:{}", ctx.subject_tenant_id())
         }
+        RateLimitScope::User => format!("upstream:{upstream_id}:user:{}", ctx.subject_id()),
+        RateLimitScope::Ip => match client_ip {
+            Some(ip) => format!("upstream:{upstream_id}:ip:{ip}"),
+            None => format!("upstream:{upstream_id}:ip:unknown"),
+        },
+        RateLimitScope::Route => format!("upstream:{upstream_id}:route:{route_id}"),
     }
 }
 
+/// Bucket key for an upstream's aggregate limit.
+///
+/// Partitioned like [`bucket_key`], except that a `route` scope counts all
+/// routes of the upstream together.
+#[must_use]
+pub fn aggregate_bucket_key(
+    config: &RateLimitConfig,
+    ctx: &SecurityContext,
+    upstream_id: Uuid,
+    client_ip: Option<IpAddr>,
+) -> String {
+    let scope = match config.scope {
+        RateLimitScope::Global | RateLimitScope::Route => "global".to_string(),
+        RateLimitScope::Tenant => format!("tenant:{}", ctx.subject_tenant_id()),
+        RateLimitScope::User => format!("user:{}", ctx.subject_id()),
+        RateLimitScope::Ip => match client_ip {
+            Some(ip) => format!("ip:{ip}"),
+            None => "ip:unknown".to_string(),
+        },
+    };
+    format!("upstream:{upstream_id}:aggregate:{scope}")
+}
+
+/// Combine an inherited limit with a more specific one.
+///
+/// The more specific (`child`) configuration wins for everything except the
+/// numbers: the stricter sustained rate and the smaller burst capacity are
+/// kept, so a descendant can tighten but never loosen what it inherits.
+#[must_use]
+pub fn min_merge(
+    parent: Option<&RateLimitConfig>,
+    child: Option<&RateLimitConfig>,
+) -> Option<RateLimitConfig> {
+    let (parent, child) = match (parent, child) {
+        (None, None) => return None,
+        (Some(only), None) | (None, Some(only)) => return Some(only.clone()),
+        (Some(parent), Some(child)) => (parent, child),
+    };
+    let mut merged = child.clone();
+    if per_second(&parent.sustained) < per_second(&child.sustained) {
+        merged.sustained = parent.sustained.clone();
+    }
+    if parent.burst.is_some() || child.burst.is_some() {
+        let capacity =
+            |c: &RateLimitConfig| c.burst.as_ref().map_or(c.sustained.rate, |b| b.capacity);
+        merged.burst = Some(BurstConfig {
+            capacity: capacity(parent).min(capacity(child)),
+        });
+    }
+    Some(merged)
+}
+
+fn per_second(rate: &SustainedRate) -> f64 {
+    f64::from(rate.rate) / window_duration(rate.window).as_secs_f64()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{
-        BurstConfig, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy, SustainedRate,
-    };
+    use crate::domain::model::{BurstConfig, DegradeConfig, RateLimitScope, SustainedRate};
 
     use super::*;
 
@@ -136,6 +525,9 @@ mod tests {
             scope: RateLimitScope::Tenant,
             strategy: RateLimitStrategy::Reject,
             cost: 1,
+            queue: None,
+            degrade: None,
+            response_headers: true,
         }
     }
 
@@ -207,9 +599,9 @@ mod tests {
         limiter.purge_keys(&active);
 
         // a and c survive, b is gone.
-        assert!(limiter.buckets.contains_key("a"));
-        assert!(!limiter.buckets.contains_key("b"));
-        assert!(limiter.buckets.contains_key("c"));
+        assert!(limiter.limiters.contains_key("a"));
+        assert!(!limiter.limiters.contains_key("b"));
+        assert!(limiter.limiters.contains_key("c"));
     }
 
     #[test]
@@ -221,6 +613,349 @@ mod tests {
 
         limiter.purge_keys(&HashSet::new());
 
-        assert!(limiter.buckets.is_empty());
+        assert!(limiter.limiters.is_empty());
+    }
+
+    fn sliding_config(rate: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..make_config(rate, Window::Second, None)
+        }
+    }
+
+    #[test]
+    fn sliding_window_weights_previous_window() {
+        let config = sliding_config(10);
+        let t0 = Instant::now();
+        let mut window = SlidingWindow::new(&config, t0);
+
+        // Full burst at the end of the first window.
+        let late = t0 + Duration::from_millis(900);
+        for _ in 0..10 {
+            assert!(window.try_acquire(1.0, late).is_ok());
+        }
+
+        // Just past the boundary ~95% of the previous window still counts.
+        let early = t0 + Duration::from_millis(1050);
+        let wait = window.try_acquire(1.0, early).unwrap_err();
+        assert!(wait > Duration::ZERO && wait <= Duration::from_secs(1));
+
+        // Half way through, half of the previous count has slid out.
+        let mid = t0 + Duration::from_millis(1500);
+        let admitted = (0..10)
+            .filter(|_| window.try_acquire(1.0, mid).is_ok())
+            .count();
+        assert_eq!(admitted, 5);
+    }
+
+    #[test]
+    fn sliding_window_resets_after_idle_windows() {
+        let config = sliding_config(2);
+        let t0 = Instant::now();
+        let mut window = SlidingWindow::new(&config, t0);
+        assert!(window.try_acquire(2.0, t0).is_ok());
+        assert!(window.try_acquire(1.0, t0).is_err());
+        assert!(window.try_acquire(2.0, t0 + Duration::from_secs(3)).is_ok());
+    }
+
+    #[test]
+    fn sliding_window_retry_after_admits_next_request() {
+        let config = sliding_config(4);
+        let t0 = Instant::now();
+        let mut window = SlidingWindow::new(&config, t0);
+        let start = t0 + Duration::from_millis(500);
+        for _ in 0..4 {
+            window.try_acquire(1.0, start).unwrap();
+        }
+        let wait = window.try_acquire(1.0, start).unwrap_err();
+        assert!(window.try_acquire(1.0, start + wait).is_ok());
+    }
+
+    #[test]
+    fn sliding_window_limiter_rejects_with_retry_after() {
+        let limiter = RateLimiter::new();
+        let config = sliding_config(2);
+        assert!(limiter.try_consume("sw", &config, "/test").is_ok());
+        assert!(limiter.try_consume("sw", &config, "/test").is_ok());
+        match limiter.try_consume("sw", &config, "/test") {
+            Err(DomainError::RateLimitExceeded {
+                retry_after_secs: Some(secs),
+                ..
+            }) => assert!((1..=2).contains(&secs)),
+            other => panic!("expected RateLimitExceeded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn config_change_rebuilds_limiter() {
+        let limiter = RateLimiter::new();
+        let config = make_config(1, Window::Minute, None);
+        assert!(limiter.try_consume("k", &config, "/test").is_ok());
+        assert!(limiter.try_consume("k", &config, "/test").is_err());
+
+        let raised = make_config(5, Window::Minute, None);
+        assert!(limiter.try_consume("k", &raised, "/test").is_ok());
+    }
+
+    fn queue_config(
+        rate: u32,
+        window: Window,
+        max_depth: u32,
+        timeout: Duration,
+    ) -> RateLimitConfig {
+        RateLimitConfig {
+            strategy: RateLimitStrategy::Queue,
+            queue: Some(QueueConfig { max_depth, timeout }),
+            ..make_config(rate, window, None)
+        }
+    }
+
+    #[tokio::test]
+    async fn queue_waits_for_capacity() {
+        let limiter = RateLimiter::new();
+        let config = queue_config(20, Window::Second, 5, Duration::from_secs(1));
+        for _ in 0..20 {
+            limiter.try_consume("q", &config, "/test").unwrap();
+        }
+        let started = Instant::now();
+        let admission = limiter.acquire("q", &config, "/test").await.unwrap();
+        assert!(matches!(admission, Admission::Admitted(_)));
+        assert!(started.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn queue_times_out_with_queue_timeout() {
+        let limiter = RateLimiter::new();
+        let config = queue_config(1, Window::Minute, 5, Duration::from_millis(50));
+        limiter.try_consume("q", &config, "/test").unwrap();
+        match limiter.acquire("q", &config, "/test").await {
+            Err(DomainError::QueueTimeout {
+                retry_after_secs, ..
+            }) => assert!(retry_after_secs >= 1),
+            other => panic!("expected QueueTimeout, got {other:?}"),
+        }
+        // The slot is released once the request gives up.
+        assert_eq!(
+            limiter
+                .queue_depths
+                .get("q")
+                .unwrap()
+                .load(Ordering::Acquire),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn full_queue_rejects_immediately() {
+        let limiter = RateLimiter::new();
+        let config = queue_config(1, Window::Minute, 1, Duration::from_millis(200));
+        limiter.try_consume("q", &config, "/test").unwrap();
+
+        let (waiting, rejected) = tokio::join!(limiter.acquire("q", &config, "/test"), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            limiter.acquire("q", &config, "/test").await
+        });
+        assert!(matches!(waiting, Err(DomainError::QueueTimeout { .. })));
+        assert!(matches!(
+            rejected,
+            Err(DomainError::RateLimitExceeded { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn degrade_returns_fallback_response() {
+        let limiter = RateLimiter::new();
+        let fallback = FallbackResponse {
+            status: 503,
+            content_type: "application/json".into(),
+            body: r#"{"error":"degraded"}"#.into(),
+        };
+        let config = RateLimitConfig {
+            strategy: RateLimitStrategy::Degrade,
+            degrade: Some(DegradeConfig {
+                fallback_response: fallback.clone(),
+            }),
+            ..make_config(1, Window::Minute, None)
+        };
+        assert!(matches!(
+            limiter.acquire("d", &config, "/test").await.unwrap(),
+            Admission::Admitted(_)
+        ));
+        assert_eq!(
+            limiter.acquire("d", &config, "/test").await.unwrap(),
+            Admission::Degraded(fallback)
+        );
+    }
+
+    #[tokio::test]
+    async fn degrade_without_fallback_rejects() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig {
+            strategy: RateLimitStrategy::Degrade,
+            ..make_config(1, Window::Minute, None)
+        };
+        limiter.acquire("d", &config, "/test").await.unwrap();
+        assert!(matches!(
+            limiter.acquire("d", &config, "/test").await,
+            Err(DomainError::RateLimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn token_bucket_reports_quota() {
+        let limiter = RateLimiter::new();
+        let config = make_config(10, Window::Second, Some(20));
+        let quota = limiter.try_consume("q", &config, "/test").unwrap();
+        assert_eq!(quota.limit, 20);
+        assert_eq!(quota.remaining, 19);
+        // One token at 10/s refills in ~100ms.
+        assert!(quota.reset_after <= Duration::from_millis(100));
+        assert!(quota.reset_after > Duration::from_millis(50));
+    }
+
+    #[test]
+    fn sliding_window_reports_quota() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..make_config(5, Window::Minute, None)
+        };
+        limiter.try_consume("q", &config, "/test").unwrap();
+        let quota = limiter.try_consume("q", &config, "/test").unwrap();
+        assert_eq!(quota.limit, 5);
+        assert_eq!(quota.remaining, 3);
+        // Counts in the current window weigh until the next one ends.
+        assert!(quota.reset_after > Duration::from_secs(60));
+        assert!(quota.reset_after <= Duration::from_secs(120));
+    }
+
+    fn ctx(tenant_id: Uuid, subject_id: Uuid) -> SecurityContext {
+        SecurityContext::builder()
+            .subject_tenant_id(tenant_id)
+            .subject_id(subject_id)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn bucket_key_partitions_by_scope() {
+        let upstream_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let (tenant, user) = (Uuid::new_v4(), Uuid::new_v4());
+        let ctx = ctx(tenant, user);
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let key = |scope| {
+            let config = RateLimitConfig {
+                scope,
+                ..make_config(1, Window::Second, None)
+            };
+            bucket_key(&config, &ctx, upstream_id, route_id, Some(ip))
+        };
+
+        assert_eq!(
+            key(RateLimitScope::Global),
+            format!("upstream:{upstream_id}:global")
+        );
+        assert_eq!(
+            key(RateLimitScope::Tenant),
+            format!("upstream:{upstream_id}:tenant:{tenant}")
+        );
+        assert_eq!(
+            key(RateLimitScope::User),
+            format!("upstream:{upstream_id}:user:{user}")
+        );
+        assert_eq!(
+            key(RateLimitScope::Ip),
+            format!("upstream:{upstream_id}:ip:203.0.113.7")
+        );
+        assert_eq!(
+            key(RateLimitScope::Route),
+            format!("upstream:{upstream_id}:route:{route_id}")
+        );
+    }
+
+    #[test]
+    fn bucket_key_without_client_ip_uses_shared_bucket() {
+        let config = RateLimitConfig {
+            scope: RateLimitScope::Ip,
+            ..make_config(1, Window::Second, None)
+        };
+        let upstream_id = Uuid::new_v4();
+        let key = bucket_key(
+            &config,
+            &ctx(Uuid::new_v4(), Uuid::new_v4()),
+            upstream_id,
+            Uuid::new_v4(),
+            None,
+        );
+        assert_eq!(key, format!("upstream:{upstream_id}:ip:unknown"));
+    }
+
+    #[test]
+    fn aggregate_bucket_key_spans_routes() {
+        let upstream_id = Uuid::new_v4();
+        let tenant = Uuid::new_v4();
+        let ctx = ctx(tenant, Uuid::new_v4());
+        let key = |scope| {
+            let config = RateLimitConfig {
+                scope,
+                ..make_config(1, Window::Second, None)
+            };
+            aggregate_bucket_key(&config, &ctx, upstream_id, None)
+        };
+
+        assert_eq!(
+            key(RateLimitScope::Route),
+            format!("upstream:{upstream_id}:aggregate:global")
+        );
+        assert_eq!(
+            key(RateLimitScope::Tenant),
+            format!("upstream:{upstream_id}:aggregate:tenant:{tenant}")
+        );
+        // Never shares state with a per-route bucket.
+        assert_ne!(
+            key(RateLimitScope::Global),
+            format!("upstream:{upstream_id}:global")
+        );
+    }
+
+    #[test]
+    fn min_merge_with_one_side_returns_it() {
+        let config = make_config(10, Window::Second, None);
+        assert_eq!(min_merge(None, None), None);
+        assert_eq!(min_merge(Some(&config), None), Some(config.clone()));
+        assert_eq!(min_merge(None, Some(&config)), Some(config));
+    }
+
+    #[test]
+    fn min_merge_keeps_stricter_rate_across_windows() {
+        // 1000/min (~16.7/s) is stricter than 100/s.
+        let parent = make_config(1000, Window::Minute, None);
+        let child = make_config(100, Window::Second, None);
+        let merged = min_merge(Some(&parent), Some(&child)).unwrap();
+        assert_eq!(merged.sustained, parent.sustained);
+
+        // A descendant can always tighten.
+        let child = make_config(2, Window::Minute, None);
+        let merged = min_merge(Some(&parent), Some(&child)).unwrap();
+        assert_eq!(merged.sustained, child.sustained);
+    }
+
+    #[test]
+    fn min_merge_takes_smaller_burst_and_child_settings() {
+        let parent = make_config(100, Window::Second, Some(50));
+        let child = RateLimitConfig {
+            scope: RateLimitScope::User,
+            strategy: RateLimitStrategy::Queue,
+            response_headers: false,
+            ..make_config(200, Window::Second, None)
+        };
+        let merged = min_merge(Some(&parent), Some(&child)).unwrap();
+        assert_eq!(merged.sustained.rate, 100);
+        // Child without burst has an effective capacity of its rate (200).
+        assert_eq!(merged.burst, Some(BurstConfig { capacity: 50 }));
+        assert_eq!(merged.scope, RateLimitScope::User);
+        assert_eq!(merged.strategy, RateLimitStrategy::Queue);
+        assert!(!merged.response_headers);
     }
 }
@@ -43,6 +43,9 @@ pub trait UpstreamRepository: Send + Sync {
         query: &ListQuery,
     ) -> Result<Vec<Upstream>, RepositoryError>;
 
+    /// List the upstreams of every tenant, for system tasks such as health probing.
+    async fn list_all(&self) -> Result<Vec<Upstream>, RepositoryError>;
+
     /// Update an existing upstream. Preserves id and tenant_id.
     async fn update(&self, upstream: Upstream) -> Result<Upstream, RepositoryError>;
 
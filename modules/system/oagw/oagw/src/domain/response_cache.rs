@@ -0,0 +1,13 @@
+use uuid::Uuid;
+
+/// Invalidation hook for the Data Plane's HTTP response cache.
+///
+/// Called by the Control Plane after a management write succeeds, so the
+/// next proxied request is served from the upstream with the new config.
+pub(crate) trait ResponseCacheInvalidator: Send + Sync {
+    /// Drop every cached response served through routes of `upstream_id`.
+    fn invalidate_upstream(&self, tenant_id: Uuid, upstream_id: Uuid);
+
+    /// Drop every cached response served through `route_id`.
+    fn invalidate_route(&self, tenant_id: Uuid, route_id: Uuid);
+}
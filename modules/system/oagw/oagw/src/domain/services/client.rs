@@ -232,6 +232,9 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
         DomainError::AuthenticationFailed { detail, instance } => {
             ServiceGatewayError::AuthenticationFailed { detail, instance }
         }
+        DomainError::GuardRejected { detail, instance } => {
+            ServiceGatewayError::GuardRejected { detail, instance }
+        }
         DomainError::PayloadTooLarge { detail, instance } => {
             ServiceGatewayError::PayloadTooLarge { detail, instance }
         }
@@ -244,6 +247,24 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
             instance,
             retry_after_secs,
         },
+        DomainError::QueueTimeout {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::QueueTimeout {
+            detail,
+            instance,
+            retry_after_secs,
+        },
+        DomainError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        },
         DomainError::SecretNotFound { detail, instance } => {
             ServiceGatewayError::SecretNotFound { detail, instance }
         }
@@ -277,6 +298,14 @@ fn sdk_create_upstream_to_domain(
         headers: req.headers().cloned().map(headers_config_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        load_balancing: req
+            .load_balancing()
+            .cloned()
+            .map(load_balancing_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
         tags: req.tags().to_vec(),
         enabled: req.enabled(),
     }
@@ -293,6 +322,14 @@ fn sdk_update_upstream_to_domain(
         headers: req.headers().cloned().map(headers_config_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        load_balancing: req
+            .load_balancing()
+            .cloned()
+            .map(load_balancing_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
         tags: req.tags().map(|s| s.to_vec()),
         enabled: req.enabled(),
     }
@@ -304,6 +341,7 @@ fn sdk_create_route_to_domain(req: oagw_sdk::CreateRouteRequest) -> model::Creat
         match_rules: match_rules_to_domain(req.match_rules().clone()),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        cache: req.cache().cloned().map(cache_config_to_domain),
         tags: req.tags().to_vec(),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -315,6 +353,7 @@ fn sdk_update_route_to_domain(req: oagw_sdk::UpdateRouteRequest) -> model::Updat
         match_rules: req.match_rules().cloned().map(match_rules_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        cache: req.cache().cloned().map(cache_config_to_domain),
         tags: req.tags().map(|s| s.to_vec()),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -348,6 +387,7 @@ fn endpoint_to_domain(v: oagw_sdk::Endpoint) -> model::Endpoint {
         scheme: scheme_to_domain(v.scheme),
         host: v.host,
         port: v.port,
+        weight: v.weight,
     }
 }
 
@@ -357,6 +397,60 @@ fn server_to_domain(v: oagw_sdk::Server) -> model::Server {
     }
 }
 
+fn load_balancing_config_to_domain(v: oagw_sdk::LoadBalancingConfig) -> model::LoadBalancingConfig {
+    model::LoadBalancingConfig {
+        strategy: match v.strategy {
+            oagw_sdk::LoadBalancingStrategy::RoundRobin => model::LoadBalancingStrategy::RoundRobin,
+            oagw_sdk::LoadBalancingStrategy::LeastInFlight => {
+                model::LoadBalancingStrategy::LeastInFlight
+            }
+            oagw_sdk::LoadBalancingStrategy::Weighted => model::LoadBalancingStrategy::Weighted,
+        },
+        health_check: v.health_check.map(|h| model::HealthCheckConfig {
+            path: h.path,
+            interval_secs: h.interval_secs,
+            timeout_secs: h.timeout_secs,
+            healthy_threshold: h.healthy_threshold,
+            unhealthy_threshold: h.unhealthy_threshold,
+        }),
+        outlier_detection: v.outlier_detection.map(|o| model::OutlierDetectionConfig {
+            consecutive_failures: o.consecutive_failures,
+            base_ejection_secs: o.base_ejection_secs,
+            max_ejection_percent: o.max_ejection_percent,
+        }),
+    }
+}
+
+fn circuit_breaker_config_to_domain(
+    v: oagw_sdk::CircuitBreakerConfig,
+) -> model::CircuitBreakerConfig {
+    model::CircuitBreakerConfig {
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        success_threshold: v.success_threshold,
+        timeout_secs: v.timeout_secs,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: model::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            oagw_sdk::CircuitBreakerScope::Global => model::CircuitBreakerScope::Global,
+            oagw_sdk::CircuitBreakerScope::PerEndpoint => model::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn cache_config_to_domain(v: oagw_sdk::CacheConfig) -> model::CacheConfig {
+    model::CacheConfig {
+        enabled: v.enabled,
+        default_ttl_secs: v.default_ttl_secs,
+        max_ttl_secs: v.max_ttl_secs,
+        max_entry_bytes: v.max_entry_bytes,
+    }
+}
+
 fn auth_config_to_domain(v: oagw_sdk::AuthConfig) -> model::AuthConfig {
     model::AuthConfig {
         plugin_type: v.plugin_type,
@@ -434,6 +528,18 @@ fn rate_limit_config_to_domain(v: oagw_sdk::RateLimitConfig) -> model::RateLimit
             oagw_sdk::RateLimitStrategy::Degrade => model::RateLimitStrategy::Degrade,
         },
         cost: v.cost,
+        queue: v.queue.map(|q| model::QueueConfig {
+            max_depth: q.max_depth,
+            timeout: q.timeout,
+        }),
+        degrade: v.degrade.map(|d| model::DegradeConfig {
+            fallback_response: model::FallbackResponse {
+                status: d.fallback_response.status,
+                content_type: d.fallback_response.content_type,
+                body: d.fallback_response.body,
+            },
+        }),
+        response_headers: v.response_headers,
     }
 }
 
@@ -441,6 +547,7 @@ fn plugins_config_to_domain(v: oagw_sdk::PluginsConfig) -> model::PluginsConfig
     model::PluginsConfig {
         sharing: sharing_mode_to_domain(v.sharing),
         items: v.items,
+        config: v.config,
     }
 }
 
@@ -516,6 +623,7 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
                     scheme: scheme_to_sdk(e.scheme),
                     host: e.host,
                     port: e.port,
+                    weight: e.weight,
                 })
                 .collect(),
         },
@@ -547,8 +655,11 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
         plugins: u.plugins.map(|p| oagw_sdk::PluginsConfig {
             sharing: sharing_mode_to_sdk(p.sharing),
             items: p.items,
+            config: p.config,
         }),
         rate_limit: u.rate_limit.map(rate_limit_config_to_sdk),
+        load_balancing: u.load_balancing.map(load_balancing_config_to_sdk),
+        circuit_breaker: u.circuit_breaker.map(circuit_breaker_config_to_sdk),
         tags: u.tags,
     }
 }
@@ -586,8 +697,10 @@ fn route_to_sdk(r: model::Route) -> oagw_sdk::Route {
         plugins: r.plugins.map(|p| oagw_sdk::PluginsConfig {
             sharing: sharing_mode_to_sdk(p.sharing),
             items: p.items,
+            config: p.config,
         }),
         rate_limit: r.rate_limit.map(rate_limit_config_to_sdk),
+        cache: r.cache.map(cache_config_to_sdk),
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
@@ -626,6 +739,72 @@ fn rate_limit_config_to_sdk(v: model::RateLimitConfig) -> oagw_sdk::RateLimitCon
             model::RateLimitStrategy::Degrade => oagw_sdk::RateLimitStrategy::Degrade,
         },
         cost: v.cost,
+        queue: v.queue.map(|q| oagw_sdk::QueueConfig {
+            max_depth: q.max_depth,
+            timeout: q.timeout,
+        }),
+        degrade: v.degrade.map(|d| oagw_sdk::DegradeConfig {
+            fallback_response: oagw_sdk::FallbackResponse {
+                status: d.fallback_response.status,
+                content_type: d.fallback_response.content_type,
+                body: d.fallback_response.body,
+            },
+        }),
+        response_headers: v.response_headers,
+    }
+}
+
+fn load_balancing_config_to_sdk(v: model::LoadBalancingConfig) -> oagw_sdk::LoadBalancingConfig {
+    oagw_sdk::LoadBalancingConfig {
+        strategy: match v.strategy {
+            model::LoadBalancingStrategy::RoundRobin => oagw_sdk::LoadBalancingStrategy::RoundRobin,
+            model::LoadBalancingStrategy::LeastInFlight => {
+                oagw_sdk::LoadBalancingStrategy::LeastInFlight
+            }
+            model::LoadBalancingStrategy::Weighted => oagw_sdk::LoadBalancingStrategy::Weighted,
+        },
+        health_check: v.health_check.map(|h| oagw_sdk::HealthCheckConfig {
+            path: h.path,
+            interval_secs: h.interval_secs,
+            timeout_secs: h.timeout_secs,
+            healthy_threshold: h.healthy_threshold,
+            unhealthy_threshold: h.unhealthy_threshold,
+        }),
+        outlier_detection: v
+            .outlier_detection
+            .map(|o| oagw_sdk::OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                base_ejection_secs: o.base_ejection_secs,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
+    }
+}
+
+fn circuit_breaker_config_to_sdk(v: model::CircuitBreakerConfig) -> oagw_sdk::CircuitBreakerConfig {
+    oagw_sdk::CircuitBreakerConfig {
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        success_threshold: v.success_threshold,
+        timeout_secs: v.timeout_secs,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: oagw_sdk::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            model::CircuitBreakerScope::Global => oagw_sdk::CircuitBreakerScope::Global,
+            model::CircuitBreakerScope::PerEndpoint => oagw_sdk::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn cache_config_to_sdk(v: model::CacheConfig) -> oagw_sdk::CacheConfig {
+    oagw_sdk::CacheConfig {
+        enabled: v.enabled,
+        default_ttl_secs: v.default_ttl_secs,
+        max_ttl_secs: v.max_ttl_secs,
+        max_entry_bytes: v.max_entry_bytes,
     }
 }
 
@@ -677,6 +856,7 @@ mod tests {
                     scheme: model::Scheme::Https,
                     host: "example.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
             },
             protocol: "http".into(),
@@ -689,6 +869,8 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            load_balancing: None,
+            circuit_breaker: None,
             tags: vec![],
         };
 
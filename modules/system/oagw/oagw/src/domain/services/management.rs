@@ -1,21 +1,42 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use super::ControlPlaneService;
 use crate::domain::error::DomainError;
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    CacheConfig, CircuitBreakerConfig, CreateRouteRequest, CreateUpstreamRequest, ListQuery,
+    LoadBalancingConfig, MatchRules, PluginsConfig, RateLimitConfig, RateLimitStrategy, Route,
+    Server, SharingMode, UpdateRouteRequest, UpdateUpstreamRequest, Upstream,
 };
+use crate::domain::plugin::{PluginBinding, merge_plugins};
+use crate::domain::rate_limit::{EffectiveRateLimits, min_merge};
 use crate::domain::repo::{RouteRepository, UpstreamRepository};
+use crate::domain::response_cache::ResponseCacheInvalidator;
+use crate::domain::tenant_hierarchy::TenantHierarchy;
+use crate::domain::upstream_observer::UpstreamObserver;
+use dashmap::DashMap;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
 use uuid::Uuid;
 
-/// Control Plane service implementation backed by in-memory repositories.
+/// How long a resolved rate limit is reused. Bounds staleness for changes
+/// this node is not told about: tenant re-parenting and writes made through
+/// other nodes.
+const RATE_LIMIT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Upstream tenant, upstream and route a resolved rate limit belongs to.
+type RateLimitCacheKey = (Uuid, Uuid, Uuid);
+
+/// Control Plane service implementation backed by the configured repositories.
 #[domain_model]
 pub(crate) struct ControlPlaneServiceImpl {
     upstreams: Arc<dyn UpstreamRepository>,
     routes: Arc<dyn RouteRepository>,
+    tenant_hierarchy: Option<Arc<dyn TenantHierarchy>>,
+    cache_invalidator: Option<Arc<dyn ResponseCacheInvalidator>>,
+    upstream_observer: Option<Arc<dyn UpstreamObserver>>,
+    /// Resolved limits and when they were resolved.
+    rate_limits: DashMap<RateLimitCacheKey, (EffectiveRateLimits, Instant)>,
 }
 
 impl ControlPlaneServiceImpl {
@@ -24,7 +45,61 @@ impl ControlPlaneServiceImpl {
         upstreams: Arc<dyn UpstreamRepository>,
         routes: Arc<dyn RouteRepository>,
     ) -> Self {
-        Self { upstreams, routes }
+        Self {
+            upstreams,
+            routes,
+            tenant_hierarchy: None,
+            cache_invalidator: None,
+            upstream_observer: None,
+            rate_limits: DashMap::new(),
+        }
+    }
+
+    /// Apply limits shared by ancestor tenants. Without a hierarchy every
+    /// tenant is treated as a root.
+    #[must_use]
+    pub(crate) fn with_tenant_hierarchy(mut self, hierarchy: Arc<dyn TenantHierarchy>) -> Self {
+        self.tenant_hierarchy = Some(hierarchy);
+        self
+    }
+
+    /// Flush cached proxy responses when upstreams or routes change.
+    #[must_use]
+    pub(crate) fn with_cache_invalidator(
+        mut self,
+        invalidator: Arc<dyn ResponseCacheInvalidator>,
+    ) -> Self {
+        self.cache_invalidator = Some(invalidator);
+        self
+    }
+
+    /// Keep Data Plane endpoint state in step with upstream writes.
+    #[must_use]
+    pub(crate) fn with_upstream_observer(mut self, observer: Arc<dyn UpstreamObserver>) -> Self {
+        self.upstream_observer = Some(observer);
+        self
+    }
+
+    fn upstream_saved(&self, upstream: &Upstream) {
+        if let Some(ref observer) = self.upstream_observer {
+            observer.upstream_saved(upstream);
+        }
+    }
+
+    fn invalidate_upstream(&self, tenant_id: Uuid, upstream_id: Uuid) {
+        // Upstreams share limits with descendants' upstreams of the same
+        // alias, so any upstream write can change other tenants' limits.
+        self.rate_limits.clear();
+        if let Some(ref invalidator) = self.cache_invalidator {
+            invalidator.invalidate_upstream(tenant_id, upstream_id);
+        }
+    }
+
+    fn invalidate_route(&self, tenant_id: Uuid, route_id: Uuid) {
+        self.rate_limits.retain(|key, _| key.2 != route_id);
+        if let Some(ref invalidator) = self.cache_invalidator {
+            invalidator.invalidate_route(tenant_id, route_id);
+        }
     }
 }
 
@@ -52,6 +127,165 @@ fn validate_alias(alias: &str) -> Result<(), DomainError> {
     Ok(())
 }
 
+/// Validate that all endpoints can form one load-balancing pool: same scheme
+/// and port, and a non-zero weight.
+fn validate_server(server: &Server) -> Result<(), DomainError> {
+    if let Some(first) = server.endpoints.first() {
+        for ep in &server.endpoints[1..] {
+            if ep.scheme != first.scheme || ep.port != first.port {
+                return Err(DomainError::validation(
+                    "all endpoints of an upstream must share the same scheme and port",
+                ));
+            }
+        }
+    }
+    if server.endpoints.iter().any(|ep| ep.weight == 0) {
+        return Err(DomainError::validation(
+            "endpoint weight must be at least 1",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_load_balancing(lb: &LoadBalancingConfig) -> Result<(), DomainError> {
+    if let Some(ref hc) = lb.health_check {
+        if !hc.path.starts_with('/') {
+            return Err(DomainError::validation(
+                "health_check.path must start with '/'",
+            ));
+        }
+        if hc.interval_secs == 0 || hc.timeout_secs == 0 {
+            return Err(DomainError::validation(
+                "health_check interval_secs and timeout_secs must be at least 1",
+            ));
+        }
+        if hc.healthy_threshold == 0 || hc.unhealthy_threshold == 0 {
+            return Err(DomainError::validation(
+                "health_check thresholds must be at least 1",
+            ));
+        }
+    }
+    if let Some(ref od) = lb.outlier_detection {
+        if od.consecutive_failures == 0 {
+            return Err(DomainError::validation(
+                "outlier_detection.consecutive_failures must be at least 1",
+            ));
+        }
+        if od.max_ejection_percent > 100 {
+            return Err(DomainError::validation(
+                "outlier_detection.max_ejection_percent must not exceed 100",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_circuit_breaker(cb: &CircuitBreakerConfig) -> Result<(), DomainError> {
+    if cb.failure_threshold == 0 || cb.success_threshold == 0 {
+        return Err(DomainError::validation(
+            "circuit_breaker thresholds must be at least 1",
+        ));
+    }
+    if cb.timeout_secs == 0 {
+        return Err(DomainError::validation(
+            "circuit_breaker.timeout_secs must be at least 1",
+        ));
+    }
+    if cb.half_open_max_requests == 0 {
+        return Err(DomainError::validation(
+            "circuit_breaker.half_open_max_requests must be at least 1",
+        ));
+    }
+    if let Some(code) = cb
+        .failure_conditions
+        .status_codes
+        .iter()
+        .find(|c| !(100..=599).contains(*c))
+    {
+        return Err(DomainError::validation(format!(
+            "circuit_breaker.failure_conditions.status_codes contains invalid status code {code}"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_rate_limit(rl: &RateLimitConfig) -> Result<(), DomainError> {
+    if rl.sustained.rate == 0 {
+        return Err(DomainError::validation(
+            "rate_limit.sustained.rate must be at least 1",
+        ));
+    }
+    match (&rl.strategy, &rl.queue) {
+        (RateLimitStrategy::Queue, None) => {
+            return Err(DomainError::validation(
+                "rate_limit.queue is required when strategy is 'queue'",
+            ));
+        }
+        (_, Some(q)) if q.max_depth == 0 || q.timeout.is_zero() => {
+            return Err(DomainError::validation(
+                "rate_limit.queue.max_depth and rate_limit.queue.timeout must be positive",
+            ));
+        }
+        _ => {}
+    }
+    if let Some(ref d) = rl.degrade
+        && !(100..=599).contains(&d.fallback_response.status)
+    {
+        return Err(DomainError::validation(format!(
+            "rate_limit.degrade.fallback_response.status {} is not a valid status code",
+            d.fallback_response.status
+        )));
+    }
+    Ok(())
+}
+
+fn validate_cache(cache: &CacheConfig) -> Result<(), DomainError> {
+    if cache.max_ttl_secs == 0 {
+        return Err(DomainError::validation(
+            "cache.max_ttl_secs must be at least 1",
+        ));
+    }
+    if cache.default_ttl_secs > cache.max_ttl_secs {
+        return Err(DomainError::validation(
+            "cache.default_ttl_secs must not exceed cache.max_ttl_secs",
+        ));
+    }
+    if cache.max_entry_bytes == 0 {
+        return Err(DomainError::validation(
+            "cache.max_entry_bytes must be at least 1",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_match_rules(rules: &MatchRules) -> Result<(), DomainError> {
+    match (&rules.http, &rules.grpc) {
+        (Some(_), None) => Ok(()),
+        (None, Some(grpc)) => {
+            let valid = |s: &str| !s.is_empty() && !s.contains('/');
+            if valid(&grpc.service) && valid(&grpc.method) {
+                Ok(())
+            } else {
+                Err(DomainError::validation(
+                    "match.grpc.service and match.grpc.method must be non-empty and contain no '/'",
+                ))
+            }
+        }
+        _ => Err(DomainError::validation(
+            "exactly one of match.http or match.grpc must be present",
+        )),
+    }
+}
+
+fn validate_plugins(plugins: &PluginsConfig) -> Result<(), DomainError> {
+    if let Some(plugin_ref) = plugins.config.keys().find(|k| !plugins.items.contains(k)) {
+        return Err(DomainError::validation(format!(
+            "plugins.config has an entry for '{plugin_ref}' which is not in plugins.items"
+        )));
+    }
+    Ok(())
+}
+
 /// Generate an alias from the upstream's server endpoints.
 /// Single endpoint: host (standard port omitted) or host:port.
 fn generate_alias(upstream: &Upstream) -> String {
@@ -75,6 +309,20 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         let tenant_id = ctx.subject_tenant_id();
         let id = Uuid::new_v4();
 
+        validate_server(&req.server)?;
+        if let Some(ref lb) = req.load_balancing {
+            validate_load_balancing(lb)?;
+        }
+        if let Some(ref cb) = req.circuit_breaker {
+            validate_circuit_breaker(cb)?;
+        }
+        if let Some(ref rl) = req.rate_limit {
+            validate_rate_limit(rl)?;
+        }
+        if let Some(ref plugins) = req.plugins {
+            validate_plugins(plugins)?;
+        }
+
         let upstream = Upstream {
             id,
             tenant_id,
@@ -86,6 +334,8 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             headers: req.headers.clone(),
             plugins: req.plugins.clone(),
             rate_limit: req.rate_limit.clone(),
+            load_balancing: req.load_balancing.clone(),
+            circuit_breaker: req.circuit_breaker.clone(),
             tags: req.tags.clone(),
         };
 
@@ -98,10 +348,14 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
 
         let upstream = Upstream { alias, ..upstream };
 
-        self.upstreams
+        let created = self
+            .upstreams
             .create(upstream)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.rate_limits.clear();
+        self.upstream_saved(&created);
+        Ok(created)
     }
 
     async fn get_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<Upstream, DomainError> {
@@ -139,6 +393,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
 
         // Apply partial update.
         if let Some(server) = req.server {
+            validate_server(&server)?;
             existing.server = server;
         }
         if let Some(protocol) = req.protocol {
@@ -155,11 +410,21 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.headers = Some(headers);
         }
         if let Some(plugins) = req.plugins {
+            validate_plugins(&plugins)?;
             existing.plugins = Some(plugins);
         }
         if let Some(rate_limit) = req.rate_limit {
+            validate_rate_limit(&rate_limit)?;
             existing.rate_limit = Some(rate_limit);
         }
+        if let Some(load_balancing) = req.load_balancing {
+            validate_load_balancing(&load_balancing)?;
+            existing.load_balancing = Some(load_balancing);
+        }
+        if let Some(circuit_breaker) = req.circuit_breaker {
+            validate_circuit_breaker(&circuit_breaker)?;
+            existing.circuit_breaker = Some(circuit_breaker);
+        }
         if let Some(tags) = req.tags {
             existing.tags = tags;
         }
@@ -167,10 +432,14 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.enabled = enabled;
         }
 
-        self.upstreams
+        let updated = self
+            .upstreams
             .update(existing)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.invalidate_upstream(tenant_id, id);
+        self.upstream_saved(&updated);
+        Ok(updated)
     }
 
     async fn delete_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
@@ -180,7 +449,12 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         self.upstreams
             .delete(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("upstream", id))
+            .map_err(|_| DomainError::not_found("upstream", id))?;
+        self.invalidate_upstream(tenant_id, id);
+        if let Some(ref observer) = self.upstream_observer {
+            observer.upstream_deleted(tenant_id, id);
+        }
+        Ok(())
     }
 
     // -- Route CRUD --
@@ -191,6 +465,16 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         req: CreateRouteRequest,
     ) -> Result<Route, DomainError> {
         let tenant_id = ctx.subject_tenant_id();
+        if let Some(ref rl) = req.rate_limit {
+            validate_rate_limit(rl)?;
+        }
+        if let Some(ref plugins) = req.plugins {
+            validate_plugins(plugins)?;
+        }
+        if let Some(ref cache) = req.cache {
+            validate_cache(cache)?;
+        }
+        validate_match_rules(&req.match_rules)?;
         // Validate that the upstream exists and belongs to this tenant.
         self.upstreams
             .get_by_id(tenant_id, req.upstream_id)
@@ -209,6 +493,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             match_rules: req.match_rules,
             plugins: req.plugins,
             rate_limit: req.rate_limit,
+            cache: req.cache,
             tags: req.tags,
             priority: req.priority,
             enabled: req.enabled,
@@ -252,14 +537,21 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             .map_err(|_| DomainError::not_found("route", id))?;
 
         if let Some(match_rules) = req.match_rules {
+            validate_match_rules(&match_rules)?;
             existing.match_rules = match_rules;
         }
         if let Some(plugins) = req.plugins {
+            validate_plugins(&plugins)?;
             existing.plugins = Some(plugins);
         }
         if let Some(rate_limit) = req.rate_limit {
+            validate_rate_limit(&rate_limit)?;
             existing.rate_limit = Some(rate_limit);
         }
+        if let Some(cache) = req.cache {
+            validate_cache(&cache)?;
+            existing.cache = Some(cache);
+        }
         if let Some(tags) = req.tags {
             existing.tags = tags;
         }
@@ -270,10 +562,13 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.enabled = enabled;
         }
 
-        self.routes
+        let updated = self
+            .routes
             .update(existing)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.invalidate_route(tenant_id, id);
+        Ok(updated)
     }
 
     async fn delete_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
@@ -281,7 +576,9 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         self.routes
             .delete(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("route", id))
+            .map_err(|_| DomainError::not_found("route", id))?;
+        self.invalidate_route(tenant_id, id);
+        Ok(())
     }
 
     // -- Resolution --
@@ -318,6 +615,80 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             .await
             .map_err(|_| DomainError::not_found("route", Uuid::nil()))
     }
+
+    async fn resolve_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream: &Upstream,
+        route: &Route,
+    ) -> Result<EffectiveRateLimits, DomainError> {
+        let key = (upstream.tenant_id, upstream.id, route.id);
+        if let Some(entry) = self.rate_limits.get(&key)
+            && entry.1.elapsed() < RATE_LIMIT_CACHE_TTL
+        {
+            return Ok(entry.0.clone());
+        }
+
+        let mut inherited: Option<RateLimitConfig> = None;
+        if let Some(ref hierarchy) = self.tenant_hierarchy {
+            let ancestors = hierarchy.ancestors(ctx, upstream.tenant_id).await?;
+            // Root first, so each level narrows what its ancestors shared.
+            for tenant_id in ancestors.into_iter().rev() {
+                let Ok(ancestor) = self
+                    .upstreams
+                    .get_by_alias(tenant_id, &upstream.alias)
+                    .await
+                else {
+                    continue;
+                };
+                let shared = ancestor
+                    .rate_limit
+                    .as_ref()
+                    .filter(|rl| rl.sharing != SharingMode::Private);
+                inherited = min_merge(inherited.as_ref(), shared);
+            }
+        }
+        let upstream_limit = min_merge(inherited.as_ref(), upstream.rate_limit.as_ref());
+        let route_limit = route
+            .rate_limit
+            .as_ref()
+            .and_then(|rl| min_merge(upstream_limit.as_ref(), Some(rl)));
+        let limits = EffectiveRateLimits {
+            upstream: upstream_limit,
+            route: route_limit,
+        };
+        self.rate_limits
+            .insert(key, (limits.clone(), Instant::now()));
+        Ok(limits)
+    }
+
+    async fn resolve_plugins(
+        &self,
+        ctx: &SecurityContext,
+        upstream: &Upstream,
+        route: &Route,
+    ) -> Result<Vec<PluginBinding>, DomainError> {
+        let mut chain = Vec::new();
+        if let Some(ref hierarchy) = self.tenant_hierarchy {
+            let ancestors = hierarchy.ancestors(ctx, upstream.tenant_id).await?;
+            for tenant_id in ancestors.into_iter().rev() {
+                let Ok(ancestor) = self
+                    .upstreams
+                    .get_by_alias(tenant_id, &upstream.alias)
+                    .await
+                else {
+                    continue;
+                };
+                let shared = ancestor
+                    .plugins
+                    .as_ref()
+                    .filter(|p| p.sharing != SharingMode::Private);
+                chain = merge_plugins(chain, shared);
+            }
+        }
+        let chain = merge_plugins(chain, upstream.plugins.as_ref());
+        Ok(merge_plugins(chain, route.plugins.as_ref()))
+    }
 }
 
 #[cfg(test)]
@@ -325,11 +696,13 @@ mod tests {
     use std::sync::Arc;
 
     use crate::domain::model::{
-        Endpoint, HttpMatch, HttpMethod, MatchRules, PathSuffixMode, Scheme, Server,
+        Endpoint, GrpcMatch, HttpMatch, HttpMethod, MatchRules, PathSuffixMode, Scheme, Server,
+        SustainedRate, Window,
     };
 
     use super::*;
     use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+    use crate::infra::tenant_hierarchy::InMemoryTenantHierarchy;
 
     fn make_service() -> ControlPlaneServiceImpl {
         ControlPlaneServiceImpl::new(
@@ -353,6 +726,7 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
             },
             protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
@@ -361,6 +735,8 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            load_balancing: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -380,6 +756,7 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -450,6 +827,7 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 8443,
+                    weight: 1,
                 }],
             },
             protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
@@ -458,6 +836,8 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            load_balancing: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -654,4 +1034,462 @@ mod tests {
         // Route should be gone.
         assert!(svc.get_route(&ctx, r.id).await.is_err());
     }
+
+    fn limit(rate: u32, window: Window, sharing: SharingMode) -> RateLimitConfig {
+        RateLimitConfig {
+            sharing,
+            algorithm: Default::default(),
+            sustained: SustainedRate { rate, window },
+            burst: None,
+            scope: Default::default(),
+            strategy: Default::default(),
+            cost: 1,
+            queue: None,
+            degrade: None,
+            response_headers: true,
+        }
+    }
+
+    async fn create_limited(
+        svc: &ControlPlaneServiceImpl,
+        tenant: Uuid,
+        rate_limit: Option<RateLimitConfig>,
+    ) -> Upstream {
+        svc.create_upstream(
+            &test_ctx(tenant),
+            CreateUpstreamRequest {
+                rate_limit,
+                ..make_create_upstream(Some("openai"))
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn resolve_rate_limit_min_merges_ancestors_upstream_and_route() {
+        let (root, mid, leaf) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let hierarchy = InMemoryTenantHierarchy::default();
+        hierarchy.set_parent(leaf, mid);
+        hierarchy.set_parent(mid, root);
+        let svc = make_service().with_tenant_hierarchy(Arc::new(hierarchy));
+
+        let enforced = limit(60, Window::Minute, SharingMode::Enforce);
+        create_limited(&svc, root, Some(enforced.clone())).await;
+        // Private limits apply only to the owning tenant.
+        create_limited(
+            &svc,
+            mid,
+            Some(limit(1, Window::Minute, SharingMode::Private)),
+        )
+        .await;
+        let own = create_limited(
+            &svc,
+            leaf,
+            Some(limit(100, Window::Second, SharingMode::Private)),
+        )
+        .await;
+
+        let ctx = test_ctx(leaf);
+        let route = svc
+            .create_route(&ctx, make_create_route(own.id))
+            .await
+            .unwrap();
+        let effective = svc.resolve_rate_limit(&ctx, &own, &route).await.unwrap();
+        assert_eq!(effective.upstream.unwrap().sustained, enforced.sustained);
+        assert_eq!(effective.route, None);
+
+        // A stricter route limit still wins.
+        let route = svc
+            .create_route(
+                &ctx,
+                CreateRouteRequest {
+                    rate_limit: Some(limit(2, Window::Minute, SharingMode::Private)),
+                    ..make_create_route(own.id)
+                },
+            )
+            .await
+            .unwrap();
+        let effective = svc.resolve_rate_limit(&ctx, &own, &route).await.unwrap();
+        let route_limit = effective.route.unwrap();
+        assert_eq!(route_limit.sustained.rate, 2);
+        assert_eq!(route_limit.sustained.window, Window::Minute);
+        // The upstream's aggregate limit is still enforced across routes.
+        assert_eq!(effective.upstream.unwrap().sustained, enforced.sustained);
+    }
+
+    #[tokio::test]
+    async fn resolve_rate_limit_inherits_when_descendant_has_none() {
+        let (parent, child) = (Uuid::new_v4(), Uuid::new_v4());
+        let hierarchy = InMemoryTenantHierarchy::default();
+        hierarchy.set_parent(child, parent);
+        let svc = make_service().with_tenant_hierarchy(Arc::new(hierarchy));
+
+        let inherited = limit(10, Window::Second, SharingMode::Inherit);
+        create_limited(&svc, parent, Some(inherited.clone())).await;
+        let own = create_limited(&svc, child, None).await;
+
+        let ctx = test_ctx(child);
+        let route = svc
+            .create_route(&ctx, make_create_route(own.id))
+            .await
+            .unwrap();
+        let effective = svc.resolve_rate_limit(&ctx, &own, &route).await.unwrap();
+        assert_eq!(effective.upstream, Some(inherited));
+    }
+
+    /// Hierarchy that counts lookups.
+    #[derive(Default)]
+    struct CountingHierarchy {
+        inner: InMemoryTenantHierarchy,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TenantHierarchy for CountingHierarchy {
+        async fn ancestors(
+            &self,
+            ctx: &SecurityContext,
+            tenant_id: Uuid,
+        ) -> Result<Vec<Uuid>, DomainError> {
+            self.calls
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            self.inner.ancestors(ctx, tenant_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_rate_limit_is_cached_until_upstream_or_route_changes() {
+        let (parent, child) = (Uuid::new_v4(), Uuid::new_v4());
+        let hierarchy = Arc::new(CountingHierarchy::default());
+        hierarchy.inner.set_parent(child, parent);
+        let svc = make_service().with_tenant_hierarchy(hierarchy.clone());
+        let calls = || hierarchy.calls.load(std::sync::atomic::Ordering::Relaxed);
+
+        let shared = create_limited(
+            &svc,
+            parent,
+            Some(limit(10, Window::Second, SharingMode::Inherit)),
+        )
+        .await;
+        let own = create_limited(&svc, child, None).await;
+        let ctx = test_ctx(child);
+        let route = svc
+            .create_route(&ctx, make_create_route(own.id))
+            .await
+            .unwrap();
+
+        svc.resolve_rate_limit(&ctx, &own, &route).await.unwrap();
+        svc.resolve_rate_limit(&ctx, &own, &route).await.unwrap();
+        assert_eq!(calls(), 1);
+
+        // An ancestor's change reaches descendants at once.
+        svc.update_upstream(
+            &test_ctx(parent),
+            shared.id,
+            UpdateUpstreamRequest {
+                rate_limit: Some(limit(5, Window::Second, SharingMode::Inherit)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let effective = svc.resolve_rate_limit(&ctx, &own, &route).await.unwrap();
+        assert_eq!(effective.upstream.unwrap().sustained.rate, 5);
+        assert_eq!(calls(), 2);
+
+        let route = svc
+            .update_route(
+                &ctx,
+                route.id,
+                UpdateRouteRequest {
+                    rate_limit: Some(limit(1, Window::Second, SharingMode::Private)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let effective = svc.resolve_rate_limit(&ctx, &own, &route).await.unwrap();
+        assert_eq!(effective.route.unwrap().sustained.rate, 1);
+        assert_eq!(calls(), 3);
+    }
+
+    fn plugins(sharing: SharingMode, items: &[&str]) -> Option<PluginsConfig> {
+        Some(PluginsConfig {
+            sharing,
+            items: items.iter().map(|i| (*i).to_string()).collect(),
+            config: Default::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn resolve_plugins_layers_shared_ancestors_upstream_and_route() {
+        let (root, mid, leaf) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let hierarchy = InMemoryTenantHierarchy::default();
+        hierarchy.set_parent(leaf, mid);
+        hierarchy.set_parent(mid, root);
+        let svc = make_service().with_tenant_hierarchy(Arc::new(hierarchy));
+
+        for (tenant, p) in [
+            (root, plugins(SharingMode::Inherit, &["root-shared"])),
+            (mid, plugins(SharingMode::Private, &["mid-private"])),
+        ] {
+            svc.create_upstream(
+                &test_ctx(tenant),
+                CreateUpstreamRequest {
+                    plugins: p,
+                    ..make_create_upstream(Some("openai"))
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let ctx = test_ctx(leaf);
+        let own = svc
+            .create_upstream(
+                &ctx,
+                CreateUpstreamRequest {
+                    plugins: plugins(SharingMode::Private, &["own"]),
+                    ..make_create_upstream(Some("openai"))
+                },
+            )
+            .await
+            .unwrap();
+        let route = svc
+            .create_route(
+                &ctx,
+                CreateRouteRequest {
+                    plugins: plugins(SharingMode::Private, &["route"]),
+                    ..make_create_route(own.id)
+                },
+            )
+            .await
+            .unwrap();
+
+        let chain = svc.resolve_plugins(&ctx, &own, &route).await.unwrap();
+        let refs: Vec<&str> = chain.iter().map(|b| b.plugin_ref.as_str()).collect();
+        assert_eq!(refs, vec!["root-shared", "own", "route"]);
+    }
+
+    #[tokio::test]
+    async fn plugin_config_must_reference_listed_plugin() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let mut p = plugins(SharingMode::Private, &["cors"]).unwrap();
+        p.config.insert("other".into(), Default::default());
+
+        let err = svc
+            .create_upstream(
+                &ctx,
+                CreateUpstreamRequest {
+                    plugins: Some(p),
+                    ..make_create_upstream(None)
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn route_cache_config_is_validated() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(None))
+            .await
+            .unwrap();
+
+        let err = svc
+            .create_route(
+                &ctx,
+                CreateRouteRequest {
+                    cache: Some(CacheConfig {
+                        enabled: true,
+                        default_ttl_secs: 600,
+                        max_ttl_secs: 60,
+                        max_entry_bytes: 1024,
+                    }),
+                    ..make_create_route(u.id)
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn route_match_rules_are_validated() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(None))
+            .await
+            .unwrap();
+        let grpc = GrpcMatch {
+            service: "example.v1.UserService".into(),
+            method: "GetUser".into(),
+        };
+
+        let route = svc
+            .create_route(
+                &ctx,
+                CreateRouteRequest {
+                    match_rules: MatchRules {
+                        http: None,
+                        grpc: Some(grpc.clone()),
+                    },
+                    ..make_create_route(u.id)
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(route.match_rules.grpc, Some(grpc.clone()));
+
+        for match_rules in [
+            MatchRules {
+                http: None,
+                grpc: None,
+            },
+            MatchRules {
+                grpc: Some(grpc),
+                ..make_create_route(u.id).match_rules
+            },
+            MatchRules {
+                http: None,
+                grpc: Some(GrpcMatch {
+                    service: "example.v1.UserService/GetUser".into(),
+                    method: String::new(),
+                }),
+            },
+        ] {
+            let err = svc
+                .create_route(
+                    &ctx,
+                    CreateRouteRequest {
+                        match_rules,
+                        ..make_create_route(u.id)
+                    },
+                )
+                .await
+                .unwrap_err();
+            assert!(matches!(err, DomainError::Validation { .. }));
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingInvalidator {
+        calls: std::sync::Mutex<Vec<(&'static str, Uuid)>>,
+    }
+
+    impl ResponseCacheInvalidator for RecordingInvalidator {
+        fn invalidate_upstream(&self, _tenant_id: Uuid, upstream_id: Uuid) {
+            self.calls.lock().unwrap().push(("upstream", upstream_id));
+        }
+
+        fn invalidate_route(&self, _tenant_id: Uuid, route_id: Uuid) {
+            self.calls.lock().unwrap().push(("route", route_id));
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_invalidate_response_cache() {
+        let invalidator = Arc::new(RecordingInvalidator::default());
+        let svc = make_service().with_cache_invalidator(invalidator.clone());
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(None))
+            .await
+            .unwrap();
+        let r = svc
+            .create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap();
+        assert!(invalidator.calls.lock().unwrap().is_empty());
+
+        svc.update_route(
+            &ctx,
+            r.id,
+            UpdateRouteRequest {
+                priority: Some(5),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        svc.delete_route(&ctx, r.id).await.unwrap();
+        svc.update_upstream(
+            &ctx,
+            u.id,
+            UpdateUpstreamRequest {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        svc.delete_upstream(&ctx, u.id).await.unwrap();
+
+        assert_eq!(
+            *invalidator.calls.lock().unwrap(),
+            vec![
+                ("route", r.id),
+                ("route", r.id),
+                ("upstream", u.id),
+                ("upstream", u.id)
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        calls: std::sync::Mutex<Vec<(&'static str, Uuid, bool)>>,
+    }
+
+    impl UpstreamObserver for RecordingObserver {
+        fn upstream_saved(&self, upstream: &Upstream) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(("saved", upstream.id, upstream.enabled));
+        }
+
+        fn upstream_deleted(&self, _tenant_id: Uuid, upstream_id: Uuid) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(("deleted", upstream_id, false));
+        }
+    }
+
+    #[tokio::test]
+    async fn upstream_writes_notify_observer() {
+        let observer = Arc::new(RecordingObserver::default());
+        let svc = make_service().with_upstream_observer(observer.clone());
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(None))
+            .await
+            .unwrap();
+        svc.update_upstream(
+            &ctx,
+            u.id,
+            UpdateUpstreamRequest {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        svc.delete_upstream(&ctx, u.id).await.unwrap();
+
+        assert_eq!(
+            *observer.calls.lock().unwrap(),
+            vec![
+                ("saved", u.id, true),
+                ("saved", u.id, false),
+                ("deleted", u.id, false)
+            ]
+        );
+    }
 }
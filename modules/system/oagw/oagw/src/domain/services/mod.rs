@@ -11,8 +11,10 @@ use uuid::Uuid;
 use crate::domain::error::DomainError;
 use crate::domain::model::{
     CreateRouteRequest, CreateUpstreamRequest, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    UpdateUpstreamRequest, Upstream, UpstreamStatus,
 };
+use crate::domain::plugin::PluginBinding;
+use crate::domain::rate_limit::EffectiveRateLimits;
 
 /// Internal Control Plane service trait — configuration management and resolution.
 #[async_trait::async_trait]
@@ -83,6 +85,32 @@ pub(crate) trait ControlPlaneService: Send + Sync {
         method: &str,
         path: &str,
     ) -> Result<Route, DomainError>;
+
+    /// Effective rate limits for a request through `route` on `upstream`.
+    ///
+    /// Limits shared by ancestor tenants' upstreams with the same alias and
+    /// the upstream's own limit form the upstream's aggregate limit; the
+    /// route's limit is narrowed by it. Both are combined with
+    /// [`min_merge`](crate::domain::rate_limit::min_merge). Results are
+    /// cached per (tenant, upstream, route) until one of them changes.
+    async fn resolve_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream: &Upstream,
+        route: &Route,
+    ) -> Result<EffectiveRateLimits, DomainError>;
+
+    /// Effective plugin chain for a request through `route` on `upstream`.
+    ///
+    /// Plugins shared by ancestor tenants' upstreams with the same alias come
+    /// first, then the upstream's own plugins, then the route's, combined with
+    /// [`merge_plugins`](crate::domain::plugin::merge_plugins).
+    async fn resolve_plugins(
+        &self,
+        ctx: &SecurityContext,
+        upstream: &Upstream,
+        route: &Route,
+    ) -> Result<Vec<PluginBinding>, DomainError>;
 }
 
 /// Internal Data Plane service trait — proxy orchestration and plugin execution.
@@ -93,4 +121,7 @@ pub(crate) trait DataPlaneService: Send + Sync {
         ctx: SecurityContext,
         req: http::Request<Body>,
     ) -> Result<http::Response<Body>, DomainError>;
+
+    /// Runtime health of the upstream's endpoints (probe state, ejections, load).
+    fn upstream_status(&self, upstream: &Upstream) -> UpstreamStatus;
 }
@@ -0,0 +1,22 @@
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+
+/// Source of tenant ancestry, used to apply configuration that ancestors
+/// share with (or enforce on) their descendants.
+#[async_trait::async_trait]
+pub(crate) trait TenantHierarchy: Send + Sync {
+    /// Ancestors of `tenant_id`, ordered from direct parent to root.
+    ///
+    /// Returns an empty list for root tenants and for tenants the hierarchy
+    /// does not know about.
+    ///
+    /// # Errors
+    /// Returns `DomainError::Internal` if the hierarchy source is unavailable.
+    async fn ancestors(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError>;
+}
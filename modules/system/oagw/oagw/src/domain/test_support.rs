@@ -6,22 +6,25 @@ use std::time::Duration;
 use crate::domain::credential::CredentialResolver;
 use modkit::client_hub::ClientHub;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use uuid::Uuid;
 
 use crate::domain::services::{
     ControlPlaneService, ControlPlaneServiceImpl, DataPlaneService, ServiceGatewayClientV1Facade,
 };
-use crate::infra::proxy::DataPlaneServiceImpl;
+use crate::infra::proxy::{DataPlaneServiceImpl, ResponseCache};
 use crate::infra::storage::{InMemoryCredentialResolver, InMemoryRouteRepo, InMemoryUpstreamRepo};
+use crate::infra::tenant_hierarchy::InMemoryTenantHierarchy;
 
 /// Re-export for tests that need to set credentials after creation.
 pub use crate::infra::storage::credential_repo::InMemoryCredentialResolver as TestCredentialResolver;
 
 /// Re-export plugin ID constants for test configurations.
-pub use crate::domain::gts_helpers::APIKEY_AUTH_PLUGIN_ID;
+pub use crate::domain::gts_helpers::{APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID};
 
 /// Builder for a fully-wired Control Plane test environment.
 pub struct TestCpBuilder {
     credentials: Vec<(String, String)>,
+    tenant_parents: Vec<(Uuid, Uuid)>,
 }
 
 impl TestCpBuilder {
@@ -29,9 +32,17 @@ impl TestCpBuilder {
     pub fn new() -> Self {
         Self {
             credentials: Vec::new(),
+            tenant_parents: Vec::new(),
         }
     }
 
+    /// Declare `(child, parent)` tenant relationships for hierarchical limits.
+    #[must_use]
+    pub fn with_tenant_parents(mut self, parents: Vec<(Uuid, Uuid)>) -> Self {
+        self.tenant_parents = parents;
+        self
+    }
+
     /// Pre-load credentials into the credential resolver.
     #[must_use]
     pub fn with_credentials(mut self, creds: Vec<(String, String)>) -> Self {
@@ -44,14 +55,23 @@ impl TestCpBuilder {
     pub(crate) fn build_and_register(self, hub: &ClientHub) -> Arc<dyn ControlPlaneService> {
         let upstream_repo = Arc::new(InMemoryUpstreamRepo::new());
         let route_repo = Arc::new(InMemoryRouteRepo::new());
-        let cp: Arc<dyn ControlPlaneService> =
-            Arc::new(ControlPlaneServiceImpl::new(upstream_repo, route_repo));
+        let hierarchy = InMemoryTenantHierarchy::default();
+        for (child, parent) in self.tenant_parents {
+            hierarchy.set_parent(child, parent);
+        }
+        let response_cache = Arc::new(ResponseCache::new(1000, 64 * 1024 * 1024));
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(
+            ControlPlaneServiceImpl::new(upstream_repo, route_repo)
+                .with_tenant_hierarchy(Arc::new(hierarchy))
+                .with_cache_invalidator(response_cache.clone()),
+        );
 
         let cred_resolver: Arc<dyn CredentialResolver> = Arc::new(
             InMemoryCredentialResolver::with_credentials(self.credentials),
         );
 
         hub.register::<dyn CredentialResolver>(cred_resolver);
+        hub.register::<ResponseCache>(response_cache);
 
         cp
     }
@@ -65,8 +85,8 @@ impl Default for TestCpBuilder {
 
 /// Builder for a fully-wired Data Plane test environment.
 ///
-/// Requires that a `CredentialResolver` is already registered in the
-/// `ClientHub` (e.g., via `TestCpBuilder`).
+/// Requires that a `CredentialResolver` and the `ResponseCache` are already
+/// registered in the `ClientHub` (e.g., via `TestCpBuilder`).
 pub struct TestDpBuilder {
     request_timeout: Option<Duration>,
 }
@@ -97,8 +117,13 @@ impl TestDpBuilder {
             .get::<dyn CredentialResolver>()
             .expect("CredentialResolver must be registered before building DP");
 
+        let response_cache = hub
+            .get::<ResponseCache>()
+            .expect("ResponseCache must be registered before building DP");
+
         let mut svc = DataPlaneServiceImpl::new(cp, cred_resolver)
-            .expect("failed to build DataPlaneServiceImpl in test");
+            .expect("failed to build DataPlaneServiceImpl in test")
+            .with_response_cache(response_cache);
         if let Some(timeout) = self.request_timeout {
             svc = svc.with_request_timeout(timeout);
         }
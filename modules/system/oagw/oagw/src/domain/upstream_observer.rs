@@ -0,0 +1,16 @@
+use uuid::Uuid;
+
+use super::model::Upstream;
+
+/// Hook for Data Plane state derived from upstream definitions, such as
+/// endpoint pools and their active health probes.
+///
+/// Called by the Control Plane after a management write succeeds, and by the
+/// module for every stored upstream at startup.
+pub(crate) trait UpstreamObserver: Send + Sync {
+    /// `upstream` was created, updated, or loaded from storage.
+    fn upstream_saved(&self, upstream: &Upstream);
+
+    /// The upstream was deleted.
+    fn upstream_deleted(&self, tenant_id: Uuid, upstream_id: Uuid);
+}
@@ -1,4 +1,5 @@
 pub(crate) mod plugin;
 pub(crate) mod proxy;
 pub(crate) mod storage;
+pub(crate) mod tenant_hierarchy;
 pub(crate) mod type_provisioning;
@@ -81,6 +81,9 @@ mod tests {
         let mut ctx = AuthContext {
             headers: HashMap::new(),
             config: make_config("authorization", "Bearer ", "cred://openai-key"),
+            method: "GET".into(),
+            path: "/".into(),
+            body: bytes::Bytes::new(),
         };
 
         plugin.authenticate(&mut ctx).await.unwrap();
@@ -101,6 +104,9 @@ mod tests {
         let mut ctx = AuthContext {
             headers: HashMap::new(),
             config: make_config("x-api-key", "", "cred://custom-key"),
+            method: "GET".into(),
+            path: "/".into(),
+            body: bytes::Bytes::new(),
         };
 
         plugin.authenticate(&mut ctx).await.unwrap();
@@ -115,6 +121,9 @@ mod tests {
         let mut ctx = AuthContext {
             headers: HashMap::new(),
             config: make_config("authorization", "Bearer ", "cred://missing"),
+            method: "GET".into(),
+            path: "/".into(),
+            body: bytes::Bytes::new(),
         };
 
         let err = plugin.authenticate(&mut ctx).await.unwrap_err();
@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::domain::plugin::{GuardContext, GuardDecision, GuardPlugin, PluginError};
+
+/// Upper bound for `Access-Control-Max-Age` (24h).
+const MAX_AGE_LIMIT: u32 = 86_400;
+
+/// Configuration for the CORS guard plugin. List values are comma-separated.
+#[derive(Debug, Deserialize)]
+struct RawCorsConfig {
+    /// Allowed origins; `*` allows any origin.
+    allowed_origins: String,
+    #[serde(default = "default_allowed_methods")]
+    allowed_methods: String,
+    #[serde(default = "default_allowed_headers")]
+    allowed_headers: String,
+    /// Response headers exposed to the browser.
+    #[serde(default)]
+    expose_headers: String,
+    /// Preflight cache duration in seconds, capped at 24h.
+    #[serde(default)]
+    max_age: Option<String>,
+    #[serde(default)]
+    allow_credentials: Option<String>,
+}
+
+fn default_allowed_methods() -> String {
+    "GET, POST".into()
+}
+
+fn default_allowed_headers() -> String {
+    "Content-Type, Authorization".into()
+}
+
+#[derive(Debug)]
+struct CorsConfig {
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+    expose_headers: Vec<String>,
+    max_age: u32,
+    allow_credentials: bool,
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn invalid(detail: impl std::fmt::Display) -> PluginError {
+    PluginError::Internal(format!("invalid cors guard config: {detail}"))
+}
+
+impl CorsConfig {
+    fn parse(config: &HashMap<String, String>) -> Result<Self, PluginError> {
+        let raw: RawCorsConfig =
+            serde_json::from_value(serde_json::to_value(config).map_err(invalid)?)
+                .map_err(invalid)?;
+
+        let max_age = match raw.max_age {
+            Some(v) => v
+                .trim()
+                .parse::<u32>()
+                .map_err(|e| invalid(format!("max_age: {e}")))?
+                .min(MAX_AGE_LIMIT),
+            None => MAX_AGE_LIMIT,
+        };
+        let allow_credentials = match raw.allow_credentials.as_deref().map(str::trim) {
+            None | Some("false") => false,
+            Some("true") => true,
+            Some(other) => return Err(invalid(format!("allow_credentials: '{other}'"))),
+        };
+
+        let config = Self {
+            allowed_origins: split_list(&raw.allowed_origins),
+            allowed_methods: split_list(&raw.allowed_methods)
+                .into_iter()
+                .map(|m| m.to_uppercase())
+                .collect(),
+            allowed_headers: split_list(&raw.allowed_headers),
+            expose_headers: split_list(&raw.expose_headers),
+            max_age,
+            allow_credentials,
+        };
+        if config.allow_credentials && config.allows_any_origin() {
+            return Err(invalid(
+                "allow_credentials cannot be combined with a wildcard origin",
+            ));
+        }
+        Ok(config)
+    }
+
+    fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|o| o == "*")
+    }
+
+    /// Exact, case-sensitive match: scheme, host and port must all agree.
+    fn allows_origin(&self, origin: &str) -> bool {
+        self.allows_any_origin() || self.allowed_origins.iter().any(|o| o == origin)
+    }
+
+    fn allows_method(&self, method: &str) -> bool {
+        self.allowed_methods.iter().any(|m| m == method)
+    }
+
+    fn allows_header(&self, header: &str) -> bool {
+        self.allowed_headers
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(header))
+    }
+
+    /// Headers shared by preflight and actual responses.
+    fn origin_headers(&self, origin: &str) -> Vec<(String, String)> {
+        let allow_origin = if self.allows_any_origin() {
+            "*"
+        } else {
+            origin
+        };
+        let mut headers = vec![
+            (
+                "access-control-allow-origin".to_string(),
+                allow_origin.to_string(),
+            ),
+            ("vary".to_string(), "Origin".to_string()),
+        ];
+        if self.allow_credentials {
+            headers.push((
+                "access-control-allow-credentials".to_string(),
+                "true".to_string(),
+            ));
+        }
+        headers
+    }
+}
+
+/// Guard plugin implementing CORS for proxied routes.
+///
+/// Preflight requests are answered locally with `204 No Content`; actual
+/// requests from disallowed origins are rejected, allowed ones get the CORS
+/// response headers. Requests without an `Origin` header are not affected.
+pub struct CorsGuardPlugin;
+
+#[async_trait::async_trait]
+impl GuardPlugin for CorsGuardPlugin {
+    async fn guard_request(&self, ctx: &GuardContext) -> Result<GuardDecision, PluginError> {
+        let config = CorsConfig::parse(&ctx.config)?;
+
+        let Some(origin) = ctx.headers.get("origin") else {
+            return Ok(GuardDecision::Allow {
+                response_headers: Vec::new(),
+            });
+        };
+        if !config.allows_origin(origin) {
+            return Err(PluginError::Rejected(format!(
+                "CORS origin '{origin}' is not allowed"
+            )));
+        }
+
+        let requested_method = ctx.headers.get("access-control-request-method");
+        let Some(requested_method) = requested_method.filter(|_| ctx.method == "OPTIONS") else {
+            let mut response_headers = config.origin_headers(origin);
+            if !config.expose_headers.is_empty() {
+                response_headers.push((
+                    "access-control-expose-headers".to_string(),
+                    config.expose_headers.join(", "),
+                ));
+            }
+            return Ok(GuardDecision::Allow { response_headers });
+        };
+
+        let requested_method = requested_method.trim().to_uppercase();
+        if !config.allows_method(&requested_method) {
+            return Err(PluginError::Rejected(format!(
+                "CORS method '{requested_method}' is not allowed"
+            )));
+        }
+        if let Some(header) = ctx
+            .headers
+            .get("access-control-request-headers")
+            .map(|h| split_list(h))
+            .unwrap_or_default()
+            .into_iter()
+            .find(|h| !config.allows_header(h))
+        {
+            return Err(PluginError::Rejected(format!(
+                "CORS header '{header}' is not allowed"
+            )));
+        }
+
+        let mut headers = config.origin_headers(origin);
+        headers.push((
+            "access-control-allow-methods".to_string(),
+            config.allowed_methods.join(", "),
+        ));
+        headers.push((
+            "access-control-allow-headers".to_string(),
+            config.allowed_headers.join(", "),
+        ));
+        headers.push((
+            "access-control-max-age".to_string(),
+            config.max_age.to_string(),
+        ));
+        Ok(GuardDecision::Respond {
+            status: 204,
+            headers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(extra: &[(&str, &str)]) -> HashMap<String, String> {
+        let mut config = HashMap::from([
+            (
+                "allowed_origins".to_string(),
+                "https://app.example.com, https://admin.example.com".to_string(),
+            ),
+            ("allowed_methods".to_string(), "GET, POST, PUT".to_string()),
+            ("expose_headers".to_string(), "X-Request-ID".to_string()),
+            ("max_age".to_string(), "3600".to_string()),
+            ("allow_credentials".to_string(), "true".to_string()),
+        ]);
+        for (k, v) in extra {
+            config.insert((*k).to_string(), (*v).to_string());
+        }
+        config
+    }
+
+    fn ctx(
+        method: &str,
+        headers: &[(&str, &str)],
+        config: HashMap<String, String>,
+    ) -> GuardContext {
+        GuardContext {
+            method: method.into(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            config,
+        }
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[tokio::test]
+    async fn preflight_is_answered_locally() {
+        let decision = CorsGuardPlugin
+            .guard_request(&ctx(
+                "OPTIONS",
+                &[
+                    ("origin", "https://app.example.com"),
+                    ("access-control-request-method", "put"),
+                    (
+                        "access-control-request-headers",
+                        "content-type, Authorization",
+                    ),
+                ],
+                config(&[]),
+            ))
+            .await
+            .unwrap();
+
+        let GuardDecision::Respond { status, headers } = decision else {
+            panic!("expected preflight response, got {decision:?}");
+        };
+        assert_eq!(status, 204);
+        assert_eq!(
+            header(&headers, "access-control-allow-origin"),
+            Some("https://app.example.com")
+        );
+        assert_eq!(
+            header(&headers, "access-control-allow-methods"),
+            Some("GET, POST, PUT")
+        );
+        assert_eq!(
+            header(&headers, "access-control-allow-headers"),
+            Some("Content-Type, Authorization")
+        );
+        assert_eq!(header(&headers, "access-control-max-age"), Some("3600"));
+        assert_eq!(
+            header(&headers, "access-control-allow-credentials"),
+            Some("true")
+        );
+        assert_eq!(header(&headers, "vary"), Some("Origin"));
+    }
+
+    #[tokio::test]
+    async fn preflight_rejects_disallowed_origin_method_and_header() {
+        let cases: [&[(&str, &str)]; 3] = [
+            &[
+                ("origin", "https://app.example.com:8443"),
+                ("access-control-request-method", "GET"),
+            ],
+            &[
+                ("origin", "https://app.example.com"),
+                ("access-control-request-method", "DELETE"),
+            ],
+            &[
+                ("origin", "https://app.example.com"),
+                ("access-control-request-method", "GET"),
+                ("access-control-request-headers", "X-Secret"),
+            ],
+        ];
+        for headers in cases {
+            let err = CorsGuardPlugin
+                .guard_request(&ctx("OPTIONS", headers, config(&[])))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, PluginError::Rejected(_)), "{headers:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn actual_request_gets_cors_headers() {
+        let decision = CorsGuardPlugin
+            .guard_request(&ctx(
+                "POST",
+                &[("origin", "https://admin.example.com")],
+                config(&[]),
+            ))
+            .await
+            .unwrap();
+
+        let GuardDecision::Allow { response_headers } = decision else {
+            panic!("expected allow, got {decision:?}");
+        };
+        assert_eq!(
+            header(&response_headers, "access-control-allow-origin"),
+            Some("https://admin.example.com")
+        );
+        assert_eq!(
+            header(&response_headers, "access-control-expose-headers"),
+            Some("X-Request-ID")
+        );
+    }
+
+    #[tokio::test]
+    async fn actual_request_from_disallowed_origin_is_rejected() {
+        let err = CorsGuardPlugin
+            .guard_request(&ctx("GET", &[("origin", "https://evil.com")], config(&[])))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PluginError::Rejected(_)));
+    }
+
+    #[tokio::test]
+    async fn request_without_origin_passes_untouched() {
+        let decision = CorsGuardPlugin
+            .guard_request(&ctx("GET", &[], config(&[])))
+            .await
+            .unwrap();
+        assert_eq!(
+            decision,
+            GuardDecision::Allow {
+                response_headers: Vec::new()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn wildcard_origin_without_credentials() {
+        let decision = CorsGuardPlugin
+            .guard_request(&ctx(
+                "GET",
+                &[("origin", "https://anything.example")],
+                config(&[("allowed_origins", "*"), ("allow_credentials", "false")]),
+            ))
+            .await
+            .unwrap();
+        let GuardDecision::Allow { response_headers } = decision else {
+            panic!("expected allow, got {decision:?}");
+        };
+        assert_eq!(
+            header(&response_headers, "access-control-allow-origin"),
+            Some("*")
+        );
+        assert_eq!(
+            header(&response_headers, "access-control-allow-credentials"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn wildcard_origin_with_credentials_is_invalid() {
+        let err = CorsGuardPlugin
+            .guard_request(&ctx(
+                "GET",
+                &[("origin", "https://app.example.com")],
+                config(&[("allowed_origins", "*")]),
+            ))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PluginError::Internal(_)));
+    }
+}
@@ -0,0 +1,250 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::domain::credential::CredentialResolver;
+use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Algorithm {
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Encoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+/// Configuration for the HMAC signature auth plugin.
+#[derive(Debug, Deserialize)]
+struct HmacConfig {
+    /// Secret reference holding the signing key.
+    secret_ref: String,
+    /// Key identifier sent alongside the signature, if the upstream needs one.
+    #[serde(default)]
+    key_id: Option<String>,
+    #[serde(default)]
+    algorithm: Algorithm,
+    #[serde(default)]
+    encoding: Encoding,
+    /// Header carrying the signature.
+    #[serde(default = "default_signature_header")]
+    signature_header: String,
+    /// Prefix prepended to the signature value (e.g. "sha256=").
+    #[serde(default)]
+    signature_prefix: String,
+    /// Header carrying the signing timestamp (unix seconds).
+    #[serde(default = "default_timestamp_header")]
+    timestamp_header: String,
+    /// Header carrying `key_id`.
+    #[serde(default = "default_key_id_header")]
+    key_id_header: String,
+}
+
+fn default_signature_header() -> String {
+    "x-signature".into()
+}
+
+fn default_timestamp_header() -> String {
+    "x-timestamp".into()
+}
+
+fn default_key_id_header() -> String {
+    "x-key-id".into()
+}
+
+/// Auth plugin that signs each request with an HMAC over the method, path,
+/// timestamp and body.
+///
+/// The signed string is
+/// `METHOD \n PATH_AND_QUERY \n TIMESTAMP \n hex(sha256(body))`; the
+/// signature and timestamp are sent in the configured headers.
+pub struct HmacAuthPlugin {
+    credential_resolver: Arc<dyn CredentialResolver>,
+}
+
+impl HmacAuthPlugin {
+    #[must_use]
+    pub fn new(credential_resolver: Arc<dyn CredentialResolver>) -> Self {
+        Self {
+            credential_resolver,
+        }
+    }
+
+    async fn sign_at(&self, ctx: &mut AuthContext, timestamp: u64) -> Result<(), PluginError> {
+        let config: HmacConfig = serde_json::from_value(
+            serde_json::to_value(&ctx.config)
+                .map_err(|e| PluginError::Internal(format!("invalid hmac auth config: {e}")))?,
+        )
+        .map_err(|e| PluginError::Internal(format!("invalid hmac auth config: {e}")))?;
+
+        let secret = self
+            .credential_resolver
+            .resolve(&config.secret_ref)
+            .await
+            .map_err(|_| PluginError::SecretNotFound(config.secret_ref.clone()))?;
+
+        let signature = sign(
+            &config,
+            secret.as_str().as_bytes(),
+            &string_to_sign(ctx, timestamp),
+        )?;
+
+        ctx.headers.insert(
+            config.signature_header.to_lowercase(),
+            format!("{}{signature}", config.signature_prefix),
+        );
+        ctx.headers.insert(
+            config.timestamp_header.to_lowercase(),
+            timestamp.to_string(),
+        );
+        if let Some(key_id) = config.key_id {
+            ctx.headers
+                .insert(config.key_id_header.to_lowercase(), key_id);
+        }
+        Ok(())
+    }
+}
+
+fn string_to_sign(ctx: &AuthContext, timestamp: u64) -> String {
+    format!(
+        "{}\n{}\n{timestamp}\n{}",
+        ctx.method.to_uppercase(),
+        ctx.path,
+        hex::encode(Sha256::digest(&ctx.body))
+    )
+}
+
+fn sign(config: &HmacConfig, key: &[u8], message: &str) -> Result<String, PluginError> {
+    let digest = match config.algorithm {
+        Algorithm::Sha256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key)
+                .map_err(|e| PluginError::Internal(format!("invalid hmac key: {e}")))?;
+            mac.update(message.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+        Algorithm::Sha512 => {
+            let mut mac = Hmac::<Sha512>::new_from_slice(key)
+                .map_err(|e| PluginError::Internal(format!("invalid hmac key: {e}")))?;
+            mac.update(message.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+    };
+    Ok(match config.encoding {
+        Encoding::Hex => hex::encode(digest),
+        Encoding::Base64 => STANDARD.encode(digest),
+    })
+}
+
+#[async_trait::async_trait]
+impl AuthPlugin for HmacAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.sign_at(ctx, timestamp).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bytes::Bytes;
+
+    use crate::infra::storage::credential_repo::InMemoryCredentialResolver;
+
+    use super::*;
+
+    const TIMESTAMP: u64 = 1_706_889_600;
+
+    fn make_plugin() -> HmacAuthPlugin {
+        HmacAuthPlugin::new(Arc::new(InMemoryCredentialResolver::with_credentials(
+            vec![("cred://signing-key".into(), "top-secret".into())],
+        )))
+    }
+
+    fn auth_ctx(config: &[(&str, &str)]) -> AuthContext {
+        AuthContext {
+            headers: HashMap::new(),
+            config: config
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            method: "POST".into(),
+            path: "/v1/orders?x=1".into(),
+            body: Bytes::from_static(br#"{"a":1}"#),
+        }
+    }
+
+    #[tokio::test]
+    async fn signs_with_hex_sha256_by_default() {
+        let mut ctx = auth_ctx(&[("secret_ref", "cred://signing-key")]);
+        make_plugin().sign_at(&mut ctx, TIMESTAMP).await.unwrap();
+
+        assert_eq!(
+            ctx.headers.get("x-signature").unwrap(),
+            "03eaed34dd2b61edd66c01f5510f627bb8a8c7b79020a106c12f6619c7a09003"
+        );
+        assert_eq!(ctx.headers.get("x-timestamp").unwrap(), "1706889600");
+        assert!(!ctx.headers.contains_key("x-key-id"));
+    }
+
+    #[tokio::test]
+    async fn honours_algorithm_encoding_and_header_overrides() {
+        let mut ctx = auth_ctx(&[
+            ("secret_ref", "cred://signing-key"),
+            ("algorithm", "sha512"),
+            ("encoding", "base64"),
+            ("signature_header", "X-Vendor-Signature"),
+            ("signature_prefix", "v1="),
+            ("timestamp_header", "X-Vendor-Date"),
+            ("key_id", "key-42"),
+            ("key_id_header", "X-Vendor-Key"),
+        ]);
+        make_plugin().sign_at(&mut ctx, TIMESTAMP).await.unwrap();
+
+        assert_eq!(
+            ctx.headers.get("x-vendor-signature").unwrap(),
+            "v1=crEedjDJ0Gi/k686tkLIzL/hmlUzUwo1TPlyP7dOff3N66zoY0PIoIyUojIOs9Wm30KdvRAjl8FD8YwgQwE3pA=="
+        );
+        assert_eq!(ctx.headers.get("x-vendor-date").unwrap(), "1706889600");
+        assert_eq!(ctx.headers.get("x-vendor-key").unwrap(), "key-42");
+    }
+
+    #[tokio::test]
+    async fn signature_covers_body() {
+        let plugin = make_plugin();
+        let mut a = auth_ctx(&[("secret_ref", "cred://signing-key")]);
+        let mut b = auth_ctx(&[("secret_ref", "cred://signing-key")]);
+        b.body = Bytes::from_static(br#"{"a":2}"#);
+        plugin.sign_at(&mut a, TIMESTAMP).await.unwrap();
+        plugin.sign_at(&mut b, TIMESTAMP).await.unwrap();
+        assert_ne!(a.headers["x-signature"], b.headers["x-signature"]);
+    }
+
+    #[tokio::test]
+    async fn unknown_algorithm_is_rejected() {
+        let mut ctx = auth_ctx(&[("secret_ref", "cred://signing-key"), ("algorithm", "md5")]);
+        let err = make_plugin().authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(_)));
+    }
+
+    #[tokio::test]
+    async fn secret_not_found_returns_error() {
+        let mut ctx = auth_ctx(&[("secret_ref", "cred://missing")]);
+        let err = make_plugin().authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(_)));
+    }
+}
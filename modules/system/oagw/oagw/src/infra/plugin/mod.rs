@@ -1,5 +1,8 @@
 pub(crate) mod apikey_auth;
+pub(crate) mod cors_guard;
+pub(crate) mod hmac_auth;
 pub(crate) mod noop_auth;
+pub(crate) mod oauth2_client_cred_auth;
 pub(crate) mod registry;
 
-pub(crate) use registry::AuthPluginRegistry;
+pub(crate) use registry::{AuthPluginRegistry, GuardPluginRegistry};
@@ -25,6 +25,9 @@ mod tests {
         let mut ctx = AuthContext {
             headers: headers.clone(),
             config: HashMap::new(),
+            method: "GET".into(),
+            path: "/".into(),
+            body: bytes::Bytes::new(),
         };
 
         plugin.authenticate(&mut ctx).await.unwrap();
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use modkit_auth::oauth2::{ClientAuthMethod, OAuthClientConfig, SecretString, Token};
+use modkit_http::{HttpClientConfig, TransportSecurity};
+use serde::Deserialize;
+use url::Url;
+
+use crate::domain::credential::{CredentialResolver, SecretValue};
+use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+
+/// Configuration for the OAuth2 client credentials auth plugins.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct OAuth2ClientCredConfig {
+    /// Token endpoint URL.
+    token_url: String,
+    /// Secret reference holding the client id.
+    client_id_ref: String,
+    /// Secret reference holding the client secret.
+    client_secret_ref: String,
+    /// Space-separated scopes to request.
+    #[serde(default)]
+    scope: Option<String>,
+    /// Audience parameter sent with the token request.
+    #[serde(default)]
+    audience: Option<String>,
+}
+
+/// A token handle plus the credentials it was obtained with, so rotated
+/// secrets are picked up on the next request.
+struct CachedToken {
+    client_id: String,
+    client_secret: SecretValue,
+    token: Token,
+}
+
+impl CachedToken {
+    fn obtained_with(&self, client_id: &SecretValue, client_secret: &SecretValue) -> bool {
+        self.client_id == client_id.as_str()
+            && self.client_secret.as_str() == client_secret.as_str()
+    }
+}
+
+/// Auth plugin that obtains an access token via the OAuth2 client credentials
+/// grant and injects it as `Authorization: Bearer <token>`.
+///
+/// Tokens are cached per configuration and refreshed in the background before
+/// they expire. A `401` from the upstream drops the cached token. Concurrent
+/// requests missing the cache wait for a single token request.
+pub struct OAuth2ClientCredAuthPlugin {
+    credential_resolver: Arc<dyn CredentialResolver>,
+    auth_method: ClientAuthMethod,
+    http_config: HttpClientConfig,
+    tokens: DashMap<OAuth2ClientCredConfig, Arc<CachedToken>>,
+    fetching: DashMap<OAuth2ClientCredConfig, Arc<tokio::sync::Mutex<()>>>,
+}
+
+impl OAuth2ClientCredAuthPlugin {
+    /// `auth_method` selects how the client authenticates to the token
+    /// endpoint: form fields or HTTP Basic.
+    #[must_use]
+    pub fn new(
+        credential_resolver: Arc<dyn CredentialResolver>,
+        auth_method: ClientAuthMethod,
+    ) -> Self {
+        Self {
+            credential_resolver,
+            auth_method,
+            http_config: HttpClientConfig::token_endpoint(),
+            tokens: DashMap::new(),
+            fetching: DashMap::new(),
+        }
+    }
+
+    /// Override the HTTP client used for token requests.
+    #[cfg(test)]
+    #[must_use]
+    fn with_http_config(mut self, http_config: HttpClientConfig) -> Self {
+        self.http_config = http_config;
+        self
+    }
+
+    async fn resolve(&self, secret_ref: &str) -> Result<SecretValue, PluginError> {
+        self.credential_resolver
+            .resolve(secret_ref)
+            .await
+            .map_err(|_| PluginError::SecretNotFound(secret_ref.to_string()))
+    }
+
+    /// Cached token for `config`, or a freshly requested one. `stale` is a
+    /// token the caller found unusable; it is never returned.
+    async fn token_for(
+        &self,
+        config: &OAuth2ClientCredConfig,
+        stale: Option<&Arc<CachedToken>>,
+    ) -> Result<Arc<CachedToken>, PluginError> {
+        let client_id = self.resolve(&config.client_id_ref).await?;
+        let client_secret = self.resolve(&config.client_secret_ref).await?;
+        let cached = || {
+            self.tokens
+                .get(config)
+                .filter(|c| c.obtained_with(&client_id, &client_secret))
+                .filter(|c| stale.is_none_or(|s| !Arc::ptr_eq(c, s)))
+                .map(|c| c.clone())
+        };
+
+        if let Some(cached) = cached() {
+            return Ok(cached);
+        }
+        // One token request per configuration at a time; whoever waited
+        // picks up the token the first caller stored.
+        let lock = self.fetching.entry(config.clone()).or_default().clone();
+        let _fetching = lock.lock().await;
+        if let Some(cached) = cached() {
+            return Ok(cached);
+        }
+
+        let token_endpoint = Url::parse(&config.token_url)
+            .map_err(|e| PluginError::Internal(format!("invalid oauth2 token_url: {e}")))?;
+        let mut http_config = self.http_config.clone();
+        if token_endpoint.scheme() == "http" {
+            // Same trust model as upstream endpoints: plain HTTP is an
+            // explicit choice of whoever configured the upstream.
+            http_config.transport = TransportSecurity::AllowInsecureHttp;
+        }
+        let token = Token::new(OAuthClientConfig {
+            token_endpoint: Some(token_endpoint),
+            client_id: client_id.as_str().to_string(),
+            client_secret: SecretString::new(client_secret.as_str()),
+            scopes: config
+                .scope
+                .as_deref()
+                .map(|s| s.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+            auth_method: self.auth_method,
+            extra_params: config
+                .audience
+                .iter()
+                .map(|a| ("audience".to_string(), a.clone()))
+                .collect(),
+            http_config: Some(http_config),
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| PluginError::AuthFailed(format!("oauth2 token request failed: {e}")))?;
+
+        let cached = Arc::new(CachedToken {
+            client_id: client_id.as_str().to_string(),
+            client_secret,
+            token,
+        });
+        self.tokens.insert(config.clone(), cached.clone());
+        Ok(cached)
+    }
+}
+
+fn parse_config(config: &HashMap<String, String>) -> Result<OAuth2ClientCredConfig, PluginError> {
+    serde_json::from_value(
+        serde_json::to_value(config)
+            .map_err(|e| PluginError::Internal(format!("invalid oauth2 auth config: {e}")))?,
+    )
+    .map_err(|e| PluginError::Internal(format!("invalid oauth2 auth config: {e}")))
+}
+
+#[async_trait::async_trait]
+impl AuthPlugin for OAuth2ClientCredAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config = parse_config(&ctx.config)?;
+
+        let cached = self.token_for(&config, None).await?;
+        let access_token = match cached.token.get() {
+            Ok(token) => token,
+            Err(_) => {
+                // Expired and the background refresh has not caught up yet:
+                // fetch a new token inline.
+                self.token_for(&config, Some(&cached))
+                    .await?
+                    .token
+                    .get()
+                    .map_err(|e| PluginError::AuthFailed(format!("oauth2 token: {e}")))?
+            }
+        };
+
+        ctx.headers.insert(
+            "authorization".into(),
+            format!("Bearer {}", access_token.expose()),
+        );
+        Ok(())
+    }
+
+    async fn on_unauthorized(&self, config: &HashMap<String, String>) {
+        if let Ok(config) = parse_config(config)
+            && self.tokens.remove(&config).is_some()
+        {
+            tracing::debug!(
+                token_url = %config.token_url,
+                "Upstream returned 401; discarded cached OAuth2 token"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bytes::Bytes;
+    use serde_json::json;
+
+    use crate::infra::storage::credential_repo::InMemoryCredentialResolver;
+    use crate::test_support::{MockBody, MockGuard, MockResponse, shared_mock};
+
+    use super::*;
+
+    fn make_plugin(auth_method: ClientAuthMethod) -> OAuth2ClientCredAuthPlugin {
+        let creds = Arc::new(InMemoryCredentialResolver::with_credentials(vec![
+            ("cred://vendor/client_id".into(), "my-client".into()),
+            ("cred://vendor/client_secret".into(), "my-secret".into()),
+        ]));
+        OAuth2ClientCredAuthPlugin::new(creds, auth_method)
+            .with_http_config(HttpClientConfig::for_testing())
+    }
+
+    fn mock_token_endpoint(guard: &mut MockGuard, access_token: &str) -> String {
+        guard.mock(
+            "POST",
+            "/oauth/token",
+            MockResponse {
+                status: 200,
+                headers: vec![("content-type".into(), "application/json".into())],
+                body: MockBody::Json(json!({
+                    "access_token": access_token,
+                    "token_type": "Bearer",
+                    "expires_in": 3600
+                })),
+            },
+        );
+        format!(
+            "http://127.0.0.1:{}{}",
+            shared_mock().port(),
+            guard.path("/oauth/token")
+        )
+    }
+
+    fn auth_ctx(token_url: &str) -> AuthContext {
+        AuthContext {
+            headers: HashMap::new(),
+            config: HashMap::from([
+                ("token_url".into(), token_url.into()),
+                ("client_id_ref".into(), "cred://vendor/client_id".into()),
+                (
+                    "client_secret_ref".into(),
+                    "cred://vendor/client_secret".into(),
+                ),
+                ("scope".into(), "read write".into()),
+                ("audience".into(), "https://api.vendor.example".into()),
+            ]),
+            method: "GET".into(),
+            path: "/v1/resource".into(),
+            body: Bytes::new(),
+        }
+    }
+
+    fn form(body: &[u8]) -> HashMap<String, String> {
+        form_urlencoded::parse(body).into_owned().collect()
+    }
+
+    #[tokio::test]
+    async fn injects_bearer_token_and_sends_form_credentials() {
+        let mut guard = MockGuard::new();
+        let token_url = mock_token_endpoint(&mut guard, "tok-form");
+        let plugin = make_plugin(ClientAuthMethod::Form);
+
+        let mut ctx = auth_ctx(&token_url);
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers.get("authorization").unwrap(), "Bearer tok-form");
+
+        let recorded = guard.recorded_requests().await;
+        assert_eq!(recorded.len(), 1);
+        let form = form(&recorded[0].body);
+        assert_eq!(form["grant_type"], "client_credentials");
+        assert_eq!(form["client_id"], "my-client");
+        assert_eq!(form["client_secret"], "my-secret");
+        assert_eq!(form["scope"], "read write");
+        assert_eq!(form["audience"], "https://api.vendor.example");
+    }
+
+    #[tokio::test]
+    async fn basic_variant_authenticates_client_with_basic_auth() {
+        let mut guard = MockGuard::new();
+        let token_url = mock_token_endpoint(&mut guard, "tok-basic");
+        let plugin = make_plugin(ClientAuthMethod::Basic);
+
+        let mut ctx = auth_ctx(&token_url);
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("authorization").unwrap(),
+            "Bearer tok-basic"
+        );
+
+        let recorded = guard.recorded_requests().await;
+        let authorization = recorded[0]
+            .headers
+            .iter()
+            .find(|(k, _)| k == "authorization")
+            .map(|(_, v)| v.as_str());
+        // base64("my-client:my-secret")
+        assert_eq!(authorization, Some("Basic bXktY2xpZW50Om15LXNlY3JldA=="));
+        assert!(!form(&recorded[0].body).contains_key("client_secret"));
+    }
+
+    #[tokio::test]
+    async fn token_is_cached_until_upstream_rejects_it() {
+        let mut guard = MockGuard::new();
+        let token_url = mock_token_endpoint(&mut guard, "tok-cached");
+        let plugin = make_plugin(ClientAuthMethod::Form);
+
+        for _ in 0..3 {
+            plugin
+                .authenticate(&mut auth_ctx(&token_url))
+                .await
+                .unwrap();
+        }
+        assert_eq!(guard.recorded_requests().await.len(), 1);
+
+        plugin.on_unauthorized(&auth_ctx(&token_url).config).await;
+        plugin
+            .authenticate(&mut auth_ctx(&token_url))
+            .await
+            .unwrap();
+        assert_eq!(guard.recorded_requests().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_cache_misses_share_one_token_request() {
+        let mut guard = MockGuard::new();
+        let token_url = mock_token_endpoint(&mut guard, "tok-shared");
+        let plugin = make_plugin(ClientAuthMethod::Form);
+
+        let results = futures_util::future::join_all((0..8).map(|_| async {
+            let mut ctx = auth_ctx(&token_url);
+            plugin.authenticate(&mut ctx).await.map(|()| ctx)
+        }))
+        .await;
+        for ctx in results {
+            assert_eq!(
+                ctx.unwrap().headers.get("authorization").unwrap(),
+                "Bearer tok-shared"
+            );
+        }
+        assert_eq!(guard.recorded_requests().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn token_endpoint_error_fails_authentication() {
+        let mut guard = MockGuard::new();
+        guard.mock(
+            "POST",
+            "/oauth/token",
+            MockResponse {
+                status: 401,
+                headers: vec![("content-type".into(), "application/json".into())],
+                body: MockBody::Json(json!({"error": "invalid_client"})),
+            },
+        );
+        let token_url = format!(
+            "http://127.0.0.1:{}{}",
+            shared_mock().port(),
+            guard.path("/oauth/token")
+        );
+        let plugin = make_plugin(ClientAuthMethod::Form);
+
+        let err = plugin
+            .authenticate(&mut auth_ctx(&token_url))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+    }
+
+    #[tokio::test]
+    async fn missing_client_secret_returns_secret_not_found() {
+        let creds = Arc::new(InMemoryCredentialResolver::new());
+        let plugin = OAuth2ClientCredAuthPlugin::new(creds, ClientAuthMethod::Form);
+
+        let err = plugin
+            .authenticate(&mut auth_ctx("https://auth.example.com/token"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(_)));
+    }
+}
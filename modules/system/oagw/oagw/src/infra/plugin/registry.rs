@@ -2,11 +2,19 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::domain::credential::CredentialResolver;
-use crate::domain::plugin::{AuthPlugin, PluginError};
+use crate::domain::plugin::{AuthPlugin, GuardPlugin, PluginError};
+
+use modkit_auth::oauth2::ClientAuthMethod;
 
 use super::apikey_auth::ApiKeyAuthPlugin;
+use super::cors_guard::CorsGuardPlugin;
+use super::hmac_auth::HmacAuthPlugin;
 use super::noop_auth::NoopAuthPlugin;
-use crate::domain::gts_helpers::{APIKEY_AUTH_PLUGIN_ID, NOOP_AUTH_PLUGIN_ID};
+use super::oauth2_client_cred_auth::OAuth2ClientCredAuthPlugin;
+use crate::domain::gts_helpers::{
+    APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID, HMAC_AUTH_PLUGIN_ID, NOOP_AUTH_PLUGIN_ID,
+    OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+};
 
 /// Registry that resolves auth plugin GTS identifiers to plugin implementations.
 pub struct AuthPluginRegistry {
@@ -14,15 +22,34 @@ pub struct AuthPluginRegistry {
 }
 
 impl AuthPluginRegistry {
-    /// Create a registry with the built-in plugins (apikey, noop).
+    /// Create a registry with the built-in plugins (apikey, noop, hmac,
+    /// oauth2 client credentials with form or basic client auth).
     #[must_use]
     pub fn with_builtins(credential_resolver: Arc<dyn CredentialResolver>) -> Self {
         let mut plugins: HashMap<String, Arc<dyn AuthPlugin>> = HashMap::new();
         plugins.insert(
             APIKEY_AUTH_PLUGIN_ID.to_string(),
-            Arc::new(ApiKeyAuthPlugin::new(credential_resolver)),
+            Arc::new(ApiKeyAuthPlugin::new(credential_resolver.clone())),
         );
         plugins.insert(NOOP_AUTH_PLUGIN_ID.to_string(), Arc::new(NoopAuthPlugin));
+        plugins.insert(
+            HMAC_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(HmacAuthPlugin::new(credential_resolver.clone())),
+        );
+        plugins.insert(
+            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(OAuth2ClientCredAuthPlugin::new(
+                credential_resolver.clone(),
+                ClientAuthMethod::Form,
+            )),
+        );
+        plugins.insert(
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(OAuth2ClientCredAuthPlugin::new(
+                credential_resolver,
+                ClientAuthMethod::Basic,
+            )),
+        );
         Self { plugins }
     }
 
@@ -38,6 +65,32 @@ impl AuthPluginRegistry {
     }
 }
 
+/// Registry that resolves guard plugin GTS identifiers to plugin implementations.
+pub struct GuardPluginRegistry {
+    plugins: HashMap<String, Arc<dyn GuardPlugin>>,
+}
+
+impl GuardPluginRegistry {
+    /// Create a registry with the built-in guard plugins (cors).
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut plugins: HashMap<String, Arc<dyn GuardPlugin>> = HashMap::new();
+        plugins.insert(CORS_GUARD_PLUGIN_ID.to_string(), Arc::new(CorsGuardPlugin));
+        Self { plugins }
+    }
+
+    /// Resolve a plugin by its GTS identifier.
+    ///
+    /// # Errors
+    /// Returns `PluginError::Internal` if the plugin is not registered.
+    pub fn resolve(&self, plugin_id: &str) -> Result<Arc<dyn GuardPlugin>, PluginError> {
+        self.plugins
+            .get(plugin_id)
+            .cloned()
+            .ok_or_else(|| PluginError::Internal(format!("unknown guard plugin: {plugin_id}")))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -63,10 +116,29 @@ mod tests {
         assert!(registry.resolve(NOOP_AUTH_PLUGIN_ID).is_ok());
     }
 
+    #[test]
+    fn resolves_hmac_and_oauth2_plugins() {
+        let registry = make_registry();
+        for id in [
+            HMAC_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+        ] {
+            assert!(registry.resolve(id).is_ok(), "{id} should be registered");
+        }
+    }
+
     #[test]
     fn unknown_plugin_returns_error() {
         let registry = make_registry();
         let err = registry.resolve("gts.x.core.oagw.auth_plugin.v1~x.core.oagw.unknown.v1");
         assert!(err.is_err());
     }
+
+    #[test]
+    fn resolves_cors_guard_plugin() {
+        let registry = GuardPluginRegistry::with_builtins();
+        assert!(registry.resolve(CORS_GUARD_PLUGIN_ID).is_ok());
+        assert!(registry.resolve(APIKEY_AUTH_PLUGIN_ID).is_err());
+    }
 }
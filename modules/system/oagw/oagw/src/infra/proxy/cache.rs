@@ -0,0 +1,840 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header};
+use uuid::Uuid;
+
+use crate::domain::model::CacheConfig;
+use crate::domain::response_cache::ResponseCacheInvalidator;
+
+/// Response header reporting how the cache handled a request (RFC 9211).
+pub(crate) const CACHE_STATUS_HEADER: &str = "cache-status";
+
+/// Headers kept on a `304 Not Modified` served from the cache (RFC 9110 §15.4.5).
+const NOT_MODIFIED_HEADERS: &[HeaderName] = &[
+    header::CACHE_CONTROL,
+    header::CONTENT_LOCATION,
+    header::DATE,
+    header::ETAG,
+    header::EXPIRES,
+    header::VARY,
+];
+
+/// How the response cache handled a request, reported in `Cache-Status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheStatus {
+    /// Served from a fresh entry without contacting the upstream.
+    Hit,
+    /// Fetched from the upstream (and stored when cacheable).
+    Miss,
+    /// A stale entry was confirmed by the upstream with `304 Not Modified`.
+    Revalidated,
+}
+
+impl CacheStatus {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Hit => "oagw; hit",
+            Self::Miss => "oagw; fwd=miss",
+            Self::Revalidated => "oagw; fwd=stale; fwd-status=304",
+        }
+    }
+}
+
+/// What the client's `Cache-Control` allows the cache to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RequestDirective {
+    /// Fresh entries may be served.
+    Use,
+    /// Entries must be revalidated with the upstream (`no-cache`, `max-age=0`).
+    Revalidate,
+    /// The cache must neither be read nor written (`no-store`).
+    Bypass,
+}
+
+pub(crate) fn request_directive(headers: &HeaderMap) -> RequestDirective {
+    let cc = CacheControl::parse(headers);
+    if cc.no_store {
+        RequestDirective::Bypass
+    } else if cc.no_cache || cc.max_age == Some(0) {
+        RequestDirective::Revalidate
+    } else {
+        RequestDirective::Use
+    }
+}
+
+/// Parsed `Cache-Control` directives relevant to a shared cache.
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    private: bool,
+    max_age: Option<u64>,
+    s_maxage: Option<u64>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut cc = Self::default();
+        let directives = headers
+            .get_all(header::CACHE_CONTROL)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','));
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((n, v)) => (n.trim(), Some(v.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            let seconds = || value.and_then(|v| v.parse::<u64>().ok());
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" => cc.no_store = true,
+                "no-cache" => cc.no_cache = true,
+                "private" => cc.private = true,
+                "max-age" => cc.max_age = seconds(),
+                "s-maxage" => cc.s_maxage = seconds(),
+                _ => {}
+            }
+        }
+        cc
+    }
+}
+
+/// Freshness lifetime of an upstream response, or `None` if a shared cache
+/// must not store it.
+///
+/// Only `200 OK` is stored. `s-maxage`, `max-age` and `Expires` are honoured
+/// in that order; `default_ttl_secs` applies when none is present. Entries
+/// with a zero lifetime are kept only if they carry an `ETag` to revalidate.
+pub(crate) fn freshness_lifetime(
+    status: StatusCode,
+    headers: &HeaderMap,
+    config: &CacheConfig,
+    now: SystemTime,
+) -> Option<Duration> {
+    let cc = CacheControl::parse(headers);
+    if status != StatusCode::OK
+        || cc.no_store
+        || cc.private
+        || headers.contains_key(header::SET_COOKIE)
+        || vary_names(headers).is_none()
+    {
+        return None;
+    }
+
+    let secs = if cc.no_cache {
+        0
+    } else {
+        cc.s_maxage
+            .or(cc.max_age)
+            .or_else(|| expires_in(headers, now))
+            .unwrap_or_else(|| u64::from(config.default_ttl_secs))
+    };
+    let lifetime = Duration::from_secs(secs.min(u64::from(config.max_ttl_secs)));
+    (!lifetime.is_zero() || headers.contains_key(header::ETAG)).then_some(lifetime)
+}
+
+/// Seconds until `Expires`, measured from `Date` when present.
+fn expires_in(headers: &HeaderMap, now: SystemTime) -> Option<u64> {
+    let date = |name| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| httpdate::parse_http_date(v).ok())
+    };
+    // An invalid `Expires` means "already expired".
+    let Some(expires) = date(header::EXPIRES) else {
+        return headers.contains_key(header::EXPIRES).then_some(0);
+    };
+    let base = date(header::DATE).unwrap_or(now);
+    Some(expires.duration_since(base).map_or(0, |d| d.as_secs()))
+}
+
+/// Request headers named by `Vary`, or `None` for `Vary: *`.
+fn vary_names(headers: &HeaderMap) -> Option<Vec<HeaderName>> {
+    let mut names = Vec::new();
+    let values = headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+    for value in values {
+        if value == "*" {
+            return None;
+        }
+        if let Ok(name) = HeaderName::from_bytes(value.to_ascii_lowercase().as_bytes())
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+    }
+    names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    Some(names)
+}
+
+/// Whether an `If-None-Match` value matches `etag` (weak comparison).
+pub(crate) fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// A stored upstream response.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Bytes,
+    stored_at: Instant,
+    /// Age the response already had when it was received (its `Age` header).
+    initial_age: Duration,
+    lifetime: Duration,
+}
+
+impl CachedResponse {
+    pub(crate) fn new(
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Bytes,
+        lifetime: Duration,
+        now: Instant,
+    ) -> Self {
+        let initial_age = headers
+            .get(header::AGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map_or(Duration::ZERO, Duration::from_secs);
+        Self {
+            status,
+            headers,
+            body,
+            stored_at: now,
+            initial_age,
+            lifetime,
+        }
+    }
+
+    fn age(&self, now: Instant) -> Duration {
+        self.initial_age + now.saturating_duration_since(self.stored_at)
+    }
+
+    pub(crate) fn is_fresh(&self, now: Instant) -> bool {
+        self.age(now) < self.lifetime
+    }
+
+    pub(crate) fn etag(&self) -> Option<&HeaderValue> {
+        self.headers.get(header::ETAG)
+    }
+
+    /// Whether the client's `If-None-Match` is satisfied by this response.
+    pub(crate) fn matches_client(&self, req_headers: &HeaderMap) -> bool {
+        let (Some(inm), Some(etag)) = (
+            req_headers
+                .get(header::IF_NONE_MATCH)
+                .and_then(|v| v.to_str().ok()),
+            self.etag().and_then(|v| v.to_str().ok()),
+        ) else {
+            return false;
+        };
+        etag_matches(inm, etag)
+    }
+
+    /// Headers to serve this response with at `now`, including `Age`.
+    pub(crate) fn headers_at(&self, now: Instant) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        headers.insert(header::AGE, HeaderValue::from(self.age(now).as_secs()));
+        headers
+    }
+
+    /// Headers for a `304 Not Modified` answering a matching conditional request.
+    pub(crate) fn not_modified_headers(&self, now: Instant) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for name in NOT_MODIFIED_HEADERS {
+            for value in self.headers.get_all(name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+        headers.insert(header::AGE, HeaderValue::from(self.age(now).as_secs()));
+        headers
+    }
+
+    /// The entry refreshed by a `304 Not Modified` from the upstream, or
+    /// `None` if the updated headers no longer allow storing it.
+    pub(crate) fn revalidated(
+        &self,
+        not_modified: &HeaderMap,
+        config: &CacheConfig,
+        now_system: SystemTime,
+        now: Instant,
+    ) -> Option<Self> {
+        let mut headers = self.headers.clone();
+        // The upstream's `Age` from the original fetch no longer applies.
+        headers.remove(header::AGE);
+        for name in not_modified.keys() {
+            if *name == header::CONTENT_LENGTH {
+                continue;
+            }
+            headers.remove(name);
+            for value in not_modified.get_all(name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+        let lifetime = freshness_lifetime(self.status, &headers, config, now_system)?;
+        Some(Self::new(
+            self.status,
+            headers,
+            self.body.clone(),
+            lifetime,
+            now,
+        ))
+    }
+
+    fn size(&self) -> usize {
+        self.body.len()
+            + self
+                .headers
+                .iter()
+                .map(|(k, v)| k.as_str().len() + v.len())
+                .sum::<usize>()
+    }
+}
+
+/// One cacheable request target on one route, scoped to the requesting
+/// subject within its tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ResourceKey {
+    pub(crate) tenant_id: Uuid,
+    pub(crate) subject_id: Uuid,
+    pub(crate) route_id: Uuid,
+    pub(crate) path_and_query: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct EntryKey {
+    resource: ResourceKey,
+    /// Values of the request headers named by the response's `Vary`.
+    variant: Vec<Option<Vec<u8>>>,
+}
+
+impl EntryKey {
+    fn new(resource: ResourceKey, vary: &[HeaderName], req_headers: &HeaderMap) -> Self {
+        let variant = vary
+            .iter()
+            .map(|name| {
+                let values: Vec<&[u8]> = req_headers
+                    .get_all(name)
+                    .iter()
+                    .map(HeaderValue::as_bytes)
+                    .collect();
+                (!values.is_empty()).then(|| values.join(&b","[..]))
+            })
+            .collect();
+        Self { resource, variant }
+    }
+}
+
+struct Slot {
+    response: CachedResponse,
+    upstream_id: Uuid,
+    size: usize,
+    tick: u64,
+}
+
+/// `Vary` header names of the variants stored for a resource.
+struct VaryIndex {
+    names: Vec<HeaderName>,
+    variants: usize,
+}
+
+#[derive(Default)]
+struct State {
+    slots: HashMap<EntryKey, Slot>,
+    /// Least recently used first.
+    lru: BTreeMap<u64, EntryKey>,
+    vary: HashMap<ResourceKey, VaryIndex>,
+    tick: u64,
+    bytes: usize,
+}
+
+impl State {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, key: &EntryKey) {
+        let Some(slot) = self.slots.remove(key) else {
+            return;
+        };
+        self.lru.remove(&slot.tick);
+        self.bytes -= slot.size;
+        if let Some(index) = self.vary.get_mut(&key.resource) {
+            index.variants -= 1;
+            if index.variants == 0 {
+                self.vary.remove(&key.resource);
+            }
+        }
+    }
+
+    fn remove_where(&mut self, pred: impl Fn(&EntryKey, &Slot) -> bool) {
+        let keys: Vec<EntryKey> = self
+            .slots
+            .iter()
+            .filter(|(k, s)| pred(k, s))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in &keys {
+            self.remove(key);
+        }
+    }
+}
+
+/// In-memory LRU cache of upstream responses for routes with caching enabled.
+///
+/// Entries are keyed by tenant, subject, route and request target, then by the
+/// request headers the response `Vary`s on, so responses are never shared
+/// across tenants or subjects. State is local to this node.
+pub struct ResponseCache {
+    state: Mutex<State>,
+    max_entries: usize,
+    max_bytes: usize,
+}
+
+impl ResponseCache {
+    /// Cache holding at most `max_entries` responses and `max_bytes` of
+    /// bodies and headers; least recently used entries are evicted first.
+    #[must_use]
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            max_entries,
+            max_bytes,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The stored variant of `resource` matching `req_headers`, fresh or stale.
+    pub(crate) fn get(
+        &self,
+        resource: &ResourceKey,
+        req_headers: &HeaderMap,
+    ) -> Option<CachedResponse> {
+        let mut state = self.lock();
+        let names = state.vary.get(resource)?.names.clone();
+        let key = EntryKey::new(resource.clone(), &names, req_headers);
+        let tick = state.next_tick();
+        let state = &mut *state;
+        let slot = state.slots.get_mut(&key)?;
+        state.lru.remove(&slot.tick);
+        state.lru.insert(tick, key);
+        slot.tick = tick;
+        Some(slot.response.clone())
+    }
+
+    pub(crate) fn insert(
+        &self,
+        resource: ResourceKey,
+        upstream_id: Uuid,
+        req_headers: &HeaderMap,
+        response: CachedResponse,
+    ) {
+        let Some(names) = vary_names(&response.headers) else {
+            return;
+        };
+        let size = response.size() + resource.path_and_query.len();
+        if size > self.max_bytes || self.max_entries == 0 {
+            return;
+        }
+
+        let mut state = self.lock();
+        // A resource whose `Vary` changed cannot be matched by the old variants.
+        if state
+            .vary
+            .get(&resource)
+            .is_some_and(|index| index.names != names)
+        {
+            state.remove_where(|k, _| k.resource == resource);
+        }
+        let key = EntryKey::new(resource.clone(), &names, req_headers);
+        state.remove(&key);
+
+        while state.slots.len() >= self.max_entries || state.bytes + size > self.max_bytes {
+            let Some((_, oldest)) = state.lru.pop_first() else {
+                break;
+            };
+            state.remove(&oldest);
+        }
+
+        let tick = state.next_tick();
+        state
+            .vary
+            .entry(resource)
+            .or_insert(VaryIndex { names, variants: 0 })
+            .variants += 1;
+        state.lru.insert(tick, key.clone());
+        state.bytes += size;
+        state.slots.insert(
+            key,
+            Slot {
+                response,
+                upstream_id,
+                size,
+                tick,
+            },
+        );
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.lock().slots.len()
+    }
+}
+
+impl ResponseCacheInvalidator for ResponseCache {
+    fn invalidate_upstream(&self, tenant_id: Uuid, upstream_id: Uuid) {
+        self.lock().remove_where(|k, slot| {
+            k.resource.tenant_id == tenant_id && slot.upstream_id == upstream_id
+        });
+    }
+
+    fn invalidate_route(&self, tenant_id: Uuid, route_id: Uuid) {
+        self.lock().remove_where(|k, _| {
+            k.resource.tenant_id == tenant_id && k.resource.route_id == route_id
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CacheConfig {
+        CacheConfig {
+            enabled: true,
+            default_ttl_secs: 0,
+            max_ttl_secs: 3600,
+            max_entry_bytes: 1024,
+        }
+    }
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (k, v) in pairs {
+            map.append(
+                HeaderName::from_bytes(k.as_bytes()).unwrap(),
+                HeaderValue::from_str(v).unwrap(),
+            );
+        }
+        map
+    }
+
+    fn resource(tenant_id: Uuid, path: &str) -> ResourceKey {
+        ResourceKey {
+            tenant_id,
+            subject_id: Uuid::nil(),
+            route_id: Uuid::nil(),
+            path_and_query: path.into(),
+        }
+    }
+
+    fn response(pairs: &[(&str, &str)], body: &'static str) -> CachedResponse {
+        CachedResponse::new(
+            StatusCode::OK,
+            headers(pairs),
+            Bytes::from_static(body.as_bytes()),
+            Duration::from_secs(60),
+            Instant::now(),
+        )
+    }
+
+    fn lifetime(pairs: &[(&str, &str)]) -> Option<Duration> {
+        freshness_lifetime(
+            StatusCode::OK,
+            &headers(pairs),
+            &config(),
+            SystemTime::now(),
+        )
+    }
+
+    #[test]
+    fn freshness_prefers_s_maxage_then_max_age_and_caps_at_max_ttl() {
+        assert_eq!(
+            lifetime(&[("cache-control", "max-age=60, s-maxage=120")]),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            lifetime(&[("cache-control", "public, max-age=60")]),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            lifetime(&[("cache-control", "max-age=999999")]),
+            Some(Duration::from_secs(3600))
+        );
+    }
+
+    #[test]
+    fn freshness_from_expires_relative_to_date() {
+        let date = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let expires = date + Duration::from_secs(300);
+        assert_eq!(
+            lifetime(&[
+                ("date", &httpdate::fmt_http_date(date)),
+                ("expires", &httpdate::fmt_http_date(expires)),
+            ]),
+            Some(Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn uncacheable_responses_are_not_stored() {
+        for pairs in [
+            &[("cache-control", "no-store")][..],
+            &[("cache-control", "private, max-age=60")],
+            &[("cache-control", "max-age=60"), ("set-cookie", "a=b")],
+            &[("cache-control", "max-age=60"), ("vary", "*")],
+            // No explicit lifetime, no default TTL and nothing to revalidate with.
+            &[],
+        ] {
+            assert_eq!(lifetime(pairs), None, "{pairs:?}");
+        }
+        assert_eq!(
+            freshness_lifetime(
+                StatusCode::NOT_FOUND,
+                &headers(&[("cache-control", "max-age=60")]),
+                &config(),
+                SystemTime::now()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn no_cache_with_etag_is_stored_but_always_stale() {
+        assert_eq!(
+            lifetime(&[("cache-control", "no-cache"), ("etag", "\"v1\"")]),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn request_directives() {
+        assert_eq!(request_directive(&headers(&[])), RequestDirective::Use);
+        assert_eq!(
+            request_directive(&headers(&[("cache-control", "no-cache")])),
+            RequestDirective::Revalidate
+        );
+        assert_eq!(
+            request_directive(&headers(&[("cache-control", "max-age=0")])),
+            RequestDirective::Revalidate
+        );
+        assert_eq!(
+            request_directive(&headers(&[("cache-control", "no-store")])),
+            RequestDirective::Bypass
+        );
+    }
+
+    #[test]
+    fn etag_matching_is_weak() {
+        assert!(etag_matches("\"a\", W/\"b\"", "\"b\""));
+        assert!(etag_matches("*", "\"x\""));
+        assert!(!etag_matches("\"a\"", "\"b\""));
+    }
+
+    #[test]
+    fn entries_are_isolated_per_tenant() {
+        let cache = ResponseCache::new(10, 1 << 20);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        cache.insert(
+            resource(a, "/rates"),
+            Uuid::nil(),
+            &HeaderMap::new(),
+            response(&[], "tenant-a"),
+        );
+
+        assert!(
+            cache
+                .get(&resource(b, "/rates"), &HeaderMap::new())
+                .is_none()
+        );
+        let hit = cache
+            .get(&resource(a, "/rates"), &HeaderMap::new())
+            .unwrap();
+        assert_eq!(hit.body, Bytes::from_static(b"tenant-a"));
+    }
+
+    #[test]
+    fn entries_are_isolated_per_subject() {
+        let cache = ResponseCache::new(10, 1 << 20);
+        let tenant = Uuid::new_v4();
+        let of = |subject_id| ResourceKey {
+            subject_id,
+            ..resource(tenant, "/me")
+        };
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        cache.insert(
+            of(alice),
+            Uuid::nil(),
+            &HeaderMap::new(),
+            response(&[], "alice"),
+        );
+
+        assert!(cache.get(&of(bob), &HeaderMap::new()).is_none());
+        assert!(cache.get(&of(alice), &HeaderMap::new()).is_some());
+    }
+
+    #[test]
+    fn variants_are_selected_by_vary_headers() {
+        let cache = ResponseCache::new(10, 1 << 20);
+        let tenant = Uuid::new_v4();
+        let en = headers(&[("accept-language", "en")]);
+        let de = headers(&[("accept-language", "de")]);
+        cache.insert(
+            resource(tenant, "/catalog"),
+            Uuid::nil(),
+            &en,
+            response(&[("vary", "Accept-Language")], "hello"),
+        );
+        cache.insert(
+            resource(tenant, "/catalog"),
+            Uuid::nil(),
+            &de,
+            response(&[("vary", "Accept-Language")], "hallo"),
+        );
+
+        let get = |h: &HeaderMap| cache.get(&resource(tenant, "/catalog"), h);
+        assert_eq!(get(&en).unwrap().body, Bytes::from_static(b"hello"));
+        assert_eq!(get(&de).unwrap().body, Bytes::from_static(b"hallo"));
+        assert!(get(&headers(&[("accept-language", "fr")])).is_none());
+    }
+
+    #[test]
+    fn least_recently_used_entry_is_evicted() {
+        let cache = ResponseCache::new(2, 1 << 20);
+        let tenant = Uuid::new_v4();
+        let none = HeaderMap::new();
+        for path in ["/a", "/b"] {
+            cache.insert(
+                resource(tenant, path),
+                Uuid::nil(),
+                &none,
+                response(&[], "x"),
+            );
+        }
+        // Touch /a so /b becomes the eviction candidate.
+        assert!(cache.get(&resource(tenant, "/a"), &none).is_some());
+        cache.insert(
+            resource(tenant, "/c"),
+            Uuid::nil(),
+            &none,
+            response(&[], "x"),
+        );
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&resource(tenant, "/a"), &none).is_some());
+        assert!(cache.get(&resource(tenant, "/b"), &none).is_none());
+        assert!(cache.get(&resource(tenant, "/c"), &none).is_some());
+    }
+
+    #[test]
+    fn byte_limit_evicts_and_rejects_oversized_entries() {
+        let cache = ResponseCache::new(10, 40);
+        let tenant = Uuid::new_v4();
+        let none = HeaderMap::new();
+        cache.insert(
+            resource(tenant, "/a"),
+            Uuid::nil(),
+            &none,
+            response(&[], "0123456789"),
+        );
+        cache.insert(
+            resource(tenant, "/b"),
+            Uuid::nil(),
+            &none,
+            response(&[], "0123456789"),
+        );
+        cache.insert(
+            resource(tenant, "/c"),
+            Uuid::nil(),
+            &none,
+            response(&[], "0123456789"),
+        );
+        assert_eq!(cache.len(), 3);
+        cache.insert(
+            resource(tenant, "/d"),
+            Uuid::nil(),
+            &none,
+            response(&[], "0123456789"),
+        );
+        assert_eq!(cache.len(), 3);
+        assert!(cache.get(&resource(tenant, "/a"), &none).is_none());
+
+        let big = "x".repeat(64).leak();
+        cache.insert(
+            resource(tenant, "/e"),
+            Uuid::nil(),
+            &none,
+            response(&[], big),
+        );
+        assert!(cache.get(&resource(tenant, "/e"), &none).is_none());
+    }
+
+    #[test]
+    fn invalidation_by_route_and_upstream() {
+        let cache = ResponseCache::new(10, 1 << 20);
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+        let none = HeaderMap::new();
+        let on_route = |route_id| ResourceKey {
+            tenant_id: tenant,
+            subject_id: Uuid::nil(),
+            route_id,
+            path_and_query: "/x".into(),
+        };
+        let (r1, r2) = (Uuid::new_v4(), Uuid::new_v4());
+        cache.insert(on_route(r1), upstream, &none, response(&[], "1"));
+        cache.insert(on_route(r2), upstream, &none, response(&[], "2"));
+
+        cache.invalidate_route(Uuid::new_v4(), r1);
+        assert_eq!(cache.len(), 2, "other tenants cannot invalidate");
+        cache.invalidate_route(tenant, r1);
+        assert!(cache.get(&on_route(r1), &none).is_none());
+        assert!(cache.get(&on_route(r2), &none).is_some());
+
+        cache.invalidate_upstream(tenant, upstream);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn revalidation_refreshes_headers_and_lifetime() {
+        let stale = CachedResponse::new(
+            StatusCode::OK,
+            headers(&[("etag", "\"v1\""), ("cache-control", "max-age=0")]),
+            Bytes::from_static(b"body"),
+            Duration::ZERO,
+            Instant::now(),
+        );
+        let now = Instant::now();
+        assert!(!stale.is_fresh(now));
+
+        let fresh = stale
+            .revalidated(
+                &headers(&[("cache-control", "max-age=30"), ("etag", "\"v1\"")]),
+                &config(),
+                SystemTime::now(),
+                now,
+            )
+            .unwrap();
+        assert!(fresh.is_fresh(now));
+        assert_eq!(fresh.body, stale.body);
+        assert!(fresh.matches_client(&headers(&[("if-none-match", "\"v1\"")])));
+    }
+}
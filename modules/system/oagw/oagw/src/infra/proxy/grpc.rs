@@ -0,0 +1,116 @@
+//! gRPC proxying: request detection, protocol headers and trailer-preserving
+//! response bodies.
+
+use http::{HeaderMap, HeaderValue};
+use http_body_util::BodyExt;
+use oagw_sdk::api::ResponseTrailers;
+use oagw_sdk::body::{Body, BodyStream, BoxError};
+
+use super::load_balancer::EndpointLease;
+
+/// Whether the request is a native gRPC call (`application/grpc`,
+/// `application/grpc+proto`, ...). gRPC-Web works over HTTP/1.1 and is
+/// proxied as plain HTTP.
+pub(crate) fn is_grpc_request(headers: &HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|ct| ct.strip_prefix("application/grpc"))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['+', ';']))
+}
+
+/// Forward the gRPC protocol headers (`grpc-timeout`, `grpc-encoding`, ...)
+/// regardless of passthrough mode, and announce trailer support, which gRPC
+/// servers require.
+pub(crate) fn prepare_outbound_headers(inbound: &HeaderMap, outbound: &mut HeaderMap) {
+    for name in inbound.keys().filter(|n| n.as_str().starts_with("grpc-")) {
+        if !outbound.contains_key(name) {
+            for value in inbound.get_all(name) {
+                outbound.append(name.clone(), value.clone());
+            }
+        }
+    }
+    outbound.insert(http::header::TE, HeaderValue::from_static("trailers"));
+}
+
+/// Stream the upstream body frame by frame, recording its trailers in the
+/// returned handle when the body ends. The lease rides along with the body
+/// so the endpoint counts as in flight until the stream is consumed or dropped.
+pub(crate) fn streaming_body(
+    response: reqwest::Response,
+    lease: EndpointLease,
+) -> (Body, ResponseTrailers) {
+    let trailers = ResponseTrailers::default();
+    let state = (
+        http::Response::from(response).into_body(),
+        trailers.clone(),
+        lease,
+    );
+    let stream: BodyStream = Box::pin(futures_util::stream::unfold(
+        state,
+        |(mut body, trailers, lease)| async move {
+            loop {
+                match body.frame().await? {
+                    Ok(frame) => match frame.into_data() {
+                        Ok(data) => return Some((Ok(data), (body, trailers, lease))),
+                        Err(frame) => {
+                            if let Ok(t) = frame.into_trailers() {
+                                trailers.set(t);
+                            }
+                        }
+                    },
+                    Err(e) => return Some((Err(Box::new(e) as BoxError), (body, trailers, lease))),
+                }
+            }
+        },
+    ));
+    (Body::Stream(stream), trailers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(k, v)| {
+                (
+                    http::HeaderName::from_static(k),
+                    HeaderValue::from_static(v),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detects_grpc_content_types() {
+        for ct in [
+            "application/grpc",
+            "application/grpc+proto",
+            "application/grpc; charset=utf-8",
+        ] {
+            assert!(is_grpc_request(&headers(&[("content-type", ct)])), "{ct}");
+        }
+        for ct in ["application/grpc-web", "application/json"] {
+            assert!(!is_grpc_request(&headers(&[("content-type", ct)])), "{ct}");
+        }
+        assert!(!is_grpc_request(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn outbound_headers_carry_grpc_metadata_and_te() {
+        let inbound = headers(&[
+            ("grpc-timeout", "5S"),
+            ("grpc-encoding", "gzip"),
+            ("x-request-id", "abc"),
+        ]);
+        let mut outbound = headers(&[("grpc-encoding", "identity")]);
+        prepare_outbound_headers(&inbound, &mut outbound);
+
+        assert_eq!(outbound["grpc-timeout"], "5S");
+        assert_eq!(outbound["grpc-encoding"], "identity");
+        assert_eq!(outbound["te"], "trailers");
+        assert!(!outbound.contains_key("x-request-id"));
+    }
+}
@@ -1,4 +1,7 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use crate::domain::model::{PassthroughMode, RequestHeaderRules};
+use crate::domain::rate_limit::RateLimitQuota;
 use http::{HeaderMap, HeaderName, HeaderValue};
 
 const HOP_BY_HOP_HEADERS: &[&str] = &[
@@ -143,6 +146,35 @@ pub fn set_host_header(headers: &mut HeaderMap, host: &str, port: u16) {
     }
 }
 
+/// Set `X-RateLimit-Limit`, `X-RateLimit-Remaining` and `X-RateLimit-Reset`
+/// (unix seconds) from the gateway's limiter, replacing any upstream values.
+pub fn set_rate_limit_headers(headers: &mut HeaderMap, quota: &RateLimitQuota, now: SystemTime) {
+    let reset = (now + quota.reset_after)
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs_f64().ceil() as u64);
+    headers.insert("x-ratelimit-limit", HeaderValue::from(quota.limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(quota.remaining));
+    headers.insert("x-ratelimit-reset", HeaderValue::from(reset));
+}
+
+/// Add headers produced by guard plugins, replacing upstream values.
+/// `Vary` is appended so upstream cache keys are preserved.
+pub fn apply_guard_headers(headers: &mut HeaderMap, guard_headers: &[(String, String)]) {
+    for (name, value) in guard_headers {
+        let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) else {
+            continue;
+        };
+        if name == http::header::VARY {
+            headers.append(name, value);
+        } else {
+            headers.insert(name, value);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -400,4 +432,22 @@ mod tests {
         assert!(headers.get("x-oagw-trace-id").is_none());
         assert_eq!(headers.get("x-custom").unwrap(), "keep");
     }
+
+    #[test]
+    fn rate_limit_headers_replace_upstream_values() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "5000".parse().unwrap());
+        let quota = RateLimitQuota {
+            limit: 100,
+            remaining: 99,
+            reset_after: std::time::Duration::from_millis(600),
+        };
+        let now = UNIX_EPOCH + std::time::Duration::from_secs(1_706_889_600);
+
+        set_rate_limit_headers(&mut headers, &quota, now);
+
+        assert_eq!(headers.get("x-ratelimit-limit").unwrap(), "100");
+        assert_eq!(headers.get("x-ratelimit-remaining").unwrap(), "99");
+        assert_eq!(headers.get("x-ratelimit-reset").unwrap(), "1706889601");
+    }
 }
@@ -0,0 +1,81 @@
+//! Active HTTP health probing for upstream endpoint pools.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::future::join_all;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::model::Scheme;
+
+use super::load_balancer::{EndpointPool, LoadBalancer};
+use super::request_builder;
+
+/// How often the checker wakes up to look for pools that are due a probe.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Pools of unprobed upstreams untouched by proxy traffic for this long are dropped.
+const POOL_IDLE_TTL: Duration = Duration::from_secs(600);
+
+/// Periodically probes endpoints of upstreams that configure `health_check`.
+///
+/// Pools of such upstreams are created when the upstream is created, updated
+/// or loaded at startup (see [`UpstreamObserver`]), so probing starts before
+/// the first proxied request and stops when the upstream is deleted.
+///
+/// [`UpstreamObserver`]: crate::domain::upstream_observer::UpstreamObserver
+pub(crate) struct HealthChecker {
+    lb: Arc<LoadBalancer>,
+    http_client: reqwest::Client,
+}
+
+impl HealthChecker {
+    pub(crate) fn new(lb: Arc<LoadBalancer>, http_client: reqwest::Client) -> Self {
+        Self { lb, http_client }
+    }
+
+    /// Run until `cancel` fires.
+    pub(crate) async fn run(&self, cancel: CancellationToken) {
+        let mut interval = tokio::time::interval(TICK);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                _ = interval.tick() => {
+                    self.lb.evict_idle(POOL_IDLE_TTL);
+                    let now = Instant::now();
+                    let due: Vec<_> = self
+                        .lb
+                        .pools()
+                        .into_iter()
+                        .filter(|p| p.probe_due(now))
+                        .collect();
+                    join_all(due.iter().map(|p| self.probe_pool(p))).await;
+                }
+            }
+        }
+    }
+
+    async fn probe_pool(&self, pool: &EndpointPool) {
+        let Some(hc) = pool.health_check() else {
+            return;
+        };
+        let timeout = Duration::from_secs(u64::from(hc.timeout_secs));
+        let probes = pool.endpoints().iter().map(|ep| {
+            // Only plain HTTP(S) endpoints can be probed; leave others untouched.
+            let url = matches!(ep.scheme, Scheme::Http | Scheme::Https)
+                .then(|| request_builder::build_upstream_url(ep, &hc.path, "", &[]));
+            async move {
+                let url = url?;
+                let result = tokio::time::timeout(timeout, self.http_client.get(&url).send()).await;
+                Some(matches!(result, Ok(Ok(ref resp)) if resp.status().is_success()))
+            }
+        });
+        let results = join_all(probes).await;
+        for (index, result) in results.into_iter().enumerate() {
+            if let Some(success) = result {
+                pool.record_probe(index, success);
+            }
+        }
+    }
+}
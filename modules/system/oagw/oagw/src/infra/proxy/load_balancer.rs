@@ -0,0 +1,668 @@
+//! Endpoint selection and health tracking for multi-endpoint upstreams.
+//!
+//! Each upstream gets an [`EndpointPool`] holding per-endpoint runtime state.
+//! Selection skips endpoints that are either marked unhealthy by active probes
+//! or temporarily ejected by passive outlier detection. If every endpoint is
+//! unavailable the pool falls back to the full endpoint set rather than
+//! failing all traffic.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use dashmap::{DashMap, DashSet};
+use uuid::Uuid;
+
+use crate::domain::model::{
+    Endpoint, EndpointStatus, HealthCheckConfig, LoadBalancingConfig, LoadBalancingStrategy,
+    OutlierDetectionConfig, Upstream, UpstreamStatus,
+};
+use crate::domain::upstream_observer::UpstreamObserver;
+
+/// Passive outlier detection applied when the upstream does not configure one.
+const DEFAULT_OUTLIER_DETECTION: OutlierDetectionConfig = OutlierDetectionConfig {
+    consecutive_failures: 5,
+    base_ejection_secs: 30,
+    max_ejection_percent: 50,
+};
+
+/// Cap on the ejection multiplier so repeated ejections stay bounded.
+const MAX_EJECTION_MULTIPLIER: u32 = 10;
+
+/// How the endpoint for a request was chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SelectionMethod {
+    /// Pinned via `X-OAGW-Target-Host`.
+    ExplicitHeader,
+    /// Single-endpoint upstream.
+    Default,
+    Balanced(LoadBalancingStrategy),
+}
+
+impl SelectionMethod {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::ExplicitHeader => "explicit_header",
+            Self::Default => "default",
+            Self::Balanced(LoadBalancingStrategy::RoundRobin) => "round_robin",
+            Self::Balanced(LoadBalancingStrategy::LeastInFlight) => "least_in_flight",
+            Self::Balanced(LoadBalancingStrategy::Weighted) => "weighted",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    in_flight: u64,
+    consecutive_failures: u32,
+    ejected_until: Option<Instant>,
+    ejection_count: u32,
+    /// Set to `true` by active probes once the unhealthy threshold is reached.
+    probe_unhealthy: bool,
+    probe_successes: u32,
+    probe_failures: u32,
+    /// Running weight for smooth weighted round-robin.
+    current_weight: i64,
+}
+
+impl EndpointState {
+    fn is_ejected(&self, now: Instant) -> bool {
+        self.ejected_until.is_some_and(|until| until > now)
+    }
+
+    fn is_available(&self, now: Instant) -> bool {
+        !self.probe_unhealthy && !self.is_ejected(now)
+    }
+}
+
+/// Runtime state for one upstream's endpoints.
+pub(crate) struct EndpointPool {
+    endpoints: Vec<Endpoint>,
+    config: LoadBalancingConfig,
+    cursor: AtomicUsize,
+    state: Mutex<Vec<EndpointState>>,
+    last_used: Mutex<Instant>,
+    next_probe_at: Mutex<Instant>,
+}
+
+impl EndpointPool {
+    fn new(endpoints: Vec<Endpoint>, config: LoadBalancingConfig) -> Self {
+        let state = endpoints.iter().map(|_| EndpointState::default()).collect();
+        let now = Instant::now();
+        Self {
+            endpoints,
+            config,
+            cursor: AtomicUsize::new(0),
+            state: Mutex::new(state),
+            last_used: Mutex::new(now),
+            next_probe_at: Mutex::new(now),
+        }
+    }
+
+    fn matches(&self, endpoints: &[Endpoint], config: &LoadBalancingConfig) -> bool {
+        self.endpoints == endpoints && &self.config == config
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<EndpointState>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn touch(&self) {
+        *self.last_used.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    fn idle_for(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(*self.last_used.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    pub(crate) fn endpoints(&self) -> &[Endpoint] {
+        &self.endpoints
+    }
+
+    pub(crate) fn health_check(&self) -> Option<&HealthCheckConfig> {
+        self.config.health_check.as_ref()
+    }
+
+    fn outlier_detection(&self) -> &OutlierDetectionConfig {
+        self.config
+            .outlier_detection
+            .as_ref()
+            .unwrap_or(&DEFAULT_OUTLIER_DETECTION)
+    }
+
+    /// Whether an active probe round is due; if so, schedules the next one.
+    pub(crate) fn probe_due(&self, now: Instant) -> bool {
+        let Some(hc) = self.health_check() else {
+            return false;
+        };
+        let mut next = self.next_probe_at.lock().unwrap_or_else(|e| e.into_inner());
+        if now < *next {
+            return false;
+        }
+        *next = now + Duration::from_secs(u64::from(hc.interval_secs));
+        true
+    }
+
+    fn pick(&self, now: Instant) -> usize {
+        let mut state = self.lock();
+        let mut candidates: Vec<usize> = (0..state.len())
+            .filter(|&i| state[i].is_available(now))
+            .collect();
+        if candidates.is_empty() {
+            // Every endpoint is down: spreading load beats rejecting everything.
+            candidates = (0..state.len()).collect();
+        }
+
+        let tick = self.cursor.fetch_add(1, Ordering::Relaxed);
+        let chosen = match self.config.strategy {
+            LoadBalancingStrategy::RoundRobin => candidates[tick % candidates.len()],
+            LoadBalancingStrategy::LeastInFlight => {
+                // Rotate the starting point so ties are broken round-robin.
+                let offset = tick % candidates.len();
+                candidates
+                    .iter()
+                    .cycle()
+                    .skip(offset)
+                    .take(candidates.len())
+                    .copied()
+                    .min_by_key(|&i| state[i].in_flight)
+                    .unwrap_or(candidates[0])
+            }
+            LoadBalancingStrategy::Weighted => {
+                let mut total = 0i64;
+                let mut best = candidates[0];
+                for &i in &candidates {
+                    let weight = i64::from(self.endpoints[i].weight.max(1));
+                    total += weight;
+                    state[i].current_weight += weight;
+                    if state[i].current_weight > state[best].current_weight {
+                        best = i;
+                    }
+                }
+                state[best].current_weight -= total;
+                best
+            }
+        };
+        state[chosen].in_flight += 1;
+        chosen
+    }
+
+    fn pin(&self, index: usize) {
+        self.lock()[index].in_flight += 1;
+    }
+
+    fn release(&self, index: usize, outcome: Option<bool>) {
+        let now = Instant::now();
+        let od = self.outlier_detection().clone();
+        let mut state = self.lock();
+        let total = state.len();
+        let ejected_now = state.iter().filter(|s| s.is_ejected(now)).count();
+        let ep = &mut state[index];
+        ep.in_flight = ep.in_flight.saturating_sub(1);
+
+        match outcome {
+            Some(true) => {
+                ep.consecutive_failures = 0;
+                if !ep.is_ejected(now) {
+                    ep.ejection_count = 0;
+                }
+            }
+            Some(false) => {
+                ep.consecutive_failures += 1;
+                let max_ejected = total * od.max_ejection_percent as usize / 100;
+                if ep.consecutive_failures >= od.consecutive_failures
+                    && !ep.is_ejected(now)
+                    && ejected_now < max_ejected
+                {
+                    ep.ejection_count = (ep.ejection_count + 1).min(MAX_EJECTION_MULTIPLIER);
+                    let secs = u64::from(od.base_ejection_secs) * u64::from(ep.ejection_count);
+                    ep.ejected_until = Some(now + Duration::from_secs(secs));
+                    ep.consecutive_failures = 0;
+                    tracing::warn!(
+                        host = %self.endpoints[index].host,
+                        port = self.endpoints[index].port,
+                        ejection_secs = secs,
+                        "Ejecting upstream endpoint after consecutive failures"
+                    );
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Apply the result of an active health probe to endpoint `index`.
+    pub(crate) fn record_probe(&self, index: usize, success: bool) {
+        let Some(hc) = self.health_check() else {
+            return;
+        };
+        let mut state = self.lock();
+        let ep = &mut state[index];
+        if success {
+            ep.probe_failures = 0;
+            ep.probe_successes = ep.probe_successes.saturating_add(1);
+            if ep.probe_unhealthy && ep.probe_successes >= hc.healthy_threshold {
+                ep.probe_unhealthy = false;
+                tracing::info!(
+                    host = %self.endpoints[index].host,
+                    port = self.endpoints[index].port,
+                    "Upstream endpoint passed health checks, restoring"
+                );
+            }
+        } else {
+            ep.probe_successes = 0;
+            ep.probe_failures = ep.probe_failures.saturating_add(1);
+            if !ep.probe_unhealthy && ep.probe_failures >= hc.unhealthy_threshold {
+                ep.probe_unhealthy = true;
+                tracing::warn!(
+                    host = %self.endpoints[index].host,
+                    port = self.endpoints[index].port,
+                    "Upstream endpoint failed health checks, marking unhealthy"
+                );
+            }
+        }
+    }
+
+    fn status(&self) -> Vec<EndpointStatus> {
+        let now = Instant::now();
+        let state = self.lock();
+        self.endpoints
+            .iter()
+            .zip(state.iter())
+            .map(|(ep, s)| EndpointStatus {
+                host: ep.host.clone(),
+                port: ep.port,
+                healthy: !s.probe_unhealthy,
+                ejected: s.is_ejected(now),
+                ejection_remaining_secs: s
+                    .ejected_until
+                    .map_or(0, |until| until.saturating_duration_since(now).as_secs()),
+                in_flight: s.in_flight,
+                consecutive_failures: s.consecutive_failures,
+                circuit_breaker: None,
+            })
+            .collect()
+    }
+}
+
+/// A selected endpoint. Holds an in-flight slot until dropped; call
+/// [`EndpointLease::record`] once the upstream outcome is known.
+pub(crate) struct EndpointLease {
+    pool: Arc<EndpointPool>,
+    index: usize,
+    method: SelectionMethod,
+    outcome: Option<bool>,
+}
+
+impl EndpointLease {
+    pub(crate) fn endpoint(&self) -> &Endpoint {
+        &self.pool.endpoints[self.index]
+    }
+
+    pub(crate) fn method(&self) -> SelectionMethod {
+        self.method
+    }
+
+    /// Record whether the request to this endpoint succeeded. Connection
+    /// errors, timeouts, and 5xx responses count as failures.
+    pub(crate) fn record(&mut self, success: bool) {
+        self.outcome = Some(success);
+    }
+}
+
+impl Drop for EndpointLease {
+    fn drop(&mut self) {
+        self.pool.release(self.index, self.outcome);
+    }
+}
+
+/// Per-upstream endpoint pools, shared by the proxy path and the health checker.
+#[derive(Default)]
+pub(crate) struct LoadBalancer {
+    pools: DashMap<Uuid, Arc<EndpointPool>>,
+    /// Enabled upstreams with active health checks. Their pools are created
+    /// when the upstream is saved or loaded and are never evicted as idle, so
+    /// probing does not wait for the first proxied request.
+    probed: DashSet<Uuid>,
+}
+
+impl LoadBalancer {
+    #[must_use]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the pool for `upstream`, rebuilding it if the endpoint list or
+    /// load-balancing settings changed since it was created.
+    fn pool(&self, upstream: &Upstream) -> Arc<EndpointPool> {
+        let config = upstream.load_balancing.clone().unwrap_or_default();
+        if let Some(pool) = self.pools.get(&upstream.id)
+            && pool.matches(&upstream.server.endpoints, &config)
+        {
+            pool.touch();
+            return pool.clone();
+        }
+        let pool = Arc::new(EndpointPool::new(upstream.server.endpoints.clone(), config));
+        self.pools.insert(upstream.id, pool.clone());
+        pool
+    }
+
+    /// Select an endpoint using the upstream's strategy. Returns `None` if the
+    /// upstream has no endpoints.
+    pub(crate) fn select(&self, upstream: &Upstream) -> Option<EndpointLease> {
+        if upstream.server.endpoints.is_empty() {
+            return None;
+        }
+        let pool = self.pool(upstream);
+        let (index, method) = if pool.endpoints.len() == 1 {
+            pool.pin(0);
+            (0, SelectionMethod::Default)
+        } else {
+            (
+                pool.pick(Instant::now()),
+                SelectionMethod::Balanced(pool.config.strategy),
+            )
+        };
+        Some(EndpointLease {
+            pool,
+            index,
+            method,
+            outcome: None,
+        })
+    }
+
+    /// Route to a specific endpoint, bypassing load balancing.
+    pub(crate) fn pin(&self, upstream: &Upstream, index: usize) -> EndpointLease {
+        let pool = self.pool(upstream);
+        pool.pin(index);
+        EndpointLease {
+            pool,
+            index,
+            method: SelectionMethod::ExplicitHeader,
+            outcome: None,
+        }
+    }
+
+    /// Runtime status of the upstream's endpoints. Upstreams that have not
+    /// served traffic yet report every endpoint as healthy.
+    pub(crate) fn status(&self, upstream: &Upstream) -> UpstreamStatus {
+        let config = upstream.load_balancing.clone().unwrap_or_default();
+        let endpoints = match self.pools.get(&upstream.id) {
+            Some(pool) if pool.matches(&upstream.server.endpoints, &config) => pool.status(),
+            _ => EndpointPool::new(upstream.server.endpoints.clone(), config).status(),
+        };
+        UpstreamStatus {
+            upstream_id: upstream.id,
+            endpoints,
+            circuit_breaker: None,
+        }
+    }
+
+    /// Snapshot of all pools (for the health checker).
+    pub(crate) fn pools(&self) -> Vec<Arc<EndpointPool>> {
+        self.pools.iter().map(|e| e.value().clone()).collect()
+    }
+
+    /// Drop pools that have not been used for `idle`, except those of probed upstreams.
+    pub(crate) fn evict_idle(&self, idle: Duration) {
+        let now = Instant::now();
+        self.pools
+            .retain(|id, pool| self.probed.contains(id) || pool.idle_for(now) < idle);
+    }
+}
+
+impl UpstreamObserver for LoadBalancer {
+    fn upstream_saved(&self, upstream: &Upstream) {
+        let probed = upstream.enabled
+            && !upstream.server.endpoints.is_empty()
+            && upstream
+                .load_balancing
+                .as_ref()
+                .is_some_and(|lb| lb.health_check.is_some());
+        if probed {
+            self.probed.insert(upstream.id);
+            self.pool(upstream);
+        } else {
+            self.probed.remove(&upstream.id);
+        }
+    }
+
+    fn upstream_deleted(&self, _tenant_id: Uuid, upstream_id: Uuid) {
+        self.probed.remove(&upstream_id);
+        self.pools.remove(&upstream_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::{Scheme, Server};
+
+    fn upstream(hosts: &[(&str, u32)], config: Option<LoadBalancingConfig>) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            alias: "pool".into(),
+            server: Server {
+                endpoints: hosts
+                    .iter()
+                    .map(|(host, weight)| Endpoint {
+                        scheme: Scheme::Https,
+                        host: (*host).into(),
+                        port: 443,
+                        weight: *weight,
+                    })
+                    .collect(),
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            load_balancing: config,
+            circuit_breaker: None,
+            tags: vec![],
+        }
+    }
+
+    fn strategy(strategy: LoadBalancingStrategy) -> Option<LoadBalancingConfig> {
+        Some(LoadBalancingConfig {
+            strategy,
+            ..Default::default()
+        })
+    }
+
+    fn pick_host(lb: &LoadBalancer, u: &Upstream) -> String {
+        lb.select(u).unwrap().endpoint().host.clone()
+    }
+
+    #[test]
+    fn round_robin_cycles_endpoints() {
+        let lb = LoadBalancer::new();
+        let u = upstream(&[("a", 1), ("b", 1), ("c", 1)], None);
+        let picks: Vec<String> = (0..6).map(|_| pick_host(&lb, &u)).collect();
+        assert_eq!(picks, ["a", "b", "c", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn least_in_flight_avoids_busy_endpoint() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            &[("a", 1), ("b", 1)],
+            strategy(LoadBalancingStrategy::LeastInFlight),
+        );
+        let held = lb.select(&u).unwrap();
+        let busy = held.endpoint().host.clone();
+        for _ in 0..4 {
+            assert_ne!(pick_host(&lb, &u), busy);
+        }
+    }
+
+    #[test]
+    fn weighted_distributes_by_weight() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            &[("a", 3), ("b", 1)],
+            strategy(LoadBalancingStrategy::Weighted),
+        );
+        let picks: Vec<String> = (0..8).map(|_| pick_host(&lb, &u)).collect();
+        assert_eq!(picks.iter().filter(|h| *h == "a").count(), 6);
+        assert_eq!(picks.iter().filter(|h| *h == "b").count(), 2);
+    }
+
+    #[test]
+    fn consecutive_failures_eject_endpoint() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            &[("a", 1), ("b", 1)],
+            Some(LoadBalancingConfig {
+                outlier_detection: Some(OutlierDetectionConfig {
+                    consecutive_failures: 2,
+                    base_ejection_secs: 60,
+                    max_ejection_percent: 50,
+                }),
+                ..Default::default()
+            }),
+        );
+        for _ in 0..2 {
+            let mut lease = lb.pin(&u, 0);
+            lease.record(false);
+        }
+
+        let status = lb.status(&u);
+        assert!(status.endpoints[0].ejected);
+        assert!(status.endpoints[0].ejection_remaining_secs > 0);
+        assert!(!status.endpoints[1].ejected);
+        for _ in 0..4 {
+            assert_eq!(pick_host(&lb, &u), "b");
+        }
+    }
+
+    #[test]
+    fn max_ejection_percent_keeps_pool_serving() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            &[("a", 1), ("b", 1)],
+            Some(LoadBalancingConfig {
+                outlier_detection: Some(OutlierDetectionConfig {
+                    consecutive_failures: 1,
+                    base_ejection_secs: 60,
+                    max_ejection_percent: 50,
+                }),
+                ..Default::default()
+            }),
+        );
+        lb.pin(&u, 0).record(false);
+        lb.pin(&u, 1).record(false);
+
+        let status = lb.status(&u);
+        let ejected = status.endpoints.iter().filter(|e| e.ejected).count();
+        assert_eq!(ejected, 1);
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let lb = LoadBalancer::new();
+        let u = upstream(&[("a", 1), ("b", 1)], None);
+        lb.pin(&u, 0).record(false);
+        lb.pin(&u, 0).record(true);
+        assert_eq!(lb.status(&u).endpoints[0].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn probes_mark_unhealthy_and_restore() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            &[("a", 1), ("b", 1)],
+            Some(LoadBalancingConfig {
+                health_check: Some(HealthCheckConfig {
+                    path: "/health".into(),
+                    interval_secs: 1,
+                    timeout_secs: 1,
+                    healthy_threshold: 2,
+                    unhealthy_threshold: 2,
+                }),
+                ..Default::default()
+            }),
+        );
+        let pool = lb.pool(&u);
+        pool.record_probe(0, false);
+        assert!(lb.status(&u).endpoints[0].healthy);
+        pool.record_probe(0, false);
+        assert!(!lb.status(&u).endpoints[0].healthy);
+        for _ in 0..4 {
+            assert_eq!(pick_host(&lb, &u), "b");
+        }
+
+        pool.record_probe(0, true);
+        pool.record_probe(0, true);
+        assert!(lb.status(&u).endpoints[0].healthy);
+    }
+
+    #[test]
+    fn all_unavailable_falls_back_to_full_pool() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            &[("a", 1), ("b", 1)],
+            Some(LoadBalancingConfig {
+                health_check: Some(HealthCheckConfig {
+                    path: "/health".into(),
+                    interval_secs: 1,
+                    timeout_secs: 1,
+                    healthy_threshold: 1,
+                    unhealthy_threshold: 1,
+                }),
+                ..Default::default()
+            }),
+        );
+        let pool = lb.pool(&u);
+        pool.record_probe(0, false);
+        pool.record_probe(1, false);
+        let picks: Vec<String> = (0..2).map(|_| pick_host(&lb, &u)).collect();
+        assert_eq!(picks, ["a", "b"]);
+    }
+
+    #[test]
+    fn saved_upstreams_with_health_checks_are_probed_without_traffic() {
+        let lb = LoadBalancer::new();
+        let checked = upstream(
+            &[("a", 1)],
+            Some(LoadBalancingConfig {
+                health_check: Some(HealthCheckConfig {
+                    path: "/health".into(),
+                    interval_secs: 1,
+                    timeout_secs: 1,
+                    healthy_threshold: 1,
+                    unhealthy_threshold: 1,
+                }),
+                ..Default::default()
+            }),
+        );
+        let unchecked = upstream(&[("b", 1)], None);
+
+        lb.upstream_saved(&checked);
+        lb.upstream_saved(&unchecked);
+        let pools = lb.pools();
+        assert_eq!(pools.len(), 1);
+        assert!(pools[0].probe_due(Instant::now()));
+
+        // Idle pools of probed upstreams are kept until the upstream goes away.
+        lb.evict_idle(Duration::ZERO);
+        assert_eq!(lb.pools().len(), 1);
+        lb.upstream_deleted(checked.tenant_id, checked.id);
+        assert!(lb.pools().is_empty());
+    }
+
+    #[test]
+    fn config_change_rebuilds_pool() {
+        let lb = LoadBalancer::new();
+        let mut u = upstream(&[("a", 1), ("b", 1)], None);
+        lb.pin(&u, 0).record(false);
+        assert_eq!(lb.status(&u).endpoints[0].consecutive_failures, 1);
+
+        u.server.endpoints.pop();
+        let status = lb.status(&u);
+        assert_eq!(status.endpoints.len(), 1);
+        assert_eq!(status.endpoints[0].consecutive_failures, 0);
+    }
+}
@@ -1,5 +1,11 @@
+pub(crate) mod cache;
+pub(crate) mod grpc;
 pub(crate) mod headers;
+pub(crate) mod health;
+pub(crate) mod load_balancer;
 pub(crate) mod request_builder;
 pub(crate) mod service;
+pub(crate) mod websocket;
 
+pub(crate) use cache::ResponseCache;
 pub(crate) use service::DataPlaneServiceImpl;
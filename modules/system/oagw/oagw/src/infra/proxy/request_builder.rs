@@ -1,38 +1,79 @@
-use crate::domain::error::DomainError;
 use crate::domain::model::{Endpoint, Scheme};
 
 /// Build the full upstream URL from endpoint, route path, path suffix, and query params.
 ///
-/// # Errors
-///
-/// Returns `DomainError::Validation` if the endpoint uses an unsupported scheme (e.g. gRPC).
+/// gRPC endpoints are dialled over TLS on port 443 and over cleartext
+/// HTTP/2 (h2c) on any other port.
+#[must_use]
 pub fn build_upstream_url(
     endpoint: &Endpoint,
     route_path: &str,
     path_suffix: &str,
     query_params: &[(String, String)],
-) -> Result<String, DomainError> {
+) -> String {
     let scheme = match endpoint.scheme {
         Scheme::Http => "http",
         Scheme::Https => "https",
         Scheme::Wss => "wss",
         Scheme::Wt => "https",
-        Scheme::Grpc => {
-            return Err(DomainError::Validation {
-                detail: "gRPC scheme is not supported for HTTP proxy".into(),
-                instance: String::new(),
-            });
-        }
+        Scheme::Grpc if endpoint.port == 443 => "https",
+        Scheme::Grpc => "http",
+    };
+    format_url(scheme, endpoint, route_path, path_suffix, query_params)
+}
+
+/// Build the upstream URL for a WebSocket handshake: `ws` for plain HTTP
+/// endpoints, `wss` for TLS ones. `None` for schemes that cannot carry
+/// WebSocket traffic (gRPC, WebTransport).
+#[must_use]
+pub fn build_websocket_url(
+    endpoint: &Endpoint,
+    route_path: &str,
+    path_suffix: &str,
+    query_params: &[(String, String)],
+) -> Option<String> {
+    let scheme = match endpoint.scheme {
+        Scheme::Http => "ws",
+        Scheme::Https | Scheme::Wss => "wss",
+        Scheme::Wt | Scheme::Grpc => return None,
     };
+    Some(format_url(
+        scheme,
+        endpoint,
+        route_path,
+        path_suffix,
+        query_params,
+    ))
+}
 
+fn format_url(
+    scheme: &str,
+    endpoint: &Endpoint,
+    route_path: &str,
+    path_suffix: &str,
+    query_params: &[(String, String)],
+) -> String {
     let host_port = if is_default_port(scheme, endpoint.port) {
         endpoint.host.clone()
     } else {
         format!("{}:{}", endpoint.host, endpoint.port)
     };
 
+    format!(
+        "{scheme}://{host_port}{}",
+        upstream_path_and_query(route_path, path_suffix, query_params)
+    )
+}
+
+/// Build the upstream path and query string (everything after the authority).
+#[must_use]
+pub fn upstream_path_and_query(
+    route_path: &str,
+    path_suffix: &str,
+    query_params: &[(String, String)],
+) -> String {
     // Combine route path + path suffix, avoiding double slashes.
-    let path = if path_suffix.is_empty() {
+    let mut path = if path_suffix.is_empty() {
         route_path.to_string()
     } else if route_path.ends_with('/') && path_suffix.starts_with('/') {
         format!("{}{}", route_path, &path_suffix[1..])
@@ -42,17 +83,15 @@ pub fn build_upstream_url(
         format!("{route_path}{path_suffix}")
     };
 
-    let mut url = format!("{scheme}://{host_port}{path}");
-
     if !query_params.is_empty() {
-        url.push('?');
+        path.push('?');
         let qs = form_urlencoded::Serializer::new(String::new())
             .extend_pairs(query_params)
             .finish();
-        url.push_str(&qs);
+        path.push_str(&qs);
     }
 
-    Ok(url)
+    path
 }
 
 fn is_default_port(scheme: &str, port: u16) -> bool {
@@ -68,6 +107,7 @@ mod tests {
             scheme: Scheme::Https,
             host: host.into(),
             port,
+            weight: 1,
         }
     }
 
@@ -78,8 +118,7 @@ mod tests {
             "/v1/chat",
             "/completions",
             &[],
-        )
-        .unwrap();
+        );
         assert_eq!(url, "https://api.openai.com/v1/chat/completions");
     }
 
@@ -90,28 +129,25 @@ mod tests {
             "/v1/chat",
             "/models/gpt-4",
             &[("version".into(), "2".into())],
-        )
-        .unwrap();
+        );
         assert_eq!(url, "https://api.openai.com/v1/chat/models/gpt-4?version=2");
     }
 
     #[test]
     fn nonstandard_port() {
-        let url = build_upstream_url(&endpoint("localhost", 8080), "/api", "", &[]).unwrap();
+        let url = build_upstream_url(&endpoint("localhost", 8080), "/api", "", &[]);
         assert_eq!(url, "https://localhost:8080/api");
     }
 
     #[test]
     fn empty_suffix() {
-        let url =
-            build_upstream_url(&endpoint("api.openai.com", 443), "/v1/models", "", &[]).unwrap();
+        let url = build_upstream_url(&endpoint("api.openai.com", 443), "/v1/models", "", &[]);
         assert_eq!(url, "https://api.openai.com/v1/models");
     }
 
     #[test]
     fn avoids_double_slash() {
-        let url =
-            build_upstream_url(&endpoint("api.openai.com", 443), "/v1/", "/chat", &[]).unwrap();
+        let url = build_upstream_url(&endpoint("api.openai.com", 443), "/v1/", "/chat", &[]);
         assert_eq!(url, "https://api.openai.com/v1/chat");
     }
 
@@ -122,8 +158,7 @@ mod tests {
             "/api",
             "/data",
             &[("key".into(), "val".into()), ("foo".into(), "bar".into())],
-        )
-        .unwrap();
+        );
         assert_eq!(url, "https://example.com/api/data?key=val&foo=bar");
     }
 
@@ -133,8 +168,9 @@ mod tests {
             scheme: Scheme::Http,
             host: "127.0.0.1".into(),
             port: 3000,
+            weight: 1,
         };
-        let url = build_upstream_url(&ep, "/v1/test", "", &[]).unwrap();
+        let url = build_upstream_url(&ep, "/v1/test", "", &[]);
         assert_eq!(url, "http://127.0.0.1:3000/v1/test");
     }
 
@@ -144,8 +180,9 @@ mod tests {
             scheme: Scheme::Http,
             host: "example.com".into(),
             port: 80,
+            weight: 1,
         };
-        let url = build_upstream_url(&ep, "/api", "", &[]).unwrap();
+        let url = build_upstream_url(&ep, "/api", "", &[]);
         assert_eq!(url, "http://example.com/api");
     }
 
@@ -156,19 +193,55 @@ mod tests {
             "/v1/search",
             "",
             &[("q".into(), "a&b".into())],
+        );
+        assert_eq!(url, "https://api.openai.com/v1/search?q=a%26b");
+    }
+
+    #[test]
+    fn grpc_scheme_uses_tls_on_443_only() {
+        let mut ep = Endpoint {
+            scheme: Scheme::Grpc,
+            host: "grpc.example.com".into(),
+            port: 443,
+            weight: 1,
+        };
+        let url = build_upstream_url(&ep, "/pkg.Svc/Call", "", &[]);
+        assert_eq!(url, "https://grpc.example.com/pkg.Svc/Call");
+
+        ep.port = 50051;
+        let url = build_upstream_url(&ep, "/pkg.Svc/Call", "", &[]);
+        assert_eq!(url, "http://grpc.example.com:50051/pkg.Svc/Call");
+    }
+
+    #[test]
+    fn websocket_url_follows_endpoint_tls() {
+        let url = build_websocket_url(
+            &endpoint("api.example.com", 443),
+            "/v1/realtime",
+            "",
+            &[("model".into(), "rt".into())],
         )
         .unwrap();
-        assert_eq!(url, "https://api.openai.com/v1/search?q=a%26b");
+        assert_eq!(url, "wss://api.example.com/v1/realtime?model=rt");
+
+        let ep = Endpoint {
+            scheme: Scheme::Http,
+            host: "127.0.0.1".into(),
+            port: 3000,
+            weight: 1,
+        };
+        let url = build_websocket_url(&ep, "/ws", "/echo", &[]).unwrap();
+        assert_eq!(url, "ws://127.0.0.1:3000/ws/echo");
     }
 
     #[test]
-    fn grpc_scheme_returns_error() {
+    fn websocket_url_rejects_grpc_endpoint() {
         let ep = Endpoint {
             scheme: Scheme::Grpc,
             host: "grpc.example.com".into(),
             port: 443,
+            weight: 1,
         };
-        let err = build_upstream_url(&ep, "/service", "", &[]).unwrap_err();
-        assert!(matches!(err, DomainError::Validation { .. }));
+        assert!(build_websocket_url(&ep, "/ws", "", &[]).is_none());
     }
 }
@@ -1,34 +1,63 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::domain::circuit_breaker::{CallOutcome, CircuitBreaker, CircuitPermit};
 use crate::domain::credential::CredentialResolver;
 use crate::domain::error::DomainError;
-use crate::domain::model::{PassthroughMode, PathSuffixMode};
-use crate::domain::plugin::AuthContext;
+use crate::domain::gts_helpers::GUARD_PLUGIN_SCHEMA;
+use crate::domain::model::{
+    CacheConfig, CircuitBreakerScope, Endpoint, FallbackResponse, PassthroughMode, PathSuffixMode,
+    Upstream, UpstreamStatus,
+};
+use crate::domain::plugin::{AuthContext, GuardContext, GuardDecision, PluginError};
+use bytes::{Bytes, BytesMut};
 use futures_util::StreamExt;
-use http::{HeaderMap, HeaderName, HeaderValue};
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
 use modkit_security::SecurityContext;
-use oagw_sdk::api::ErrorSource;
+use oagw_sdk::api::{ClientIp, ErrorSource};
 use oagw_sdk::body::{Body, BodyStream, BoxError};
+use uuid::Uuid;
 
 use crate::domain::services::{ControlPlaneService, DataPlaneService};
 
-use crate::domain::rate_limit::RateLimiter;
-use crate::infra::plugin::AuthPluginRegistry;
+use crate::domain::rate_limit::{
+    Admission, RateLimitQuota, RateLimiter, aggregate_bucket_key, bucket_key,
+};
+use crate::infra::plugin::{AuthPluginRegistry, GuardPluginRegistry};
 
-use super::headers;
-use super::request_builder;
+use super::cache::{
+    self, CACHE_STATUS_HEADER, CacheStatus, CachedResponse, RequestDirective, ResourceKey,
+    ResponseCache,
+};
+use super::health::HealthChecker;
+use super::load_balancer::{EndpointLease, LoadBalancer, SelectionMethod};
+use super::websocket::{self, Handshake};
+use super::{grpc, headers, request_builder};
 
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const TARGET_HOST_HEADER: &str = "x-oagw-target-host";
+
+/// Wire protocol of an inbound proxy request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Http,
+    Grpc,
+    WebSocket,
+}
 
 /// Data Plane service implementation: proxy orchestration and plugin execution.
 pub struct DataPlaneServiceImpl {
     cp: Arc<dyn ControlPlaneService>,
     http_client: reqwest::Client,
+    grpc_client: reqwest::Client,
     auth_registry: AuthPluginRegistry,
+    guard_registry: GuardPluginRegistry,
     rate_limiter: RateLimiter,
+    load_balancer: Arc<LoadBalancer>,
+    circuit_breaker: CircuitBreaker,
+    response_cache: Option<Arc<ResponseCache>>,
     request_timeout: Duration,
 }
 
@@ -47,6 +76,13 @@ impl DataPlaneServiceImpl {
             // No overall timeout — SSE streams run indefinitely.
             // Request-header timeout is applied via tokio::time::timeout below.
             .build()?;
+        // gRPC needs HTTP/2 end to end: prior knowledge over cleartext,
+        // ALPN `h2` over TLS.
+        let grpc_client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .http2_prior_knowledge()
+            .build()?;
 
         let auth_registry = AuthPluginRegistry::with_builtins(credential_resolver);
         let rate_limiter = RateLimiter::new();
@@ -54,18 +90,211 @@ impl DataPlaneServiceImpl {
         Ok(Self {
             cp,
             http_client,
+            grpc_client,
             auth_registry,
+            guard_registry: GuardPluginRegistry::with_builtins(),
             rate_limiter,
+            load_balancer: Arc::new(LoadBalancer::new()),
+            circuit_breaker: CircuitBreaker::new(),
+            response_cache: None,
             request_timeout: REQUEST_TIMEOUT,
         })
     }
 
+    /// Share endpoint pools with the Control Plane's upstream observer.
+    #[must_use]
+    pub(crate) fn with_load_balancer(mut self, load_balancer: Arc<LoadBalancer>) -> Self {
+        self.load_balancer = load_balancer;
+        self
+    }
+
+    /// Active health checker sharing this service's endpoint pools.
+    pub(crate) fn health_checker(&self) -> HealthChecker {
+        HealthChecker::new(self.load_balancer.clone(), self.http_client.clone())
+    }
+
+    /// Pick the endpoint for a request, honouring `X-OAGW-Target-Host`.
+    fn select_endpoint(
+        &self,
+        upstream: &Upstream,
+        alias: &str,
+        req_headers: &HeaderMap,
+        instance: &str,
+    ) -> Result<EndpointLease, DomainError> {
+        let endpoints = &upstream.server.endpoints;
+        let valid_hosts = || {
+            endpoints
+                .iter()
+                .map(|e| e.host.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let Some(target) = req_headers.get(TARGET_HOST_HEADER) else {
+            let common_suffix = format!(".{alias}");
+            if endpoints.len() > 1
+                && endpoints
+                    .iter()
+                    .any(|e| e.host != alias && e.host.ends_with(&common_suffix))
+            {
+                return Err(DomainError::MissingTargetHost {
+                    instance: instance.to_string(),
+                });
+            }
+            return self.load_balancer.select(upstream).ok_or_else(|| {
+                DomainError::DownstreamError {
+                    detail: "upstream has no endpoints".into(),
+                    instance: instance.to_string(),
+                }
+            });
+        };
+
+        let target = target
+            .to_str()
+            .ok()
+            .filter(|t| is_valid_target_host(t))
+            .ok_or_else(|| DomainError::InvalidTargetHost {
+                instance: instance.to_string(),
+            })?;
+        let index = endpoints
+            .iter()
+            .position(|e| e.host.eq_ignore_ascii_case(target))
+            .ok_or_else(|| DomainError::UnknownTargetHost {
+                detail: format!(
+                    "X-OAGW-Target-Host '{target}' does not match any configured endpoint. Valid hosts: [{}]",
+                    valid_hosts()
+                ),
+                instance: instance.to_string(),
+            })?;
+        Ok(self.load_balancer.pin(upstream, index))
+    }
+
+    /// Pick the endpoint for a request and pass the upstream's circuit
+    /// breaker. With per-endpoint scope, endpoints whose circuit is open are
+    /// skipped unless the request pins one via `X-OAGW-Target-Host`.
+    fn admit(
+        &self,
+        upstream: &Upstream,
+        alias: &str,
+        req_headers: &HeaderMap,
+        instance: &str,
+    ) -> Result<(EndpointLease, Option<CircuitPermit>), DomainError> {
+        let Some(cb) = upstream.circuit_breaker.as_ref().filter(|cb| cb.enabled) else {
+            let lease = self.select_endpoint(upstream, alias, req_headers, instance)?;
+            return Ok((lease, None));
+        };
+
+        if cb.scope == CircuitBreakerScope::Global {
+            let lease = self.select_endpoint(upstream, alias, req_headers, instance)?;
+            let permit =
+                self.circuit_breaker
+                    .try_acquire(&upstream_circuit_key(upstream), cb, instance)?;
+            return Ok((lease, Some(permit)));
+        }
+
+        // Rejected leases stay in flight until we return so the balancer
+        // moves on to a different endpoint.
+        let mut skipped = Vec::new();
+        loop {
+            let lease = self.select_endpoint(upstream, alias, req_headers, instance)?;
+            let key = endpoint_circuit_key(upstream, lease.endpoint());
+            match self.circuit_breaker.try_acquire(&key, cb, instance) {
+                Ok(permit) => return Ok((lease, Some(permit))),
+                Err(e)
+                    if lease.method() == SelectionMethod::ExplicitHeader
+                        || skipped.len() + 1 >= upstream.server.endpoints.len() =>
+                {
+                    return Err(e);
+                }
+                Err(_) => skipped.push(lease),
+            }
+        }
+    }
+
+    /// Perform the upstream WebSocket handshake and, once upgraded, bridge
+    /// the session. A handshake the upstream refuses is passed through as an
+    /// ordinary upstream response.
+    async fn forward_websocket(
+        &self,
+        url: &str,
+        outbound_headers: &HeaderMap,
+        client_messages: BodyStream,
+        mut lease: EndpointLease,
+        mut permit: Option<CircuitPermit>,
+        instance_uri: &str,
+    ) -> Result<(StatusCode, HeaderMap, Body), DomainError> {
+        let timeout = self.request_timeout;
+        let handshake = tokio::time::timeout(timeout, websocket::connect(url, outbound_headers))
+            .await
+            .map_err(|_| {
+                lease.record(false);
+                if let Some(p) = permit.as_mut() {
+                    p.record(CallOutcome::Timeout);
+                }
+                DomainError::RequestTimeout {
+                    detail: format!("WebSocket handshake with {url} timed out after {timeout:?}"),
+                    instance: instance_uri.to_string(),
+                }
+            })?
+            .map_err(|e| {
+                lease.record(false);
+                if let Some(p) = permit.as_mut() {
+                    p.record(CallOutcome::ConnectionError);
+                }
+                match e {
+                    tokio_tungstenite::tungstenite::Error::Io(_)
+                    | tokio_tungstenite::tungstenite::Error::Tls(_) => {
+                        DomainError::ConnectionTimeout {
+                            detail: e.to_string(),
+                            instance: instance_uri.to_string(),
+                        }
+                    }
+                    _ => DomainError::DownstreamError {
+                        detail: e.to_string(),
+                        instance: instance_uri.to_string(),
+                    },
+                }
+            })?;
+
+        let (status, mut resp_headers, body) = match handshake {
+            Handshake::Upgraded(resp_headers, socket) => {
+                lease.record(true);
+                if let Some(p) = permit.as_mut() {
+                    p.record(CallOutcome::Status(
+                        StatusCode::SWITCHING_PROTOCOLS.as_u16(),
+                    ));
+                }
+                // The lease rides along with the session.
+                let body = websocket::bridge(socket, client_messages, lease);
+                (StatusCode::SWITCHING_PROTOCOLS, resp_headers, body)
+            }
+            Handshake::Rejected(response) => {
+                let (parts, body) = response.into_parts();
+                lease.record(!parts.status.is_server_error());
+                if let Some(p) = permit.as_mut() {
+                    p.record(CallOutcome::Status(parts.status.as_u16()));
+                }
+                let body = body.map_or(Body::Empty, |b| Body::Bytes(b.into()));
+                (parts.status, parts.headers, body)
+            }
+        };
+        headers::sanitize_response_headers(&mut resp_headers);
+        Ok((status, resp_headers, body))
+    }
+
     /// Override the request timeout.
     #[must_use]
     pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
         self.request_timeout = timeout;
         self
     }
+
+    /// Serve routes with caching enabled from `cache`.
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -88,6 +317,8 @@ impl DataPlaneService for DataPlaneServiceImpl {
             }
         };
 
+        let raw_query = req.uri().query().map(str::to_owned);
+
         // Parse query parameters with proper URL decoding.
         let query_params: Vec<(String, String)> = req
             .uri()
@@ -102,15 +333,39 @@ impl DataPlaneService for DataPlaneServiceImpl {
         let (parts, body) = req.into_parts();
         let method = parts.method;
         let req_headers = parts.headers;
+        let client_ip = parts.extensions.get::<ClientIp>().map(|ip| ip.0);
+        let protocol = if websocket::is_upgrade_request(&method, &req_headers) {
+            Protocol::WebSocket
+        } else if grpc::is_grpc_request(&req_headers) {
+            Protocol::Grpc
+        } else {
+            Protocol::Http
+        };
 
-        // Convert Body to Bytes for the outbound HTTP request.
-        let body_bytes = body
-            .into_bytes()
-            .await
-            .map_err(|e| DomainError::Validation {
-                detail: format!("failed to read request body: {e}"),
-                instance: instance_uri.clone(),
-            })?;
+        // A WebSocket request body carries the client's messages for the whole
+        // session and a gRPC body the messages of a (client-)streaming call;
+        // both are forwarded as they arrive. Any other body is buffered for
+        // the outbound request.
+        let (body_bytes, body_stream) = match protocol {
+            Protocol::WebSocket | Protocol::Grpc => (Bytes::new(), Some(body.into_stream())),
+            Protocol::Http => {
+                let bytes = body
+                    .into_bytes()
+                    .await
+                    .map_err(|e| DomainError::Validation {
+                        detail: format!("failed to read request body: {e}"),
+                        instance: instance_uri.clone(),
+                    })?;
+                (bytes, None)
+            }
+        };
+
+        // A CORS preflight is routed by the method it asks about.
+        let preflight_method = (method == http::Method::OPTIONS)
+            .then(|| req_headers.get(http::header::ACCESS_CONTROL_REQUEST_METHOD))
+            .flatten()
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_uppercase);
 
         // 1. Resolve upstream by alias.
         let upstream = self.cp.resolve_upstream(&ctx, &alias).await?;
@@ -118,7 +373,12 @@ impl DataPlaneService for DataPlaneServiceImpl {
         // 2. Resolve route.
         let route = self
             .cp
-            .resolve_route(&ctx, upstream.id, method.as_ref(), &path_suffix)
+            .resolve_route(
+                &ctx,
+                upstream.id,
+                preflight_method.as_deref().unwrap_or(method.as_ref()),
+                &path_suffix,
+            )
             .await?;
 
         // 2b. Validate query parameters against route's allowlist.
@@ -155,6 +415,131 @@ impl DataPlaneService for DataPlaneServiceImpl {
             }
         }
 
+        // 2d. Execute guard plugins. Guards run before auth so preflights are
+        // answered without resolving credentials or consuming rate limits.
+        let plugins = self.cp.resolve_plugins(&ctx, &upstream, &route).await?;
+        let mut guard_headers = Vec::new();
+        let guard_bindings = plugins
+            .iter()
+            .filter(|b| b.plugin_ref.starts_with(GUARD_PLUGIN_SCHEMA));
+        for binding in guard_bindings {
+            let plugin = self
+                .guard_registry
+                .resolve(&binding.plugin_ref)
+                .map_err(|e| DomainError::Internal {
+                    message: e.to_string(),
+                })?;
+            let guard_ctx = GuardContext {
+                method: method.to_string(),
+                headers: req_headers
+                    .iter()
+                    .filter_map(|(k, v)| {
+                        v.to_str()
+                            .ok()
+                            .map(|s| (k.as_str().to_string(), s.to_string()))
+                    })
+                    .collect(),
+                config: binding.config.clone(),
+            };
+            match plugin
+                .guard_request(&guard_ctx)
+                .await
+                .map_err(|e| guard_error(e, &instance_uri))?
+            {
+                GuardDecision::Allow { response_headers } => {
+                    guard_headers.extend(response_headers);
+                }
+                GuardDecision::Respond { status, headers } => {
+                    return guard_response(status, &headers, instance_uri);
+                }
+            }
+        }
+        if preflight_method.is_some() {
+            return Err(DomainError::GuardRejected {
+                detail: "CORS is not enabled for this route".into(),
+                instance: instance_uri,
+            });
+        }
+
+        // 2e. Check rate limits: the upstream's aggregate bucket, then the
+        // route's. Done before the cache so hits count against the limits,
+        // before auth so signatures and tokens are fresh when a queued
+        // request is sent, and before endpoint selection so queued requests
+        // hold no lease or half-open slot.
+        let mut quota: Option<RateLimitQuota> = None;
+        let limits = self.cp.resolve_rate_limit(&ctx, &upstream, &route).await?;
+        let buckets = [
+            limits
+                .upstream
+                .map(|rl| (aggregate_bucket_key(&rl, &ctx, upstream.id, client_ip), rl)),
+            limits
+                .route
+                .map(|rl| (bucket_key(&rl, &ctx, upstream.id, route.id, client_ip), rl)),
+        ];
+        for (key, rl) in buckets.into_iter().flatten() {
+            match self.rate_limiter.acquire(&key, &rl, &instance_uri).await? {
+                // Report whichever bucket runs out first.
+                Admission::Admitted(q) if rl.response_headers => {
+                    if quota.is_none_or(|current| q.remaining < current.remaining) {
+                        quota = Some(q);
+                    }
+                }
+                Admission::Admitted(_) => {}
+                Admission::Degraded(fallback) => {
+                    let mut resp = degraded_response(&fallback, instance_uri)?;
+                    headers::apply_guard_headers(resp.headers_mut(), &guard_headers);
+                    return Ok(resp);
+                }
+            }
+        }
+
+        // 2f. Serve from the response cache. Hits skip auth and the upstream
+        // entirely.
+        let mut cache_lookup = None;
+        if let Some(cache) = self.response_cache.as_ref()
+            && let Some(config) = route.cache.as_ref().filter(|c| c.enabled)
+            && method == http::Method::GET
+            && protocol == Protocol::Http
+        {
+            let directive = cache::request_directive(&req_headers);
+            if directive != RequestDirective::Bypass {
+                let resource = ResourceKey {
+                    tenant_id: ctx.subject_tenant_id(),
+                    subject_id: ctx.subject_id(),
+                    route_id: route.id,
+                    path_and_query: match raw_query {
+                        Some(ref q) => format!("{path_suffix}?{q}"),
+                        None => path_suffix.clone(),
+                    },
+                };
+                let cached = cache.get(&resource, &req_headers);
+                let now = Instant::now();
+                if let Some(ref entry) = cached
+                    && directive == RequestDirective::Use
+                    && entry.is_fresh(now)
+                {
+                    let (status, mut resp_headers, body) =
+                        cached_parts(entry, &req_headers, now, CacheStatus::Hit);
+                    if let Some(ref quota) = quota {
+                        headers::set_rate_limit_headers(
+                            &mut resp_headers,
+                            quota,
+                            SystemTime::now(),
+                        );
+                    }
+                    headers::apply_guard_headers(&mut resp_headers, &guard_headers);
+                    return build_response(status, resp_headers, body, instance_uri);
+                }
+                cache_lookup = Some(CacheLookup {
+                    cache: cache.clone(),
+                    resource,
+                    upstream_id: upstream.id,
+                    config: config.clone(),
+                    stale: cached.filter(|e| e.etag().is_some()),
+                });
+            }
+        }
+
         // 3. Prepare outbound headers (passthrough + strip).
         let mode = upstream
             .headers
@@ -169,8 +554,27 @@ impl DataPlaneService for DataPlaneServiceImpl {
         let mut outbound_headers = headers::apply_passthrough(&req_headers, &mode, &allowlist);
         headers::strip_hop_by_hop(&mut outbound_headers);
         headers::strip_internal_headers(&mut outbound_headers);
+        match protocol {
+            Protocol::Grpc => grpc::prepare_outbound_headers(&req_headers, &mut outbound_headers),
+            Protocol::WebSocket => {
+                websocket::prepare_outbound_headers(&req_headers, &mut outbound_headers);
+            }
+            Protocol::Http => {}
+        }
+
+        // path_suffix is the full path from the proxy URL; strip the route prefix
+        // so we get: endpoint + route_path + remaining_suffix.
+        let route_path = match (&route.match_rules.http, &route.match_rules.grpc) {
+            (Some(http_match), _) => http_match.path.clone(),
+            (None, Some(grpc_match)) => grpc_match.path(),
+            (None, None) => "/".to_owned(),
+        };
+        let route_path = route_path.as_str();
+        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
 
-        // 4. Execute auth plugin.
+        // 4. Execute auth plugin. Runs after admission, right before the
+        // request is sent.
+        let mut auth_plugin = None;
         if let Some(ref auth) = upstream.auth {
             let plugin = self.auth_registry.resolve(&auth.plugin_type).map_err(|e| {
                 DomainError::AuthenticationFailed {
@@ -189,6 +593,13 @@ impl DataPlaneService for DataPlaneServiceImpl {
             let mut auth_ctx = AuthContext {
                 headers: auth_headers,
                 config: auth.config.clone().unwrap_or_default(),
+                method: method.to_string(),
+                path: request_builder::upstream_path_and_query(
+                    route_path,
+                    remaining_suffix,
+                    &query_params,
+                ),
+                body: body_bytes.clone(),
             };
             plugin
                 .authenticate(&mut auth_ctx)
@@ -223,6 +634,7 @@ impl DataPlaneService for DataPlaneServiceImpl {
                     outbound_headers.insert(name, val);
                 }
             }
+            auth_plugin = Some((plugin, auth_ctx.config));
         }
 
         // 5. Apply header rules + set Host.
@@ -231,98 +643,405 @@ impl DataPlaneService for DataPlaneServiceImpl {
         {
             headers::apply_header_rules(&mut outbound_headers, rules);
         }
-        let endpoint =
-            upstream
-                .server
-                .endpoints
-                .first()
-                .ok_or_else(|| DomainError::DownstreamError {
-                    detail: "upstream has no endpoints".into(),
-                    instance: instance_uri.clone(),
-                })?;
+        if let Some(ref lookup) = cache_lookup {
+            // The cache answers the client's conditionals itself and only
+            // sends its own validator upstream.
+            outbound_headers.remove(http::header::IF_NONE_MATCH);
+            outbound_headers.remove(http::header::IF_MODIFIED_SINCE);
+            if let Some(etag) = lookup.stale.as_ref().and_then(CachedResponse::etag) {
+                outbound_headers.insert(http::header::IF_NONE_MATCH, etag.clone());
+            }
+        }
+        let (mut lease, mut permit) = self.admit(&upstream, &alias, &req_headers, &instance_uri)?;
+        let endpoint = lease.endpoint().clone();
+        tracing::debug!(
+            upstream_id = %upstream.id,
+            endpoint_host = %endpoint.host,
+            selection_method = lease.method().as_str(),
+            "Selected upstream endpoint"
+        );
         headers::set_host_header(&mut outbound_headers, &endpoint.host, endpoint.port);
 
-        // 6. Check rate limit (upstream then route).
-        if let Some(ref rl) = upstream.rate_limit {
-            let key = format!("upstream:{}", upstream.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
+        // 6-7. Forward the request. WebSocket sessions are bridged after the
+        // upstream handshake; everything else is sent with a timeout on the
+        // response headers.
+        let (status, mut resp_headers, body, trailers) = if protocol == Protocol::WebSocket
+            && let Some(client_messages) = body_stream
+        {
+            let url = request_builder::build_websocket_url(
+                &endpoint,
+                route_path,
+                remaining_suffix,
+                &query_params,
+            )
+            .ok_or_else(|| DomainError::ProtocolError {
+                detail: format!(
+                    "WebSocket upgrade is not supported for {:?} endpoints",
+                    endpoint.scheme
+                ),
+                instance: instance_uri.clone(),
+            })?;
+            let (status, resp_headers, body) = self
+                .forward_websocket(
+                    &url,
+                    &outbound_headers,
+                    client_messages,
+                    lease,
+                    permit,
+                    &instance_uri,
+                )
+                .await?;
+            (status, resp_headers, body, None)
+        } else {
+            let url = request_builder::build_upstream_url(
+                &endpoint,
+                route_path,
+                remaining_suffix,
+                &query_params,
+            );
+            let client = if protocol == Protocol::Grpc {
+                &self.grpc_client
+            } else {
+                &self.http_client
+            };
+            let outbound_body = match body_stream {
+                Some(stream) => reqwest::Body::wrap_stream(stream),
+                None => reqwest::Body::from(body_bytes),
+            };
+            let send_future = client
+                .request(method, &url)
+                .headers(outbound_headers)
+                .body(outbound_body)
+                .send();
+
+            let timeout = self.request_timeout;
+            let response = tokio::time::timeout(timeout, send_future)
+                .await
+                .map_err(|_| {
+                    lease.record(false);
+                    if let Some(p) = permit.as_mut() {
+                        p.record(CallOutcome::Timeout);
+                    }
+                    DomainError::RequestTimeout {
+                        detail: format!("request to {url} timed out after {timeout:?}"),
+                        instance: instance_uri.clone(),
+                    }
+                })?
+                .map_err(|e| {
+                    lease.record(false);
+                    if let Some(p) = permit.as_mut() {
+                        p.record(CallOutcome::ConnectionError);
+                    }
+                    if e.is_connect() {
+                        DomainError::ConnectionTimeout {
+                            detail: e.to_string(),
+                            instance: instance_uri.clone(),
+                        }
+                    } else {
+                        DomainError::DownstreamError {
+                            detail: e.to_string(),
+                            instance: instance_uri.clone(),
+                        }
+                    }
+                })?;
+
+            // 8. Build streaming response.
+            let status = response.status();
+            lease.record(!status.is_server_error());
+            if let Some(mut p) = permit {
+                p.record(CallOutcome::Status(status.as_u16()));
+            }
+            let mut resp_headers = response.headers().clone();
+            headers::sanitize_response_headers(&mut resp_headers);
+
+            match cache_lookup {
+                Some(lookup) => {
+                    let (status, resp_headers, body) = lookup
+                        .complete(
+                            status,
+                            resp_headers,
+                            response,
+                            lease,
+                            &req_headers,
+                            &instance_uri,
+                        )
+                        .await?;
+                    (status, resp_headers, body, None)
+                }
+                None if protocol == Protocol::Grpc => {
+                    let (body, trailers) = grpc::streaming_body(response, lease);
+                    (status, resp_headers, body, Some(trailers))
+                }
+                None => (status, resp_headers, streaming_body(response, lease), None),
+            }
+        };
+        if status == http::StatusCode::UNAUTHORIZED
+            && let Some((plugin, config)) = auth_plugin
+        {
+            plugin.on_unauthorized(&config).await;
         }
-        if let Some(ref rl) = route.rate_limit {
-            let key = format!("route:{}", route.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
+        if let Some(ref quota) = quota {
+            headers::set_rate_limit_headers(&mut resp_headers, quota, SystemTime::now());
         }
+        headers::apply_guard_headers(&mut resp_headers, &guard_headers);
 
-        // 7. Build URL.
-        // path_suffix is the full path from the proxy URL; strip the route prefix
-        // so we get: endpoint + route_path + remaining_suffix.
-        let route_path = route
-            .match_rules
-            .http
-            .as_ref()
-            .map_or("/", |h| h.path.as_str());
-        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
-        let url = request_builder::build_upstream_url(
-            endpoint,
-            route_path,
-            remaining_suffix,
-            &query_params,
-        )?;
-
-        // 8. Forward request with timeout on response headers.
-        let send_future = self
-            .http_client
-            .request(method, &url)
-            .headers(outbound_headers)
-            .body(body_bytes)
-            .send();
+        let mut resp = build_response(status, resp_headers, body, instance_uri)?;
+        if let Some(trailers) = trailers {
+            resp.extensions_mut().insert(trailers);
+        }
+        Ok(resp)
+    }
 
-        let timeout = self.request_timeout;
-        let response = tokio::time::timeout(timeout, send_future)
-            .await
-            .map_err(|_| DomainError::RequestTimeout {
-                detail: format!("request to {url} timed out after {timeout:?}"),
-                instance: instance_uri.clone(),
-            })?
-            .map_err(|e| {
-                if e.is_connect() {
-                    DomainError::ConnectionTimeout {
-                        detail: e.to_string(),
-                        instance: instance_uri.clone(),
-                    }
-                } else {
-                    DomainError::DownstreamError {
-                        detail: e.to_string(),
-                        instance: instance_uri.clone(),
+    fn upstream_status(&self, upstream: &Upstream) -> UpstreamStatus {
+        let mut status = self.load_balancer.status(upstream);
+        if let Some(cb) = upstream.circuit_breaker.as_ref().filter(|cb| cb.enabled) {
+            match cb.scope {
+                CircuitBreakerScope::Global => {
+                    let key = upstream_circuit_key(upstream);
+                    status.circuit_breaker =
+                        Some(self.circuit_breaker.status(&key).unwrap_or_default());
+                }
+                CircuitBreakerScope::PerEndpoint => {
+                    for (ep_status, ep) in
+                        status.endpoints.iter_mut().zip(&upstream.server.endpoints)
+                    {
+                        let key = endpoint_circuit_key(upstream, ep);
+                        ep_status.circuit_breaker =
+                            Some(self.circuit_breaker.status(&key).unwrap_or_default());
                     }
                 }
-            })?;
+            }
+        }
+        status
+    }
+}
 
-        // 9. Build streaming response.
-        let status = response.status();
-        let mut resp_headers = response.headers().clone();
-        headers::sanitize_response_headers(&mut resp_headers);
+/// Response cache state carried from lookup to the upstream response.
+struct CacheLookup {
+    cache: Arc<ResponseCache>,
+    resource: ResourceKey,
+    upstream_id: Uuid,
+    config: CacheConfig,
+    /// Stale entry revalidated with `If-None-Match`.
+    stale: Option<CachedResponse>,
+}
 
-        let body_stream: BodyStream = Box::pin(
-            response
-                .bytes_stream()
-                .map(|r| r.map_err(|e| Box::new(e) as BoxError)),
-        );
+impl CacheLookup {
+    /// Serve the upstream response, storing it when cacheable, or the stale
+    /// entry when the upstream confirmed it with `304 Not Modified`.
+    async fn complete(
+        self,
+        status: StatusCode,
+        resp_headers: HeaderMap,
+        response: reqwest::Response,
+        lease: EndpointLease,
+        req_headers: &HeaderMap,
+        instance_uri: &str,
+    ) -> Result<(StatusCode, HeaderMap, Body), DomainError> {
+        let now = Instant::now();
+        if status == StatusCode::NOT_MODIFIED
+            && let Some(stale) = self.stale
+        {
+            let entry = match stale.revalidated(&resp_headers, &self.config, SystemTime::now(), now)
+            {
+                Some(fresh) => {
+                    self.cache
+                        .insert(self.resource, self.upstream_id, req_headers, fresh.clone());
+                    fresh
+                }
+                None => stale,
+            };
+            return Ok(cached_parts(
+                &entry,
+                req_headers,
+                now,
+                CacheStatus::Revalidated,
+            ));
+        }
 
-        let mut resp = http::Response::builder()
-            .status(status)
-            .body(Body::Stream(body_stream))
-            .map_err(|e| DomainError::DownstreamError {
-                detail: format!("failed to build response: {e}"),
-                instance: instance_uri,
+        let miss = |mut headers: HeaderMap, body| {
+            headers.append(
+                CACHE_STATUS_HEADER,
+                HeaderValue::from_static(CacheStatus::Miss.as_str()),
+            );
+            (status, headers, body)
+        };
+        let Some(lifetime) =
+            cache::freshness_lifetime(status, &resp_headers, &self.config, SystemTime::now())
+        else {
+            return Ok(miss(resp_headers, streaming_body(response, lease)));
+        };
+        let limit = usize::try_from(self.config.max_entry_bytes).unwrap_or(usize::MAX);
+        if response
+            .content_length()
+            .is_some_and(|len| len > self.config.max_entry_bytes)
+        {
+            return Ok(miss(resp_headers, streaming_body(response, lease)));
+        }
+
+        // Buffer up to the entry limit; larger bodies are streamed through
+        // without being stored.
+        let mut stream = response.bytes_stream();
+        let mut buf = BytesMut::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| DomainError::DownstreamError {
+                detail: format!("failed to read upstream response: {e}"),
+                instance: instance_uri.to_string(),
             })?;
+            buf.extend_from_slice(&chunk);
+            if buf.len() > limit {
+                let head = futures_util::stream::once(std::future::ready(Ok(buf.freeze())));
+                let rest = stream.map(move |r| {
+                    let _ = &lease;
+                    r.map_err(|e| Box::new(e) as BoxError)
+                });
+                let body_stream: BodyStream = Box::pin(head.chain(rest));
+                return Ok(miss(resp_headers, Body::Stream(body_stream)));
+            }
+        }
 
-        *resp.headers_mut() = resp_headers;
-        resp.extensions_mut().insert(ErrorSource::Upstream);
+        let entry = CachedResponse::new(status, resp_headers, buf.freeze(), lifetime, now);
+        self.cache
+            .insert(self.resource, self.upstream_id, req_headers, entry.clone());
+        Ok(cached_parts(&entry, req_headers, now, CacheStatus::Miss))
+    }
+}
 
-        Ok(resp)
+/// Status, headers and body for serving a cache entry, answering a matching
+/// `If-None-Match` with `304 Not Modified`.
+fn cached_parts(
+    entry: &CachedResponse,
+    req_headers: &HeaderMap,
+    now: Instant,
+    cache_status: CacheStatus,
+) -> (StatusCode, HeaderMap, Body) {
+    let (status, mut headers, body) = if entry.matches_client(req_headers) {
+        (
+            StatusCode::NOT_MODIFIED,
+            entry.not_modified_headers(now),
+            Body::Empty,
+        )
+    } else {
+        (
+            entry.status,
+            entry.headers_at(now),
+            Body::Bytes(entry.body.clone()),
+        )
+    };
+    headers.append(
+        CACHE_STATUS_HEADER,
+        HeaderValue::from_static(cache_status.as_str()),
+    );
+    (status, headers, body)
+}
+
+/// Stream the upstream body. The lease rides along with the body so the
+/// endpoint counts as in flight until the stream is fully consumed or dropped.
+fn streaming_body(response: reqwest::Response, lease: EndpointLease) -> Body {
+    let body_stream: BodyStream = Box::pin(response.bytes_stream().map(move |r| {
+        let _ = &lease;
+        r.map_err(|e| Box::new(e) as BoxError)
+    }));
+    Body::Stream(body_stream)
+}
+
+fn build_response(
+    status: StatusCode,
+    resp_headers: HeaderMap,
+    body: Body,
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    let mut resp = http::Response::builder()
+        .status(status)
+        .body(body)
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("failed to build response: {e}"),
+            instance: instance_uri,
+        })?;
+
+    *resp.headers_mut() = resp_headers;
+    resp.extensions_mut().insert(ErrorSource::Upstream);
+
+    Ok(resp)
+}
+
+/// Build the gateway-originated response served by the `degrade` rate-limit strategy.
+fn degraded_response(
+    fallback: &FallbackResponse,
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    let mut resp = http::Response::builder()
+        .status(fallback.status)
+        .header(http::header::CONTENT_TYPE, fallback.content_type.as_str())
+        .body(Body::from(fallback.body.clone()))
+        .map_err(|e| DomainError::Internal {
+            message: format!("invalid degrade fallback response for {instance_uri}: {e}"),
+        })?;
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
+}
+
+/// Build the gateway-originated response for a guard that answered locally.
+fn guard_response(
+    status: u16,
+    guard_headers: &[(String, String)],
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    let mut resp = http::Response::builder()
+        .status(status)
+        .body(Body::Empty)
+        .map_err(|e| DomainError::Internal {
+            message: format!("invalid guard response for {instance_uri}: {e}"),
+        })?;
+    headers::apply_guard_headers(resp.headers_mut(), guard_headers);
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
+}
+
+fn guard_error(err: PluginError, instance_uri: &str) -> DomainError {
+    match err {
+        PluginError::Rejected(detail) => DomainError::GuardRejected {
+            detail,
+            instance: instance_uri.to_string(),
+        },
+        PluginError::SecretNotFound(detail) => DomainError::SecretNotFound {
+            detail,
+            instance: instance_uri.to_string(),
+        },
+        PluginError::AuthFailed(_) | PluginError::Internal(_) => DomainError::Internal {
+            message: err.to_string(),
+        },
     }
 }
 
+fn upstream_circuit_key(upstream: &Upstream) -> String {
+    format!("upstream:{}", upstream.id)
+}
+
+fn endpoint_circuit_key(upstream: &Upstream, endpoint: &Endpoint) -> String {
+    format!(
+        "upstream:{}/endpoint:{}:{}",
+        upstream.id, endpoint.host, endpoint.port
+    )
+}
+
+/// Whether an `X-OAGW-Target-Host` value is a bare hostname or IP address
+/// (no port, path, or other special characters).
+fn is_valid_target_host(host: &str) -> bool {
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return true;
+    }
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
 /// Normalize a URL path: collapse consecutive slashes and resolve `.`/`..` segments.
 /// Segments that would escape above the root are discarded.
 fn normalize_path(path: &str) -> String {
@@ -346,7 +1065,7 @@ fn normalize_path(path: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::normalize_path;
+    use super::{is_valid_target_host, normalize_path};
 
     #[test]
     fn normalize_collapses_double_slashes() {
@@ -372,4 +1091,20 @@ mod tests {
     fn normalize_preserves_clean_path() {
         assert_eq!(normalize_path("/alias/v1/chat"), "/alias/v1/chat");
     }
+
+    #[test]
+    fn target_host_accepts_hostnames_and_ips() {
+        assert!(is_valid_target_host("us.vendor.com"));
+        assert!(is_valid_target_host("10.0.0.1"));
+        assert!(is_valid_target_host("::1"));
+    }
+
+    #[test]
+    fn target_host_rejects_ports_paths_and_junk() {
+        assert!(!is_valid_target_host("us.vendor.com:443"));
+        assert!(!is_valid_target_host("us.vendor.com/admin"));
+        assert!(!is_valid_target_host(""));
+        assert!(!is_valid_target_host("bad..host"));
+        assert!(!is_valid_target_host("-bad.host"));
+    }
 }
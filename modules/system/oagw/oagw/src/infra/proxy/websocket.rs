@@ -0,0 +1,207 @@
+//! WebSocket proxying: upstream handshake and message bridging.
+//!
+//! The client side of a session is a pair of body streams (see
+//! `ServiceGatewayClientV1::proxy_request`): each request chunk becomes one
+//! upstream message and each upstream message one response chunk. Chunks
+//! start with the message opcode so text and binary messages keep their type.
+
+use std::future::ready;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::{SinkExt, StreamExt};
+use http::{HeaderMap, HeaderName};
+use oagw_sdk::body::{Body, BodyStream, BoxError};
+use oagw_sdk::ws::{BINARY_OPCODE, TEXT_OPCODE};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::{self, Message, Utf8Bytes};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// Handshake headers forwarded from the client. The rest of the handshake
+/// (key, version, upgrade) is generated for the upstream connection.
+const FORWARDED_HANDSHAKE_HEADERS: &[&str] = &["sec-websocket-protocol"];
+
+/// Handshake headers of the upstream `101` that only apply to the upstream
+/// connection.
+const UPSTREAM_HANDSHAKE_HEADERS: &[&str] = &["sec-websocket-accept", "sec-websocket-extensions"];
+
+pub(crate) type UpstreamSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Outcome of the upstream handshake.
+pub(crate) enum Handshake {
+    /// The upstream switched protocols; carries its `101` response headers.
+    Upgraded(HeaderMap, Box<UpstreamSocket>),
+    /// The upstream answered with a plain HTTP response instead.
+    Rejected(http::Response<Option<Vec<u8>>>),
+}
+
+/// Whether the request asks to upgrade to WebSocket.
+pub(crate) fn is_upgrade_request(method: &http::Method, headers: &HeaderMap) -> bool {
+    method == http::Method::GET
+        && headers
+            .get_all(http::header::UPGRADE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .any(|token| token.trim().eq_ignore_ascii_case("websocket"))
+}
+
+/// Forward the client's subprotocol offer regardless of passthrough mode.
+pub(crate) fn prepare_outbound_headers(inbound: &HeaderMap, outbound: &mut HeaderMap) {
+    for name in FORWARDED_HANDSHAKE_HEADERS {
+        for value in inbound.get_all(*name) {
+            outbound.append(*name, value.clone());
+        }
+    }
+}
+
+/// Open the upstream WebSocket at `url`, sending `headers` with the handshake.
+pub(crate) async fn connect(
+    url: &str,
+    headers: &HeaderMap,
+) -> Result<Handshake, tungstenite::Error> {
+    let mut request = url.into_client_request()?;
+    let generated: Vec<HeaderName> = request.headers().keys().cloned().collect();
+    for (name, value) in headers {
+        // Extensions such as permessage-deflate are negotiated per hop.
+        if !generated.contains(name) && name != "sec-websocket-extensions" {
+            request.headers_mut().append(name.clone(), value.clone());
+        }
+    }
+
+    match tokio_tungstenite::connect_async(request).await {
+        Ok((socket, response)) => {
+            let mut headers = response.into_parts().0.headers;
+            for name in UPSTREAM_HANDSHAKE_HEADERS {
+                headers.remove(*name);
+            }
+            Ok(Handshake::Upgraded(headers, Box::new(socket)))
+        }
+        Err(tungstenite::Error::Http(response)) => Ok(Handshake::Rejected(*response)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Bridge an upgraded session: `client` chunks are sent upstream until the
+/// client stream ends, which closes the upstream connection; upstream
+/// messages are returned as the response body, which ends when the upstream
+/// closes. `guard` rides along with the response body.
+pub(crate) fn bridge<G: Send + 'static>(
+    socket: Box<UpstreamSocket>,
+    mut client: BodyStream,
+    guard: G,
+) -> Body {
+    let (mut sink, stream) = socket.split();
+    tokio::spawn(async move {
+        while let Some(Ok(chunk)) = client.next().await {
+            let message = match to_message(chunk) {
+                Ok(message) => message,
+                Err(reason) => {
+                    tracing::debug!(reason, "Closing WebSocket session on malformed chunk");
+                    let _ = sink
+                        .send(Message::Close(Some(CloseFrame {
+                            code: CloseCode::Invalid,
+                            reason: Utf8Bytes::from_static(reason),
+                        })))
+                        .await;
+                    return;
+                }
+            };
+            if sink.send(message).await.is_err() {
+                return;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    let messages = stream
+        .take_while(|m| ready(!is_end_of_session(m)))
+        .filter_map(move |m| {
+            let _ = &guard;
+            ready(match m {
+                // Ping/pong are answered by the connection itself.
+                Ok(message) => to_chunk(&message).map(Ok),
+                Err(e) => Some(Err(Box::new(e) as BoxError)),
+            })
+        });
+    Body::Stream(Box::pin(messages))
+}
+
+/// A close frame ends the session, and so does an upstream that drops the
+/// connection without completing the closing handshake.
+fn is_end_of_session(message: &Result<Message, tungstenite::Error>) -> bool {
+    matches!(
+        message,
+        Ok(Message::Close(_))
+            | Err(tungstenite::Error::ConnectionClosed
+                | tungstenite::Error::AlreadyClosed
+                | tungstenite::Error::Protocol(
+                    tungstenite::error::ProtocolError::ResetWithoutClosingHandshake
+                ))
+    )
+}
+
+/// Upstream message for a client chunk: the opcode byte selects text or
+/// binary, the rest is the payload.
+fn to_message(chunk: Bytes) -> Result<Message, &'static str> {
+    match chunk.first() {
+        Some(&TEXT_OPCODE) => Utf8Bytes::try_from(chunk.slice(1..))
+            .map(Message::Text)
+            .map_err(|_| "text message is not valid UTF-8"),
+        Some(&BINARY_OPCODE) => Ok(Message::Binary(chunk.slice(1..))),
+        _ => Err("message chunk has no text or binary opcode"),
+    }
+}
+
+/// Response chunk for an upstream data message; `None` for control frames.
+fn to_chunk(message: &Message) -> Option<Bytes> {
+    let (opcode, payload) = match message {
+        Message::Text(text) => (TEXT_OPCODE, text.as_bytes()),
+        Message::Binary(data) => (BINARY_OPCODE, &data[..]),
+        _ => return None,
+    };
+    let mut chunk = BytesMut::with_capacity(payload.len() + 1);
+    chunk.put_u8(opcode);
+    chunk.put_slice(payload);
+    Some(chunk.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_upgrade_requests() {
+        let mut headers = HeaderMap::new();
+        headers.insert(http::header::UPGRADE, "WebSocket".parse().unwrap());
+        assert!(is_upgrade_request(&http::Method::GET, &headers));
+        assert!(!is_upgrade_request(&http::Method::POST, &headers));
+
+        headers.insert(http::header::UPGRADE, "h2c".parse().unwrap());
+        assert!(!is_upgrade_request(&http::Method::GET, &headers));
+        assert!(!is_upgrade_request(&http::Method::GET, &HeaderMap::new()));
+    }
+
+    #[test]
+    fn chunks_map_to_text_or_binary_messages_by_opcode() {
+        assert_eq!(
+            to_message(Bytes::from_static(b"\x01hi")),
+            Ok(Message::text("hi"))
+        );
+        // Valid UTF-8 sent as binary stays binary.
+        assert_eq!(
+            to_message(Bytes::from_static(b"\x02hi")),
+            Ok(Message::binary(Bytes::from_static(b"hi")))
+        );
+        assert!(to_message(Bytes::from_static(b"\x01\xff")).is_err());
+        assert!(to_message(Bytes::new()).is_err());
+
+        for message in [Message::text("hi"), Message::binary(vec![0xff_u8, 0x00])] {
+            let chunk = to_chunk(&message).unwrap();
+            assert_eq!(to_message(chunk), Ok(message));
+        }
+        assert_eq!(to_chunk(&Message::Ping(Bytes::new())), None);
+    }
+}
@@ -0,0 +1,51 @@
+//! Database error conversion helpers.
+
+use modkit_db::secure::ScopeError;
+use sea_orm::{DbErr, SqlErr};
+
+use crate::domain::repo::RepositoryError;
+
+pub(crate) type OagwDbProvider = modkit_db::DBProvider<modkit_db::DbError>;
+
+/// Convert a secure-ORM error into a `RepositoryError`.
+///
+/// Unique-constraint violations become `Conflict` so callers see the same
+/// error as with the in-memory repositories.
+pub(crate) fn scope_err(e: ScopeError) -> RepositoryError {
+    match e {
+        ScopeError::Db(e) => db_err(e),
+        other => RepositoryError::Internal(other.to_string()),
+    }
+}
+
+pub(crate) fn db_err(e: DbErr) -> RepositoryError {
+    match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(detail)) => RepositoryError::Conflict(detail),
+        _ => RepositoryError::Internal(format!("database error: {e}")),
+    }
+}
+
+pub(crate) fn conn_err(e: modkit_db::DbError) -> RepositoryError {
+    RepositoryError::Internal(format!("database connection error: {e}"))
+}
+
+/// In-memory SQLite provider with the OAGW schema applied.
+#[cfg(test)]
+pub(crate) async fn test_db() -> std::sync::Arc<OagwDbProvider> {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("Failed to connect to in-memory database");
+    run_migrations_for_testing(&db, super::migrations::Migrator::migrations())
+        .await
+        .expect("Failed to run migrations");
+    std::sync::Arc::new(OagwDbProvider::new(db))
+}
@@ -0,0 +1,2 @@
+pub(crate) mod route;
+pub(crate) mod upstream;
@@ -0,0 +1,27 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+/// Persisted route. Match rules and nested configuration blocks are stored as
+/// JSON text (see [`crate::infra::storage::json_columns`]).
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "oagw_routes")]
+#[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub upstream_id: Uuid,
+    pub match_rules: String,
+    pub plugins: Option<String>,
+    pub rate_limit: Option<String>,
+    pub cache: Option<String>,
+    pub tags: String,
+    pub priority: i32,
+    pub enabled: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,30 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+/// Persisted upstream. Nested configuration blocks are stored as JSON text
+/// (see [`crate::infra::storage::json_columns`]).
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "oagw_upstreams")]
+#[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub alias: String,
+    pub protocol: String,
+    pub enabled: bool,
+    pub server: String,
+    pub auth: Option<String>,
+    pub headers: Option<String>,
+    pub plugins: Option<String>,
+    pub rate_limit: Option<String>,
+    pub load_balancing: Option<String>,
+    pub circuit_breaker: Option<String>,
+    pub tags: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,824 @@
+//! Serde mirrors of the nested domain value types stored as JSON text columns.
+//!
+//! The domain model carries no serde derives, and REST DTOs must not leak
+//! outside the API layer, so the storage layer owns its own persisted shape.
+//! Keep these types backward compatible: rows written by older releases must
+//! still deserialize.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model as domain;
+
+// ---------------------------------------------------------------------------
+// Enums
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SharingMode {
+    Private,
+    Inherit,
+    Enforce,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Scheme {
+    Http,
+    Https,
+    Wss,
+    Wt,
+    Grpc,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PassthroughMode {
+    None,
+    Allowlist,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RateLimitAlgorithm {
+    TokenBucket,
+    SlidingWindow,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Window {
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RateLimitScope {
+    Global,
+    Tenant,
+    User,
+    Ip,
+    Route,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RateLimitStrategy {
+    Reject,
+    Queue,
+    Degrade,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum LoadBalancingStrategy {
+    RoundRobin,
+    LeastInFlight,
+    Weighted,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CircuitBreakerScope {
+    Global,
+    PerEndpoint,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(crate) enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PathSuffixMode {
+    Disabled,
+    Append,
+}
+
+// ---------------------------------------------------------------------------
+// Structs
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Endpoint {
+    pub scheme: Scheme,
+    pub host: String,
+    pub port: u16,
+    /// Added with weighted load balancing; absent in older rows.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Server {
+    pub endpoints: Vec<Endpoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct AuthConfig {
+    pub plugin_type: String,
+    pub sharing: SharingMode,
+    #[serde(default)]
+    pub config: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RequestHeaderRules {
+    #[serde(default)]
+    pub set: HashMap<String, String>,
+    #[serde(default)]
+    pub add: HashMap<String, String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+    pub passthrough: PassthroughMode,
+    #[serde(default)]
+    pub passthrough_allowlist: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ResponseHeaderRules {
+    #[serde(default)]
+    pub set: HashMap<String, String>,
+    #[serde(default)]
+    pub add: HashMap<String, String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HeadersConfig {
+    #[serde(default)]
+    pub request: Option<RequestHeaderRules>,
+    #[serde(default)]
+    pub response: Option<ResponseHeaderRules>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SustainedRate {
+    pub rate: u32,
+    pub window: Window,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BurstConfig {
+    pub capacity: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RateLimitConfig {
+    pub sharing: SharingMode,
+    pub algorithm: RateLimitAlgorithm,
+    pub sustained: SustainedRate,
+    #[serde(default)]
+    pub burst: Option<BurstConfig>,
+    pub scope: RateLimitScope,
+    pub strategy: RateLimitStrategy,
+    pub cost: u32,
+    #[serde(default)]
+    pub queue: Option<QueueConfig>,
+    #[serde(default)]
+    pub degrade: Option<DegradeConfig>,
+    /// Added with `X-RateLimit-*` headers; absent in older rows.
+    #[serde(default = "default_true")]
+    pub response_headers: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct QueueConfig {
+    pub max_depth: u32,
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DegradeConfig {
+    pub fallback_status: u16,
+    pub fallback_content_type: String,
+    pub fallback_body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct LoadBalancingConfig {
+    pub strategy: LoadBalancingStrategy,
+    #[serde(default)]
+    pub health_check: Option<HealthCheckConfig>,
+    #[serde(default)]
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HealthCheckConfig {
+    pub path: String,
+    pub interval_secs: u32,
+    pub timeout_secs: u32,
+    pub healthy_threshold: u32,
+    pub unhealthy_threshold: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct OutlierDetectionConfig {
+    pub consecutive_failures: u32,
+    pub base_ejection_secs: u32,
+    pub max_ejection_percent: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CircuitBreakerConfig {
+    pub enabled: bool,
+    pub failure_threshold: u32,
+    pub success_threshold: u32,
+    pub timeout_secs: u32,
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct FailureConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CacheConfig {
+    pub enabled: bool,
+    pub default_ttl_secs: u32,
+    pub max_ttl_secs: u32,
+    pub max_entry_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PluginsConfig {
+    pub sharing: SharingMode,
+    #[serde(default)]
+    pub items: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub config: HashMap<String, HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HttpMatch {
+    pub methods: Vec<HttpMethod>,
+    pub path: String,
+    #[serde(default)]
+    pub query_allowlist: Vec<String>,
+    pub path_suffix_mode: PathSuffixMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GrpcMatch {
+    pub service: String,
+    pub method: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MatchRules {
+    #[serde(default)]
+    pub http: Option<HttpMatch>,
+    #[serde(default)]
+    pub grpc: Option<GrpcMatch>,
+}
+
+// ---------------------------------------------------------------------------
+// domain → storage
+// ---------------------------------------------------------------------------
+
+impl From<&domain::SharingMode> for SharingMode {
+    fn from(v: &domain::SharingMode) -> Self {
+        match v {
+            domain::SharingMode::Private => Self::Private,
+            domain::SharingMode::Inherit => Self::Inherit,
+            domain::SharingMode::Enforce => Self::Enforce,
+        }
+    }
+}
+
+impl From<&domain::Scheme> for Scheme {
+    fn from(v: &domain::Scheme) -> Self {
+        match v {
+            domain::Scheme::Http => Self::Http,
+            domain::Scheme::Https => Self::Https,
+            domain::Scheme::Wss => Self::Wss,
+            domain::Scheme::Wt => Self::Wt,
+            domain::Scheme::Grpc => Self::Grpc,
+        }
+    }
+}
+
+impl From<&domain::PassthroughMode> for PassthroughMode {
+    fn from(v: &domain::PassthroughMode) -> Self {
+        match v {
+            domain::PassthroughMode::None => Self::None,
+            domain::PassthroughMode::Allowlist => Self::Allowlist,
+            domain::PassthroughMode::All => Self::All,
+        }
+    }
+}
+
+impl From<&domain::RateLimitAlgorithm> for RateLimitAlgorithm {
+    fn from(v: &domain::RateLimitAlgorithm) -> Self {
+        match v {
+            domain::RateLimitAlgorithm::TokenBucket => Self::TokenBucket,
+            domain::RateLimitAlgorithm::SlidingWindow => Self::SlidingWindow,
+        }
+    }
+}
+
+impl From<&domain::Window> for Window {
+    fn from(v: &domain::Window) -> Self {
+        match v {
+            domain::Window::Second => Self::Second,
+            domain::Window::Minute => Self::Minute,
+            domain::Window::Hour => Self::Hour,
+            domain::Window::Day => Self::Day,
+        }
+    }
+}
+
+impl From<&domain::RateLimitScope> for RateLimitScope {
+    fn from(v: &domain::RateLimitScope) -> Self {
+        match v {
+            domain::RateLimitScope::Global => Self::Global,
+            domain::RateLimitScope::Tenant => Self::Tenant,
+            domain::RateLimitScope::User => Self::User,
+            domain::RateLimitScope::Ip => Self::Ip,
+            domain::RateLimitScope::Route => Self::Route,
+        }
+    }
+}
+
+impl From<&domain::RateLimitStrategy> for RateLimitStrategy {
+    fn from(v: &domain::RateLimitStrategy) -> Self {
+        match v {
+            domain::RateLimitStrategy::Reject => Self::Reject,
+            domain::RateLimitStrategy::Queue => Self::Queue,
+            domain::RateLimitStrategy::Degrade => Self::Degrade,
+        }
+    }
+}
+
+impl From<&domain::HttpMethod> for HttpMethod {
+    fn from(v: &domain::HttpMethod) -> Self {
+        match v {
+            domain::HttpMethod::Get => Self::Get,
+            domain::HttpMethod::Post => Self::Post,
+            domain::HttpMethod::Put => Self::Put,
+            domain::HttpMethod::Delete => Self::Delete,
+            domain::HttpMethod::Patch => Self::Patch,
+        }
+    }
+}
+
+impl From<&domain::PathSuffixMode> for PathSuffixMode {
+    fn from(v: &domain::PathSuffixMode) -> Self {
+        match v {
+            domain::PathSuffixMode::Disabled => Self::Disabled,
+            domain::PathSuffixMode::Append => Self::Append,
+        }
+    }
+}
+
+impl From<&domain::Server> for Server {
+    fn from(v: &domain::Server) -> Self {
+        Self {
+            endpoints: v
+                .endpoints
+                .iter()
+                .map(|e| Endpoint {
+                    scheme: (&e.scheme).into(),
+                    host: e.host.clone(),
+                    port: e.port,
+                    weight: e.weight,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&domain::AuthConfig> for AuthConfig {
+    fn from(v: &domain::AuthConfig) -> Self {
+        Self {
+            plugin_type: v.plugin_type.clone(),
+            sharing: (&v.sharing).into(),
+            config: v.config.clone(),
+        }
+    }
+}
+
+impl From<&domain::HeadersConfig> for HeadersConfig {
+    fn from(v: &domain::HeadersConfig) -> Self {
+        Self {
+            request: v.request.as_ref().map(|r| RequestHeaderRules {
+                set: r.set.clone(),
+                add: r.add.clone(),
+                remove: r.remove.clone(),
+                passthrough: (&r.passthrough).into(),
+                passthrough_allowlist: r.passthrough_allowlist.clone(),
+            }),
+            response: v.response.as_ref().map(|r| ResponseHeaderRules {
+                set: r.set.clone(),
+                add: r.add.clone(),
+                remove: r.remove.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&domain::RateLimitConfig> for RateLimitConfig {
+    fn from(v: &domain::RateLimitConfig) -> Self {
+        Self {
+            sharing: (&v.sharing).into(),
+            algorithm: (&v.algorithm).into(),
+            sustained: SustainedRate {
+                rate: v.sustained.rate,
+                window: (&v.sustained.window).into(),
+            },
+            burst: v.burst.as_ref().map(|b| BurstConfig {
+                capacity: b.capacity,
+            }),
+            scope: (&v.scope).into(),
+            strategy: (&v.strategy).into(),
+            cost: v.cost,
+            queue: v.queue.as_ref().map(|q| QueueConfig {
+                max_depth: q.max_depth,
+                timeout_ms: u64::try_from(q.timeout.as_millis()).unwrap_or(u64::MAX),
+            }),
+            degrade: v.degrade.as_ref().map(|d| DegradeConfig {
+                fallback_status: d.fallback_response.status,
+                fallback_content_type: d.fallback_response.content_type.clone(),
+                fallback_body: d.fallback_response.body.clone(),
+            }),
+            response_headers: v.response_headers,
+        }
+    }
+}
+
+impl From<&domain::LoadBalancingConfig> for LoadBalancingConfig {
+    fn from(v: &domain::LoadBalancingConfig) -> Self {
+        Self {
+            strategy: match v.strategy {
+                domain::LoadBalancingStrategy::RoundRobin => LoadBalancingStrategy::RoundRobin,
+                domain::LoadBalancingStrategy::LeastInFlight => {
+                    LoadBalancingStrategy::LeastInFlight
+                }
+                domain::LoadBalancingStrategy::Weighted => LoadBalancingStrategy::Weighted,
+            },
+            health_check: v.health_check.as_ref().map(|h| HealthCheckConfig {
+                path: h.path.clone(),
+                interval_secs: h.interval_secs,
+                timeout_secs: h.timeout_secs,
+                healthy_threshold: h.healthy_threshold,
+                unhealthy_threshold: h.unhealthy_threshold,
+            }),
+            outlier_detection: v
+                .outlier_detection
+                .as_ref()
+                .map(|o| OutlierDetectionConfig {
+                    consecutive_failures: o.consecutive_failures,
+                    base_ejection_secs: o.base_ejection_secs,
+                    max_ejection_percent: o.max_ejection_percent,
+                }),
+        }
+    }
+}
+
+impl From<&domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: &domain::CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            success_threshold: v.success_threshold,
+            timeout_secs: v.timeout_secs,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: FailureConditions {
+                status_codes: v.failure_conditions.status_codes.clone(),
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: match v.scope {
+                domain::CircuitBreakerScope::Global => CircuitBreakerScope::Global,
+                domain::CircuitBreakerScope::PerEndpoint => CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
+impl From<&domain::CacheConfig> for CacheConfig {
+    fn from(v: &domain::CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_ttl_secs: v.max_ttl_secs,
+            max_entry_bytes: v.max_entry_bytes,
+        }
+    }
+}
+
+impl From<&domain::PluginsConfig> for PluginsConfig {
+    fn from(v: &domain::PluginsConfig) -> Self {
+        Self {
+            sharing: (&v.sharing).into(),
+            items: v.items.clone(),
+            config: v.config.clone(),
+        }
+    }
+}
+
+impl From<&domain::MatchRules> for MatchRules {
+    fn from(v: &domain::MatchRules) -> Self {
+        Self {
+            http: v.http.as_ref().map(|h| HttpMatch {
+                methods: h.methods.iter().map(Into::into).collect(),
+                path: h.path.clone(),
+                query_allowlist: h.query_allowlist.clone(),
+                path_suffix_mode: (&h.path_suffix_mode).into(),
+            }),
+            grpc: v.grpc.as_ref().map(|g| GrpcMatch {
+                service: g.service.clone(),
+                method: g.method.clone(),
+            }),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// storage → domain
+// ---------------------------------------------------------------------------
+
+impl From<SharingMode> for domain::SharingMode {
+    fn from(v: SharingMode) -> Self {
+        match v {
+            SharingMode::Private => Self::Private,
+            SharingMode::Inherit => Self::Inherit,
+            SharingMode::Enforce => Self::Enforce,
+        }
+    }
+}
+
+impl From<Scheme> for domain::Scheme {
+    fn from(v: Scheme) -> Self {
+        match v {
+            Scheme::Http => Self::Http,
+            Scheme::Https => Self::Https,
+            Scheme::Wss => Self::Wss,
+            Scheme::Wt => Self::Wt,
+            Scheme::Grpc => Self::Grpc,
+        }
+    }
+}
+
+impl From<PassthroughMode> for domain::PassthroughMode {
+    fn from(v: PassthroughMode) -> Self {
+        match v {
+            PassthroughMode::None => Self::None,
+            PassthroughMode::Allowlist => Self::Allowlist,
+            PassthroughMode::All => Self::All,
+        }
+    }
+}
+
+impl From<RateLimitAlgorithm> for domain::RateLimitAlgorithm {
+    fn from(v: RateLimitAlgorithm) -> Self {
+        match v {
+            RateLimitAlgorithm::TokenBucket => Self::TokenBucket,
+            RateLimitAlgorithm::SlidingWindow => Self::SlidingWindow,
+        }
+    }
+}
+
+impl From<Window> for domain::Window {
+    fn from(v: Window) -> Self {
+        match v {
+            Window::Second => Self::Second,
+            Window::Minute => Self::Minute,
+            Window::Hour => Self::Hour,
+            Window::Day => Self::Day,
+        }
+    }
+}
+
+impl From<RateLimitScope> for domain::RateLimitScope {
+    fn from(v: RateLimitScope) -> Self {
+        match v {
+            RateLimitScope::Global => Self::Global,
+            RateLimitScope::Tenant => Self::Tenant,
+            RateLimitScope::User => Self::User,
+            RateLimitScope::Ip => Self::Ip,
+            RateLimitScope::Route => Self::Route,
+        }
+    }
+}
+
+impl From<RateLimitStrategy> for domain::RateLimitStrategy {
+    fn from(v: RateLimitStrategy) -> Self {
+        match v {
+            RateLimitStrategy::Reject => Self::Reject,
+            RateLimitStrategy::Queue => Self::Queue,
+            RateLimitStrategy::Degrade => Self::Degrade,
+        }
+    }
+}
+
+impl From<HttpMethod> for domain::HttpMethod {
+    fn from(v: HttpMethod) -> Self {
+        match v {
+            HttpMethod::Get => Self::Get,
+            HttpMethod::Post => Self::Post,
+            HttpMethod::Put => Self::Put,
+            HttpMethod::Delete => Self::Delete,
+            HttpMethod::Patch => Self::Patch,
+        }
+    }
+}
+
+impl From<PathSuffixMode> for domain::PathSuffixMode {
+    fn from(v: PathSuffixMode) -> Self {
+        match v {
+            PathSuffixMode::Disabled => Self::Disabled,
+            PathSuffixMode::Append => Self::Append,
+        }
+    }
+}
+
+impl From<Server> for domain::Server {
+    fn from(v: Server) -> Self {
+        Self {
+            endpoints: v
+                .endpoints
+                .into_iter()
+                .map(|e| domain::Endpoint {
+                    scheme: e.scheme.into(),
+                    host: e.host,
+                    port: e.port,
+                    weight: e.weight,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<AuthConfig> for domain::AuthConfig {
+    fn from(v: AuthConfig) -> Self {
+        Self {
+            plugin_type: v.plugin_type,
+            sharing: v.sharing.into(),
+            config: v.config,
+        }
+    }
+}
+
+impl From<HeadersConfig> for domain::HeadersConfig {
+    fn from(v: HeadersConfig) -> Self {
+        Self {
+            request: v.request.map(|r| domain::RequestHeaderRules {
+                set: r.set,
+                add: r.add,
+                remove: r.remove,
+                passthrough: r.passthrough.into(),
+                passthrough_allowlist: r.passthrough_allowlist,
+            }),
+            response: v.response.map(|r| domain::ResponseHeaderRules {
+                set: r.set,
+                add: r.add,
+                remove: r.remove,
+            }),
+        }
+    }
+}
+
+impl From<RateLimitConfig> for domain::RateLimitConfig {
+    fn from(v: RateLimitConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            algorithm: v.algorithm.into(),
+            sustained: domain::SustainedRate {
+                rate: v.sustained.rate,
+                window: v.sustained.window.into(),
+            },
+            burst: v.burst.map(|b| domain::BurstConfig {
+                capacity: b.capacity,
+            }),
+            scope: v.scope.into(),
+            strategy: v.strategy.into(),
+            cost: v.cost,
+            queue: v.queue.map(|q| domain::QueueConfig {
+                max_depth: q.max_depth,
+                timeout: Duration::from_millis(q.timeout_ms),
+            }),
+            degrade: v.degrade.map(|d| domain::DegradeConfig {
+                fallback_response: domain::FallbackResponse {
+                    status: d.fallback_status,
+                    content_type: d.fallback_content_type,
+                    body: d.fallback_body,
+                },
+            }),
+            response_headers: v.response_headers,
+        }
+    }
+}
+
+impl From<LoadBalancingConfig> for domain::LoadBalancingConfig {
+    fn from(v: LoadBalancingConfig) -> Self {
+        Self {
+            strategy: match v.strategy {
+                LoadBalancingStrategy::RoundRobin => domain::LoadBalancingStrategy::RoundRobin,
+                LoadBalancingStrategy::LeastInFlight => {
+                    domain::LoadBalancingStrategy::LeastInFlight
+                }
+                LoadBalancingStrategy::Weighted => domain::LoadBalancingStrategy::Weighted,
+            },
+            health_check: v.health_check.map(|h| domain::HealthCheckConfig {
+                path: h.path,
+                interval_secs: h.interval_secs,
+                timeout_secs: h.timeout_secs,
+                healthy_threshold: h.healthy_threshold,
+                unhealthy_threshold: h.unhealthy_threshold,
+            }),
+            outlier_detection: v.outlier_detection.map(|o| domain::OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                base_ejection_secs: o.base_ejection_secs,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            success_threshold: v.success_threshold,
+            timeout_secs: v.timeout_secs,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: domain::FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: match v.scope {
+                CircuitBreakerScope::Global => domain::CircuitBreakerScope::Global,
+                CircuitBreakerScope::PerEndpoint => domain::CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_ttl_secs: v.max_ttl_secs,
+            max_entry_bytes: v.max_entry_bytes,
+        }
+    }
+}
+
+impl From<PluginsConfig> for domain::PluginsConfig {
+    fn from(v: PluginsConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            items: v.items,
+            config: v.config,
+        }
+    }
+}
+
+impl From<MatchRules> for domain::MatchRules {
+    fn from(v: MatchRules) -> Self {
+        Self {
+            http: v.http.map(|h| domain::HttpMatch {
+                methods: h.methods.into_iter().map(Into::into).collect(),
+                path: h.path,
+                query_allowlist: h.query_allowlist,
+                path_suffix_mode: h.path_suffix_mode.into(),
+            }),
+            grpc: v.grpc.map(|g| domain::GrpcMatch {
+                service: g.service,
+                method: g.method,
+            }),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Conversions between domain entities and SeaORM models.
+
+use sea_orm::ActiveValue::Set;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::domain::model::{
+    AuthConfig, CacheConfig, CircuitBreakerConfig, HeadersConfig, LoadBalancingConfig, MatchRules,
+    PluginsConfig, RateLimitConfig, Route, Server, Upstream,
+};
+use crate::domain::repo::RepositoryError;
+
+use super::entity::{route, upstream};
+use super::json_columns;
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, RepositoryError> {
+    serde_json::to_string(value)
+        .map_err(|e| RepositoryError::Internal(format!("failed to encode column: {e}")))
+}
+
+fn from_json<T: DeserializeOwned>(raw: &str) -> Result<T, RepositoryError> {
+    serde_json::from_str(raw)
+        .map_err(|e| RepositoryError::Internal(format!("failed to decode column: {e}")))
+}
+
+fn opt_to_json<'a, D, S>(value: Option<&'a D>) -> Result<Option<String>, RepositoryError>
+where
+    S: Serialize + From<&'a D>,
+{
+    value.map(|v| to_json(&S::from(v))).transpose()
+}
+
+fn opt_from_json<S, D>(raw: Option<&str>) -> Result<Option<D>, RepositoryError>
+where
+    S: DeserializeOwned + Into<D>,
+{
+    raw.map(|r| from_json::<S>(r).map(Into::into)).transpose()
+}
+
+pub(crate) fn upstream_to_active_model(
+    u: &Upstream,
+) -> Result<upstream::ActiveModel, RepositoryError> {
+    Ok(upstream::ActiveModel {
+        id: Set(u.id),
+        tenant_id: Set(u.tenant_id),
+        alias: Set(u.alias.clone()),
+        protocol: Set(u.protocol.clone()),
+        enabled: Set(u.enabled),
+        server: Set(to_json(&json_columns::Server::from(&u.server))?),
+        auth: Set(opt_to_json::<AuthConfig, json_columns::AuthConfig>(
+            u.auth.as_ref(),
+        )?),
+        headers: Set(opt_to_json::<HeadersConfig, json_columns::HeadersConfig>(
+            u.headers.as_ref(),
+        )?),
+        plugins: Set(opt_to_json::<PluginsConfig, json_columns::PluginsConfig>(
+            u.plugins.as_ref(),
+        )?),
+        rate_limit: Set(
+            opt_to_json::<RateLimitConfig, json_columns::RateLimitConfig>(u.rate_limit.as_ref())?,
+        ),
+        load_balancing: Set(opt_to_json::<
+            LoadBalancingConfig,
+            json_columns::LoadBalancingConfig,
+        >(u.load_balancing.as_ref())?),
+        circuit_breaker: Set(opt_to_json::<
+            CircuitBreakerConfig,
+            json_columns::CircuitBreakerConfig,
+        >(u.circuit_breaker.as_ref())?),
+        tags: Set(to_json(&u.tags)?),
+    })
+}
+
+impl TryFrom<upstream::Model> for Upstream {
+    type Error = RepositoryError;
+
+    fn try_from(m: upstream::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: m.id,
+            tenant_id: m.tenant_id,
+            alias: m.alias,
+            server: Server::from(from_json::<json_columns::Server>(&m.server)?),
+            protocol: m.protocol,
+            enabled: m.enabled,
+            auth: opt_from_json::<json_columns::AuthConfig, _>(m.auth.as_deref())?,
+            headers: opt_from_json::<json_columns::HeadersConfig, _>(m.headers.as_deref())?,
+            plugins: opt_from_json::<json_columns::PluginsConfig, _>(m.plugins.as_deref())?,
+            rate_limit: opt_from_json::<json_columns::RateLimitConfig, _>(m.rate_limit.as_deref())?,
+            load_balancing: opt_from_json::<json_columns::LoadBalancingConfig, _>(
+                m.load_balancing.as_deref(),
+            )?,
+            circuit_breaker: opt_from_json::<json_columns::CircuitBreakerConfig, _>(
+                m.circuit_breaker.as_deref(),
+            )?,
+            tags: from_json(&m.tags)?,
+        })
+    }
+}
+
+pub(crate) fn route_to_active_model(r: &Route) -> Result<route::ActiveModel, RepositoryError> {
+    Ok(route::ActiveModel {
+        id: Set(r.id),
+        tenant_id: Set(r.tenant_id),
+        upstream_id: Set(r.upstream_id),
+        match_rules: Set(to_json(&json_columns::MatchRules::from(&r.match_rules))?),
+        plugins: Set(opt_to_json::<PluginsConfig, json_columns::PluginsConfig>(
+            r.plugins.as_ref(),
+        )?),
+        rate_limit: Set(
+            opt_to_json::<RateLimitConfig, json_columns::RateLimitConfig>(r.rate_limit.as_ref())?,
+        ),
+        cache: Set(opt_to_json::<CacheConfig, json_columns::CacheConfig>(
+            r.cache.as_ref(),
+        )?),
+        tags: Set(to_json(&r.tags)?),
+        priority: Set(r.priority),
+        enabled: Set(r.enabled),
+    })
+}
+
+impl TryFrom<route::Model> for Route {
+    type Error = RepositoryError;
+
+    fn try_from(m: route::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: m.id,
+            tenant_id: m.tenant_id,
+            upstream_id: m.upstream_id,
+            match_rules: MatchRules::from(from_json::<json_columns::MatchRules>(&m.match_rules)?),
+            plugins: opt_from_json::<json_columns::PluginsConfig, _>(m.plugins.as_deref())?,
+            rate_limit: opt_from_json::<json_columns::RateLimitConfig, _>(m.rate_limit.as_deref())?,
+            cache: opt_from_json::<json_columns::CacheConfig, _>(m.cache.as_deref())?,
+            tags: from_json(&m.tags)?,
+            priority: m.priority,
+            enabled: m.enabled,
+        })
+    }
+}
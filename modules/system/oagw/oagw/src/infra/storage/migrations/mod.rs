@@ -0,0 +1,20 @@
+use sea_orm_migration::prelude::*;
+
+mod m20261018_000001_initial;
+mod m20261018_000002_load_balancing;
+mod m20261018_000003_circuit_breaker;
+mod m20261018_000004_route_cache;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261018_000001_initial::Migration),
+            Box::new(m20261018_000002_load_balancing::Migration),
+            Box::new(m20261018_000003_circuit_breaker::Migration),
+            Box::new(m20261018_000004_route_cache::Migration),
+        ]
+    }
+}
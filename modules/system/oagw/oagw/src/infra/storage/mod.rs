@@ -1,7 +1,16 @@
 pub(crate) mod credential_repo;
+pub(crate) mod db;
+pub(crate) mod entity;
+pub(crate) mod json_columns;
+pub(crate) mod mapper;
+pub(crate) mod migrations;
 pub(crate) mod route_repo;
+pub(crate) mod route_sea_repo;
 pub(crate) mod upstream_repo;
+pub(crate) mod upstream_sea_repo;
 
 pub(crate) use credential_repo::InMemoryCredentialResolver;
 pub(crate) use route_repo::InMemoryRouteRepo;
+pub(crate) use route_sea_repo::SeaOrmRouteRepo;
 pub(crate) use upstream_repo::InMemoryUpstreamRepo;
+pub(crate) use upstream_sea_repo::SeaOrmUpstreamRepo;
@@ -99,52 +99,17 @@ impl RouteRepository for InMemoryRouteRepo {
             .map(|ids| ids.clone())
             .unwrap_or_default();
 
-        let request_method = parse_method(method);
-
-        let mut best: Option<Route> = None;
-        let mut best_path_len = 0;
-        let mut best_priority = i32::MIN;
-
-        for id in &route_ids {
-            let Some(route_ref) = self.store.get(id) else {
-                continue;
-            };
-            let route = route_ref.value();
-
-            // Must match tenant.
-            if route.tenant_id != tenant_id {
-                continue;
-            }
-            // Must be enabled.
-            if !route.enabled {
-                continue;
-            }
-            // Must have HTTP match rules.
-            let Some(http_match) = &route.match_rules.http else {
-                continue;
-            };
-            // Method must match (unknown methods never match).
-            let Some(req_method) = &request_method else {
-                continue;
-            };
-            if !http_match.methods.contains(req_method) {
-                continue;
-            }
-            // Path must be a prefix match.
-            if !path.starts_with(&http_match.path) {
-                continue;
-            }
-
-            let path_len = http_match.path.len();
-            let priority = route.priority;
+        let candidates: Vec<Route> = route_ids
+            .iter()
+            .filter_map(|id| {
+                self.store
+                    .get(id)
+                    .filter(|r| r.tenant_id == tenant_id)
+                    .map(|r| r.clone())
+            })
+            .collect();
 
-            // Select by longest path prefix, then highest priority.
-            if path_len > best_path_len || (path_len == best_path_len && priority > best_priority) {
-                best_path_len = path_len;
-                best_priority = priority;
-                best = Some(route.clone());
-            }
-        }
+        let best = select_best_match(&candidates, method, path).cloned();
 
         best.ok_or(RepositoryError::NotFound {
             entity: "route",
@@ -218,6 +183,55 @@ impl RouteRepository for InMemoryRouteRepo {
     }
 }
 
+/// Pick the route that best matches `method` and `path` among `routes`.
+///
+/// Match criteria: enabled, HTTP match rules present, method matches, path
+/// prefix matches. Among matches the longest path prefix wins, then the
+/// highest priority. Callers are responsible for tenant/upstream filtering.
+pub(crate) fn select_best_match<'a>(
+    routes: impl IntoIterator<Item = &'a Route>,
+    method: &str,
+    path: &str,
+) -> Option<&'a Route> {
+    // Unknown methods never match.
+    let request_method = parse_method(method)?;
+
+    let mut best: Option<&Route> = None;
+    let mut best_path_len = 0;
+    let mut best_priority = i32::MIN;
+
+    for route in routes {
+        if !route.enabled {
+            continue;
+        }
+        let path_len = if let Some(http_match) = &route.match_rules.http {
+            if !http_match.methods.contains(&request_method) || !path.starts_with(&http_match.path)
+            {
+                continue;
+            }
+            http_match.path.len()
+        } else if let Some(grpc_match) = &route.match_rules.grpc {
+            // gRPC calls are `POST /{service}/{method}` and match exactly.
+            if request_method != HttpMethod::Post || path != grpc_match.path() {
+                continue;
+            }
+            path.len()
+        } else {
+            continue;
+        };
+        let priority = route.priority;
+
+        // Select by longest path prefix, then highest priority.
+        if path_len > best_path_len || (path_len == best_path_len && priority > best_priority) {
+            best_path_len = path_len;
+            best_priority = priority;
+            best = Some(route);
+        }
+    }
+
+    best
+}
+
 fn parse_method(s: &str) -> Option<HttpMethod> {
     match s.to_uppercase().as_str() {
         "GET" => Some(HttpMethod::Get),
@@ -231,7 +245,7 @@ fn parse_method(s: &str) -> Option<HttpMethod> {
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{HttpMatch, MatchRules, PathSuffixMode};
+    use crate::domain::model::{GrpcMatch, HttpMatch, MatchRules, PathSuffixMode};
 
     use super::*;
 
@@ -257,6 +271,7 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority,
             enabled: true,
@@ -363,6 +378,48 @@ mod tests {
         assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
     }
 
+    #[tokio::test]
+    async fn find_matching_grpc_service_and_method() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let mut grpc = make_route(tenant, upstream, vec![], "", 0);
+        grpc.match_rules = MatchRules {
+            http: None,
+            grpc: Some(GrpcMatch {
+                service: "example.v1.UserService".into(),
+                method: "GetUser".into(),
+            }),
+        };
+        let catch_all = make_route(tenant, upstream, vec![HttpMethod::Post], "/", 10);
+        repo.create(grpc.clone()).await.unwrap();
+        repo.create(catch_all.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "POST", "/example.v1.UserService/GetUser")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, grpc.id);
+
+        let matched = repo
+            .find_matching(
+                tenant,
+                upstream,
+                "POST",
+                "/example.v1.UserService/ListUsers",
+            )
+            .await
+            .unwrap();
+        assert_eq!(matched.id, catch_all.id);
+
+        repo.delete(tenant, catch_all.id).await.unwrap();
+        let result = repo
+            .find_matching(tenant, upstream, "GET", "/example.v1.UserService/GetUser")
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+    }
+
     #[tokio::test]
     async fn list_by_upstream_returns_correct_set() {
         let repo = InMemoryRouteRepo::new();
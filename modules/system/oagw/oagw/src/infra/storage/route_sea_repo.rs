@@ -0,0 +1,301 @@
+use std::sync::Arc;
+
+use modkit_db::secure::{AccessScope, SecureDeleteExt, SecureEntityExt, secure_insert};
+use sea_orm::sea_query::Expr;
+use sea_orm::{Condition, EntityTrait, Order, QueryFilter};
+use uuid::Uuid;
+
+use crate::domain::model::{ListQuery, Route};
+use crate::domain::repo::{RepositoryError, RouteRepository};
+
+use super::db::{OagwDbProvider, conn_err, scope_err};
+use super::entity::route::{Column, Entity as RouteEntity};
+use super::mapper::route_to_active_model;
+use super::route_repo::select_best_match;
+
+/// SeaORM-backed route repository.
+///
+/// Every query is tenant-scoped through `SecureConn` using an
+/// `AccessScope::for_tenant` built from the caller-supplied tenant id.
+pub struct SeaOrmRouteRepo {
+    db: Arc<OagwDbProvider>,
+}
+
+impl SeaOrmRouteRepo {
+    #[must_use]
+    pub fn new(db: Arc<OagwDbProvider>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl RouteRepository for SeaOrmRouteRepo {
+    async fn create(&self, route: Route) -> Result<Route, RepositoryError> {
+        let scope = AccessScope::for_tenant(route.tenant_id);
+        let am = route_to_active_model(&route)?;
+        let conn = self.db.conn().map_err(conn_err)?;
+
+        secure_insert::<RouteEntity>(am, &scope, &conn)
+            .await
+            .map_err(scope_err)?;
+        Ok(route)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let found = RouteEntity::find()
+            .filter(Condition::all().add(Expr::col(Column::Id).eq(id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .one(&conn)
+            .await
+            .map_err(scope_err)?;
+
+        found
+            .map(Route::try_from)
+            .transpose()?
+            .ok_or(RepositoryError::NotFound {
+                entity: "route",
+                id,
+            })
+    }
+
+    async fn list_by_upstream(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<Route>, RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let rows = RouteEntity::find()
+            .filter(Condition::all().add(Expr::col(Column::UpstreamId).eq(upstream_id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .order_by(Column::Id, Order::Asc)
+            .offset(u64::from(query.skip))
+            .limit(u64::from(query.top))
+            .all(&conn)
+            .await
+            .map_err(scope_err)?;
+        rows.into_iter().map(Route::try_from).collect()
+    }
+
+    async fn find_matching(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        method: &str,
+        path: &str,
+    ) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let rows = RouteEntity::find()
+            .filter(
+                Condition::all()
+                    .add(Expr::col(Column::UpstreamId).eq(upstream_id))
+                    .add(Expr::col(Column::Enabled).eq(true)),
+            )
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .all(&conn)
+            .await
+            .map_err(scope_err)?;
+
+        let candidates = rows
+            .into_iter()
+            .map(Route::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        select_best_match(&candidates, method, path)
+            .cloned()
+            .ok_or(RepositoryError::NotFound {
+                entity: "route",
+                id: Uuid::nil(),
+            })
+    }
+
+    async fn update(&self, route: Route) -> Result<Route, RepositoryError> {
+        let scope = AccessScope::for_tenant(route.tenant_id);
+        let id = route.id;
+
+        self.get_by_id(route.tenant_id, id).await?;
+
+        let am = route_to_active_model(&route)?;
+        let conn = self.db.conn().map_err(conn_err)?;
+        modkit_db::secure::secure_update_with_scope::<RouteEntity>(am, &scope, id, &conn)
+            .await
+            .map_err(scope_err)?;
+        Ok(route)
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let result = RouteEntity::delete_many()
+            .filter(Condition::all().add(Expr::col(Column::Id).eq(id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await
+            .map_err(scope_err)?;
+
+        if result.rows_affected == 0 {
+            return Err(RepositoryError::NotFound {
+                entity: "route",
+                id,
+            });
+        }
+        Ok(())
+    }
+
+    async fn delete_by_upstream(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+    ) -> Result<u64, RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let result = RouteEntity::delete_many()
+            .filter(Condition::all().add(Expr::col(Column::UpstreamId).eq(upstream_id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await
+            .map_err(scope_err)?;
+        Ok(result.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::{GrpcMatch, HttpMatch, HttpMethod, MatchRules, PathSuffixMode};
+    use crate::infra::storage::db::test_db;
+
+    use super::*;
+
+    fn make_route(
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        methods: Vec<HttpMethod>,
+        path: &str,
+        priority: i32,
+    ) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            tenant_id,
+            upstream_id,
+            match_rules: MatchRules {
+                http: Some(HttpMatch {
+                    methods,
+                    path: path.into(),
+                    query_allowlist: vec!["version".into()],
+                    path_suffix_mode: PathSuffixMode::Append,
+                }),
+                grpc: None,
+            },
+            plugins: None,
+            rate_limit: None,
+            cache: None,
+            tags: vec![],
+            priority,
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_and_get_round_trip() {
+        let repo = SeaOrmRouteRepo::new(test_db().await);
+        let tenant = Uuid::new_v4();
+        let mut r = make_route(tenant, Uuid::new_v4(), vec![HttpMethod::Post], "/v1", 3);
+        r.match_rules.grpc = Some(GrpcMatch {
+            service: "pkg.Svc".into(),
+            method: "Call".into(),
+        });
+        repo.create(r.clone()).await.unwrap();
+
+        let fetched = repo.get_by_id(tenant, r.id).await.unwrap();
+        assert_eq!(fetched, r);
+    }
+
+    #[tokio::test]
+    async fn find_matching_longest_prefix_then_priority() {
+        let repo = SeaOrmRouteRepo::new(test_db().await);
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let short = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1", 0);
+        let long = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 0);
+        let long_hi = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 10);
+        let mut disabled = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat/x", 0);
+        disabled.enabled = false;
+        for r in [&short, &long, &long_hi, &disabled] {
+            repo.create(r.clone()).await.unwrap();
+        }
+
+        let m = repo
+            .find_matching(tenant, upstream, "POST", "/v1/chat/x/completions")
+            .await
+            .unwrap();
+        assert_eq!(m.id, long_hi.id);
+
+        let m = repo
+            .find_matching(tenant, upstream, "POST", "/v1/models")
+            .await
+            .unwrap();
+        assert_eq!(m.id, short.id);
+
+        assert!(
+            repo.find_matching(tenant, upstream, "GET", "/v1/chat")
+                .await
+                .is_err()
+        );
+        assert!(
+            repo.find_matching(Uuid::new_v4(), upstream, "POST", "/v1/chat")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn update_and_delete() {
+        let repo = SeaOrmRouteRepo::new(test_db().await);
+        let tenant = Uuid::new_v4();
+        let mut r = make_route(tenant, Uuid::new_v4(), vec![HttpMethod::Get], "/a", 0);
+        repo.create(r.clone()).await.unwrap();
+
+        r.priority = 7;
+        r.tags = vec!["x".into()];
+        repo.update(r.clone()).await.unwrap();
+        assert_eq!(repo.get_by_id(tenant, r.id).await.unwrap(), r);
+
+        assert!(matches!(
+            repo.delete(Uuid::new_v4(), r.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        repo.delete(tenant, r.id).await.unwrap();
+        assert!(repo.get_by_id(tenant, r.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn delete_by_upstream_is_tenant_scoped() {
+        let repo = SeaOrmRouteRepo::new(test_db().await);
+        let t1 = Uuid::new_v4();
+        let t2 = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        for path in ["/a", "/b"] {
+            repo.create(make_route(t1, upstream, vec![HttpMethod::Get], path, 0))
+                .await
+                .unwrap();
+        }
+        let survivor = make_route(t2, upstream, vec![HttpMethod::Get], "/c", 0);
+        repo.create(survivor.clone()).await.unwrap();
+
+        let deleted = repo.delete_by_upstream(t1, upstream).await.unwrap();
+        assert_eq!(deleted, 2);
+
+        let remaining = repo
+            .list_by_upstream(t2, upstream, &ListQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, survivor.id);
+    }
+}
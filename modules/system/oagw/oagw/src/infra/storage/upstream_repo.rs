@@ -97,6 +97,10 @@ impl UpstreamRepository for InMemoryUpstreamRepo {
         Ok(all.into_iter().skip(skip).take(top).collect())
     }
 
+    async fn list_all(&self) -> Result<Vec<Upstream>, RepositoryError> {
+        Ok(self.store.iter().map(|e| e.value().clone()).collect())
+    }
+
     async fn update(&self, upstream: Upstream) -> Result<Upstream, RepositoryError> {
         let id = upstream.id;
         let tenant_id = upstream.tenant_id;
@@ -169,6 +173,7 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
             },
             protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
@@ -177,6 +182,8 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            load_balancing: None,
+            circuit_breaker: None,
             tags: vec![],
         }
     }
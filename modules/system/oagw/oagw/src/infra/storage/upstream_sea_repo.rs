@@ -0,0 +1,323 @@
+use std::sync::Arc;
+
+use modkit_db::secure::{AccessScope, SecureDeleteExt, SecureEntityExt, secure_insert};
+use sea_orm::sea_query::Expr;
+use sea_orm::{Condition, EntityTrait, Order, QueryFilter};
+use uuid::Uuid;
+
+use crate::domain::model::{ListQuery, Upstream};
+use crate::domain::repo::{RepositoryError, UpstreamRepository};
+
+use super::db::{OagwDbProvider, conn_err, scope_err};
+use super::entity::upstream::{Column, Entity as UpstreamEntity};
+use super::mapper::upstream_to_active_model;
+
+/// SeaORM-backed upstream repository.
+///
+/// Every query is tenant-scoped through `SecureConn` using an
+/// `AccessScope::for_tenant` built from the caller-supplied tenant id.
+pub struct SeaOrmUpstreamRepo {
+    db: Arc<OagwDbProvider>,
+}
+
+impl SeaOrmUpstreamRepo {
+    #[must_use]
+    pub fn new(db: Arc<OagwDbProvider>) -> Self {
+        Self { db }
+    }
+
+    async fn find_one(
+        &self,
+        tenant_id: Uuid,
+        cond: Condition,
+    ) -> Result<Option<Upstream>, RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let found = UpstreamEntity::find()
+            .filter(cond)
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .one(&conn)
+            .await
+            .map_err(scope_err)?;
+        found.map(Upstream::try_from).transpose()
+    }
+}
+
+#[async_trait::async_trait]
+impl UpstreamRepository for SeaOrmUpstreamRepo {
+    async fn create(&self, upstream: Upstream) -> Result<Upstream, RepositoryError> {
+        let scope = AccessScope::for_tenant(upstream.tenant_id);
+        let am = upstream_to_active_model(&upstream)?;
+        let conn = self.db.conn().map_err(conn_err)?;
+
+        secure_insert::<UpstreamEntity>(am, &scope, &conn)
+            .await
+            .map_err(|e| match scope_err(e) {
+                RepositoryError::Conflict(_) => RepositoryError::Conflict(format!(
+                    "alias '{}' already exists for tenant",
+                    upstream.alias
+                )),
+                other => other,
+            })?;
+        Ok(upstream)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<Upstream, RepositoryError> {
+        self.find_one(
+            tenant_id,
+            Condition::all().add(Expr::col(Column::Id).eq(id)),
+        )
+        .await?
+        .ok_or(RepositoryError::NotFound {
+            entity: "upstream",
+            id,
+        })
+    }
+
+    async fn get_by_alias(
+        &self,
+        tenant_id: Uuid,
+        alias: &str,
+    ) -> Result<Upstream, RepositoryError> {
+        self.find_one(
+            tenant_id,
+            Condition::all().add(Expr::col(Column::Alias).eq(alias)),
+        )
+        .await?
+        .ok_or(RepositoryError::NotFound {
+            entity: "upstream",
+            id: Uuid::nil(),
+        })
+    }
+
+    async fn list(
+        &self,
+        tenant_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<Upstream>, RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let rows = UpstreamEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .order_by(Column::Id, Order::Asc)
+            .offset(u64::from(query.skip))
+            .limit(u64::from(query.top))
+            .all(&conn)
+            .await
+            .map_err(scope_err)?;
+        rows.into_iter().map(Upstream::try_from).collect()
+    }
+
+    async fn list_all(&self) -> Result<Vec<Upstream>, RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let rows = UpstreamEntity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .all(&conn)
+            .await
+            .map_err(scope_err)?;
+        rows.into_iter().map(Upstream::try_from).collect()
+    }
+
+    async fn update(&self, upstream: Upstream) -> Result<Upstream, RepositoryError> {
+        let scope = AccessScope::for_tenant(upstream.tenant_id);
+        let id = upstream.id;
+
+        // Existence check first so a missing row maps to NotFound rather than
+        // the generic "not accessible" scope denial.
+        self.get_by_id(upstream.tenant_id, id).await?;
+
+        let am = upstream_to_active_model(&upstream)?;
+        let conn = self.db.conn().map_err(conn_err)?;
+        modkit_db::secure::secure_update_with_scope::<UpstreamEntity>(am, &scope, id, &conn)
+            .await
+            .map_err(|e| match scope_err(e) {
+                RepositoryError::Conflict(_) => RepositoryError::Conflict(format!(
+                    "alias '{}' already exists for tenant",
+                    upstream.alias
+                )),
+                other => other,
+            })?;
+        Ok(upstream)
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        let conn = self.db.conn().map_err(conn_err)?;
+        let result = UpstreamEntity::delete_many()
+            .filter(Condition::all().add(Expr::col(Column::Id).eq(id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await
+            .map_err(scope_err)?;
+
+        if result.rows_affected == 0 {
+            return Err(RepositoryError::NotFound {
+                entity: "upstream",
+                id,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::domain::model::{
+        AuthConfig, Endpoint, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
+        RateLimitStrategy, Scheme, Server, SharingMode, SustainedRate, Window,
+    };
+    use crate::infra::storage::db::test_db;
+
+    use super::*;
+
+    fn make_upstream(tenant_id: Uuid, alias: &str) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id,
+            alias: alias.into(),
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            load_balancing: None,
+            circuit_breaker: None,
+            tags: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn create_and_get_round_trip_preserves_nested_config() {
+        let repo = SeaOrmUpstreamRepo::new(test_db().await);
+        let tenant = Uuid::new_v4();
+        let mut u = make_upstream(tenant, "openai");
+        u.auth = Some(AuthConfig {
+            plugin_type: "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.apikey.v1".into(),
+            sharing: SharingMode::Inherit,
+            config: Some(HashMap::from([("header".into(), "Authorization".into())])),
+        });
+        u.rate_limit = Some(RateLimitConfig {
+            sharing: SharingMode::Enforce,
+            algorithm: RateLimitAlgorithm::TokenBucket,
+            sustained: SustainedRate {
+                rate: 10,
+                window: Window::Minute,
+            },
+            burst: None,
+            scope: RateLimitScope::Tenant,
+            strategy: RateLimitStrategy::Reject,
+            cost: 1,
+            queue: None,
+            degrade: None,
+            response_headers: true,
+        });
+        u.tags = vec!["llm".into()];
+
+        repo.create(u.clone()).await.unwrap();
+
+        let fetched = repo.get_by_id(tenant, u.id).await.unwrap();
+        assert_eq!(fetched, u);
+        let by_alias = repo.get_by_alias(tenant, "openai").await.unwrap();
+        assert_eq!(by_alias.id, u.id);
+    }
+
+    #[tokio::test]
+    async fn alias_uniqueness_same_tenant() {
+        let repo = SeaOrmUpstreamRepo::new(test_db().await);
+        let tenant = Uuid::new_v4();
+
+        repo.create(make_upstream(tenant, "openai")).await.unwrap();
+        let err = repo.create(make_upstream(tenant, "openai")).await;
+        assert!(matches!(err, Err(RepositoryError::Conflict(_))));
+
+        // Other tenants may reuse the alias.
+        repo.create(make_upstream(Uuid::new_v4(), "openai"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn update_changes_alias() {
+        let repo = SeaOrmUpstreamRepo::new(test_db().await);
+        let tenant = Uuid::new_v4();
+        let mut u = make_upstream(tenant, "openai");
+        repo.create(u.clone()).await.unwrap();
+
+        u.alias = "openai-v2".into();
+        u.enabled = false;
+        let updated = repo.update(u.clone()).await.unwrap();
+        assert_eq!(updated.alias, "openai-v2");
+
+        assert!(repo.get_by_alias(tenant, "openai").await.is_err());
+        let fetched = repo.get_by_alias(tenant, "openai-v2").await.unwrap();
+        assert!(!fetched.enabled);
+    }
+
+    #[tokio::test]
+    async fn update_missing_returns_not_found() {
+        let repo = SeaOrmUpstreamRepo::new(test_db().await);
+        let result = repo.update(make_upstream(Uuid::new_v4(), "ghost")).await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn cross_tenant_isolation() {
+        let repo = SeaOrmUpstreamRepo::new(test_db().await);
+        let owner = Uuid::new_v4();
+        let attacker = Uuid::new_v4();
+        let u = make_upstream(owner, "openai");
+        repo.create(u.clone()).await.unwrap();
+
+        assert!(repo.get_by_id(attacker, u.id).await.is_err());
+        assert!(matches!(
+            repo.delete(attacker, u.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(repo.get_by_id(owner, u.id).await.is_ok());
+
+        repo.delete(owner, u.id).await.unwrap();
+        assert!(repo.get_by_id(owner, u.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_with_pagination_is_sorted_by_id() {
+        let repo = SeaOrmUpstreamRepo::new(test_db().await);
+        let tenant = Uuid::new_v4();
+        for i in 0..5 {
+            repo.create(make_upstream(tenant, &format!("svc-{i}")))
+                .await
+                .unwrap();
+        }
+        repo.create(make_upstream(Uuid::new_v4(), "other"))
+            .await
+            .unwrap();
+
+        let all = repo
+            .list(tenant, &ListQuery { top: 50, skip: 0 })
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 5);
+        for w in all.windows(2) {
+            assert!(w[0].id < w[1].id, "IDs must be in ascending order");
+        }
+
+        let page = repo
+            .list(tenant, &ListQuery { top: 2, skip: 1 })
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].id, all[1].id);
+    }
+}
@@ -0,0 +1,89 @@
+//! Tenant ancestry sources for hierarchical configuration.
+
+use std::sync::Arc;
+
+#[cfg(any(test, feature = "test-utils"))]
+use dashmap::DashMap;
+use modkit::client_hub::ClientHub;
+use modkit_security::SecurityContext;
+use tenant_resolver_sdk::{GetAncestorsOptions, TenantResolverClient, TenantResolverError};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::tenant_hierarchy::TenantHierarchy;
+
+/// Upper bound on ancestry depth; guards against cycles in misconfigured trees.
+#[cfg(any(test, feature = "test-utils"))]
+const MAX_DEPTH: usize = 64;
+
+/// Ancestry backed by the tenant-resolver module.
+///
+/// The client is looked up per call, so the tenant resolver may start after
+/// OAGW. Without a registered resolver every tenant is treated as a root.
+pub(crate) struct TenantResolverHierarchy {
+    hub: Arc<ClientHub>,
+}
+
+impl TenantResolverHierarchy {
+    pub(crate) fn new(hub: Arc<ClientHub>) -> Self {
+        Self { hub }
+    }
+}
+
+#[async_trait::async_trait]
+impl TenantHierarchy for TenantResolverHierarchy {
+    async fn ancestors(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let Ok(resolver) = self.hub.get::<dyn TenantResolverClient>() else {
+            return Ok(Vec::new());
+        };
+        match resolver
+            .get_ancestors(ctx, tenant_id, &GetAncestorsOptions::default())
+            .await
+        {
+            Ok(resp) => Ok(resp.ancestors.into_iter().map(|t| t.id).collect()),
+            Err(TenantResolverError::TenantNotFound { .. }) => Ok(Vec::new()),
+            Err(e) => Err(DomainError::Internal {
+                message: format!("failed to resolve ancestors of tenant {tenant_id}: {e}"),
+            }),
+        }
+    }
+}
+
+/// In-memory parent links, for tests.
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Default)]
+pub(crate) struct InMemoryTenantHierarchy {
+    parents: DashMap<Uuid, Uuid>,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl InMemoryTenantHierarchy {
+    pub(crate) fn set_parent(&self, child: Uuid, parent: Uuid) {
+        self.parents.insert(child, parent);
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+#[async_trait::async_trait]
+impl TenantHierarchy for InMemoryTenantHierarchy {
+    async fn ancestors(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let mut chain = Vec::new();
+        let mut current = tenant_id;
+        while let Some(parent) = self.parents.get(&current).map(|p| *p) {
+            if chain.len() >= MAX_DEPTH || parent == tenant_id || chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent);
+            current = parent;
+        }
+        Ok(chain)
+    }
+}
@@ -6,6 +6,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -38,6 +39,82 @@ fn default_cost() -> u32 {
     1
 }
 
+fn default_queue_max_depth() -> u32 {
+    100
+}
+
+fn default_queue_timeout() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn default_fallback_status() -> u16 {
+    503
+}
+
+fn default_fallback_content_type() -> String {
+    "application/json".to_string()
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_health_interval_secs() -> u32 {
+    10
+}
+
+fn default_health_timeout_secs() -> u32 {
+    2
+}
+
+fn default_healthy_threshold() -> u32 {
+    2
+}
+
+fn default_unhealthy_threshold() -> u32 {
+    3
+}
+
+fn default_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_base_ejection_secs() -> u32 {
+    30
+}
+
+fn default_max_ejection_percent() -> u32 {
+    50
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_success_threshold() -> u32 {
+    3
+}
+
+fn default_open_timeout_secs() -> u32 {
+    30
+}
+
+fn default_half_open_max_requests() -> u32 {
+    3
+}
+
+fn default_failure_status_codes() -> Vec<u16> {
+    vec![500, 502, 503, 504]
+}
+
+fn default_cache_max_ttl_secs() -> u32 {
+    3600
+}
+
+fn default_cache_max_entry_bytes() -> u64 {
+    1024 * 1024
+}
+
 #[derive(Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 enum Scheme {
@@ -56,6 +133,8 @@ struct Endpoint {
     host: String,
     #[serde(default = "default_port")]
     port: u16,
+    #[serde(default = "default_weight")]
+    weight: u32,
 }
 
 #[derive(Deserialize)]
@@ -63,6 +142,94 @@ struct Server {
     endpoints: Vec<Endpoint>,
 }
 
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    LeastInFlight,
+    Weighted,
+}
+
+#[derive(Deserialize)]
+struct HealthCheckConfig {
+    path: String,
+    #[serde(default = "default_health_interval_secs")]
+    interval_secs: u32,
+    #[serde(default = "default_health_timeout_secs")]
+    timeout_secs: u32,
+    #[serde(default = "default_healthy_threshold")]
+    healthy_threshold: u32,
+    #[serde(default = "default_unhealthy_threshold")]
+    unhealthy_threshold: u32,
+}
+
+#[derive(Deserialize)]
+struct OutlierDetectionConfig {
+    #[serde(default = "default_consecutive_failures")]
+    consecutive_failures: u32,
+    #[serde(default = "default_base_ejection_secs")]
+    base_ejection_secs: u32,
+    #[serde(default = "default_max_ejection_percent")]
+    max_ejection_percent: u32,
+}
+
+#[derive(Deserialize, Default)]
+struct LoadBalancingConfig {
+    #[serde(default)]
+    strategy: LoadBalancingStrategy,
+    #[serde(default)]
+    health_check: Option<HealthCheckConfig>,
+    #[serde(default)]
+    outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+#[derive(Deserialize)]
+struct FailureConditions {
+    #[serde(default = "default_failure_status_codes")]
+    status_codes: Vec<u16>,
+    #[serde(default = "default_true")]
+    timeout: bool,
+    #[serde(default = "default_true")]
+    connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: default_failure_status_codes(),
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum CircuitBreakerScope {
+    #[default]
+    Global,
+    PerEndpoint,
+}
+
+#[derive(Deserialize)]
+struct CircuitBreakerConfig {
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default = "default_failure_threshold")]
+    failure_threshold: u32,
+    #[serde(default = "default_success_threshold")]
+    success_threshold: u32,
+    #[serde(default = "default_open_timeout_secs")]
+    timeout_secs: u32,
+    #[serde(default = "default_half_open_max_requests")]
+    half_open_max_requests: u32,
+    #[serde(default)]
+    failure_conditions: FailureConditions,
+    #[serde(default)]
+    scope: CircuitBreakerScope,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 enum SharingMode {
@@ -123,12 +290,26 @@ struct HeadersConfig {
     response: Option<ResponseHeaderRules>,
 }
 
+#[derive(Deserialize)]
+struct CacheConfig {
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default)]
+    default_ttl_secs: u32,
+    #[serde(default = "default_cache_max_ttl_secs")]
+    max_ttl_secs: u32,
+    #[serde(default = "default_cache_max_entry_bytes")]
+    max_entry_bytes: u64,
+}
+
 #[derive(Deserialize, Default)]
 struct PluginsConfig {
     #[serde(default)]
     sharing: SharingMode,
     #[serde(default)]
     items: Vec<String>,
+    #[serde(default)]
+    config: HashMap<String, HashMap<String, String>>,
 }
 
 #[derive(Deserialize, Default)]
@@ -196,6 +377,38 @@ struct RateLimitConfig {
     strategy: RateLimitStrategy,
     #[serde(default = "default_cost")]
     cost: u32,
+    #[serde(default)]
+    queue: Option<QueueConfig>,
+    #[serde(default)]
+    degrade: Option<DegradeConfig>,
+    #[serde(default = "default_true")]
+    response_headers: bool,
+}
+
+#[derive(Deserialize)]
+struct QueueConfig {
+    #[serde(default = "default_queue_max_depth")]
+    max_depth: u32,
+    #[serde(
+        default = "default_queue_timeout",
+        with = "modkit_utils::humantime_serde"
+    )]
+    timeout: Duration,
+}
+
+#[derive(Deserialize)]
+struct DegradeConfig {
+    fallback_response: FallbackResponse,
+}
+
+#[derive(Deserialize)]
+struct FallbackResponse {
+    #[serde(default = "default_fallback_status")]
+    status: u16,
+    #[serde(default = "default_fallback_content_type")]
+    content_type: String,
+    #[serde(default)]
+    body: String,
 }
 
 #[derive(Deserialize)]
@@ -257,6 +470,10 @@ struct UpstreamPayload {
     #[serde(default)]
     rate_limit: Option<RateLimitConfig>,
     #[serde(default)]
+    load_balancing: Option<LoadBalancingConfig>,
+    #[serde(default)]
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default = "default_true")]
     enabled: bool,
@@ -274,6 +491,8 @@ struct RoutePayload {
     #[serde(default)]
     rate_limit: Option<RateLimitConfig>,
     #[serde(default)]
+    cache: Option<CacheConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default)]
     priority: i32,
@@ -303,6 +522,7 @@ impl From<Endpoint> for domain::Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -315,6 +535,69 @@ impl From<Server> for domain::Server {
     }
 }
 
+impl From<LoadBalancingStrategy> for domain::LoadBalancingStrategy {
+    fn from(v: LoadBalancingStrategy) -> Self {
+        match v {
+            LoadBalancingStrategy::RoundRobin => Self::RoundRobin,
+            LoadBalancingStrategy::LeastInFlight => Self::LeastInFlight,
+            LoadBalancingStrategy::Weighted => Self::Weighted,
+        }
+    }
+}
+
+impl From<HealthCheckConfig> for domain::HealthCheckConfig {
+    fn from(v: HealthCheckConfig) -> Self {
+        Self {
+            path: v.path,
+            interval_secs: v.interval_secs,
+            timeout_secs: v.timeout_secs,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+        }
+    }
+}
+
+impl From<OutlierDetectionConfig> for domain::OutlierDetectionConfig {
+    fn from(v: OutlierDetectionConfig) -> Self {
+        Self {
+            consecutive_failures: v.consecutive_failures,
+            base_ejection_secs: v.base_ejection_secs,
+            max_ejection_percent: v.max_ejection_percent,
+        }
+    }
+}
+
+impl From<LoadBalancingConfig> for domain::LoadBalancingConfig {
+    fn from(v: LoadBalancingConfig) -> Self {
+        Self {
+            strategy: v.strategy.into(),
+            health_check: v.health_check.map(Into::into),
+            outlier_detection: v.outlier_detection.map(Into::into),
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            success_threshold: v.success_threshold,
+            timeout_secs: v.timeout_secs,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: domain::FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: match v.scope {
+                CircuitBreakerScope::Global => domain::CircuitBreakerScope::Global,
+                CircuitBreakerScope::PerEndpoint => domain::CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
 impl From<SharingMode> for domain::SharingMode {
     fn from(v: SharingMode) -> Self {
         match v {
@@ -445,6 +728,29 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
             scope: v.scope.into(),
             strategy: v.strategy.into(),
             cost: v.cost,
+            queue: v.queue.map(|q| domain::QueueConfig {
+                max_depth: q.max_depth,
+                timeout: q.timeout,
+            }),
+            degrade: v.degrade.map(|d| domain::DegradeConfig {
+                fallback_response: domain::FallbackResponse {
+                    status: d.fallback_response.status,
+                    content_type: d.fallback_response.content_type,
+                    body: d.fallback_response.body,
+                },
+            }),
+            response_headers: v.response_headers,
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_ttl_secs: v.max_ttl_secs,
+            max_entry_bytes: v.max_entry_bytes,
         }
     }
 }
@@ -454,6 +760,7 @@ impl From<PluginsConfig> for domain::PluginsConfig {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -520,6 +827,8 @@ impl From<UpstreamPayload> for ProvisionedUpstream {
                 headers: p.headers.map(Into::into),
                 plugins: p.plugins.map(Into::into),
                 rate_limit: p.rate_limit.map(Into::into),
+                load_balancing: p.load_balancing.map(Into::into),
+                circuit_breaker: p.circuit_breaker.map(Into::into),
                 tags: p.tags,
                 enabled: p.enabled,
             },
@@ -536,6 +845,7 @@ impl From<RoutePayload> for ProvisionedRoute {
                 match_rules: p.match_rules.into(),
                 plugins: p.plugins.map(Into::into),
                 rate_limit: p.rate_limit.map(Into::into),
+                cache: p.cache.map(Into::into),
                 tags: p.tags,
                 priority: p.priority,
                 enabled: p.enabled,
@@ -1,8 +1,14 @@
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
-use crate::config::OagwConfig;
+use crate::config::{OagwConfig, StorageBackend};
 use crate::domain::credential::CredentialResolver;
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    CreateRouteRequest, CreateUpstreamRequest, ListQuery, Route, UpdateRouteRequest,
+    UpdateUpstreamRequest, Upstream,
+};
+use crate::domain::repo::{RouteRepository, UpstreamRepository};
 use crate::domain::type_catalog::oagw_gts_entities;
 use crate::domain::type_provisioning::TypeProvisioningService;
 use crate::infra::type_provisioning::TypeProvisioningServiceImpl;
@@ -12,6 +18,7 @@ use modkit::contracts::SystemCapability;
 use modkit::{Module, ModuleCtx, RestApiCapability};
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 use types_registry_sdk::{RegisterResult, RegisterSummary, TypesRegistryClient};
 
@@ -19,8 +26,15 @@ use crate::api::rest::routes;
 use crate::domain::services::{
     ControlPlaneService, ControlPlaneServiceImpl, DataPlaneService, ServiceGatewayClientV1Facade,
 };
-use crate::infra::proxy::DataPlaneServiceImpl;
-use crate::infra::storage::{InMemoryCredentialResolver, InMemoryRouteRepo, InMemoryUpstreamRepo};
+use crate::domain::upstream_observer::UpstreamObserver;
+use crate::infra::proxy::health::HealthChecker;
+use crate::infra::proxy::load_balancer::LoadBalancer;
+use crate::infra::proxy::{DataPlaneServiceImpl, ResponseCache};
+use crate::infra::storage::{
+    InMemoryCredentialResolver, InMemoryRouteRepo, InMemoryUpstreamRepo, SeaOrmRouteRepo,
+    SeaOrmUpstreamRepo,
+};
+use crate::infra::tenant_hierarchy::TenantResolverHierarchy;
 
 /// Shared application state injected into all handlers.
 #[derive(Clone)]
@@ -34,12 +48,14 @@ pub struct AppState {
 #[modkit::module(
     name = "oagw",
     deps = ["types-registry"],
-    capabilities = [system, rest]
+    capabilities = [stateful, system, rest, db],
+    lifecycle(entry = "serve")
 )]
 pub struct OutboundApiGatewayModule {
     state: arc_swap::ArcSwapOption<AppState>,
     registry_client: OnceLock<Arc<dyn TypesRegistryClient>>,
     type_provisioning: OnceLock<Arc<dyn TypeProvisioningService>>,
+    health_checker: OnceLock<HealthChecker>,
 }
 
 impl Default for OutboundApiGatewayModule {
@@ -48,39 +64,101 @@ impl Default for OutboundApiGatewayModule {
             state: arc_swap::ArcSwapOption::from(None),
             registry_client: OnceLock::new(),
             type_provisioning: OnceLock::new(),
+            health_checker: OnceLock::new(),
         }
     }
 }
 
+impl OutboundApiGatewayModule {
+    /// Background loop: active health probes for upstream endpoints.
+    async fn serve(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        let checker = self
+            .health_checker
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("HealthChecker not set — init() must run first"))?;
+        info!("OAGW endpoint health checker started");
+        checker.run(cancel).await;
+        info!("OAGW endpoint health checker stopped");
+        Ok(())
+    }
+}
+
+impl modkit::contracts::DatabaseCapability for OutboundApiGatewayModule {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        info!("Providing OAGW database migrations");
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
 #[async_trait]
 impl Module for OutboundApiGatewayModule {
     async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
         info!("Initializing Outbound API Gateway module");
 
         let cfg: OagwConfig = ctx.config()?;
-        info!("OAGW config: proxy_timeout_secs={}", cfg.proxy_timeout_secs);
+        info!(
+            "OAGW config: proxy_timeout_secs={}, storage={:?}",
+            cfg.proxy_timeout_secs, cfg.storage
+        );
 
         // -- Control Plane init --
-        let upstream_repo = Arc::new(InMemoryUpstreamRepo::new());
-        let route_repo = Arc::new(InMemoryRouteRepo::new());
-        let cp: Arc<dyn ControlPlaneService> =
-            Arc::new(ControlPlaneServiceImpl::new(upstream_repo, route_repo));
+        let (upstream_repo, route_repo): (Arc<dyn UpstreamRepository>, Arc<dyn RouteRepository>) =
+            match cfg.storage {
+                StorageBackend::Memory => (
+                    Arc::new(InMemoryUpstreamRepo::new()),
+                    Arc::new(InMemoryRouteRepo::new()),
+                ),
+                StorageBackend::Database => {
+                    let db = Arc::new(ctx.db_required()?);
+                    (
+                        Arc::new(SeaOrmUpstreamRepo::new(db.clone())),
+                        Arc::new(SeaOrmRouteRepo::new(db)),
+                    )
+                }
+            };
 
+        // Credentials come from config (or the secret providers behind it) and stay in
+        // memory, whatever the storage backend.
         let cred_resolver = InMemoryCredentialResolver::new();
         for (secret_ref, value) in &cfg.credentials {
             info!("Seeding credential: {secret_ref}");
             cred_resolver.set(secret_ref.clone(), value.clone());
         }
         let cred_resolver: Arc<dyn CredentialResolver> = Arc::new(cred_resolver);
+        let response_cache = Arc::new(ResponseCache::new(
+            cfg.response_cache.max_entries,
+            cfg.response_cache.max_bytes,
+        ));
+        // Endpoint pools follow upstream writes, and start with the stored
+        // upstreams so health probes run before the first proxied request.
+        let load_balancer = Arc::new(LoadBalancer::new());
+        for upstream in upstream_repo
+            .list_all()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load upstreams: {e}"))?
+        {
+            load_balancer.upstream_saved(&upstream);
+        }
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(
+            ControlPlaneServiceImpl::new(upstream_repo, route_repo)
+                .with_tenant_hierarchy(Arc::new(TenantResolverHierarchy::new(ctx.client_hub())))
+                .with_cache_invalidator(response_cache.clone())
+                .with_upstream_observer(load_balancer.clone()),
+        );
 
         ctx.client_hub()
             .register::<dyn CredentialResolver>(cred_resolver.clone());
 
         // -- Data Plane init --
-        let dp: Arc<dyn DataPlaneService> = Arc::new(
-            DataPlaneServiceImpl::new(cp.clone(), cred_resolver)?
-                .with_request_timeout(Duration::from_secs(cfg.proxy_timeout_secs)),
-        );
+        let dp_impl = DataPlaneServiceImpl::new(cp.clone(), cred_resolver)?
+            .with_request_timeout(Duration::from_secs(cfg.proxy_timeout_secs))
+            .with_response_cache(response_cache)
+            .with_load_balancer(load_balancer);
+        self.health_checker
+            .set(dp_impl.health_checker())
+            .map_err(|_| anyhow::anyhow!("HealthChecker already set"))?;
+        let dp: Arc<dyn DataPlaneService> = Arc::new(dp_impl);
 
         // -- Facade (for external SDK consumers) --
         let oagw: Arc<dyn ServiceGatewayClientV1> =
@@ -144,7 +222,10 @@ impl SystemCapability for OutboundApiGatewayModule {
         let provisioning: Arc<dyn TypeProvisioningService> =
             Arc::new(TypeProvisioningServiceImpl::new(registry));
 
-        // -- Materialize provisioned upstreams and routes into in-memory repos --
+        // -- Materialize provisioned upstreams and routes into the repos --
+        // With persistent storage the definitions survive restarts, so
+        // provisioning reconciles: missing entities are created and stored
+        // ones are updated when their definition changed.
         let app_state = self
             .state
             .load()
@@ -158,19 +239,28 @@ impl SystemCapability for OutboundApiGatewayModule {
             let ctx = SecurityContext::builder()
                 .subject_tenant_id(u.tenant_id)
                 .build()?;
-            let created = app_state
-                .cp
-                .create_upstream(&ctx, u.request.clone())
-                .await
-                .map_err(|e| {
-                    anyhow::anyhow!("Failed to provision upstream (tenant={}): {e}", u.tenant_id)
-                })?;
-            info!(
-                id = %created.id,
-                tenant_id = %u.tenant_id,
-                alias = %created.alias,
-                "Provisioned upstream from types-registry"
-            );
+            match provision_upstream(app_state.cp.as_ref(), &ctx, &u.request).await {
+                Ok(outcome) => {
+                    let upstream = outcome.entity();
+                    info!(
+                        id = %upstream.id,
+                        tenant_id = %u.tenant_id,
+                        alias = %upstream.alias,
+                        outcome = outcome.label(),
+                        "Provisioned upstream from types-registry"
+                    );
+                }
+                Err(DomainError::Conflict { detail }) => {
+                    tracing::warn!(
+                        tenant_id = %u.tenant_id,
+                        detail = %detail,
+                        "Provisioned upstream conflicts with an unrelated upstream, skipping"
+                    );
+                }
+                Err(e) => {
+                    anyhow::bail!("Failed to provision upstream (tenant={}): {e}", u.tenant_id)
+                }
+            }
         }
 
         let routes = provisioning.list_routes().await?;
@@ -178,16 +268,15 @@ impl SystemCapability for OutboundApiGatewayModule {
             let ctx = SecurityContext::builder()
                 .subject_tenant_id(r.tenant_id)
                 .build()?;
-            let created = app_state
-                .cp
-                .create_route(&ctx, r.request.clone())
+            let outcome = provision_route(app_state.cp.as_ref(), &ctx, &r.request)
                 .await
                 .map_err(|e| {
                     anyhow::anyhow!("Failed to provision route (tenant={}): {e}", r.tenant_id)
                 })?;
             info!(
-                id = %created.id,
+                id = %outcome.entity().id,
                 tenant_id = %r.tenant_id,
+                outcome = outcome.label(),
                 "Provisioned route from types-registry"
             );
         }
@@ -206,6 +295,154 @@ impl SystemCapability for OutboundApiGatewayModule {
     }
 }
 
+/// Result of reconciling one provisioned definition with the stored entities.
+enum Provisioned<T> {
+    Created(T),
+    Updated(T),
+    Unchanged(T),
+}
+
+impl<T> Provisioned<T> {
+    fn entity(&self) -> &T {
+        match self {
+            Self::Created(e) | Self::Updated(e) | Self::Unchanged(e) => e,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "created",
+            Self::Updated(_) => "updated",
+            Self::Unchanged(_) => "unchanged",
+        }
+    }
+}
+
+const ALL: ListQuery = ListQuery {
+    top: u32::MAX,
+    skip: 0,
+};
+
+/// Whether `current` differs from what `def` asks for. Optional settings the
+/// definition leaves unset keep their stored value, as with a regular update.
+fn upstream_differs(current: &Upstream, def: &CreateUpstreamRequest) -> bool {
+    fn set_and_differs<T: PartialEq>(def: Option<&T>, current: Option<&T>) -> bool {
+        def.is_some() && def != current
+    }
+    current.server != def.server
+        || current.protocol != def.protocol
+        || current.tags != def.tags
+        || current.enabled != def.enabled
+        || set_and_differs(def.auth.as_ref(), current.auth.as_ref())
+        || set_and_differs(def.headers.as_ref(), current.headers.as_ref())
+        || set_and_differs(def.plugins.as_ref(), current.plugins.as_ref())
+        || set_and_differs(def.rate_limit.as_ref(), current.rate_limit.as_ref())
+        || set_and_differs(def.load_balancing.as_ref(), current.load_balancing.as_ref())
+        || set_and_differs(
+            def.circuit_breaker.as_ref(),
+            current.circuit_breaker.as_ref(),
+        )
+}
+
+fn route_differs(current: &Route, def: &CreateRouteRequest) -> bool {
+    current.priority != def.priority
+        || current.tags != def.tags
+        || current.enabled != def.enabled
+        || (def.plugins.is_some() && def.plugins != current.plugins)
+        || (def.rate_limit.is_some() && def.rate_limit != current.rate_limit)
+        || (def.cache.is_some() && def.cache != current.cache)
+}
+
+/// Create the upstream of a provisioned definition, or update the stored one
+/// (matched by alias, or by server when the alias is generated) if the
+/// definition changed since it was stored.
+///
+/// # Errors
+/// `Conflict` if the alias is taken by an upstream with a different server.
+async fn provision_upstream(
+    cp: &dyn ControlPlaneService,
+    ctx: &SecurityContext,
+    def: &CreateUpstreamRequest,
+) -> Result<Provisioned<Upstream>, DomainError> {
+    let conflict = match cp.create_upstream(ctx, def.clone()).await {
+        Ok(created) => return Ok(Provisioned::Created(created)),
+        Err(e @ DomainError::Conflict { .. }) => e,
+        Err(e) => return Err(e),
+    };
+
+    let Some(current) =
+        cp.list_upstreams(ctx, &ALL)
+            .await?
+            .into_iter()
+            .find(|u| match &def.alias {
+                Some(alias) => &u.alias == alias,
+                None => u.server == def.server,
+            })
+    else {
+        return Err(conflict);
+    };
+    if !upstream_differs(&current, def) {
+        return Ok(Provisioned::Unchanged(current));
+    }
+
+    let update = UpdateUpstreamRequest {
+        server: Some(def.server.clone()),
+        protocol: Some(def.protocol.clone()),
+        alias: None,
+        auth: def.auth.clone(),
+        headers: def.headers.clone(),
+        plugins: def.plugins.clone(),
+        rate_limit: def.rate_limit.clone(),
+        load_balancing: def.load_balancing.clone(),
+        circuit_breaker: def.circuit_breaker.clone(),
+        tags: Some(def.tags.clone()),
+        enabled: Some(def.enabled),
+    };
+    cp.update_upstream(ctx, current.id, update)
+        .await
+        .map(Provisioned::Updated)
+}
+
+/// Create the route of a provisioned definition, or update the stored route
+/// with the same upstream and match rules if the definition changed.
+async fn provision_route(
+    cp: &dyn ControlPlaneService,
+    ctx: &SecurityContext,
+    def: &CreateRouteRequest,
+) -> Result<Provisioned<Route>, DomainError> {
+    let current = cp
+        .list_routes(ctx, def.upstream_id, &ALL)
+        .await
+        .ok()
+        .and_then(|routes| {
+            routes
+                .into_iter()
+                .find(|r| r.match_rules == def.match_rules)
+        });
+    let Some(current) = current else {
+        return cp
+            .create_route(ctx, def.clone())
+            .await
+            .map(Provisioned::Created);
+    };
+    if !route_differs(&current, def) {
+        return Ok(Provisioned::Unchanged(current));
+    }
+
+    let update = UpdateRouteRequest {
+        match_rules: None,
+        plugins: def.plugins.clone(),
+        rate_limit: def.rate_limit.clone(),
+        cache: def.cache.clone(),
+        tags: Some(def.tags.clone()),
+        priority: Some(def.priority),
+        enabled: Some(def.enabled),
+    };
+    cp.update_route(ctx, current.id, update)
+        .await
+        .map(Provisioned::Updated)
+}
+
 impl RestApiCapability for OutboundApiGatewayModule {
     fn register_rest(
         &self,
@@ -227,3 +464,129 @@ impl RestApiCapability for OutboundApiGatewayModule {
         Ok(router)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::{
+        Endpoint, HttpMatch, HttpMethod, MatchRules, PathSuffixMode, Scheme, Server,
+    };
+    use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+    use uuid::Uuid;
+
+    fn make_cp() -> ControlPlaneServiceImpl {
+        ControlPlaneServiceImpl::new(
+            Arc::new(InMemoryUpstreamRepo::new()),
+            Arc::new(InMemoryRouteRepo::new()),
+        )
+    }
+
+    fn ctx() -> SecurityContext {
+        SecurityContext::builder()
+            .subject_tenant_id(Uuid::from_u128(1))
+            .subject_id(Uuid::from_u128(2))
+            .build()
+            .unwrap()
+    }
+
+    fn upstream_def(host: &str) -> CreateUpstreamRequest {
+        CreateUpstreamRequest {
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: host.into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            alias: Some("openai".into()),
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            load_balancing: None,
+            circuit_breaker: None,
+            tags: vec![],
+            enabled: true,
+        }
+    }
+
+    fn route_def(upstream_id: Uuid, priority: i32) -> CreateRouteRequest {
+        CreateRouteRequest {
+            upstream_id,
+            match_rules: MatchRules {
+                http: Some(HttpMatch {
+                    methods: vec![HttpMethod::Post],
+                    path: "/v1/chat/completions".into(),
+                    query_allowlist: vec![],
+                    path_suffix_mode: PathSuffixMode::Append,
+                }),
+                grpc: None,
+            },
+            plugins: None,
+            rate_limit: None,
+            cache: None,
+            tags: vec![],
+            priority,
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn changed_upstream_definition_updates_stored_upstream() {
+        let cp = make_cp();
+        let ctx = ctx();
+
+        let created = provision_upstream(&cp, &ctx, &upstream_def("api.openai.com"))
+            .await
+            .unwrap();
+        assert_eq!(created.label(), "created");
+        let id = created.entity().id;
+
+        let again = provision_upstream(&cp, &ctx, &upstream_def("api.openai.com"))
+            .await
+            .unwrap();
+        assert_eq!(again.label(), "unchanged");
+
+        let mut changed = upstream_def("eu.api.openai.com");
+        changed.tags = vec!["eu".into()];
+        let updated = provision_upstream(&cp, &ctx, &changed).await.unwrap();
+        assert_eq!(updated.label(), "updated");
+        assert_eq!(updated.entity().id, id);
+
+        let stored = cp.get_upstream(&ctx, id).await.unwrap();
+        assert_eq!(stored.server.endpoints[0].host, "eu.api.openai.com");
+        assert_eq!(stored.tags, vec!["eu".to_owned()]);
+        assert_eq!(cp.list_upstreams(&ctx, &ALL).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn changed_route_definition_updates_stored_route() {
+        let cp = make_cp();
+        let ctx = ctx();
+        let upstream = cp
+            .create_upstream(&ctx, upstream_def("api.openai.com"))
+            .await
+            .unwrap();
+
+        let created = provision_route(&cp, &ctx, &route_def(upstream.id, 0))
+            .await
+            .unwrap();
+        assert_eq!(created.label(), "created");
+        let again = provision_route(&cp, &ctx, &route_def(upstream.id, 0))
+            .await
+            .unwrap();
+        assert_eq!(again.label(), "unchanged");
+
+        let updated = provision_route(&cp, &ctx, &route_def(upstream.id, 10))
+            .await
+            .unwrap();
+        assert_eq!(updated.label(), "updated");
+        assert_eq!(updated.entity().id, created.entity().id);
+
+        let routes = cp.list_routes(&ctx, upstream.id, &ALL).await.unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].priority, 10);
+    }
+}
@@ -41,6 +41,14 @@ impl<'a> ApiV1<'a> {
         )
     }
 
+    pub fn get_upstream_status(&self, id: &str) -> RequestCase<'a> {
+        RequestCase::new(
+            self.harness,
+            Method::GET,
+            format!("/oagw/v1/upstreams/{id}/status"),
+        )
+    }
+
     pub fn delete_upstream(&self, id: &str) -> RequestCase<'a> {
         RequestCase::new(
             self.harness,
@@ -1,5 +1,6 @@
 //! Top-level test harness that wires all components together.
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -46,6 +47,21 @@ impl AppHarness {
     pub(crate) fn router(&self) -> &axum::Router {
         &self.router
     }
+
+    /// Serve the REST API on `127.0.0.1:0` for tests that need a real
+    /// connection (WebSocket upgrades). The server lives as long as the
+    /// test runtime.
+    pub async fn serve(&self) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind test server");
+        let addr = listener.local_addr().expect("failed to get local addr");
+        let router = self.router.clone();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+        addr
+    }
 }
 
 /// Builder for [`AppHarness`].
@@ -53,6 +69,7 @@ impl AppHarness {
 pub struct AppHarnessBuilder {
     credentials: Vec<(String, String)>,
     request_timeout: Option<Duration>,
+    tenant_parents: Vec<(Uuid, Uuid)>,
 }
 
 impl AppHarnessBuilder {
@@ -66,6 +83,12 @@ impl AppHarnessBuilder {
         self
     }
 
+    /// Make `parent` the parent tenant of `child`.
+    pub fn with_tenant_parent(mut self, child: Uuid, parent: Uuid) -> Self {
+        self.tenant_parents.push((child, parent));
+        self
+    }
+
     pub async fn build(self) -> AppHarness {
         let hub = ClientHub::new();
 
@@ -73,6 +96,7 @@ impl AppHarnessBuilder {
         if !self.credentials.is_empty() {
             cp_builder = cp_builder.with_credentials(self.credentials);
         }
+        cp_builder = cp_builder.with_tenant_parents(self.tenant_parents);
 
         let mut dp_builder = TestDpBuilder::new();
         if let Some(timeout) = self.request_timeout {
@@ -1,7 +1,7 @@
 //! Mock upstream server for integration tests.
 //!
 //! Simulates upstream services: OpenAI-compatible HTTP JSON, SSE streaming,
-//! error conditions, WebSocket, WebTransport stub.
+//! error conditions, gRPC (h2c), WebSocket, WebTransport stub.
 //!
 //! # Usage
 //! ```ignore
@@ -265,7 +265,12 @@ impl MockUpstream {
             .route("/error/500", get(error_500))
             // Response header test
             .route("/response-headers", get(response_with_bad_headers))
-            // WebSocket (future use)
+            // gRPC over h2c
+            .route("/oagw.test.v1.Echo/Unary", post(grpc_unary))
+            .route("/oagw.test.v1.Echo/ServerStream", post(grpc_server_stream))
+            .route("/oagw.test.v1.Echo/Fail", post(grpc_fail))
+            .route("/oagw.test.v1.Echo/Bidi", post(grpc_bidi))
+            // WebSocket
             .route("/ws/echo", get(ws_echo))
             // WebTransport stub (future use)
             .route("/wt/stub", get(wt_stub))
@@ -565,7 +570,126 @@ async fn error_500(
 }
 
 // ---------------------------------------------------------------------------
-// WebSocket handlers (future use — OAGW WS proxy not in this phase)
+// gRPC handlers
+// ---------------------------------------------------------------------------
+
+/// Build a gRPC response: `messages` as data frames followed by `trailers`.
+/// Selected request headers are echoed back as `x-received-*` headers.
+fn grpc_response(
+    request_headers: &HeaderMap,
+    messages: Vec<Bytes>,
+    trailers: &[(&'static str, &'static str)],
+) -> axum::response::Response {
+    let mut builder = axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/grpc");
+    for name in ["te", "grpc-timeout", "authorization"] {
+        if let Some(value) = request_headers.get(name) {
+            builder = builder.header(format!("x-received-{name}"), value);
+        }
+    }
+    let trailers: HeaderMap = trailers
+        .iter()
+        .map(|(k, v)| {
+            (
+                axum::http::HeaderName::from_static(k),
+                axum::http::HeaderValue::from_static(v),
+            )
+        })
+        .collect();
+    let frames = messages
+        .into_iter()
+        .map(http_body::Frame::data)
+        .chain(std::iter::once(http_body::Frame::trailers(trailers)))
+        .map(Ok::<_, std::convert::Infallible>);
+    builder
+        .body(axum::body::Body::new(http_body_util::StreamBody::new(
+            futures_util::stream::iter(frames),
+        )))
+        .expect("response builder should not fail")
+}
+
+async fn grpc_unary(
+    State(state): State<Arc<SharedState>>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> axum::response::Response {
+    state
+        .record("POST", &uri.to_string(), &headers, &body)
+        .await;
+    grpc_response(
+        &headers,
+        vec![body],
+        &[("grpc-status", "0"), ("x-echo-trailer", "done")],
+    )
+}
+
+async fn grpc_server_stream(
+    State(state): State<Arc<SharedState>>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> axum::response::Response {
+    state
+        .record("POST", &uri.to_string(), &headers, &body)
+        .await;
+    grpc_response(
+        &headers,
+        vec![body.clone(), body.clone(), body],
+        &[("grpc-status", "0")],
+    )
+}
+
+/// Echo each request data frame as soon as it arrives, like a bidirectional
+/// streaming call.
+async fn grpc_bidi(
+    State(state): State<Arc<SharedState>>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    body: axum::body::Body,
+) -> axum::response::Response {
+    use futures_util::StreamExt;
+
+    state.record("POST", &uri.to_string(), &headers, &[]).await;
+    let trailers: HeaderMap = std::iter::once((
+        axum::http::HeaderName::from_static("grpc-status"),
+        axum::http::HeaderValue::from_static("0"),
+    ))
+    .collect();
+    let frames = body
+        .into_data_stream()
+        .map(|chunk| chunk.map(http_body::Frame::data))
+        .chain(futures_util::stream::once(std::future::ready(Ok(
+            http_body::Frame::trailers(trailers),
+        ))));
+    axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/grpc")
+        .body(axum::body::Body::new(http_body_util::StreamBody::new(
+            frames,
+        )))
+        .expect("response builder should not fail")
+}
+
+async fn grpc_fail(
+    State(state): State<Arc<SharedState>>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    state.record("POST", &uri.to_string(), &headers, &[]).await;
+    grpc_response(
+        &headers,
+        Vec::new(),
+        &[
+            ("grpc-status", "14"),
+            ("grpc-message", "backend%20unavailable"),
+        ],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// WebSocket handlers
 // ---------------------------------------------------------------------------
 
 async fn ws_echo(
@@ -573,9 +697,18 @@ async fn ws_echo(
     OriginalUri(uri): OriginalUri,
     headers: HeaderMap,
     ws: WebSocketUpgrade,
-) -> impl IntoResponse {
+) -> axum::response::Response {
     state.record("GET", &uri.to_string(), &headers, &[]).await;
-    ws.on_upgrade(handle_ws_echo)
+    let mut response = ws
+        .protocols(["oagw.test"])
+        .on_upgrade(handle_ws_echo)
+        .into_response();
+    if let Some(auth) = headers.get("authorization") {
+        response
+            .headers_mut()
+            .insert("x-received-authorization", auth.clone());
+    }
+    response
 }
 
 async fn handle_ws_echo(mut socket: WebSocket) {
@@ -9,12 +9,14 @@ pub mod response;
 
 pub use body::{IntoBody, Json};
 pub use harness::{AppHarness, AppHarnessBuilder};
-pub use mock::{MockBody, MockGuard, MockResponse, MockUpstream, RecordedRequest, RouteKey};
+pub use mock::{
+    MockBody, MockGuard, MockResponse, MockUpstream, RecordedRequest, RouteKey, shared_mock,
+};
 pub use request::RequestCase;
 pub use response::TestResponse;
 
 pub use crate::domain::gts_helpers::{format_route_gts, format_upstream_gts, parse_resource_gts};
 pub use crate::domain::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, TestAppState, TestCpBuilder, TestCredentialResolver, TestDpBuilder,
-    build_test_app_state, build_test_gateway,
+    APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID, TestAppState, TestCpBuilder,
+    TestCredentialResolver, TestDpBuilder, build_test_app_state, build_test_gateway,
 };
@@ -3,6 +3,7 @@
 use axum::body::Body;
 use http::StatusCode;
 use http::header::HeaderMap;
+use http_body_util::BodyExt;
 use serde::de::DeserializeOwned;
 
 /// Eagerly-collected HTTP response with sync assertion methods.
@@ -10,20 +11,24 @@ pub struct TestResponse {
     status: StatusCode,
     headers: HeaderMap,
     body_bytes: Vec<u8>,
+    trailers: Option<HeaderMap>,
 }
 
 impl TestResponse {
-    /// Consume an `http::Response<Body>`, collecting the body into bytes.
+    /// Consume an `http::Response<Body>`, collecting the body into bytes
+    /// and keeping its trailers, if any.
     pub async fn from_response(resp: http::Response<Body>) -> Self {
         let (parts, body) = resp.into_parts();
-        let body_bytes = axum::body::to_bytes(body, usize::MAX)
+        let collected = body
+            .collect()
             .await
-            .expect("failed to collect response body")
-            .to_vec();
+            .expect("failed to collect response body");
+        let trailers = collected.trailers().cloned();
         Self {
             status: parts.status,
             headers: parts.headers,
-            body_bytes,
+            body_bytes: collected.to_bytes().to_vec(),
+            trailers,
         }
     }
 
@@ -73,6 +78,10 @@ impl TestResponse {
         &self.headers
     }
 
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
     pub fn bytes(&self) -> &[u8] {
         &self.body_bytes
     }
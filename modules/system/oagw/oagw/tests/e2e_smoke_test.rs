@@ -359,6 +359,256 @@ async fn e2e_rate_limit_returns_429() {
         .await;
 }
 
+async fn setup_rate_limited_upstream(h: &AppHarness, alias: &str, rate_limit: serde_json::Value) {
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "enabled": true,
+            "tags": [],
+            "rate_limit": rate_limit
+        }))
+        .expect_status(201)
+        .await;
+    let uid = resp.json()["id"].as_str().unwrap().to_string();
+
+    let (_, upstream_uuid) = parse_resource_gts(&uid).unwrap();
+    h.api_v1()
+        .post_route()
+        .with_body(serde_json::json!({
+            "upstream_id": upstream_uuid,
+            "match": {
+                "http": {
+                    "methods": ["GET"],
+                    "path": "/v1/models"
+                }
+            },
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+}
+
+// 18.2: Sliding window — requests over the windowed limit are rejected with 429.
+#[tokio::test]
+async fn e2e_sliding_window_rejects_over_limit() {
+    let h = AppHarness::builder().build().await;
+    setup_rate_limited_upstream(
+        &h,
+        "e2e-sliding",
+        serde_json::json!({
+            "algorithm": "sliding_window",
+            "sustained": {"rate": 3, "window": "minute"},
+            "strategy": "reject"
+        }),
+    )
+    .await;
+
+    for _ in 0..3 {
+        h.api_v1()
+            .proxy_get("e2e-sliding", "v1/models")
+            .expect_status(200)
+            .await;
+    }
+    let resp = h
+        .api_v1()
+        .proxy_get("e2e-sliding", "v1/models")
+        .expect_status(429)
+        .await;
+    assert!(resp.headers().contains_key("retry-after"));
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.rate_limit.exceeded.v1"
+    );
+}
+
+// 18.5 B: strategy=queue — a queued request that gets no capacity times out with 503.
+#[tokio::test]
+async fn e2e_rate_limit_queue_timeout_returns_503() {
+    let h = AppHarness::builder().build().await;
+    setup_rate_limited_upstream(
+        &h,
+        "e2e-queue",
+        serde_json::json!({
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "queue",
+            "queue": {"max_depth": 10, "timeout": "100ms"}
+        }),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("e2e-queue", "v1/models")
+        .expect_status(200)
+        .await;
+    let resp = h
+        .api_v1()
+        .proxy_get("e2e-queue", "v1/models")
+        .expect_status(503)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+    assert!(resp.headers().contains_key("retry-after"));
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.queue.timeout.v1"
+    );
+}
+
+// 18.5 B: strategy=queue — a queued request proceeds once capacity frees up.
+#[tokio::test]
+async fn e2e_rate_limit_queue_waits_for_capacity() {
+    let h = AppHarness::builder().build().await;
+    setup_rate_limited_upstream(
+        &h,
+        "e2e-queue-ok",
+        serde_json::json!({
+            "sustained": {"rate": 10, "window": "second"},
+            "strategy": "queue",
+            "queue": {"max_depth": 10, "timeout": "2s"}
+        }),
+    )
+    .await;
+
+    for _ in 0..11 {
+        h.api_v1()
+            .proxy_get("e2e-queue-ok", "v1/models")
+            .expect_status(200)
+            .await;
+    }
+}
+
+// 18.5 C: strategy=degrade — exceeded requests get the configured fallback response.
+#[tokio::test]
+async fn e2e_rate_limit_degrade_serves_fallback() {
+    let h = AppHarness::builder().build().await;
+    setup_rate_limited_upstream(
+        &h,
+        "e2e-degrade",
+        serde_json::json!({
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "degrade",
+            "degrade": {
+                "fallback_response": {
+                    "status": 503,
+                    "body": "{\"error\": \"Service temporarily degraded\"}"
+                }
+            }
+        }),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("e2e-degrade", "v1/models")
+        .expect_status(200)
+        .await;
+    let resp = h
+        .api_v1()
+        .proxy_get("e2e-degrade", "v1/models")
+        .expect_status(503)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+    resp.assert_header("content-type", "application/json");
+    assert_eq!(resp.json()["error"], "Service temporarily degraded");
+}
+
+// 18.1: Successful rate-limited responses carry X-RateLimit-* headers.
+#[tokio::test]
+async fn e2e_rate_limit_headers_on_success() {
+    let h = AppHarness::builder().build().await;
+    setup_rate_limited_upstream(
+        &h,
+        "e2e-rl-headers",
+        serde_json::json!({
+            "sustained": {"rate": 10, "window": "minute"},
+            "burst": {"capacity": 5}
+        }),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("e2e-rl-headers", "v1/models")
+        .expect_status(200)
+        .await;
+    resp.assert_header("x-ratelimit-limit", "5");
+    resp.assert_header("x-ratelimit-remaining", "4");
+    let reset: u64 = resp.headers()["x-ratelimit-reset"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert!(reset > now && reset <= now + 7);
+}
+
+// 18.1.1: response_headers=false omits X-RateLimit-*; 429 keeps Retry-After.
+#[tokio::test]
+async fn e2e_rate_limit_headers_can_be_disabled() {
+    let h = AppHarness::builder().build().await;
+    setup_rate_limited_upstream(
+        &h,
+        "e2e-rl-no-headers",
+        serde_json::json!({
+            "sustained": {"rate": 1, "window": "second"},
+            "burst": {"capacity": 1},
+            "response_headers": false
+        }),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("e2e-rl-no-headers", "v1/models")
+        .expect_status(200)
+        .await;
+    assert!(
+        !resp
+            .headers()
+            .keys()
+            .any(|name| name.as_str().starts_with("x-ratelimit-"))
+    );
+
+    let resp = h
+        .api_v1()
+        .proxy_get("e2e-rl-no-headers", "v1/models")
+        .expect_status(429)
+        .await;
+    assert!(resp.headers().contains_key("retry-after"));
+    resp.assert_header("x-oagw-error-source", "gateway");
+}
+
+#[tokio::test]
+async fn e2e_queue_strategy_without_queue_config_returns_400() {
+    let h = AppHarness::builder().build().await;
+    h.api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "e2e-queue-missing",
+            "enabled": true,
+            "tags": [],
+            "rate_limit": {
+                "sustained": {"rate": 1, "window": "minute"},
+                "strategy": "queue"
+            }
+        }))
+        .expect_status(400)
+        .await;
+}
+
 // 10.5: E2E — management lifecycle.
 #[tokio::test]
 async fn e2e_management_lifecycle() {
@@ -55,6 +55,7 @@ async fn get_upstream_by_gts_id() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -113,6 +114,7 @@ async fn update_upstream_preserves_id() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -152,6 +154,7 @@ async fn delete_upstream_returns_204() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -182,6 +185,7 @@ async fn create_route_success() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -235,6 +239,7 @@ async fn list_upstreams_with_pagination() {
                             scheme: oagw_sdk::Scheme::Https,
                             host: format!("host{i}.example.com"),
                             port: 443,
+                            weight: 1,
                         }],
                     },
                     "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -520,3 +525,73 @@ async fn list_routes_filters_by_upstream() {
         assert_eq!(route["upstream_id"].as_str().unwrap(), uuid_a);
     }
 }
+
+// GET upstream status -> 200 with one entry per endpoint.
+#[tokio::test]
+async fn get_upstream_status_lists_endpoints() {
+    let h = AppHarness::builder().build().await;
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            h.security_context().clone(),
+            oagw_sdk::CreateUpstreamRequest::builder(
+                oagw_sdk::Server {
+                    endpoints: vec![
+                        oagw_sdk::Endpoint {
+                            scheme: oagw_sdk::Scheme::Https,
+                            host: "a.example.com".into(),
+                            port: 443,
+                            weight: 1,
+                        },
+                        oagw_sdk::Endpoint {
+                            scheme: oagw_sdk::Scheme::Https,
+                            host: "b.example.com".into(),
+                            port: 443,
+                            weight: 3,
+                        },
+                    ],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("status-test")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let gts_id = format_upstream_gts(upstream.id);
+    let resp = h
+        .api_v1()
+        .get_upstream_status(&gts_id)
+        .expect_status(200)
+        .await;
+    let json = resp.json();
+    assert_eq!(json["id"].as_str().unwrap(), gts_id);
+    let endpoints = json["endpoints"].as_array().unwrap();
+    assert_eq!(endpoints.len(), 2);
+    assert_eq!(endpoints[0]["host"], "a.example.com");
+    assert_eq!(endpoints[0]["healthy"], true);
+    assert_eq!(endpoints[0]["ejected"], false);
+}
+
+// Endpoints with mismatched ports -> 400.
+#[tokio::test]
+async fn create_upstream_mixed_ports_returns_400() {
+    let h = AppHarness::builder().build().await;
+
+    h.api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [
+                    {"host": "a.example.com", "port": 443, "scheme": "https"},
+                    {"host": "b.example.com", "port": 8443, "scheme": "https"}
+                ]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "mixed-ports"
+        }))
+        .expect_status(400)
+        .await;
+}
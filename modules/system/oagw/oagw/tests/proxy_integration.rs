@@ -1,15 +1,22 @@
 use http::{Method, StatusCode};
 use oagw::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, AppHarness, MockBody, MockGuard, MockResponse, parse_resource_gts,
+    APIKEY_AUTH_PLUGIN_ID, AppHarness, CORS_GUARD_PLUGIN_ID, MockBody, MockGuard, MockResponse,
+    parse_resource_gts,
 };
 use oagw_sdk::Body;
-use oagw_sdk::api::ErrorSource;
+use oagw_sdk::SecurityContext;
+use oagw_sdk::api::{ClientIp, ErrorSource, ResponseTrailers};
+use oagw_sdk::error::ServiceGatewayError;
+use oagw_sdk::ws::WebSocketMessage;
 use oagw_sdk::{
-    BurstConfig, CreateRouteRequest, CreateUpstreamRequest, Endpoint, HttpMatch, HttpMethod,
-    MatchRules, PathSuffixMode, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
-    RateLimitStrategy, Scheme, Server, SharingMode, SustainedRate, Window,
+    BurstConfig, CacheConfig, CircuitBreakerConfig, CircuitBreakerScope, CreateRouteRequest,
+    CreateUpstreamRequest, Endpoint, FailureConditions, GrpcMatch, HttpMatch, HttpMethod,
+    MatchRules, PathSuffixMode, PluginsConfig, QueueConfig, RateLimitAlgorithm, RateLimitConfig,
+    RateLimitScope, RateLimitStrategy, Scheme, Server, SharingMode, SustainedRate,
+    UpdateRouteRequest, Window,
 };
 use serde_json::json;
+use uuid::Uuid;
 
 async fn setup_openai_mock() -> AppHarness {
     let h = AppHarness::builder()
@@ -129,6 +136,7 @@ async fn proxy_injects_auth_header() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -197,6 +205,117 @@ async fn proxy_injects_auth_header() {
     assert_eq!(auth_header, "Bearer sk-test123");
 }
 
+// 6.13: HMAC signatures are computed after a queued request is admitted,
+// so upstreams checking timestamp skew see the send time.
+#[tokio::test]
+async fn proxy_hmac_signs_queued_request_after_admission() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/models",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"data": []})),
+        },
+    );
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://hmac-key".into(), "signing-key".into())])
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("hmac-queued")
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.hmac.v1".into(),
+                sharing: SharingMode::Private,
+                config: Some(
+                    [("secret_ref".into(), "cred://hmac-key".into())]
+                        .into_iter()
+                        .collect(),
+                ),
+            })
+            .rate_limit(RateLimitConfig {
+                sustained: SustainedRate {
+                    rate: 1,
+                    window: Window::Second,
+                },
+                strategy: RateLimitStrategy::Queue,
+                queue: Some(QueueConfig {
+                    max_depth: 1,
+                    timeout: std::time::Duration::from_secs(5),
+                }),
+                ..per_minute(1, RateLimitScope::Tenant, SharingMode::Private)
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/models"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let send = || {
+        let req = http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/hmac-queued{}", guard.path("/v1/models")))
+            .body(Body::Empty)
+            .unwrap();
+        h.facade().proxy_request(ctx.clone(), req)
+    };
+    assert_eq!(send().await.unwrap().status(), StatusCode::OK);
+    let queued_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert_eq!(send().await.unwrap().status(), StatusCode::OK);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 2);
+    let signed_at: u64 = recorded[1]
+        .headers
+        .iter()
+        .find(|(k, _)| k == "x-timestamp")
+        .map(|(_, v)| v.parse().unwrap())
+        .expect("x-timestamp header missing");
+    // The second request waits about a second for the token bucket.
+    assert!(
+        signed_at > queued_at,
+        "signed at {signed_at}, queued at {queued_at}"
+    );
+}
+
 // 6.14: SSE streaming — proxy to dynamic SSE mock via MockGuard.
 #[tokio::test]
 async fn proxy_sse_streaming() {
@@ -230,6 +349,7 @@ async fn proxy_sse_streaming() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -346,6 +466,7 @@ async fn proxy_disabled_upstream_returns_503() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: 9999,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -387,6 +508,7 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -403,6 +525,9 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                 scope: RateLimitScope::Tenant,
                 strategy: RateLimitStrategy::Reject,
                 cost: 1,
+                queue: None,
+                degrade: None,
+                response_headers: true,
             })
             .build(),
         )
@@ -453,6 +578,318 @@ async fn proxy_rate_limit_exceeded_returns_429() {
     }
 }
 
+fn tenant_ctx(tenant_id: Uuid) -> SecurityContext {
+    SecurityContext::builder()
+        .subject_tenant_id(tenant_id)
+        .subject_id(Uuid::new_v4())
+        .build()
+        .unwrap()
+}
+
+fn per_minute(rate: u32, scope: RateLimitScope, sharing: SharingMode) -> RateLimitConfig {
+    RateLimitConfig {
+        sharing,
+        algorithm: RateLimitAlgorithm::TokenBucket,
+        sustained: SustainedRate {
+            rate,
+            window: Window::Minute,
+        },
+        burst: None,
+        scope,
+        strategy: RateLimitStrategy::Reject,
+        cost: 1,
+        queue: None,
+        degrade: None,
+        response_headers: true,
+    }
+}
+
+/// Create an upstream with a `GET /v1/models` route in the caller's tenant.
+/// Returns the upstream id.
+async fn create_limited_route(
+    h: &AppHarness,
+    ctx: &SecurityContext,
+    alias: &str,
+    upstream_limit: Option<RateLimitConfig>,
+    route_limit: Option<RateLimitConfig>,
+) -> Uuid {
+    let mut upstream = CreateUpstreamRequest::builder(
+        Server {
+            endpoints: vec![Endpoint {
+                scheme: Scheme::Http,
+                host: "127.0.0.1".into(),
+                port: h.mock_port(),
+                weight: 1,
+            }],
+        },
+        "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+    )
+    .alias(alias);
+    if let Some(rl) = upstream_limit {
+        upstream = upstream.rate_limit(rl);
+    }
+    let upstream = h
+        .facade()
+        .create_upstream(ctx.clone(), upstream.build())
+        .await
+        .unwrap();
+
+    let mut route = CreateRouteRequest::builder(
+        upstream.id,
+        MatchRules {
+            http: Some(HttpMatch {
+                methods: vec![HttpMethod::Get],
+                path: "/v1/models".into(),
+                query_allowlist: vec![],
+                path_suffix_mode: PathSuffixMode::Append,
+            }),
+            grpc: None,
+        },
+    );
+    if let Some(rl) = route_limit {
+        route = route.rate_limit(rl);
+    }
+    h.facade()
+        .create_route(ctx.clone(), route.build())
+        .await
+        .unwrap();
+    upstream.id
+}
+
+async fn get_models(
+    h: &AppHarness,
+    ctx: &SecurityContext,
+    alias: &str,
+    client_ip: Option<&str>,
+) -> Result<StatusCode, ServiceGatewayError> {
+    let mut req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/{alias}/v1/models"))
+        .body(Body::Empty)
+        .unwrap();
+    if let Some(ip) = client_ip {
+        req.extensions_mut().insert(ClientIp(ip.parse().unwrap()));
+    }
+    h.facade()
+        .proxy_request(ctx.clone(), req)
+        .await
+        .map(|resp| resp.status())
+}
+
+fn assert_rate_limited(result: Result<StatusCode, ServiceGatewayError>) {
+    assert!(
+        matches!(result, Err(ServiceGatewayError::RateLimitExceeded { .. })),
+        "expected rate limit error, got {result:?}"
+    );
+}
+
+// 18.3: User scope keeps a separate bucket per subject within a tenant.
+#[tokio::test]
+async fn proxy_user_scope_isolates_subjects() {
+    let h = AppHarness::builder().build().await;
+    let alice = h.security_context().clone();
+    let bob = tenant_ctx(alice.subject_tenant_id());
+    create_limited_route(
+        &h,
+        &alice,
+        "rl-user-scope",
+        Some(per_minute(1, RateLimitScope::User, SharingMode::Private)),
+        None,
+    )
+    .await;
+
+    assert_eq!(
+        get_models(&h, &alice, "rl-user-scope", None).await.unwrap(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_models(&h, &bob, "rl-user-scope", None).await.unwrap(),
+        StatusCode::OK
+    );
+    assert_rate_limited(get_models(&h, &alice, "rl-user-scope", None).await);
+}
+
+// 18.3: IP scope keys on the client address reported by the transport.
+#[tokio::test]
+async fn proxy_ip_scope_isolates_client_addresses() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    create_limited_route(
+        &h,
+        &ctx,
+        "rl-ip-scope",
+        Some(per_minute(1, RateLimitScope::Ip, SharingMode::Private)),
+        None,
+    )
+    .await;
+
+    for ip in ["198.51.100.1", "198.51.100.2"] {
+        assert_eq!(
+            get_models(&h, &ctx, "rl-ip-scope", Some(ip)).await.unwrap(),
+            StatusCode::OK
+        );
+    }
+    assert_rate_limited(get_models(&h, &ctx, "rl-ip-scope", Some("198.51.100.1")).await);
+}
+
+// 18.3: A stricter route limit applies on top of the upstream limit.
+#[tokio::test]
+async fn proxy_route_limit_combines_with_upstream_limit() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    create_limited_route(
+        &h,
+        &ctx,
+        "rl-route-merge",
+        Some(per_minute(
+            100,
+            RateLimitScope::Tenant,
+            SharingMode::Private,
+        )),
+        Some(per_minute(1, RateLimitScope::Route, SharingMode::Private)),
+    )
+    .await;
+
+    assert_eq!(
+        get_models(&h, &ctx, "rl-route-merge", None).await.unwrap(),
+        StatusCode::OK
+    );
+    assert_rate_limited(get_models(&h, &ctx, "rl-route-merge", None).await);
+}
+
+// 18.3: The upstream limit is one aggregate bucket across all its routes.
+#[tokio::test]
+async fn proxy_upstream_limit_is_shared_across_routes() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    let upstream_id = create_limited_route(
+        &h,
+        &ctx,
+        "rl-aggregate",
+        Some(per_minute(2, RateLimitScope::Tenant, SharingMode::Private)),
+        Some(per_minute(100, RateLimitScope::Route, SharingMode::Private)),
+    )
+    .await;
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream_id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/status".into(),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Append,
+                    }),
+                    grpc: None,
+                },
+            )
+            .rate_limit(per_minute(100, RateLimitScope::Route, SharingMode::Private))
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        assert_eq!(
+            get_models(&h, &ctx, "rl-aggregate", None).await.unwrap(),
+            StatusCode::OK
+        );
+    }
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri("/rl-aggregate/status/200")
+        .body(Body::Empty)
+        .unwrap();
+    assert_rate_limited(
+        h.facade()
+            .proxy_request(ctx.clone(), req)
+            .await
+            .map(|resp| resp.status()),
+    );
+}
+
+// 18.6: Child limit is min(parent enforced limit, child limit).
+#[tokio::test]
+async fn proxy_hierarchical_min_merge_descendant_overrides() {
+    let (parent, child) = (Uuid::new_v4(), Uuid::new_v4());
+    let h = AppHarness::builder()
+        .with_tenant_parent(child, parent)
+        .build()
+        .await;
+    let (parent_ctx, child_ctx) = (tenant_ctx(parent), tenant_ctx(child));
+    create_limited_route(
+        &h,
+        &parent_ctx,
+        "rl-hierarchy",
+        Some(per_minute(
+            1000,
+            RateLimitScope::Tenant,
+            SharingMode::Enforce,
+        )),
+        None,
+    )
+    .await;
+    create_limited_route(
+        &h,
+        &child_ctx,
+        "rl-hierarchy",
+        Some(per_minute(2, RateLimitScope::Tenant, SharingMode::Private)),
+        None,
+    )
+    .await;
+
+    for _ in 0..2 {
+        assert_eq!(
+            get_models(&h, &child_ctx, "rl-hierarchy", None)
+                .await
+                .unwrap(),
+            StatusCode::OK
+        );
+    }
+    assert_rate_limited(get_models(&h, &child_ctx, "rl-hierarchy", None).await);
+}
+
+// 18.6: An enforced ancestor limit caps a looser descendant limit.
+#[tokio::test]
+async fn proxy_hierarchical_enforced_ancestor_limit_caps_descendant() {
+    let (parent, child) = (Uuid::new_v4(), Uuid::new_v4());
+    let h = AppHarness::builder()
+        .with_tenant_parent(child, parent)
+        .build()
+        .await;
+    let (parent_ctx, child_ctx) = (tenant_ctx(parent), tenant_ctx(child));
+    create_limited_route(
+        &h,
+        &parent_ctx,
+        "rl-hierarchy-cap",
+        Some(per_minute(1, RateLimitScope::Tenant, SharingMode::Enforce)),
+        None,
+    )
+    .await;
+    create_limited_route(
+        &h,
+        &child_ctx,
+        "rl-hierarchy-cap",
+        Some(per_minute(
+            1000,
+            RateLimitScope::Tenant,
+            SharingMode::Private,
+        )),
+        None,
+    )
+    .await;
+
+    assert_eq!(
+        get_models(&h, &child_ctx, "rl-hierarchy-cap", None)
+            .await
+            .unwrap(),
+        StatusCode::OK
+    );
+    assert_rate_limited(get_models(&h, &child_ctx, "rl-hierarchy-cap", None).await);
+}
+
 // 6.16: Upstream timeout — proxy to gated mock that never responds, assert 504.
 // Uses multi_thread runtime so the timer driver runs on a dedicated thread,
 // preventing stalls when other test binaries compete for CPU.
@@ -486,6 +923,7 @@ async fn proxy_upstream_timeout_returns_504() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -546,6 +984,7 @@ async fn proxy_query_allowlist_allowed_param_succeeds() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -600,6 +1039,7 @@ async fn proxy_query_allowlist_unknown_param_rejected() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -660,6 +1100,7 @@ async fn proxy_nonexistent_auth_plugin_returns_error() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -740,6 +1181,7 @@ async fn proxy_recorded_request_has_correct_uri_and_body() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -806,6 +1248,7 @@ async fn proxy_response_headers_sanitized() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -898,6 +1341,7 @@ async fn proxy_path_suffix_disabled_rejects_extra_path() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -986,6 +1430,7 @@ async fn proxy_with_mock_guard_custom_response() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -1038,3 +1483,1180 @@ async fn proxy_with_mock_guard_custom_response() {
     assert_eq!(recorded.len(), 1);
     assert!(recorded[0].uri.contains("/custom/endpoint"));
 }
+
+async fn setup_two_endpoint_upstream(h: &AppHarness, guard: &MockGuard, alias: &str) {
+    let ctx = h.security_context().clone();
+    let endpoint = |host: &str| Endpoint {
+        scheme: Scheme::Http,
+        host: host.into(),
+        port: h.mock_port(),
+        weight: 1,
+    };
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![endpoint("127.0.0.1"), endpoint("localhost")],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias(alias)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx,
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/lb"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+}
+
+fn recorded_hosts(recorded: &[oagw::test_support::RecordedRequest]) -> Vec<String> {
+    recorded
+        .iter()
+        .map(|r| {
+            r.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("host"))
+                .map(|(_, v)| v.split(':').next().unwrap_or_default().to_string())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+// Round-robin spreads requests across all endpoints of an upstream.
+#[tokio::test]
+async fn proxy_round_robin_across_endpoints() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/lb",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Text("ok".into()),
+        },
+    );
+    let h = AppHarness::builder().build().await;
+    setup_two_endpoint_upstream(&h, &guard, "lb-rr").await;
+
+    for _ in 0..4 {
+        let req = http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/lb-rr{}", guard.path("/lb")))
+            .body(Body::Empty)
+            .unwrap();
+        let response = h
+            .facade()
+            .proxy_request(h.security_context().clone(), req)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let hosts = recorded_hosts(&guard.recorded_requests().await);
+    assert_eq!(hosts.len(), 4);
+    assert_eq!(hosts.iter().filter(|h| *h == "127.0.0.1").count(), 2);
+    assert_eq!(hosts.iter().filter(|h| *h == "localhost").count(), 2);
+}
+
+// X-OAGW-Target-Host pins the request to the matching endpoint.
+#[tokio::test]
+async fn proxy_target_host_header_pins_endpoint() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/lb",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Text("ok".into()),
+        },
+    );
+    let h = AppHarness::builder().build().await;
+    setup_two_endpoint_upstream(&h, &guard, "lb-pin").await;
+
+    for _ in 0..3 {
+        let req = http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/lb-pin{}", guard.path("/lb")))
+            .header("x-oagw-target-host", "localhost")
+            .body(Body::Empty)
+            .unwrap();
+        let response = h
+            .facade()
+            .proxy_request(h.security_context().clone(), req)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let hosts = recorded_hosts(&guard.recorded_requests().await);
+    assert_eq!(hosts, vec!["localhost"; 3]);
+}
+
+// Unknown X-OAGW-Target-Host is rejected before reaching upstream.
+#[tokio::test]
+async fn proxy_unknown_target_host_rejected() {
+    let guard = MockGuard::new();
+    let h = AppHarness::builder().build().await;
+    setup_two_endpoint_upstream(&h, &guard, "lb-unknown").await;
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/lb-unknown{}", guard.path("/lb")))
+        .header("x-oagw-target-host", "other.example.com")
+        .body(Body::Empty)
+        .unwrap();
+    let err = h
+        .facade()
+        .proxy_request(h.security_context().clone(), req)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("other.example.com"), "{err}");
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// Circuit breaker opens after consecutive 5xx and fails fast without calling upstream.
+#[tokio::test]
+async fn proxy_circuit_breaker_opens_after_failures() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/flaky",
+        MockResponse {
+            status: 503,
+            headers: vec![],
+            body: MockBody::Text("unavailable".into()),
+        },
+    );
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("cb-test")
+            .circuit_breaker(CircuitBreakerConfig {
+                enabled: true,
+                failure_threshold: 2,
+                success_threshold: 1,
+                timeout_secs: 60,
+                half_open_max_requests: 1,
+                failure_conditions: FailureConditions {
+                    status_codes: vec![503],
+                    timeout: true,
+                    connection_error: true,
+                },
+                scope: CircuitBreakerScope::Global,
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/flaky"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let request = || {
+        http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/cb-test{}", guard.path("/flaky")))
+            .body(Body::Empty)
+            .unwrap()
+    };
+
+    for _ in 0..2 {
+        let response = h
+            .facade()
+            .proxy_request(ctx.clone(), request())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    match h.facade().proxy_request(ctx.clone(), request()).await {
+        Err(oagw_sdk::error::ServiceGatewayError::CircuitBreakerOpen {
+            retry_after_secs, ..
+        }) => assert!(retry_after_secs > 0),
+        Err(other) => panic!("expected CircuitBreakerOpen, got {other:?}"),
+        Ok(_) => panic!("expected circuit breaker to reject the request"),
+    }
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+
+    let status = h
+        .api_v1()
+        .get_upstream_status(&oagw::test_support::format_upstream_gts(upstream.id))
+        .expect_status(200)
+        .await
+        .json();
+    assert_eq!(status["circuit_breaker"]["state"], "open");
+    assert_eq!(status["circuit_breaker"]["rejected_requests_total"], 1);
+}
+
+// ---------------------------------------------------------------------------
+// CORS guard plugin
+// ---------------------------------------------------------------------------
+
+async fn setup_cors_upstream(h: &AppHarness, guard: &MockGuard, alias: &str) {
+    let ctx = h.security_context().clone();
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias(alias)
+            .plugins(PluginsConfig {
+                sharing: SharingMode::Private,
+                items: vec![CORS_GUARD_PLUGIN_ID.into()],
+                config: [(
+                    CORS_GUARD_PLUGIN_ID.to_string(),
+                    [
+                        ("allowed_origins", "https://app.example.com"),
+                        ("allowed_methods", "GET, POST"),
+                        ("expose_headers", "X-Request-ID"),
+                        ("max_age", "600"),
+                    ]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+                )]
+                .into_iter()
+                .collect(),
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx,
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/items"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn proxy_cors_preflight_answered_without_upstream_call() {
+    let guard = MockGuard::new();
+    let h = AppHarness::builder().build().await;
+    setup_cors_upstream(&h, &guard, "cors-preflight").await;
+
+    let req = http::Request::builder()
+        .method(Method::OPTIONS)
+        .uri(format!("/cors-preflight{}", guard.path("/items")))
+        .header(http::header::ORIGIN, "https://app.example.com")
+        .header(http::header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+        .header(http::header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+        .body(Body::Empty)
+        .unwrap();
+    let response = h
+        .facade()
+        .proxy_request(h.security_context().clone(), req)
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let headers = response.headers();
+    assert_eq!(
+        headers["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(headers["access-control-allow-methods"], "GET, POST");
+    assert_eq!(headers["access-control-max-age"], "600");
+    assert_eq!(headers["vary"], "Origin");
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+#[tokio::test]
+async fn proxy_cors_actual_request_gets_cors_headers() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/items",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!([])),
+        },
+    );
+    let h = AppHarness::builder().build().await;
+    setup_cors_upstream(&h, &guard, "cors-actual").await;
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/cors-actual{}", guard.path("/items")))
+        .header(http::header::ORIGIN, "https://app.example.com")
+        .body(Body::Empty)
+        .unwrap();
+    let response = h
+        .facade()
+        .proxy_request(h.security_context().clone(), req)
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(
+        response.headers()["access-control-expose-headers"],
+        "X-Request-ID"
+    );
+}
+
+#[tokio::test]
+async fn proxy_cors_disallowed_origin_rejected_with_403() {
+    let guard = MockGuard::new();
+    let h = AppHarness::builder().build().await;
+    setup_cors_upstream(&h, &guard, "cors-reject").await;
+
+    let req = http::Request::builder()
+        .method(Method::OPTIONS)
+        .uri(format!("/cors-reject{}", guard.path("/items")))
+        .header(http::header::ORIGIN, "https://evil.com")
+        .header(http::header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+        .body(Body::Empty)
+        .unwrap();
+    let err = h
+        .facade()
+        .proxy_request(h.security_context().clone(), req)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, ServiceGatewayError::GuardRejected { ref detail, .. } if detail.contains("https://evil.com")),
+        "unexpected error: {err:?}"
+    );
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Response caching
+// ---------------------------------------------------------------------------
+
+fn cache_config() -> CacheConfig {
+    CacheConfig {
+        enabled: true,
+        default_ttl_secs: 0,
+        max_ttl_secs: 3600,
+        max_entry_bytes: 1024 * 1024,
+    }
+}
+
+/// Create an upstream with a cached `GET /rates` route in the caller's tenant.
+async fn setup_cached_route(
+    h: &AppHarness,
+    ctx: &SecurityContext,
+    guard: &MockGuard,
+    alias: &str,
+) -> oagw_sdk::Route {
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias(alias)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/rates"),
+                        query_allowlist: vec!["base".into()],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .cache(cache_config())
+            .build(),
+        )
+        .await
+        .unwrap()
+}
+
+fn json_response(headers: &[(&str, &str)], body: serde_json::Value) -> MockResponse {
+    MockResponse {
+        status: 200,
+        headers: headers
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+        body: MockBody::Json(body),
+    }
+}
+
+async fn get_rates(
+    h: &AppHarness,
+    ctx: &SecurityContext,
+    guard: &MockGuard,
+    alias: &str,
+    headers: &[(&str, &str)],
+) -> http::Response<Body> {
+    let mut req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/{alias}{}?base=EUR", guard.path("/rates")));
+    for (k, v) in headers {
+        req = req.header(*k, *v);
+    }
+    h.facade()
+        .proxy_request(ctx.clone(), req.body(Body::Empty).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn proxy_cache_serves_repeat_get_without_upstream_call() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(&[("cache-control", "max-age=60")], json!({"usd": 1.08})),
+    );
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    setup_cached_route(&h, &ctx, &guard, "cache-hit").await;
+
+    let first = get_rates(&h, &ctx, &guard, "cache-hit", &[]).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(first.headers()["cache-status"], "oagw; fwd=miss");
+
+    let second = get_rates(&h, &ctx, &guard, "cache-hit", &[]).await;
+    assert_eq!(second.status(), StatusCode::OK);
+    assert_eq!(second.headers()["cache-status"], "oagw; hit");
+    assert!(second.headers().contains_key("age"));
+    let body = second.into_body().into_bytes().await.unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        json!({"usd": 1.08})
+    );
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+
+    // `no-store` bypasses the cache.
+    get_rates(
+        &h,
+        &ctx,
+        &guard,
+        "cache-hit",
+        &[("cache-control", "no-store")],
+    )
+    .await;
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+#[tokio::test]
+async fn proxy_cache_answers_matching_if_none_match_with_304() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(
+            &[("cache-control", "max-age=60"), ("etag", "\"r1\"")],
+            json!({}),
+        ),
+    );
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    setup_cached_route(&h, &ctx, &guard, "cache-inm").await;
+
+    get_rates(&h, &ctx, &guard, "cache-inm", &[]).await;
+    let resp = get_rates(
+        &h,
+        &ctx,
+        &guard,
+        "cache-inm",
+        &[("if-none-match", "\"r1\"")],
+    )
+    .await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(resp.headers()["etag"], "\"r1\"");
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+}
+
+#[tokio::test]
+async fn proxy_cache_revalidates_stale_entry_with_if_none_match() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(
+            &[("cache-control", "no-cache"), ("etag", "\"v1\"")],
+            json!({"usd": 1.08}),
+        ),
+    );
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    setup_cached_route(&h, &ctx, &guard, "cache-reval").await;
+
+    get_rates(&h, &ctx, &guard, "cache-reval", &[]).await;
+    guard.mock(
+        "GET",
+        "/rates",
+        MockResponse {
+            status: 304,
+            headers: vec![
+                ("etag".into(), "\"v1\"".into()),
+                ("cache-control".into(), "max-age=60".into()),
+            ],
+            body: MockBody::Text(String::new()),
+        },
+    );
+
+    let revalidated = get_rates(&h, &ctx, &guard, "cache-reval", &[]).await;
+    assert_eq!(revalidated.status(), StatusCode::OK);
+    assert_eq!(
+        revalidated.headers()["cache-status"],
+        "oagw; fwd=stale; fwd-status=304"
+    );
+    let body = revalidated.into_body().into_bytes().await.unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        json!({"usd": 1.08})
+    );
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 2);
+    assert!(
+        recorded[1]
+            .headers
+            .iter()
+            .any(|(k, v)| k == "if-none-match" && v == "\"v1\""),
+        "revalidation must send the stored validator"
+    );
+
+    // The 304 refreshed the entry with max-age=60.
+    let hit = get_rates(&h, &ctx, &guard, "cache-reval", &[]).await;
+    assert_eq!(hit.headers()["cache-status"], "oagw; hit");
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+#[tokio::test]
+async fn proxy_cache_selects_variant_by_vary_header() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(
+            &[("cache-control", "max-age=60"), ("vary", "Accept-Language")],
+            json!({}),
+        ),
+    );
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    setup_cached_route(&h, &ctx, &guard, "cache-vary").await;
+
+    let en = [("accept-language", "en")];
+    let de = [("accept-language", "de")];
+    get_rates(&h, &ctx, &guard, "cache-vary", &en).await;
+    get_rates(&h, &ctx, &guard, "cache-vary", &de).await;
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+
+    let resp = get_rates(&h, &ctx, &guard, "cache-vary", &de).await;
+    assert_eq!(resp.headers()["cache-status"], "oagw; hit");
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+#[tokio::test]
+async fn proxy_cache_is_isolated_between_tenants() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(&[("cache-control", "max-age=60")], json!({})),
+    );
+    let h = AppHarness::builder().build().await;
+    let tenant_a = h.security_context().clone();
+    let tenant_b = tenant_ctx(Uuid::new_v4());
+    setup_cached_route(&h, &tenant_a, &guard, "cache-tenant").await;
+    setup_cached_route(&h, &tenant_b, &guard, "cache-tenant").await;
+
+    get_rates(&h, &tenant_a, &guard, "cache-tenant", &[]).await;
+    let resp = get_rates(&h, &tenant_b, &guard, "cache-tenant", &[]).await;
+
+    assert_eq!(resp.headers()["cache-status"], "oagw; fwd=miss");
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+#[tokio::test]
+async fn proxy_cache_is_isolated_between_subjects() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(&[("cache-control", "max-age=60")], json!({})),
+    );
+    let h = AppHarness::builder().build().await;
+    let alice = h.security_context().clone();
+    let bob = tenant_ctx(alice.subject_tenant_id());
+    setup_cached_route(&h, &alice, &guard, "cache-subject").await;
+
+    get_rates(&h, &alice, &guard, "cache-subject", &[]).await;
+    let resp = get_rates(&h, &bob, &guard, "cache-subject", &[]).await;
+
+    assert_eq!(resp.headers()["cache-status"], "oagw; fwd=miss");
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+#[tokio::test]
+async fn proxy_cache_hits_count_against_rate_limit() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(&[("cache-control", "max-age=60")], json!({})),
+    );
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    let route = setup_cached_route(&h, &ctx, &guard, "cache-limited").await;
+    h.facade()
+        .update_route(
+            ctx.clone(),
+            route.id,
+            UpdateRouteRequest::builder()
+                .rate_limit(per_minute(2, RateLimitScope::Route, SharingMode::Private))
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    get_rates(&h, &ctx, &guard, "cache-limited", &[]).await;
+    let hit = get_rates(&h, &ctx, &guard, "cache-limited", &[]).await;
+    assert_eq!(hit.headers()["cache-status"], "oagw; hit");
+    assert_eq!(hit.headers()["x-ratelimit-remaining"], "0");
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/cache-limited{}?base=EUR", guard.path("/rates")))
+        .body(Body::Empty)
+        .unwrap();
+    assert_rate_limited(
+        h.facade()
+            .proxy_request(ctx.clone(), req)
+            .await
+            .map(|resp| resp.status()),
+    );
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+}
+
+#[tokio::test]
+async fn proxy_cache_invalidated_by_route_update() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/rates",
+        json_response(&[("cache-control", "max-age=60")], json!({})),
+    );
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    let route = setup_cached_route(&h, &ctx, &guard, "cache-invalidate").await;
+
+    get_rates(&h, &ctx, &guard, "cache-invalidate", &[]).await;
+    get_rates(&h, &ctx, &guard, "cache-invalidate", &[]).await;
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+
+    h.facade()
+        .update_route(
+            ctx.clone(),
+            route.id,
+            UpdateRouteRequest::builder().priority(1).build(),
+        )
+        .await
+        .unwrap();
+
+    let resp = get_rates(&h, &ctx, &guard, "cache-invalidate", &[]).await;
+    assert_eq!(resp.headers()["cache-status"], "oagw; fwd=miss");
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+// ---------------------------------------------------------------------------
+// gRPC and WebSocket proxying
+// ---------------------------------------------------------------------------
+
+/// Create an upstream on the shared mock with one route per match rule.
+async fn create_protocol_routes(
+    h: &AppHarness,
+    alias: &str,
+    scheme: Scheme,
+    protocol: &str,
+    routes: Vec<MatchRules>,
+    rate_limit: Option<RateLimitConfig>,
+) {
+    let ctx = h.security_context().clone();
+    let mut builder = CreateUpstreamRequest::builder(
+        Server {
+            endpoints: vec![Endpoint {
+                scheme,
+                host: "127.0.0.1".into(),
+                port: h.mock_port(),
+                weight: 1,
+            }],
+        },
+        protocol,
+    )
+    .alias(alias)
+    .auth(oagw_sdk::AuthConfig {
+        plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+        sharing: SharingMode::Private,
+        config: Some(
+            [
+                ("header".into(), "authorization".into()),
+                ("prefix".into(), "Bearer ".into()),
+                ("secret_ref".into(), "cred://proto-key".into()),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+    });
+    if let Some(rate_limit) = rate_limit {
+        builder = builder.rate_limit(rate_limit);
+    }
+    let upstream = h
+        .facade()
+        .create_upstream(ctx.clone(), builder.build())
+        .await
+        .unwrap();
+    for match_rules in routes {
+        h.facade()
+            .create_route(
+                ctx.clone(),
+                CreateRouteRequest::builder(upstream.id, match_rules).build(),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+async fn grpc_harness(alias: &str) -> AppHarness {
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://proto-key".into(), "grpc-secret".into())])
+        .build()
+        .await;
+    let routes = ["Unary", "ServerStream", "Bidi", "Fail"]
+        .into_iter()
+        .map(|method| MatchRules {
+            http: None,
+            grpc: Some(GrpcMatch {
+                service: "oagw.test.v1.Echo".into(),
+                method: method.into(),
+            }),
+        })
+        .collect();
+    create_protocol_routes(
+        &h,
+        alias,
+        Scheme::Grpc,
+        "gts.x.core.oagw.protocol.v1~x.core.oagw.grpc.v1",
+        routes,
+        None,
+    )
+    .await;
+    h
+}
+
+fn grpc_request(alias: &str, method: &str, payload: &'static [u8]) -> http::Request<Body> {
+    http::Request::builder()
+        .method(Method::POST)
+        .uri(format!("/{alias}/oagw.test.v1.Echo/{method}"))
+        .header(http::header::CONTENT_TYPE, "application/grpc")
+        .header("grpc-timeout", "5S")
+        .body(Body::from(bytes::Bytes::from_static(payload)))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn proxy_grpc_unary_passes_trailers_through() {
+    let h = grpc_harness("grpc-unary").await;
+
+    let response = h
+        .facade()
+        .proxy_request(
+            h.security_context().clone(),
+            grpc_request("grpc-unary", "Unary", b"\0\0\0\0\x02hi"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-received-te"], "trailers");
+    assert_eq!(response.headers()["x-received-grpc-timeout"], "5S");
+    assert_eq!(
+        response.headers()["x-received-authorization"],
+        "Bearer grpc-secret"
+    );
+
+    let trailers = response
+        .extensions()
+        .get::<ResponseTrailers>()
+        .cloned()
+        .expect("gRPC responses carry trailers");
+    let body = response.into_body().into_bytes().await.unwrap();
+    assert_eq!(&body[..], b"\0\0\0\0\x02hi");
+    let trailers = trailers.get().expect("trailers after body end");
+    assert_eq!(trailers["grpc-status"], "0");
+    assert_eq!(trailers["x-echo-trailer"], "done");
+}
+
+#[tokio::test]
+async fn proxy_grpc_streams_request_messages_to_upstream() {
+    use futures_util::StreamExt;
+
+    let h = grpc_harness("grpc-bidi").await;
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<bytes::Bytes, oagw_sdk::body::BoxError>>(1);
+    let requests: oagw_sdk::body::BodyStream =
+        Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|msg| (msg, rx))
+        }));
+    tx.send(Ok(bytes::Bytes::from_static(b"\0\0\0\0\x01a")))
+        .await
+        .unwrap();
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri("/grpc-bidi/oagw.test.v1.Echo/Bidi")
+        .header(http::header::CONTENT_TYPE, "application/grpc")
+        .body(Body::Stream(requests))
+        .unwrap();
+
+    let response = h
+        .facade()
+        .proxy_request(h.security_context().clone(), req)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let trailers = response
+        .extensions()
+        .get::<ResponseTrailers>()
+        .cloned()
+        .unwrap();
+    let mut replies = response.into_body().into_stream();
+    // Each reply arrives while the request stream is still open.
+    assert_eq!(
+        &replies.next().await.unwrap().unwrap()[..],
+        b"\0\0\0\0\x01a"
+    );
+    tx.send(Ok(bytes::Bytes::from_static(b"\0\0\0\0\x01b")))
+        .await
+        .unwrap();
+    assert_eq!(
+        &replies.next().await.unwrap().unwrap()[..],
+        b"\0\0\0\0\x01b"
+    );
+    drop(tx);
+    while let Some(chunk) = replies.next().await {
+        assert!(chunk.unwrap().is_empty());
+    }
+    assert_eq!(trailers.get().unwrap()["grpc-status"], "0");
+}
+
+#[tokio::test]
+async fn proxy_grpc_server_stream_and_status_over_rest() {
+    let h = grpc_harness("grpc-rest").await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-rest", "oagw.test.v1.Echo/ServerStream")
+        .with_header(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/grpc"),
+        )
+        .with_body(b"\0\0\0\0\x01x".to_vec())
+        .expect_status(200)
+        .await;
+    assert_eq!(resp.bytes(), b"\0\0\0\0\x01x\0\0\0\0\x01x\0\0\0\0\x01x");
+    assert_eq!(resp.trailers().unwrap()["grpc-status"], "0");
+
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-rest", "oagw.test.v1.Echo/Fail")
+        .with_header(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/grpc"),
+        )
+        .with_body(Vec::new())
+        .expect_status(200)
+        .await;
+    let trailers = resp.trailers().unwrap();
+    assert_eq!(trailers["grpc-status"], "14");
+    assert_eq!(trailers["grpc-message"], "backend%20unavailable");
+    resp.assert_header("x-oagw-error-source", "upstream");
+}
+
+#[tokio::test]
+async fn proxy_grpc_gateway_error_is_reported_as_grpc_status() {
+    let h = grpc_harness("grpc-errors").await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-errors", "oagw.test.v1.Echo/Unknown")
+        .with_header(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/grpc"),
+        )
+        .with_body(Vec::new())
+        .expect_status(200)
+        .await;
+    resp.assert_header("content-type", "application/grpc");
+    resp.assert_header("grpc-status", "12");
+    resp.assert_header("x-oagw-error-source", "gateway");
+}
+
+fn websocket_rules() -> MatchRules {
+    MatchRules {
+        http: Some(HttpMatch {
+            methods: vec![HttpMethod::Get],
+            path: "/ws/echo".into(),
+            query_allowlist: vec![],
+            path_suffix_mode: PathSuffixMode::Disabled,
+        }),
+        grpc: None,
+    }
+}
+
+async fn websocket_harness(alias: &str, rate_limit: Option<RateLimitConfig>) -> AppHarness {
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://proto-key".into(), "ws-secret".into())])
+        .build()
+        .await;
+    create_protocol_routes(
+        &h,
+        alias,
+        Scheme::Http,
+        "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+        vec![websocket_rules()],
+        rate_limit,
+    )
+    .await;
+    h
+}
+
+fn websocket_request(alias: &str, messages: Vec<WebSocketMessage>) -> http::Request<Body> {
+    let stream = futures_util::stream::iter(
+        messages
+            .into_iter()
+            .map(|m| Ok::<_, oagw_sdk::body::BoxError>(m.to_body_chunk().unwrap())),
+    );
+    http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/{alias}/ws/echo"))
+        .header(http::header::UPGRADE, "websocket")
+        .header(http::header::CONNECTION, "upgrade")
+        .header(http::header::SEC_WEBSOCKET_PROTOCOL, "oagw.test")
+        .body(Body::Stream(Box::pin(stream)))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn proxy_websocket_echoes_messages_with_auth() {
+    let h = websocket_harness("ws-facade", None).await;
+
+    let response = h
+        .facade()
+        .proxy_request(
+            h.security_context().clone(),
+            websocket_request(
+                "ws-facade",
+                vec![
+                    WebSocketMessage::Text("hello".into()),
+                    // Valid UTF-8, but sent as binary.
+                    WebSocketMessage::Binary(b"raw".to_vec()),
+                    WebSocketMessage::Binary(vec![0xff, 0x00]),
+                ],
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+    assert_eq!(
+        response.headers()["x-received-authorization"],
+        "Bearer ws-secret"
+    );
+    assert_eq!(
+        response.headers()[http::header::SEC_WEBSOCKET_PROTOCOL],
+        "oagw.test"
+    );
+    assert!(
+        !response
+            .headers()
+            .contains_key(http::header::SEC_WEBSOCKET_ACCEPT)
+    );
+
+    let messages: Vec<WebSocketMessage> =
+        futures_util::StreamExt::collect::<Vec<_>>(response.into_body().into_stream())
+            .await
+            .into_iter()
+            .map(|chunk| WebSocketMessage::from_body_chunk(&chunk.unwrap()).unwrap())
+            .collect();
+    assert_eq!(
+        messages,
+        vec![
+            WebSocketMessage::Text("hello".into()),
+            WebSocketMessage::Binary(b"raw".to_vec()),
+            WebSocketMessage::Binary(vec![0xff, 0x00]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn proxy_websocket_handshake_is_rate_limited() {
+    let h = websocket_harness(
+        "ws-limited",
+        Some(per_minute(1, RateLimitScope::Tenant, SharingMode::Private)),
+    )
+    .await;
+    let ctx = h.security_context().clone();
+
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), websocket_request("ws-limited", vec![]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+    let err = h
+        .facade()
+        .proxy_request(ctx, websocket_request("ws-limited", vec![]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ServiceGatewayError::RateLimitExceeded { .. }));
+}
+
+#[tokio::test]
+async fn proxy_websocket_over_rest_connection() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::{Message, http::HeaderValue};
+
+    let h = websocket_harness("ws-rest", None).await;
+    let addr = h.serve().await;
+
+    let mut request = format!("ws://{addr}/oagw/v1/proxy/ws-rest/ws/echo")
+        .into_client_request()
+        .unwrap();
+    request.headers_mut().insert(
+        http::header::SEC_WEBSOCKET_PROTOCOL,
+        HeaderValue::from_static("oagw.test"),
+    );
+    let (mut socket, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+    assert_eq!(
+        response.headers()["x-received-authorization"],
+        "Bearer ws-secret"
+    );
+    assert_eq!(response.headers()["x-oagw-error-source"], "upstream");
+
+    socket.send(Message::text("ping over oagw")).await.unwrap();
+    socket
+        .send(Message::binary(vec![1_u8, 2, 0xff]))
+        .await
+        .unwrap();
+    socket
+        .send(Message::binary(b"utf-8 binary".to_vec()))
+        .await
+        .unwrap();
+    assert_eq!(
+        socket.next().await.unwrap().unwrap(),
+        Message::text("ping over oagw")
+    );
+    assert_eq!(
+        socket.next().await.unwrap().unwrap(),
+        Message::binary(vec![1_u8, 2, 0xff])
+    );
+    assert_eq!(
+        socket.next().await.unwrap().unwrap(),
+        Message::binary(b"utf-8 binary".to_vec())
+    );
+
+    socket.close(None).await.unwrap();
+    while let Some(Ok(msg)) = socket.next().await {
+        assert!(msg.is_close(), "unexpected message after close: {msg:?}");
+    }
+}